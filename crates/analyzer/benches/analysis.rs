@@ -0,0 +1,78 @@
+//! Benchmarks for ink! analyzer parsing, diagnostics, completions and actions
+//! on small, medium and large ink! contracts.
+//!
+//! Run with `cargo bench -p ink-analyzer --bench analysis`.
+//!
+//! To catch performance regressions, save a baseline (e.g. on the main branch)
+//! with `cargo bench -p ink-analyzer --bench analysis -- --save-baseline main`,
+//! and then compare changes against it with `cargo bench -p ink-analyzer --bench analysis -- --baseline main`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use ink_analyzer::{Analysis, TextRange, TextSize};
+use ink_analyzer_ir::InkFile;
+
+/// Returns the benchmark contracts (i.e. small and medium fixture contracts,
+/// and a large synthetic contract).
+fn contracts() -> Vec<(&'static str, String)> {
+    vec![
+        ("small", test_utils::read_source_code("contracts/flipper")),
+        ("medium", test_utils::read_source_code("contracts/erc20")),
+        ("large", test_utils::synthetic_contract(250)),
+    ]
+}
+
+/// Returns the offset inside the last ink! attribute argument list in the code
+/// (i.e. a typical position for completions and actions).
+fn offset(code: &str) -> TextSize {
+    let offset = code
+        .rfind("#[ink(")
+        .map_or(0, |offset| offset + "#[ink(".len());
+    TextSize::from(offset as u32)
+}
+
+fn parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parsing");
+    for (size, code) in contracts() {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &code, |b, code| {
+            b.iter(|| InkFile::parse(black_box(code)))
+        });
+    }
+    group.finish();
+}
+
+fn diagnostics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("diagnostics");
+    for (size, code) in contracts() {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &code, |b, code| {
+            b.iter(|| Analysis::new(black_box(code)).diagnostics())
+        });
+    }
+    group.finish();
+}
+
+fn completions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("completions");
+    for (size, code) in contracts() {
+        let analysis = Analysis::new(&code);
+        let offset = offset(&code);
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| analysis.completions(black_box(offset)))
+        });
+    }
+    group.finish();
+}
+
+fn actions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("actions");
+    for (size, code) in contracts() {
+        let analysis = Analysis::new(&code);
+        let range = TextRange::empty(offset(&code));
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| analysis.actions(black_box(range)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parsing, diagnostics, completions, actions);
+criterion_main!(benches);
@@ -1,86 +1,906 @@
 //! Types and abstractions for performing semantic analysis of ink! smart contract code.
 
-use ink_analyzer_ir::syntax::{TextRange, TextSize};
-use ink_analyzer_ir::InkFile;
+use ink_analyzer_ir::syntax::{TextRange, TextSize, TokenAtOffset};
+use ink_analyzer_ir::{FromSyntax, InkFile};
 use itertools::Itertools;
+use std::fmt;
+use std::num::NonZeroUsize;
 
-pub use actions::{Action, ActionKind};
-pub use completions::Completion;
-pub use diagnostics::{Diagnostic, Severity};
-pub use hover::Hover;
-pub use inlay_hints::InlayHint;
+pub use actions::{Action, ActionKind, UnresolvedAction};
+pub use builders::{ActionBuilder, BuildError, DiagnosticBuilder};
+pub use completions::{resolve_completion, Completion, CompletionKind};
+pub use contract_info::{ArgInfo, CallableInfo, ContractInfo, EventFieldInfo, EventInfo};
+pub use diagnostics::{
+    CachedDiagnostics, Diagnostic, DiagnosticCode, DiagnosticsCache, DiagnosticsConfig,
+    RelatedInformation, Severity,
+};
+pub use folding_ranges::{FoldingRange, FoldingRangeKind};
+pub use hover::{Hover, HoverConfig};
+pub use indent::IndentStyle;
+pub use inlay_hints::{InlayHint, InlayHintsConfig};
+pub use metadata::{ArgMetadata, CallableMetadata, ContractMetadata};
+pub use modules::{child_module_candidates, parent_module_candidates};
+pub use queries::{content_hash, FileIndex, IndexedSelector};
+pub use registry::{MacroDefinition, MacroRegistry, MacroScope};
 pub use signature_help::SignatureHelp;
-pub use text_edit::TextEdit;
+pub use symbols::{Symbol, SymbolIndex, SymbolKind};
+pub use text_edit::{merge_edits, DocumentEdit, EditConflict, TextEdit};
+
+use queries::Queries;
 
 mod actions;
+mod builders;
 mod completions;
+mod contract_info;
 mod diagnostics;
+mod folding_ranges;
+mod generated;
 mod hover;
+mod indent;
 mod inlay_hints;
+mod metadata;
+mod modules;
+mod queries;
+mod registry;
+mod selection_ranges;
 mod signature_help;
+mod symbols;
 mod text_edit;
 mod utils;
 
 /// Entry point for asking for semantic information about ink! smart contract code.
+///
+/// # Positions
+/// Analysis methods that accept offsets or text ranges return empty results
+/// for offsets and text ranges that aren't valid positions in the code (see [`Analysis::validate_range`]).
+///
+/// # Ordering
+/// Results are returned in a deterministic order (i.e. independent of traversal details):
+/// - diagnostics are sorted by range (i.e. start offset, then end offset), then severity (errors first).
+/// - completions are sorted by range (i.e. start offset, then end offset).
+/// - actions are sorted by kind (quickfixes first, then refactors, then source actions),
+///   then the range of their first text edit (i.e. start offset, then end offset).
+///
+/// Ties keep the order in which results are computed.
 #[derive(Debug)]
 pub struct Analysis {
     /// The ink! smart contract code being analyzed.
     file: InkFile,
+    /// The ink! language version targeted by the smart contract code.
+    version: Version,
+    /// Registry of ink! attribute macros that are unknown to ink! analyzer.
+    macros: MacroRegistry,
+    /// Indentation style for generated code (if any).
+    indent: Option<IndentStyle>,
+    /// Memoized intermediate results shared across analysis calls (e.g. the ink! entity index and diagnostics).
+    queries: Queries,
+}
+
+/// The ink! language version targeted by the smart contract code.
+///
+/// Used to adjust semantic rules that differ between ink! versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Version {
+    /// ink! 3.x
+    V3,
+    /// ink! 4.x
+    #[default]
+    V4,
+}
+
+/// An error for analysis inputs that can't be analyzed, see [`Analysis::validate_range`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AnalysisError {
+    /// The text range isn't inside the code (i.e. it ends after the end of the code).
+    OutOfBounds(TextRange),
+    /// The offset isn't at a char boundary (i.e. it's inside a multi-byte character).
+    NotCharBoundary(TextSize),
 }
 
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalysisError::OutOfBounds(range) => {
+                write!(f, "text range {range:?} is outside the code")
+            }
+            AnalysisError::NotCharBoundary(offset) => {
+                write!(f, "offset {offset:?} is not at a char boundary")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
 impl Analysis {
-    /// Creates an analysis instance from smart contract code.
+    /// Creates an analysis instance from smart contract code (targeting the latest supported ink! version).
     pub fn new(code: &str) -> Self {
+        Self::new_with_version(code, Version::default())
+    }
+
+    /// Creates an analysis instance from smart contract code targeting the given ink! version.
+    pub fn new_with_version(code: &str, version: Version) -> Self {
         Self {
             file: InkFile::parse(code),
+            version,
+            macros: MacroRegistry::default(),
+            indent: None,
+            queries: Queries::default(),
         }
     }
 
+    /// Sets the registry of ink! attribute macros that are unknown to ink! analyzer
+    /// (e.g. experimental or newer ink! attribute macros like `#[ink::precompile]`),
+    /// so that they get completions and diagnostics based on their registered definitions.
+    pub fn with_macro_registry(mut self, registry: MacroRegistry) -> Self {
+        self.macros = registry;
+        // Invalidates results that depend on the macro registry.
+        self.queries = Queries::default();
+        self
+    }
+
+    /// Sets the indentation style for generated code (e.g. for actions, quickfixes and completions)
+    /// (e.g. based on editor settings).
+    ///
+    /// Defaults to the indentation style detected from the smart contract code (if any),
+    /// or 4 spaces otherwise.
+    pub fn with_indent_style(mut self, style: IndentStyle) -> Self {
+        self.indent = Some(style);
+        self
+    }
+
+    /// Returns true if the smart contract code contains ink! entities,
+    /// without parsing the code (i.e. a fast pre-scan for cheaply skipping plain Rust files).
+    ///
+    /// See [`InkFile::contains_ink_entities`] for details.
+    pub fn contains_ink_entities(code: &str) -> bool {
+        InkFile::contains_ink_entities(code)
+    }
+
     /// Returns the intermediate representation (IR) of the smart contract code.
     pub fn file(&self) -> &InkFile {
         &self.file
     }
 
+    /// Returns the names of the out-of-line child modules (i.e. `mod x;` declarations)
+    /// in the root of the ink! contracts in the smart contract code.
+    ///
+    /// Clients can use this (along with [`parent_module_candidates`]) to determine whether a file is
+    /// an out-of-line child module of an ink! contract (see [`DiagnosticsConfig::contract_child_module`]).
+    pub fn contract_child_modules(&self) -> Vec<String> {
+        modules::contract_child_modules(&self.file)
+    }
+
+    /// Returns the ink! language version targeted by the analysis.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns the indentation style for generated code
+    /// (i.e. either the configured indentation style, the indentation style detected from
+    /// the smart contract code or 4 spaces, in that order of precedence).
+    pub fn indent_style(&self) -> IndentStyle {
+        self.indent
+            .or_else(|| IndentStyle::detect_for_node(self.file.syntax()))
+            .unwrap_or_default()
+    }
+
     /// Runs diagnostics for the smart contract code.
     pub fn diagnostics(&self) -> Vec<Diagnostic> {
-        diagnostics::diagnostics(&self.file)
+        self.diagnostics_with_config(&DiagnosticsConfig::default())
+    }
+
+    /// Runs diagnostics for the smart contract code using the given diagnostics configuration.
+    pub fn diagnostics_with_config(&self, config: &DiagnosticsConfig) -> Vec<Diagnostic> {
+        let style = self.indent_style();
+        sorted_diagnostics(
+            self.queries
+                .diagnostics(&self.file, self.version, config, &self.macros)
+                .into_iter()
+                .map(|diagnostic| indent_diagnostic(diagnostic, style))
+                .collect(),
+        )
+    }
+
+    /// Runs diagnostics for the smart contract code using the given diagnostics configuration,
+    /// and passes each diagnostic to the callback as soon as it's available
+    /// (e.g. for publishing partial results for large files).
+    ///
+    /// Diagnostics are passed to the callback in the order they're computed
+    /// (i.e. they're not sorted, see [`Analysis`] doc), so callers should sort the collected results
+    /// if they need the same order as [`Self::diagnostics_with_config`].
+    /// Results are memoized once complete (i.e. subsequent calls with the same configuration,
+    /// including calls to [`Self::diagnostics_with_config`], don't recompute them).
+    pub fn diagnostics_with<F>(&self, config: &DiagnosticsConfig, mut callback: F)
+    where
+        F: FnMut(Diagnostic),
+    {
+        let style = self.indent_style();
+        self.queries.diagnostics_with(
+            &self.file,
+            self.version,
+            config,
+            &self.macros,
+            |diagnostic| callback(indent_diagnostic(diagnostic, style)),
+        );
     }
 
-    /// Computes ink! attribute completions at the given position.
+    /// Runs diagnostics for the smart contract code using the given diagnostics configuration
+    /// on multiple threads (i.e. based on the available parallelism), and returns the same results
+    /// as [`Self::diagnostics_with_config`].
+    ///
+    /// Each thread parses its own copy of the smart contract code,
+    /// so this is only faster for large files with many ink! entities.
+    pub fn diagnostics_parallel(&self, config: &DiagnosticsConfig) -> Vec<Diagnostic> {
+        let style = self.indent_style();
+        let num_threads = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        sorted_diagnostics(
+            diagnostics::diagnostics_parallel(
+                &self.file,
+                self.version,
+                config,
+                &self.macros,
+                num_threads,
+            )
+            .into_iter()
+            .map(|diagnostic| indent_diagnostic(diagnostic, style))
+            .collect(),
+        )
+    }
+
+    /// Returns an error if the text range isn't a valid position in the code
+    /// (i.e. it isn't inside the code, or it doesn't start and end at char boundaries).
+    ///
+    /// Analysis methods return empty results for invalid text ranges (and offsets),
+    /// so this is useful for clients that need to distinguish invalid inputs from empty results.
+    pub fn validate_range(&self, range: TextRange) -> Result<(), AnalysisError> {
+        if !self.file.syntax().text_range().contains_range(range) {
+            return Err(AnalysisError::OutOfBounds(range));
+        }
+        for offset in [range.start(), range.end()] {
+            let is_char_boundary = match self.file.syntax().token_at_offset(offset) {
+                TokenAtOffset::Single(token) => token
+                    .text()
+                    .is_char_boundary((offset - token.text_range().start()).into()),
+                // Offsets between tokens (or in empty files) are always at char boundaries.
+                TokenAtOffset::Between(..) | TokenAtOffset::None => true,
+            };
+            if !is_char_boundary {
+                return Err(AnalysisError::NotCharBoundary(offset));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the offset isn't a valid position in the code,
+    /// see [`Analysis::validate_range`].
+    pub fn validate_offset(&self, offset: TextSize) -> Result<(), AnalysisError> {
+        self.validate_range(TextRange::empty(offset))
+    }
+
+    /// Computes ink! attribute and ink! environment idiom completions at the given position.
     pub fn completions(&self, position: TextSize) -> Vec<Completion> {
-        completions::completions(&self.file, position)
+        if self.validate_offset(position).is_err() {
+            return Vec::new();
+        }
+        let style = self.indent_style();
+        sorted_completions(
+            completions::completions(
+                &self.file,
+                &self.queries,
+                position,
+                self.version,
+                &self.macros,
+            )
+            .into_iter()
+            .map(|mut completion| {
+                // NOTE: Documentation for completions with an identifier is resolved lazily,
+                // see `resolve_completion` doc.
+                completion.edit = indent_edit(completion.edit, style);
+                completion
+            })
+            .collect(),
+        )
     }
 
     /// Computes ink! attribute code/intent actions for the given text range.
     pub fn actions(&self, range: TextRange) -> Vec<Action> {
-        // Returns quickfixes (for diagnostics) + generic code actions.
-        diagnostics::diagnostics(&self.file)
+        self.actions_with_config(range, &DiagnosticsConfig::default())
+    }
+
+    /// Computes ink! attribute code/intent actions for the given text range,
+    /// with quickfixes for the diagnostics computed using the given diagnostics configuration.
+    ///
+    /// Callers should use the same configuration as for [`Analysis::diagnostics_with_config`],
+    /// so that quickfixes are only offered for the diagnostics shown to users.
+    pub fn actions_with_config(&self, range: TextRange, config: &DiagnosticsConfig) -> Vec<Action> {
+        let style = self.indent_style();
+        self.unindented_actions(range, config)
             .into_iter()
-            .filter_map(|it| it.quickfixes)
-            .flatten()
-            // Filters out diagnostics that apply to the given text range.
-            .filter(|action| {
-                range.contains_range(action.range) || action.range.contains_range(range)
-            })
-            // Combines quickfixes and generic actions (with quickfixes taking priority).
-            .chain(actions::actions(&self.file, range))
-            // Deduplicate by edits.
-            .unique_by(|item| item.edits.clone())
+            .map(|action| indent_action(action, style))
             .collect()
     }
 
-    /// Returns descriptive/informational text for the ink! attribute at the given text range (if any).
+    /// Computes ink! attribute code/intent actions for the given offset (i.e. the cursor position).
+    ///
+    /// The focus range is derived from the offset (i.e. the whole attribute for offsets on or at the edges
+    /// of an attribute, otherwise the token at the offset), see [`Analysis::actions`].
+    pub fn actions_at(&self, offset: TextSize) -> Vec<Action> {
+        if self.validate_offset(offset).is_err() {
+            return Vec::new();
+        }
+        self.actions(utils::focus_range_at_offset(&self.file, offset))
+    }
+
+    /// Computes unresolved ink! attribute code/intent actions (i.e. without text edits)
+    /// for the given text range, see [`Analysis::resolve_action`].
+    pub fn unresolved_actions(&self, range: TextRange) -> Vec<UnresolvedAction> {
+        self.unresolved_actions_with_config(range, &DiagnosticsConfig::default())
+    }
+
+    /// Computes unresolved ink! attribute code/intent actions (i.e. without text edits)
+    /// for the given text range using the given diagnostics configuration,
+    /// see [`Analysis::actions_with_config`] and [`Analysis::resolve_action_with_config`].
+    pub fn unresolved_actions_with_config(
+        &self,
+        range: TextRange,
+        config: &DiagnosticsConfig,
+    ) -> Vec<UnresolvedAction> {
+        actions::unresolved(&self.unindented_actions(range, config))
+    }
+
+    /// Returns the ink! attribute code/intent action (i.e. including its text edits)
+    /// with the given identifier (if any) for the given text range, see [`UnresolvedAction::id`].
+    pub fn resolve_action(&self, range: TextRange, id: &str) -> Option<Action> {
+        self.resolve_action_with_config(range, id, &DiagnosticsConfig::default())
+    }
+
+    /// Returns the ink! attribute code/intent action (i.e. including its text edits)
+    /// with the given identifier (if any) for the given text range using the given diagnostics configuration
+    /// (i.e. the same configuration used for computing the unresolved action),
+    /// see [`Analysis::unresolved_actions_with_config`].
+    pub fn resolve_action_with_config(
+        &self,
+        range: TextRange,
+        id: &str,
+        config: &DiagnosticsConfig,
+    ) -> Option<Action> {
+        actions::resolve(self.unindented_actions(range, config), id)
+            .map(|action| indent_action(action, self.indent_style()))
+    }
+
+    /// Returns a "fix all" source action that combines all safe quickfixes in the file
+    /// (i.e. preferred quickfixes, see [`Action::is_preferred`]) into a single action (if any).
+    ///
+    /// Quickfixes whose edits conflict with the edits of previously combined quickfixes are skipped
+    /// (i.e. they can be applied by running the action again).
+    pub fn fix_all(&self) -> Option<Action> {
+        self.fix_all_with_config(&DiagnosticsConfig::default())
+    }
+
+    /// Returns a "fix all" source action that combines all safe quickfixes for the diagnostics
+    /// computed using the given diagnostics configuration into a single action (if any),
+    /// see [`Analysis::fix_all`].
+    ///
+    /// Callers should use the same configuration as for [`Analysis::diagnostics_with_config`],
+    /// so that quickfixes for disabled diagnostics are never applied.
+    pub fn fix_all_with_config(&self, config: &DiagnosticsConfig) -> Option<Action> {
+        let diagnostics = self
+            .queries
+            .diagnostics(&self.file, self.version, config, &self.macros);
+        actions::fix_all(&diagnostics, self.file.syntax().text_range())
+            .map(|action| indent_action(action, self.indent_style()))
+    }
+
+    /// Computes ink! attribute code/intent actions for the given text range
+    /// (with canonical indenting for text edits).
+    ///
+    /// Quickfixes are taken from the memoized diagnostics for the diagnostics configuration
+    /// (i.e. the same diagnostics returned by [`Analysis::diagnostics_with_config`]).
+    fn unindented_actions(&self, range: TextRange, config: &DiagnosticsConfig) -> Vec<Action> {
+        if self.validate_range(range).is_err() {
+            return Vec::new();
+        }
+        // Returns quickfixes (for diagnostics) + generic code actions.
+        sorted_actions(
+            self.queries
+                .diagnostics(&self.file, self.version, config, &self.macros)
+                .into_iter()
+                .filter_map(|it| it.quickfixes)
+                .flatten()
+                // Filters out diagnostics that apply to the given text range.
+                .filter(|action| {
+                    range.contains_range(action.range) || action.range.contains_range(range)
+                })
+                // Combines quickfixes and generic actions (with quickfixes taking priority).
+                .chain(actions::actions(&self.file, &self.queries, range))
+                // Deduplicate by edits.
+                .unique_by(|item| item.edits.clone())
+                .collect(),
+        )
+    }
+
+    /// Returns descriptive/informational text for the ink! attribute, ink! type or ink! environment function
+    /// at the given text range (if any).
     pub fn hover(&self, range: TextRange) -> Option<Hover> {
-        hover::hover(&self.file, range)
+        self.hover_with_config(range, &HoverConfig::default())
+    }
+
+    /// Returns descriptive/informational text for the ink! attribute, ink! type or ink! environment function
+    /// at the given text range (if any) using the given hover configuration.
+    pub fn hover_with_config(&self, range: TextRange, config: &HoverConfig) -> Option<Hover> {
+        self.validate_range(range).ok()?;
+        hover::hover(&self.file, &self.queries, range, self.version, config)
+    }
+
+    /// Computes folding ranges for ink! entities
+    /// (i.e. ink! contract modules, impl blocks containing ink! messages,
+    /// ink! and ink! e2e test functions and long doc comment blocks).
+    pub fn folding_ranges(&self) -> Vec<FoldingRange> {
+        folding_ranges::folding_ranges(&self.file)
     }
 
     /// Computes ink! attribute argument inlay hints for the given text range (if any).
     pub fn inlay_hints(&self, range: Option<TextRange>) -> Vec<InlayHint> {
-        inlay_hints::inlay_hints(&self.file, range)
+        self.inlay_hints_with_config(range, &InlayHintsConfig::default())
+    }
+
+    /// Computes ink! attribute argument inlay hints for the given text range (if any)
+    /// using the given inlay hints configuration.
+    pub fn inlay_hints_with_config(
+        &self,
+        range: Option<TextRange>,
+        config: &InlayHintsConfig,
+    ) -> Vec<InlayHint> {
+        if range.map_or(false, |range| self.validate_range(range).is_err()) {
+            return Vec::new();
+        }
+        inlay_hints::inlay_hints(&self.file, &self.queries, range, config)
+    }
+
+    /// Computes experimental (heuristic) relative execution cost inlay hints for ink! messages
+    /// for the given text range (if any).
+    ///
+    /// The cost is estimated by counting storage reads and writes, loops and cross-contract calls,
+    /// so it's only a coarse relative indication and NOT an actual gas/weight estimate.
+    pub fn cost_hints(&self, range: Option<TextRange>) -> Vec<InlayHint> {
+        if range.map_or(false, |range| self.validate_range(range).is_err()) {
+            return Vec::new();
+        }
+        inlay_hints::cost_hints(&self.file, range)
+    }
+
+    /// Computes ink! syntax aware selection ranges (i.e. for "expand selection") for the given position,
+    /// ordered from the narrowest to the widest range.
+    pub fn selection_ranges(&self, position: TextSize) -> Vec<TextRange> {
+        if self.validate_offset(position).is_err() {
+            return Vec::new();
+        }
+        selection_ranges::selection_ranges(&self.file, position)
     }
 
     /// Computes ink! attribute signature help for the given position.
     pub fn signature_help(&self, position: TextSize) -> Vec<SignatureHelp> {
+        if self.validate_offset(position).is_err() {
+            return Vec::new();
+        }
         signature_help::signature_help(&self.file, position)
     }
+
+    /// Computes symbols for ink! entities (e.g. ink! contracts, events, constructors and messages).
+    ///
+    /// See [`SymbolIndex`] for searching ink! entity symbols across multiple files.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        symbols::symbols(&self.file)
+    }
+
+    /// Returns a persistable index of the symbols and selectors for ink! entities
+    /// (i.e. keyed by a hash of the file contents, see [`SymbolIndex::insert_index`]).
+    pub fn file_index(&self) -> FileIndex {
+        self.queries.file_index(&self.file)
+    }
+
+    /// Returns a high-level read-only model of the ink! contracts in the file
+    /// (i.e. constructors, messages and events with their selectors, flags and doc comments).
+    pub fn contract_info(&self) -> Vec<ContractInfo> {
+        contract_info::contract_info(&self.file, self.queries.selectors(&self.file))
+    }
+
+    /// Cross-checks the smart contract code against ink! contract metadata
+    /// (i.e. the contract API described by the `metadata.json` file generated by `cargo contract build`),
+    /// and returns diagnostics for any drift (e.g. mismatched selectors, message names or argument types).
+    pub fn verify_metadata(&self, metadata: &ContractMetadata) -> Vec<Diagnostic> {
+        self.verify_metadata_with_config(metadata, &DiagnosticsConfig::default())
+    }
+
+    /// Cross-checks the smart contract code against ink! contract metadata using the given configuration
+    /// (i.e. the metadata name is matched against the package name from `Cargo.toml` (if any)),
+    /// see [`Analysis::verify_metadata`].
+    pub fn verify_metadata_with_config(
+        &self,
+        metadata: &ContractMetadata,
+        config: &DiagnosticsConfig,
+    ) -> Vec<Diagnostic> {
+        let style = self.indent_style();
+        let crate_name = config
+            .cargo_toml
+            .as_deref()
+            .and_then(utils::cargo_package_name);
+        sorted_diagnostics(
+            metadata::verify(&self.file, metadata, crate_name.as_deref())
+                .into_iter()
+                .map(|diagnostic| indent_diagnostic(diagnostic, style))
+                .collect(),
+        )
+    }
+}
+
+/// Sorts diagnostics by range, then severity (see [`Analysis`] doc).
+fn sorted_diagnostics(mut diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    // NOTE: `sort_by_key` is stable, so ties keep the order in which diagnostics are computed.
+    diagnostics.sort_by_key(|diagnostic| {
+        (
+            diagnostic.range.start(),
+            diagnostic.range.end(),
+            match diagnostic.severity {
+                Severity::Error => 0,
+                Severity::Warning => 1,
+                Severity::Deprecation => 2,
+            },
+        )
+    });
+    diagnostics
+}
+
+/// Sorts completions by range (see [`Analysis`] doc).
+fn sorted_completions(mut completions: Vec<Completion>) -> Vec<Completion> {
+    completions.sort_by_key(|completion| (completion.range.start(), completion.range.end()));
+    completions
+}
+
+/// Sorts actions by kind, then the range of their first text edit (see [`Analysis`] doc).
+fn sorted_actions(mut actions: Vec<Action>) -> Vec<Action> {
+    actions.sort_by_key(|action| {
+        (
+            action_kind_order(action.kind),
+            action
+                .edits
+                .first()
+                .map(|edit| (edit.range.start(), edit.range.end())),
+        )
+    });
+    actions
+}
+
+/// Returns the sort order of the action kind (i.e. quickfixes, then refactors, then source actions).
+fn action_kind_order(kind: ActionKind) -> u8 {
+    match kind {
+        ActionKind::QuickFix => 0,
+        ActionKind::Refactor | ActionKind::RefactorExtract | ActionKind::RefactorRewrite => 1,
+        ActionKind::Source => 2,
+        ActionKind::SourceFixAll => 3,
+    }
+}
+
+/// Converts the canonical indenting of the diagnostic's quickfixes to the given indentation style.
+fn indent_diagnostic(mut diagnostic: Diagnostic, style: IndentStyle) -> Diagnostic {
+    diagnostic.quickfixes = diagnostic.quickfixes.map(|quickfixes| {
+        quickfixes
+            .into_iter()
+            .map(|action| indent_action(action, style))
+            .collect()
+    });
+    diagnostic
+}
+
+/// Converts the canonical indenting of the action's edits to the given indentation style.
+fn indent_action(mut action: Action, style: IndentStyle) -> Action {
+    action.edits = action
+        .edits
+        .into_iter()
+        .map(|edit| indent_edit(edit, style))
+        .collect();
+    action
+}
+
+/// Converts the canonical indenting of the text edit to the given indentation style.
+fn indent_edit(mut edit: TextEdit, style: IndentStyle) -> TextEdit {
+    edit.text = style.apply(&edit.text);
+    edit.snippet = edit.snippet.map(|snippet| style.apply(&snippet));
+    edit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::parse_offset_at;
+
+    #[test]
+    fn deterministic_ordering_works() {
+        let code = r#"
+            #[ink::contract]
+            #[ink(env=crate::Environment)]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                #[ink(storage)]
+                pub struct MyContract2 {}
+
+                impl MyContract {
+                    #[ink(constructor, payable)]
+                    pub fn new() -> Self {}
+
+                    #[ink(message, selector = 1)]
+                    pub fn my_message(&self) {}
+
+                    #[ink(message, selector = 1)]
+                    pub fn my_message2(&self) {}
+                }
+
+                #[ink(event)]
+                pub struct MyEvent {
+                    #[ink(topic)]
+                    value: Mapping<u8, u8>,
+                }
+            }
+
+            #[ink::
+        "#;
+        let analysis = Analysis::new(code);
+        let completion_offset =
+            TextSize::from(parse_offset_at(code, Some("#[ink::->")).unwrap() as u32);
+        let action_offset = TextSize::from(parse_offset_at(code, Some("<-mod")).unwrap() as u32);
+        let action_range = TextRange::new(action_offset, action_offset);
+
+        // Results are sorted as documented.
+        let diagnostics = analysis.diagnostics();
+        assert!(diagnostics.len() > 1);
+        assert!(diagnostics.windows(2).all(|pair| {
+            (pair[0].range.start(), pair[0].range.end())
+                <= (pair[1].range.start(), pair[1].range.end())
+        }));
+        let completions = analysis.completions(completion_offset);
+        assert!(!completions.is_empty());
+        assert!(completions.windows(2).all(|pair| {
+            (pair[0].range.start(), pair[0].range.end())
+                <= (pair[1].range.start(), pair[1].range.end())
+        }));
+        let actions = analysis.actions(action_range);
+        assert!(actions.len() > 1);
+        let action_key = |action: &Action| {
+            (
+                action_kind_order(action.kind),
+                action
+                    .edits
+                    .first()
+                    .map(|edit| (edit.range.start(), edit.range.end())),
+            )
+        };
+        assert!(actions
+            .windows(2)
+            .all(|pair| action_key(&pair[0]) <= action_key(&pair[1])));
+
+        // Results are stable across runs and analysis instances.
+        for _ in 0..3 {
+            let analysis = Analysis::new(code);
+            assert_eq!(analysis.diagnostics(), diagnostics);
+            assert_eq!(analysis.completions(completion_offset), completions);
+            assert_eq!(analysis.actions(action_range), actions);
+            assert_eq!(
+                analysis.diagnostics_parallel(&DiagnosticsConfig::default()),
+                diagnostics
+            );
+        }
+    }
+
+    #[test]
+    fn resolve_action_works() {
+        let code = "mod my_contract {}";
+        let analysis = Analysis::new(code);
+        let range = TextRange::new(TextSize::from(0), TextSize::from(15));
+
+        // Unresolved actions match resolved actions (except for text edits).
+        let actions = analysis.actions(range);
+        let unresolved_actions = analysis.unresolved_actions(range);
+        assert!(!actions.is_empty());
+        assert_eq!(unresolved_actions.len(), actions.len());
+        for (unresolved_action, action) in unresolved_actions.iter().zip(&actions) {
+            assert_eq!(unresolved_action.label, action.label);
+            assert_eq!(unresolved_action.kind, action.kind);
+            assert_eq!(unresolved_action.range, action.range);
+
+            // Resolves text edits.
+            assert_eq!(
+                analysis
+                    .resolve_action(range, &unresolved_action.id)
+                    .as_ref(),
+                Some(action)
+            );
+        }
+
+        // Identifiers are unique.
+        assert!(unresolved_actions
+            .iter()
+            .map(|action| &action.id)
+            .all_unique());
+
+        // Unknown identifiers are not resolved.
+        assert!(analysis.resolve_action(range, "unknown").is_none());
+    }
+
+    #[test]
+    fn actions_at_works() {
+        let code = r#"
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn my_message(&self) {}
+                }
+            }
+        "#;
+        let analysis = Analysis::new(code);
+        let attr_range = TextRange::new(
+            TextSize::from(parse_offset_at(code, Some("<-#[ink(message)]")).unwrap() as u32),
+            TextSize::from(parse_offset_at(code, Some("#[ink(message)]")).unwrap() as u32),
+        );
+        let name_range = TextRange::new(
+            TextSize::from(parse_offset_at(code, Some("<-my_message")).unwrap() as u32),
+            TextSize::from(parse_offset_at(code, Some("my_message")).unwrap() as u32),
+        );
+
+        for (pat, expected_range) in [
+            // (pat, range) where:
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // range = text range of the equivalent range-based actions.
+            // Start of the attribute.
+            (Some("<-#[ink(message)]"), attr_range),
+            // End of the attribute.
+            (Some("#[ink(message)]"), attr_range),
+            // Inside the attribute.
+            (Some("#[ink(mess"), attr_range),
+            (Some("#[ink(message"), attr_range),
+            // Item name.
+            (Some("pub fn my_mess"), name_range),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
+            let results = analysis.actions_at(offset);
+            assert!(!results.is_empty(), "pat: {pat:?}");
+            assert_eq!(results, analysis.actions(expected_range), "pat: {pat:?}");
+        }
+
+        // Offsets inside whitespace use an empty focus range.
+        let offset = TextSize::from(parse_offset_at(code, Some("my_contract {")).unwrap() as u32)
+            + TextSize::from(1);
+        assert_eq!(
+            analysis.actions_at(offset),
+            analysis.actions(TextRange::empty(offset))
+        );
+
+        // Invalid offsets yield empty results.
+        assert!(analysis
+            .actions_at(TextSize::of(code) + TextSize::from(1))
+            .is_empty());
+    }
+
+    #[test]
+    fn actions_with_config_works() {
+        let code = "#[ink::contract]\nmod my_contract {}";
+        let analysis = Analysis::new(code);
+        let range = analysis.file().syntax().text_range();
+        let has_storage_quickfix = |actions: &[Action]| {
+            actions.iter().any(|action| {
+                action.kind == ActionKind::QuickFix
+                    && action
+                        .edits
+                        .iter()
+                        .any(|edit| edit.text.contains("#[ink(storage)]"))
+            })
+        };
+
+        // Quickfixes are offered for diagnostics with the default config.
+        assert!(has_storage_quickfix(&analysis.actions(range)));
+
+        // Quickfixes aren't offered for disabled diagnostics.
+        let mut config = DiagnosticsConfig::default();
+        config.disabled.insert(DiagnosticCode::MissingStorage);
+        let actions = analysis.actions_with_config(range, &config);
+        assert!(!actions.is_empty());
+        assert!(!has_storage_quickfix(&actions));
+
+        // Unresolved actions resolve to the same actions for the same config.
+        let unresolved_actions = analysis.unresolved_actions_with_config(range, &config);
+        assert_eq!(unresolved_actions.len(), actions.len());
+        for (unresolved_action, action) in unresolved_actions.iter().zip(&actions) {
+            assert_eq!(
+                analysis.resolve_action_with_config(range, &unresolved_action.id, &config),
+                Some(action.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn fix_all_works() {
+        // No diagnostics.
+        assert!(Analysis::new("mod my_contract {}").fix_all().is_none());
+
+        let code = "#[ink::contract]\nmod my_contract {}";
+        let analysis = Analysis::new(code);
+        let diagnostics = analysis.diagnostics();
+        assert!(diagnostics
+            .iter()
+            .filter_map(|diagnostic| diagnostic.quickfixes.as_ref())
+            .flatten()
+            .any(|action| action.is_preferred));
+
+        let action = analysis.fix_all().unwrap();
+        assert_eq!(action.kind, ActionKind::SourceFixAll);
+        assert_eq!(action.range, analysis.file().syntax().text_range());
+
+        // Applying the combined edits fixes (some of) the diagnostics.
+        let mut fixed_code = code.to_string();
+        for edit in action.edits.iter().rev() {
+            fixed_code.replace_range(std::ops::Range::<usize>::from(edit.range), &edit.text);
+        }
+        assert!(Analysis::new(&fixed_code).diagnostics().len() < diagnostics.len());
+
+        // Quickfixes for disabled diagnostics are never applied.
+        let inserts_storage = |action: &Action| {
+            action
+                .edits
+                .iter()
+                .any(|edit| edit.text.contains("#[ink(storage)]"))
+        };
+        assert!(inserts_storage(&action));
+        let mut config = DiagnosticsConfig::default();
+        config.disabled.insert(DiagnosticCode::MissingStorage);
+        let action = analysis.fix_all_with_config(&config).unwrap();
+        assert!(!inserts_storage(&action));
+    }
+
+    #[test]
+    fn validate_range_works() {
+        // `é` is 2 bytes.
+        let code = "#[ink(messagé)]\nfn my_message() {}";
+        let analysis = Analysis::new(code);
+        let len = TextSize::of(code);
+        let inside_char = TextSize::from(13);
+        let out_of_bounds = len + TextSize::from(1);
+
+        for (range, expected_result) in [
+            (TextRange::empty(0.into()), Ok(())),
+            (TextRange::empty(len), Ok(())),
+            (TextRange::new(0.into(), len), Ok(())),
+            (TextRange::new(2.into(), 12.into()), Ok(())),
+            (
+                TextRange::empty(out_of_bounds),
+                Err(AnalysisError::OutOfBounds(TextRange::empty(out_of_bounds))),
+            ),
+            (
+                TextRange::new(0.into(), inside_char),
+                Err(AnalysisError::NotCharBoundary(inside_char)),
+            ),
+        ] {
+            assert_eq!(
+                analysis.validate_range(range),
+                expected_result,
+                "range: {range:?}"
+            );
+        }
+
+        // Invalid offsets and ranges yield empty results (instead of panicking).
+        for offset in [inside_char, out_of_bounds] {
+            assert!(analysis.completions(offset).is_empty());
+            assert!(analysis.signature_help(offset).is_empty());
+            assert!(analysis.selection_ranges(offset).is_empty());
+            assert!(analysis.actions(TextRange::empty(offset)).is_empty());
+            assert!(analysis.hover(TextRange::empty(offset)).is_none());
+        }
+    }
 }
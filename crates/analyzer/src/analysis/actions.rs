@@ -3,50 +3,155 @@
 use ink_analyzer_ir::syntax::{SyntaxNode, TextRange, TextSize};
 use ink_analyzer_ir::{FromSyntax, InkAttribute, InkFile};
 use itertools::Itertools;
+use std::collections::HashMap;
 
+use super::queries::Queries;
 use super::utils;
-use crate::analysis::text_edit;
-use crate::TextEdit;
+use crate::analysis::text_edit::{self, EditBuilder};
+use crate::{Diagnostic, DocumentEdit, Severity, TextEdit};
 
 mod attr;
 pub mod entity;
 mod item;
+mod merge;
 
 /// An ink! attribute code/intent action.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Action {
     /// Label which identifies the action.
     pub label: String,
     /// The kind of the action (e.g quickfix or refactor).
     pub kind: ActionKind,
     /// Range where the action is activated.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
     pub range: TextRange,
     /// Text edits that will performed by the action.
     pub edits: Vec<TextEdit>,
+    /// Text edits that will performed by the action in other documents (e.g. the project's `Cargo.toml`).
+    pub document_edits: Vec<DocumentEdit>,
+    /// Whether the action is the preferred fix for its diagnostic
+    /// (i.e. safe for clients to auto-apply, e.g. for "fix all" or on save).
+    pub is_preferred: bool,
+    /// Rank of the action relative to other quickfixes for the same diagnostic (higher is better).
+    pub priority: u8,
 }
 
 /// The kind of the action (e.g quickfix or refactor).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum ActionKind {
     QuickFix,
     Refactor,
+    /// A refactor that extracts code into a new item (e.g. extracting ink! messages into an ink! trait definition).
+    RefactorExtract,
+    /// A refactor that rewrites code without changing its semantics
+    /// (e.g. flattening ink! attributes or pinning derived selectors).
+    RefactorRewrite,
+    /// A source action that applies to the whole item or file (e.g. reordering ink! contract items).
+    Source,
+    /// A source action that applies all safe quickfixes in a file, see [`crate::Analysis::fix_all`].
+    SourceFixAll,
+}
+
+/// An unresolved ink! attribute code/intent action (i.e. without its text edits),
+/// see [`crate::Analysis::resolve_action`].
+///
+/// Useful for clients that only compute text edits for actions that are actually applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnresolvedAction {
+    /// Identifier for resolving the action (i.e. computing its text edits).
+    ///
+    /// The identifier is derived from the kind, range and label of the action,
+    /// so it's only valid for the same document contents and text range.
+    pub id: String,
+    /// Label which identifies the action.
+    pub label: String,
+    /// The kind of the action (e.g quickfix or refactor).
+    pub kind: ActionKind,
+    /// Range where the action is activated.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
+    pub range: TextRange,
+    /// Whether the action is the preferred fix for its diagnostic, see [`Action::is_preferred`].
+    pub is_preferred: bool,
+    /// Rank of the action relative to other quickfixes for the same diagnostic, see [`Action::priority`].
+    pub priority: u8,
+}
+
+/// Returns unresolved actions (i.e. without text edits) for the given actions.
+pub fn unresolved(actions: &[Action]) -> Vec<UnresolvedAction> {
+    actions
+        .iter()
+        .zip(ids(actions))
+        .map(|(action, id)| UnresolvedAction {
+            id,
+            label: action.label.clone(),
+            kind: action.kind,
+            range: action.range,
+            is_preferred: action.is_preferred,
+            priority: action.priority,
+        })
+        .collect()
+}
+
+/// Returns the action with the given identifier (if any), see [`UnresolvedAction::id`].
+pub fn resolve(actions: Vec<Action>, id: &str) -> Option<Action> {
+    let ids = ids(&actions);
+    actions
+        .into_iter()
+        .zip(ids)
+        .find_map(|(action, action_id)| (action_id == id).then_some(action))
+}
+
+/// Returns identifiers for the given actions.
+///
+/// Identifiers of actions with the same kind, range and label are disambiguated by their position.
+fn ids(actions: &[Action]) -> Vec<String> {
+    let mut n_occurrences: HashMap<String, usize> = HashMap::new();
+    actions
+        .iter()
+        .map(|action| {
+            let base_id = format!(
+                "{}:{}:{}:{}",
+                match action.kind {
+                    ActionKind::QuickFix => "quickfix",
+                    ActionKind::Refactor => "refactor",
+                    ActionKind::RefactorExtract => "refactor.extract",
+                    ActionKind::RefactorRewrite => "refactor.rewrite",
+                    ActionKind::Source => "source",
+                    ActionKind::SourceFixAll => "fixall",
+                },
+                u32::from(action.range.start()),
+                u32::from(action.range.end()),
+                action.label
+            );
+            let n = n_occurrences.entry(base_id.clone()).or_insert(0);
+            *n += 1;
+            if *n == 1 {
+                base_id
+            } else {
+                format!("{base_id}:{n}")
+            }
+        })
+        .collect()
 }
 
 /// Computes ink! attribute actions for the text range.
-pub fn actions(file: &InkFile, range: TextRange) -> Vec<Action> {
+pub fn actions(file: &InkFile, queries: &Queries, range: TextRange) -> Vec<Action> {
     let mut results = Vec::new();
 
     // Compute AST item-based ink! attribute actions.
-    item::actions(&mut results, file, range);
+    item::actions(&mut results, file, queries, range);
 
     // Compute ink! attribute actions based on focused ink! attribute.
-    attr::actions(&mut results, file, range);
+    attr::actions(&mut results, file, queries, range);
 
     results
         .into_iter()
         // Deduplicate by edits.
-        .unique_by(|item| item.edits.clone())
+        .unique_by(|item| (item.edits.clone(), item.document_edits.clone()))
         // Format edits.
         .map(|item| Action {
             edits: text_edit::format_edits(item.edits, file).collect(),
@@ -55,6 +160,42 @@ pub fn actions(file: &InkFile, range: TextRange) -> Vec<Action> {
         .collect()
 }
 
+/// Returns a "fix all" source action that combines the safe quickfixes
+/// (i.e. preferred quickfixes, see [`Action::is_preferred`]) of the diagnostics (if any).
+///
+/// Quickfixes for errors take precedence over quickfixes for warnings,
+/// and quickfixes whose edits conflict with the edits of previously combined quickfixes are skipped
+/// (i.e. they can be applied by running the action again), see [`merge::merge`].
+pub fn fix_all(diagnostics: &[Diagnostic], range: TextRange) -> Option<Action> {
+    let mut quickfixes: Vec<(bool, &Action)> = diagnostics
+        .iter()
+        .flat_map(|diagnostic| {
+            diagnostic
+                .quickfixes
+                .iter()
+                .flatten()
+                .filter(|action| action.is_preferred)
+                .map(move |action| (diagnostic.severity != Severity::Error, action))
+        })
+        .collect();
+    quickfixes.sort_by_key(|(is_warning, _)| *is_warning);
+    let quickfixes: Vec<Action> = quickfixes
+        .into_iter()
+        .map(|(_, action)| action.clone())
+        .collect();
+
+    let merged = merge::merge(&quickfixes);
+    (!merged.merged.is_empty()).then(|| Action {
+        label: "Fix all auto-fixable ink! problems.".to_string(),
+        kind: ActionKind::SourceFixAll,
+        range,
+        edits: merged.edits,
+        document_edits: merged.document_edits,
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
 impl Action {
     /// Removes an ink! attribute.
     pub(crate) fn remove_attribute(attr: &InkAttribute) -> Self {
@@ -63,6 +204,9 @@ impl Action {
             kind: ActionKind::QuickFix,
             range: attr.syntax().text_range(),
             edits: vec![TextEdit::delete(attr.syntax().text_range())],
+            document_edits: Vec::new(),
+            is_preferred: false,
+            priority: 0,
         }
     }
 
@@ -73,6 +217,9 @@ impl Action {
             kind: ActionKind::QuickFix,
             range: item.text_range(),
             edits: vec![TextEdit::delete(item.text_range())],
+            document_edits: Vec::new(),
+            is_preferred: false,
+            priority: 0,
         }
     }
 
@@ -97,23 +244,18 @@ impl Action {
     ) -> Self {
         // Gets the unindented insert text.
         // NOTE: removes item's top-level indenting (if any).
-        let mut insert_text = utils::item_indenting(item).map_or(item.to_string(), |item_indent| {
-            utils::reduce_indenting(item.to_string().as_str(), item_indent.as_str())
+        let item_text = utils::item_text(item);
+        let insert_text = utils::item_indenting(item).map_or(item_text.clone(), |item_indent| {
+            utils::reduce_indenting(item_text.as_str(), item_indent.as_str())
         });
 
-        // Applies indenting based on insert location (if specified).
+        // Applies indenting based on insert location (if specified) and affixes (if any).
+        let mut insert_edit = EditBuilder::insert(insert_text, offset).affixes(
+            prefix_option.map(ToString::to_string),
+            suffix_option.map(ToString::to_string),
+        );
         if let Some(indent) = indent_option {
-            insert_text = utils::apply_indenting(insert_text.as_str(), indent);
-        }
-
-        // Adds prefix (if any).
-        if let Some(prefix) = prefix_option {
-            insert_text = format!("{prefix}{insert_text}");
-        }
-
-        // Adds suffix (if any).
-        if let Some(suffix) = suffix_option {
-            insert_text = format!("{insert_text}{suffix}");
+            insert_edit = insert_edit.indent(indent);
         }
 
         Self {
@@ -122,10 +264,13 @@ impl Action {
             range: item.text_range(),
             edits: vec![
                 // Insert a copy of the item at the specified offset.
-                TextEdit::insert(insert_text, offset),
+                insert_edit.build(),
                 // Delete the item from current location.
                 TextEdit::delete(item.text_range()),
             ],
+            document_edits: Vec::new(),
+            is_preferred: false,
+            priority: 0,
         }
     }
 }
@@ -2,15 +2,16 @@
 
 use crate::{ActionKind, TextEdit};
 use ink_analyzer_ir::syntax::TextRange;
-use ink_analyzer_ir::{FromAST, FromSyntax, InkFile};
+use ink_analyzer_ir::{FromAST, FromSyntax, InkArgKind, InkFile, InkImpl};
 
-use super::Action;
+use super::{entity, Action};
+use crate::analysis::queries::Queries;
 use crate::analysis::utils;
 
 /// Computes ink! attribute-based actions at the given text range.
-pub fn actions(results: &mut Vec<Action>, file: &InkFile, range: TextRange) {
+pub fn actions(results: &mut Vec<Action>, file: &InkFile, queries: &Queries, range: TextRange) {
     // Only computes actions if the focused range is part of/covered by an ink! attribute.
-    if let Some(ink_attr) = utils::covering_ink_attribute(file, range) {
+    if let Some(ink_attr) = utils::indexed_covering_ink_attribute(file, queries, range) {
         // Only computes actions for closed attributes because
         // unclosed attributes are too tricky for useful contextual edits.
         if ink_attr.ast().r_brack_token().is_some() {
@@ -54,10 +55,30 @@ pub fn actions(results: &mut Vec<Action>, file: &InkFile, range: TextRange) {
                                 )
                             }),
                         )],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     });
                 }
             }
         }
+
+        // Pins derived selectors if the focused ink! attribute is an ink! namespace argument of an ink! impl
+        // (i.e. selectors depend on the namespace).
+        let is_namespace_attr = ink_attr
+            .args()
+            .iter()
+            .any(|arg| *arg.kind() == InkArgKind::Namespace);
+        if let Some(ink_impl) = ink_attr
+            .syntax()
+            .parent()
+            .filter(|_| is_namespace_attr)
+            .and_then(InkImpl::cast)
+        {
+            if let Some(action) = entity::pin_selectors(&ink_impl, ActionKind::RefactorRewrite) {
+                results.push(action);
+            }
+        }
     }
 }
 
@@ -400,12 +421,52 @@ mod tests {
                 Some("<-#["),
                 vec![(", handle_status=true", Some("<-)]"), Some("<-)]"))],
             ),
+            // Pins derived selectors for ink! namespace arguments of ink! impls.
+            (
+                r#"
+                    #[ink(namespace = "my_namespace")]
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn my_message(&self) {}
+                    }
+                "#,
+                Some("<-#["),
+                vec![
+                    (r#", keep_attr="""#, Some("<-)]"), Some("<-)]")),
+                    ("impl, ", Some("<-namespace"), Some("<-namespace")),
+                    // First 4-bytes of Blake2b-256 hash of "my_namespace::my_message".
+                    (
+                        ", selector = 0xabe89c04",
+                        Some("<-)]\n                        pub fn my_message"),
+                        Some("<-)]\n                        pub fn my_message"),
+                    ),
+                ],
+            ),
+            (
+                r#"
+                    #[ink(namespace = "my_namespace")]
+                    impl MyContract {
+                        #[ink(message, selector = 1)]
+                        pub fn my_message(&self) {}
+                    }
+                "#,
+                Some("<-#["),
+                vec![
+                    (r#", keep_attr="""#, Some("<-)]"), Some("<-)]")),
+                    ("impl, ", Some("<-namespace"), Some("<-namespace")),
+                ],
+            ),
         ] {
             let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
             let range = TextRange::new(offset, offset);
 
             let mut results = Vec::new();
-            actions(&mut results, &InkFile::parse(code), range);
+            actions(
+                &mut results,
+                &InkFile::parse(code),
+                &Queries::default(),
+                range,
+            );
 
             assert_eq!(
                 results
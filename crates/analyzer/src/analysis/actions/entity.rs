@@ -1,11 +1,18 @@
 //! ink! entity code/intent actions.
 
-use ink_analyzer_ir::syntax::{AstNode, TextRange, TextSize};
+use ink_analyzer_ir::ast::{HasAttrs, HasDocComments, HasGenericParams, HasModuleItem, HasName};
+use ink_analyzer_ir::syntax::{AstNode, SyntaxKind, SyntaxNode, TextRange, TextSize};
 use ink_analyzer_ir::{
-    ast, ChainExtension, Contract, Event, FromSyntax, IsInkStruct, IsInkTrait, TraitDefinition,
+    ast, ChainExtension, Contract, Event, FromInkAttribute, FromSyntax, InkArgKind, InkAttribute,
+    InkAttributeKind, InkImpl, IsInkCallable, IsInkFn, IsInkStruct, IsInkTrait, Message, Storage,
+    TraitDefinition,
 };
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::ops::Range;
 
 use super::{Action, ActionKind};
+use crate::analysis::text_edit::EditBuilder;
 use crate::analysis::utils;
 use crate::codegen::snippets::{
     CHAIN_EXTENSION_PLAIN, CHAIN_EXTENSION_SNIPPET, CONSTRUCTOR_PLAIN, CONSTRUCTOR_SNIPPET,
@@ -31,8 +38,6 @@ pub fn add_storage(
                 .as_ref()
                 .map(utils::item_insert_offset_start))
             .map(|insert_offset| {
-                // Sets insert indent.
-                let indent = utils::item_children_indenting(module.syntax());
                 // Gets the "resolved" contract name.
                 let contract_name = utils::resolve_contract_name(contract);
 
@@ -40,25 +45,25 @@ pub fn add_storage(
                     label: "Add ink! storage `struct`.".to_string(),
                     kind,
                     range: utils::contract_declaration_range(contract),
-                    edits: vec![TextEdit::insert_with_snippet(
-                        utils::apply_indenting(
-                            contract_name
-                                .as_deref()
-                                .map(|name| STORAGE_PLAIN.replace("Storage", name))
-                                .as_deref()
-                                .unwrap_or(STORAGE_PLAIN),
-                            &indent,
-                        ),
+                    edits: vec![EditBuilder::insert(
+                        contract_name
+                            .as_deref()
+                            .map(|name| STORAGE_PLAIN.replace("Storage", name))
+                            .unwrap_or(STORAGE_PLAIN.to_string()),
                         insert_offset,
-                        Some(utils::apply_indenting(
-                            contract_name
-                                .as_deref()
-                                .map(|name| STORAGE_SNIPPET.replace("Storage", name))
-                                .as_deref()
-                                .unwrap_or(STORAGE_SNIPPET),
-                            &indent,
-                        )),
-                    )],
+                    )
+                    .snippet(
+                        contract_name
+                            .as_deref()
+                            .map(|name| STORAGE_SNIPPET.replace("Storage", name))
+                            .unwrap_or(STORAGE_SNIPPET.to_string()),
+                    )
+                    // Sets insert indent.
+                    .indent_as_child_of(module.syntax())
+                    .build()],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }
             })
     })
@@ -78,35 +83,32 @@ pub fn add_event(
                 .as_ref()
                 .map(utils::item_insert_offset_after_last_struct_or_start))
             .map(|insert_offset| {
-                // Sets insert indent.
-                let indent = utils::item_children_indenting(module.syntax());
-                // Suggests an event name based on the "resolved" contract name.
-                let suggested_event_name =
-                    utils::resolve_contract_name(contract).map(|name| format!("My{name}Event"));
+                // Suggests a unique event name based on the "resolved" contract name.
+                let (plain, snippet) = with_unique_name(
+                    EVENT_PLAIN,
+                    EVENT_SNIPPET,
+                    "Event",
+                    &utils::resolve_contract_name(contract)
+                        .map(|name| format!("My{name}Event"))
+                        .unwrap_or("Event".to_string()),
+                    &module
+                        .item_list()
+                        .as_ref()
+                        .map(utils::item_names)
+                        .unwrap_or_default(),
+                );
 
                 Action {
                     label: "Add ink! event `struct`.".to_string(),
                     kind,
                     range: utils::contract_declaration_range(contract),
-                    edits: vec![TextEdit::insert_with_snippet(
-                        utils::apply_indenting(
-                            suggested_event_name
-                                .as_deref()
-                                .map(|name| EVENT_PLAIN.replace("Event", name))
-                                .as_deref()
-                                .unwrap_or(EVENT_PLAIN),
-                            &indent,
-                        ),
-                        insert_offset,
-                        Some(utils::apply_indenting(
-                            suggested_event_name
-                                .as_deref()
-                                .map(|name| EVENT_SNIPPET.replace("Event", name))
-                                .as_deref()
-                                .unwrap_or(EVENT_SNIPPET),
-                            &indent,
-                        )),
-                    )],
+                    edits: vec![EditBuilder::insert(plain, insert_offset)
+                        .snippet(snippet)
+                        .indent_as_child_of(module.syntax())
+                        .build()],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }
             })
     })
@@ -127,8 +129,24 @@ pub fn add_topic(
                 .as_ref()
                 .map(utils::field_insert_offset_end_and_affixes))
             .map(|(insert_offset, prefix, suffix)| {
-                // Sets insert indent.
-                let indent = utils::item_children_indenting(struct_item.syntax());
+                // Suggests a unique topic name.
+                let (plain, snippet) = with_unique_name(
+                    TOPIC_PLAIN,
+                    TOPIC_SNIPPET,
+                    "topic",
+                    "topic",
+                    &struct_item
+                        .field_list()
+                        .map(|field_list| match field_list {
+                            ast::FieldList::RecordFieldList(record_field_list) => record_field_list
+                                .fields()
+                                .filter_map(|field| field.name())
+                                .map(|name| name.to_string())
+                                .collect(),
+                            ast::FieldList::TupleFieldList(_) => HashSet::new(),
+                        })
+                        .unwrap_or_default(),
+                );
 
                 Action {
                     label: "Add ink! topic `field`.".to_string(),
@@ -137,21 +155,14 @@ pub fn add_topic(
                         struct_item.clone(),
                     ))
                     .unwrap_or(struct_item.syntax().text_range()),
-                    edits: vec![TextEdit::insert_with_snippet(
-                        format!(
-                            "{}{}{}",
-                            prefix.as_deref().unwrap_or_default(),
-                            utils::apply_indenting(TOPIC_PLAIN, &indent),
-                            suffix.as_deref().unwrap_or_default()
-                        ),
-                        insert_offset,
-                        Some(format!(
-                            "{}{}{}",
-                            prefix.as_deref().unwrap_or_default(),
-                            utils::apply_indenting(TOPIC_SNIPPET, &indent),
-                            suffix.as_deref().unwrap_or_default()
-                        )),
-                    )],
+                    edits: vec![EditBuilder::insert(plain, insert_offset)
+                        .snippet(snippet)
+                        .indent_as_child_of(struct_item.syntax())
+                        .affixes(prefix, suffix)
+                        .build()],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }
             })
     })
@@ -203,21 +214,14 @@ fn add_callable_to_contract(
                 label,
                 kind,
                 range: utils::contract_declaration_range(contract),
-                edits: vec![TextEdit::insert_with_snippet(
-                    format!(
-                        "{}{}{}",
-                        prefix.as_deref().unwrap_or_default(),
-                        utils::apply_indenting(plain, &indent),
-                        suffix.as_deref().unwrap_or_default()
-                    ),
-                    insert_offset,
-                    Some(format!(
-                        "{}{}{}",
-                        prefix.as_deref().unwrap_or_default(),
-                        utils::apply_indenting(snippet, &indent),
-                        suffix.as_deref().unwrap_or_default()
-                    )),
-                )],
+                edits: vec![EditBuilder::insert(plain, insert_offset)
+                    .snippet(snippet)
+                    .indent(indent)
+                    .affixes(prefix, suffix)
+                    .build()],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }))
 }
 
@@ -227,13 +231,21 @@ pub fn add_constructor_to_contract(
     kind: ActionKind,
     insert_offset_option: Option<TextSize>,
 ) -> Option<Action> {
+    // Suggests a unique constructor name.
+    let (plain, snippet) = with_unique_name(
+        CONSTRUCTOR_PLAIN,
+        CONSTRUCTOR_SNIPPET,
+        "new",
+        "new",
+        &contract_fn_names(contract),
+    );
     add_callable_to_contract(
         contract,
         kind,
         insert_offset_option,
         "Add ink! constructor `fn`.".to_string(),
-        CONSTRUCTOR_PLAIN,
-        CONSTRUCTOR_SNIPPET,
+        &plain,
+        &snippet,
     )
 }
 
@@ -243,13 +255,21 @@ pub fn add_message_to_contract(
     kind: ActionKind,
     insert_offset_option: Option<TextSize>,
 ) -> Option<Action> {
+    // Suggests a unique message name.
+    let (plain, snippet) = with_unique_name(
+        MESSAGE_PLAIN,
+        MESSAGE_SNIPPET,
+        "message",
+        "message",
+        &contract_fn_names(contract),
+    );
     add_callable_to_contract(
         contract,
         kind,
         insert_offset_option,
         "Add ink! message `fn`.".to_string(),
-        MESSAGE_PLAIN,
-        MESSAGE_SNIPPET,
+        &plain,
+        &snippet,
     )
 }
 
@@ -268,21 +288,18 @@ fn add_callable_to_impl(
             .assoc_item_list()
             .as_ref()
             .map(utils::assoc_item_insert_offset_end))
-        .map(|insert_offset| {
-            // Sets insert indent.
-            let indent = utils::item_children_indenting(impl_item.syntax());
-
-            Action {
-                label,
-                kind,
-                range: utils::ast_item_declaration_range(&ast::Item::Impl(impl_item.clone()))
-                    .unwrap_or(impl_item.syntax().text_range()),
-                edits: vec![TextEdit::insert_with_snippet(
-                    utils::apply_indenting(plain, &indent),
-                    insert_offset,
-                    Some(utils::apply_indenting(snippet, &indent)),
-                )],
-            }
+        .map(|insert_offset| Action {
+            label,
+            kind,
+            range: utils::ast_item_declaration_range(&ast::Item::Impl(impl_item.clone()))
+                .unwrap_or(impl_item.syntax().text_range()),
+            edits: vec![EditBuilder::insert(plain, insert_offset)
+                .snippet(snippet)
+                .indent_as_child_of(impl_item.syntax())
+                .build()],
+            document_edits: Vec::new(),
+            is_preferred: false,
+            priority: 0,
         })
 }
 
@@ -292,13 +309,21 @@ pub fn add_constructor_to_impl(
     kind: ActionKind,
     insert_offset_option: Option<TextSize>,
 ) -> Option<Action> {
+    // Suggests a unique constructor name.
+    let (plain, snippet) = with_unique_name(
+        CONSTRUCTOR_PLAIN,
+        CONSTRUCTOR_SNIPPET,
+        "new",
+        "new",
+        &impl_fn_names(impl_item),
+    );
     add_callable_to_impl(
         impl_item,
         kind,
         insert_offset_option,
         "Add ink! constructor `fn`.".to_string(),
-        CONSTRUCTOR_PLAIN,
-        CONSTRUCTOR_SNIPPET,
+        &plain,
+        &snippet,
     )
 }
 
@@ -308,16 +333,762 @@ pub fn add_message_to_impl(
     kind: ActionKind,
     insert_offset_option: Option<TextSize>,
 ) -> Option<Action> {
+    // Suggests a unique message name.
+    let (plain, snippet) = with_unique_name(
+        MESSAGE_PLAIN,
+        MESSAGE_SNIPPET,
+        "message",
+        "message",
+        &impl_fn_names(impl_item),
+    );
     add_callable_to_impl(
         impl_item,
         kind,
         insert_offset_option,
         "Add ink! message `fn`.".to_string(),
-        MESSAGE_PLAIN,
-        MESSAGE_SNIPPET,
+        &plain,
+        &snippet,
     )
 }
 
+/// Pins the current selectors of all ink! constructors and ink! messages of an ink! impl
+/// that rely on derived selectors (i.e. have no `selector` argument) by adding explicit `selector` arguments.
+///
+/// This preserves the on-chain API of the ink! contract across refactors that change derived selectors
+/// (e.g. changing the `namespace` argument of the ink! impl or the path of the implemented trait).
+///
+/// Returns `None` for ink! impls whose derived selectors don't depend on a namespace or trait path
+/// (i.e. inherent impls without a `namespace` argument), and ink! impls without derived selectors
+/// (i.e. all ink! constructors and ink! messages already have `selector` arguments).
+///
+/// For trait implementations, the selectors are pinned in the ink! trait definition (if it's defined in the same file),
+/// because ink! messages in trait implementations can't have `selector` arguments.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/master/crates/ink/ir/src/ir/item_impl/callable.rs#L346-L368>.
+pub fn pin_selectors(ink_impl: &InkImpl, kind: ActionKind) -> Option<Action> {
+    let trait_definition = match ink_impl.trait_type() {
+        Some(_) => Some(ink_impl.trait_definition()?),
+        // Derived selectors of inherent impls only depend on the namespace (if any).
+        None if ink_impl.namespace_arg().is_some() => None,
+        None => return None,
+    };
+
+    let edits: Vec<TextEdit> = derived_selectors(ink_impl.constructors())
+        .chain(derived_selectors(ink_impl.messages()))
+        .filter_map(|(selector, name, ink_attr)| {
+            // Determines the ink! attribute that receives the `selector` argument.
+            let ink_attr = match trait_definition.as_ref() {
+                // Pins the selector in the matching ink! trait definition message (if any).
+                Some(trait_definition) => trait_definition
+                    .messages()
+                    .iter()
+                    .find(|message| {
+                        message
+                            .fn_item()
+                            .and_then(HasName::name)
+                            .zip(name.as_ref())
+                            .map_or(false, |(message_name, name)| {
+                                message_name.text() == name.text()
+                            })
+                    })
+                    .filter(|message| message.selector_arg().is_none())?
+                    .ink_attr()
+                    .clone(),
+                None => ink_attr,
+            };
+            let (insert_offset, insert_prefix, insert_suffix) =
+                utils::ink_arg_insert_offset_and_affixes(&ink_attr, Some(InkArgKind::Selector))?;
+            Some(TextEdit::insert(
+                format!(
+                    "{}selector = 0x{selector:08x}{}",
+                    insert_prefix.unwrap_or_default(),
+                    insert_suffix.unwrap_or_default()
+                ),
+                insert_offset,
+            ))
+        })
+        .collect();
+
+    (!edits.is_empty()).then(|| Action {
+        label: "Pin current selectors of ink! constructors and ink! messages.".to_string(),
+        kind,
+        range: utils::ink_impl_declaration_range(ink_impl),
+        edits,
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
+/// Returns the derived selector, name and ink! attribute of ink! callables that rely on derived selectors
+/// (i.e. have no `selector` argument).
+fn derived_selectors<T>(
+    callables: &[T],
+) -> impl Iterator<Item = (u32, Option<ast::Name>, InkAttribute)> + '_
+where
+    T: IsInkCallable + FromInkAttribute,
+{
+    callables
+        .iter()
+        .filter(|callable| callable.selector_arg().is_none())
+        .filter_map(|callable| {
+            Some((
+                callable.composed_selector()?.into_be_u32(),
+                callable.fn_item().and_then(HasName::name),
+                callable.ink_attr().clone(),
+            ))
+        })
+}
+
+/// Adds an ink! event `struct` (with topics for the mutated storage fields) to the parent ink! contract
+/// and emits it at the end of an ink! message that mutates storage fields.
+pub fn emit_event_for_storage_changes(message: &Message, kind: ActionKind) -> Option<Action> {
+    let fn_item = message.fn_item()?;
+    let stmt_list = fn_item.body()?.stmt_list()?;
+    let contract = ink_analyzer_ir::ink_ancestors::<Contract>(message.syntax()).next()?;
+    let module = contract.module()?;
+    let item_list = module.item_list()?;
+
+    // Collects the names and types of storage fields
+    // (except for storage-only types that can't be emitted, e.g. `Mapping` and `Lazy`).
+    let is_storage_only_type = |ty: &ast::Type| {
+        ty.syntax()
+            .descendants()
+            .filter_map(ast::PathSegment::cast)
+            .any(|segment| {
+                segment.name_ref().map_or(false, |name| {
+                    matches!(name.text().as_str(), "Mapping" | "Lazy" | "StorageVec")
+                })
+            })
+    };
+    let storage_fields: Vec<(String, ast::Type)> = match contract
+        .storage()
+        .and_then(IsInkStruct::struct_item)
+        .and_then(ast::Struct::field_list)
+    {
+        Some(ast::FieldList::RecordFieldList(field_list)) => field_list
+            .fields()
+            .filter_map(|field| field.name().map(|name| name.to_string()).zip(field.ty()))
+            .filter(|(_, ty)| !is_storage_only_type(ty))
+            .collect(),
+        _ => return None,
+    };
+
+    // Collects mutated storage fields (in order of first mutation)
+    // i.e. storage fields that are either assigned to or are receivers of mutating method calls.
+    let mut mutated_fields: Vec<&(String, ast::Type)> = Vec::new();
+    for node in stmt_list.syntax().descendants() {
+        let target = if let Some(bin_expr) = ast::BinExpr::cast(node.clone()) {
+            matches!(bin_expr.op_kind(), Some(ast::BinaryOp::Assignment { .. }))
+                .then(|| bin_expr.lhs())
+                .flatten()
+        } else if let Some(method_call) = ast::MethodCallExpr::cast(node) {
+            method_call
+                .name_ref()
+                .filter(|name| utils::MUTATING_METHODS.contains(&name.text().as_str()))
+                .and_then(|_| method_call.receiver())
+        } else {
+            None
+        };
+        let Some(field) = target
+            .as_ref()
+            .and_then(utils::self_field_name)
+            .and_then(|name| {
+                storage_fields
+                    .iter()
+                    .find(|(field_name, _)| *field_name == name)
+            })
+        else {
+            continue;
+        };
+        if !mutated_fields.contains(&field) {
+            mutated_fields.push(field);
+        }
+    }
+    if mutated_fields.is_empty() {
+        return None;
+    }
+
+    // Suggests a unique event name based on the message name.
+    let taken_names: Vec<String> = item_list
+        .items()
+        .filter_map(|item| match item {
+            ast::Item::Struct(struct_item) => struct_item.name(),
+            ast::Item::Enum(enum_item) => enum_item.name(),
+            _ => None,
+        })
+        .map(|name| name.to_string())
+        .collect();
+    let base_name = fn_item
+        .name()
+        .map(|name| crate::utils::pascal_case(&name.to_string()))
+        .unwrap_or("StorageChanged".to_string());
+    let event_name = std::iter::once(base_name.clone())
+        .chain((1..).map(|idx| {
+            if idx == 1 {
+                format!("{base_name}Event")
+            } else {
+                format!("{base_name}Event{idx}")
+            }
+        }))
+        .find(|name| !taken_names.contains(name))?;
+
+    // Composes the event `struct` and the emit statement.
+    let event_text = format!(
+        "#[ink(event)]\npub struct {event_name} {{\n{}}}",
+        mutated_fields
+            .iter()
+            .map(|(name, ty)| format!("    #[ink(topic)]\n    {name}: {ty},\n"))
+            .collect::<String>()
+    );
+    let emit_text = format!(
+        "self.env().emit_event({event_name} {{ {} }});",
+        mutated_fields
+            .iter()
+            .map(|(name, ty)| {
+                if COPY_TYPES.contains(&ty.to_string().as_str()) {
+                    format!("{name}: self.{name}")
+                } else {
+                    format!("{name}: self.{name}.clone()")
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+
+    // Determines the insert offset and text for the emit statement (i.e. at the end of the message body).
+    let stmt_indent = |node: &SyntaxNode| {
+        utils::item_indenting(node).unwrap_or(format!(
+            "{}    ",
+            utils::item_indenting(fn_item.syntax()).unwrap_or_default()
+        ))
+    };
+    let emit_edit = match stmt_list.tail_expr() {
+        // Emits after a tail assignment (i.e. converts it to a statement).
+        Some(ast::Expr::BinExpr(bin_expr))
+            if matches!(bin_expr.op_kind(), Some(ast::BinaryOp::Assignment { .. })) =>
+        {
+            TextEdit::insert(
+                format!(";\n{}{emit_text}", stmt_indent(bin_expr.syntax())),
+                bin_expr.syntax().text_range().end(),
+            )
+        }
+        // Emits before other tail expressions (i.e. return values).
+        Some(tail_expr) => TextEdit::insert(emit_text, tail_expr.syntax().text_range().start()),
+        // Otherwise, emits after the last statement.
+        None => {
+            let last_stmt = stmt_list.statements().last()?;
+            TextEdit::insert(
+                format!("\n{}{emit_text}", stmt_indent(last_stmt.syntax())),
+                last_stmt.syntax().text_range().end(),
+            )
+        }
+    };
+
+    Some(Action {
+        label: "Emit event for this change.".to_string(),
+        kind,
+        range: utils::ast_item_declaration_range(&ast::Item::Fn(fn_item.clone()))
+            .unwrap_or(fn_item.syntax().text_range()),
+        edits: vec![
+            // Adds the event `struct` after the last `struct` (or at the start) of the contract.
+            EditBuilder::insert(
+                event_text,
+                utils::item_insert_offset_after_last_struct_or_start(&item_list),
+            )
+            .indent_as_child_of(module.syntax())
+            .build(),
+            emit_edit,
+        ],
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
+/// Names of common `Copy` types used for storage fields.
+const COPY_TYPES: [&str; 18] = [
+    "bool",
+    "char",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "AccountId",
+    "Balance",
+    "Hash",
+    "Timestamp",
+];
+
+/// Adds a field to an ink! storage `struct` and initializes it (i.e. with `Default::default()`)
+/// in all `struct` expressions for the ink! storage `struct` in the parent ink! contract
+/// (e.g. `Self { .. }` in ink! constructors).
+///
+/// The field is always added after the existing fields (i.e. regardless of the cursor position),
+/// so that the storage layout of the existing fields is preserved.
+pub fn add_storage_field(storage: &Storage, kind: ActionKind) -> Option<Action> {
+    let struct_item = storage.struct_item()?;
+    let field_list = struct_item.field_list()?;
+    let ast::FieldList::RecordFieldList(record_field_list) = &field_list else {
+        return None;
+    };
+
+    // Suggests a unique field name.
+    let name = utils::suggest_unique_name(
+        "field",
+        &record_field_list
+            .fields()
+            .filter_map(|field| field.name())
+            .map(|name| name.to_string())
+            .collect(),
+    );
+    let (insert_offset, prefix, suffix) = utils::field_insert_offset_end_and_affixes(&field_list);
+    let field_edit = EditBuilder::insert(format!("{name}: bool,"), insert_offset)
+        .snippet(format!("{name}: ${{1:bool}},"))
+        .indent_as_child_of(struct_item.syntax())
+        .affixes(prefix, suffix)
+        .build();
+
+    // Initializes the field in `struct` expressions for the ink! storage `struct`.
+    let init_fields = [format!("{name}: Default::default()")];
+    let init_edits = ink_analyzer_ir::ink_ancestors::<Contract>(storage.syntax())
+        .next()
+        .map(|contract| storage_struct_exprs(&contract, struct_item))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|record_expr| record_expr_fields_insert_edit(&record_expr, &init_fields));
+
+    Some(Action {
+        label: "Add storage field.".to_string(),
+        kind,
+        range: utils::ast_item_declaration_range(&ast::Item::Struct(struct_item.clone()))
+            .unwrap_or(struct_item.syntax().text_range()),
+        edits: std::iter::once(field_edit).chain(init_edits).collect(),
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
+/// Removes a field from an ink! storage `struct`, its initializers in all `struct` expressions
+/// for the ink! storage `struct` in the parent ink! contract (e.g. `Self { .. }` in ink! constructors)
+/// and statements that assign to or mutate the field (e.g. `self.value = value;`).
+///
+/// Other usages of the field (e.g. `self.value` as a return value) are left in place
+/// and their number is flagged in the label of the action (i.e. they need to be updated manually).
+pub fn remove_storage_field(field: &ast::RecordField, kind: ActionKind) -> Option<Action> {
+    let name = field.name()?.to_string();
+    let storage: Storage = ink_analyzer_ir::ink_parent(field.syntax())?;
+    let struct_item = storage.struct_item()?.clone();
+    let contract = ink_analyzer_ir::ink_ancestors::<Contract>(storage.syntax()).next();
+
+    // Removes the field and its initializers.
+    let mut edits = vec![TextEdit::delete(removal_range(
+        field.syntax(),
+        Some(SyntaxKind::COMMA),
+    ))];
+    let record_exprs = contract
+        .as_ref()
+        .map(|contract| storage_struct_exprs(contract, &struct_item))
+        .unwrap_or_default();
+    edits.extend(
+        record_exprs
+            .iter()
+            .filter_map(ast::RecordExpr::record_expr_field_list)
+            .flat_map(|field_list| field_list.fields())
+            .filter(|field_init| {
+                field_init
+                    .field_name()
+                    .map_or(false, |field_name| field_name.text() == name)
+            })
+            .map(|field_init| {
+                TextEdit::delete(removal_range(field_init.syntax(), Some(SyntaxKind::COMMA)))
+            }),
+    );
+
+    // Removes statements (and tail expressions) that assign to or mutate the field.
+    let usages = contract
+        .as_ref()
+        .map(|contract| storage_field_usages(contract, &struct_item, &name))
+        .unwrap_or_default();
+    let mut removed_stmts: Vec<SyntaxNode> = Vec::new();
+    for usage in &usages {
+        let Some(stmt) = usage
+            .syntax()
+            .ancestors()
+            .find_map(|node| mutation_stmt(&node, &name))
+        else {
+            continue;
+        };
+        if !removed_stmts.contains(&stmt) {
+            edits.push(TextEdit::delete(removal_range(&stmt, None)));
+            removed_stmts.push(stmt);
+        }
+    }
+    let n_remaining_usages = usages
+        .iter()
+        .filter(|usage| {
+            !removed_stmts.iter().any(|stmt| {
+                stmt.text_range()
+                    .contains_range(usage.syntax().text_range())
+            })
+        })
+        .count();
+
+    Some(Action {
+        label: if n_remaining_usages == 0 {
+            format!("Remove `{name}` storage field.")
+        } else {
+            format!(
+                "Remove `{name}` storage field ({n_remaining_usages} remaining usage(s) need to be updated)."
+            )
+        },
+        kind,
+        range: field.syntax().text_range(),
+        edits,
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
+/// Returns the `struct` expressions for the ink! storage `struct` in the ink! contract
+/// (i.e. `StorageName { .. }` expressions and `Self { .. }` expressions in `impl` blocks for the ink! storage `struct`).
+fn storage_struct_exprs(contract: &Contract, struct_item: &ast::Struct) -> Vec<ast::RecordExpr> {
+    let (Some(module), Some(name)) = (contract.module(), struct_item.name()) else {
+        return Vec::new();
+    };
+    let name = name.to_string();
+    module
+        .syntax()
+        .descendants()
+        .filter_map(ast::RecordExpr::cast)
+        .filter(|record_expr| match record_expr.path() {
+            Some(path) if path.to_string() == name => true,
+            Some(path) if path.to_string() == "Self" => is_in_impl_for(record_expr.syntax(), &name),
+            _ => false,
+        })
+        .collect()
+}
+
+/// Returns the usages of the ink! storage field (i.e. `self.field` expressions in `impl` blocks
+/// for the ink! storage `struct`) in the ink! contract.
+fn storage_field_usages(
+    contract: &Contract,
+    struct_item: &ast::Struct,
+    field_name: &str,
+) -> Vec<ast::FieldExpr> {
+    let (Some(module), Some(name)) = (contract.module(), struct_item.name()) else {
+        return Vec::new();
+    };
+    let name = name.to_string();
+    module
+        .syntax()
+        .descendants()
+        .filter_map(ast::FieldExpr::cast)
+        .filter(|field_expr| {
+            matches!(field_expr.expr(), Some(ast::Expr::PathExpr(path_expr)) if path_expr.to_string() == "self")
+                && field_expr
+                    .name_ref()
+                    .map_or(false, |name_ref| name_ref.text() == field_name)
+                && is_in_impl_for(field_expr.syntax(), &name)
+        })
+        .collect()
+}
+
+/// Returns true if the closest `impl` block ancestor of the syntax node is an `impl` block for the named type.
+fn is_in_impl_for(node: &SyntaxNode, type_name: &str) -> bool {
+    node.ancestors()
+        .find_map(ast::Impl::cast)
+        .and_then(|impl_item| impl_item.self_ty())
+        .map_or(false, |self_ty| self_ty.to_string() == type_name)
+}
+
+/// Returns the node (if any) if it's a statement (or tail expression) that assigns to or mutates the named `self` field
+/// (e.g. `self.value = value;`, `self.count += 1;` or `self.values.push(value);`).
+fn mutation_stmt(node: &SyntaxNode, field_name: &str) -> Option<SyntaxNode> {
+    let expr = match ast::ExprStmt::cast(node.clone()) {
+        Some(expr_stmt) => expr_stmt.expr()?,
+        None => ast::Expr::cast(node.clone()).filter(|expr| {
+            expr.syntax()
+                .parent()
+                .and_then(ast::StmtList::cast)
+                .and_then(|stmt_list| stmt_list.tail_expr())
+                .as_ref()
+                == Some(expr)
+        })?,
+    };
+    let target = match &expr {
+        ast::Expr::BinExpr(bin_expr)
+            if matches!(bin_expr.op_kind(), Some(ast::BinaryOp::Assignment { .. })) =>
+        {
+            bin_expr.lhs()
+        }
+        ast::Expr::MethodCallExpr(method_call)
+            if method_call.name_ref().map_or(false, |name| {
+                utils::MUTATING_METHODS.contains(&name.text().as_str())
+            }) =>
+        {
+            method_call.receiver()
+        }
+        _ => None,
+    }?;
+    (utils::self_field_name(&target).as_deref() == Some(field_name)).then(|| node.clone())
+}
+
+/// Returns an edit for adding field initializers to a `struct` expression (if any)
+/// (i.e. unless the `struct` expression has a base expression e.g. `..Default::default()`).
+pub fn record_expr_fields_insert_edit(
+    record_expr: &ast::RecordExpr,
+    fields: &[String],
+) -> Option<TextEdit> {
+    let field_list = record_expr.record_expr_field_list()?;
+    if fields.is_empty() || field_list.dotdot_token().is_some() {
+        return None;
+    }
+    Some(match field_list.fields().last() {
+        // Inserts after the last field (i.e. before its trailing comma, if any).
+        Some(last_field) => {
+            let separator = if field_list.syntax().text().contains_char('\n') {
+                format!(
+                    ",\n{}",
+                    utils::item_indenting(last_field.syntax()).unwrap_or_default()
+                )
+            } else {
+                ", ".to_string()
+            };
+            TextEdit::insert(
+                format!("{separator}{}", fields.join(&separator)),
+                last_field.syntax().text_range().end(),
+            )
+        }
+        // Replaces empty field lists.
+        None => TextEdit::replace(
+            format!("{{ {} }}", fields.join(", ")),
+            field_list.syntax().text_range(),
+        ),
+    })
+}
+
+/// Returns the text range for removing a list item or statement
+/// (i.e. including its delimiter - if any - and its preceding whitespace if it's on its own line).
+fn removal_range(node: &SyntaxNode, delimiter: Option<SyntaxKind>) -> TextRange {
+    let range = delimiter.map_or(node.text_range(), |delimiter| {
+        utils::node_and_delimiter_range(node, delimiter)
+    });
+    let start = (range.start() == node.text_range().start())
+        .then(|| node.first_token().and_then(|token| token.prev_token()))
+        .flatten()
+        .filter(|token| token.kind() == SyntaxKind::WHITESPACE && token.text().contains('\n'))
+        .map_or(range.start(), |token| token.text_range().start());
+    TextRange::new(start, range.end())
+}
+
+/// Extracts ink! messages of an inherent ink! impl into a new ink! trait definition
+/// and an implementation of the ink! trait definition for the ink! contract.
+///
+/// Only ink! messages that overlap the given text range are extracted,
+/// unless the range doesn't overlap any ink! message (e.g. it's on the `impl` declaration),
+/// in which case all ink! messages are extracted.
+pub fn extract_trait_definition(
+    ink_impl: &InkImpl,
+    range: TextRange,
+    kind: ActionKind,
+) -> Option<Action> {
+    let impl_item = ink_impl.impl_item()?;
+    // Only inherent `impl` blocks can be extracted.
+    if impl_item.trait_().is_some() {
+        return None;
+    }
+    let contract = ink_analyzer_ir::ink_ancestors::<Contract>(ink_impl.syntax()).next()?;
+    let item_list = contract.module()?.item_list()?;
+    let self_ty = impl_item.self_ty()?;
+
+    // Determines the ink! messages to extract.
+    let fn_items: Vec<ast::Fn> = ink_impl
+        .messages()
+        .iter()
+        .filter_map(IsInkFn::fn_item)
+        .cloned()
+        .collect();
+    let selected_fns: Vec<&ast::Fn> = fn_items
+        .iter()
+        .filter(|fn_item| {
+            let fn_range = fn_item.syntax().text_range();
+            fn_range.contains_range(range)
+                || fn_range
+                    .intersect(range)
+                    .map_or(false, |intersection| !intersection.is_empty())
+        })
+        .collect();
+    let extracted_fns = if selected_fns.is_empty() {
+        fn_items.iter().collect()
+    } else {
+        selected_fns
+    };
+    if extracted_fns.is_empty() {
+        return None;
+    }
+
+    // Suggests a unique trait name based on the "resolved" contract name.
+    let trait_name = utils::suggest_unique_name(
+        &format!(
+            "{}Api",
+            utils::resolve_contract_name(&contract).unwrap_or(self_ty.to_string())
+        ),
+        &utils::item_names(&item_list),
+    );
+
+    // Composes the ink! trait definition messages (i.e. declarations with the docs and all ink! attributes)
+    // and their implementations (i.e. with only an ink! message attribute, because ink! messages
+    // in trait implementations can't have other ink! attribute arguments like `selector` or `payable`).
+    let mut declarations = Vec::new();
+    let mut implementations = Vec::new();
+    for fn_item in &extracted_fns {
+        let signature = fn_signature(fn_item)?;
+        let docs: String = fn_item
+            .doc_comments()
+            .map(|comment| format!("{comment}\n"))
+            .collect();
+        let ink_attrs: String = ink_analyzer_ir::ink_attrs(fn_item.syntax())
+            .map(|attr| format!("{}\n", attr.syntax()))
+            .collect();
+        declarations.push(format!("{docs}{ink_attrs}{signature};"));
+
+        // Removes the `fn`'s top-level indenting from the body.
+        let body = fn_item.body()?;
+        let body_text = utils::item_text(body.syntax());
+        let body_text = match utils::item_indenting(fn_item.syntax()) {
+            Some(fn_indent) => utils::reduce_indenting(&body_text, &fn_indent),
+            None => body_text,
+        };
+        implementations.push(format!("#[ink(message)]\n{signature} {body_text}"));
+    }
+    let indent_items = |items: &[String]| {
+        items
+            .iter()
+            .map(|item| utils::apply_indenting(item, "    "))
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    };
+    let insert_text = format!(
+        "#[ink::trait_definition]\npub trait {trait_name} {{\n{}\n}}\n\nimpl {trait_name} for {self_ty} {{\n{}\n}}",
+        indent_items(&declarations),
+        indent_items(&implementations),
+    );
+
+    Some(Action {
+        label: "Extract ink! message(s) into an ink! trait definition.".to_string(),
+        kind,
+        range: utils::ink_impl_declaration_range(ink_impl),
+        edits: extracted_fns
+            .iter()
+            // Removes the extracted ink! messages from the inherent `impl` block.
+            .map(|fn_item| TextEdit::delete(fn_item.syntax().text_range()))
+            // Adds the ink! trait definition and its implementation after the inherent `impl` block.
+            .chain([
+                EditBuilder::insert(insert_text, impl_item.syntax().text_range().end())
+                    .indent_as_sibling_of(impl_item.syntax())
+                    .build(),
+            ])
+            .collect(),
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
+/// Implements an ink! trait definition for the ink! contract in the same file
+/// (i.e. adds an `impl` block with stubs for all the trait definition's ink! messages).
+pub fn implement_trait_definition(
+    trait_definition: &TraitDefinition,
+    kind: ActionKind,
+) -> Option<Action> {
+    let trait_item = trait_definition.trait_item()?;
+    let trait_name = trait_item.name()?.to_string();
+    // Prefers the parent ink! contract (if any),
+    // otherwise defaults to the first ink! contract in the file.
+    let contract = ink_analyzer_ir::ink_ancestors::<Contract>(trait_item.syntax())
+        .next()
+        .or_else(|| {
+            let root = trait_item.syntax().ancestors().last()?;
+            ink_analyzer_ir::ink_closest_descendants::<Contract>(&root).next()
+        })?;
+    let module = contract.module()?;
+    let item_list = module.item_list()?;
+    let contract_name = utils::resolve_contract_name(&contract)?;
+
+    // Bails if the ink! contract already implements the trait.
+    let is_implemented = item_list.items().any(|item| match item {
+        ast::Item::Impl(impl_item) => impl_item.trait_().map_or(false, |trait_type| {
+            trait_type.to_string().rsplit("::").next() == Some(trait_name.as_str())
+        }),
+        _ => false,
+    });
+    if is_implemented {
+        return None;
+    }
+
+    // Composes ink! message stubs (i.e. with only an ink! message attribute, because ink! messages
+    // in trait implementations can't have other ink! attribute arguments like `selector` or `payable`).
+    let stubs = trait_definition
+        .messages()
+        .iter()
+        .filter_map(IsInkFn::fn_item)
+        .map(|fn_item| {
+            fn_signature(fn_item).map(|signature| {
+                utils::apply_indenting(
+                    &format!("#[ink(message)]\n{signature} {{\n    todo!()\n}}"),
+                    "    ",
+                )
+            })
+        })
+        .collect::<Option<Vec<String>>>()?;
+    let insert_text = format!(
+        "impl {trait_name} for {contract_name} {{\n{}\n}}",
+        stubs.join("\n\n")
+    );
+
+    Some(Action {
+        label: format!("Implement for contract `{contract_name}`."),
+        kind,
+        range: utils::ink_trait_declaration_range(trait_definition),
+        edits: vec![
+            EditBuilder::insert(insert_text, utils::item_insert_offset_impl(&item_list))
+                .indent_as_child_of(module.syntax())
+                .build(),
+        ],
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
+/// Returns the signature of a `fn` item (i.e. without qualifiers, visibility, `where` clause and body).
+fn fn_signature(fn_item: &ast::Fn) -> Option<String> {
+    Some(format!(
+        "fn {}{}{}{}",
+        fn_item.name()?,
+        fn_item
+            .generic_param_list()
+            .map(|it| it.to_string())
+            .unwrap_or_default(),
+        fn_item.param_list()?,
+        fn_item
+            .ret_type()
+            .map(|ret_type| format!(" {ret_type}"))
+            .unwrap_or_default()
+    ))
+}
+
 /// Adds an ink! message `fn` declaration to an ink! trait definition `trait` item.
 pub fn add_message_to_trait_definition(
     trait_definition: &TraitDefinition,
@@ -332,18 +1103,30 @@ pub fn add_message_to_trait_definition(
                 .as_ref()
                 .map(utils::assoc_item_insert_offset_end))
             .map(|insert_offset| {
-                // Sets insert indent.
-                let indent = utils::item_children_indenting(trait_item.syntax());
+                // Suggests a unique message name.
+                let (plain, snippet) = with_unique_name(
+                    TRAIT_MESSAGE_PLAIN,
+                    TRAIT_MESSAGE_SNIPPET,
+                    "message",
+                    "message",
+                    &trait_item
+                        .assoc_item_list()
+                        .as_ref()
+                        .map(utils::assoc_fn_names)
+                        .unwrap_or_default(),
+                );
 
                 Action {
                     label: "Add ink! message `fn`.".to_string(),
                     kind,
                     range: utils::ink_trait_declaration_range(trait_definition),
-                    edits: vec![TextEdit::insert_with_snippet(
-                        utils::apply_indenting(TRAIT_MESSAGE_PLAIN, &indent),
-                        insert_offset,
-                        Some(utils::apply_indenting(TRAIT_MESSAGE_SNIPPET, &indent)),
-                    )],
+                    edits: vec![EditBuilder::insert(plain, insert_offset)
+                        .snippet(snippet)
+                        .indent_as_child_of(trait_item.syntax())
+                        .build()],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }
             })
     })
@@ -362,20 +1145,17 @@ pub fn add_error_code(
                 .assoc_item_list()
                 .as_ref()
                 .map(utils::assoc_item_insert_offset_start))
-            .map(|insert_offset| {
-                // Sets insert indent.
-                let indent = utils::item_children_indenting(trait_item.syntax());
-
-                Action {
-                    label: "Add `ErrorCode` type for ink! chain extension.".to_string(),
-                    kind,
-                    range: utils::ink_trait_declaration_range(chain_extension),
-                    edits: vec![TextEdit::insert_with_snippet(
-                        utils::apply_indenting(ERROR_CODE_PLAIN, &indent),
-                        insert_offset,
-                        Some(utils::apply_indenting(ERROR_CODE_SNIPPET, &indent)),
-                    )],
-                }
+            .map(|insert_offset| Action {
+                label: "Add `ErrorCode` type for ink! chain extension.".to_string(),
+                kind,
+                range: utils::ink_trait_declaration_range(chain_extension),
+                edits: vec![EditBuilder::insert(ERROR_CODE_PLAIN, insert_offset)
+                    .snippet(ERROR_CODE_SNIPPET)
+                    .indent_as_child_of(trait_item.syntax())
+                    .build()],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             })
     })
 }
@@ -394,23 +1174,68 @@ pub fn add_extension(
                 .as_ref()
                 .map(utils::assoc_item_insert_offset_end))
             .map(|insert_offset| {
-                // Sets insert indent.
-                let indent = utils::item_children_indenting(trait_item.syntax());
+                // Suggests a unique extension name.
+                let (plain, snippet) = with_unique_name(
+                    EXTENSION_PLAIN,
+                    EXTENSION_SNIPPET,
+                    "extension",
+                    "extension",
+                    &trait_item
+                        .assoc_item_list()
+                        .as_ref()
+                        .map(utils::assoc_fn_names)
+                        .unwrap_or_default(),
+                );
 
                 Action {
                     label: "Add ink! extension `fn`.".to_string(),
                     kind,
                     range: utils::ink_trait_declaration_range(chain_extension),
-                    edits: vec![TextEdit::insert_with_snippet(
-                        utils::apply_indenting(EXTENSION_PLAIN, &indent),
-                        insert_offset,
-                        Some(utils::apply_indenting(EXTENSION_SNIPPET, &indent)),
-                    )],
+                    edits: vec![EditBuilder::insert(plain, insert_offset)
+                        .snippet(snippet)
+                        .indent_as_child_of(trait_item.syntax())
+                        .build()],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }
             })
     })
 }
 
+/// Renumbers the ids of the ink! extensions of an ink! chain extension sequentially (starting from 1),
+/// while keeping the relative order of the current ids.
+///
+/// ink! extensions with missing or invalid ids are numbered last (in source order).
+pub fn renumber_extensions(chain_extension: &ChainExtension, kind: ActionKind) -> Option<Action> {
+    let edits: Vec<TextEdit> = chain_extension
+        .extensions()
+        .iter()
+        .filter_map(|extension| Some((extension.id(), extension.extension_arg()?)))
+        .enumerate()
+        // Duplicate ids keep their source order.
+        .sorted_by_key(|(idx, (id, _))| (id.is_none(), *id, *idx))
+        .zip(1u32..)
+        .filter_map(|((_, (id, arg)), new_id)| {
+            (id != Some(new_id)).then(|| match arg.value() {
+                Some(value) => TextEdit::replace(new_id.to_string(), value.text_range()),
+                None => TextEdit::replace(format!("extension = {new_id}"), arg.text_range()),
+            })
+        })
+        .sorted_by_key(|edit| edit.range.start())
+        .collect();
+
+    (!edits.is_empty()).then(|| Action {
+        label: "Renumber ink! extensions sequentially.".to_string(),
+        kind,
+        range: utils::ink_trait_declaration_range(chain_extension),
+        edits,
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
 /// Adds an ink! test `fn` to a `mod` item.
 pub fn add_ink_test(
     module: &ast::Module,
@@ -424,19 +1249,31 @@ pub fn add_ink_test(
             .as_ref()
             .map(utils::item_insert_offset_end))
         .map(|insert_offset| {
-            // Sets insert indent.
-            let indent = utils::item_children_indenting(module.syntax());
+            // Suggests a unique test name.
+            let (plain, snippet) = with_unique_name(
+                INK_TEST_PLAIN,
+                INK_TEST_SNIPPET,
+                "it_works",
+                "it_works",
+                &module
+                    .item_list()
+                    .as_ref()
+                    .map(utils::item_names)
+                    .unwrap_or_default(),
+            );
 
             Action {
                 label: "Add ink! test `fn`.".to_string(),
                 kind,
                 range: utils::ast_item_declaration_range(&ast::Item::Module(module.clone()))
                     .unwrap_or(module.syntax().text_range()),
-                edits: vec![TextEdit::insert_with_snippet(
-                    utils::apply_indenting(INK_TEST_PLAIN, &indent),
-                    insert_offset,
-                    Some(utils::apply_indenting(INK_TEST_SNIPPET, &indent)),
-                )],
+                edits: vec![EditBuilder::insert(plain, insert_offset)
+                    .snippet(snippet)
+                    .indent_as_child_of(module.syntax())
+                    .build()],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }
         })
 }
@@ -454,21 +1291,479 @@ pub fn add_ink_e2e_test(
             .as_ref()
             .map(utils::item_insert_offset_end))
         .map(|insert_offset| {
-            // Sets insert indent.
-            let indent = utils::item_children_indenting(module.syntax());
+            // Suggests a unique test name.
+            let (plain, snippet) = with_unique_name(
+                INK_E2E_TEST_PLAIN,
+                INK_E2E_TEST_SNIPPET,
+                "it_works",
+                "it_works",
+                &module
+                    .item_list()
+                    .as_ref()
+                    .map(utils::item_names)
+                    .unwrap_or_default(),
+            );
 
             Action {
                 label: "Add ink! e2e test `fn`.".to_string(),
                 kind,
                 range: utils::ast_item_declaration_range(&ast::Item::Module(module.clone()))
                     .unwrap_or(module.syntax().text_range()),
-                edits: vec![TextEdit::insert_with_snippet(
-                    utils::apply_indenting(INK_E2E_TEST_PLAIN, &indent),
-                    insert_offset,
-                    Some(utils::apply_indenting(INK_E2E_TEST_SNIPPET, &indent)),
-                )],
+                edits: vec![EditBuilder::insert(plain, insert_offset)
+                    .snippet(snippet)
+                    .indent_as_child_of(module.syntax())
+                    .build()],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
+            }
+        })
+}
+
+/// Adds an ink! e2e tests `mod` to an ink! contract `mod` item
+/// (i.e. with an ink! e2e test for each ink! constructor that instantiates the contract
+/// and calls each ink! message).
+pub fn add_e2e_tests_module(
+    contract: &Contract,
+    kind: ActionKind,
+    insert_offset_option: Option<TextSize>,
+) -> Option<Action> {
+    let module = contract.module()?;
+    let item_list = module.item_list()?;
+    // Bails if the ink! contract already has an ink! e2e tests `mod`.
+    let has_e2e_tests = item_list.items().any(|item| match item {
+        ast::Item::Module(module) => module
+            .attrs()
+            .any(|attr| utils::is_cfg_e2e_tests_attr(&attr)),
+        _ => false,
+    });
+    if has_e2e_tests || contract.constructors().is_empty() {
+        return None;
+    }
+    let contract_name = utils::resolve_contract_name(contract)?;
+    let contract_ref = format!("{contract_name}Ref");
+    let module_name = module.name()?.to_string();
+
+    // Returns the name and placeholder arguments of an ink! callable.
+    let name_and_args = |fn_item: &ast::Fn| -> Option<(String, String)> {
+        let args = fn_item
+            .param_list()
+            .map(|param_list| {
+                param_list
+                    .params()
+                    .map(|_| "Default::default()")
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        Some((fn_item.name()?.to_string(), args))
+    };
+
+    // Composes calls for all ink! messages
+    // (i.e. dry-run calls for immutable messages and calls for mutable messages).
+    let message_calls: Vec<String> = contract
+        .messages()
+        .iter()
+        .filter_map(IsInkFn::fn_item)
+        .filter_map(|fn_item| {
+            let (name, args) = name_and_args(fn_item)?;
+            let is_mut = fn_item
+                .param_list()
+                .and_then(|param_list| param_list.self_param())
+                .map_or(false, |self_param| self_param.mut_token().is_some());
+            let call = if is_mut {
+                format!(
+                    "client\n    .call(&ink_e2e::alice(), {name}_message, 0, None)\n    .await\n    .expect(\"`{name}` failed\")"
+                )
+            } else {
+                format!("client\n    .call_dry_run(&ink_e2e::alice(), &{name}_message, 0, None)\n    .await")
+            };
+            Some(format!(
+                "// Calls `{name}`.\nlet {name}_message = build_message::<{contract_ref}>(contract_account_id.clone())\n    .call(|contract| contract.{name}({args}));\nlet _{name}_result = {call};"
+            ))
+        })
+        .collect();
+
+    // Composes an ink! e2e test for each ink! constructor.
+    let mut test_names = HashSet::new();
+    let tests: Vec<String> = contract
+        .constructors()
+        .iter()
+        .filter_map(IsInkFn::fn_item)
+        .filter_map(|fn_item| {
+            let (name, args) = name_and_args(fn_item)?;
+            let test_name = utils::suggest_unique_name(&format!("{name}_works"), &test_names);
+            test_names.insert(test_name.clone());
+            let body = [format!(
+                "// Instantiates the contract.\nlet constructor = {contract_ref}::{name}({args});\nlet contract_account_id = client\n    .instantiate(\"{module_name}\", &ink_e2e::alice(), constructor, 0, None)\n    .await\n    .expect(\"instantiate failed\")\n    .account_id;"
+            )]
+            .into_iter()
+            .chain(message_calls.iter().cloned())
+            .chain(["Ok(())".to_string()])
+            .map(|stmt| utils::apply_indenting(&stmt, "    "))
+            .collect::<Vec<String>>()
+            .join("\n\n");
+            Some(format!(
+                "#[ink_e2e::test]\nasync fn {test_name}(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {{\n{body}\n}}"
+            ))
+        })
+        .collect();
+    if tests.is_empty() {
+        return None;
+    }
+
+    // Suggests a unique `mod` name.
+    let e2e_module_name = utils::suggest_unique_name("e2e_tests", &utils::item_names(&item_list));
+    let module_body = [
+        "use super::*;\nuse ink_e2e::build_message;".to_string(),
+        "type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;".to_string(),
+    ]
+    .into_iter()
+    .chain(tests)
+    .map(|item| utils::apply_indenting(&item, "    "))
+    .collect::<Vec<String>>()
+    .join("\n\n");
+    let insert_text = format!(
+        "#[cfg(all(test, feature = \"e2e-tests\"))]\nmod {e2e_module_name} {{\n{module_body}\n}}"
+    );
+
+    Some(Action {
+        label: "Add ink! e2e tests `mod`.".to_string(),
+        kind,
+        range: utils::contract_declaration_range(contract),
+        edits: vec![EditBuilder::insert(
+            insert_text,
+            // Sets insert offset or defaults to inserting at the end of the associated items list.
+            insert_offset_option.unwrap_or(utils::item_insert_offset_end(&item_list)),
+        )
+        .indent_as_child_of(module.syntax())
+        .build()],
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
+/// Adds a unit tests `mod` to an ink! contract `mod` item
+/// (i.e. with an ink! test for each ink! constructor and ink! message,
+/// with `let` statements for the arguments based on the real signatures of the callables).
+pub fn add_unit_tests_module(
+    contract: &Contract,
+    kind: ActionKind,
+    insert_offset_option: Option<TextSize>,
+) -> Option<Action> {
+    let module = contract.module()?;
+    let item_list = module.item_list()?;
+    // Bails if the ink! contract already has a unit tests `mod`.
+    let has_unit_tests = item_list.items().any(|item| match item {
+        ast::Item::Module(module) => module
+            .attrs()
+            .any(|attr| utils::is_cfg_test_attr(&attr) && !utils::is_cfg_e2e_tests_attr(&attr)),
+        _ => false,
+    });
+    if has_unit_tests {
+        return None;
+    }
+    let contract_name = utils::resolve_contract_name(contract)?;
+    // Uses the first ink! constructor to instantiate the contract.
+    let constructor_fn = contract.constructors().iter().find_map(IsInkFn::fn_item)?;
+
+    // Returns the name, argument `let` statements and arguments of an ink! callable.
+    let callable_parts = |fn_item: &ast::Fn| -> Option<(String, Vec<String>, String)> {
+        let (lets, args): (Vec<String>, Vec<String>) = fn_item
+            .param_list()
+            .into_iter()
+            .flat_map(|param_list| param_list.params())
+            .enumerate()
+            .map(|(idx, param)| {
+                let arg_name = match param.pat() {
+                    Some(ast::Pat::IdentPat(ident_pat)) => ident_pat
+                        .name()
+                        .map(|name| name.to_string().trim_start_matches('_').to_string())
+                        .filter(|name| !name.is_empty()),
+                    _ => None,
+                }
+                .unwrap_or(format!("arg{}", idx + 1));
+                let ty = param.ty().map(|ty| format!(": {ty}")).unwrap_or_default();
+                (
+                    format!("let {arg_name}{ty} = Default::default();"),
+                    arg_name,
+                )
+            })
+            .unzip();
+        Some((fn_item.name()?.to_string(), lets, args.join(", ")))
+    };
+    let (constructor_name, constructor_lets, constructor_args) = callable_parts(constructor_fn)?;
+    let accounts_setup = "let accounts = ink::env::test::default_accounts::<Environment>();\n\
+    ink::env::test::set_caller::<Environment>(accounts.alice);";
+
+    // Composes an ink! test for each ink! constructor and ink! message.
+    let mut test_names = HashSet::new();
+    let mut compose_test = |name: &str, stmts: Vec<String>| -> String {
+        let test_name = utils::suggest_unique_name(&format!("{name}_works"), &test_names);
+        test_names.insert(test_name.clone());
+        let body = utils::apply_indenting(&stmts.join("\n"), "    ");
+        format!("#[ink::test]\nfn {test_name}() {{\n{body}\n}}")
+    };
+    let mut tests = Vec::new();
+    for fn_item in contract.constructors().iter().filter_map(IsInkFn::fn_item) {
+        if let Some((name, lets, args)) = callable_parts(fn_item) {
+            let stmts = [accounts_setup.to_string(), String::new()]
+                .into_iter()
+                .chain(lets)
+                .chain([format!("let _contract = {contract_name}::{name}({args});")])
+                .collect();
+            tests.push(compose_test(&name, stmts));
+        }
+    }
+    for fn_item in contract.messages().iter().filter_map(IsInkFn::fn_item) {
+        if let Some((name, lets, args)) = callable_parts(fn_item) {
+            let is_mut = fn_item
+                .param_list()
+                .and_then(|param_list| param_list.self_param())
+                .map_or(false, |self_param| self_param.mut_token().is_some());
+            let call = format!("contract.{name}({args})");
+            let stmts = [accounts_setup.to_string(), String::new()]
+                .into_iter()
+                .chain(constructor_lets.iter().cloned())
+                .chain([format!(
+                    "let {}contract = {contract_name}::{constructor_name}({constructor_args});",
+                    if is_mut { "mut " } else { "" }
+                )])
+                .chain(lets)
+                .chain([if fn_item.ret_type().is_some() {
+                    format!("let _result = {call};")
+                } else {
+                    format!("{call};")
+                }])
+                .collect();
+            tests.push(compose_test(&name, stmts));
+        }
+    }
+
+    // Suggests a unique `mod` name.
+    let tests_module_name = utils::suggest_unique_name("tests", &utils::item_names(&item_list));
+    let module_body = ["use super::*;".to_string()]
+        .into_iter()
+        .chain(tests)
+        .map(|item| utils::apply_indenting(&item, "    "))
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    let insert_text = format!("#[cfg(test)]\nmod {tests_module_name} {{\n{module_body}\n}}");
+
+    Some(Action {
+        label: "Add ink! unit tests `mod`.".to_string(),
+        kind,
+        range: utils::contract_declaration_range(contract),
+        edits: vec![EditBuilder::insert(
+            insert_text,
+            // Sets insert offset or defaults to inserting at the end of the associated items list.
+            insert_offset_option.unwrap_or(utils::item_insert_offset_end(&item_list)),
+        )
+        .indent_as_child_of(module.syntax())
+        .build()],
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
+/// Reorders the items of an ink! contract `mod` into the conventional order
+/// (i.e. ink! storage, ink! events, ink! `impl` blocks - with ink! constructors before ink! messages - and tests `mod`s).
+///
+/// NOTE: Only ink! entities and tests `mod`s are reordered (i.e. all other items keep their positions),
+/// and comments preceding an item are moved together with the item.
+pub fn organize_contract(contract: &Contract, kind: ActionKind) -> Option<Action> {
+    let module = contract.module()?;
+    let item_list = module.item_list()?;
+    let root_text = module.syntax().ancestors().last()?.to_string();
+    let ink_impl_ranges: HashSet<TextRange> = contract
+        .impls()
+        .iter()
+        .filter_map(InkImpl::impl_item)
+        .map(|impl_item| impl_item.syntax().text_range())
+        .collect();
+
+    // Ranks items in the conventional order.
+    let blocks: Vec<(TextRange, String, u8)> = item_list
+        .items()
+        .filter_map(|item| {
+            let rank = match &item {
+                ast::Item::Struct(_) => {
+                    ink_analyzer_ir::ink_attrs(item.syntax()).find_map(|attr| match attr.kind() {
+                        InkAttributeKind::Arg(InkArgKind::Storage) => Some(0),
+                        InkAttributeKind::Arg(InkArgKind::Event) => Some(1),
+                        _ => None,
+                    })
+                }
+                ast::Item::Impl(impl_item) => ink_impl_ranges
+                    .contains(&impl_item.syntax().text_range())
+                    .then_some(2),
+                ast::Item::Module(mod_item) => mod_item
+                    .attrs()
+                    .any(|attr| utils::is_cfg_test_attr(&attr))
+                    .then_some(3),
+                _ => None,
+            }?;
+            let range = item_block_range(item.syntax());
+            let mut text = root_text[Range::<usize>::from(range)].to_string();
+
+            // Reorders ink! constructors and ink! messages in ink! `impl` blocks.
+            if let ast::Item::Impl(impl_item) = &item {
+                let callable_blocks: Vec<(TextRange, String, u8)> = impl_item
+                    .assoc_item_list()
+                    .map(|assoc_item_list| {
+                        assoc_item_list
+                            .assoc_items()
+                            .filter_map(|assoc_item| {
+                                let rank = ink_analyzer_ir::ink_attrs(assoc_item.syntax())
+                                    .find_map(|attr| match attr.kind() {
+                                        InkAttributeKind::Arg(InkArgKind::Constructor) => Some(0),
+                                        InkAttributeKind::Arg(InkArgKind::Message) => Some(1),
+                                        _ => None,
+                                    })?;
+                                let range = item_block_range(assoc_item.syntax());
+                                let text = root_text[Range::<usize>::from(range)].to_string();
+                                Some((range, text, rank))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                // Applies edits from last to first, so that the offsets of preceding edits remain valid.
+                for edit in reorder_edits(&root_text, callable_blocks).into_iter().rev() {
+                    text.replace_range(
+                        Range::<usize>::from(edit.range - range.start()),
+                        &edit.text,
+                    );
+                }
             }
+
+            Some((range, text, rank))
         })
+        .collect();
+
+    let edits = reorder_edits(&root_text, blocks);
+    (!edits.is_empty()).then(|| Action {
+        label: "Organize ink! contract items.".to_string(),
+        kind,
+        range: utils::contract_declaration_range(contract),
+        edits,
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
+    })
+}
+
+/// Returns replace edits for reordering ranked blocks of text (i.e. a text range, its text and its rank)
+/// by rank (i.e. the text range of each block is replaced with the text of the block that should be in its position).
+fn reorder_edits(root_text: &str, blocks: Vec<(TextRange, String, u8)>) -> Vec<TextEdit> {
+    let sorted_texts: Vec<&String> = blocks
+        .iter()
+        .sorted_by_key(|(_, _, rank)| *rank)
+        .map(|(_, text, _)| text)
+        .collect();
+    blocks
+        .iter()
+        .zip(sorted_texts)
+        .filter(|((range, _, _), text)| root_text[Range::<usize>::from(*range)] != ***text)
+        .map(|((range, _, _), text)| TextEdit::replace(text.to_string(), *range))
+        .collect()
+}
+
+/// Returns the text range of an item including its preceding comments
+/// (i.e. excluding trailing comments of the preceding element).
+fn item_block_range(node: &SyntaxNode) -> TextRange {
+    let mut start = node.text_range().start();
+    let mut elem_option = node.prev_sibling_or_token();
+    while let Some(elem) = elem_option {
+        match elem.kind() {
+            SyntaxKind::WHITESPACE => (),
+            SyntaxKind::COMMENT => {
+                // Trailing comments are on the same line as the preceding element.
+                let is_trailing = elem.prev_sibling_or_token().map_or(false, |prev| {
+                    prev.kind() != SyntaxKind::WHITESPACE || !prev.to_string().contains('\n')
+                });
+                if is_trailing {
+                    break;
+                }
+                start = elem.text_range().start();
+            }
+            _ => break,
+        }
+        elem_option = elem.prev_sibling_or_token();
+    }
+    TextRange::new(start, node.text_range().end())
+}
+
+/// Returns the names of associated `fn`s in all `impl` blocks of an ink! contract.
+fn contract_fn_names(contract: &Contract) -> HashSet<String> {
+    contract
+        .module()
+        .and_then(ast::Module::item_list)
+        .as_ref()
+        .map(utils::impl_fn_names)
+        .unwrap_or_default()
+}
+
+/// Returns the names of associated `fn`s in an `impl` block and its sibling `impl` blocks (if any).
+fn impl_fn_names(impl_item: &ast::Impl) -> HashSet<String> {
+    let mut names = impl_item
+        .assoc_item_list()
+        .as_ref()
+        .map(utils::assoc_fn_names)
+        .unwrap_or_default();
+    if let Some(item_list) = impl_item.syntax().parent().and_then(ast::ItemList::cast) {
+        names.extend(utils::impl_fn_names(&item_list));
+    }
+    names
+}
+
+/// Returns the plain text and snippet for an ink! entity with its default name
+/// (e.g. `message` in `MESSAGE_PLAIN` and `MESSAGE_SNIPPET`) replaced by a unique name
+/// based on the preferred name (e.g. `message2` if `message` is unavailable).
+fn with_unique_name(
+    plain: &str,
+    snippet: &str,
+    default_name: &str,
+    preferred_name: &str,
+    unavailable_names: &HashSet<String>,
+) -> (String, String) {
+    let name = utils::suggest_unique_name(preferred_name, unavailable_names);
+    (
+        rename_entity(plain, default_name, &name),
+        rename_entity(snippet, default_name, &name),
+    )
+}
+
+/// Replaces all whole word occurrences of the default name with the new name in an ink! entity's code
+/// (except for attributes, e.g. `message` in `#[ink(message)]`).
+fn rename_entity(code: &str, default_name: &str, name: &str) -> String {
+    if default_name == name {
+        return code.to_string();
+    }
+    let is_ident_char = |char: char| char.is_alphanumeric() || char == '_';
+    code.split('\n')
+        .map(|line| {
+            if line.trim_start().starts_with("#[") {
+                return line.to_string();
+            }
+            let mut result = String::new();
+            let mut last_end = 0;
+            for (start, _) in line.match_indices(default_name) {
+                let end = start + default_name.len();
+                let is_whole_word = !line[..start].ends_with(is_ident_char)
+                    && !line[end..].starts_with(is_ident_char);
+                if is_whole_word {
+                    result.push_str(&line[last_end..start]);
+                    result.push_str(name);
+                    last_end = end;
+                }
+            }
+            result.push_str(&line[last_end..]);
+            result
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
 /// Creates an insert edit with a snippet and indenting.
@@ -478,17 +1773,14 @@ fn insert_edit_with_snippet_and_indent(
     snippet_option: Option<&str>,
     indent_option: Option<&str>,
 ) -> TextEdit {
-    TextEdit::insert_with_snippet(
-        match indent_option {
-            Some(indent) => utils::apply_indenting(text, indent),
-            None => text.to_string(),
-        },
-        offset,
-        snippet_option.map(|snippet| match indent_option {
-            Some(indent) => utils::apply_indenting(snippet, indent),
-            None => snippet.to_string(),
-        }),
-    )
+    let mut builder = EditBuilder::insert(text, offset);
+    if let Some(snippet) = snippet_option {
+        builder = builder.snippet(snippet);
+    }
+    if let Some(indent) = indent_option {
+        builder = builder.indent(indent);
+    }
+    builder.build()
 }
 
 /// Add an ink! contract `mod`.
@@ -503,6 +1795,9 @@ pub fn add_contract(offset: TextSize, kind: ActionKind, indent_option: Option<&s
             Some(CONTRACT_SNIPPET),
             indent_option,
         )],
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
     }
 }
 
@@ -522,6 +1817,9 @@ pub fn add_trait_definition(
             Some(TRAIT_DEFINITION_SNIPPET),
             indent_option,
         )],
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
     }
 }
 
@@ -541,6 +1839,9 @@ pub fn add_chain_extension(
             Some(CHAIN_EXTENSION_SNIPPET),
             indent_option,
         )],
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
     }
 }
 
@@ -556,5 +1857,8 @@ pub fn add_storage_item(offset: TextSize, kind: ActionKind, indent_option: Optio
             Some(STORAGE_ITEM_SNIPPET),
             indent_option,
         )],
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
     }
 }
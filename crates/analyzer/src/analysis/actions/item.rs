@@ -3,18 +3,21 @@
 use ink_analyzer_ir::ast::HasAttrs;
 use ink_analyzer_ir::syntax::{AstNode, SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TextSize};
 use ink_analyzer_ir::{
-    ast, ChainExtension, Contract, Event, FromInkAttribute, FromSyntax, InkArgKind, InkAttribute,
-    InkAttributeKind, InkFile, InkImpl, InkMacroKind, TraitDefinition,
+    ast, ChainExtension, Contract, Event, FromAST, FromInkAttribute, FromSyntax, InkArgKind,
+    InkAttribute, InkAttributeKind, InkFile, InkImpl, InkMacroKind, Message, Storage,
+    TraitDefinition,
 };
 use itertools::Itertools;
+use std::collections::HashSet;
 
 use super::entity;
 use super::{Action, ActionKind};
+use crate::analysis::queries::Queries;
 use crate::analysis::utils;
 use crate::TextEdit;
 
 /// Computes AST item-based ink! attribute actions at the given text range.
-pub fn actions(results: &mut Vec<Action>, file: &InkFile, range: TextRange) {
+pub fn actions(results: &mut Vec<Action>, file: &InkFile, queries: &Queries, range: TextRange) {
     match utils::focused_element(file, range) {
         // Computes actions based on focused element (if it can be determined).
         Some(focused_elem) => {
@@ -68,8 +71,21 @@ pub fn actions(results: &mut Vec<Action>, file: &InkFile, range: TextRange) {
                             // Suggests ink! attribute arguments based on the context.
                             ink_arg_actions(results, target, item_declaration_text_range);
 
-                            // Suggests actions for "flattening" ink! attributes (if any).
+                            // Suggests actions for "flattening" ink! attributes (if any),
+                            // otherwise suggests actions for normalizing ink! attributes (if any)
+                            // (flattening already sorts and formats ink! attribute arguments).
+                            let n_results = results.len();
                             flatten_attrs(results, target, item_declaration_text_range);
+                            if results.len() == n_results {
+                                normalize_attrs(results, target, item_declaration_text_range);
+                            }
+
+                            // Suggests removing the ink! storage field (if any).
+                            if let Some(action) = record_field.as_ref().and_then(|field| {
+                                entity::remove_storage_field(field, ActionKind::Refactor)
+                            }) {
+                                results.push(action);
+                            }
                         }
 
                         // Only computes ink! entity actions if the focus is on either
@@ -84,6 +100,7 @@ pub fn actions(results: &mut Vec<Action>, file: &InkFile, range: TextRange) {
                                 results,
                                 &ast_item,
                                 is_focused_on_body.then_some(focused_elem_insert_offset()),
+                                range,
                             );
                         }
                     }
@@ -94,7 +111,12 @@ pub fn actions(results: &mut Vec<Action>, file: &InkFile, range: TextRange) {
                             .map_or(false, |it| it.kind() == SyntaxKind::SOURCE_FILE);
                         if is_in_file_root {
                             // Suggests root-level ink! entities based on the context.
-                            root_ink_entity_actions(results, file, focused_elem_insert_offset());
+                            root_ink_entity_actions(
+                                results,
+                                file,
+                                queries,
+                                focused_elem_insert_offset(),
+                            );
                         }
                     }
                 }
@@ -105,7 +127,7 @@ pub fn actions(results: &mut Vec<Action>, file: &InkFile, range: TextRange) {
             if file.syntax().text_range().is_empty()
                 && file.syntax().text_range().contains_range(range)
             {
-                root_ink_entity_actions(results, file, range.end());
+                root_ink_entity_actions(results, file, queries, range.end());
             }
         }
     }
@@ -143,6 +165,9 @@ fn ink_macro_actions(results: &mut Vec<Action>, target: &SyntaxNode, range: Text
                         format!("#[{}]", macro_kind.path_as_str(),),
                         insert_offset,
                     )],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 });
             }
         }
@@ -253,6 +278,9 @@ fn ink_arg_actions(results: &mut Vec<Action>, target: &SyntaxNode, range: TextRa
                         )
                     }),
                 )],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             });
         }
     }
@@ -263,6 +291,7 @@ fn item_ink_entity_actions(
     results: &mut Vec<Action>,
     item: &ast::Item,
     insert_offset_option: Option<TextSize>,
+    range: TextRange,
 ) {
     let mut add_result = |action_option: Option<Action>| {
         // Add action to accumulator (if any).
@@ -306,6 +335,23 @@ fn item_ink_entity_actions(
                         ActionKind::Refactor,
                         insert_offset_option,
                     ));
+
+                    // Adds ink! e2e tests `mod` (if it doesn't exist).
+                    add_result(entity::add_e2e_tests_module(
+                        &contract,
+                        ActionKind::Refactor,
+                        insert_offset_option,
+                    ));
+
+                    // Adds unit tests `mod` (if it doesn't exist).
+                    add_result(entity::add_unit_tests_module(
+                        &contract,
+                        ActionKind::Refactor,
+                        insert_offset_option,
+                    ));
+
+                    // Reorders ink! contract items (if necessary).
+                    add_result(entity::organize_contract(&contract, ActionKind::Source));
                 }
                 None => {
                     let is_cfg_test = module.attrs().any(|attr| utils::is_cfg_test_attr(&attr));
@@ -336,10 +382,10 @@ fn item_ink_entity_actions(
             // Only computes ink! entities if impl item is not a trait `impl` and additionally either:
             // - has an ink! `impl` attribute.
             // - contains at least one ink! constructor or ink! message.
-            // - has an ink! contract as the direct parent.
+            // - has an ink! contract as the direct parent (or via non-ink! inline child `mod` items).
             if impl_item.trait_().is_none()
                 && (InkImpl::can_cast(impl_item.syntax())
-                    || ink_analyzer_ir::ink_parent::<Contract>(impl_item.syntax()).is_some())
+                    || utils::parent_contract(impl_item.syntax()).is_some())
             {
                 // Adds ink! constructor.
                 add_result(entity::add_constructor_to_impl(
@@ -355,6 +401,22 @@ fn item_ink_entity_actions(
                     insert_offset_option,
                 ));
             }
+
+            if let Some(ink_impl) = InkImpl::cast(impl_item.syntax().clone()) {
+                // Extracts ink! messages into an ink! trait definition.
+                add_result(entity::extract_trait_definition(
+                    &ink_impl,
+                    range,
+                    ActionKind::RefactorExtract,
+                ));
+
+                // Pins derived selectors if the ink! impl either has an ink! namespace argument or is a trait implementation
+                // (i.e. selectors depend on the namespace or trait path).
+                add_result(entity::pin_selectors(
+                    &ink_impl,
+                    ActionKind::RefactorRewrite,
+                ));
+            }
         }
         ast::Item::Trait(trait_item) => {
             if let Some((attr, _)) = utils::primary_ink_attribute_candidate(
@@ -379,6 +441,12 @@ fn item_ink_entity_actions(
                                     ActionKind::Refactor,
                                     insert_offset_option,
                                 ));
+
+                                // Renumbers ink! extension ids sequentially (if necessary).
+                                add_result(entity::renumber_extensions(
+                                    &chain_extension,
+                                    ActionKind::RefactorRewrite,
+                                ));
                             }
                         }
                         InkMacroKind::TraitDefinition => {
@@ -389,6 +457,12 @@ fn item_ink_entity_actions(
                                     ActionKind::Refactor,
                                     insert_offset_option,
                                 ));
+
+                                // Implements the ink! trait definition for the ink! contract (if any).
+                                add_result(entity::implement_trait_definition(
+                                    &trait_definition,
+                                    ActionKind::Refactor,
+                                ));
                             }
                         }
                         // Ignores other macros.
@@ -397,6 +471,28 @@ fn item_ink_entity_actions(
                 }
             }
         }
+        ast::Item::Fn(fn_item) => {
+            if let Some(message) =
+                ink_analyzer_ir::ink_attrs(fn_item.syntax()).find_map(Message::cast)
+            {
+                // Adds and emits an ink! event for storage changes (if any).
+                add_result(entity::emit_event_for_storage_changes(
+                    &message,
+                    ActionKind::Refactor,
+                ));
+
+                // Extracts the ink! message into an ink! trait definition.
+                if let Some(ink_impl) = ink_analyzer_ir::parent_ast_item(fn_item.syntax())
+                    .and_then(|item| InkImpl::cast(item.syntax().clone()))
+                {
+                    add_result(entity::extract_trait_definition(
+                        &ink_impl,
+                        range,
+                        ActionKind::RefactorExtract,
+                    ));
+                }
+            }
+        }
         ast::Item::Struct(struct_item) => {
             if let Some(event) = ink_analyzer_ir::ink_attrs(struct_item.syntax())
                 .find(|attr| *attr.kind() == InkAttributeKind::Arg(InkArgKind::Event))
@@ -409,6 +505,13 @@ fn item_ink_entity_actions(
                     insert_offset_option,
                 ));
             }
+            if let Some(storage) = ink_analyzer_ir::ink_attrs(struct_item.syntax())
+                .find(|attr| *attr.kind() == InkAttributeKind::Arg(InkArgKind::Storage))
+                .and_then(Storage::cast)
+            {
+                // Adds ink! storage field.
+                add_result(entity::add_storage_field(&storage, ActionKind::Refactor));
+            }
         }
         // Ignores other items.
         _ => (),
@@ -416,8 +519,13 @@ fn item_ink_entity_actions(
 }
 
 /// Computes root-level ink! entity macro actions.
-fn root_ink_entity_actions(results: &mut Vec<Action>, file: &InkFile, offset: TextSize) {
-    if file.contracts().is_empty() {
+fn root_ink_entity_actions(
+    results: &mut Vec<Action>,
+    file: &InkFile,
+    queries: &Queries,
+    offset: TextSize,
+) {
+    if queries.index(file).contracts().is_empty() {
         // Adds ink! contract.
         results.push(entity::add_contract(offset, ActionKind::Refactor, None));
     }
@@ -449,7 +557,7 @@ fn flatten_attrs(results: &mut Vec<Action>, target: &SyntaxNode, range: TextRang
         if other_arg_attrs.clone().next().is_some() {
             results.push(Action {
                 label: "Flatten ink! attribute arguments.".to_string(),
-                kind: ActionKind::Refactor,
+                kind: ActionKind::RefactorRewrite,
                 range,
                 edits: [TextEdit::replace(
                     format!(
@@ -478,11 +586,75 @@ fn flatten_attrs(results: &mut Vec<Action>, target: &SyntaxNode, range: TextRang
                 // Removes other argument-based ink! attributes.
                 .chain(other_arg_attrs.map(|attr| TextEdit::delete(attr.syntax().text_range())))
                 .collect(),
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             });
         }
     }
 }
 
+/// Computes actions for "normalizing" ink! attributes for the target syntax node
+/// (i.e. primary ink! attribute argument first, consistent formatting and no duplicate ink! attribute arguments).
+fn normalize_attrs(results: &mut Vec<Action>, target: &SyntaxNode, range: TextRange) {
+    let attrs: Vec<InkAttribute> = ink_analyzer_ir::ink_attrs(target).collect();
+    // Only normalizes ink! attributes with known ink! attribute arguments with "complete" values
+    // (i.e. bails if formatting could change the meaning of the ink! attributes).
+    let is_normalizable = attrs.iter().all(|attr| {
+        !matches!(
+            attr.kind(),
+            InkAttributeKind::Macro(InkMacroKind::Unknown)
+                | InkAttributeKind::Arg(InkArgKind::Unknown)
+        ) && attr.args().iter().all(|arg| {
+            *arg.kind() != InkArgKind::Unknown
+                && arg.name().is_some()
+                && arg.meta().eq().is_some() == arg.value().is_some()
+        })
+    });
+    if !is_normalizable {
+        return;
+    }
+
+    let mut seen_args = HashSet::new();
+    let edits: Vec<TextEdit> = attrs
+        .iter()
+        .filter_map(|attr| {
+            let path = attr.ast().path()?.to_string();
+            // Sorts ink! attribute arguments by priority (i.e. primary ink! attribute argument first),
+            // and removes duplicates (including duplicates in other ink! attributes).
+            let args: Vec<String> = attr
+                .args()
+                .iter()
+                .sorted()
+                .map(ToString::to_string)
+                .filter(|arg| seen_args.insert(arg.clone()))
+                .collect();
+            let normalized_text = match attr.kind() {
+                // Removes argument-based ink! attributes whose ink! attribute arguments are all duplicates.
+                InkAttributeKind::Arg(_) if args.is_empty() => None,
+                InkAttributeKind::Macro(_) if args.is_empty() => Some(format!("#[{path}]")),
+                _ => Some(format!("#[{path}({})]", args.join(", "))),
+            };
+            match normalized_text {
+                Some(text) => (text != attr.syntax().to_string())
+                    .then(|| TextEdit::replace(text, attr.syntax().text_range())),
+                None => Some(TextEdit::delete(attr.syntax().text_range())),
+            }
+        })
+        .collect();
+    if !edits.is_empty() {
+        results.push(Action {
+            label: "Normalize ink! attributes.".to_string(),
+            kind: ActionKind::RefactorRewrite,
+            range,
+            edits,
+            document_edits: Vec::new(),
+            is_preferred: false,
+            priority: 0,
+        });
+    }
+}
+
 /// Determines if the selection range is in an AST item's declaration
 /// (i.e not on meta - attributes/rustdoc - nor inside the AST item's item list or body)
 /// for an item that can be annotated with ink! attributes or can have ink! attribute descendants.
@@ -1026,6 +1198,49 @@ mod tests {
                     },
                 ],
             ),
+            (
+                r#"
+                    #[ink::chain_extension]
+                    pub trait MyTrait {
+                        type ErrorCode = MyErrorCode;
+
+                        #[ink(extension=5)]
+                        fn my_extension();
+
+                        #[ink(extension=2)]
+                        fn my_extension2();
+
+                        #[ink(extension=2)]
+                        fn my_extension3();
+                    }
+                "#,
+                Some("<-pub"),
+                vec![
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "#[ink(extension = 1)]",
+                            start_pat: Some("fn my_extension3();"),
+                            end_pat: Some("fn my_extension3();"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Renumber",
+                        edits: vec![
+                            TestResultTextRange {
+                                text: "3",
+                                start_pat: Some("<-5)]"),
+                                end_pat: Some("extension=5"),
+                            },
+                            TestResultTextRange {
+                                text: "1",
+                                start_pat: Some("<-2)]"),
+                                end_pat: Some("extension=2"),
+                            },
+                        ],
+                    },
+                ],
+            ),
             (
                 r#"
                     #[ink::trait_definition]
@@ -1863,15 +2078,770 @@ mod tests {
                     },
                 ],
             ),
-        ] {
-            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
-            let range = TextRange::new(offset, offset);
+            (
+                r#"
+                    #[ink(impl, namespace="my_namespace")]
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn my_message(&self) {}
 
-            let mut results = Vec::new();
-            actions(&mut results, &InkFile::parse(code), range);
+                        #[ink(message, selector=1)]
+                        pub fn my_pinned_message(&self) {}
+                    }
+                "#,
+                Some("<-impl MyContract {"),
+                vec![
+                    TestResultAction {
+                        label: "Normalize",
+                        edits: vec![TestResultTextRange {
+                            text: r#"#[ink(impl, namespace = "my_namespace")]"#,
+                            start_pat: Some(r#"<-#[ink(impl, namespace="my_namespace")]"#),
+                            end_pat: Some(r#"#[ink(impl, namespace="my_namespace")]"#),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "#[ink(constructor)]",
+                            start_pat: Some("pub fn my_pinned_message(&self) {}"),
+                            end_pat: Some("pub fn my_pinned_message(&self) {}"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "#[ink(message)]",
+                            start_pat: Some("pub fn my_pinned_message(&self) {}"),
+                            end_pat: Some("pub fn my_pinned_message(&self) {}"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Pin current selectors",
+                        edits: vec![TestResultTextRange {
+                            // First 4-bytes of Blake2b-256 hash of "my_namespace::my_message".
+                            text: ", selector = 0xabe89c04",
+                            start_pat: Some("<-)]\n                        pub fn my_message"),
+                            end_pat: Some("<-)]\n                        pub fn my_message"),
+                        }],
+                    },
+                ],
+            ),
+            (
+                r#"
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self);
+                    }
 
-            // Verifies actions.
-            verify_actions(code, &results, &expected_results);
+                    impl MyTrait for MyContract {
+                        #[ink(message)]
+                        fn my_message(&self) {}
+                    }
+                "#,
+                Some("<-impl MyTrait for MyContract {"),
+                vec![
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "#[ink(impl)]",
+                            start_pat: Some("<-impl MyTrait for MyContract {"),
+                            end_pat: Some("<-impl MyTrait for MyContract {"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Pin current selectors",
+                        edits: vec![TestResultTextRange {
+                            // First 4-bytes of Blake2b-256 hash of "MyTrait::my_message".
+                            text: ", selector = 0x04c49446",
+                            start_pat: Some("<-)]\n                        fn my_message(&self);"),
+                            end_pat: Some("<-)]\n                        fn my_message(&self);"),
+                        }],
+                    },
+                ],
+            ),
+            (
+                r#"
+                    #[ink(impl, namespace = "my_namespace")]
+                    impl MyContract {
+                        #[ink(constructor, selector = 1)]
+                        pub fn new() -> Self {}
+
+                        #[ink(message, selector = 2)]
+                        pub fn my_message(&self) {}
+                    }
+                "#,
+                Some("<-impl MyContract {"),
+                vec![
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "#[ink(constructor)]",
+                            start_pat: Some("pub fn my_message(&self) {}"),
+                            end_pat: Some("pub fn my_message(&self) {}"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "#[ink(message)]",
+                            start_pat: Some("pub fn my_message(&self) {}"),
+                            end_pat: Some("pub fn my_message(&self) {}"),
+                        }],
+                    },
+                ],
+            ),
+            (
+                r#"
+                    #[ink::trait_definition(namespace = "my_namespace")]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self);
+                    }
+
+                    impl MyTrait for MyContract {
+                        #[ink(message)]
+                        fn my_message(&self) {}
+                    }
+                "#,
+                Some("<-impl MyTrait for MyContract {"),
+                vec![
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "#[ink(impl)]",
+                            start_pat: Some("<-impl MyTrait for MyContract {"),
+                            end_pat: Some("<-impl MyTrait for MyContract {"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Pin current selectors",
+                        edits: vec![TestResultTextRange {
+                            // First 4-bytes of Blake2b-256 hash of "my_namespace::MyTrait::my_message".
+                            text: ", selector = 0x84452b5e",
+                            start_pat: Some("<-)]\n                        fn my_message(&self);"),
+                            end_pat: Some("<-)]\n                        fn my_message(&self);"),
+                        }],
+                    },
+                ],
+            ),
+            (
+                r#"
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message, selector = 1)]
+                        fn my_message(&self);
+                    }
+
+                    impl MyTrait for MyContract {
+                        #[ink(message)]
+                        fn my_message(&self) {}
+                    }
+                "#,
+                Some("<-impl MyTrait for MyContract {"),
+                vec![TestResultAction {
+                    label: "Add",
+                    edits: vec![TestResultTextRange {
+                        text: "#[ink(impl)]",
+                        start_pat: Some("<-impl MyTrait for MyContract {"),
+                        end_pat: Some("<-impl MyTrait for MyContract {"),
+                    }],
+                }],
+            ),
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContract {
+                            value: bool,
+                            owner: AccountId,
+                            balances: Mapping<AccountId, Balance>,
+                        }
+
+                        impl MyContract {
+                            #[ink(message)]
+                            pub fn set_value(&mut self, value: bool) {
+                                self.balances.insert(self.owner, &0);
+                                self.value = value;
+                            }
+                        }
+                    }
+                "#,
+                Some("self.value = value;"),
+                vec![
+                    TestResultAction {
+                        label: "Emit event",
+                        edits: vec![
+                            TestResultTextRange {
+                                text: "pub struct SetValue {",
+                                start_pat: Some("Balance>,\n                        }"),
+                                end_pat: Some("Balance>,\n                        }"),
+                            },
+                            TestResultTextRange {
+                                text: "self.env().emit_event(SetValue { value: self.value });",
+                                start_pat: Some("self.value = value;"),
+                                end_pat: Some("self.value = value;"),
+                            },
+                        ],
+                    },
+                    TestResultAction {
+                        label: "Extract",
+                        edits: vec![
+                            TestResultTextRange {
+                                text: "",
+                                start_pat: Some("<-#[ink(message)]"),
+                                end_pat: Some("self.value = value;\n                            }"),
+                            },
+                            TestResultTextRange {
+                                text: "fn set_value(&mut self, value: bool);",
+                                start_pat: Some("value;\n                            }\n                        }"),
+                                end_pat: Some("value;\n                            }\n                        }"),
+                            },
+                        ],
+                    },
+                ],
+            ),
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContract {
+                            value: bool,
+                        }
+
+                        impl MyContract {
+                            #[ink(message)]
+                            pub fn get(&self) -> bool {
+                                self.value
+                            }
+                        }
+                    }
+                "#,
+                Some("self.value"),
+                vec![TestResultAction {
+                    label: "Extract",
+                    edits: vec![
+                        TestResultTextRange {
+                            text: "",
+                            start_pat: Some("<-#[ink(message)]"),
+                            end_pat: Some("self.value\n                            }"),
+                        },
+                        TestResultTextRange {
+                            text: "impl MyContractApi for MyContract {",
+                            start_pat: Some("self.value\n                            }\n                        }"),
+                            end_pat: Some("self.value\n                            }\n                        }"),
+                        },
+                    ],
+                }],
+            ),
+            (
+                r#"
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self) -> bool;
+                    }
+
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContract {}
+
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn new() -> Self {}
+                        }
+                    }
+                "#,
+                Some("<-pub trait MyTrait {"),
+                vec![
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: r#"(keep_attr = "")"#,
+                            start_pat: Some("#[ink::trait_definition"),
+                            end_pat: Some("#[ink::trait_definition"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: r#"(namespace = "my_namespace")"#,
+                            start_pat: Some("#[ink::trait_definition"),
+                            end_pat: Some("#[ink::trait_definition"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "#[ink(message)]",
+                            start_pat: Some("fn my_message(&self) -> bool;"),
+                            end_pat: Some("fn my_message(&self) -> bool;"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Implement for contract `MyContract`",
+                        edits: vec![TestResultTextRange {
+                            text: "impl MyTrait for MyContract {",
+                            start_pat: Some("pub fn new() -> Self {}\n                        }"),
+                            end_pat: Some("pub fn new() -> Self {}\n                        }"),
+                        }],
+                    },
+                ],
+            ),
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContract {}
+
+                        #[ink(event)]
+                        pub struct MyMyContractEvent {}
+
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn new() -> Self {}
+
+                            #[ink(message)]
+                            pub fn message(&self) {}
+                        }
+
+                    }
+                "#,
+                Some("<-\n                    }"),
+                vec![
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "pub struct MyMyContractEvent2 {}",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "pub fn new2() -> Self {",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "pub fn message2(&self) {",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add ink! e2e tests",
+                        edits: vec![TestResultTextRange {
+                            text: "async fn new_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add ink! unit tests",
+                        edits: vec![TestResultTextRange {
+                            text: "fn new_works() {",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                ],
+            ),
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContract {
+                            value: bool,
+                        }
+
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn new(value: bool) -> Self {}
+
+                            #[ink(message)]
+                            pub fn flip(&mut self) {}
+                        }
+
+                        #[cfg(all(test, feature = "e2e-tests"))]
+                        mod e2e_tests {}
+
+                    }
+                "#,
+                Some("<-\n                    }"),
+                vec![
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "pub struct MyMyContractEvent {}",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "pub fn new2() -> Self {",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "pub fn message(&self) {",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add ink! unit tests",
+                        edits: vec![TestResultTextRange {
+                            text: "let mut contract = MyContract::new(value);",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                ],
+            ),
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        impl MyContract {
+                            #[ink(message)]
+                            pub fn flip(&mut self) {}
+
+                            #[ink(constructor)]
+                            pub fn new(value: bool) -> Self {}
+                        }
+
+                        #[ink(storage)]
+                        pub struct MyContract {
+                            value: bool,
+                        }
+
+                        #[cfg(all(test, feature = "e2e-tests"))]
+                        mod e2e_tests {}
+
+                    }
+                "#,
+                Some("<-\n                    }"),
+                vec![
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "pub struct MyMyContractEvent {}",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "pub fn new2() -> Self {",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add",
+                        edits: vec![TestResultTextRange {
+                            text: "pub fn message(&self) {",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Add ink! unit tests",
+                        edits: vec![TestResultTextRange {
+                            text: "let mut contract = MyContract::new(value);",
+                            start_pat: Some("<-\n                    }"),
+                            end_pat: Some("<-\n                    }"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Organize",
+                        edits: vec![
+                            // Moves ink! storage before the ink! `impl` block.
+                            TestResultTextRange {
+                                text: "#[ink(storage)]\n                        pub struct MyContract {",
+                                start_pat: Some("<-impl MyContract {"),
+                                end_pat: Some("<-\n\n                        #[ink(storage)]"),
+                            },
+                            // Moves ink! `impl` block (with ink! constructors before ink! messages)
+                            // after ink! storage.
+                            TestResultTextRange {
+                                text: "pub fn new(value: bool) -> Self {}\n\n                            #[ink(message)]",
+                                start_pat: Some("<-#[ink(storage)]"),
+                                end_pat: Some("value: bool,\n                        }"),
+                            },
+                        ],
+                    },
+                ],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
+            let range = TextRange::new(offset, offset);
+
+            let mut results = Vec::new();
+            actions(&mut results, &InkFile::parse(code), &Queries::default(), range);
+
+            // Verifies actions.
+            verify_actions(code, &results, &expected_results);
+        }
+    }
+
+    #[test]
+    fn storage_field_actions_works() {
+        for (code, pat, expected_results) in [
+            // (code, pat, Vec<(label, Vec<(text, start_pat, end_pat)>)>) where:
+            // code = source code,
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // label = the label text (of a substring of it) for the action,
+            // edit = the text (of a substring of it) that will inserted,
+            // start_pat = substring used to find the start of the edit offset (see `test_utils::parse_offset_at` doc),
+            // end_pat = substring used to find the end of the edit offset (see `test_utils::parse_offset_at` doc).
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContract {
+                            value: bool,
+                        }
+
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn new(value: bool) -> Self {
+                                Self { value }
+                            }
+
+                            #[ink(constructor)]
+                            pub fn default() -> Self {
+                                Self {
+                                    value: false,
+                                }
+                            }
+                        }
+                    }
+                "#,
+                Some("pub struct My"),
+                vec![TestResultAction {
+                    label: "Add storage field",
+                    edits: vec![
+                        TestResultTextRange {
+                            text: "field: bool,",
+                            start_pat: Some("value: bool,"),
+                            end_pat: Some("value: bool,"),
+                        },
+                        TestResultTextRange {
+                            text: ", field: Default::default()",
+                            start_pat: Some("Self { value"),
+                            end_pat: Some("Self { value"),
+                        },
+                        TestResultTextRange {
+                            text:
+                                ",\n                                    field: Default::default()",
+                            start_pat: Some("value: false"),
+                            end_pat: Some("value: false"),
+                        },
+                    ],
+                }],
+            ),
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContract {
+                            value: bool,
+                            count: u32,
+                        }
+
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn new(value: bool) -> Self {
+                                Self { value, count: 0 }
+                            }
+
+                            #[ink(message)]
+                            pub fn flip(&mut self) {
+                                self.value = !self.value;
+                                self.count += 1;
+                            }
+
+                            #[ink(message)]
+                            pub fn get(&self) -> bool {
+                                self.value
+                            }
+                        }
+                    }
+                "#,
+                Some("cou"),
+                vec![TestResultAction {
+                    label: "Remove `count` storage field.",
+                    edits: vec![
+                        TestResultTextRange {
+                            text: "",
+                            start_pat: Some("value: bool,"),
+                            end_pat: Some("count: u32,"),
+                        },
+                        TestResultTextRange {
+                            text: "",
+                            start_pat: Some("Self { value"),
+                            end_pat: Some("count: 0"),
+                        },
+                        TestResultTextRange {
+                            text: "",
+                            start_pat: Some("self.value = !self.value;"),
+                            end_pat: Some("self.count += 1;"),
+                        },
+                    ],
+                }],
+            ),
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContract {
+                            value: bool,
+                            count: u32,
+                        }
+
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn new(value: bool) -> Self {
+                                Self { value, count: 0 }
+                            }
+
+                            #[ink(message)]
+                            pub fn flip(&mut self) {
+                                self.value = !self.value;
+                                self.count += 1;
+                            }
+
+                            #[ink(message)]
+                            pub fn get(&self) -> bool {
+                                self.value
+                            }
+                        }
+                    }
+                "#,
+                Some("valu"),
+                vec![TestResultAction {
+                    label:
+                        "Remove `value` storage field (1 remaining usage(s) need to be updated).",
+                    edits: vec![
+                        TestResultTextRange {
+                            text: "",
+                            start_pat: Some("pub struct MyContract {"),
+                            end_pat: Some("value: bool,"),
+                        },
+                        TestResultTextRange {
+                            text: "",
+                            start_pat: Some("Self { "),
+                            end_pat: Some("Self { value,"),
+                        },
+                        TestResultTextRange {
+                            text: "",
+                            start_pat: Some("pub fn flip(&mut self) {"),
+                            end_pat: Some("self.value = !self.value;"),
+                        },
+                    ],
+                }],
+            ),
+            // Non-storage fields.
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(event)]
+                        pub struct MyEvent {
+                            value: bool,
+                        }
+                    }
+                "#,
+                Some("valu"),
+                vec![],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
+            let range = TextRange::new(offset, offset);
+
+            let mut results = Vec::new();
+            actions(
+                &mut results,
+                &InkFile::parse(code),
+                &Queries::default(),
+                range,
+            );
+
+            // Verifies storage field actions (i.e. ignores other actions).
+            let results: Vec<Action> = results
+                .into_iter()
+                .filter(|action| action.label.contains("storage field"))
+                .collect();
+            verify_actions(code, &results, &expected_results);
+        }
+    }
+
+    #[test]
+    fn normalize_attrs_works() {
+        for (code, expected_texts) in [
+            // (code, Vec<text>) where:
+            // code = source code,
+            // text = the text of each edit for the normalize action (empty if no action is expected).
+
+            // Sorts arguments, formats values and removes duplicates.
+            (
+                "#[ink(payable, message, payable, selector=1)]\npub fn my_message(&self) {}",
+                vec!["#[ink(message, payable, selector = 1)]"],
+            ),
+            (
+                "#[ink::contract(env=crate::Environment)]\nmod my_contract {}",
+                vec!["#[ink::contract(env = crate::Environment)]"],
+            ),
+            (
+                "#[ink::contract()]\nmod my_contract {}",
+                vec!["#[ink::contract]"],
+            ),
+            // Ignores normalized attributes.
+            (
+                "#[ink(message, payable)]\npub fn my_message(&self) {}",
+                vec![],
+            ),
+            ("#[ink::contract]\nmod my_contract {}", vec![]),
+            // Ignores attributes with unknown arguments.
+            (
+                "#[ink(payable, message, unknown)]\npub fn my_message(&self) {}",
+                vec![],
+            ),
+        ] {
+            let target = InkFile::parse(code)
+                .syntax()
+                .descendants()
+                .find_map(ast::Item::cast)
+                .unwrap();
+
+            let mut results = Vec::new();
+            normalize_attrs(&mut results, target.syntax(), target.syntax().text_range());
+
+            let texts: Vec<&str> = results
+                .iter()
+                .flat_map(|action| action.edits.iter().map(|edit| edit.text.as_str()))
+                .collect();
+            assert_eq!(texts, expected_texts, "code: {code}");
         }
     }
 
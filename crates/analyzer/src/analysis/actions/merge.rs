@@ -0,0 +1,200 @@
+//! Merging of text edits from multiple actions (e.g. for "fix all" actions).
+
+use crate::analysis::text_edit;
+use crate::{Action, DocumentEdit, TextEdit};
+
+/// The result of merging the text edits of multiple actions.
+#[derive(Debug, Default)]
+pub struct MergedEdits {
+    /// Merged text edits (sorted by position).
+    pub edits: Vec<TextEdit>,
+    /// Merged text edits for other documents (sorted by path and position).
+    pub document_edits: Vec<DocumentEdit>,
+    /// Indices of the actions whose text edits were merged.
+    pub merged: Vec<usize>,
+    /// Indices of the actions whose text edits were skipped because they conflict
+    /// (i.e. overlap) with the text edits of previously merged actions.
+    pub conflicts: Vec<usize>,
+}
+
+/// Merges the text edits of actions (in order of priority - i.e. earlier actions take precedence),
+/// see [`text_edit::merge_edits`].
+///
+/// An action is either merged as a whole or skipped as a whole (i.e. if any of its text edits
+/// conflict with the text edits of previously merged actions).
+///
+/// Snippets are discarded (i.e. only plain text is merged),
+/// because tab stops and placeholders from different actions can't be combined.
+pub fn merge(actions: &[Action]) -> MergedEdits {
+    let mut result = MergedEdits::default();
+    for (idx, action) in actions.iter().enumerate() {
+        let edits = text_edit::merge_edits([&result.edits, &plain_edits(&action.edits)]);
+        let document_edits = action
+            .document_edits
+            .iter()
+            .map(|document_edit| {
+                let existing_edits = result
+                    .document_edits
+                    .iter()
+                    .find(|it| it.path == document_edit.path)
+                    .map_or(&[][..], |it| it.edits.as_slice());
+                text_edit::merge_edits([
+                    existing_edits,
+                    plain_edits(&document_edit.edits).as_slice(),
+                ])
+                .map(|edits| (document_edit.path.clone(), edits))
+            })
+            .collect::<Result<Vec<(String, Vec<TextEdit>)>, _>>();
+
+        match (edits, document_edits) {
+            (Ok(edits), Ok(document_edits)) => {
+                result.edits = edits;
+                for (path, edits) in document_edits {
+                    match result
+                        .document_edits
+                        .iter_mut()
+                        .find(|document_edit| document_edit.path == path)
+                    {
+                        Some(document_edit) => document_edit.edits = edits,
+                        None => result.document_edits.push(DocumentEdit { path, edits }),
+                    }
+                }
+                result.merged.push(idx);
+            }
+            // Skips actions with conflicting edits.
+            _ => result.conflicts.push(idx),
+        }
+    }
+
+    result.document_edits.sort_by(|a, b| a.path.cmp(&b.path));
+    result
+}
+
+/// Returns the text edits without snippets.
+fn plain_edits(edits: &[TextEdit]) -> Vec<TextEdit> {
+    edits
+        .iter()
+        .map(|edit| TextEdit::replace(edit.plain_text().into_owned(), edit.range))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ActionKind;
+    use ink_analyzer_ir::syntax::{TextRange, TextSize};
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(TextSize::from(start), TextSize::from(end))
+    }
+
+    fn action(edits: Vec<TextEdit>) -> Action {
+        Action {
+            label: "Fix.".to_string(),
+            kind: ActionKind::QuickFix,
+            range: range(0, 0),
+            edits,
+            document_edits: Vec::new(),
+            is_preferred: true,
+            priority: 1,
+        }
+    }
+
+    #[test]
+    fn merge_works() {
+        for (actions, expected_ranges, expected_merged, expected_conflicts) in [
+            // Disjoint and adjacent edits.
+            (
+                vec![
+                    action(vec![TextEdit::delete(range(5, 10))]),
+                    action(vec![TextEdit::delete(range(0, 5))]),
+                ],
+                vec![range(0, 5), range(5, 10)],
+                vec![0, 1],
+                vec![],
+            ),
+            // Overlapping edits (i.e. the later action is skipped as a whole).
+            (
+                vec![
+                    action(vec![TextEdit::delete(range(0, 5))]),
+                    action(vec![
+                        TextEdit::delete(range(3, 8)),
+                        TextEdit::delete(range(10, 12)),
+                    ]),
+                    action(vec![TextEdit::delete(range(10, 12))]),
+                ],
+                vec![range(0, 5), range(10, 12)],
+                vec![0, 2],
+                vec![1],
+            ),
+            // Insertions inside replaced ranges conflict, but insertions at boundaries don't.
+            (
+                vec![
+                    action(vec![TextEdit::delete(range(0, 5))]),
+                    action(vec![TextEdit::insert("a".to_string(), TextSize::from(3))]),
+                    action(vec![TextEdit::insert("b".to_string(), TextSize::from(5))]),
+                    action(vec![TextEdit::insert("c".to_string(), TextSize::from(5))]),
+                ],
+                vec![range(0, 5), range(5, 5), range(5, 5)],
+                vec![0, 2, 3],
+                vec![1],
+            ),
+            // Identical edits are merged once.
+            (
+                vec![
+                    action(vec![TextEdit::insert(
+                        "use a;".to_string(),
+                        TextSize::from(0),
+                    )]),
+                    action(vec![
+                        TextEdit::insert("use a;".to_string(), TextSize::from(0)),
+                        TextEdit::delete(range(5, 10)),
+                    ]),
+                ],
+                vec![range(0, 0), range(5, 10)],
+                vec![0, 1],
+                vec![],
+            ),
+        ] {
+            let result = merge(&actions);
+            assert_eq!(
+                result
+                    .edits
+                    .iter()
+                    .map(|edit| edit.range)
+                    .collect::<Vec<_>>(),
+                expected_ranges
+            );
+            assert_eq!(result.merged, expected_merged);
+            assert_eq!(result.conflicts, expected_conflicts);
+        }
+    }
+
+    #[test]
+    fn merge_document_edits_works() {
+        let cargo_action = |edit| Action {
+            document_edits: vec![DocumentEdit {
+                path: "Cargo.toml".to_string(),
+                edits: vec![edit],
+            }],
+            ..action(Vec::new())
+        };
+        let result = merge(&[
+            cargo_action(TextEdit::insert(
+                "[features]".to_string(),
+                TextSize::from(0),
+            )),
+            cargo_action(TextEdit::insert(
+                "[features]".to_string(),
+                TextSize::from(0),
+            )),
+            cargo_action(TextEdit::delete(range(0, 5))),
+            cargo_action(TextEdit::delete(range(2, 8))),
+        ]);
+        assert!(result.edits.is_empty());
+        assert_eq!(result.document_edits.len(), 1);
+        assert_eq!(result.document_edits[0].edits.len(), 2);
+        assert_eq!(result.merged, vec![0, 1, 2]);
+        assert_eq!(result.conflicts, vec![3]);
+    }
+}
@@ -0,0 +1,379 @@
+//! Builders for ink! analyzer actions and diagnostics
+//! (e.g. for actions and diagnostics computed by external passes).
+
+use ink_analyzer_ir::syntax::TextRange;
+
+use crate::{
+    Action, ActionKind, Diagnostic, DiagnosticCode, DocumentEdit, RelatedInformation, Severity,
+    TextEdit,
+};
+
+/// An error returned when building an action or diagnostic from invalid parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// The diagnostic message or action label is empty.
+    EmptyMessage,
+    /// The text range is missing.
+    MissingRange,
+    /// The action has no text edits.
+    EmptyEdits,
+    /// The text ranges of two (or more) text edits (for the same document) overlap.
+    OverlappingEdits,
+}
+
+/// Builder for [`Diagnostic`]s.
+#[derive(Debug, Clone)]
+pub struct DiagnosticBuilder {
+    message: String,
+    range: Option<TextRange>,
+    severity: Severity,
+    code: Option<DiagnosticCode>,
+    quickfixes: Vec<Action>,
+    related_information: Vec<RelatedInformation>,
+}
+
+impl Diagnostic {
+    /// Returns a builder for a diagnostic with the given message
+    /// (the severity defaults to [`Severity::Error`]).
+    pub fn builder(message: impl Into<String>) -> DiagnosticBuilder {
+        DiagnosticBuilder {
+            message: message.into(),
+            range: None,
+            severity: Severity::Error,
+            code: None,
+            quickfixes: Vec::new(),
+            related_information: Vec::new(),
+        }
+    }
+}
+
+impl DiagnosticBuilder {
+    /// Sets the text range to highlight.
+    pub fn range(mut self, range: TextRange) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Sets the severity level.
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets the diagnostic code.
+    pub fn code(mut self, code: DiagnosticCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Adds a quickfix.
+    pub fn quickfix(mut self, action: Action) -> Self {
+        self.quickfixes.push(action);
+        self
+    }
+
+    /// Adds a related location.
+    pub fn related(mut self, message: impl Into<String>, range: TextRange) -> Self {
+        self.related_information.push(RelatedInformation {
+            message: message.into(),
+            range,
+        });
+        self
+    }
+
+    /// Returns the diagnostic, or an error if the message is empty or the range is missing.
+    pub fn build(self) -> Result<Diagnostic, BuildError> {
+        if self.message.trim().is_empty() {
+            return Err(BuildError::EmptyMessage);
+        }
+
+        Ok(Diagnostic {
+            message: self.message,
+            range: self.range.ok_or(BuildError::MissingRange)?,
+            severity: self.severity,
+            code: self.code,
+            quickfixes: (!self.quickfixes.is_empty()).then_some(self.quickfixes),
+            related_information: (!self.related_information.is_empty())
+                .then_some(self.related_information),
+        })
+    }
+}
+
+/// Builder for [`Action`]s.
+#[derive(Debug, Clone)]
+pub struct ActionBuilder {
+    label: String,
+    kind: ActionKind,
+    range: Option<TextRange>,
+    edits: Vec<TextEdit>,
+    document_edits: Vec<DocumentEdit>,
+    is_preferred: bool,
+    priority: u8,
+}
+
+impl Action {
+    /// Returns a builder for an action with the given label and kind.
+    pub fn builder(label: impl Into<String>, kind: ActionKind) -> ActionBuilder {
+        ActionBuilder {
+            label: label.into(),
+            kind,
+            range: None,
+            edits: Vec::new(),
+            document_edits: Vec::new(),
+            is_preferred: false,
+            priority: 0,
+        }
+    }
+}
+
+impl ActionBuilder {
+    /// Sets the range where the action is activated.
+    pub fn range(mut self, range: TextRange) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Adds a text edit.
+    pub fn edit(mut self, edit: TextEdit) -> Self {
+        self.edits.push(edit);
+        self
+    }
+
+    /// Adds a text edit for another document
+    /// (the path is relative to the directory of the analyzed file, see [`DocumentEdit`] doc).
+    pub fn document_edit(mut self, path: impl Into<String>, edit: TextEdit) -> Self {
+        let path = path.into();
+        match self
+            .document_edits
+            .iter_mut()
+            .find(|document_edit| document_edit.path == path)
+        {
+            Some(document_edit) => document_edit.edits.push(edit),
+            None => self.document_edits.push(DocumentEdit {
+                path,
+                edits: vec![edit],
+            }),
+        }
+        self
+    }
+
+    /// Marks the action as the preferred fix for its diagnostic, see [`Action::is_preferred`].
+    pub fn preferred(mut self) -> Self {
+        self.is_preferred = true;
+        self
+    }
+
+    /// Sets the rank of the action relative to other quickfixes for the same diagnostic,
+    /// see [`Action::priority`].
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the action, or an error if the label is empty, the range is missing,
+    /// there are no text edits (for any document) or the text edits for the same document overlap.
+    pub fn build(self) -> Result<Action, BuildError> {
+        if self.label.trim().is_empty() {
+            return Err(BuildError::EmptyMessage);
+        }
+        let range = self.range.ok_or(BuildError::MissingRange)?;
+        if self.edits.is_empty() && self.document_edits.is_empty() {
+            return Err(BuildError::EmptyEdits);
+        }
+
+        let has_overlap = has_overlapping_edits(&self.edits)
+            || self
+                .document_edits
+                .iter()
+                .any(|document_edit| has_overlapping_edits(&document_edit.edits));
+        if has_overlap {
+            return Err(BuildError::OverlappingEdits);
+        }
+
+        Ok(Action {
+            label: self.label,
+            kind: self.kind,
+            range,
+            edits: self.edits,
+            document_edits: self.document_edits,
+            is_preferred: self.is_preferred,
+            priority: self.priority,
+        })
+    }
+}
+
+/// Returns true if the text ranges of two (or more) text edits overlap.
+///
+/// NOTE: Insertions (i.e. empty ranges) can share an offset with each other
+/// and with the boundaries of other edits.
+fn has_overlapping_edits(edits: &[TextEdit]) -> bool {
+    let mut edit_ranges: Vec<TextRange> = edits.iter().map(|edit| edit.range).collect();
+    edit_ranges.sort_by_key(|range| (range.start(), range.end()));
+    edit_ranges
+        .windows(2)
+        .any(|pair| pair[0].end() > pair[1].start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_analyzer_ir::syntax::TextSize;
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(TextSize::from(start), TextSize::from(end))
+    }
+
+    #[test]
+    fn diagnostic_builder_works() {
+        let action = Action::builder("Remove item.", ActionKind::QuickFix)
+            .range(range(0, 5))
+            .edit(TextEdit::delete(range(0, 5)))
+            .build()
+            .unwrap();
+        assert_eq!(
+            Diagnostic::builder("Invalid item.")
+                .range(range(0, 5))
+                .severity(Severity::Warning)
+                .code(DiagnosticCode::InvalidItemKind)
+                .quickfix(action.clone())
+                .related("Declared here.", range(10, 15))
+                .build(),
+            Ok(Diagnostic {
+                message: "Invalid item.".to_string(),
+                range: range(0, 5),
+                severity: Severity::Warning,
+                code: Some(DiagnosticCode::InvalidItemKind),
+                quickfixes: Some(vec![action]),
+                related_information: Some(vec![RelatedInformation {
+                    message: "Declared here.".to_string(),
+                    range: range(10, 15),
+                }]),
+            })
+        );
+
+        // Defaults to an error without a code and quickfixes.
+        assert_eq!(
+            Diagnostic::builder("Invalid item.")
+                .range(range(0, 5))
+                .build(),
+            Ok(Diagnostic {
+                message: "Invalid item.".to_string(),
+                range: range(0, 5),
+                severity: Severity::Error,
+                code: None,
+                quickfixes: None,
+                related_information: None,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_diagnostic_builder_fails() {
+        assert_eq!(
+            Diagnostic::builder(" ").range(range(0, 5)).build(),
+            Err(BuildError::EmptyMessage)
+        );
+        assert_eq!(
+            Diagnostic::builder("Invalid item.").build(),
+            Err(BuildError::MissingRange)
+        );
+    }
+
+    #[test]
+    fn action_builder_works() {
+        for edit_ranges in [
+            vec![range(0, 5)],
+            // Adjacent edits.
+            vec![range(0, 5), range(5, 10)],
+            // Insertions at the same offset.
+            vec![range(5, 5), range(5, 5)],
+        ] {
+            let mut builder =
+                Action::builder("Fix item.", ActionKind::Refactor).range(range(0, 10));
+            for edit_range in &edit_ranges {
+                builder = builder.edit(TextEdit::replace("item".to_string(), *edit_range));
+            }
+            let action = builder.build().unwrap();
+            assert_eq!(action.label, "Fix item.");
+            assert_eq!(action.kind, ActionKind::Refactor);
+            assert_eq!(action.range, range(0, 10));
+            assert_eq!(
+                action
+                    .edits
+                    .iter()
+                    .map(|edit| edit.range)
+                    .collect::<Vec<_>>(),
+                edit_ranges
+            );
+        }
+    }
+
+    #[test]
+    fn action_builder_document_edits_works() {
+        let action = Action::builder("Add e2e tests.", ActionKind::QuickFix)
+            .range(range(0, 10))
+            .edit(TextEdit::insert(
+                "mod e2e_tests {}".to_string(),
+                TextSize::from(10),
+            ))
+            // Edits for different documents can overlap.
+            .document_edit(
+                "Cargo.toml",
+                TextEdit::insert("[features]".to_string(), TextSize::from(5)),
+            )
+            .document_edit(
+                "Cargo.toml",
+                TextEdit::insert("e2e-tests = []".to_string(), TextSize::from(5)),
+            )
+            .document_edit("../Cargo.toml", TextEdit::delete(range(0, 10)))
+            .build()
+            .unwrap();
+        assert_eq!(action.edits.len(), 1);
+        assert_eq!(
+            action
+                .document_edits
+                .iter()
+                .map(|document_edit| (document_edit.path.as_str(), document_edit.edits.len()))
+                .collect::<Vec<_>>(),
+            vec![("Cargo.toml", 2), ("../Cargo.toml", 1)]
+        );
+    }
+
+    #[test]
+    fn invalid_action_builder_fails() {
+        for (builder, expected_error) in [
+            (
+                Action::builder("", ActionKind::QuickFix)
+                    .range(range(0, 5))
+                    .edit(TextEdit::delete(range(0, 5))),
+                BuildError::EmptyMessage,
+            ),
+            (
+                Action::builder("Remove item.", ActionKind::QuickFix)
+                    .edit(TextEdit::delete(range(0, 5))),
+                BuildError::MissingRange,
+            ),
+            (
+                Action::builder("Remove item.", ActionKind::QuickFix).range(range(0, 5)),
+                BuildError::EmptyEdits,
+            ),
+            (
+                Action::builder("Remove item.", ActionKind::QuickFix)
+                    .range(range(0, 10))
+                    .edit(TextEdit::delete(range(0, 5)))
+                    .edit(TextEdit::delete(range(3, 10))),
+                BuildError::OverlappingEdits,
+            ),
+            (
+                Action::builder("Remove item.", ActionKind::QuickFix)
+                    .range(range(0, 10))
+                    .document_edit("Cargo.toml", TextEdit::delete(range(0, 5)))
+                    .document_edit("Cargo.toml", TextEdit::delete(range(3, 10))),
+                BuildError::OverlappingEdits,
+            ),
+        ] {
+            assert_eq!(builder.build(), Err(expected_error));
+        }
+    }
+}
@@ -1,35 +1,143 @@
-//! ink! attribute completions.
+//! ink! attribute and ink! environment idiom completions.
 
+use crate::analysis::generated;
+use crate::analysis::hover::content;
+use crate::analysis::queries::Queries;
+use crate::analysis::registry::MacroRegistry;
 use crate::analysis::text_edit::TextEdit;
-use ink_analyzer_ir::syntax::{AstNode, SyntaxKind, SyntaxToken, TextRange, TextSize};
+use crate::Version;
+use ink_analyzer_ir::ast::HasName;
+use ink_analyzer_ir::syntax::{AstNode, AstToken, SyntaxKind, SyntaxToken, TextRange, TextSize};
 use ink_analyzer_ir::{
-    FromSyntax, InkArgKind, InkAttributeKind, InkFile, InkMacroKind, IsInkEntity,
+    ast, Constructor, Contract, FromInkAttribute, FromSyntax, InkArg, InkArgKind, InkAttribute,
+    InkAttributeKind, InkFile, InkMacroKind, IsInkEntity, IsInkFn, IsInkStruct, ItemAtOffset,
+    Message, Selector,
 };
 
 use super::utils;
 
 /// An ink! attribute completion item.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Completion {
     /// Label which identifies the completion.
     pub label: String,
     /// Range of identifier that is being completed.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
     pub range: TextRange,
     /// Replacement text for the completion.
     pub edit: TextEdit,
     /// Descriptive information about the completion.
     pub detail: Option<String>,
+    /// Identifier for lazily resolving the full documentation for the completion (if any),
+    /// see [`resolve_completion`] doc.
+    pub id: Option<String>,
+    /// The kind of the completion.
+    pub kind: CompletionKind,
+    /// Text used by clients to sort completions
+    /// (i.e. ranks ink! completions above generic Rust completions, and then by kind and label).
+    pub sort_text: String,
+    /// Text used by clients to filter completions (if it's different from the label).
+    pub filter_text: Option<String>,
+    /// Full documentation (in markdown format) for the completion (if any).
+    ///
+    /// This is only set for completions without an identifier,
+    /// documentation for other completions is resolved lazily (see [`Completion::id`]).
+    pub documentation: Option<String>,
+}
+
+/// The kind of a completion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum CompletionKind {
+    /// ink! attribute macro (e.g. `ink::contract`) or ink! attribute path segment (e.g. `ink`).
+    Macro,
+    /// ink! attribute argument (e.g. `message`).
+    Argument,
+    /// ink! attribute argument value (e.g. `true` for `derive`).
+    Value,
+    /// Code snippet (e.g. ink! environment idioms like `self.env().caller()`).
+    Snippet,
+}
+
+impl Completion {
+    /// Creates a completion for a text edit
+    /// (i.e. with sort and filter text derived from its kind, label and edit).
+    fn new(
+        kind: CompletionKind,
+        label: String,
+        edit: TextEdit,
+        detail: String,
+        id: Option<String>,
+    ) -> Self {
+        // Leading separators (e.g. `, ` for ink! attribute arguments) aren't typed by the user.
+        let filter_text = edit
+            .text
+            .trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        let rank = match kind {
+            CompletionKind::Value => 0,
+            CompletionKind::Argument => 1,
+            CompletionKind::Macro => 2,
+            CompletionKind::Snippet => 3,
+        };
+        Self {
+            // Leading zeros rank ink! completions above generic Rust completions
+            // (e.g. from rust-analyzer) in clients that merge completion lists.
+            sort_text: format!("0000{rank}_{label}"),
+            filter_text: (filter_text != label).then(|| filter_text.to_string()),
+            range: edit.range,
+            label,
+            edit,
+            detail: Some(detail),
+            id,
+            kind,
+            documentation: None,
+        }
+    }
 }
 
 /// Computes ink! attribute completions at the given offset.
-pub fn completions(file: &InkFile, offset: TextSize) -> Vec<Completion> {
+pub fn completions(
+    file: &InkFile,
+    queries: &Queries,
+    offset: TextSize,
+    version: Version,
+    registry: &MacroRegistry,
+) -> Vec<Completion> {
     let mut results = Vec::new();
 
     // Compute ink! attribute macro completions.
     macro_completions(&mut results, file, offset);
 
-    // Compute ink! attribute argument completions.
-    argument_completions(&mut results, file, offset);
+    // Compute registered ink! attribute macro completions.
+    registered_macro_completions(&mut results, file, offset, registry);
+
+    let is_registered_macro = file
+        .item_at_offset(offset)
+        .normalized_parent_ink_attr()
+        .map_or(false, |(ink_attr, ..)| {
+            registry.resolve(&ink_attr).is_some()
+        });
+    if is_registered_macro {
+        // Compute registered ink! attribute macro argument completions.
+        registered_argument_completions(&mut results, file, offset, registry);
+    } else {
+        // Compute ink! attribute argument completions.
+        argument_completions(&mut results, file, offset, version);
+
+        // Compute ink! attribute argument value completions.
+        argument_value_completions(&mut results, file, offset, version);
+    }
+
+    // Compute ink! environment idiom completions.
+    env_completions(&mut results, file, offset);
+
+    // Compute ink! environment method completions.
+    env_method_completions(&mut results, file, offset);
+
+    // Compute ink! e2e test scaffolding completions.
+    e2e_completions(&mut results, file, queries, offset);
 
     results
 }
@@ -203,12 +311,13 @@ pub fn macro_completions(results: &mut Vec<Completion>, file: &InkFile, offset:
                             },
                             macro_kind.macro_name()
                         );
-                        results.push(Completion {
-                            label: edit.clone(),
-                            range: edit_range,
-                            edit: TextEdit::replace(edit, edit_range),
-                            detail: Some(format!("ink! {macro_kind} attribute macro.")),
-                        });
+                        results.push(Completion::new(
+                            CompletionKind::Macro,
+                            edit.clone(),
+                            TextEdit::replace(edit, edit_range),
+                            format!("ink! {macro_kind} attribute macro."),
+                            Some(completion_id(&InkAttributeKind::Macro(macro_kind))),
+                        ));
                     }
                 } else if prev_token_is_left_bracket && !has_other_ink_macro_siblings {
                     // Suggests the `ink` and `ink_e2e` path segments if
@@ -229,16 +338,17 @@ pub fn macro_completions(results: &mut Vec<Completion>, file: &InkFile, offset:
                         if focused_token_prefix
                             .map_or(false, |prefix| ink_macro_crate_name.starts_with(prefix))
                         {
-                            results.push(Completion {
-                                label: ink_macro_crate_name.to_string(),
-                                range: edit_range,
-                                edit: TextEdit::replace_with_snippet(
+                            results.push(Completion::new(
+                                CompletionKind::Macro,
+                                ink_macro_crate_name.to_string(),
+                                TextEdit::replace_with_snippet(
                                     ink_macro_crate_name.to_string(),
                                     edit_range,
                                     ink_macro_crate_name_snippet.map(ToString::to_string),
                                 ),
-                                detail: Some(detail.to_string()),
-                            });
+                                detail.to_string(),
+                                None,
+                            ));
                         }
                     }
                 }
@@ -247,8 +357,150 @@ pub fn macro_completions(results: &mut Vec<Completion>, file: &InkFile, offset:
     }
 }
 
+/// Computes registered ink! attribute macro completions (see [`MacroRegistry`]) at the given offset.
+pub fn registered_macro_completions(
+    results: &mut Vec<Completion>,
+    file: &InkFile,
+    offset: TextSize,
+    registry: &MacroRegistry,
+) {
+    let item_at_offset = file.item_at_offset(offset);
+
+    // Only computes completions for attributes.
+    let Some((attr, ..)) = item_at_offset.normalized_parent_attr() else {
+        return;
+    };
+    let Some(path_start) = attr
+        .l_brack_token()
+        .map(|l_brack| l_brack.text_range().end())
+    else {
+        return;
+    };
+    let path_range = TextRange::new(path_start, offset.max(path_start));
+    if offset < path_start || !file.syntax().text_range().contains_range(path_range) {
+        return;
+    }
+
+    // Only computes completions if the offset is in the attribute path position
+    // (i.e. only path characters are allowed between the `[` delimiter and the offset).
+    let path_text = file.syntax().text().slice(path_range).to_string();
+    let prefix = path_text.trim_start();
+    if !prefix
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == ':')
+    {
+        return;
+    }
+
+    // Only suggest registered ink! attribute macros if the AST item has no other ink! attributes.
+    let has_other_ink_siblings = ink_analyzer_ir::parent_ast_item(attr.syntax())
+        .map_or(false, |item| {
+            ink_analyzer_ir::ink_attrs(item.syntax()).any(|it| it.syntax() != attr.syntax())
+        });
+    if has_other_ink_siblings {
+        return;
+    }
+
+    let edit_range = TextRange::new(offset - TextSize::of(prefix), offset);
+    let parent_item_kind = item_at_offset.normalized_parent_item_syntax_kind();
+    for definition in registry.iter() {
+        let path = definition.path();
+        let is_valid_scope = match parent_item_kind {
+            Some(kind) => definition.is_valid_scope(kind),
+            // Only suggests macros for unknown AST item types if a path prefix is present.
+            None => !prefix.is_empty(),
+        };
+        if is_valid_scope && path.starts_with(prefix) {
+            results.push(Completion::new(
+                CompletionKind::Macro,
+                path.clone(),
+                TextEdit::replace(path, edit_range),
+                format!("ink! {} attribute macro.", definition.name),
+                None,
+            ));
+        }
+    }
+}
+
+/// Computes registered ink! attribute macro argument completions (see [`MacroRegistry`]) at the given offset.
+pub fn registered_argument_completions(
+    results: &mut Vec<Completion>,
+    file: &InkFile,
+    offset: TextSize,
+    registry: &MacroRegistry,
+) {
+    let item_at_offset = file.item_at_offset(offset);
+
+    // Only computes completions for registered ink! attribute macros.
+    let Some((ink_attr, ..)) = item_at_offset.normalized_parent_ink_attr() else {
+        return;
+    };
+    let Some(definition) = registry.resolve(&ink_attr) else {
+        return;
+    };
+    let Some(focused_token) = item_at_offset.focused_token() else {
+        return;
+    };
+
+    // Only computes completions if the focused token is in an argument context.
+    let focused_token_is_delimiter = matches!(
+        focused_token.kind(),
+        SyntaxKind::L_PAREN | SyntaxKind::COMMA
+    );
+    let prev_non_trivia_token_is_delimiter = matches!(
+        item_at_offset
+            .prev_non_trivia_token()
+            .map(|prev_token| prev_token.kind()),
+        Some(SyntaxKind::L_PAREN | SyntaxKind::COMMA)
+    );
+    if !focused_token_is_delimiter && !prev_non_trivia_token_is_delimiter {
+        return;
+    }
+
+    // Removes the delimiter (i.e `(` and `,`) from text range if it's the focused token.
+    let (edit_range, prefix) = if focused_token_is_delimiter {
+        let focused_token_end = focused_token.text_range().end();
+        (TextRange::new(focused_token_end, focused_token_end), "")
+    } else {
+        (
+            focused_token.text_range(),
+            item_at_offset.focused_token_prefix().unwrap_or_default(),
+        )
+    };
+    // Inserts some space between the comma and the argument.
+    let space = if focused_token.kind() == SyntaxKind::COMMA {
+        " "
+    } else {
+        ""
+    };
+
+    // Filters out arguments that are already present (except the focused one).
+    let existing_args: Vec<String> = ink_attr
+        .args()
+        .iter()
+        .filter(|arg| !arg.text_range().contains_inclusive(offset))
+        .filter_map(|arg| arg.name().map(ToString::to_string))
+        .collect();
+    for arg_name in &definition.args {
+        if !existing_args.contains(arg_name) && arg_name.starts_with(prefix) {
+            results.push(Completion::new(
+                CompletionKind::Argument,
+                arg_name.clone(),
+                TextEdit::replace(format!("{space}{arg_name}"), edit_range),
+                format!("`#[{}]` attribute argument.", definition.path()),
+                None,
+            ));
+        }
+    }
+}
+
 /// Computes ink! attribute argument completions at the given offset.
-pub fn argument_completions(results: &mut Vec<Completion>, file: &InkFile, offset: TextSize) {
+pub fn argument_completions(
+    results: &mut Vec<Completion>,
+    file: &InkFile,
+    offset: TextSize,
+    version: Version,
+) {
     let item_at_offset = file.item_at_offset(offset);
 
     // Only computes completions if a focused token can be determined.
@@ -350,19 +602,688 @@ pub fn argument_completions(results: &mut Vec<Completion>, file: &InkFile, offse
                         Some(edit_range.end()),
                         Some(ink_attr.syntax()),
                     );
-                    results.push(Completion {
-                        label: edit.clone(),
-                        range: edit_range,
-                        edit: TextEdit::replace_with_snippet(
+                    results.push(Completion::new(
+                        CompletionKind::Argument,
+                        edit.clone(),
+                        TextEdit::replace_with_snippet(
                             format!("{prefix}{edit}"),
                             edit_range,
                             snippet.map(|snippet| format!("{prefix}{snippet}")),
                         ),
-                        detail: Some(format!("ink! {arg_kind} attribute argument.")),
-                    });
+                        format!("ink! {arg_kind} attribute argument."),
+                        Some(arg_completion_id(arg_kind, &ink_attr, version)),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Computes ink! attribute argument value completions at the given offset.
+pub fn argument_value_completions(
+    results: &mut Vec<Completion>,
+    file: &InkFile,
+    offset: TextSize,
+    version: Version,
+) {
+    let item_at_offset = file.item_at_offset(offset);
+
+    // Only computes completions for ink! attributes.
+    if let Some((ink_attr, ..)) = item_at_offset.normalized_parent_ink_attr() {
+        // Only computes completions if the offset is in the value position of an ink! attribute argument
+        // (i.e. after the `=` separator).
+        let Some(ink_arg) = ink_attr.args().iter().find(|arg| {
+            arg.meta().eq().map_or(false, |eq| {
+                let eq_end = eq.syntax().text_range().end();
+                eq_end <= offset
+                    && match arg.value() {
+                        Some(value) => offset <= value.text_range().end(),
+                        // Only whitespace is allowed between the `=` separator and the offset for empty values.
+                        None => {
+                            file.syntax()
+                                .text_range()
+                                .contains_range(TextRange::new(eq_end, offset))
+                                && file
+                                    .syntax()
+                                    .text()
+                                    .slice(TextRange::new(eq_end, offset))
+                                    .to_string()
+                                    .trim()
+                                    .is_empty()
+                        }
+                    }
+            })
+        }) else {
+            return;
+        };
+
+        // Replaces the current value (if any) or inserts at the offset,
+        // except for string values where the suggestion is inserted at the offset
+        // (i.e. additional contracts are appended to the list).
+        let (edit_range, prefix) = match ink_arg.value() {
+            Some(value)
+                if value.kind() != SyntaxKind::STRING
+                    && value.text_range().contains_inclusive(offset) =>
+            {
+                let range = value.text_range();
+                let prefix = file
+                    .syntax()
+                    .text()
+                    .slice(TextRange::new(range.start(), offset))
+                    .to_string();
+                (range, prefix)
+            }
+            _ => (TextRange::new(offset, offset), String::new()),
+        };
+
+        for (label, edit) in argument_value_suggestions(file, &ink_attr, ink_arg, offset) {
+            // Filters suggestions by the typed value prefix (if any).
+            if edit.starts_with(&prefix) {
+                results.push(Completion::new(
+                    CompletionKind::Value,
+                    label,
+                    TextEdit::replace(edit, edit_range),
+                    format!("ink! {} attribute argument value.", ink_arg.kind()),
+                    Some(arg_completion_id(*ink_arg.kind(), &ink_attr, version)),
+                ));
+            }
+        }
+    }
+}
+
+/// Returns value suggestions (i.e. label and edit text pairs) for an ink! attribute argument.
+fn argument_value_suggestions(
+    file: &InkFile,
+    ink_attr: &InkAttribute,
+    ink_arg: &InkArg,
+    offset: TextSize,
+) -> Vec<(String, String)> {
+    match ink_arg.kind() {
+        InkArgKind::HandleStatus | InkArgKind::Derive => ["true", "false"]
+            .into_iter()
+            .map(|value| (value.to_string(), value.to_string()))
+            .collect(),
+        InkArgKind::Selector => {
+            // Suggests the wildcard selector and the derived selector of the parent callable (if any).
+            let derived_selector = std::iter::once(ink_attr.clone())
+                .chain(ink_attr.siblings())
+                .find_map(|attr| {
+                    Message::cast(attr.clone())
+                        .and_then(|message| Selector::derive(&message))
+                        .or_else(|| {
+                            Constructor::cast(attr)
+                                .and_then(|constructor| Selector::derive(&constructor))
+                        })
+                });
+            std::iter::once("_".to_string())
+                .chain(derived_selector.map(|selector| format!("0x{:08x}", selector.into_be_u32())))
+                .map(|value| (value.clone(), value))
+                .collect()
+        }
+        InkArgKind::Env | InkArgKind::Environment => {
+            // Suggests the default environment and custom environments defined in the file (if any).
+            std::iter::once("ink::env::DefaultEnvironment".to_string())
+                .chain(file.syntax().descendants().filter_map(|node| {
+                    let impl_item = ast::Impl::cast(node)?;
+                    let trait_name = path_last_segment_name(impl_item.trait_()?)?;
+                    (trait_name == "Environment").then_some(())?;
+                    let ty_name = path_last_segment_name(impl_item.self_ty()?)?;
+                    let mod_path: Vec<String> = impl_item
+                        .syntax()
+                        .ancestors()
+                        .filter_map(ast::Module::cast)
+                        .filter_map(|module| module.name().map(|name| name.to_string()))
+                        .collect();
+                    Some(
+                        std::iter::once("crate".to_string())
+                            .chain(mod_path.into_iter().rev())
+                            .chain(std::iter::once(ty_name))
+                            .collect::<Vec<String>>()
+                            .join("::"),
+                    )
+                }))
+                .map(|value| (value.clone(), value))
+                .collect()
+        }
+        InkArgKind::AdditionalContracts => {
+            // Suggests `Cargo.toml` paths for crates whose contract references (i.e. `<crate>::<Contract>Ref`)
+            // are used in the file, excluding those that are already listed.
+            let string_value_range = ink_arg
+                .value()
+                .filter(|value| value.kind() == SyntaxKind::STRING)
+                .map(|value| value.text_range());
+            // Only inserts into existing string values if the offset is between the quotes.
+            if string_value_range.map_or(false, |range| {
+                offset <= range.start() || range.end() <= offset
+            }) {
+                return Vec::new();
+            }
+            let current_value = ink_arg.value().and_then(|value| value.as_string());
+            let mut crate_names: Vec<String> = Vec::new();
+            for path in file.syntax().descendants().filter_map(ast::Path::cast) {
+                let segments: Vec<String> = path
+                    .segments()
+                    .map(|segment| segment.syntax().to_string())
+                    .collect();
+                // NOTE: `segments` yields all segments of the full path (e.g. `adder::AdderRef::new`),
+                // so the contract reference can be followed by associated items.
+                if let [crate_name, rest @ ..] = segments.as_slice() {
+                    if rest.iter().any(|segment| segment.ends_with("Ref"))
+                        && !matches!(
+                            crate_name.as_str(),
+                            "ink" | "ink_env" | "ink_e2e" | "self" | "super" | "crate" | "Self"
+                        )
+                        && !crate_names.contains(crate_name)
+                        && current_value.as_ref().map_or(true, |value| {
+                            !value.contains(&format!("{crate_name}/Cargo.toml"))
+                        })
+                    {
+                        crate_names.push(crate_name.to_string());
+                    }
                 }
             }
+
+            crate_names
+                .into_iter()
+                .map(|crate_name| {
+                    let manifest_path = format!("{crate_name}/Cargo.toml");
+                    let edit = if string_value_range.is_some() {
+                        // Separates the new path from the preceding path (if any).
+                        let is_after_quote_or_whitespace = file
+                            .syntax()
+                            .text()
+                            .char_at(offset - TextSize::from(1))
+                            .map_or(true, |c| c == '"' || c.is_whitespace());
+                        if is_after_quote_or_whitespace {
+                            manifest_path.clone()
+                        } else {
+                            format!(" {manifest_path}")
+                        }
+                    } else {
+                        format!("\"{manifest_path}\"")
+                    };
+                    (manifest_path, edit)
+                })
+                .collect()
+        }
+        InkArgKind::KeepAttr => {
+            let string_value_range = ink_arg
+                .value()
+                .filter(|value| value.kind() == SyntaxKind::STRING)
+                .map(|value| value.text_range());
+            // Only inserts into existing string values if the offset is between the quotes.
+            if string_value_range.map_or(false, |range| {
+                offset <= range.start() || range.end() <= offset
+            }) {
+                return Vec::new();
+            }
+            // Suggests commonly kept attributes, excluding those that are already listed.
+            let current_entries = ink_arg.as_keep_attr_list().unwrap_or_default();
+            ["cfg", "derive", "allow"]
+                .into_iter()
+                .filter(|name| !current_entries.iter().any(|entry| entry.text() == *name))
+                .map(|name| {
+                    let edit = if string_value_range.is_some() {
+                        // Separates the new entry from the preceding entry (if any).
+                        let is_after_quote_comma_or_whitespace = file
+                            .syntax()
+                            .text()
+                            .char_at(offset - TextSize::from(1))
+                            .map_or(true, |c| c == '"' || c == ',' || c.is_whitespace());
+                        if is_after_quote_comma_or_whitespace {
+                            name.to_string()
+                        } else {
+                            format!(",{name}")
+                        }
+                    } else {
+                        format!("\"{name}\"")
+                    };
+                    (name.to_string(), edit)
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the name of the last path segment for a path type (if any).
+fn path_last_segment_name(ty: ast::Type) -> Option<String> {
+    match ty {
+        ast::Type::PathType(path_type) => path_type
+            .path()?
+            .segment()?
+            .name_ref()
+            .map(|name_ref| name_ref.to_string()),
+        _ => None,
+    }
+}
+
+/// Computes ink! environment idiom completions (e.g. `self.env().caller()`) at the given offset
+/// (i.e. at the start of statements in the bodies of `fn` items in ink! contracts).
+pub fn env_completions(results: &mut Vec<Completion>, file: &InkFile, offset: TextSize) {
+    let item_at_offset = file.item_at_offset(offset);
+
+    // Only computes completions for (partially typed) expressions at the start of statements
+    // in the bodies of `fn` items.
+    let Some((fn_item, edit_range, prefix)) = stmt_start_context(&item_at_offset, offset) else {
+        return;
+    };
+
+    // Only computes completions for `fn` items in ink! contracts
+    // (ink! e2e tests use e2e test scaffolding completions instead, see `e2e_completions`).
+    let Some(contract) = ink_analyzer_ir::ink_ancestors::<Contract>(fn_item.syntax()).next() else {
+        return;
+    };
+    if is_e2e_test_fn(&fn_item) {
+        return;
+    }
+
+    // Uses `self.env()` for methods and `Self::env()` for associated functions (i.e. static contexts).
+    let has_self_param = fn_item
+        .param_list()
+        .and_then(|param_list| param_list.self_param())
+        .is_some();
+    let env = if has_self_param {
+        "self.env()"
+    } else {
+        "Self::env()"
+    };
+
+    let mut suggestions = vec![
+        (
+            env.to_string(),
+            None,
+            "Returns the ink! environment.".to_string(),
+        ),
+        (
+            format!("{env}.caller()"),
+            None,
+            "Returns the address of the caller of the executed contract.".to_string(),
+        ),
+    ];
+
+    // Suggests emitting the contract's ink! events.
+    for event in contract.events() {
+        if let Some(struct_item) = event.struct_item() {
+            if let Some(name) = struct_item.name() {
+                let fields: Vec<String> = match struct_item.field_list() {
+                    Some(ast::FieldList::RecordFieldList(field_list)) => field_list
+                        .fields()
+                        .filter_map(|field| field.name().map(|name| name.to_string()))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                let (edit, snippet) = if fields.is_empty() {
+                    let edit = format!("{env}.emit_event({name} {{}})");
+                    (edit, None)
+                } else {
+                    (
+                        format!("{env}.emit_event({name} {{ {} }})", fields.join(", ")),
+                        Some(format!(
+                            "{env}.emit_event({name} {{ {} }})",
+                            fields
+                                .iter()
+                                .enumerate()
+                                .map(|(idx, field)| format!("{field}: ${{{}:{field}}}", idx + 1))
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        )),
+                    )
+                };
+                suggestions.push((edit, snippet, format!("Emits the `{name}` ink! event.")));
+            }
+        }
+    }
+
+    // Filters suggestions by the focused prefix (if any) and adds completions to accumulator.
+    for (edit, snippet, detail) in suggestions {
+        if edit.starts_with(prefix) {
+            results.push(Completion::new(
+                CompletionKind::Snippet,
+                edit.clone(),
+                TextEdit::replace_with_snippet(edit, edit_range, snippet),
+                detail,
+                None,
+            ));
+        }
+    }
+}
+
+/// Computes ink! environment method completions (e.g. `caller()` for `self.env().ca`) at the given offset
+/// (i.e. for method calls on the ink! environment in ink! contracts, see `generated::ENV_METHODS`).
+pub fn env_method_completions(results: &mut Vec<Completion>, file: &InkFile, offset: TextSize) {
+    let item_at_offset = file.item_at_offset(offset);
+
+    // Only computes completions if a focused token can be determined.
+    let Some(focused_token) = item_at_offset.focused_token() else {
+        return;
+    };
+
+    // Only computes completions for (partially typed) method names (e.g. `self.env().ca`)
+    // or right after the `.` (e.g. `self.env().`).
+    let (parent, edit_range, prefix) = match focused_token.kind() {
+        SyntaxKind::DOT => (focused_token.parent(), TextRange::new(offset, offset), ""),
+        SyntaxKind::IDENT => (
+            focused_token
+                .parent()
+                .filter(|parent| ast::NameRef::can_cast(parent.kind()))
+                .and_then(|name_ref| name_ref.parent()),
+            focused_token.text_range(),
+            item_at_offset.focused_token_prefix().unwrap_or_default(),
+        ),
+        _ => return,
+    };
+    let Some(parent) = parent else {
+        return;
+    };
+
+    // Only computes completions for method calls on the ink! environment (i.e. `self.env()` or `Self::env()`).
+    let (receiver, has_arg_list) = match ast::FieldExpr::cast(parent.clone()) {
+        Some(field_expr) => (field_expr.expr(), false),
+        None => match ast::MethodCallExpr::cast(parent.clone()) {
+            Some(call) => (call.receiver(), true),
+            None => return,
+        },
+    };
+    if !receiver.map_or(false, |receiver| utils::is_env_call(&receiver)) {
+        return;
+    }
+
+    // Only computes completions in ink! contracts.
+    if ink_analyzer_ir::ink_ancestors::<Contract>(&parent)
+        .next()
+        .is_none()
+    {
+        return;
+    }
+
+    for method in generated::ENV_METHODS {
+        if method.name.starts_with(prefix) {
+            // Only completes the method name if the method call already has an argument list.
+            let (edit, snippet) = if has_arg_list {
+                (method.name.to_string(), None)
+            } else {
+                let (call, snippet) = method.call();
+                let snippet = (!method.params.is_empty()).then_some(snippet);
+                (call, snippet)
+            };
+            let mut completion = Completion::new(
+                CompletionKind::Snippet,
+                edit.clone(),
+                TextEdit::replace_with_snippet(edit, edit_range, snippet),
+                method.signature(),
+                None,
+            );
+            completion.documentation = Some(method.doc());
+            results.push(completion);
+        }
+    }
+}
+
+/// Computes ink! e2e test scaffolding completions (e.g. `ink_e2e::alice()`, `client.instantiate(...)`
+/// and `client.call(...)`) at the given offset
+/// (i.e. at the start of statements in the bodies of ink! e2e test `fn` items).
+pub fn e2e_completions(
+    results: &mut Vec<Completion>,
+    file: &InkFile,
+    queries: &Queries,
+    offset: TextSize,
+) {
+    let item_at_offset = file.item_at_offset(offset);
+
+    // Only computes completions for (partially typed) expressions at the start of statements
+    // in the bodies of `fn` items.
+    let Some((fn_item, edit_range, prefix)) = stmt_start_context(&item_at_offset, offset) else {
+        return;
+    };
+
+    // Only computes completions for ink! e2e test `fn` items.
+    if !is_e2e_test_fn(&fn_item) {
+        return;
+    }
+
+    let mut suggestions = vec![(
+        "ink_e2e::alice()".to_string(),
+        None,
+        "Returns the `alice` test account keypair.".to_string(),
+    )];
+
+    // Uses the ink! contract that contains the ink! e2e test (if any),
+    // otherwise the first ink! contract in the file (i.e. for e2e tests in a separate module).
+    let contract = ink_analyzer_ir::ink_ancestors::<Contract>(fn_item.syntax())
+        .next()
+        .or_else(|| queries.index(file).contracts().first().cloned());
+    let contract_names = contract.as_ref().and_then(|contract| {
+        let module_name = contract.module()?.name()?.to_string();
+        let storage_name = contract.storage()?.struct_item()?.name()?.to_string();
+        let ref_name = generated::contract_ref_name(contract)?;
+        Some((module_name, storage_name, ref_name))
+    });
+    if let Some((contract, (module_name, storage_name, ref_name))) =
+        contract.as_ref().zip(contract_names)
+    {
+        // Returns the plain text and snippet for the arguments of the ink! callable,
+        // with snippet placeholders starting at the given index.
+        let callable_args = |fn_item: &ast::Fn, start_idx: usize| -> (String, String) {
+            let names: Vec<String> = fn_item
+                .param_list()
+                .map(|param_list| {
+                    param_list
+                        .params()
+                        .filter_map(|param| param.pat().map(|pat| pat.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            (
+                names.join(", "),
+                names
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, name)| format!("${{{}:{name}}}", idx + start_idx))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            )
+        };
+
+        // Suggests instantiating the ink! contract with each of its ink! constructors.
+        for fn_item in contract.constructors().iter().filter_map(IsInkFn::fn_item) {
+            if let Some(name) = fn_item.name() {
+                let (args, args_snippet) = callable_args(fn_item, 1);
+                let instantiate = |args: &str| {
+                    format!(
+                        "client.instantiate(\"{module_name}\", &ink_e2e::alice(), \
+                        {ref_name}::{name}({args}), 0, None).await"
+                    )
+                };
+                suggestions.push((
+                    instantiate(&args),
+                    (!args.is_empty()).then(|| instantiate(&args_snippet)),
+                    format!(
+                        "Instantiates the `{storage_name}` ink! contract \
+                        using the `{name}` ink! constructor."
+                    ),
+                ));
+            }
+        }
+
+        // Suggests calling each ink! message of the ink! contract.
+        for fn_item in contract.messages().iter().filter_map(IsInkFn::fn_item) {
+            if let Some(name) = fn_item.name() {
+                let (args, args_snippet) = callable_args(fn_item, 2);
+                let call = |account_id: &str, args: &str| {
+                    format!(
+                        "client.call(&ink_e2e::alice(), \
+                        build_message::<{ref_name}>({account_id}.clone())\
+                        .call(|{module_name}| {module_name}.{name}({args})), 0, None).await"
+                    )
+                };
+                suggestions.push((
+                    call("contract_account_id", &args),
+                    Some(call("${1:contract_account_id}", &args_snippet)),
+                    format!(
+                        "Calls the `{name}` ink! message of the `{storage_name}` ink! contract."
+                    ),
+                ));
+            }
+        }
+    }
+
+    // Filters suggestions by the focused prefix (if any) and adds completions to accumulator.
+    for (edit, snippet, detail) in suggestions {
+        if edit.starts_with(prefix) {
+            results.push(Completion::new(
+                CompletionKind::Snippet,
+                edit.clone(),
+                TextEdit::replace_with_snippet(edit, edit_range, snippet),
+                detail,
+                None,
+            ));
+        }
+    }
+}
+
+/// Returns true if the `fn` item is an ink! e2e test.
+fn is_e2e_test_fn(fn_item: &ast::Fn) -> bool {
+    ink_analyzer_ir::ink_attrs(fn_item.syntax())
+        .any(|attr| *attr.kind() == InkAttributeKind::Macro(InkMacroKind::E2ETest))
+}
+
+/// Returns the `fn` item, the edit range and the focused prefix (if any) for expression completions
+/// at the given offset (i.e. for (partially typed) expressions at the start of statements
+/// in the bodies of `fn` items).
+fn stmt_start_context(
+    item_at_offset: &ItemAtOffset,
+    offset: TextSize,
+) -> Option<(ast::Fn, TextRange, &str)> {
+    // Only computes context if a focused token can be determined.
+    let focused_token = item_at_offset.focused_token()?;
+
+    // Only computes context in the bodies of `fn` items.
+    let fn_item =
+        ink_analyzer_ir::closest_ancestor_ast_type::<SyntaxToken, ast::Fn>(focused_token)?;
+    let is_in_fn_body = fn_item.body().map_or(false, |body| {
+        let body_range = body.syntax().text_range();
+        body_range.start() < offset && offset < body_range.end()
+    });
+    if !is_in_fn_body {
+        return None;
+    }
+
+    // Only computes context for (partially typed) expressions at the start of statements.
+    let focused_token_is_expr_prefix = matches!(
+        focused_token.kind(),
+        SyntaxKind::IDENT | SyntaxKind::SELF_KW | SyntaxKind::SELF_TYPE_KW
+    );
+    let focused_token_is_stmt_delimiter = matches!(
+        focused_token.kind(),
+        SyntaxKind::L_CURLY | SyntaxKind::SEMICOLON
+    );
+    let prev_non_trivia_token_is_stmt_delimiter = matches!(
+        item_at_offset
+            .prev_non_trivia_token()
+            .map(|prev_token| prev_token.kind()),
+        Some(SyntaxKind::L_CURLY | SyntaxKind::SEMICOLON | SyntaxKind::R_CURLY)
+    );
+    let (edit_range, prefix) = if focused_token_is_stmt_delimiter {
+        (TextRange::new(offset, offset), "")
+    } else if prev_non_trivia_token_is_stmt_delimiter {
+        if focused_token_is_expr_prefix {
+            (
+                focused_token.text_range(),
+                item_at_offset.focused_token_prefix().unwrap_or_default(),
+            )
+        } else if focused_token.kind().is_trivia() || focused_token.kind() == SyntaxKind::R_CURLY {
+            (TextRange::new(offset, offset), "")
+        } else {
+            return None;
+        }
+    } else {
+        return None;
+    };
+
+    Some((fn_item, edit_range, prefix))
+}
+
+/// Returns the full documentation (in markdown format) for the completion with the given identifier (if any).
+///
+/// Completion identifiers are the ink! attribute macro path (e.g. `ink::contract`)
+/// or the ink! attribute argument wrapped in the ink! attribute (e.g. `ink(message)`),
+/// optionally followed by a `#` separated entity and ink! version qualifier for documentation
+/// that depends on the target ink! entity (e.g. `ink(payable)#constructor-v4`).
+pub fn resolve_completion(id: &str) -> Option<String> {
+    let (path, qualifier) = match id.split_once('#') {
+        Some((path, qualifier)) => (path, Some(qualifier)),
+        None => (id, None),
+    };
+    let attr_kind = if let Some(arg_name) = path
+        .strip_prefix("ink(")
+        .and_then(|suffix| suffix.strip_suffix(')'))
+    {
+        InkAttributeKind::Arg(InkArgKind::from(arg_name))
+    } else {
+        InkAttributeKind::Macro(InkMacroKind::from(path.split_once("::")?))
+    };
+    let doc = content::doc(&attr_kind);
+    if doc.is_empty() {
+        return None;
+    }
+
+    match qualifier {
+        Some(qualifier) => {
+            let entity_doc = entity_doc(&attr_kind, qualifier)?;
+            Some(format!("{doc}\n\n{entity_doc}"))
+        }
+        None => Some(doc.to_string()),
+    }
+}
+
+/// Returns the completion identifier for an ink! attribute kind, see [`resolve_completion`] doc.
+fn completion_id(attr_kind: &InkAttributeKind) -> String {
+    match attr_kind {
+        InkAttributeKind::Arg(arg_kind) => format!("ink({arg_kind})"),
+        InkAttributeKind::Macro(macro_kind) => {
+            format!("{}::{}", macro_kind.crate_name(), macro_kind.macro_name())
+        }
+    }
+}
+
+/// Returns the completion identifier for an ink! attribute argument in the context of the ink! attribute
+/// (i.e. qualified by the target ink! entity and ink! version if the documentation depends on them),
+/// see [`resolve_completion`] doc.
+fn arg_completion_id(arg_kind: InkArgKind, ink_attr: &InkAttribute, version: Version) -> String {
+    let id = completion_id(&InkAttributeKind::Arg(arg_kind));
+    match arg_kind {
+        // How `payable` ink! constructors can reject an instantiation differs between ink! versions
+        // (i.e. ink! 4.x constructors can be fallible).
+        InkArgKind::Payable => {
+            let is_constructor = ink_attr.syntax().parent().map_or(false, |parent| {
+                ink_analyzer_ir::ink_arg_by_kind(&parent, InkArgKind::Constructor).is_some()
+            });
+            if is_constructor {
+                let version = match version {
+                    Version::V3 => "v3",
+                    Version::V4 => "v4",
+                };
+                format!("{id}#constructor-{version}")
+            } else {
+                id
+            }
+        }
+        _ => id,
+    }
+}
+
+/// Returns the ink! entity specific documentation (if any) for the ink! attribute kind and
+/// the completion identifier qualifier, see [`resolve_completion`] doc.
+fn entity_doc(attr_kind: &InkAttributeKind, qualifier: &str) -> Option<&'static str> {
+    match (attr_kind, qualifier) {
+        (InkAttributeKind::Arg(InkArgKind::Payable), "constructor-v3") => {
+            Some(content::payable_constructor_doc(Version::V3))
+        }
+        (InkAttributeKind::Arg(InkArgKind::Payable), "constructor-v4") => {
+            Some(content::payable_constructor_doc(Version::V4))
         }
+        _ => None,
     }
 }
 
@@ -1138,7 +2059,7 @@ mod tests {
             let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
 
             let mut results = Vec::new();
-            argument_completions(&mut results, &InkFile::parse(code), offset);
+            argument_completions(&mut results, &InkFile::parse(code), offset, Version::V4);
 
             assert_eq!(
                 results
@@ -1159,4 +2080,811 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn argument_value_completions_works() {
+        for (code, pat, expected_results) in [
+            // (code, pat, [(edit, pat_start, pat_end)]) where:
+            // code = source code,
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // edit = the text that will inserted (represented without whitespace for simplicity),
+            // pat_start = substring used to find the start of the edit offset (see `test_utils::parse_offset_at` doc),
+            // pat_end = substring used to find the end of the edit offset (see `test_utils::parse_offset_at` doc).
+
+            // Non value context.
+            (
+                r#"
+                    #[ink(message, selector)]
+                    pub fn my_message(&self) {}
+                "#,
+                Some("selector"),
+                vec![],
+            ),
+            // Selector.
+            (
+                r#"
+                    #[ink(message, selector = )]
+                    pub fn my_message(&self) {}
+                "#,
+                Some("= "),
+                vec![
+                    ("_", Some("= "), Some("= ")),
+                    ("0x6a469e03", Some("= "), Some("= ")),
+                ],
+            ),
+            (
+                r#"
+                    #[ink(constructor)]
+                    #[ink(selector = )]
+                    pub fn new() -> Self {}
+                "#,
+                Some("= "),
+                vec![
+                    ("_", Some("= "), Some("= ")),
+                    ("0x9bae9d5e", Some("= "), Some("= ")),
+                ],
+            ),
+            // Handle status.
+            (
+                r#"
+                    #[ink(extension = 1, handle_status = t)]
+                    fn my_extension();
+                "#,
+                Some("= t"),
+                vec![("true", Some("<-t)"), Some("= t"))],
+            ),
+            // Derive.
+            (
+                r#"
+                    #[ink::storage_item(derive = )]
+                    struct MyStorageItem {}
+                "#,
+                Some("= "),
+                vec![
+                    ("true", Some("= "), Some("= ")),
+                    ("false", Some("= "), Some("= ")),
+                ],
+            ),
+            // Environment.
+            (
+                r#"
+                    #[derive(Clone)]
+                    pub enum MyEnvironment {}
+
+                    impl ink::env::Environment for MyEnvironment {}
+
+                    #[ink::contract(env = )]
+                    mod my_contract {}
+                "#,
+                Some("env = "),
+                vec![
+                    (
+                        "ink::env::DefaultEnvironment",
+                        Some("env = "),
+                        Some("env = "),
+                    ),
+                    ("crate::MyEnvironment", Some("env = "), Some("env = ")),
+                ],
+            ),
+            // Keep attributes.
+            (
+                r#"
+                    #[ink::contract(keep_attr = )]
+                    mod my_contract {}
+                "#,
+                Some("keep_attr = "),
+                vec![
+                    (r#""cfg""#, Some("keep_attr = "), Some("keep_attr = ")),
+                    (r#""derive""#, Some("keep_attr = "), Some("keep_attr = ")),
+                    (r#""allow""#, Some("keep_attr = "), Some("keep_attr = ")),
+                ],
+            ),
+            (
+                r#"
+                    #[ink::contract(keep_attr = "cfg,")]
+                    mod my_contract {}
+                "#,
+                Some("cfg,"),
+                vec![
+                    ("derive", Some("cfg,"), Some("cfg,")),
+                    ("allow", Some("cfg,"), Some("cfg,")),
+                ],
+            ),
+            (
+                r#"
+                    #[ink::contract(keep_attr = "foo")]
+                    mod my_contract {}
+                "#,
+                Some("foo"),
+                vec![
+                    (",cfg", Some("foo"), Some("foo")),
+                    (",derive", Some("foo"), Some("foo")),
+                    (",allow", Some("foo"), Some("foo")),
+                ],
+            ),
+            // Additional contracts.
+            (
+                r#"
+                    #[ink_e2e::test(additional_contracts = )]
+                    async fn it_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+                        let constructor = adder::AdderRef::new();
+                    }
+                "#,
+                Some("additional_contracts = "),
+                vec![(
+                    r#""adder/Cargo.toml""#,
+                    Some("additional_contracts = "),
+                    Some("additional_contracts = "),
+                )],
+            ),
+            (
+                r#"
+                    #[ink_e2e::test(additional_contracts = "adder/Cargo.toml")]
+                    async fn it_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+                        let adder = adder::AdderRef::new();
+                        let flipper = flipper::FlipperRef::new();
+                    }
+                "#,
+                Some("adder/Cargo.toml"),
+                vec![(
+                    "flipper/Cargo.toml",
+                    Some("adder/Cargo.toml"),
+                    Some("adder/Cargo.toml"),
+                )],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
+
+            let mut results = Vec::new();
+            argument_value_completions(&mut results, &InkFile::parse(code), offset, Version::V4);
+
+            assert_eq!(
+                results
+                    .into_iter()
+                    .map(|completion| (remove_whitespace(completion.edit.text), completion.range))
+                    .collect::<Vec<(String, TextRange)>>(),
+                expected_results
+                    .into_iter()
+                    .map(|(edit, pat_start, pat_end)| (
+                        remove_whitespace(edit.to_string()),
+                        TextRange::new(
+                            TextSize::from(parse_offset_at(code, pat_start).unwrap() as u32),
+                            TextSize::from(parse_offset_at(code, pat_end).unwrap() as u32)
+                        )
+                    ))
+                    .collect::<Vec<(String, TextRange)>>(),
+                "code: {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn env_completions_works() {
+        let contract = |body: &str| {
+            format!(
+                r#"
+                    #[ink::contract]
+                    mod my_contract {{
+                        #[ink(event)]
+                        pub struct Transfer {{
+                            from: AccountId,
+                            value: Balance,
+                        }}
+
+                        impl MyContract {{
+                            {body}
+                        }}
+                    }}
+                "#
+            )
+        };
+        for (code, pat, expected_results) in [
+            // (code, pat, [(edit, pat_start, pat_end)]) where:
+            // code = source code,
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // edit = the text that will inserted (represented without whitespace for simplicity),
+            // pat_start = substring used to find the start of the edit offset (see `test_utils::parse_offset_at` doc),
+            // pat_end = substring used to find the end of the edit offset (see `test_utils::parse_offset_at` doc).
+
+            // Outside ink! contract.
+            (String::from("fn my_fn(&self) { }"), Some("{"), vec![]),
+            // Outside `fn` body.
+            (
+                contract("#[ink(message)] pub fn my_message(&self) {}"),
+                Some("my_mess"),
+                vec![],
+            ),
+            // Not at the start of a statement.
+            (
+                contract("#[ink(message)] pub fn my_message(&self) { let x = }"),
+                Some("= "),
+                vec![],
+            ),
+            // Methods.
+            (
+                contract("#[ink(message)] pub fn my_message(&self) { }"),
+                Some("(&self) {"),
+                vec![
+                    ("self.env()", Some("(&self) {"), Some("(&self) {")),
+                    ("self.env().caller()", Some("(&self) {"), Some("(&self) {")),
+                    (
+                        "self.env().emit_event(Transfer { from, value })",
+                        Some("(&self) {"),
+                        Some("(&self) {"),
+                    ),
+                ],
+            ),
+            (
+                contract("#[ink(message)] pub fn my_message(&self) { let x = 1; se }"),
+                Some("1; se"),
+                vec![
+                    ("self.env()", Some("<-se }"), Some("1; se")),
+                    ("self.env().caller()", Some("<-se }"), Some("1; se")),
+                    (
+                        "self.env().emit_event(Transfer { from, value })",
+                        Some("<-se }"),
+                        Some("1; se"),
+                    ),
+                ],
+            ),
+            // Associated functions (i.e. static contexts).
+            (
+                contract("#[ink(constructor)] pub fn new() -> Self { Self }"),
+                Some("Self { Self"),
+                vec![
+                    ("Self::env()", Some("<-Self }"), Some("Self { Self")),
+                    (
+                        "Self::env().caller()",
+                        Some("<-Self }"),
+                        Some("Self { Self"),
+                    ),
+                    (
+                        "Self::env().emit_event(Transfer { from, value })",
+                        Some("<-Self }"),
+                        Some("Self { Self"),
+                    ),
+                ],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(&code, pat).unwrap() as u32);
+
+            let mut results = Vec::new();
+            env_completions(&mut results, &InkFile::parse(&code), offset);
+
+            assert_eq!(
+                results
+                    .into_iter()
+                    .map(|completion| (remove_whitespace(completion.edit.text), completion.range))
+                    .collect::<Vec<(String, TextRange)>>(),
+                expected_results
+                    .into_iter()
+                    .map(|(edit, pat_start, pat_end)| (
+                        remove_whitespace(edit.to_string()),
+                        TextRange::new(
+                            TextSize::from(parse_offset_at(&code, pat_start).unwrap() as u32),
+                            TextSize::from(parse_offset_at(&code, pat_end).unwrap() as u32)
+                        )
+                    ))
+                    .collect::<Vec<(String, TextRange)>>(),
+                "code: {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn env_method_completions_works() {
+        let contract = |body: &str| {
+            format!(
+                r#"
+                    #[ink::contract]
+                    mod my_contract {{
+                        impl MyContract {{
+                            {body}
+                        }}
+                    }}
+                "#
+            )
+        };
+        for (code, pat, expected_results) in [
+            // (code, pat, [(edit, pat_start, pat_end)]) where:
+            // code = source code,
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // edit = the text that will inserted (represented without whitespace for simplicity),
+            // pat_start = substring used to find the start of the edit offset (see `test_utils::parse_offset_at` doc),
+            // pat_end = substring used to find the end of the edit offset (see `test_utils::parse_offset_at` doc).
+
+            // Outside ink! contract.
+            (
+                String::from("fn my_fn(&self) { self.env().ca }"),
+                Some("env().ca"),
+                vec![],
+            ),
+            // Not an ink! environment method call.
+            (
+                contract("#[ink(message)] pub fn my_message(&self) { self.ca }"),
+                Some("self.ca"),
+                vec![],
+            ),
+            // ink! environment method calls.
+            (
+                contract("#[ink(message)] pub fn my_message(&self) { self.env().ca }"),
+                Some("env().ca"),
+                vec![
+                    ("caller()", Some("<-ca }"), Some("env().ca")),
+                    ("caller_is_origin()", Some("<-ca }"), Some("env().ca")),
+                ],
+            ),
+            (
+                contract("#[ink(constructor)] pub fn new() -> Self { Self::env().tra }"),
+                Some("env().tra"),
+                vec![
+                    (
+                        "transfer(destination, value)",
+                        Some("<-tra }"),
+                        Some("env().tra"),
+                    ),
+                    ("transferred_value()", Some("<-tra }"), Some("env().tra")),
+                ],
+            ),
+            // Existing argument list.
+            (
+                contract("#[ink(message)] pub fn my_message(&self) { self.env().caller(); }"),
+                Some("env().ca"),
+                vec![
+                    ("caller", Some("<-caller()"), Some("env().caller")),
+                    ("caller_is_origin", Some("<-caller()"), Some("env().caller")),
+                ],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(&code, pat).unwrap() as u32);
+
+            let mut results = Vec::new();
+            env_method_completions(&mut results, &InkFile::parse(&code), offset);
+
+            assert_eq!(
+                results
+                    .into_iter()
+                    .map(|completion| (remove_whitespace(completion.edit.text), completion.range))
+                    .collect::<Vec<(String, TextRange)>>(),
+                expected_results
+                    .into_iter()
+                    .map(|(edit, pat_start, pat_end)| (
+                        remove_whitespace(edit.to_string()),
+                        TextRange::new(
+                            TextSize::from(parse_offset_at(&code, pat_start).unwrap() as u32),
+                            TextSize::from(parse_offset_at(&code, pat_end).unwrap() as u32)
+                        )
+                    ))
+                    .collect::<Vec<(String, TextRange)>>(),
+                "code: {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn e2e_completions_works() {
+        let e2e_test = |body: &str| {
+            format!(
+                r#"
+                    #[ink::contract]
+                    mod flipper {{
+                        #[ink(storage)]
+                        pub struct Flipper {{
+                            value: bool,
+                        }}
+
+                        impl Flipper {{
+                            #[ink(constructor)]
+                            pub fn new(init_value: bool) -> Self {{
+                                Self {{ value: init_value }}
+                            }}
+
+                            #[ink(message)]
+                            pub fn get(&self) -> bool {{
+                                self.value
+                            }}
+                        }}
+
+                        mod e2e_tests {{
+                            {body}
+                        }}
+                    }}
+                "#
+            )
+        };
+        for (code, pat, expected_results) in [
+            // (code, pat, [(edit, pat_start, pat_end)]) where:
+            // code = source code,
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // edit = the text that will inserted (represented without whitespace for simplicity),
+            // pat_start = substring used to find the start of the edit offset (see `test_utils::parse_offset_at` doc),
+            // pat_end = substring used to find the end of the edit offset (see `test_utils::parse_offset_at` doc).
+
+            // Not an ink! e2e test.
+            (
+                e2e_test("#[test] fn it_works() { }"),
+                Some("it_works() {"),
+                vec![],
+            ),
+            // Not at the start of a statement.
+            (
+                e2e_test("#[ink_e2e::test] async fn it_works() { let x = }"),
+                Some("= "),
+                vec![],
+            ),
+            // ink! e2e tests.
+            (
+                e2e_test("#[ink_e2e::test] async fn it_works() { }"),
+                Some("it_works() {"),
+                vec![
+                    ("ink_e2e::alice()", Some("it_works() {"), Some("it_works() {")),
+                    (
+                        r#"client.instantiate("flipper", &ink_e2e::alice(), FlipperRef::new(init_value), 0, None).await"#,
+                        Some("it_works() {"),
+                        Some("it_works() {"),
+                    ),
+                    (
+                        "client.call(&ink_e2e::alice(), build_message::<FlipperRef>(contract_account_id.clone()).call(|flipper| flipper.get()), 0, None).await",
+                        Some("it_works() {"),
+                        Some("it_works() {"),
+                    ),
+                ],
+            ),
+            (
+                e2e_test("#[ink_e2e::test] async fn it_works() { let x = 1; cl }"),
+                Some("1; cl"),
+                vec![
+                    (
+                        r#"client.instantiate("flipper", &ink_e2e::alice(), FlipperRef::new(init_value), 0, None).await"#,
+                        Some("<-cl }"),
+                        Some("1; cl"),
+                    ),
+                    (
+                        "client.call(&ink_e2e::alice(), build_message::<FlipperRef>(contract_account_id.clone()).call(|flipper| flipper.get()), 0, None).await",
+                        Some("<-cl }"),
+                        Some("1; cl"),
+                    ),
+                ],
+            ),
+            (
+                e2e_test("#[ink_e2e::test] async fn it_works() { ink_e }"),
+                Some("{ ink_e"),
+                vec![("ink_e2e::alice()", Some("<-ink_e }"), Some("{ ink_e"))],
+            ),
+            // ink! e2e tests outside the ink! contract.
+            (
+                String::from(
+                    r#"
+                        #[ink::contract]
+                        mod flipper {
+                            #[ink(storage)]
+                            pub struct Flipper {}
+
+                            impl Flipper {
+                                #[ink(constructor)]
+                                pub fn default() -> Self {}
+                            }
+                        }
+
+                        #[ink_e2e::test]
+                        async fn it_works() { cl }
+                    "#,
+                ),
+                Some("{ cl"),
+                vec![(
+                    r#"client.instantiate("flipper", &ink_e2e::alice(), FlipperRef::default(), 0, None).await"#,
+                    Some("<-cl }"),
+                    Some("{ cl"),
+                )],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(&code, pat).unwrap() as u32);
+
+            let mut results = Vec::new();
+            e2e_completions(
+                &mut results,
+                &InkFile::parse(&code),
+                &Queries::default(),
+                offset,
+            );
+
+            assert_eq!(
+                results
+                    .into_iter()
+                    .map(|completion| (remove_whitespace(completion.edit.text), completion.range))
+                    .collect::<Vec<(String, TextRange)>>(),
+                expected_results
+                    .into_iter()
+                    .map(|(edit, pat_start, pat_end)| (
+                        remove_whitespace(edit.to_string()),
+                        TextRange::new(
+                            TextSize::from(parse_offset_at(&code, pat_start).unwrap() as u32),
+                            TextSize::from(parse_offset_at(&code, pat_end).unwrap() as u32)
+                        )
+                    ))
+                    .collect::<Vec<(String, TextRange)>>(),
+                "code: {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn resolve_works() {
+        for (id, expected_doc) in [
+            (
+                "ink::contract",
+                Some(content::doc(&InkAttributeKind::Macro(
+                    InkMacroKind::Contract,
+                ))),
+            ),
+            (
+                "ink_e2e::test",
+                Some(content::doc(&InkAttributeKind::Macro(
+                    InkMacroKind::E2ETest,
+                ))),
+            ),
+            (
+                "ink(message)",
+                Some(content::doc(&InkAttributeKind::Arg(InkArgKind::Message))),
+            ),
+            (
+                "ink(payable)",
+                Some(content::doc(&InkAttributeKind::Arg(InkArgKind::Payable))),
+            ),
+            // Unknown ink! attribute macros and arguments.
+            ("ink::unknown", None),
+            ("ink(unknown)", None),
+            ("", None),
+            // Unknown entity qualifiers.
+            ("ink(payable)#unknown", None),
+            ("ink(message)#constructor-v4", None),
+        ] {
+            let results = resolve_completion(id);
+            assert_eq!(results.is_some(), expected_doc.is_some(), "id: {id}");
+            if let Some(expected_doc) = expected_doc {
+                assert!(results.unwrap().starts_with(expected_doc), "id: {id}");
+            }
+        }
+
+        // ink! version specific `payable` docs are only added for ink! constructors.
+        let payable_doc = content::doc(&InkAttributeKind::Arg(InkArgKind::Payable));
+        assert_eq!(
+            resolve_completion("ink(payable)").as_deref(),
+            Some(payable_doc)
+        );
+        for version in [Version::V3, Version::V4] {
+            let id = format!(
+                "ink(payable)#constructor-{}",
+                match version {
+                    Version::V3 => "v3",
+                    Version::V4 => "v4",
+                }
+            );
+            assert_eq!(
+                resolve_completion(&id),
+                Some(format!(
+                    "{payable_doc}\n\n{}",
+                    content::payable_constructor_doc(version)
+                )),
+                "id: {id}"
+            );
+        }
+
+        // Completion identifiers for ink! attribute macros and arguments can be resolved.
+        for attr_kind in [
+            InkAttributeKind::Macro(InkMacroKind::Contract),
+            InkAttributeKind::Macro(InkMacroKind::E2ETest),
+            InkAttributeKind::Arg(InkArgKind::Storage),
+            InkAttributeKind::Arg(InkArgKind::Selector),
+        ] {
+            assert_eq!(
+                resolve_completion(&completion_id(&attr_kind)).as_deref(),
+                Some(content::doc(&attr_kind)),
+                "attribute: {attr_kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn arg_completion_id_works() {
+        for (code, version, expected_id) in [
+            (
+                r#"
+                    #[ink(message, pay)]
+                    pub fn my_message(&self) {}
+                "#,
+                Version::V4,
+                "ink(payable)",
+            ),
+            (
+                r#"
+                    #[ink(constructor, pay)]
+                    pub fn new() -> Self {}
+                "#,
+                Version::V4,
+                "ink(payable)#constructor-v4",
+            ),
+            (
+                r#"
+                    #[ink(constructor)]
+                    #[ink(pay)]
+                    pub fn new() -> Self {}
+                "#,
+                Version::V3,
+                "ink(payable)#constructor-v3",
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, Some("pay")).unwrap() as u32);
+            let mut results = Vec::new();
+            argument_completions(&mut results, &InkFile::parse(code), offset, version);
+
+            let payable = results
+                .iter()
+                .find(|completion| completion.label == "payable")
+                .unwrap();
+            assert_eq!(payable.id.as_deref(), Some(expected_id), "code: {code}");
+        }
+    }
+
+    #[test]
+    fn completion_metadata_works() {
+        for (code, pat, expected_kind, expected_sort_prefix, expected_filter_text, has_docs) in [
+            // (code, pat, kind, sort_prefix, filter_text, has_docs) where:
+            // code = source code,
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // kind = the expected completion kind of the first completion,
+            // sort_prefix = the expected prefix of the sort text of the first completion,
+            // filter_text = the expected filter text of the first completion,
+            // has_docs = whether the first completion has documentation (i.e. inline or lazily resolved).
+            (
+                "#[ink::co",
+                None,
+                CompletionKind::Macro,
+                "00002_",
+                None,
+                true,
+            ),
+            ("#[i", None, CompletionKind::Macro, "00002_", None, false),
+            (
+                "#[ink(message, pa",
+                None,
+                CompletionKind::Argument,
+                "00001_",
+                None,
+                true,
+            ),
+            (
+                r#"
+                    #[ink::storage_item(derive = )]
+                    struct MyStorageItem {}
+                "#,
+                Some("= "),
+                CompletionKind::Value,
+                "00000_",
+                None,
+                true,
+            ),
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        impl MyContract {
+                            #[ink(message)]
+                            pub fn my_message(&self) { let x = 1; se }
+                        }
+                    }
+                "#,
+                Some("1; se"),
+                CompletionKind::Snippet,
+                "00003_",
+                None,
+                false,
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
+            let results = crate::Analysis::new(code).completions(offset);
+            let completion = results.first().unwrap();
+
+            assert_eq!(completion.kind, expected_kind, "code: {code}");
+            assert!(
+                completion.sort_text.starts_with(expected_sort_prefix),
+                "code: {code}"
+            );
+            assert!(
+                completion.sort_text.ends_with(&completion.label),
+                "code: {code}"
+            );
+            assert_eq!(
+                completion.filter_text.as_deref(),
+                expected_filter_text,
+                "code: {code}"
+            );
+            // Documentation is only inline for completions without an identifier.
+            assert!(
+                completion.id.is_none() || completion.documentation.is_none(),
+                "code: {code}"
+            );
+            let documentation = completion
+                .documentation
+                .clone()
+                .or_else(|| completion.id.as_deref().and_then(resolve_completion));
+            assert_eq!(documentation.is_some(), has_docs, "code: {code}");
+        }
+    }
+
+    #[test]
+    fn registered_macro_completions_works() {
+        let mut registry = MacroRegistry::new();
+        registry.register(crate::MacroDefinition {
+            crate_name: "ink".to_string(),
+            name: "precompile".to_string(),
+            scopes: vec![crate::MacroScope::Trait],
+            args: vec!["address".to_string()],
+        });
+
+        for (code, pat, expected_results) in [
+            // (code, pat, [(edit, pat_start, pat_end)]) where:
+            // code = source code,
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // edit = the text that will inserted,
+            // pat_start = substring used to find the start of the edit offset (see `test_utils::parse_offset_at` doc),
+            // pat_end = substring used to find the end of the edit offset (see `test_utils::parse_offset_at` doc).
+
+            // Macros.
+            (
+                "#[]\npub trait MyPrecompile {}",
+                Some("#["),
+                vec![("ink::precompile", Some("#["), Some("#["))],
+            ),
+            (
+                "#[ink::pre]\npub trait MyPrecompile {}",
+                Some("pre"),
+                vec![("ink::precompile", Some("#["), Some("pre"))],
+            ),
+            // Invalid scope.
+            ("#[ink::pre]\npub struct MyPrecompile;", Some("pre"), vec![]),
+            // Arguments.
+            (
+                "#[ink::precompile()]\npub trait MyPrecompile {}",
+                Some("("),
+                vec![("address", Some("("), Some("("))],
+            ),
+            (
+                "#[ink::precompile(addr)]\npub trait MyPrecompile {}",
+                Some("addr"),
+                vec![("address", Some("<-addr"), Some("addr"))],
+            ),
+            // Duplicate arguments.
+            (
+                "#[ink::precompile(address=0x900,)]\npub trait MyPrecompile {}",
+                Some(","),
+                vec![],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
+
+            let file = InkFile::parse(code);
+            let mut results = Vec::new();
+            registered_macro_completions(&mut results, &file, offset, &registry);
+            registered_argument_completions(&mut results, &file, offset, &registry);
+
+            assert_eq!(
+                results
+                    .into_iter()
+                    .map(|completion| (completion.edit.text, completion.range))
+                    .collect::<Vec<(String, TextRange)>>(),
+                expected_results
+                    .into_iter()
+                    .map(|(edit, pat_start, pat_end)| (
+                        edit.to_string(),
+                        TextRange::new(
+                            TextSize::from(parse_offset_at(code, pat_start).unwrap() as u32),
+                            TextSize::from(parse_offset_at(code, pat_end).unwrap() as u32)
+                        )
+                    ))
+                    .collect::<Vec<(String, TextRange)>>(),
+                "code: {code}"
+            );
+        }
+    }
 }
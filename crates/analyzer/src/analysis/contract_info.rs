@@ -0,0 +1,367 @@
+//! A high-level read-only model of ink! contracts
+//! (e.g. for integrators like block explorers and documentation generators).
+
+use ink_analyzer_ir::ast::{HasDocComments, HasName};
+use ink_analyzer_ir::syntax::{AstNode, TextRange};
+use ink_analyzer_ir::{ast, Contract, Event, FromSyntax, InkFile, IsInkCallable, IsInkStruct};
+
+use super::metadata;
+use super::queries::SelectorTable;
+
+/// An ink! contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContractInfo {
+    /// Name of the ink! contract (i.e. the name of the `mod` item).
+    pub name: String,
+    /// Doc comments of the ink! contract (i.e. without the comment markers).
+    pub docs: String,
+    /// Name of the ink! storage `struct` (if any).
+    pub storage: Option<String>,
+    /// ink! constructors.
+    pub constructors: Vec<CallableInfo>,
+    /// ink! messages.
+    pub messages: Vec<CallableInfo>,
+    /// ink! events.
+    pub events: Vec<EventInfo>,
+    /// Range of the ink! contract (i.e. the whole `mod` item including attributes).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
+    pub range: TextRange,
+}
+
+/// An ink! constructor or ink! message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallableInfo {
+    /// Label of the callable (e.g. `flip` or `Flip::flip` for trait implementations),
+    /// i.e. the same as its label in the ink! contract metadata.
+    pub label: String,
+    /// Doc comments of the callable (i.e. without the comment markers).
+    pub docs: String,
+    /// Composed selector of the callable (if it can be computed).
+    pub selector: Option<u32>,
+    /// Whether the callable is payable (i.e. `#[ink(payable)]`).
+    pub is_payable: bool,
+    /// Whether the callable is the default callable (i.e. `#[ink(default)]`).
+    pub is_default: bool,
+    /// Whether the callable mutates contract storage (i.e. an ink! message with a `&mut self` receiver).
+    pub is_mutable: bool,
+    /// Arguments of the callable (i.e. excluding the `self` receiver).
+    pub args: Vec<ArgInfo>,
+    /// Return type of the callable (if any) (e.g. `Result<(), Error>`).
+    pub return_type: Option<String>,
+    /// Range of the callable (i.e. the whole `fn` item including attributes).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
+    pub range: TextRange,
+}
+
+/// An ink! constructor or ink! message argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArgInfo {
+    /// Name (i.e. pattern) of the argument.
+    pub name: String,
+    /// Type of the argument (if any) (e.g. `AccountId` or `Vec<u8>`).
+    pub type_name: Option<String>,
+}
+
+/// An ink! event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventInfo {
+    /// Name of the ink! event (i.e. the name of the `struct` item).
+    pub name: String,
+    /// Doc comments of the ink! event (i.e. without the comment markers).
+    pub docs: String,
+    /// Whether the ink! event is anonymous (i.e. `#[ink(anonymous)]`).
+    pub is_anonymous: bool,
+    /// Signature topic of the ink! event (if any) (i.e. `None` for anonymous events).
+    pub signature_topic: Option<[u8; 32]>,
+    /// Fields of the ink! event.
+    pub fields: Vec<EventFieldInfo>,
+    /// Range of the ink! event (i.e. the whole `struct` item including attributes).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
+    pub range: TextRange,
+}
+
+/// An ink! event field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventFieldInfo {
+    /// Name of the field.
+    pub name: String,
+    /// Type of the field (if any) (e.g. `Option<AccountId>`).
+    pub type_name: Option<String>,
+    /// Whether the field is an ink! topic (i.e. `#[ink(topic)]`).
+    pub is_topic: bool,
+}
+
+/// Returns the ink! contracts in the file.
+pub fn contract_info(file: &InkFile, selectors: &SelectorTable) -> Vec<ContractInfo> {
+    file.contracts()
+        .iter()
+        .filter_map(|contract| {
+            let module = contract.module()?;
+            Some(ContractInfo {
+                name: module.name()?.to_string(),
+                docs: docs(module),
+                storage: contract
+                    .storage()
+                    .and_then(IsInkStruct::struct_item)
+                    .and_then(HasName::name)
+                    .map(|name| name.to_string()),
+                constructors: callable_infos(contract.constructors(), selectors),
+                messages: callable_infos(contract.messages(), selectors),
+                events: event_infos(contract),
+                range: contract.syntax().text_range(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the callable infos for ink! callables (i.e. ink! constructors or ink! messages).
+fn callable_infos<T>(callables: &[T], selectors: &SelectorTable) -> Vec<CallableInfo>
+where
+    T: IsInkCallable,
+{
+    callables
+        .iter()
+        .filter_map(|callable| {
+            let label = metadata::callable_label(callable)?;
+            let fn_item = callable.fn_item()?;
+            let self_param = fn_item
+                .param_list()
+                .and_then(|param_list| param_list.self_param());
+            Some(CallableInfo {
+                label,
+                docs: docs(fn_item),
+                selector: selectors
+                    .get(&fn_item.syntax().text_range())
+                    .map(|selector| selector.into_be_u32()),
+                is_payable: callable.payable_arg().is_some(),
+                is_default: callable.default_arg().is_some(),
+                is_mutable: self_param.map_or(false, |self_param| {
+                    match self_param.ty() {
+                        // Explicitly typed receiver (e.g. `self: &mut Self`).
+                        Some(ast::Type::RefType(ref_type)) => ref_type.mut_token().is_some(),
+                        Some(_) => false,
+                        None => {
+                            self_param.amp_token().is_some() && self_param.mut_token().is_some()
+                        }
+                    }
+                }),
+                args: fn_item
+                    .param_list()
+                    .map(|param_list| {
+                        param_list
+                            .params()
+                            .map(|param| ArgInfo {
+                                name: param
+                                    .pat()
+                                    .map(|pat| pat.syntax().to_string())
+                                    .unwrap_or_default(),
+                                type_name: param.ty().map(|ty| ty.syntax().to_string()),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                return_type: fn_item
+                    .ret_type()
+                    .and_then(|ret_type| ret_type.ty())
+                    .map(|ty| ty.syntax().to_string()),
+                range: fn_item.syntax().text_range(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the event infos for the ink! events of an ink! contract.
+fn event_infos(contract: &Contract) -> Vec<EventInfo> {
+    contract
+        .events()
+        .iter()
+        .filter_map(|event| {
+            let struct_item = event.struct_item()?;
+            Some(EventInfo {
+                name: struct_item.name()?.to_string(),
+                docs: docs(struct_item),
+                is_anonymous: event.anonymous_arg().is_some(),
+                signature_topic: event.signature_topic(),
+                fields: event_field_infos(event, struct_item),
+                range: struct_item.syntax().text_range(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the field infos for an ink! event.
+fn event_field_infos(event: &Event, struct_item: &ast::Struct) -> Vec<EventFieldInfo> {
+    let topic_ranges: Vec<TextRange> = event
+        .topics()
+        .iter()
+        .filter_map(|topic| topic.field())
+        .map(|field| field.syntax().text_range())
+        .collect();
+    match struct_item.field_list() {
+        Some(ast::FieldList::RecordFieldList(field_list)) => field_list
+            .fields()
+            .filter_map(|field| {
+                Some(EventFieldInfo {
+                    name: field.name()?.to_string(),
+                    type_name: field.ty().map(|ty| ty.syntax().to_string()),
+                    is_topic: topic_ranges.contains(&field.syntax().text_range()),
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the text of the doc comments of the item (i.e. without the comment markers),
+/// with one line per doc comment.
+fn docs(item: &impl HasDocComments) -> String {
+    item.doc_comments()
+        .filter_map(|comment| {
+            comment.doc_comment().map(|text| {
+                // Removes the conventional space after the comment marker.
+                text.strip_prefix(' ').unwrap_or(text).to_string()
+            })
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::queries::Queries;
+
+    #[test]
+    fn contract_info_works() {
+        let code = r#"
+            /// A flipper contract.
+            #[ink::contract]
+            mod flipper {
+                #[ink(storage)]
+                pub struct Flipper {
+                    value: bool,
+                }
+
+                /// Emitted when the value is flipped.
+                #[ink(event)]
+                pub struct Flipped {
+                    #[ink(topic)]
+                    from: Option<AccountId>,
+                    value: bool,
+                }
+
+                #[ink(event, anonymous)]
+                pub struct Anonymous {}
+
+                impl Flipper {
+                    /// Creates a new flipper.
+                    ///
+                    /// With the given initial value.
+                    #[ink(constructor, default)]
+                    pub fn new(init_value: bool) -> Self {}
+
+                    #[ink(message, payable, selector = 0xA)]
+                    pub fn flip(&mut self) {}
+
+                    #[ink(message)]
+                    pub fn get(&self) -> bool {}
+                }
+
+                impl MyTrait for Flipper {
+                    #[ink(message)]
+                    fn set(self: &mut Self, value: bool, to: Vec<u8>) {}
+                }
+            }
+        "#;
+        let file = InkFile::parse(code);
+        let queries = Queries::default();
+        let results = contract_info(&file, queries.selectors(&file));
+        assert_eq!(results.len(), 1);
+
+        let contract = &results[0];
+        assert_eq!(contract.name, "flipper");
+        assert_eq!(contract.docs, "A flipper contract.");
+        assert_eq!(contract.storage.as_deref(), Some("Flipper"));
+
+        // Constructors.
+        assert_eq!(contract.constructors.len(), 1);
+        let constructor = &contract.constructors[0];
+        assert_eq!(constructor.label, "new");
+        assert_eq!(
+            constructor.docs,
+            "Creates a new flipper.\n\nWith the given initial value."
+        );
+        assert_eq!(constructor.selector, Some(0x9bae9d5e));
+        assert!(constructor.is_default && !constructor.is_payable && !constructor.is_mutable);
+        assert_eq!(
+            constructor.args,
+            vec![ArgInfo {
+                name: "init_value".to_string(),
+                type_name: Some("bool".to_string()),
+            }]
+        );
+        assert_eq!(constructor.return_type.as_deref(), Some("Self"));
+
+        // Messages.
+        let messages: Vec<(&str, bool, bool, usize, Option<&str>)> = contract
+            .messages
+            .iter()
+            .map(|message| {
+                (
+                    message.label.as_str(),
+                    message.is_payable,
+                    message.is_mutable,
+                    message.args.len(),
+                    message.return_type.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                ("flip", true, true, 0, None),
+                ("get", false, false, 0, Some("bool")),
+                ("MyTrait::set", false, true, 2, None),
+            ]
+        );
+        assert_eq!(contract.messages[0].selector, Some(0xA));
+        assert_eq!(contract.messages[1].selector, Some(0x2f865bd9));
+        assert!(contract.messages[2].selector.is_some());
+
+        // Events.
+        assert_eq!(contract.events.len(), 2);
+        let event = &contract.events[0];
+        assert_eq!(event.name, "Flipped");
+        assert_eq!(event.docs, "Emitted when the value is flipped.");
+        assert!(!event.is_anonymous && event.signature_topic.is_some());
+        assert_eq!(
+            event.fields,
+            vec![
+                EventFieldInfo {
+                    name: "from".to_string(),
+                    type_name: Some("Option<AccountId>".to_string()),
+                    is_topic: true,
+                },
+                EventFieldInfo {
+                    name: "value".to_string(),
+                    type_name: Some("bool".to_string()),
+                    is_topic: false,
+                },
+            ]
+        );
+        let anonymous_event = &contract.events[1];
+        assert!(anonymous_event.is_anonymous && anonymous_event.signature_topic.is_none());
+
+        // Ranges.
+        assert_eq!(
+            &code[contract.range],
+            file.contracts()[0].syntax().to_string()
+        );
+        assert!(code[constructor.range].starts_with("/// Creates a new flipper."));
+    }
+}
@@ -18,13 +18,22 @@
 //! used in the [Contract struct's constructor](https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/contract.rs#L61-L73) as well as related unit tests.
 
 use ink_analyzer_ir::syntax::TextRange;
-use ink_analyzer_ir::InkFile;
-use itertools::Itertools;
+use ink_analyzer_ir::{FromSyntax, InkFile};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
 
+use crate::analysis::registry::MacroRegistry;
 use crate::analysis::text_edit;
-use crate::{Action, TextEdit};
+use crate::{Action, TextEdit, Version};
 
+pub use cache::{CachedDiagnostics, DiagnosticsCache};
+
+mod cache;
 mod file;
+mod no_std;
+mod registered_macro;
+mod suppression;
 mod utils;
 
 mod chain_extension;
@@ -43,52 +52,837 @@ mod trait_definition;
 
 /// A diagnostic error or warning.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Diagnostic {
     /// Error or warning message.
     pub message: String,
     /// Text range to highlight.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
     pub range: TextRange,
     /// The severity level of the diagnostic.
     pub severity: Severity,
+    /// The stable code of the check that produced the diagnostic
+    /// (only `None` for diagnostics computed by external passes).
+    pub code: Option<DiagnosticCode>,
     /// Quickfixes (suggested edits/actions) for the diagnostic (if any).
     pub quickfixes: Option<Vec<Action>>,
+    /// Related locations (e.g. other declarations involved in a mismatch) for the diagnostic (if any).
+    pub related_information: Option<Vec<RelatedInformation>>,
+}
+
+/// A related location for a diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelatedInformation {
+    /// Description of the related location.
+    pub message: String,
+    /// Text range of the related location.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
+    pub range: TextRange,
 }
 
 /// The severity level of a diagnostic.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Severity {
     /// A diagnostic error.
     Error,
     /// A diagnostic warning.
     Warning,
+    /// A diagnostic warning for deprecated ink! constructs
+    /// (i.e. constructs that still work for the target ink! version, but are discouraged
+    /// and may be removed in a future ink! version).
+    ///
+    /// LSP clients render these with the "deprecated" diagnostic tag (e.g. with a strikethrough).
+    Deprecation,
+}
+
+/// URL of the hosted documentation for diagnostic codes.
+const DOCS_URL: &str =
+    "https://github.com/ink-analyzer/ink-analyzer/blob/master/crates/analyzer/DIAGNOSTICS.md";
+
+/// A stable code that identifies the check (i.e. lint category) that produced a diagnostic.
+///
+/// Codes are formatted as `E<number>-<name>` (e.g. `E0017-missing-storage`),
+/// and are documented at <https://github.com/ink-analyzer/ink-analyzer/blob/master/crates/analyzer/DIAGNOSTICS.md>.
+///
+/// Codes can be used to disable checks and override their severity (see [`DiagnosticsConfig`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum DiagnosticCode {
+    /// Invalid identifiers (i.e. identifiers starting with `__ink_`).
+    InvalidIdentifier,
+    /// Unknown ink! attributes.
+    UnknownAttribute,
+    /// Missing or invalid ink! attribute arguments and argument values.
+    InvalidArgument,
+    /// Duplicate ink! attributes and ink! attribute arguments.
+    DuplicateAttribute,
+    /// ink! attributes and ink! attribute arguments in the wrong order.
+    AttributeOrder,
+    /// Conflicting ink! attributes and ink! attribute arguments.
+    ConflictingAttribute,
+    /// ink! attributes applied to the wrong kind of item.
+    InvalidItemKind,
+    /// ink! entities in an invalid scope (i.e. with an invalid parent or ancestor).
+    InvalidScope,
+    /// ink! entities with invalid visibility.
+    InvalidVisibility,
+    /// ink! entities with invalid modifiers (e.g. `const`, `async`, `unsafe`, `default` or an explicit ABI).
+    InvalidModifier,
+    /// Unsupported generics and trait bounds.
+    UnsupportedGenerics,
+    /// Invalid ink! message and ink! constructor signatures (i.e. receivers and return types).
+    InvalidSignature,
+    /// Invalid ink! trait definition and ink! chain extension items.
+    InvalidTraitItem,
+    /// Features that aren't supported by the target ink! version.
+    VersionIncompatible,
+    /// ink! contracts that aren't inline `mod` items.
+    NonInlineModule,
+    /// Multiple ink! contracts in the same file.
+    DuplicateContract,
+    /// Missing ink! storage definition.
+    MissingStorage,
+    /// Multiple ink! storage definitions.
+    DuplicateStorage,
+    /// Missing ink! constructor(s).
+    MissingConstructor,
+    /// Missing ink! message(s).
+    MissingMessage,
+    /// ink! impl blocks without ink! constructors and ink! messages.
+    EmptyImpl,
+    /// Overlapping selectors.
+    OverlappingSelectors,
+    /// Multiple wildcard selectors.
+    MultipleWildcardSelectors,
+    /// Invalid selectors (e.g. wildcard selectors on ink! trait definition methods).
+    InvalidSelector,
+    /// ink! messages that are unreachable at dispatch time.
+    UnreachableMessage,
+    /// Mismatches between ink! trait definitions and their implementations.
+    TraitImplMismatch,
+    /// Missing, invalid or duplicate `ErrorCode` types for ink! chain extensions.
+    InvalidErrorCode,
+    /// Overlapping ink! extension ids.
+    OverlappingExtensionIds,
+    /// Ignored status codes (i.e. for ink! extensions with `handle_status = false`).
+    IgnoredStatusCode,
+    /// ink! events with more topics than supported by the target runtime.
+    TooManyTopics,
+    /// `cfg` attributes on ink! event fields.
+    CfgEventField,
+    /// Storage incompatible types (e.g. `std` collections and nested non-packed types).
+    StorageIncompatibleType,
+    /// `Mapping` used outside ink! storage or reset in ink! messages.
+    MappingMisuse,
+    /// `Lazy` storage fields that are read but never initialized.
+    UninitializedLazy,
+    /// Debug-only code that's not gated behind the `ink-debug` feature.
+    UngatedDebugCode,
+    /// Mismatches between ink! contract code and contract metadata.
+    MetadataMismatch,
+    /// Unused suppression comments (i.e. unknown codes or codes that don't match any diagnostics).
+    UnusedSuppression,
+    /// ink! e2e tests that aren't gated behind the `e2e-tests` feature.
+    UngatedE2ETest,
+    /// ink! contract crates without the `no_std` prelude (i.e. `#![cfg_attr(not(feature = "std"), no_std, no_main)]`).
+    MissingNoStd,
+    /// `std`-only constructs (e.g. `std` paths and `println!`) in ink! contract code.
+    StdOnlyConstruct,
+    /// `impl` blocks that use the ink! environment but aren't ink! impls (i.e. missing `#[ink(impl)]`).
+    MissingInkImpl,
+    /// Tests that use ink! off-chain environment test helpers without `#[ink::test]`.
+    MissingInkTest,
+    /// `additional_contracts` entries of ink! e2e tests whose `Cargo.toml` doesn't exist.
+    UnresolvedAdditionalContract,
+    /// ink! storage fields that aren't initialized in ink! constructors.
+    UninitializedStorageField,
+    /// ink! entities that are defined but never used (e.g. ink! events that are never emitted).
+    UnusedEntity,
+}
+
+impl DiagnosticCode {
+    /// Returns the name of the code (e.g. `missing-storage`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagnosticCode::InvalidIdentifier => "invalid-identifier",
+            DiagnosticCode::UnknownAttribute => "unknown-attribute",
+            DiagnosticCode::InvalidArgument => "invalid-argument",
+            DiagnosticCode::DuplicateAttribute => "duplicate-attribute",
+            DiagnosticCode::AttributeOrder => "attribute-order",
+            DiagnosticCode::ConflictingAttribute => "conflicting-attribute",
+            DiagnosticCode::InvalidItemKind => "invalid-item-kind",
+            DiagnosticCode::InvalidScope => "invalid-scope",
+            DiagnosticCode::InvalidVisibility => "invalid-visibility",
+            DiagnosticCode::InvalidModifier => "invalid-modifier",
+            DiagnosticCode::UnsupportedGenerics => "unsupported-generics",
+            DiagnosticCode::InvalidSignature => "invalid-signature",
+            DiagnosticCode::InvalidTraitItem => "invalid-trait-item",
+            DiagnosticCode::VersionIncompatible => "version-incompatible",
+            DiagnosticCode::NonInlineModule => "non-inline-module",
+            DiagnosticCode::DuplicateContract => "duplicate-contract",
+            DiagnosticCode::MissingStorage => "missing-storage",
+            DiagnosticCode::DuplicateStorage => "duplicate-storage",
+            DiagnosticCode::MissingConstructor => "missing-constructor",
+            DiagnosticCode::MissingMessage => "missing-message",
+            DiagnosticCode::EmptyImpl => "empty-impl",
+            DiagnosticCode::OverlappingSelectors => "overlapping-selectors",
+            DiagnosticCode::MultipleWildcardSelectors => "multiple-wildcard-selectors",
+            DiagnosticCode::InvalidSelector => "invalid-selector",
+            DiagnosticCode::UnreachableMessage => "unreachable-message",
+            DiagnosticCode::TraitImplMismatch => "trait-impl-mismatch",
+            DiagnosticCode::InvalidErrorCode => "invalid-error-code",
+            DiagnosticCode::OverlappingExtensionIds => "overlapping-extension-ids",
+            DiagnosticCode::IgnoredStatusCode => "ignored-status-code",
+            DiagnosticCode::TooManyTopics => "too-many-topics",
+            DiagnosticCode::CfgEventField => "cfg-event-field",
+            DiagnosticCode::StorageIncompatibleType => "storage-incompatible-type",
+            DiagnosticCode::MappingMisuse => "mapping-misuse",
+            DiagnosticCode::UninitializedLazy => "uninitialized-lazy",
+            DiagnosticCode::UngatedDebugCode => "ungated-debug-code",
+            DiagnosticCode::MetadataMismatch => "metadata-mismatch",
+            DiagnosticCode::UnusedSuppression => "unused-suppression",
+            DiagnosticCode::UngatedE2ETest => "ungated-e2e-test",
+            DiagnosticCode::MissingNoStd => "missing-no-std",
+            DiagnosticCode::StdOnlyConstruct => "std-only-construct",
+            DiagnosticCode::MissingInkImpl => "missing-ink-impl",
+            DiagnosticCode::MissingInkTest => "missing-ink-test",
+            DiagnosticCode::UnresolvedAdditionalContract => "unresolved-additional-contract",
+            DiagnosticCode::UninitializedStorageField => "uninitialized-storage-field",
+            DiagnosticCode::UnusedEntity => "unused-entity",
+        }
+    }
+
+    /// Returns the stable number of the code (i.e. numbers are never reused or reassigned).
+    pub fn number(&self) -> u16 {
+        match self {
+            DiagnosticCode::InvalidIdentifier => 1,
+            DiagnosticCode::UnknownAttribute => 2,
+            DiagnosticCode::InvalidArgument => 3,
+            DiagnosticCode::DuplicateAttribute => 4,
+            DiagnosticCode::AttributeOrder => 5,
+            DiagnosticCode::ConflictingAttribute => 6,
+            DiagnosticCode::InvalidItemKind => 7,
+            DiagnosticCode::InvalidScope => 8,
+            DiagnosticCode::InvalidVisibility => 9,
+            DiagnosticCode::InvalidModifier => 10,
+            DiagnosticCode::UnsupportedGenerics => 11,
+            DiagnosticCode::InvalidSignature => 12,
+            DiagnosticCode::InvalidTraitItem => 13,
+            DiagnosticCode::VersionIncompatible => 14,
+            DiagnosticCode::NonInlineModule => 15,
+            DiagnosticCode::DuplicateContract => 16,
+            DiagnosticCode::MissingStorage => 17,
+            DiagnosticCode::DuplicateStorage => 18,
+            DiagnosticCode::MissingConstructor => 19,
+            DiagnosticCode::MissingMessage => 20,
+            DiagnosticCode::EmptyImpl => 21,
+            DiagnosticCode::OverlappingSelectors => 22,
+            DiagnosticCode::MultipleWildcardSelectors => 23,
+            DiagnosticCode::InvalidSelector => 24,
+            DiagnosticCode::UnreachableMessage => 25,
+            DiagnosticCode::TraitImplMismatch => 26,
+            DiagnosticCode::InvalidErrorCode => 27,
+            DiagnosticCode::OverlappingExtensionIds => 28,
+            DiagnosticCode::IgnoredStatusCode => 29,
+            DiagnosticCode::TooManyTopics => 30,
+            DiagnosticCode::CfgEventField => 31,
+            DiagnosticCode::StorageIncompatibleType => 32,
+            DiagnosticCode::MappingMisuse => 33,
+            DiagnosticCode::UninitializedLazy => 34,
+            DiagnosticCode::UngatedDebugCode => 35,
+            DiagnosticCode::MetadataMismatch => 36,
+            DiagnosticCode::UnusedSuppression => 37,
+            DiagnosticCode::UngatedE2ETest => 38,
+            DiagnosticCode::MissingNoStd => 39,
+            DiagnosticCode::StdOnlyConstruct => 40,
+            DiagnosticCode::MissingInkImpl => 41,
+            DiagnosticCode::MissingInkTest => 42,
+            DiagnosticCode::UnresolvedAdditionalContract => 43,
+            DiagnosticCode::UninitializedStorageField => 44,
+            DiagnosticCode::UnusedEntity => 45,
+        }
+    }
+
+    /// Returns the code with the given name (if any).
+    pub fn from_name(name: &str) -> Option<Self> {
+        DiagnosticCode::ALL
+            .iter()
+            .find(|code| code.name() == name)
+            .copied()
+    }
+
+    /// Returns the code for the given text (if any),
+    /// the text can be either the full code (e.g. `E0017-missing-storage`),
+    /// only the number part (e.g. `E0017`) or only the name (e.g. `missing-storage`).
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        DiagnosticCode::ALL.iter().copied().find(|code| {
+            let number = format!("E{:04}", code.number());
+            text == code.to_string() || text == number || text == code.name()
+        })
+    }
+
+    /// Returns the URL of the hosted documentation for the code.
+    pub fn code_description(&self) -> String {
+        format!("{DOCS_URL}#{}", self.to_string().to_lowercase())
+    }
+
+    /// All diagnostic codes.
+    pub const ALL: [DiagnosticCode; 45] = [
+        DiagnosticCode::InvalidIdentifier,
+        DiagnosticCode::UnknownAttribute,
+        DiagnosticCode::InvalidArgument,
+        DiagnosticCode::DuplicateAttribute,
+        DiagnosticCode::AttributeOrder,
+        DiagnosticCode::ConflictingAttribute,
+        DiagnosticCode::InvalidItemKind,
+        DiagnosticCode::InvalidScope,
+        DiagnosticCode::InvalidVisibility,
+        DiagnosticCode::InvalidModifier,
+        DiagnosticCode::UnsupportedGenerics,
+        DiagnosticCode::InvalidSignature,
+        DiagnosticCode::InvalidTraitItem,
+        DiagnosticCode::VersionIncompatible,
+        DiagnosticCode::NonInlineModule,
+        DiagnosticCode::DuplicateContract,
+        DiagnosticCode::MissingStorage,
+        DiagnosticCode::DuplicateStorage,
+        DiagnosticCode::MissingConstructor,
+        DiagnosticCode::MissingMessage,
+        DiagnosticCode::EmptyImpl,
+        DiagnosticCode::OverlappingSelectors,
+        DiagnosticCode::MultipleWildcardSelectors,
+        DiagnosticCode::InvalidSelector,
+        DiagnosticCode::UnreachableMessage,
+        DiagnosticCode::TraitImplMismatch,
+        DiagnosticCode::InvalidErrorCode,
+        DiagnosticCode::OverlappingExtensionIds,
+        DiagnosticCode::IgnoredStatusCode,
+        DiagnosticCode::TooManyTopics,
+        DiagnosticCode::CfgEventField,
+        DiagnosticCode::StorageIncompatibleType,
+        DiagnosticCode::MappingMisuse,
+        DiagnosticCode::UninitializedLazy,
+        DiagnosticCode::UngatedDebugCode,
+        DiagnosticCode::MetadataMismatch,
+        DiagnosticCode::UnusedSuppression,
+        DiagnosticCode::UngatedE2ETest,
+        DiagnosticCode::MissingNoStd,
+        DiagnosticCode::StdOnlyConstruct,
+        DiagnosticCode::MissingInkImpl,
+        DiagnosticCode::MissingInkTest,
+        DiagnosticCode::UnresolvedAdditionalContract,
+        DiagnosticCode::UninitializedStorageField,
+        DiagnosticCode::UnusedEntity,
+    ];
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{:04}-{}", self.number(), self.name())
+    }
+}
+
+/// Diagnostics configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticsConfig {
+    /// The maximum number of topics per ink! event supported by the target runtime
+    /// (including the signature topic for non-anonymous events).
+    ///
+    /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/env/src/types.rs>.
+    pub max_event_topics: usize,
+    /// The target ink! version (if set, overrides the version of the analysis).
+    pub version: Option<Version>,
+    /// Whether to run audit diagnostics (e.g. for debug-only code that's compiled into the contract).
+    pub audit: bool,
+    /// The text of the project's `Cargo.toml` (if known),
+    /// used by quickfixes that need to declare cargo features.
+    pub cargo_toml: Option<String>,
+    /// Codes of disabled checks (i.e. diagnostics with these codes are not emitted).
+    pub disabled: HashSet<DiagnosticCode>,
+    /// Severity overrides for checks (e.g. for downgrading errors to warnings).
+    pub severity_overrides: HashMap<DiagnosticCode, Severity>,
+    /// Whether the file is an out-of-line child module of an ink! contract
+    /// (i.e. a file declared via a `mod x;` item in the root of an ink! contract's `mod` item),
+    /// in which case ink! impls, ink! constructors and ink! messages are valid at the file root.
+    ///
+    /// See [`Analysis::contract_child_modules`](crate::Analysis::contract_child_modules) and
+    /// [`parent_module_candidates`](crate::parent_module_candidates) for resolving this.
+    pub contract_child_module: bool,
+    /// The root directory of the ink! contract crate (i.e. the directory of its `Cargo.toml`) (if known),
+    /// used for resolving `Cargo.toml`-relative paths (e.g. `additional_contracts` of ink! e2e tests)
+    /// on the file system.
+    pub workspace_root: Option<PathBuf>,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            max_event_topics: 4,
+            version: None,
+            audit: false,
+            cargo_toml: None,
+            disabled: HashSet::new(),
+            severity_overrides: HashMap::new(),
+            contract_child_module: false,
+            workspace_root: None,
+        }
+    }
+}
+
+impl DiagnosticsConfig {
+    /// Returns true if diagnostics with the given code are enabled.
+    pub fn is_enabled(&self, code: DiagnosticCode) -> bool {
+        !self.disabled.contains(&code)
+    }
+
+    /// Returns the severity of a diagnostic (i.e. after applying severity overrides).
+    pub fn severity(&self, diagnostic: &Diagnostic) -> Severity {
+        diagnostic
+            .code
+            .and_then(|code| self.severity_overrides.get(&code).copied())
+            .unwrap_or(diagnostic.severity)
+    }
 }
 
 /// Runs diagnostics for the source file.
-pub fn diagnostics(file: &InkFile) -> Vec<Diagnostic> {
+pub fn diagnostics(
+    file: &InkFile,
+    version: Version,
+    config: &DiagnosticsConfig,
+    registry: &MacroRegistry,
+) -> Vec<Diagnostic> {
+    let mut results = Vec::new();
+    diagnostics_with(file, version, config, registry, |diagnostic| {
+        results.push(diagnostic);
+    });
+    results
+}
+
+/// Runs diagnostics for the source file, and passes each diagnostic to the callback
+/// as soon as the unit of diagnostics that produced it is complete (e.g. an ink! contract),
+/// in the same order as [`diagnostics`] returns them.
+pub fn diagnostics_with<F>(
+    file: &InkFile,
+    version: Version,
+    config: &DiagnosticsConfig,
+    registry: &MacroRegistry,
+    callback: F,
+) where
+    F: FnMut(Diagnostic),
+{
+    let version = config.version.unwrap_or(version);
+    let mut emitter = Emitter::new(file, config, registry, callback);
+    for unit in file::units(file) {
+        let mut results = Vec::new();
+        file::diagnostics(&mut results, file, unit, version, config);
+        emitter.emit(results);
+    }
+    emitter.finish();
+}
+
+/// Runs diagnostics for the source file using (at most) the given number of worker threads,
+/// and returns the same diagnostics (in the same order) as [`diagnostics`].
+///
+/// Syntax trees can't be shared across threads, so each worker thread parses its own copy
+/// of the source file, which only pays off for large files with many ink! entities.
+pub fn diagnostics_parallel(
+    file: &InkFile,
+    version: Version,
+    config: &DiagnosticsConfig,
+    registry: &MacroRegistry,
+    num_threads: usize,
+) -> Vec<Diagnostic> {
+    let units = file::units(file);
+    let num_threads = num_threads.clamp(1, units.len());
+    if num_threads == 1 {
+        return diagnostics(file, version, config, registry);
+    }
+
+    // Distributes units of diagnostics across worker threads (in a round-robin fashion).
+    let version = config.version.unwrap_or(version);
+    let code = file.syntax().to_string();
+    let mut batches: Vec<(usize, Vec<Diagnostic>)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..num_threads)
+            .map(|worker| {
+                let (code, units) = (&code, &units);
+                scope.spawn(move || {
+                    let file = InkFile::parse(code);
+                    units
+                        .iter()
+                        .enumerate()
+                        .skip(worker)
+                        .step_by(num_threads)
+                        .map(|(idx, unit)| {
+                            let mut results = Vec::new();
+                            file::diagnostics(&mut results, &file, *unit, version, config);
+                            (idx, results)
+                        })
+                        .collect::<Vec<(usize, Vec<Diagnostic>)>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|error| std::panic::resume_unwind(error))
+            })
+            .collect()
+    });
+
+    // Restores the order of units.
+    batches.sort_by_key(|(idx, _)| *idx);
     let mut results = Vec::new();
-    file::diagnostics(&mut results, file);
+    let mut emitter = Emitter::new(file, config, registry, |diagnostic| {
+        results.push(diagnostic)
+    });
+    for (_, batch) in batches {
+        emitter.emit(batch);
+    }
+    emitter.finish();
     results
-        .into_iter()
+}
+
+/// Deduplicates, filters, configures and formats batches of diagnostics
+/// before passing them to a callback.
+struct Emitter<'a, F> {
+    file: &'a InkFile,
+    config: &'a DiagnosticsConfig,
+    registry: &'a MacroRegistry,
+    /// Text ranges of registered ink! attribute macros, see `registered_macro::diagnostics` doc.
+    registered_ranges: Vec<TextRange>,
+    /// Suppression comments, see `suppression` module doc.
+    suppressions: Vec<suppression::Suppression>,
+    /// Indices of suppression comments and their suppressed codes that matched a diagnostic.
+    used_suppressions: HashSet<(usize, usize)>,
+    /// Deduplication keys (i.e. range, severity and quickfix edits) of emitted diagnostics.
+    emitted: HashSet<(TextRange, Severity, Option<Vec<TextEdit>>)>,
+    callback: F,
+}
+
+impl<'a, F> Emitter<'a, F>
+where
+    F: FnMut(Diagnostic),
+{
+    fn new(
+        file: &'a InkFile,
+        config: &'a DiagnosticsConfig,
+        registry: &'a MacroRegistry,
+        callback: F,
+    ) -> Self {
+        Self {
+            file,
+            config,
+            registry,
+            registered_ranges: registered_macro::registered_attr_ranges(file, registry),
+            suppressions: suppression::suppressions(file),
+            used_suppressions: HashSet::new(),
+            emitted: HashSet::new(),
+            callback,
+        }
+    }
+
+    /// Emits a batch of diagnostics, except for diagnostics for registered ink! attribute macros
+    /// (see `registered_macro::diagnostics` doc).
+    fn emit(&mut self, batch: Vec<Diagnostic>) {
+        for diagnostic in batch {
+            let is_registered = self
+                .registered_ranges
+                .iter()
+                .any(|range| range.contains_range(diagnostic.range));
+            if !is_registered {
+                self.emit_one(diagnostic);
+            }
+        }
+    }
+
+    /// Emits diagnostics for registered ink! attribute macros and unused suppression comments.
+    fn finish(mut self) {
+        let mut results = Vec::new();
+        registered_macro::diagnostics(&mut results, self.file, self.registry);
+        for diagnostic in results {
+            self.emit_one(diagnostic);
+        }
+
+        // Ensures that there are no unused suppression comments,
+        // see `suppression::unused_suppressions` doc.
+        let mut results = Vec::new();
+        suppression::unused_suppressions(&mut results, &self.suppressions, &self.used_suppressions);
+        for diagnostic in results {
+            self.emit_one(diagnostic);
+        }
+    }
+
+    fn emit_one(&mut self, mut diagnostic: Diagnostic) {
+        // Apply suppression comments, see `suppression` module doc.
+        let suppressed = self
+            .suppressions
+            .iter()
+            .enumerate()
+            .find_map(|(idx, suppression)| {
+                suppression
+                    .matches(&diagnostic)
+                    .map(|code_idx| (idx, code_idx))
+            });
+        if let Some(key) = suppressed {
+            self.used_suppressions.insert(key);
+            return;
+        }
+
+        // Apply diagnostics config (i.e. disabled checks and severity overrides).
+        if matches!(diagnostic.code, Some(code) if !self.config.is_enabled(code)) {
+            return;
+        }
+        diagnostic.severity = self.config.severity(&diagnostic);
+
         // Deduplicate by range, severity and quickfix edits.
-        .unique_by(|item| {
-            let quickfix_edits: Option<Vec<TextEdit>> = item
-                .quickfixes
-                .as_ref()
-                .map(|it| it.iter().flat_map(|it| it.edits.clone()).collect());
-            (item.range, item.severity, quickfix_edits)
-        })
-        // Format edits.
-        .map(|diagnostic| Diagnostic {
-            quickfixes: diagnostic.quickfixes.map(|fixes| {
+        let quickfix_edits: Option<Vec<TextEdit>> = diagnostic
+            .quickfixes
+            .as_ref()
+            .map(|it| it.iter().flat_map(|it| it.edits.clone()).collect());
+        if !self
+            .emitted
+            .insert((diagnostic.range, diagnostic.severity, quickfix_edits))
+        {
+            return;
+        }
+
+        // Rank quickfixes and format edits.
+        (self.callback)(Diagnostic {
+            quickfixes: diagnostic.quickfixes.map(|mut fixes| {
+                rank_quickfixes(&mut fixes);
                 fixes
                     .into_iter()
                     .map(|action| Action {
-                        edits: text_edit::format_edits(action.edits, file).collect(),
+                        edits: text_edit::format_edits(action.edits, self.file).collect(),
                         ..action
                     })
                     .collect()
             }),
             ..diagnostic
-        })
-        .collect()
+        });
+    }
+}
+
+/// Ranks the quickfixes of a diagnostic, unless the diagnostic already ranked them
+/// (i.e. set a priority).
+///
+/// Quickfixes are listed from the most to the least relevant, so priority decreases with position.
+///
+/// **NOTE**: Quickfixes are never implicitly preferred (i.e. marked as safe to auto-apply),
+/// each diagnostic explicitly marks quickfixes that can't lose user code (e.g. insertions) as preferred.
+fn rank_quickfixes(quickfixes: &mut [Action]) {
+    let is_ranked = quickfixes.iter().any(|action| action.priority != 0);
+    if is_ranked {
+        return;
+    }
+    let n_quickfixes = quickfixes.len();
+    for (idx, action) in quickfixes.iter_mut().enumerate() {
+        action.priority = u8::try_from(n_quickfixes - idx).unwrap_or(u8::MAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::quote_as_pretty_string;
+
+    #[test]
+    fn streaming_and_parallel_diagnostics_works() {
+        let code = quote_as_pretty_string! {
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn my_message(&self, #[ink(payable)] value: u8) {}
+                }
+            }
+
+            #[ink::contract]
+            mod my_contract2 {}
+
+            #[ink::trait_definition]
+            pub trait MyTrait {
+                #[ink(constructor)]
+                fn my_constructor() -> Self;
+            }
+
+            #[ink::storage_item]
+            pub enum MyStorageItem {}
+
+            #[ink::test]
+            fn it_works() {}
+
+            #[ink::chain_extension]
+            pub trait MyChainExtension {}
+
+            #[ink(event)]
+            pub struct MyEvent {}
+        };
+        let file = InkFile::parse(&code);
+        let (version, config, registry) = (
+            Version::default(),
+            DiagnosticsConfig::default(),
+            MacroRegistry::default(),
+        );
+
+        let expected_results = diagnostics(&file, version, &config, &registry);
+        assert!(!expected_results.is_empty());
+
+        // Streaming diagnostics.
+        let mut results = Vec::new();
+        diagnostics_with(&file, version, &config, &registry, |diagnostic| {
+            results.push(diagnostic);
+        });
+        assert_eq!(results, expected_results);
+
+        // Parallel diagnostics.
+        for num_threads in [0, 1, 2, 3, 8, 100] {
+            assert_eq!(
+                diagnostics_parallel(&file, version, &config, &registry, num_threads),
+                expected_results,
+                "num_threads: {num_threads}"
+            );
+        }
+    }
+
+    #[test]
+    fn diagnostics_config_works() {
+        let code = quote_as_pretty_string! {
+            #[ink::contract]
+            mod my_contract {
+                impl MyContract {
+                    #[ink(constructor, payable)]
+                    pub fn new() -> Result<Self, ()> {}
+
+                    #[ink(message)]
+                    pub fn my_message(&self) {}
+                }
+            }
+        };
+        let file = InkFile::parse(&code);
+        let registry = MacroRegistry::default();
+        let codes = |config: &DiagnosticsConfig| -> Vec<(Option<DiagnosticCode>, Severity)> {
+            diagnostics(&file, Version::V4, config, &registry)
+                .into_iter()
+                .map(|diagnostic| (diagnostic.code, diagnostic.severity))
+                .collect()
+        };
+
+        // Default config.
+        let mut config = DiagnosticsConfig::default();
+        assert_eq!(
+            codes(&config),
+            vec![(Some(DiagnosticCode::MissingStorage), Severity::Error)]
+        );
+
+        // Severity overrides.
+        config
+            .severity_overrides
+            .insert(DiagnosticCode::MissingStorage, Severity::Warning);
+        assert_eq!(
+            codes(&config),
+            vec![(Some(DiagnosticCode::MissingStorage), Severity::Warning)]
+        );
+
+        // Target ink! version.
+        config.version = Some(Version::V3);
+        assert_eq!(
+            codes(&config),
+            vec![
+                (Some(DiagnosticCode::MissingStorage), Severity::Warning),
+                (Some(DiagnosticCode::VersionIncompatible), Severity::Error),
+            ]
+        );
+
+        // Disabled checks.
+        config.disabled.insert(DiagnosticCode::MissingStorage);
+        assert_eq!(
+            codes(&config),
+            vec![(Some(DiagnosticCode::VersionIncompatible), Severity::Error)]
+        );
+        assert_eq!(
+            diagnostics_parallel(&file, Version::V4, &config, &registry, 2),
+            diagnostics(&file, Version::V4, &config, &registry)
+        );
+    }
+
+    #[test]
+    fn diagnostic_codes_works() {
+        let mut numbers = HashSet::new();
+        for code in DiagnosticCode::ALL {
+            // Numbers are unique.
+            assert!(numbers.insert(code.number()), "{code}");
+
+            assert_eq!(DiagnosticCode::from_name(code.name()), Some(code));
+            assert_eq!(DiagnosticCode::parse(&code.to_string()), Some(code));
+            assert_eq!(
+                DiagnosticCode::parse(&format!("E{:04}", code.number())),
+                Some(code)
+            );
+            assert_eq!(DiagnosticCode::parse(code.name()), Some(code));
+        }
+        assert_eq!(DiagnosticCode::from_name("unknown-code"), None);
+        assert_eq!(DiagnosticCode::parse("E9999"), None);
+
+        assert_eq!(
+            DiagnosticCode::MissingStorage.to_string(),
+            "E0017-missing-storage"
+        );
+        assert_eq!(
+            DiagnosticCode::MissingStorage.code_description(),
+            format!("{DOCS_URL}#e0017-missing-storage")
+        );
+    }
+
+    #[test]
+    fn rank_quickfixes_works() {
+        let quickfix = |label: &str| {
+            Action::builder(label, crate::ActionKind::QuickFix)
+                .range(TextRange::default())
+                .edit(TextEdit::delete(TextRange::default()))
+        };
+        let ranks = |quickfixes: &[Action]| -> Vec<(bool, u8)> {
+            quickfixes
+                .iter()
+                .map(|action| (action.is_preferred, action.priority))
+                .collect()
+        };
+
+        for (mut quickfixes, expected_ranks) in [
+            // Quickfixes aren't implicitly preferred.
+            (vec![quickfix("Fix.").build().unwrap()], vec![(false, 1)]),
+            // Explicitly preferred quickfixes are preserved.
+            (
+                vec![quickfix("Fix.").preferred().build().unwrap()],
+                vec![(true, 1)],
+            ),
+            // Priority decreases with position.
+            (
+                vec![
+                    quickfix("Fix A.").build().unwrap(),
+                    quickfix("Fix B.").build().unwrap(),
+                ],
+                vec![(false, 2), (false, 1)],
+            ),
+            // Explicit ranks are preserved.
+            (
+                vec![
+                    quickfix("Fix A.").build().unwrap(),
+                    quickfix("Fix B.").preferred().priority(5).build().unwrap(),
+                ],
+                vec![(false, 0), (true, 5)],
+            ),
+        ] {
+            rank_quickfixes(&mut quickfixes);
+            assert_eq!(ranks(&quickfixes), expected_ranks);
+        }
+    }
 }
@@ -0,0 +1,122 @@
+//! Caching of diagnostics per document version.
+
+use std::collections::HashMap;
+
+use super::Diagnostic;
+
+/// A cache of the last diagnostics computed for each document.
+///
+/// Documents are identified by an arbitrary (but unique) id (e.g. a URI),
+/// and diagnostics are only recomputed when the document version changes
+/// (e.g. so that unchanged documents don't get re-analyzed).
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticsCache {
+    entries: HashMap<String, CachedDiagnostics>,
+    next_result_id: u64,
+}
+
+/// Cached diagnostics for a document version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedDiagnostics {
+    /// Unique identifier for this diagnostics result
+    /// (i.e. it changes whenever the diagnostics are recomputed).
+    pub result_id: String,
+    /// Version of the document for which the diagnostics were computed.
+    pub version: i32,
+    /// The diagnostics.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl DiagnosticsCache {
+    /// Creates an empty diagnostics cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached diagnostics for the document version
+    /// or computes (and caches) them if the document version isn't cached.
+    pub fn get_or_compute<F>(&mut self, id: &str, version: i32, compute: F) -> &CachedDiagnostics
+    where
+        F: FnOnce() -> Vec<Diagnostic>,
+    {
+        let is_cached = self
+            .entries
+            .get(id)
+            .map_or(false, |entry| entry.version == version);
+        if !is_cached {
+            self.next_result_id += 1;
+            self.entries.insert(
+                id.to_string(),
+                CachedDiagnostics {
+                    result_id: self.next_result_id.to_string(),
+                    version,
+                    diagnostics: compute(),
+                },
+            );
+        }
+        &self.entries[id]
+    }
+
+    /// Returns the cached diagnostics (if any) for the document (regardless of version).
+    pub fn get(&self, id: &str) -> Option<&CachedDiagnostics> {
+        self.entries.get(id)
+    }
+
+    /// Removes the cached diagnostics for the document.
+    pub fn remove(&mut self, id: &str) -> Option<CachedDiagnostics> {
+        self.entries.remove(id)
+    }
+
+    /// Removes all cached diagnostics (e.g. after a configuration change).
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Analysis;
+
+    #[test]
+    fn diagnostics_cache_works() {
+        let mut cache = DiagnosticsCache::new();
+        let mut n_computations = 0;
+        let mut diagnostics = |cache: &mut DiagnosticsCache, id: &str, version: i32, code: &str| {
+            cache
+                .get_or_compute(id, version, || {
+                    n_computations += 1;
+                    Analysis::new(code).diagnostics()
+                })
+                .clone()
+        };
+
+        // Computes diagnostics for new documents.
+        let code = "#[ink::contract]\nmod my_contract {}";
+        let result = diagnostics(&mut cache, "1", 0, code);
+        // 3 Expected diagnostics for missing storage, constructor and message.
+        assert_eq!(result.diagnostics.len(), 3);
+        assert_eq!(result.version, 0);
+
+        // Returns cached diagnostics for unchanged document versions.
+        let cached_result = diagnostics(&mut cache, "1", 0, "");
+        assert_eq!(cached_result, result);
+
+        // Recomputes diagnostics for new document versions (with a new result id).
+        let updated_result = diagnostics(&mut cache, "1", 1, "");
+        assert!(updated_result.diagnostics.is_empty());
+        assert_ne!(updated_result.result_id, result.result_id);
+
+        // Caches diagnostics per document.
+        let other_result = diagnostics(&mut cache, "2", 1, code);
+        assert_eq!(other_result.diagnostics.len(), 3);
+        assert_ne!(other_result.result_id, updated_result.result_id);
+        assert_eq!(n_computations, 3);
+
+        // Removes cached diagnostics.
+        assert!(cache.remove("1").is_some());
+        assert!(cache.get("1").is_none());
+        cache.clear();
+        assert!(cache.get("2").is_none());
+    }
+}
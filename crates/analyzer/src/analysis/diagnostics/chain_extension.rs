@@ -2,17 +2,19 @@
 
 use ink_analyzer_ir::ast::{AstNode, HasName};
 use ink_analyzer_ir::meta::MetaValue;
+use ink_analyzer_ir::syntax::TextRange;
 use ink_analyzer_ir::{
     ast, ChainExtension, Extension, FromInkAttribute, FromSyntax, InkArg, InkArgKind,
-    InkAttributeKind, IsInkTrait,
+    InkAttributeKind, IsInkFn, IsInkTrait,
 };
-use std::collections::HashSet;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
 
 use super::{extension, utils};
 use crate::analysis::actions::entity as entity_actions;
 use crate::analysis::text_edit::TextEdit;
 use crate::analysis::utils as analysis_utils;
-use crate::{Action, ActionKind, Diagnostic, Severity};
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, RelatedInformation, Severity};
 
 const CHAIN_EXTENSION_SCOPE_NAME: &str = "chain extension";
 
@@ -55,6 +57,10 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, chain_extension: &ChainExtensi
     // Ensures that no ink! extension ids are overlapping, see `ensure_no_overlapping_ids` doc.
     ensure_no_overlapping_ids(results, chain_extension);
 
+    // Ensures that ink! extensions with `handle_status = false` have compatible return types,
+    // see `ensure_compatible_handle_status_return_types` doc.
+    ensure_compatible_handle_status_return_types(results, chain_extension);
+
     // Ensures that only valid quasi-direct ink! attribute descendants (i.e ink! descendants without any ink! ancestors),
     // see `ensure_valid_quasi_direct_ink_descendants` doc.
     ensure_valid_quasi_direct_ink_descendants(results, chain_extension);
@@ -101,6 +107,7 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, chain_extension:
                                 .to_string(),
                             range,
                             severity: Severity::Error,
+                            code: Some(DiagnosticCode::InvalidTraitItem),
                             quickfixes: Some(vec![Action {
                                 label: "Add ink! extension attribute.".to_string(),
                                 kind: ActionKind::QuickFix,
@@ -123,7 +130,11 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, chain_extension:
                                     },
                                 ))
                                 .collect(),
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
                             }]),
+                            related_information: None,
                         });
                     }
                 }
@@ -148,6 +159,7 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, chain_extension:
                                 |it| it.syntax().text_range(),
                             ),
                         severity: Severity::Error,
+                        code: Some(DiagnosticCode::InvalidErrorCode),
                         quickfixes: name_marker.as_ref().map(|name| {
                             vec![Action {
                                 label: "Rename associated type to `ErrorCode`.".to_string(),
@@ -157,8 +169,12 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, chain_extension:
                                     "ErrorCode".to_string(),
                                     name.syntax().text_range(),
                                 )],
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
                             }]
                         }),
+                        related_information: None,
                     });
                 }
 
@@ -199,6 +215,7 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, chain_extension:
                             .to_string(),
                         range: type_alias.syntax().text_range(),
                         severity: Severity::Error,
+                        code: Some(DiagnosticCode::InvalidErrorCode),
                         quickfixes: Some(vec![Action {
                             label: "Add `ErrorCode` default type.".to_string(),
                             kind: ActionKind::QuickFix,
@@ -208,7 +225,11 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, chain_extension:
                                 insert_offset,
                                 Some(format!("{insert_prefix}${{1:()}}{insert_suffix}")),
                             )],
+                            document_edits: Vec::new(),
+                            is_preferred: true,
+                            priority: 0,
                         }]),
+                        related_information: None,
                     });
                 }
             },
@@ -245,12 +266,19 @@ fn ensure_error_code_type_quantity(
                         .to_string(),
                     range: analysis_utils::ink_trait_declaration_range(chain_extension),
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::InvalidErrorCode),
                     quickfixes: entity_actions::add_error_code(
                         chain_extension,
                         ActionKind::QuickFix,
                         None,
                     )
-                    .map(|action| vec![action]),
+                    .map(|action| {
+                        vec![Action {
+                            is_preferred: true,
+                            ..action
+                        }]
+                    }),
+                    related_information: None,
                 });
             } else if error_codes.len() > 1 {
                 for item in &error_codes[1..] {
@@ -259,13 +287,18 @@ fn ensure_error_code_type_quantity(
                             .to_string(),
                         range: item.syntax().text_range(),
                         severity: Severity::Error,
+                        code: Some(DiagnosticCode::InvalidErrorCode),
                         quickfixes: Some(vec![Action {
                             label: "Remove duplicate `ErrorCode` type for ink! chain extension."
                                 .to_string(),
                             kind: ActionKind::QuickFix,
                             range: item.syntax().text_range(),
                             edits: vec![TextEdit::delete(item.syntax().text_range())],
+                            document_edits: Vec::new(),
+                            is_preferred: false,
+                            priority: 0,
                         }]),
+                        related_information: None,
                     });
                 }
             };
@@ -277,48 +310,165 @@ fn ensure_error_code_type_quantity(
 ///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/chain_extension.rs#L292-L306>.
 fn ensure_no_overlapping_ids(results: &mut Vec<Diagnostic>, chain_extension: &ChainExtension) {
-    let mut seen_ids: HashSet<u32> = HashSet::new();
+    // Maps ids to the text range of the first ink! extension with the id.
+    let mut seen_ids: HashMap<u32, TextRange> = HashMap::new();
     let mut unavailable_ids = init_unavailable_ids(chain_extension);
     for (idx, extension) in chain_extension.extensions().iter().enumerate() {
         if let Some(id) = extension.id() {
-            if seen_ids.get(&id).is_some() {
-                // Determines text range for the argument value.
-                let value_range_option = extension
-                    .ink_attr()
-                    .args()
-                    .iter()
-                    .find(|it| *it.kind() == InkArgKind::Extension)
-                    .and_then(InkArg::value)
-                    .map(MetaValue::text_range);
-                results.push(Diagnostic {
-                    message: "Extension ids must be unique across all ink! extensions in an ink! chain extension."
-                        .to_string(),
-                    range: value_range_option.unwrap_or(extension.ink_attr().syntax().text_range()),
-                    severity: Severity::Error,
-                    quickfixes: value_range_option.map(|range| {
-                        let suggested_id = analysis_utils::suggest_unique_id(
-                            Some(idx as u32 + 1),
-                            &mut unavailable_ids,
-                        );
-                        vec![Action {
-                            label: "Replace with a unique extension id.".to_string(),
-                            kind: ActionKind::QuickFix,
-                            range,
-                            edits: vec![TextEdit::replace_with_snippet(
-                                format!("{suggested_id}"),
+            // Determines text range for the argument value.
+            let value_range_option = extension
+                .ink_attr()
+                .args()
+                .iter()
+                .find(|it| *it.kind() == InkArgKind::Extension)
+                .and_then(InkArg::value)
+                .map(MetaValue::text_range);
+            let range = value_range_option.unwrap_or(extension.ink_attr().syntax().text_range());
+            match seen_ids.get(&id) {
+                Some(first_range) => {
+                    results.push(Diagnostic {
+                        message: "Extension ids must be unique across all ink! extensions in an ink! chain extension."
+                            .to_string(),
+                        range,
+                        severity: Severity::Error,
+                        code: Some(DiagnosticCode::OverlappingExtensionIds),
+                        quickfixes: value_range_option.map(|range| {
+                            let suggested_id = analysis_utils::suggest_unique_id(
+                                Some(idx as u32 + 1),
+                                &mut unavailable_ids,
+                            );
+                            vec![Action {
+                                label: "Replace with a unique extension id.".to_string(),
+                                kind: ActionKind::QuickFix,
                                 range,
-                                Some(format!("${{1:{suggested_id}}}")),
-                            )],
-                        }]
-                    }),
-                });
+                                edits: vec![TextEdit::replace_with_snippet(
+                                    format!("{suggested_id}"),
+                                    range,
+                                    Some(format!("${{1:{suggested_id}}}")),
+                                )],
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
+                            }]
+                            .into_iter()
+                            // Alternatively, renumbers all ink! extension ids sequentially.
+                            .chain(entity_actions::renumber_extensions(
+                                chain_extension,
+                                ActionKind::QuickFix,
+                            ))
+                            .collect()
+                        }),
+                        related_information: Some(vec![RelatedInformation {
+                            message: format!("Extension id `{id}` is first used here."),
+                            range: *first_range,
+                        }]),
+                    });
+                }
+                None => {
+                    seen_ids.insert(id, range);
+                }
             }
+        }
+    }
+}
 
-            seen_ids.insert(id);
+/// Ensures that ink! extensions with `handle_status = false` have return types that are compatible with
+/// ignoring the status code returned by the chain extension method.
+///
+/// The status code is never converted into the `ErrorCode` type when `handle_status = false`,
+/// so a `Result<T, Self::ErrorCode>` return type is most likely a mistake.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/macro/src/lib.rs#L1065-L1079>.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/chain_extension.rs#L147-L163>.
+fn ensure_compatible_handle_status_return_types(
+    results: &mut Vec<Diagnostic>,
+    chain_extension: &ChainExtension,
+) {
+    for extension in chain_extension.extensions() {
+        let handle_status_false_arg = extension
+            .handle_status_arg()
+            .filter(|arg| arg.as_bool() == Ok(false));
+        let ret_ty = extension
+            .fn_item()
+            .and_then(ast::Fn::ret_type)
+            .as_ref()
+            .and_then(ast::RetType::ty);
+        let result_args = ret_ty
+            .as_ref()
+            .and_then(result_type_args)
+            .filter(|(_, ty)| {
+                let ty_text = ty
+                    .syntax()
+                    .descendants_with_tokens()
+                    .filter_map(|elem| elem.into_token())
+                    .filter(|token| !token.kind().is_trivia())
+                    .join("");
+                ty_text == "Self::ErrorCode"
+            });
+        if let Some(((arg, (ok_ty, ty)), ret_ty)) =
+            handle_status_false_arg.zip(result_args).zip(ret_ty)
+        {
+            // Edit range for quickfix.
+            let range = analysis_utils::ink_arg_and_delimiter_removal_range(&arg, None);
+            let ret_ty_range = ret_ty.syntax().text_range();
+            results.push(Diagnostic {
+                message:
+                    "The status code is ignored for ink! extensions with `handle_status = false`, \
+                so `Self::ErrorCode` is never returned as an error."
+                        .to_string(),
+                range: ty.syntax().text_range(),
+                severity: Severity::Warning,
+                code: Some(DiagnosticCode::IgnoredStatusCode),
+                quickfixes: Some(vec![
+                    // Returns the `Ok` type directly (i.e. the status code is ignored).
+                    Action {
+                        label: format!("Change return type to `{ok_ty}`."),
+                        kind: ActionKind::QuickFix,
+                        range: ret_ty_range,
+                        edits: vec![TextEdit::replace(ok_ty.to_string(), ret_ty_range)],
+                        document_edits: Vec::new(),
+                        is_preferred: true,
+                        priority: 0,
+                    },
+                    // Handles the status code (i.e. it's converted into `Self::ErrorCode`).
+                    Action {
+                        label: "Remove `handle_status = false`.".to_string(),
+                        kind: ActionKind::QuickFix,
+                        range,
+                        edits: vec![TextEdit::delete(range)],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
+                    },
+                ]),
+                related_information: None,
+            });
         }
     }
 }
 
+/// Returns the `Ok` and error types of a `Result<T, E>` type (if any).
+fn result_type_args(ty: &ast::Type) -> Option<(ast::Type, ast::Type)> {
+    match ty {
+        ast::Type::PathType(path_type) => {
+            let segment = path_type.path()?.segment()?;
+            if segment.name_ref()?.text() != "Result" {
+                return None;
+            }
+            let mut type_args = segment
+                .generic_arg_list()?
+                .generic_args()
+                .filter_map(|arg| match arg {
+                    ast::GenericArg::TypeArg(type_arg) => type_arg.ty(),
+                    _ => None,
+                });
+            type_args.next().zip(type_args.next())
+        }
+        _ => None,
+    }
+}
+
 /// Ensures that only valid quasi-direct ink! attribute descendants (i.e ink! descendants without any ink! ancestors).
 ///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/chain_extension.rs#L476-L487>.
@@ -1165,11 +1315,123 @@ mod tests {
                 "chain extension: {code}"
             );
             // Verifies quickfixes.
-            let quick_fix_label = &results[0].quickfixes.as_ref().unwrap()[0].label;
+            let quickfixes = results[0].quickfixes.as_ref().unwrap();
+            let quick_fix_label = &quickfixes[0].label;
             assert!(
                 quick_fix_label.contains("Replace")
                     && quick_fix_label.contains("unique extension id")
             );
+            assert!(quickfixes[1].label.contains("Renumber"));
+            // Verifies related information.
+            assert_eq!(
+                results[0].related_information.as_ref().map(Vec::len),
+                Some(1),
+                "chain extension: {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn compatible_handle_status_return_types_works() {
+        for code in valid_chain_extensions!().chain([quote! {
+            #[ink::chain_extension]
+            pub trait MyChainExtension {
+                type ErrorCode = ();
+
+                #[ink(extension=1)]
+                fn my_extension() -> Result<bool, Self::ErrorCode>;
+
+                #[ink(extension=2, handle_status=true)]
+                fn my_extension2() -> Result<bool, Self::ErrorCode>;
+
+                #[ink(extension=3, handle_status=false)]
+                fn my_extension3() -> Result<bool, MyError>;
+            }
+        }]) {
+            let chain_extension = parse_first_chain_extension(quote_as_str! {
+                #code
+            });
+
+            let mut results = Vec::new();
+            ensure_compatible_handle_status_return_types(&mut results, &chain_extension);
+            assert!(results.is_empty(), "chain extension: {code}");
+        }
+    }
+
+    #[test]
+    fn incompatible_handle_status_return_types_fails() {
+        for (code, ok_ty, (start_ret_ty_pat, end_ret_ty_pat), start_pat, end_pat) in [
+            (
+                quote! {
+                    #[ink(extension=1, handle_status=false)]
+                    fn my_extension() -> Result<bool, Self::ErrorCode>;
+                },
+                "bool",
+                (
+                    "<-Result<bool, Self::ErrorCode>",
+                    "Result<bool, Self::ErrorCode>",
+                ),
+                Some("<-, handle_status"),
+                Some("handle_status = false"),
+            ),
+            (
+                quote! {
+                    #[ink(extension=1)]
+                    #[ink(handle_status=false)]
+                    fn my_extension(a: i32) -> core::result::Result<(), Self::ErrorCode>;
+                },
+                "()",
+                (
+                    "<-core::result::Result<(), Self::ErrorCode>",
+                    "core::result::Result<(), Self::ErrorCode>",
+                ),
+                Some("<-#[ink(handle_status"),
+                Some("#[ink(handle_status = false)]"),
+            ),
+        ] {
+            let code = quote_as_pretty_string! {
+                #[ink::chain_extension]
+                pub trait MyChainExtension {
+                    type ErrorCode = ();
+
+                    #code
+                }
+            };
+            let chain_extension = parse_first_chain_extension(&code);
+
+            let mut results = Vec::new();
+            ensure_compatible_handle_status_return_types(&mut results, &chain_extension);
+
+            // 1 diagnostic for the incompatible return type.
+            assert_eq!(results.len(), 1, "chain extension: {code}");
+            // Diagnostic should be a warning.
+            assert_eq!(
+                results[0].severity,
+                Severity::Warning,
+                "chain extension: {code}"
+            );
+            // Verifies quickfixes.
+            let expected_quickfixes = vec![
+                TestResultAction {
+                    label: "Change return type",
+                    edits: vec![TestResultTextRange {
+                        text: ok_ty,
+                        start_pat: Some(start_ret_ty_pat),
+                        end_pat: Some(end_ret_ty_pat),
+                    }],
+                },
+                TestResultAction {
+                    label: "Remove `handle_status = false`",
+                    edits: vec![TestResultTextRange {
+                        text: "",
+                        start_pat,
+                        end_pat,
+                    }],
+                },
+            ];
+            let quickfixes = results[0].quickfixes.as_ref().unwrap();
+            assert!(quickfixes[0].is_preferred, "chain extension: {code}");
+            verify_actions(&code, quickfixes, &expected_quickfixes);
         }
     }
 
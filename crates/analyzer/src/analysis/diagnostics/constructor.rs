@@ -6,7 +6,7 @@ use ink_analyzer_ir::{ast, Constructor, IsInkFn};
 use super::utils;
 use crate::analysis::text_edit::TextEdit;
 use crate::analysis::utils as analysis_utils;
-use crate::{Action, ActionKind, Diagnostic, Severity};
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, Severity, Version};
 
 const CONSTRUCTOR_SCOPE_NAME: &str = "constructor";
 
@@ -15,7 +15,7 @@ const CONSTRUCTOR_SCOPE_NAME: &str = "constructor";
 /// The entry point for finding ink! constructor semantic rules is the constructor module of the `ink_ir` crate.
 ///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_impl/constructor.rs#L155-L170>.
-pub fn diagnostics(results: &mut Vec<Diagnostic>, constructor: &Constructor) {
+pub fn diagnostics(results: &mut Vec<Diagnostic>, constructor: &Constructor, version: Version) {
     // Runs generic diagnostics, see `utils::run_generic_diagnostics` doc.
     utils::run_generic_diagnostics(results, constructor);
 
@@ -45,6 +45,12 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, constructor: &Constructor) {
         }
     }
 
+    // Ensures that ink! constructor only returns a `Result` for ink! versions that support fallible constructors,
+    // see `ensure_valid_return_type_for_version` doc.
+    if let Some(diagnostic) = ensure_valid_return_type_for_version(constructor, version) {
+        results.push(diagnostic);
+    }
+
     // Ensures that ink! constructor has no ink! descendants, see `utils::ensure_no_ink_descendants` doc.
     utils::ensure_no_ink_descendants(results, constructor, CONSTRUCTOR_SCOPE_NAME);
 }
@@ -68,6 +74,7 @@ fn ensure_return_type(fn_item: &ast::Fn) -> Option<Diagnostic> {
         message: "ink! constructor must have a return type.".to_string(),
         range,
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidSignature),
         quickfixes: fn_item
             .param_list()
             .map(|param_list| param_list.syntax().text_range().end())
@@ -81,11 +88,53 @@ fn ensure_return_type(fn_item: &ast::Fn) -> Option<Diagnostic> {
                         insert_offset,
                         Some(" -> ${1:Self}".to_string()),
                     )],
+                    document_edits: Vec::new(),
+                    is_preferred: true,
+                    priority: 0,
                 }]
             }),
+        related_information: None,
     })
 }
 
+/// Ensures that ink! constructor doesn't return a `Result` when targeting ink! versions
+/// that don't support fallible constructors (i.e. ink! 3.x).
+///
+/// Since ink! 4.x, constructors can return either `Self` or `Result<Self, E>`,
+/// while ink! 3.x constructors must return `Self`.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v3.4.0/crates/lang/ir/src/ir/item_impl/constructor.rs>.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_impl/constructor.rs#L91-L105>.
+fn ensure_valid_return_type_for_version(
+    constructor: &Constructor,
+    version: Version,
+) -> Option<Diagnostic> {
+    match version {
+        Version::V3 => constructor
+            .fn_item()
+            .and_then(|fn_item| fn_item.ret_type()?.ty())
+            .filter(|ty| match ty {
+                ast::Type::PathType(path_type) => path_type
+                    .path()
+                    .and_then(|path| path.segment()?.name_ref())
+                    .map_or(false, |name_ref| name_ref.text() == "Result"),
+                _ => false,
+            })
+            .map(|ty| Diagnostic {
+                message: "Fallible ink! constructors (i.e. returning `Result<Self, E>`) \
+                are only supported since ink! 4.x, so ink! 3.x constructors must return `Self`."
+                    .to_string(),
+                range: ty.syntax().text_range(),
+                severity: Severity::Error,
+                code: Some(DiagnosticCode::VersionIncompatible),
+                quickfixes: None,
+                related_information: None,
+            }),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -603,6 +652,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn valid_return_type_for_version_works() {
+        // All ink! constructors are valid when targeting ink! 4.x.
+        for code in valid_constructors!() {
+            let constructor = parse_first_constructor(quote_as_str! {
+                #code
+            });
+
+            let result = ensure_valid_return_type_for_version(&constructor, Version::V4);
+            assert!(result.is_none(), "constructor: {code}");
+        }
+
+        // Infallible (and `payable`) ink! constructors are valid when targeting ink! 3.x.
+        for code in [
+            quote! {
+                #[ink(constructor)]
+                pub fn my_constructor() -> Self {}
+            },
+            quote! {
+                #[ink(constructor, payable)]
+                pub fn my_constructor() -> Self {}
+            },
+            quote! {
+                #[ink(constructor, payable, selector=1)]
+                pub fn my_constructor(a: i32) -> Self {}
+            },
+            quote! {
+                #[ink(constructor)]
+                #[ink(payable)]
+                pub fn my_constructor() -> Self {}
+            },
+        ] {
+            let constructor = parse_first_constructor(quote_as_str! {
+                #code
+            });
+
+            let result = ensure_valid_return_type_for_version(&constructor, Version::V3);
+            assert!(result.is_none(), "constructor: {code}");
+        }
+    }
+
+    #[test]
+    fn return_type_for_version_fails() {
+        for code in [
+            quote! {
+                #[ink(constructor)]
+                pub fn my_constructor() -> Result<Self, ()> {}
+            },
+            quote! {
+                #[ink(constructor, payable)]
+                pub fn my_constructor(a: i32) -> Result<Self, Error> {}
+            },
+        ] {
+            let code = quote_as_pretty_string! {
+                #code
+            };
+            let constructor = parse_first_constructor(&code);
+
+            let result = ensure_valid_return_type_for_version(&constructor, Version::V3);
+
+            // Verifies diagnostics.
+            assert!(result.is_some(), "constructor: {code}");
+            let diagnostic = result.as_ref().unwrap();
+            assert_eq!(diagnostic.severity, Severity::Error, "constructor: {code}");
+            assert_eq!(
+                diagnostic.code,
+                Some(DiagnosticCode::VersionIncompatible),
+                "constructor: {code}"
+            );
+            assert!(
+                code[diagnostic.range].starts_with("Result<Self"),
+                "constructor: {code}"
+            );
+            // No quickfixes (i.e. the constructor body would need to be updated as well).
+            assert!(diagnostic.quickfixes.is_none(), "constructor: {code}");
+        }
+    }
+
     #[test]
     fn no_ink_descendants_works() {
         for code in valid_constructors!() {
@@ -689,7 +816,7 @@ mod tests {
             });
 
             let mut results = Vec::new();
-            diagnostics(&mut results, &constructor);
+            diagnostics(&mut results, &constructor, Version::V4);
             assert!(results.is_empty(), "constructor: {code}");
         }
     }
@@ -4,23 +4,32 @@ use ink_analyzer_ir::ast::HasName;
 use ink_analyzer_ir::meta::MetaValue;
 use ink_analyzer_ir::syntax::{AstNode, SyntaxKind, SyntaxNode, SyntaxToken};
 use ink_analyzer_ir::{
-    ast, Contract, FromInkAttribute, FromSyntax, InkArg, InkArgKind, InkAttributeKind,
-    InkMacroKind, IsInkCallable, IsInkEntity, Selector, SelectorArg, Storage,
+    ast, Contract, FromInkAttribute, FromSyntax, InkArg, InkArgKind, InkAttribute,
+    InkAttributeKind, InkMacroKind, IsInkCallable, IsInkEntity, IsInkFn, IsInkStruct, Message,
+    Selector, SelectorArg, Storage,
 };
 use std::collections::HashSet;
 
-use super::{constructor, event, ink_e2e_test, ink_impl, ink_test, message, storage, utils};
+use super::{
+    constructor, event, ink_e2e_test, ink_impl, ink_test, message, storage, utils,
+    DiagnosticsConfig,
+};
 use crate::analysis::actions::entity as entity_actions;
 use crate::analysis::text_edit::TextEdit;
 use crate::analysis::utils as analysis_utils;
-use crate::{Action, ActionKind, Diagnostic, Severity};
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, DocumentEdit, Severity, Version};
 
 /// Runs all ink! contract diagnostics.
 ///
 /// The entry point for finding ink! contract semantic rules is the contract module of the `ink_ir` crate.
 ///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/contract.rs#L47-L73>.
-pub fn diagnostics(results: &mut Vec<Diagnostic>, contract: &Contract) {
+pub fn diagnostics(
+    results: &mut Vec<Diagnostic>,
+    contract: &Contract,
+    version: Version,
+    config: &DiagnosticsConfig,
+) {
     // Runs generic diagnostics, see `utils::run_generic_diagnostics` doc.
     utils::run_generic_diagnostics(results, contract);
 
@@ -39,12 +48,12 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, contract: &Contract) {
 
     // Runs ink! event diagnostics, see `event::diagnostics` doc.
     for item in contract.events() {
-        event::diagnostics(results, item);
+        event::diagnostics(results, item, config);
     }
 
     // Runs ink! impl diagnostics, see `ink_impl::diagnostics` doc.
     for item in contract.impls() {
-        ink_impl::diagnostics(results, item, true);
+        ink_impl::diagnostics(results, item, true, version);
     }
 
     // Ensures that at least one ink! constructor, see `ensure_contains_constructor` doc.
@@ -54,7 +63,7 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, contract: &Contract) {
 
     // Runs ink! constructor diagnostics, see `constructor::diagnostics` doc.
     for item in contract.constructors() {
-        constructor::diagnostics(results, item);
+        constructor::diagnostics(results, item, version);
     }
 
     // Ensures that at least one ink! message, see `ensure_contains_message` doc.
@@ -71,6 +80,10 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, contract: &Contract) {
     // see `ensure_no_overlapping_selectors` doc.
     ensure_no_overlapping_selectors(results, contract);
 
+    // Ensures that no ink! messages are unreachable because of selector overrides in ink! trait definitions,
+    // see `ensure_no_unreachable_messages` doc.
+    ensure_no_unreachable_messages(results, contract);
+
     // Ensures that at most one wildcard selector exists among ink! messages, as well as ink! constructors,
     // see `ensure_at_most_one_wildcard_selector` doc.
     ensure_at_most_one_wildcard_selector(results, contract);
@@ -79,10 +92,26 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, contract: &Contract) {
     // see `ensure_root_items` doc.
     ensure_root_items(results, contract);
 
+    // Ensures that the environment type (if any) is valid, see `utils::ensure_valid_environment` doc.
+    // NOTE: The environment type is used inside the ink! contract `mod` (i.e. by generated code).
+    if let Some(env_arg) = contract.env_arg() {
+        let scope = contract
+            .module()
+            .and_then(ast::Module::item_list)
+            .map_or(contract.syntax().clone(), |item_list| {
+                item_list.syntax().clone()
+            });
+        utils::ensure_valid_environment(results, &env_arg, &scope);
+    }
+
     // Ensures that ink! messages and constructors are defined in the root of an `impl` item,
     // see `ensure_impl_parent_for_callables` doc.
     ensure_impl_parent_for_callables(results, contract);
 
+    // Ensures that `impl` blocks that use the ink! environment are ink! impls,
+    // see `ensure_env_impls_annotated` doc.
+    ensure_env_impls_annotated(results, contract);
+
     // Runs ink! test diagnostics, see `ink_test::diagnostics` doc.
     for item in contract.tests() {
         ink_test::diagnostics(results, item);
@@ -90,12 +119,33 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, contract: &Contract) {
 
     // Runs ink! e2e test diagnostics, see `ink_e2e_test::diagnostics` doc.
     for item in contract.e2e_tests() {
-        ink_e2e_test::diagnostics(results, item);
+        ink_e2e_test::diagnostics(results, item, config);
     }
 
     // Ensures that only valid quasi-direct ink! attribute descendants (i.e ink! descendants without any ink! ancestors),
     // See `ensure_valid_quasi_direct_ink_descendants` doc.
     ensure_valid_quasi_direct_ink_descendants(results, contract);
+
+    // Ensures that `Mapping` is only used in ink! storage and isn't re-created in ink! messages,
+    // see `ensure_valid_mapping_usage` doc.
+    ensure_valid_mapping_usage(results, contract);
+
+    // Ensures that ink! constructors initialize all ink! storage fields,
+    // see `ensure_initialized_storage_fields` doc.
+    ensure_initialized_storage_fields(results, contract);
+
+    // Ensures that ink! events are emitted, see `ensure_emitted_events` doc.
+    ensure_emitted_events(results, contract);
+
+    // Ensures that `Lazy` storage fields read in ink! messages are set in at least one ink! constructor,
+    // see `ensure_initialized_lazy_fields` doc.
+    ensure_initialized_lazy_fields(results, contract);
+
+    // Ensures that debug-only code is gated behind the `ink-debug` feature (only in audit mode),
+    // see `ensure_gated_debug_code` doc.
+    if config.audit {
+        ensure_gated_debug_code(results, contract, config.cargo_toml.as_deref());
+    }
 }
 
 /// Ensures that ink! contract attribute is applied to an inline `mod` item.
@@ -120,6 +170,7 @@ fn ensure_inline_module(contract: &Contract) -> Option<Diagnostic> {
                     .to_string(),
                 range: declaration_range,
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::NonInlineModule),
                 quickfixes: Some(vec![Action {
                     label: "Add inline body to ink! contract `mod`.".to_string(),
                     kind: ActionKind::QuickFix,
@@ -128,13 +179,18 @@ fn ensure_inline_module(contract: &Contract) -> Option<Diagnostic> {
                         format!("{}{{}}", if semicolon_token.is_some() { " " } else { "" }),
                         quickfix_range,
                     )],
+                    document_edits: Vec::new(),
+                    is_preferred: true,
+                    priority: 0,
                 }]),
+                related_information: None,
             }
         }),
         None => Some(Diagnostic {
             message: "ink! contracts must be inline `mod` items".to_string(),
             range: declaration_range,
             severity: Severity::Error,
+            code: Some(DiagnosticCode::NonInlineModule),
             quickfixes: Some(if contract.syntax().kind() == SyntaxKind::ITEM_LIST {
                 vec![Action::remove_attribute(contract.ink_attr())]
             } else {
@@ -143,6 +199,7 @@ fn ensure_inline_module(contract: &Contract) -> Option<Diagnostic> {
                     Action::remove_item(contract.syntax()),
                 ]
             }),
+            related_information: None,
         }),
     }
 }
@@ -162,11 +219,20 @@ fn ensure_storage_quantity(results: &mut Vec<Diagnostic>, contract: &Contract) {
             message: "Missing ink! storage definition.".to_string(),
             range: analysis_utils::contract_declaration_range(contract),
             severity: Severity::Error,
-            quickfixes: entity_actions::add_storage(contract, ActionKind::QuickFix, None)
-                .map(|action| vec![action]),
+            code: Some(DiagnosticCode::MissingStorage),
+            quickfixes: entity_actions::add_storage(contract, ActionKind::QuickFix, None).map(
+                |action| {
+                    vec![Action {
+                        is_preferred: true,
+                        ..action
+                    }]
+                },
+            ),
+            related_information: None,
         },
         "Only one ink! storage definition can be defined for an ink! contract.",
         Severity::Error,
+        DiagnosticCode::DuplicateStorage,
     );
 }
 
@@ -185,12 +251,19 @@ fn ensure_contains_constructor(contract: &Contract) -> Option<Diagnostic> {
                 .to_string(),
             range,
             severity: Severity::Error,
+            code: Some(DiagnosticCode::MissingConstructor),
             quickfixes: entity_actions::add_constructor_to_contract(
                 contract,
                 ActionKind::QuickFix,
                 None,
             )
-            .map(|action| vec![action]),
+            .map(|action| {
+                vec![Action {
+                    is_preferred: true,
+                    ..action
+                }]
+            }),
+            related_information: None,
         },
     )
 }
@@ -209,12 +282,19 @@ fn ensure_contains_message(contract: &Contract) -> Option<Diagnostic> {
             message: "At least one ink! message must be defined for an ink! contract.".to_string(),
             range,
             severity: Severity::Error,
+            code: Some(DiagnosticCode::MissingMessage),
             quickfixes: entity_actions::add_message_to_contract(
                 contract,
                 ActionKind::QuickFix,
                 None,
             )
-            .map(|action| vec![action]),
+            .map(|action| {
+                vec![Action {
+                    is_preferred: true,
+                    ..action
+                }]
+            }),
+            related_information: None,
         },
     )
 }
@@ -302,6 +382,7 @@ fn ensure_no_overlapping_selectors(results: &mut Vec<Diagnostic>, contract: &Con
                         .or(fn_declaration_range())
                         .unwrap_or(node.text_range()),
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::OverlappingSelectors),
                     quickfixes: value_range_option
                         // Quickfix for using a unique selector value.
                         .map(|range| {
@@ -318,6 +399,9 @@ fn ensure_no_overlapping_selectors(results: &mut Vec<Diagnostic>, contract: &Con
                                     range,
                                     Some(format!("${{1:{suggested_id}}}")),
                                 )],
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
                             }]
                         })
                         .or(fn_name_option().map(|name| {
@@ -330,8 +414,12 @@ fn ensure_no_overlapping_selectors(results: &mut Vec<Diagnostic>, contract: &Con
                                     name.syntax().text_range(),
                                     Some(format!("${{1:{name}2}}")),
                                 )],
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
                             }]
                         })),
+                    related_information: None,
                 });
             }
 
@@ -340,6 +428,126 @@ fn ensure_no_overlapping_selectors(results: &mut Vec<Diagnostic>, contract: &Con
     }
 }
 
+/// Ensures that no ink! messages are unreachable at dispatch time because of selector overrides
+/// in ink! trait definitions.
+///
+/// The selector of an ink! message in a trait implementation is overridden by the `selector` argument
+/// of the matching ink! trait definition message (if any), so it can clash with the selector of another ink! message
+/// even though their composed selectors (see `ensure_no_overlapping_selectors`) are unique.
+/// The ink! message that's defined first takes precedence at dispatch time.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/trait_def/item/mod.rs#L336-L337>.
+fn ensure_no_unreachable_messages(results: &mut Vec<Diagnostic>, contract: &Contract) {
+    // Maps ink! messages to their effective selector and selector override (if any).
+    let mut effective_selectors: Vec<(u32, &Message, Option<SelectorArg>)> = Vec::new();
+    for ink_impl in contract.impls() {
+        let trait_definition = ink_impl.trait_definition();
+        for message in ink_impl.messages() {
+            let selector_override = trait_definition.as_ref().and_then(|trait_definition| {
+                let name = message.fn_item()?.name()?.to_string();
+                trait_definition
+                    .messages()
+                    .iter()
+                    .find(|declaration| {
+                        declaration
+                            .fn_item()
+                            .and_then(HasName::name)
+                            .map_or(false, |declaration_name| {
+                                declaration_name.to_string() == name
+                            })
+                    })?
+                    .selector_arg()
+                    .filter(|arg| arg.as_u32().is_some())
+            });
+            let selector = match selector_override.as_ref() {
+                Some(arg) => arg.as_u32(),
+                None => message.composed_selector().map(Selector::into_be_u32),
+            };
+            if let Some(selector) = selector {
+                effective_selectors.push((selector, message, selector_override));
+            }
+        }
+    }
+    effective_selectors.sort_by_key(|(_, message, _)| message.syntax().text_range().start());
+
+    let mut unavailable_ids: HashSet<u32> = effective_selectors
+        .iter()
+        .map(|(selector, ..)| *selector)
+        .collect();
+    for (idx, (selector, message, selector_override)) in effective_selectors.iter().enumerate() {
+        // Only handles overlaps that involve a selector override,
+        // other overlaps are handled by `ensure_no_overlapping_selectors`.
+        let Some((_, winner, winner_override)) =
+            effective_selectors[..idx]
+                .iter()
+                .find(|(other_selector, _, other_override)| {
+                    other_selector == selector
+                        && (selector_override.is_some() || other_override.is_some())
+                })
+        else {
+            continue;
+        };
+
+        let fn_name = |message: &Message| {
+            message
+                .fn_item()
+                .and_then(HasName::name)
+                .map(|name| name.to_string())
+                .unwrap_or_default()
+        };
+        // Prefers changing the explicit selector of the unreachable ink! message (if any).
+        let explicit_selector = selector_override
+            .clone()
+            .or_else(|| message.selector_arg())
+            .or_else(|| winner_override.clone())
+            .or_else(|| winner.selector_arg());
+        let value_range_option = explicit_selector
+            .as_ref()
+            .map(SelectorArg::arg)
+            .and_then(InkArg::value)
+            .map(MetaValue::text_range);
+        results.push(Diagnostic {
+            message: format!(
+                "ink! message `{}` is unreachable at dispatch time because its selector `0x{selector:08x}`{} \
+                is also used by ink! message `{}`, which takes precedence because it's defined first.",
+                fn_name(message),
+                if selector_override.is_some() {
+                    " (overridden by the ink! trait definition)"
+                } else {
+                    ""
+                },
+                fn_name(winner),
+            ),
+            range: message
+                .fn_item()
+                .and_then(HasName::name)
+                .map(|name| name.syntax().text_range())
+                .unwrap_or(message.syntax().text_range()),
+            severity: Severity::Error,
+            code: Some(DiagnosticCode::UnreachableMessage),
+            quickfixes: value_range_option.map(|range| {
+                // Quickfix for using a unique selector value.
+                let suggested_id =
+                    analysis_utils::suggest_unique_id(Some(idx as u32 + 1), &mut unavailable_ids);
+                vec![Action {
+                    label: "Replace with a unique selector.".to_string(),
+                    kind: ActionKind::QuickFix,
+                    range,
+                    edits: vec![TextEdit::replace_with_snippet(
+                        format!("{suggested_id}"),
+                        range,
+                        Some(format!("${{1:{suggested_id}}}")),
+                    )],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
+                }]
+            }),
+            related_information: None,
+        });
+    }
+}
+
 /// Returns all ink! selector arguments for a list of ink! callable entities.
 fn get_selector_args<T>(items: &[T]) -> Vec<SelectorArg>
 where
@@ -381,12 +589,17 @@ fn ensure_at_most_one_wildcard_selector(results: &mut Vec<Diagnostic>, contract:
                         message: format!("At most one wildcard (`_`) selector can be defined across all ink! {name}s in an ink! contract."),
                         range: selector.text_range(),
                         severity: Severity::Error,
+                        code: Some(DiagnosticCode::MultipleWildcardSelectors),
                         quickfixes: Some(vec![Action {
                             label: "Remove wildcard selector.".to_string(),
                             kind: ActionKind::QuickFix,
                             range,
                             edits: vec![TextEdit::delete(range)],
+                            document_edits: Vec::new(),
+                            is_preferred: false,
+                            priority: 0,
                         }]),
+                        related_information: None,
                     });
                 } else {
                     has_seen_wildcard = true;
@@ -396,16 +609,23 @@ fn ensure_at_most_one_wildcard_selector(results: &mut Vec<Diagnostic>, contract:
     }
 }
 
-/// Ensures that item is defined in the root of this specific ink! contract.
+/// Ensures that item is defined in the root of this specific ink! contract
+/// (or optionally in the root of a non-ink! inline child `mod` item of the ink! contract).
 fn ensure_parent_contract<T>(
     contract: &Contract,
     item: &T,
     ink_scope_name: &str,
+    allow_child_modules: bool,
 ) -> Option<Diagnostic>
 where
     T: FromSyntax,
 {
-    let is_parent = match ink_analyzer_ir::ink_parent::<Contract>(item.syntax()) {
+    let parent_contract = if allow_child_modules {
+        analysis_utils::parent_contract(item.syntax())
+    } else {
+        ink_analyzer_ir::ink_parent::<Contract>(item.syntax())
+    };
+    let is_parent = match parent_contract {
         Some(parent_contract) => parent_contract.syntax() == contract.syntax(),
         None => false,
     };
@@ -416,6 +636,7 @@ where
         ),
         range: item.syntax().text_range(),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidScope),
         quickfixes: contract
             .module()
             .and_then(ast::Module::item_list)
@@ -428,10 +649,12 @@ where
                     Some(analysis_utils::item_children_indenting(contract.syntax()).as_str()),
                 )]
             }),
+        related_information: None,
     })
 }
 
-/// Ensures that ink! storage, ink! events and ink! impls are defined in the root of the ink! contract.
+/// Ensures that ink! storage, ink! events and ink! impls are defined in the root of the ink! contract
+/// (ink! impls can also be split across non-ink! inline child `mod` items of the ink! contract).
 ///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_mod.rs#L377-L379>.
 ///
@@ -450,18 +673,18 @@ fn ensure_root_items(results: &mut Vec<Diagnostic>, contract: &Contract) {
     results.extend(
         // All storage definitions.
         ink_analyzer_ir::ink_closest_descendants::<Storage>(contract.syntax())
-            .filter_map(|item| ensure_parent_contract(contract, &item, "storage"))
+            .filter_map(|item| ensure_parent_contract(contract, &item, "storage", false))
             .chain(
                 contract
                     .events()
                     .iter()
-                    .filter_map(|item| ensure_parent_contract(contract, item, "event")),
+                    .filter_map(|item| ensure_parent_contract(contract, item, "event", false)),
             )
             .chain(
                 contract
                     .impls()
                     .iter()
-                    .filter_map(|item| ensure_parent_contract(contract, item, "impl")),
+                    .filter_map(|item| ensure_parent_contract(contract, item, "impl", true)),
             ),
     );
 }
@@ -520,6 +743,549 @@ fn ensure_valid_quasi_direct_ink_descendants(results: &mut Vec<Diagnostic>, cont
     });
 }
 
+/// Ensures that (inherent) `impl` blocks for the ink! storage `struct` that use the ink! environment
+/// (i.e. `self.env()` or `Self::env()`) are either annotated with `#[ink(impl)]`
+/// or contain at least one ink! constructor or ink! message.
+///
+/// Otherwise, the `impl` block is a plain Rust `impl` block (i.e. it compiles and the ink! environment
+/// is accessible in it, but ink! doesn't treat it as part of the contract's ink! impls),
+/// so the diagnostic is only a hint that the `#[ink(impl)]` annotation may be missing.
+fn ensure_env_impls_annotated(results: &mut Vec<Diagnostic>, contract: &Contract) {
+    let Some(item_list) = contract.module().and_then(ast::Module::item_list) else {
+        return;
+    };
+    let Some(storage_name) = contract
+        .storage()
+        .and_then(IsInkStruct::struct_item)
+        .and_then(|struct_item| struct_item.name())
+        .map(|name| name.to_string())
+    else {
+        return;
+    };
+    for impl_item in item_list
+        .syntax()
+        .descendants()
+        .filter_map(ast::Impl::cast)
+        .filter(|impl_item| {
+            impl_item.trait_().is_none()
+                && is_storage_self_ty(impl_item, &storage_name)
+                && analysis_utils::parent_contract(impl_item.syntax())
+                    .map_or(false, |parent| parent.syntax() == contract.syntax())
+        })
+    {
+        let has_ink_attrs = impl_item
+            .syntax()
+            .descendants()
+            .filter_map(ast::Attr::cast)
+            .any(|attr| InkAttribute::cast(attr).is_some());
+        let uses_env = impl_item
+            .syntax()
+            .descendants()
+            .filter_map(ast::Expr::cast)
+            .any(|expr| analysis_utils::is_env_call(&expr));
+        if has_ink_attrs || !uses_env {
+            continue;
+        }
+
+        let range = analysis_utils::ast_item_declaration_range(&ast::Item::Impl(impl_item.clone()))
+            .unwrap_or(impl_item.syntax().text_range());
+        results.push(Diagnostic {
+            message: "This `impl` block uses the ink! environment but it's not an ink! impl \
+            (i.e. it has no `#[ink(impl)]` annotation nor any ink! constructors or ink! messages), \
+            so it's treated as a plain Rust `impl` block."
+                .to_string(),
+            range,
+            severity: Severity::Warning,
+            code: Some(DiagnosticCode::MissingInkImpl),
+            quickfixes: Some(vec![Action {
+                label: "Add `#[ink(impl)]` annotation.".to_string(),
+                kind: ActionKind::QuickFix,
+                range,
+                edits: vec![TextEdit::insert(
+                    "#[ink(impl)]".to_string(),
+                    analysis_utils::ink_attribute_insert_offset(impl_item.syntax()),
+                )],
+                document_edits: Vec::new(),
+                // Plain Rust `impl` blocks are valid, so the annotation isn't necessarily the intended fix.
+                is_preferred: false,
+                priority: 0,
+            }]),
+            related_information: None,
+        });
+    }
+}
+
+/// Returns true if the self type of the `impl` block is the ink! storage `struct`.
+fn is_storage_self_ty(impl_item: &ast::Impl, storage_name: &str) -> bool {
+    match impl_item.self_ty() {
+        Some(ast::Type::PathType(path_type)) => path_type
+            .path()
+            .and_then(|path| path.segment())
+            .and_then(|segment| segment.name_ref())
+            .map_or(false, |name_ref| name_ref.text() == storage_name),
+        _ => false,
+    }
+}
+
+/// Ensures that `Mapping` is only used as (part of) the type of ink! storage fields,
+/// and that new `Mapping` values aren't created in ink! messages.
+///
+/// `Mapping` is a lazy (i.e. non-packed) storage type so it can't be used in ink! events,
+/// nor as ink! constructor and ink! message parameters or return types.
+/// Also, creating a new `Mapping` value (e.g. via `Mapping::default()`) in an ink! message doesn't clear
+/// the existing entries of the `Mapping` storage field it replaces.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/storage/src/lazy/mapping.rs>.
+fn ensure_valid_mapping_usage(results: &mut Vec<Diagnostic>, contract: &Contract) {
+    const MAPPING_DOCS: &str = "https://use.ink/datastructures/mapping";
+
+    // Ensures that ink! event fields don't use `Mapping`.
+    for event in contract.events() {
+        if let Some(ast::FieldList::RecordFieldList(field_list)) =
+            event.struct_item().and_then(ast::Struct::field_list)
+        {
+            for ty in field_list.fields().filter_map(|field| field.ty()) {
+                if contains_mapping_type(&ty) {
+                    results.push(Diagnostic {
+                        message: format!(
+                            "`Mapping` can only be used in ink! storage, not in ink! event fields. \
+                            See <{MAPPING_DOCS}> for details."
+                        ),
+                        range: ty.syntax().text_range(),
+                        severity: Severity::Warning,
+                        code: Some(DiagnosticCode::MappingMisuse),
+                        quickfixes: None,
+                        related_information: None,
+                    });
+                }
+            }
+        }
+    }
+
+    // Ensures that ink! constructor and ink! message parameters and return types don't use `Mapping`.
+    let fn_items = contract
+        .constructors()
+        .iter()
+        .filter_map(IsInkFn::fn_item)
+        .map(|fn_item| (fn_item, "constructor"))
+        .chain(
+            contract
+                .messages()
+                .iter()
+                .filter_map(IsInkFn::fn_item)
+                .map(|fn_item| (fn_item, "message")),
+        );
+    for (fn_item, ink_scope_name) in fn_items {
+        let param_types = fn_item
+            .param_list()
+            .into_iter()
+            .flat_map(|param_list| param_list.params())
+            .filter_map(|param| param.ty());
+        let ret_type = fn_item.ret_type().and_then(|ret_type| ret_type.ty());
+        for ty in param_types.chain(ret_type) {
+            if contains_mapping_type(&ty) {
+                results.push(Diagnostic {
+                    message: format!(
+                        "`Mapping` can only be used in ink! storage, \
+                        not in ink! {ink_scope_name} parameters or return types. \
+                        See <{MAPPING_DOCS}> for details."
+                    ),
+                    range: ty.syntax().text_range(),
+                    severity: Severity::Warning,
+                    code: Some(DiagnosticCode::MappingMisuse),
+                    quickfixes: None,
+                    related_information: None,
+                });
+            }
+        }
+    }
+
+    // Ensures that new `Mapping` values aren't created in ink! messages.
+    for fn_item in contract.messages().iter().filter_map(IsInkFn::fn_item) {
+        if let Some(body) = fn_item.body() {
+            for call_expr in body.syntax().descendants().filter_map(ast::CallExpr::cast) {
+                let is_mapping_constructor = match call_expr.expr() {
+                    Some(ast::Expr::PathExpr(path_expr)) => {
+                        path_expr.path().map_or(false, |path| {
+                            path.segment()
+                                .and_then(|segment| segment.name_ref())
+                                .map_or(false, |name| {
+                                    matches!(name.text().as_str(), "new" | "default")
+                                })
+                                && path
+                                    .qualifier()
+                                    .and_then(|qualifier| qualifier.segment())
+                                    .and_then(|segment| segment.name_ref())
+                                    .map_or(false, |name| name.text() == "Mapping")
+                        })
+                    }
+                    _ => false,
+                };
+                if is_mapping_constructor {
+                    results.push(Diagnostic {
+                        message: format!(
+                            "Creating a new `Mapping` in an ink! message doesn't clear \
+                            the existing entries of the `Mapping` it replaces in storage, \
+                            use `Mapping::remove` to remove entries instead. \
+                            See <{MAPPING_DOCS}> for details."
+                        ),
+                        range: call_expr.syntax().text_range(),
+                        severity: Severity::Warning,
+                        code: Some(DiagnosticCode::MappingMisuse),
+                        quickfixes: None,
+                        related_information: None,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Ensures that `struct` expressions for the ink! storage `struct` in ink! constructors
+/// (e.g. `Self { .. }`) initialize all ink! storage fields.
+///
+/// `struct` expressions with a base expression (e.g. `Self { value, ..Default::default() }`) are ignored.
+fn ensure_initialized_storage_fields(results: &mut Vec<Diagnostic>, contract: &Contract) {
+    let Some(struct_item) = contract.storage().and_then(IsInkStruct::struct_item) else {
+        return;
+    };
+    let (Some(storage_name), Some(ast::FieldList::RecordFieldList(field_list))) =
+        (struct_item.name(), struct_item.field_list())
+    else {
+        return;
+    };
+    let storage_name = storage_name.to_string();
+    let storage_fields: Vec<String> = field_list
+        .fields()
+        .filter_map(|field| field.name().map(|name| name.to_string()))
+        .collect();
+    if storage_fields.is_empty() {
+        return;
+    }
+
+    for record_expr in contract
+        .constructors()
+        .iter()
+        .filter_map(IsInkFn::fn_item)
+        .filter_map(ast::Fn::body)
+        .flat_map(|body| body.syntax().descendants())
+        .filter_map(ast::RecordExpr::cast)
+    {
+        // Only checks `struct` expressions for the ink! storage `struct` without a base expression.
+        let Some(path) = record_expr.path().filter(|path| {
+            let path = path.to_string();
+            path == "Self" || path == storage_name
+        }) else {
+            continue;
+        };
+        let Some(expr_field_list) = record_expr
+            .record_expr_field_list()
+            .filter(|field_list| field_list.dotdot_token().is_none())
+        else {
+            continue;
+        };
+
+        // Collects missing storage fields.
+        let initialized_fields: HashSet<String> = expr_field_list
+            .fields()
+            .filter_map(|field| field.field_name())
+            .map(|name| name.to_string())
+            .collect();
+        let missing_fields: Vec<&String> = storage_fields
+            .iter()
+            .filter(|name| !initialized_fields.contains(*name))
+            .collect();
+        if missing_fields.is_empty() {
+            continue;
+        }
+
+        let range = path.syntax().text_range();
+        results.push(Diagnostic {
+            message: format!(
+                "Missing initialization for ink! storage field(s) {} in ink! constructor.",
+                missing_fields
+                    .iter()
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            range,
+            severity: Severity::Error,
+            code: Some(DiagnosticCode::UninitializedStorageField),
+            quickfixes: entity_actions::record_expr_fields_insert_edit(
+                &record_expr,
+                &missing_fields
+                    .iter()
+                    .map(|name| format!("{name}: Default::default()"))
+                    .collect::<Vec<String>>(),
+            )
+            .map(|edit| {
+                vec![Action {
+                    label: "Initialize missing storage field(s) with `Default::default()`."
+                        .to_string(),
+                    kind: ActionKind::QuickFix,
+                    range,
+                    edits: vec![edit],
+                    document_edits: Vec::new(),
+                    is_preferred: true,
+                    priority: 0,
+                }]
+            }),
+            related_information: None,
+        });
+    }
+}
+
+/// Ensures that ink! events are emitted (i.e. constructed) somewhere in the ink! contract.
+///
+/// ink! events are emitted by constructing them (e.g. `self.env().emit_event(MyEvent { .. })`),
+/// so ink! events whose names are never used in `struct` expressions (or path expressions
+/// e.g. for unit structs) in the ink! contract are never emitted.
+fn ensure_emitted_events(results: &mut Vec<Diagnostic>, contract: &Contract) {
+    let Some(module) = contract.module() else {
+        return;
+    };
+
+    // Collects names of constructed types (i.e. last path segments).
+    let constructed_names: HashSet<String> = module
+        .syntax()
+        .descendants()
+        .filter_map(|node| match ast::RecordExpr::cast(node.clone()) {
+            Some(record_expr) => record_expr.path(),
+            None => ast::PathExpr::cast(node).and_then(|path_expr| path_expr.path()),
+        })
+        .filter_map(|path| path.segment()?.name_ref())
+        .map(|name_ref| name_ref.to_string())
+        .collect();
+
+    for struct_item in contract
+        .events()
+        .iter()
+        .filter_map(IsInkStruct::struct_item)
+    {
+        let Some(name) = struct_item.name() else {
+            continue;
+        };
+        if constructed_names.contains(&name.to_string()) {
+            continue;
+        }
+
+        let range =
+            analysis_utils::ast_item_declaration_range(&ast::Item::Struct(struct_item.clone()))
+                .unwrap_or(struct_item.syntax().text_range());
+        results.push(Diagnostic {
+            message: format!("ink! event `{name}` is never emitted."),
+            range,
+            severity: Severity::Warning,
+            code: Some(DiagnosticCode::UnusedEntity),
+            quickfixes: Some(vec![Action::remove_item(struct_item.syntax())]),
+            related_information: None,
+        });
+    }
+}
+
+/// Ensures that `Lazy` storage fields that are read in ink! messages
+/// are set (i.e. initialized) in at least one ink! constructor.
+///
+/// Reading a `Lazy` storage field that was never set returns `None` (or the default value)
+/// at runtime, which is rarely the intended behavior.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/storage/src/lazy/mod.rs>.
+fn ensure_initialized_lazy_fields(results: &mut Vec<Diagnostic>, contract: &Contract) {
+    // Collects the names of `Lazy` storage fields.
+    let lazy_fields: Vec<String> = match contract
+        .storage()
+        .and_then(IsInkStruct::struct_item)
+        .and_then(ast::Struct::field_list)
+    {
+        Some(ast::FieldList::RecordFieldList(field_list)) => field_list
+            .fields()
+            .filter(|field| {
+                field
+                    .ty()
+                    .map_or(false, |ty| contains_named_type(&ty, "Lazy"))
+            })
+            .filter_map(|field| field.name().map(|name| name.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    };
+    if lazy_fields.is_empty() {
+        return;
+    }
+
+    // Returns the name of the `Lazy` storage field (if any) that's the receiver of a method call
+    // (e.g. `value` for `self.value.get()`, `instance.value.set(&0)` or `value.set(&0)`).
+    let lazy_receiver_name = |method_call: &ast::MethodCallExpr| -> Option<String> {
+        let name = match method_call.receiver()? {
+            ast::Expr::FieldExpr(field_expr) => field_expr.name_ref()?.to_string(),
+            ast::Expr::PathExpr(path_expr) => path_expr.path()?.as_single_name_ref()?.to_string(),
+            _ => return None,
+        };
+        lazy_fields.contains(&name).then_some(name)
+    };
+    // Collects `Lazy` storage fields that are set in at least one ink! constructor.
+    let initialized_fields: HashSet<String> = method_calls(contract.constructors(), &["set"])
+        .filter_map(|method_call| lazy_receiver_name(&method_call))
+        .collect();
+
+    // Flags reads of `Lazy` storage fields that are never set in any ink! constructor.
+    for method_call in method_calls(contract.messages(), &["get", "get_or_default", "try_get"]) {
+        let is_self_field = matches!(
+            method_call.receiver(),
+            Some(ast::Expr::FieldExpr(field_expr)) if matches!(
+                field_expr.expr(),
+                Some(ast::Expr::PathExpr(path_expr)) if path_expr.to_string() == "self"
+            )
+        );
+        if !is_self_field {
+            continue;
+        }
+        if let Some(name) = lazy_receiver_name(&method_call) {
+            if !initialized_fields.contains(&name) {
+                results.push(Diagnostic {
+                    message: format!(
+                        "`Lazy` storage field `{name}` is read in an ink! message \
+                        but it's never set in any ink! constructor, \
+                        so it returns `None` (or the default value) until it's set."
+                    ),
+                    range: method_call.syntax().text_range(),
+                    severity: Severity::Warning,
+                    code: Some(DiagnosticCode::UninitializedLazy),
+                    quickfixes: None,
+                    related_information: None,
+                });
+            }
+        }
+    }
+}
+
+/// Ensures that debug-only code (i.e. `debug_print!` and `debug_println!` macro calls)
+/// is gated behind the `ink-debug` feature, so that it's excluded from release builds.
+///
+/// The quickfix wraps the enclosing statement (or item) with a `#[cfg(feature = "ink-debug")]` attribute
+/// and declares the `ink-debug` feature in the project's `Cargo.toml` (if its text is known).
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/env/src/lib.rs>.
+fn ensure_gated_debug_code(
+    results: &mut Vec<Diagnostic>,
+    contract: &Contract,
+    cargo_toml: Option<&str>,
+) {
+    for macro_call in contract
+        .syntax()
+        .descendants()
+        .filter_map(ast::MacroCall::cast)
+    {
+        let is_debug_macro = macro_call
+            .path()
+            .and_then(|path| path.segment())
+            .and_then(|segment| segment.name_ref())
+            .map_or(false, |name| {
+                matches!(name.text().as_str(), "debug_print" | "debug_println")
+            });
+        // Ignores debug-only code that's already gated (or that's only compiled for tests).
+        let is_gated = || {
+            macro_call.syntax().ancestors().any(|node| {
+                node.children().filter_map(ast::Attr::cast).any(|attr| {
+                    analysis_utils::is_cfg_ink_debug_attr(&attr)
+                        || analysis_utils::is_cfg_test_attr(&attr)
+                })
+            })
+        };
+        if !is_debug_macro || is_gated() {
+            continue;
+        }
+
+        // Determines the enclosing statement (or item), tail expressions are ignored
+        // because attributes on expressions are unstable.
+        let gate_target = macro_call.syntax().ancestors().find(|node| {
+            node.parent().map_or(false, |parent| {
+                ast::ItemList::can_cast(parent.kind())
+                    || ast::StmtList::cast(parent).map_or(false, |stmt_list| {
+                        stmt_list
+                            .tail_expr()
+                            .map_or(true, |tail_expr| tail_expr.syntax() != node)
+                    })
+            })
+        });
+        results.push(Diagnostic {
+            message: "Debug-only code should be gated behind the `ink-debug` feature \
+            (i.e. `#[cfg(feature = \"ink-debug\")]`) to keep it out of release builds."
+                .to_string(),
+            range: macro_call.syntax().text_range(),
+            severity: Severity::Warning,
+            code: Some(DiagnosticCode::UngatedDebugCode),
+            quickfixes: gate_target.map(|node| {
+                vec![Action {
+                    label: "Gate debug-only code behind the `ink-debug` feature.".to_string(),
+                    kind: ActionKind::QuickFix,
+                    range: macro_call.syntax().text_range(),
+                    edits: vec![TextEdit::insert(
+                        r#"#[cfg(feature = "ink-debug")]"#.to_string(),
+                        node.text_range().start(),
+                    )],
+                    // Declares the `ink-debug` feature in the `Cargo.toml` (if necessary).
+                    document_edits: cargo_toml
+                        .and_then(|cargo_toml| {
+                            analysis_utils::cargo_feature_edit(
+                                cargo_toml,
+                                "ink-debug",
+                                r#"["ink/ink-debug"]"#,
+                            )
+                        })
+                        .map(|edit| DocumentEdit {
+                            path: "Cargo.toml".to_string(),
+                            edits: vec![edit],
+                        })
+                        .into_iter()
+                        .collect(),
+                    is_preferred: false,
+                    priority: 0,
+                }]
+            }),
+            related_information: None,
+        });
+    }
+}
+
+/// Returns method call expressions with the given method names in the bodies of the ink! callables.
+fn method_calls<'a, T>(
+    items: &'a [T],
+    method_names: &'a [&str],
+) -> impl Iterator<Item = ast::MethodCallExpr> + 'a
+where
+    T: IsInkFn,
+{
+    items
+        .iter()
+        .filter_map(IsInkFn::fn_item)
+        .filter_map(ast::Fn::body)
+        .flat_map(|body| body.syntax().descendants())
+        .filter_map(ast::MethodCallExpr::cast)
+        .filter(|method_call| {
+            method_call
+                .name_ref()
+                .map_or(false, |name| method_names.contains(&name.text().as_str()))
+        })
+}
+
+/// Returns true if the type is (or contains) a `Mapping` type.
+fn contains_mapping_type(ty: &ast::Type) -> bool {
+    contains_named_type(ty, "Mapping")
+}
+
+/// Returns true if the type is (or contains) a type with the given name (e.g. `Mapping` or `Lazy`).
+fn contains_named_type(ty: &ast::Type, type_name: &str) -> bool {
+    ty.syntax()
+        .descendants()
+        .filter_map(ast::PathSegment::cast)
+        .any(|segment| {
+            segment
+                .name_ref()
+                .map_or(false, |name| name.text() == type_name)
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1338,6 +2104,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn no_unreachable_messages_works() {
+        for code in valid_contracts!() {
+            let contract = parse_first_contract(quote_as_str! {
+                #code
+            });
+
+            let mut results = Vec::new();
+            ensure_no_unreachable_messages(&mut results, &contract);
+            assert!(results.is_empty(), "contract: {code}");
+        }
+    }
+
+    #[test]
+    fn unreachable_messages_fails() {
+        for (code, expected_unreachable, expected_quickfix_pat) in [
+            // Inherent message takes precedence over trait message with a selector override.
+            (
+                quote_as_pretty_string! {
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message, selector = 0xA)]
+                        fn my_trait_message(&self);
+                    }
+
+                    #[ink::contract]
+                    mod my_contract {
+                        impl MyContract {
+                            #[ink(message, selector = 10)]
+                            pub fn my_message(&self) {}
+                        }
+
+                        impl MyTrait for MyContract {
+                            #[ink(message)]
+                            fn my_trait_message(&self) {}
+                        }
+                    }
+                },
+                "my_trait_message",
+                "selector = 0xA",
+            ),
+            // Trait message with a selector override takes precedence over inherent message.
+            (
+                quote_as_pretty_string! {
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message, selector = 0xA)]
+                        fn my_trait_message(&self);
+                    }
+
+                    #[ink::contract]
+                    mod my_contract {
+                        impl MyTrait for MyContract {
+                            #[ink(message)]
+                            fn my_trait_message(&self) {}
+                        }
+
+                        impl MyContract {
+                            #[ink(message, selector = 10)]
+                            pub fn my_message(&self) {}
+                        }
+                    }
+                },
+                "my_message",
+                "selector = 10",
+            ),
+        ] {
+            let contract = parse_first_contract(&code);
+
+            let mut results = Vec::new();
+            ensure_no_unreachable_messages(&mut results, &contract);
+            assert_eq!(results.len(), 1, "code: {code}");
+            assert_eq!(results[0].severity, Severity::Error);
+            assert!(results[0]
+                .message
+                .contains(&format!("`{expected_unreachable}` is unreachable")));
+            // Verifies that the quickfix replaces the explicit selector of the unreachable message.
+            let quickfixes = results[0].quickfixes.as_ref().unwrap();
+            assert_eq!(quickfixes.len(), 1);
+            assert!(quickfixes[0].label.contains("unique selector"));
+            assert_eq!(
+                &code[quickfixes[0].edits[0].range],
+                expected_quickfix_pat.rsplit(' ').next().unwrap()
+            );
+            assert_eq!(
+                u32::from(quickfixes[0].edits[0].range.end()) as usize,
+                parse_offset_at(&code, Some(expected_quickfix_pat)).unwrap()
+            );
+        }
+    }
+
     #[test]
     // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_mod.rs#L883-L902>.
     fn one_or_no_wildcard_selectors_works() {
@@ -1561,33 +2418,110 @@ mod tests {
     }
 
     #[test]
-    fn root_items_not_in_root_fails() {
-        let code = quote_as_pretty_string! {
-            #[ink::contract]
-            mod my_contract {
-                fn my_contract_container() {
+    fn root_items_in_child_modules_and_nested_contracts_works() {
+        for code in [
+            // ink! impls in inline child modules of the ink! contract.
+            quote_as_str! {
+                #[ink::contract]
+                mod my_contract {
                     #[ink(storage)]
                     pub struct MyContract {}
 
-                    #[ink(event)]
-                    pub struct MyEvent {}
+                    mod constructors {
+                        use super::*;
 
-                    impl MyContract {
-                        #[ink(constructor)]
-                        pub fn my_constructor() -> Self {
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn my_constructor() -> Self {}
                         }
-
-                        #[ink(message)]
-                        pub fn my_message() {}
                     }
 
-                    #[ink(impl)]
-                    impl MyContract {}
+                    mod messages {
+                        mod nested {
+                            #[ink(impl)]
+                            impl super::super::MyContract {
+                                #[ink(message)]
+                                pub fn my_message(&self) {}
+                            }
+                        }
+                    }
                 }
-            }
-        };
-        let contract = parse_first_contract(&code);
-
+            },
+            // ink! contract nested in a non-ink! module.
+            quote_as_str! {
+                mod outer {
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContract {}
+
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn my_constructor() -> Self {}
+
+                            #[ink(message)]
+                            pub fn my_message(&self) {}
+                        }
+                    }
+                }
+            },
+        ] {
+            let contract = parse_first_contract(code);
+
+            let mut results = Vec::new();
+            ensure_root_items(&mut results, &contract);
+            assert!(results.is_empty(), "contract: {code}");
+        }
+    }
+
+    #[test]
+    fn storage_in_child_module_fails() {
+        let contract = parse_first_contract(quote_as_str! {
+            #[ink::contract]
+            mod my_contract {
+                mod storage {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+                }
+            }
+        });
+
+        let mut results = Vec::new();
+        ensure_root_items(&mut results, &contract);
+
+        // ink! storage must still be defined in the root of the ink! contract.
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].code, Some(DiagnosticCode::InvalidScope));
+    }
+
+    #[test]
+    fn root_items_not_in_root_fails() {
+        let code = quote_as_pretty_string! {
+            #[ink::contract]
+            mod my_contract {
+                fn my_contract_container() {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+
+                    #[ink(event)]
+                    pub struct MyEvent {}
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {
+                        }
+
+                        #[ink(message)]
+                        pub fn my_message() {}
+                    }
+
+                    #[ink(impl)]
+                    impl MyContract {}
+                }
+            }
+        };
+        let contract = parse_first_contract(&code);
+
         let mut results = Vec::new();
         ensure_root_items(&mut results, &contract);
 
@@ -1748,16 +2682,1013 @@ mod tests {
     }
 
     #[test]
-    // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_mod.rs#L593-L640>.
-    fn compound_diagnostic_works() {
-        for code in valid_contracts!() {
+    fn env_impls_annotated_works() {
+        for code in [
+            // ink! impl annotation.
+            quote! {
+                #[ink(impl)]
+                impl MyContract {
+                    fn caller(&self) -> AccountId {
+                        self.env().caller()
+                    }
+                }
+            },
+            // ink! callables.
+            quote! {
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn caller(&self) -> AccountId {
+                        self.env().caller()
+                    }
+                }
+            },
+            // No ink! environment usage.
+            quote! {
+                impl MyContract {
+                    fn is_zero(value: u32) -> bool {
+                        value == 0
+                    }
+                }
+            },
+            // Non-storage self type.
+            quote! {
+                pub struct Helper;
+
+                impl Helper {
+                    fn caller() -> AccountId {
+                        Self::env().caller()
+                    }
+                }
+            },
+            // Trait implementation.
+            quote! {
+                impl Default for MyContract {
+                    fn default() -> Self {
+                        Self::env().caller();
+                        Self {}
+                    }
+                }
+            },
+        ] {
+            let contract = parse_first_contract(quote_as_str! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+
+                    #code
+                }
+            });
+
+            let mut results = Vec::new();
+            ensure_env_impls_annotated(&mut results, &contract);
+            assert!(results.is_empty(), "code: {code}");
+        }
+    }
+
+    #[test]
+    fn unannotated_env_impls_fails() {
+        for code in [
+            quote! {
+                fn caller(&self) -> AccountId {
+                    self.env().caller()
+                }
+            },
+            quote! {
+                fn block_number() -> BlockNumber {
+                    Self::env().block_number()
+                }
+            },
+        ] {
+            let code = quote_as_pretty_string! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+
+                    impl MyContract {
+                        #code
+                    }
+                }
+            };
+            let contract = parse_first_contract(&code);
+
+            let mut results = Vec::new();
+            ensure_env_impls_annotated(&mut results, &contract);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "code: {code}");
+            assert_eq!(results[0].severity, Severity::Warning, "code: {code}");
+            assert_eq!(
+                results[0].code,
+                Some(DiagnosticCode::MissingInkImpl),
+                "code: {code}"
+            );
+            // Verifies quickfixes.
+            verify_actions(
+                &code,
+                results[0].quickfixes.as_ref().unwrap(),
+                &[TestResultAction {
+                    label: "Add `#[ink(impl)]`",
+                    edits: vec![TestResultTextRange {
+                        text: "#[ink(impl)]",
+                        start_pat: Some("<-impl MyContract {"),
+                        end_pat: Some("<-impl MyContract {"),
+                    }],
+                }],
+            );
+        }
+    }
+
+    #[test]
+    fn valid_mapping_usage_works() {
+        for code in valid_contracts!().chain([quote! {
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {
+                    balances: Mapping<AccountId, Balance>,
+                }
+
+                #[ink(event)]
+                pub struct Transfer {
+                    value: Balance,
+                }
+
+                impl MyContract {
+                    #[ink(constructor)]
+                    pub fn new() -> Self {
+                        let mut balances = Mapping::default();
+                        Self { balances }
+                    }
+
+                    #[ink(message)]
+                    pub fn balance_of(&self, owner: AccountId) -> Balance {
+                        self.balances.get(owner).unwrap_or_default()
+                    }
+                }
+            }
+        }]) {
             let contract = parse_first_contract(quote_as_str! {
                 #code
             });
 
             let mut results = Vec::new();
-            diagnostics(&mut results, &contract);
+            ensure_valid_mapping_usage(&mut results, &contract);
             assert!(results.is_empty(), "contract: {code}");
         }
     }
+
+    #[test]
+    fn invalid_mapping_usage_fails() {
+        for (items, expected_start_pat, expected_end_pat) in [
+            // `Mapping` in ink! event fields.
+            (
+                quote! {
+                    #[ink(event)]
+                    pub struct Transfer {
+                        balances: Mapping<AccountId, Balance>,
+                    }
+                },
+                "<-Mapping<AccountId, Balance>->",
+                "Mapping<AccountId, Balance>->",
+            ),
+            // `Mapping` in ink! message parameters and return types.
+            (
+                quote! {
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn set(&mut self, balances: Mapping<AccountId, Balance>) {}
+                    }
+                },
+                "<-Mapping<AccountId, Balance>->",
+                "Mapping<AccountId, Balance>->",
+            ),
+            (
+                quote! {
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn get(&self) -> Option<ink::storage::Mapping<AccountId, Balance>> {}
+                    }
+                },
+                "<-Option<ink::storage::Mapping",
+                "Mapping<AccountId, Balance>>",
+            ),
+            // New `Mapping` values in ink! messages.
+            (
+                quote! {
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn reset(&mut self) {
+                            self.balances = Mapping::default();
+                        }
+                    }
+                },
+                "<-Mapping::default()",
+                "Mapping::default()",
+            ),
+            (
+                quote! {
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn reset(&mut self) {
+                            self.balances = Mapping::<AccountId, Balance>::new();
+                        }
+                    }
+                },
+                "<-Mapping::<AccountId, Balance>::new()",
+                "Mapping::<AccountId, Balance>::new()",
+            ),
+        ] {
+            let code = quote_as_pretty_string! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {
+                        balances: Mapping<AccountId, Balance>,
+                    }
+
+                    #items
+                }
+            };
+            let contract = parse_first_contract(&code);
+
+            let mut results = Vec::new();
+            ensure_valid_mapping_usage(&mut results, &contract);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "contract: {code}");
+            assert_eq!(results[0].severity, Severity::Warning, "contract: {code}");
+            assert!(results[0].message.contains("Mapping"), "contract: {code}");
+            assert_eq!(
+                results[0].range,
+                TextRange::new(
+                    TextSize::from(parse_offset_at(&code, Some(expected_start_pat)).unwrap() as u32),
+                    TextSize::from(parse_offset_at(&code, Some(expected_end_pat)).unwrap() as u32)
+                ),
+                "contract: {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn initialized_lazy_fields_works() {
+        for code in valid_contracts!().chain([
+            // Set in constructor via `Self` instance.
+            quote! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {
+                        value: Lazy<u8>,
+                    }
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            let mut instance = Self { value: Lazy::new() };
+                            instance.value.set(&1);
+                            instance
+                        }
+
+                        #[ink(message)]
+                        pub fn get(&self) -> Option<u8> {
+                            self.value.get()
+                        }
+                    }
+                }
+            },
+            // Set in constructor via local variable.
+            quote! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {
+                        value: ink::storage::Lazy<u8>,
+                    }
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            let mut value = Lazy::new();
+                            value.set(&1);
+                            Self { value }
+                        }
+
+                        #[ink(message)]
+                        pub fn get(&self) -> u8 {
+                            self.value.get_or_default()
+                        }
+                    }
+                }
+            },
+            // Never read.
+            quote! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {
+                        value: Lazy<u8>,
+                    }
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            Self { value: Lazy::new() }
+                        }
+
+                        #[ink(message)]
+                        pub fn set(&mut self, value: u8) {
+                            self.value.set(&value);
+                        }
+                    }
+                }
+            },
+        ]) {
+            let contract = parse_first_contract(quote_as_str! {
+                #code
+            });
+
+            let mut results = Vec::new();
+            ensure_initialized_lazy_fields(&mut results, &contract);
+            assert!(results.is_empty(), "contract: {code}");
+        }
+    }
+
+    #[test]
+    fn uninitialized_lazy_fields_fails() {
+        for (constructor_body, message_body, expected_pat) in [
+            (
+                quote! { Self { value: Lazy::new() } },
+                quote! { self.value.get() },
+                "self.value.get()",
+            ),
+            (
+                quote! { Self { value: Default::default() } },
+                quote! { self.value.get_or_default() },
+                "self.value.get_or_default()",
+            ),
+            // Setting a different field doesn't initialize the `Lazy` field.
+            (
+                quote! {
+                    let mut instance = Self { value: Lazy::new() };
+                    instance.other.set(&1);
+                    instance
+                },
+                quote! { self.value.get() },
+                "self.value.get()",
+            ),
+        ] {
+            let code = quote_as_pretty_string! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {
+                        value: Lazy<u8>,
+                        other: Lazy<u8>,
+                    }
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            #constructor_body
+                        }
+
+                        #[ink(message)]
+                        pub fn get(&self) {
+                            #message_body;
+                        }
+                    }
+                }
+            };
+            let contract = parse_first_contract(&code);
+
+            let mut results = Vec::new();
+            ensure_initialized_lazy_fields(&mut results, &contract);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "contract: {code}");
+            assert_eq!(results[0].severity, Severity::Warning, "contract: {code}");
+            assert!(results[0].message.contains("`value`"), "contract: {code}");
+            assert_eq!(
+                results[0].range,
+                TextRange::new(
+                    TextSize::from(
+                        parse_offset_at(&code, Some(&format!("<-{expected_pat}"))).unwrap() as u32
+                    ),
+                    TextSize::from(parse_offset_at(&code, Some(expected_pat)).unwrap() as u32)
+                ),
+                "contract: {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn initialized_storage_fields_works() {
+        for code in valid_contracts!().chain([
+            // All fields initialized.
+            quote! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {
+                        value: bool,
+                        count: u32,
+                    }
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new(value: bool) -> Self {
+                            Self { value, count: 0 }
+                        }
+
+                        #[ink(constructor)]
+                        pub fn default() -> Self {
+                            MyContract {
+                                value: false,
+                                count: 0,
+                            }
+                        }
+                    }
+                }
+            },
+            // Base expression.
+            quote! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    #[derive(Default)]
+                    pub struct MyContract {
+                        value: bool,
+                        count: u32,
+                    }
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new(value: bool) -> Self {
+                            Self {
+                                value,
+                                ..Default::default()
+                            }
+                        }
+                    }
+                }
+            },
+            // Non-storage `struct` expressions and non-constructor `struct` expressions.
+            quote! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {
+                        value: bool,
+                    }
+
+                    pub struct Other {
+                        value: bool,
+                    }
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            let _ = Other {};
+                            Self { value: false }
+                        }
+
+                        #[ink(message)]
+                        pub fn reset(&mut self) {
+                            *self = Self {};
+                        }
+                    }
+                }
+            },
+        ]) {
+            let contract = parse_first_contract(quote_as_str! {
+                #code
+            });
+
+            let mut results = Vec::new();
+            ensure_initialized_storage_fields(&mut results, &contract);
+            assert!(results.is_empty(), "contract: {code}");
+        }
+    }
+
+    #[test]
+    fn uninitialized_storage_fields_fails() {
+        for (constructor_body, expected_range_pats, expected_missing, expected_quickfix) in [
+            (
+                quote! { Self { value: false } },
+                (Some("<-Self { value"), Some("<- { value")),
+                vec!["`count`"],
+                TestResultTextRange {
+                    text: ", count: Default::default()",
+                    start_pat: Some("value: false"),
+                    end_pat: Some("value: false"),
+                },
+            ),
+            (
+                quote! { MyContract { count: 0 } },
+                (Some("<-MyContract { count"), Some("<- { count")),
+                vec!["`value`"],
+                TestResultTextRange {
+                    text: ", value: Default::default()",
+                    start_pat: Some("count: 0"),
+                    end_pat: Some("count: 0"),
+                },
+            ),
+            (
+                quote! { Self {} },
+                (Some("<-Self {}"), Some("<- {}")),
+                vec!["`value`", "`count`"],
+                TestResultTextRange {
+                    text: "{ value: Default::default(), count: Default::default() }",
+                    start_pat: Some("<-{}"),
+                    end_pat: Some("{}"),
+                },
+            ),
+        ] {
+            let code = quote_as_pretty_string! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {
+                        value: bool,
+                        count: u32,
+                    }
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            #constructor_body
+                        }
+                    }
+                }
+            };
+            let contract = parse_first_contract(&code);
+
+            let mut results = Vec::new();
+            ensure_initialized_storage_fields(&mut results, &contract);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "contract: {code}");
+            assert_eq!(results[0].severity, Severity::Error, "contract: {code}");
+            for name in expected_missing {
+                assert!(results[0].message.contains(name), "contract: {code}");
+            }
+            assert_eq!(
+                results[0].range,
+                TextRange::new(
+                    TextSize::from(parse_offset_at(&code, expected_range_pats.0).unwrap() as u32),
+                    TextSize::from(parse_offset_at(&code, expected_range_pats.1).unwrap() as u32)
+                ),
+                "contract: {code}"
+            );
+            // Verifies quickfixes.
+            let expected_quickfixes = vec![TestResultAction {
+                label: "Initialize",
+                edits: vec![expected_quickfix],
+            }];
+            let quickfixes = results[0].quickfixes.as_ref().unwrap();
+            verify_actions(&code, quickfixes, &expected_quickfixes);
+        }
+    }
+
+    #[test]
+    fn emitted_events_works() {
+        for code in [
+            // Emitted via `struct` expression.
+            quote! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+
+                    #[ink(event)]
+                    pub struct MyEvent {
+                        value: bool,
+                    }
+
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn my_message(&self) {
+                            self.env().emit_event(MyEvent { value: true });
+                        }
+                    }
+                }
+            },
+            // Emitted via a local variable of a unit struct.
+            quote! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+
+                    #[ink(event)]
+                    pub struct MyEvent;
+
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn my_message(&self) {
+                            let event = MyEvent;
+                            self.env().emit_event(event);
+                        }
+                    }
+                }
+            },
+        ] {
+            let contract = parse_first_contract(quote_as_str! {
+                #code
+            });
+
+            let mut results = Vec::new();
+            ensure_emitted_events(&mut results, &contract);
+            assert!(results.is_empty(), "contract: {code}");
+        }
+    }
+
+    #[test]
+    fn unemitted_events_fails() {
+        let code = quote_as_pretty_string! {
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                #[ink(event)]
+                pub struct MyEvent {
+                    value: bool,
+                }
+
+                #[ink(event)]
+                pub struct MyOtherEvent {
+                    value: bool,
+                }
+
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn my_message(&self) {
+                        self.env().emit_event(MyEvent { value: true });
+                    }
+                }
+            }
+        };
+        let contract = parse_first_contract(&code);
+
+        let mut results = Vec::new();
+        ensure_emitted_events(&mut results, &contract);
+
+        // Verifies diagnostics.
+        assert_eq!(results.len(), 1, "contract: {code}");
+        assert_eq!(results[0].severity, Severity::Warning);
+        assert_eq!(results[0].code, Some(DiagnosticCode::UnusedEntity));
+        assert!(results[0].message.contains("`MyOtherEvent`"));
+        // Verifies quickfixes.
+        let expected_quickfixes = vec![TestResultAction {
+            label: "Remove item",
+            edits: vec![TestResultTextRange {
+                text: "",
+                start_pat: Some("<-#[ink(event)]->"),
+                end_pat: Some("value: bool,\n    }->"),
+            }],
+        }];
+        let quickfixes = results[0].quickfixes.as_ref().unwrap();
+        verify_actions(&code, quickfixes, &expected_quickfixes);
+        // Removal quickfixes aren't safe to auto-apply.
+        assert!(quickfixes.iter().all(|action| !action.is_preferred));
+    }
+
+    #[test]
+    fn gated_debug_code_works() {
+        for items in [
+            // No debug-only code.
+            quote! {},
+            // Gated statement.
+            quote! {
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn my_message(&self) {
+                        #[cfg(feature = "ink-debug")]
+                        ink::env::debug_println!("my_message");
+                    }
+                }
+            },
+            // Gated block.
+            quote! {
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn my_message(&self) {
+                        #[cfg(feature = "ink-debug")]
+                        {
+                            let value = 1;
+                            ink::env::debug_println!("value: {}", value);
+                        }
+                    }
+                }
+            },
+            // Gated item.
+            quote! {
+                #[cfg(feature = "ink-debug")]
+                fn log(message: &str) {
+                    ink::env::debug_println!("{}", message);
+                }
+            },
+            // Test-only code.
+            quote! {
+                #[cfg(test)]
+                mod tests {
+                    fn it_works() {
+                        ink::env::debug_println!("it_works");
+                    }
+                }
+            },
+        ] {
+            let contract = parse_first_contract(quote_as_str! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {}
+                    }
+
+                    #items
+                }
+            });
+
+            let mut results = Vec::new();
+            ensure_gated_debug_code(&mut results, &contract, None);
+            assert!(results.is_empty(), "items: {items}");
+        }
+    }
+
+    #[test]
+    fn ungated_debug_code_fails() {
+        for (items, cargo_toml, expected_quickfixes, expected_cargo_edit) in [
+            // Statement in ink! message.
+            (
+                quote! {
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn my_message(&self) {
+                            ink::env::debug_println!("my_message");
+                        }
+                    }
+                },
+                None,
+                vec![TestResultAction {
+                    label: "Gate debug-only code",
+                    edits: vec![TestResultTextRange {
+                        text: r#"#[cfg(feature = "ink-debug")]"#,
+                        start_pat: Some("<-ink::env::debug_println!"),
+                        end_pat: Some("<-ink::env::debug_println!"),
+                    }],
+                }],
+                None,
+            ),
+            // Statement in helper function with `[features]` table in `Cargo.toml`.
+            (
+                quote! {
+                    fn log(message: &str) {
+                        debug_print!("{}", message);
+                    }
+                },
+                Some("[package]\nname = \"my_contract\"\n\n[features]\ndefault = [\"std\"]\nstd = [\n    \"ink/std\",\n]\n"),
+                vec![TestResultAction {
+                    label: "Gate debug-only code",
+                    edits: vec![TestResultTextRange {
+                        text: r#"#[cfg(feature = "ink-debug")]"#,
+                        start_pat: Some("<-debug_print!"),
+                        end_pat: Some("<-debug_print!"),
+                    }],
+                }],
+                Some(("ink-debug = [\"ink/ink-debug\"]\n", 86)),
+            ),
+            // Statement with no `[features]` table in `Cargo.toml`.
+            (
+                quote! {
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn my_message(&self) {
+                            ink_env::debug_println!("my_message");
+                        }
+                    }
+                },
+                Some("[package]\nname = \"my_contract\"\n"),
+                vec![TestResultAction {
+                    label: "Gate debug-only code",
+                    edits: vec![TestResultTextRange {
+                        text: r#"#[cfg(feature = "ink-debug")]"#,
+                        start_pat: Some("<-ink_env::debug_println!"),
+                        end_pat: Some("<-ink_env::debug_println!"),
+                    }],
+                }],
+                Some(("\n[features]\nink-debug = [\"ink/ink-debug\"]\n", 31)),
+            ),
+            // Feature already declared in `Cargo.toml`.
+            (
+                quote! {
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn my_message(&self) {
+                            ink::env::debug_println!("my_message");
+                        }
+                    }
+                },
+                Some("[features]\nink-debug = [\"ink/ink-debug\"]\n"),
+                vec![TestResultAction {
+                    label: "Gate debug-only code",
+                    edits: vec![TestResultTextRange {
+                        text: r#"#[cfg(feature = "ink-debug")]"#,
+                        start_pat: Some("<-ink::env::debug_println!"),
+                        end_pat: Some("<-ink::env::debug_println!"),
+                    }],
+                }],
+                None,
+            ),
+        ] {
+            let code = quote_as_pretty_string! {
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {}
+                    }
+
+                    #items
+                }
+            };
+            let contract = parse_first_contract(&code);
+
+            let mut results = Vec::new();
+            ensure_gated_debug_code(&mut results, &contract, cargo_toml);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "contract: {code}");
+            assert_eq!(results[0].severity, Severity::Warning, "contract: {code}");
+            // Verifies quickfixes.
+            let quickfixes = results[0].quickfixes.as_ref().unwrap();
+            verify_actions(&code, quickfixes, &expected_quickfixes);
+            // Verifies `Cargo.toml` edits.
+            let cargo_edits: Vec<(&str, TextRange)> = quickfixes[0]
+                .document_edits
+                .iter()
+                .inspect(|document_edit| assert_eq!(document_edit.path, "Cargo.toml"))
+                .flat_map(|document_edit| &document_edit.edits)
+                .map(|edit| (edit.text.as_str(), edit.range))
+                .collect();
+            let expected_cargo_edits: Vec<(&str, TextRange)> = expected_cargo_edit
+                .map(|(text, offset)| (text, TextRange::empty(TextSize::from(offset))))
+                .into_iter()
+                .collect();
+            assert_eq!(cargo_edits, expected_cargo_edits, "contract: {code}");
+        }
+    }
+
+    #[test]
+    // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_mod.rs#L593-L640>.
+    fn compound_diagnostic_works() {
+        for code in valid_contracts!() {
+            let contract = parse_first_contract(quote_as_str! {
+                #code
+            });
+
+            let mut results = Vec::new();
+            diagnostics(
+                &mut results,
+                &contract,
+                Version::V4,
+                &DiagnosticsConfig::default(),
+            );
+            // Ignores unused ink! entity warnings (e.g. for events that are never emitted),
+            // because the fixtures are minimal and still valid.
+            results.retain(|item| item.code != Some(DiagnosticCode::UnusedEntity));
+            assert!(results.is_empty(), "contract: {code}");
+        }
+    }
+
+    #[test]
+    fn contemporary_body_syntax_works() {
+        for body in [
+            // `let-else` statements.
+            quote! {
+                let Some(value) = self.value else {
+                    return;
+                };
+            },
+            // Labeled block expressions.
+            quote! {
+                let _ = 'block: {
+                    if true {
+                        break 'block 1;
+                    }
+                    2
+                };
+            },
+            // `async` blocks and closures.
+            quote! {
+                let _ = async move { 1 };
+                let _ = |value: u8| -> u8 { value + 1 };
+            },
+            // Inline `const` blocks.
+            quote! {
+                let _ = const { 1 + 1 };
+            },
+            // Unknown/unsupported syntax (i.e. body parse errors).
+            quote! {
+                let _ = ;
+                yeet 1;
+                do catch {}
+            },
+        ] {
+            let contract = parse_first_contract(quote_as_str! {
+                #[ink::contract]
+                mod minimal {
+                    #[ink(storage)]
+                    pub struct Minimal {}
+
+                    impl Minimal {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {}
+
+                        #[ink(message)]
+                        pub fn minimal_message(&self) {
+                            #body
+                        }
+                    }
+                }
+            });
+
+            let mut results = Vec::new();
+            diagnostics(
+                &mut results,
+                &contract,
+                Version::V4,
+                &DiagnosticsConfig::default(),
+            );
+            assert!(results.is_empty(), "body: {body}");
+        }
+    }
+
+    #[test]
+    fn body_parse_errors_dont_affect_attribute_diagnostics() {
+        let results: Vec<Vec<(String, Severity)>> = [
+            // No body (i.e. no parse errors).
+            quote! {},
+            quote! {
+                let _ = ;
+            },
+            quote! {
+                yeet 1;
+            },
+            quote! {
+                let Some(value) = self.value else { return };
+                do catch {}
+            },
+        ]
+        .into_iter()
+        .map(|body| {
+            let contract = parse_first_contract(quote_as_str! {
+                #[ink::contract]
+                mod minimal {
+                    #[ink(storage)]
+                    pub struct Minimal {}
+
+                    impl Minimal {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            #body
+                        }
+
+                        #[ink(message, anonymous)]
+                        pub fn minimal_message(&self) {
+                            #body
+                        }
+                    }
+                }
+            });
+
+            let mut results = Vec::new();
+            diagnostics(
+                &mut results,
+                &contract,
+                Version::V4,
+                &DiagnosticsConfig::default(),
+            );
+            results
+                .into_iter()
+                .map(|diagnostic| (diagnostic.message, diagnostic.severity))
+                .collect()
+        })
+        .collect();
+
+        // Attribute level diagnostics are the same regardless of the (parse) errors in the bodies.
+        assert!(!results[0].is_empty());
+        for (idx, result) in results.iter().enumerate().skip(1) {
+            assert_eq!(result, &results[0], "body: {idx}");
+        }
+    }
 }
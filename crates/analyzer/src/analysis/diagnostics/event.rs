@@ -2,12 +2,13 @@
 
 use ink_analyzer_ir::ast::{AstNode, HasAttrs, HasGenericParams};
 use ink_analyzer_ir::{
-    ast, Event, FromSyntax, InkArgKind, InkAttributeKind, IsInkEntity, IsInkStruct,
+    ast, Event, FromInkAttribute, FromSyntax, InkArgKind, InkAttributeKind, IsInkEntity,
+    IsInkStruct,
 };
 
-use super::{topic, utils};
+use super::{topic, utils, DiagnosticsConfig};
 use crate::analysis::text_edit::TextEdit;
-use crate::{Action, ActionKind, Diagnostic, Severity};
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, Severity};
 
 const EVENT_SCOPE_NAME: &str = "event";
 
@@ -16,7 +17,7 @@ const EVENT_SCOPE_NAME: &str = "event";
 /// The entry point for finding ink! event semantic rules is the event module of the `ink_ir` crate.
 ///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item/event.rs#L86-L148>.
-pub fn diagnostics(results: &mut Vec<Diagnostic>, event: &Event) {
+pub fn diagnostics(results: &mut Vec<Diagnostic>, event: &Event, config: &DiagnosticsConfig) {
     // Runs generic diagnostics, see `utils::run_generic_diagnostics` doc.
     utils::run_generic_diagnostics(results, event);
 
@@ -49,6 +50,9 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, event: &Event) {
 
     // Ensures that ink! event fields are not annotated with `cfg` attributes, see `ensure_no_cfg_event_fields` doc.
     ensure_no_cfg_event_fields(results, event);
+
+    // Ensures that the number of ink! event topics doesn't exceed the runtime's limit, see `ensure_max_topics` doc.
+    ensure_max_topics(results, event, config.max_event_topics);
 }
 
 /// Ensures that ink! event `struct` has no generic parameters.
@@ -63,12 +67,17 @@ fn ensure_no_generics_on_struct(event: &Event) -> Option<Diagnostic> {
                 .to_string(),
             range: generics.syntax().text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::UnsupportedGenerics),
             quickfixes: Some(vec![Action {
                 label: "Remove generic types.".to_string(),
                 kind: ActionKind::QuickFix,
                 range: generics.syntax().text_range(),
                 edits: vec![TextEdit::delete(generics.syntax().text_range())],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }]),
+            related_information: None,
         })
 }
 
@@ -82,7 +91,9 @@ fn ensure_only_ink_topic_descendants(results: &mut Vec<Diagnostic>, item: &Event
                 message: format!("`{}` can't be used inside an ink! event.", attr.syntax()),
                 range: attr.syntax().text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::InvalidScope),
                 quickfixes: Some(vec![Action::remove_attribute(&attr)]),
+                related_information: None,
             });
         }
     }
@@ -103,12 +114,17 @@ fn ensure_no_cfg_event_fields(results: &mut Vec<Diagnostic>, event: &Event) {
                                     .to_string(),
                                 range: attr.syntax().text_range(),
                                 severity: Severity::Error,
+                                code: Some(DiagnosticCode::CfgEventField),
                                 quickfixes: Some(vec![Action {
                                     label: format!("Remove `{attr}` attribute."),
                                     kind: ActionKind::QuickFix,
                                     range: attr.syntax().text_range(),
                                     edits: vec![TextEdit::delete(attr.syntax().text_range())],
+                                    document_edits: Vec::new(),
+                                    is_preferred: false,
+                                    priority: 0,
                                 }]),
+                                related_information: None,
                             });
                         }
                     }
@@ -118,6 +134,40 @@ fn ensure_no_cfg_event_fields(results: &mut Vec<Diagnostic>, event: &Event) {
     }
 }
 
+/// Ensures that the number of ink! topic fields (plus the signature topic for non-anonymous events)
+/// doesn't exceed the maximum number of event topics supported by the runtime.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/env/src/types.rs>.
+fn ensure_max_topics(results: &mut Vec<Diagnostic>, event: &Event, max_topics: usize) {
+    // Non-anonymous events reserve one topic for the event signature.
+    let max_field_topics = if event.anonymous_arg().is_some() {
+        max_topics
+    } else {
+        max_topics.saturating_sub(1)
+    };
+
+    for topic in event.topics().iter().skip(max_field_topics) {
+        let attr = topic.ink_attr();
+        results.push(Diagnostic {
+            message: format!(
+                "ink! event exceeds the maximum of {max_topics} topics{} supported by the runtime.",
+                if event.anonymous_arg().is_some() {
+                    ""
+                } else {
+                    " (including the signature topic)"
+                }
+            ),
+            range: topic.field().map_or(attr.syntax().text_range(), |field| {
+                field.syntax().text_range()
+            }),
+            severity: Severity::Warning,
+            code: Some(DiagnosticCode::TooManyTopics),
+            quickfixes: Some(vec![Action::remove_attribute(attr)]),
+            related_information: None,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -490,6 +540,135 @@ mod tests {
         );
     }
 
+    #[test]
+    fn max_topics_works() {
+        for code in valid_events!() {
+            let event = parse_first_event(quote_as_str! {
+                #code
+            });
+
+            let mut results = Vec::new();
+            ensure_max_topics(&mut results, &event, 4);
+            assert!(results.is_empty(), "event: {code}");
+        }
+
+        // Anonymous events can use all topics.
+        let event = parse_first_event(quote_as_str! {
+            #[ink(event, anonymous)]
+            pub struct MyEvent {
+                #[ink(topic)]
+                field_1: i32,
+                #[ink(topic)]
+                field_2: i32,
+            }
+        });
+        let mut results = Vec::new();
+        ensure_max_topics(&mut results, &event, 2);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn too_many_topics_fails() {
+        for (code, max_topics, expected_quickfixes) in [
+            // Default limit (i.e. 3 topic fields + signature topic).
+            (
+                quote_as_pretty_string! {
+                    #[ink(event)]
+                    pub struct MyEvent {
+                        #[ink(topic)]
+                        field_1: i32,
+                        #[ink(topic)]
+                        field_2: i32,
+                        #[ink(topic)]
+                        field_3: i32,
+                        #[ink(topic)]
+                        field_4: i32,
+                    }
+                },
+                4,
+                vec![vec![TestResultAction {
+                    label: "Remove `#[ink(topic)]`",
+                    edits: vec![TestResultTextRange {
+                        text: "",
+                        start_pat: Some("<-#[ink(topic)]\n    field_4"),
+                        end_pat: Some("<-\n    field_4"),
+                    }],
+                }]],
+            ),
+            // Custom limit.
+            (
+                quote_as_pretty_string! {
+                    #[ink(event)]
+                    pub struct MyEvent {
+                        #[ink(topic)]
+                        field_1: i32,
+                        #[ink(topic)]
+                        field_2: i32,
+                        #[ink(topic)]
+                        field_3: i32,
+                    }
+                },
+                2,
+                vec![
+                    vec![TestResultAction {
+                        label: "Remove `#[ink(topic)]`",
+                        edits: vec![TestResultTextRange {
+                            text: "",
+                            start_pat: Some("<-#[ink(topic)]\n    field_2"),
+                            end_pat: Some("<-\n    field_2"),
+                        }],
+                    }],
+                    vec![TestResultAction {
+                        label: "Remove `#[ink(topic)]`",
+                        edits: vec![TestResultTextRange {
+                            text: "",
+                            start_pat: Some("<-#[ink(topic)]\n    field_3"),
+                            end_pat: Some("<-\n    field_3"),
+                        }],
+                    }],
+                ],
+            ),
+            // Anonymous event.
+            (
+                quote_as_pretty_string! {
+                    #[ink(event, anonymous)]
+                    pub struct MyEvent {
+                        #[ink(topic)]
+                        field_1: i32,
+                        #[ink(topic)]
+                        field_2: i32,
+                    }
+                },
+                1,
+                vec![vec![TestResultAction {
+                    label: "Remove `#[ink(topic)]`",
+                    edits: vec![TestResultTextRange {
+                        text: "",
+                        start_pat: Some("<-#[ink(topic)]\n    field_2"),
+                        end_pat: Some("<-\n    field_2"),
+                    }],
+                }]],
+            ),
+        ] {
+            let event = parse_first_event(&code);
+
+            let mut results = Vec::new();
+            ensure_max_topics(&mut results, &event, max_topics);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), expected_quickfixes.len(), "event: {code}");
+            for (idx, expected_quickfixes) in expected_quickfixes.iter().enumerate() {
+                assert_eq!(results[idx].severity, Severity::Warning, "event: {code}");
+                // Verifies quickfixes.
+                verify_actions(
+                    &code,
+                    results[idx].quickfixes.as_ref().unwrap(),
+                    expected_quickfixes,
+                );
+            }
+        }
+    }
+
     #[test]
     // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item/event.rs#L249-L260>.
     fn compound_diagnostic_works() {
@@ -499,7 +678,7 @@ mod tests {
             });
 
             let mut results = Vec::new();
-            diagnostics(&mut results, &event);
+            diagnostics(&mut results, &event, &DiagnosticsConfig::default());
             assert!(results.is_empty(), "event: {code}");
         }
     }
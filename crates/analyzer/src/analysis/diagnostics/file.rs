@@ -1,53 +1,285 @@
 //! ink! file level diagnostics.
 
-use ink_analyzer_ir::{InkAttributeKind, InkFile};
+use ink_analyzer_ir::ast::{HasName, HasVisibility};
+use ink_analyzer_ir::syntax::AstNode;
+use ink_analyzer_ir::{
+    ast, ChainExtension, Contract, FromSyntax, InkArgKind, InkAttribute, InkAttributeKind,
+    InkE2ETest, InkFile, InkTest, InkVisitor, IsInkEntity, IsInkFn, IsInkTrait, StorageItem,
+    TraitDefinition,
+};
+use std::collections::HashSet;
+use std::mem;
 
+use super::DiagnosticsConfig;
 use super::{
-    chain_extension, contract, ink_e2e_test, ink_test, storage_item, trait_definition, utils,
+    chain_extension, contract, ink_e2e_test, ink_test, no_std, storage_item, trait_definition,
+    utils,
 };
-use crate::{Diagnostic, Severity};
+use crate::analysis::text_edit::TextEdit;
+use crate::analysis::utils as analysis_utils;
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, Severity, Version};
+
+/// A unit of ink! file level diagnostics
+/// (i.e. diagnostics that can be run independently of (and in parallel with) other units).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    /// Generic diagnostics and the ink! contract quantity diagnostic.
+    File,
+    /// ink! contract diagnostics for the ink! contract at the given index.
+    Contract(usize),
+    /// ink! trait definition diagnostics for the ink! trait definition at the given index.
+    TraitDefinition(usize),
+    /// ink! chain extension diagnostics for the ink! chain extension at the given index.
+    ChainExtension(usize),
+    /// ink! storage item diagnostics for the ink! storage item at the given index.
+    StorageItem(usize),
+    /// ink! test diagnostics for the ink! test at the given index.
+    Test(usize),
+    /// ink! e2e test diagnostics for the ink! e2e test at the given index.
+    E2ETest(usize),
+    /// ink! attribute macro quasi-direct descendant diagnostics.
+    QuasiDirectDescendants,
+}
 
-/// Runs ink! file level diagnostics.
-pub fn diagnostics(results: &mut Vec<Diagnostic>, file: &InkFile) {
-    // Runs generic diagnostics `utils::run_generic_diagnostics` doc.
-    utils::run_generic_diagnostics(results, file);
+/// Returns the ink! file level diagnostics units for the file (in the order they should be run).
+///
+/// Units for ink! entities are collected with a single walk of the root ink! entities of the file
+/// (see [`UnitCollector`]).
+pub fn units(file: &InkFile) -> Vec<Unit> {
+    let mut collector = UnitCollector::default();
+    ink_analyzer_ir::walk_ink_file(&mut collector, file);
+    [Unit::File]
+        .into_iter()
+        .chain(collector.units)
+        .chain([Unit::QuasiDirectDescendants])
+        .collect()
+}
 
-    // Ensures that at most one ink! contract, See `ensure_contract_quantity`.
-    ensure_contract_quantity(results, file);
+/// An ink! visitor that collects diagnostics units for the root ink! entities of a file
+/// (i.e. children of root ink! entities are diagnosed by the unit of their root ink! entity, so they're not visited).
+#[derive(Debug, Default)]
+struct UnitCollector {
+    units: Vec<Unit>,
+}
+
+impl UnitCollector {
+    /// Adds the unit for the next ink! entity of the unit's type
+    /// (i.e. the index is the number of previously collected units of the same type).
+    fn add(&mut self, unit: fn(usize) -> Unit) {
+        let kind = mem::discriminant(&unit(0));
+        let idx = self
+            .units
+            .iter()
+            .filter(|it| mem::discriminant(*it) == kind)
+            .count();
+        self.units.push(unit(idx));
+    }
+}
 
-    // ink! contract diagnostics.
-    for item in file.contracts() {
-        contract::diagnostics(results, item);
+impl InkVisitor for UnitCollector {
+    fn visit_contract(&mut self, _: &Contract) {
+        self.add(Unit::Contract);
     }
 
-    // Runs ink! trait definition diagnostics, see `trait_definition::diagnostics` doc.
-    for item in file.trait_definitions() {
-        trait_definition::diagnostics(results, item);
+    fn visit_trait_definition(&mut self, _: &TraitDefinition) {
+        self.add(Unit::TraitDefinition);
     }
 
-    // Runs ink! chain extension diagnostics, see `chain_extension::diagnostics` doc.
-    for item in file.chain_extensions() {
-        chain_extension::diagnostics(results, item);
+    fn visit_chain_extension(&mut self, _: &ChainExtension) {
+        self.add(Unit::ChainExtension);
     }
 
-    // Runs ink! storage item diagnostics, see `storage_item::diagnostics` doc.
-    for item in file.storage_items() {
-        storage_item::diagnostics(results, item);
+    fn visit_storage_item(&mut self, _: &StorageItem) {
+        self.add(Unit::StorageItem);
+    }
+
+    fn visit_test(&mut self, _: &InkTest) {
+        self.add(Unit::Test);
+    }
+
+    fn visit_e2e_test(&mut self, _: &InkE2ETest) {
+        self.add(Unit::E2ETest);
+    }
+}
+
+/// Runs ink! file level diagnostics for the given unit.
+pub fn diagnostics(
+    results: &mut Vec<Diagnostic>,
+    file: &InkFile,
+    unit: Unit,
+    version: Version,
+    config: &DiagnosticsConfig,
+) {
+    match unit {
+        Unit::File => {
+            // Runs generic diagnostics `utils::run_generic_diagnostics` doc.
+            utils::run_generic_diagnostics(results, file);
+
+            // Ensures that at most one ink! contract, See `ensure_contract_quantity`.
+            ensure_contract_quantity(results, file);
+
+            // Ensures that deprecated ink! 3.x crate paths aren't used with ink! 4.x,
+            // see `ensure_no_deprecated_crate_paths` doc.
+            if version == Version::V4 {
+                ensure_no_deprecated_crate_paths(results, file);
+            }
+
+            // Ensures that tests that use ink! off-chain environment test helpers are ink! tests,
+            // see `ink_test::ensure_env_helper_tests_annotated` doc.
+            ink_test::ensure_env_helper_tests_annotated(results, file);
+
+            // Runs ink! contract crate `no_std` compatibility diagnostics (if the project's `Cargo.toml` is known),
+            // see `no_std::diagnostics` doc.
+            if let Some(cargo_toml) = config.cargo_toml.as_deref() {
+                no_std::diagnostics(results, file, cargo_toml);
+            }
+        }
+        // ink! contract diagnostics.
+        Unit::Contract(idx) => {
+            if let Some(item) = file.contracts().get(idx) {
+                contract::diagnostics(results, item, version, config);
+            }
+        }
+        // Runs ink! trait definition diagnostics, see `trait_definition::diagnostics` doc.
+        Unit::TraitDefinition(idx) => {
+            if let Some(item) = file.trait_definitions().get(idx) {
+                trait_definition::diagnostics(results, item);
+
+                // Ensures that the ink! trait definition is implemented,
+                // see `ensure_implemented_trait_definition` doc.
+                if let Some(diagnostic) = ensure_implemented_trait_definition(file, item) {
+                    results.push(diagnostic);
+                }
+            }
+        }
+        // Runs ink! chain extension diagnostics, see `chain_extension::diagnostics` doc.
+        Unit::ChainExtension(idx) => {
+            if let Some(item) = file.chain_extensions().get(idx) {
+                chain_extension::diagnostics(results, item);
+
+                // Ensures that the ink! extensions are called, see `ensure_called_extensions` doc.
+                ensure_called_extensions(results, file, item);
+            }
+        }
+        // Runs ink! storage item diagnostics, see `storage_item::diagnostics` doc.
+        Unit::StorageItem(idx) => {
+            if let Some(item) = file.storage_items().get(idx) {
+                storage_item::diagnostics(results, item);
+            }
+        }
+        // Runs ink! test diagnostics, see `ink_test::diagnostics` doc.
+        Unit::Test(idx) => {
+            if let Some(item) = file.tests().get(idx) {
+                ink_test::diagnostics(results, item);
+            }
+        }
+        // Runs ink! e2e test diagnostics, see `ink_e2e_test::diagnostics` doc.
+        Unit::E2ETest(idx) => {
+            if let Some(item) = file.e2e_tests().get(idx) {
+                ink_e2e_test::diagnostics(results, item, config);
+            }
+        }
+        // Ensures that only ink! attribute macro quasi-direct descendants (i.e ink! descendants without any ink! ancestors),
+        // See `ensure_valid_quasi_direct_ink_descendants` doc.
+        Unit::QuasiDirectDescendants => {
+            ensure_valid_quasi_direct_ink_descendants(results, file, config.contract_child_module);
+
+            // Ensures that ink! constructors and ink! messages are defined inside an ink! contract
+            // (unless the file is an out-of-line child module of an ink! contract),
+            // see `ensure_callables_in_contract` doc.
+            if !config.contract_child_module {
+                ensure_callables_in_contract(results, file);
+            }
+        }
     }
+}
 
-    // Runs ink! test diagnostics, see `ink_test::diagnostics` doc.
-    for item in file.tests() {
-        ink_test::diagnostics(results, item);
+/// Ensures that the ink! trait definition is implemented in the file.
+///
+/// Only applies to private ink! trait definitions in files with an ink! contract,
+/// because public ink! trait definitions and ink! trait definitions in files without ink! contracts
+/// (e.g. in library crates) are typically implemented (or called via cross-contract calls) in other crates.
+fn ensure_implemented_trait_definition(
+    file: &InkFile,
+    trait_definition: &TraitDefinition,
+) -> Option<Diagnostic> {
+    if file.contracts().is_empty() {
+        return None;
     }
+    let trait_item = trait_definition
+        .trait_item()
+        .filter(|trait_item| trait_item.visibility().is_none())?;
+    let name = trait_item.name()?.to_string();
+    let is_implemented = file
+        .syntax()
+        .descendants()
+        .filter_map(ast::Impl::cast)
+        .filter_map(|impl_item| match impl_item.trait_()? {
+            ast::Type::PathType(path_type) => path_type.path()?.segment()?.name_ref(),
+            _ => None,
+        })
+        .any(|name_ref| name_ref.text() == name.as_str());
+    (!is_implemented).then(|| {
+        let range =
+            analysis_utils::ast_item_declaration_range(&ast::Item::Trait(trait_item.clone()))
+                .unwrap_or(trait_item.syntax().text_range());
+        Diagnostic {
+            message: format!("ink! trait definition `{name}` is never implemented."),
+            range,
+            severity: Severity::Warning,
+            code: Some(DiagnosticCode::UnusedEntity),
+            quickfixes: Some(vec![Action::remove_item(trait_item.syntax())]),
+            related_information: None,
+        }
+    })
+}
 
-    // Runs ink! e2e test diagnostics, see `ink_e2e_test::diagnostics` doc.
-    for item in file.e2e_tests() {
-        ink_e2e_test::diagnostics(results, item);
+/// Ensures that the ink! extensions of the ink! chain extension are called in the file
+/// (e.g. `self.env().extension().my_extension()`).
+///
+/// Only applies to files with an ink! contract, because ink! chain extensions in files
+/// without ink! contracts (e.g. in library crates) are typically used in other crates.
+fn ensure_called_extensions(
+    results: &mut Vec<Diagnostic>,
+    file: &InkFile,
+    chain_extension: &ChainExtension,
+) {
+    if file.contracts().is_empty() {
+        return;
     }
 
-    // Ensures that only ink! attribute macro quasi-direct descendants (i.e ink! descendants without any ink! ancestors),
-    // See `ensure_valid_quasi_direct_ink_descendants` doc.
-    ensure_valid_quasi_direct_ink_descendants(results, file);
+    // Collects names of called methods.
+    let called_names: HashSet<String> = file
+        .syntax()
+        .descendants()
+        .filter_map(ast::MethodCallExpr::cast)
+        .filter_map(|method_call| method_call.name_ref())
+        .map(|name_ref| name_ref.to_string())
+        .collect();
+
+    for fn_item in chain_extension
+        .extensions()
+        .iter()
+        .filter_map(IsInkFn::fn_item)
+    {
+        let Some(name) = fn_item.name() else {
+            continue;
+        };
+        if called_names.contains(&name.to_string()) {
+            continue;
+        }
+
+        let range = analysis_utils::ast_item_declaration_range(&ast::Item::Fn(fn_item.clone()))
+            .unwrap_or(fn_item.syntax().text_range());
+        results.push(Diagnostic {
+            message: format!("ink! extension `{name}` is never called."),
+            range,
+            severity: Severity::Warning,
+            code: Some(DiagnosticCode::UnusedEntity),
+            quickfixes: Some(vec![Action::remove_item(fn_item.syntax())]),
+            related_information: None,
+        });
+    }
 }
 
 /// Ensures that there are not multiple ink! contract definitions.
@@ -61,25 +293,351 @@ fn ensure_contract_quantity(results: &mut Vec<Diagnostic>, file: &InkFile) {
         file.contracts(),
         "Only one ink! contract per file is currently supported.",
         Severity::Error,
+        DiagnosticCode::DuplicateContract,
     );
 }
 
+/// Ensures that ink! 4.x code doesn't use paths from the `ink_lang` crate
+/// (e.g. `#[ink_lang::contract]` or `use ink_lang as ink;`).
+///
+/// The `ink_lang` crate was merged into the `ink` crate in ink! 4.x
+/// (i.e. `ink_lang::contract` is `ink::contract` in ink! 4.x).
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.0.0/CHANGELOG.md>.
+fn ensure_no_deprecated_crate_paths(results: &mut Vec<Diagnostic>, file: &InkFile) {
+    for path in file.syntax().descendants().filter_map(ast::Path::cast) {
+        let Some(name_ref) = path
+            .qualifier()
+            .is_none()
+            .then(|| path.segment().and_then(|segment| segment.name_ref()))
+            .flatten()
+            .filter(|name_ref| name_ref.text() == "ink_lang")
+        else {
+            continue;
+        };
+        let range = path.syntax().text_range();
+        // `use ink_lang as ink;` is redundant with ink! 4.x, so it's removed instead of replaced.
+        let use_item = path
+            .syntax()
+            .parent()
+            .and_then(ast::UseTree::cast)
+            .filter(|use_tree| {
+                use_tree.use_tree_list().is_none()
+                    && use_tree
+                        .rename()
+                        .and_then(|rename| rename.name())
+                        .map_or(false, |name| name.text() == "ink")
+            })
+            .and_then(|use_tree| use_tree.syntax().parent())
+            .and_then(ast::Use::cast);
+        let quickfix = match use_item {
+            Some(use_item) => Action {
+                label: "Remove `use ink_lang as ink;`.".to_string(),
+                kind: ActionKind::QuickFix,
+                range: use_item.syntax().text_range(),
+                edits: vec![TextEdit::delete(use_item.syntax().text_range())],
+                document_edits: Vec::new(),
+                is_preferred: true,
+                priority: 0,
+            },
+            None => Action {
+                label: "Replace `ink_lang` with `ink`.".to_string(),
+                kind: ActionKind::QuickFix,
+                range,
+                edits: vec![TextEdit::replace(
+                    "ink".to_string(),
+                    name_ref.syntax().text_range(),
+                )],
+                document_edits: Vec::new(),
+                is_preferred: true,
+                priority: 0,
+            },
+        };
+        results.push(Diagnostic {
+            message: "The `ink_lang` crate is deprecated in ink! 4.x, use the `ink` crate instead."
+                .to_string(),
+            range,
+            severity: Severity::Deprecation,
+            code: Some(DiagnosticCode::VersionIncompatible),
+            quickfixes: Some(vec![quickfix]),
+            related_information: None,
+        });
+    }
+}
+
 /// Ensures that only ink! attribute macro quasi-direct descendants (i.e ink! descendants without any ink! ancestors).
 ///
+/// Out-of-line child modules of an ink! contract (i.e. files declared via a `mod x;` item
+/// in the root of an ink! contract's `mod` item) can additionally define ink! impls,
+/// ink! constructors and ink! messages at the file root.
+///
+/// ink! constructors and ink! messages in `impl` blocks are skipped
+/// because they're handled by `ensure_callables_in_contract`.
+///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item/mod.rs#L98-L114>.
-fn ensure_valid_quasi_direct_ink_descendants(results: &mut Vec<Diagnostic>, file: &InkFile) {
+fn ensure_valid_quasi_direct_ink_descendants(
+    results: &mut Vec<Diagnostic>,
+    file: &InkFile,
+    is_contract_child_module: bool,
+) {
     utils::ensure_valid_quasi_direct_ink_descendants(results, file, |attr| {
         matches!(attr.kind(), InkAttributeKind::Macro(_))
+            || callable_impl(attr).is_some()
+            || (is_contract_child_module
+                && matches!(
+                    attr.kind(),
+                    InkAttributeKind::Arg(
+                        InkArgKind::Impl
+                            | InkArgKind::Namespace
+                            | InkArgKind::Constructor
+                            | InkArgKind::Message
+                            | InkArgKind::Payable
+                            | InkArgKind::Default
+                            | InkArgKind::Selector
+                    )
+                ))
     });
 }
 
+/// Ensures that ink! constructors and ink! messages are defined in an `impl` block
+/// inside an ink! contract's `mod` item (e.g. catches `impl` blocks copied outside the ink! contract).
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_mod.rs#L410-L469>.
+fn ensure_callables_in_contract(results: &mut Vec<Diagnostic>, file: &InkFile) {
+    for (attr, impl_item) in file
+        .tree()
+        .ink_attrs_closest_descendants()
+        .filter_map(|attr| callable_impl(&attr).map(|impl_item| (attr, impl_item)))
+    {
+        let ink_scope_name = match attr.kind() {
+            InkAttributeKind::Arg(InkArgKind::Constructor) => "constructor",
+            _ => "message",
+        };
+        let range = attr.syntax().text_range();
+
+        // Moves the `impl` block into the nearest ink! contract's `mod` item (if any).
+        let impl_range = impl_item.syntax().text_range();
+        let move_to_contract = file
+            .contracts()
+            .iter()
+            .filter_map(|contract| {
+                let mod_item = contract.module()?;
+                Some((mod_item, mod_item.item_list()?))
+            })
+            .min_by_key(|(mod_item, _)| {
+                let mod_range = mod_item.syntax().text_range();
+                if mod_range.end() <= impl_range.start() {
+                    impl_range.start() - mod_range.end()
+                } else {
+                    mod_range.start() - impl_range.end()
+                }
+            })
+            .map(|(mod_item, item_list)| {
+                let mut action = Action::move_item(
+                    impl_item.syntax(),
+                    analysis_utils::item_insert_offset_by_scope_name(&item_list, "impl"),
+                    format!(
+                        "Move `impl` block into the {}ink! contract `mod`.",
+                        mod_item
+                            .name()
+                            .map(|name| format!("`{name}` "))
+                            .unwrap_or_default()
+                    ),
+                    Some(analysis_utils::item_children_indenting(mod_item.syntax()).as_str()),
+                );
+                // Makes the self type path relative to the ink! contract's `mod` item (if necessary)
+                // (e.g. `impl my_contract::MyContract` -> `impl MyContract`).
+                if let Some((path, relative_path)) = relative_self_ty_path(&impl_item, mod_item) {
+                    // NOTE: Moving only changes indenting, so the self type path is preceded
+                    // by the same number of occurrences of the path text in the insert text.
+                    let path_text = path.to_string();
+                    let n_preceding = impl_item.syntax().text().to_string()
+                        [..usize::from(path.syntax().text_range().start() - impl_range.start())]
+                        .matches(path_text.as_str())
+                        .count();
+                    if let Some((idx, _)) = action.edits[0]
+                        .text
+                        .match_indices(path_text.as_str())
+                        .nth(n_preceding)
+                    {
+                        action.edits[0]
+                            .text
+                            .replace_range(idx..idx + path_text.len(), &relative_path);
+                    }
+                }
+                action
+            });
+        // Adds an ink! contract attribute macro to the parent (non-ink!) `mod` item (if any).
+        let add_contract_macro = ink_analyzer_ir::parent_ast_item(impl_item.syntax())
+            .and_then(|item| match item {
+                ast::Item::Module(mod_item)
+                    if mod_item.item_list().is_some()
+                        && ink_analyzer_ir::ink_attrs(mod_item.syntax())
+                            .next()
+                            .is_none() =>
+                {
+                    Some(mod_item)
+                }
+                _ => None,
+            })
+            .map(|mod_item| Action {
+                label: "Add ink! contract attribute macro to the parent `mod`.".to_string(),
+                kind: ActionKind::QuickFix,
+                range,
+                edits: vec![TextEdit::insert(
+                    format!(
+                        "#[ink::contract]\n{}",
+                        analysis_utils::item_indenting(mod_item.syntax()).unwrap_or_default()
+                    ),
+                    analysis_utils::ink_attribute_insert_offset(mod_item.syntax()),
+                )],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
+            });
+        // Removes the ink! attribute or the item.
+        let remove_attr_or_item = [Action::remove_attribute(&attr)].into_iter().chain(
+            ink_analyzer_ir::parent_ast_item(attr.syntax())
+                .map(|item| Action::remove_item(item.syntax())),
+        );
+        let quickfixes: Vec<Action> = move_to_contract
+            .into_iter()
+            .chain(add_contract_macro)
+            .chain(remove_attr_or_item)
+            .collect();
+
+        results.push(Diagnostic {
+            message: format!(
+                "ink! {ink_scope_name}s must be defined in an `impl` block inside an ink! contract `mod`."
+            ),
+            range,
+            severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidScope),
+            quickfixes: Some(quickfixes),
+            related_information: None,
+        });
+    }
+}
+
+/// Returns the self type path of the `impl` item and the equivalent path relative to the `mod` item
+/// if the self type path is qualified by the `mod` item (e.g. `my_contract::MyContract` -> `MyContract`).
+///
+/// The `mod` item qualifier can only be preceded by `crate`, `self` and/or `super` path segments.
+fn relative_self_ty_path(
+    impl_item: &ast::Impl,
+    mod_item: &ast::Module,
+) -> Option<(ast::Path, String)> {
+    let mod_name = mod_item.name()?;
+    let path = match impl_item.self_ty()? {
+        ast::Type::PathType(path_type) => path_type.path()?,
+        _ => return None,
+    };
+    let is_keyword_path = |path: &ast::Path| {
+        std::iter::successors(Some(path.clone()), ast::Path::qualifier).all(|path| {
+            path.segment().map_or(false, |segment| {
+                matches!(
+                    segment.kind(),
+                    Some(
+                        ast::PathSegmentKind::CrateKw
+                            | ast::PathSegmentKind::SelfKw
+                            | ast::PathSegmentKind::SuperKw
+                    )
+                )
+            })
+        })
+    };
+    let mod_qualifier =
+        std::iter::successors(path.qualifier(), ast::Path::qualifier).find(|qualifier| {
+            qualifier
+                .segment()
+                .and_then(|segment| segment.name_ref())
+                .map_or(false, |name_ref| name_ref.text() == mod_name.text())
+                && qualifier.qualifier().as_ref().map_or(true, is_keyword_path)
+        })?;
+    let relative_path = path.syntax().text().to_string()[usize::from(
+        mod_qualifier.syntax().text_range().end() - path.syntax().text_range().start(),
+    )..]
+        .trim_start_matches("::")
+        .to_string();
+    Some((path, relative_path))
+}
+
+/// Returns the parent `impl` item of an ink! constructor or ink! message attribute (if any).
+fn callable_impl(attr: &InkAttribute) -> Option<ast::Impl> {
+    if !matches!(
+        attr.kind(),
+        InkAttributeKind::Arg(InkArgKind::Constructor | InkArgKind::Message)
+    ) {
+        return None;
+    }
+    match ink_analyzer_ir::parent_ast_item(attr.syntax())? {
+        ast::Item::Fn(fn_item) => fn_item
+            .syntax()
+            .parent()
+            .filter(|node| ast::AssocItemList::can_cast(node.kind()))?
+            .parent()
+            .and_then(ast::Impl::cast),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_utils::verify_actions;
+    use ink_analyzer_ir::syntax::{TextRange, TextSize};
     use ink_analyzer_ir::InkFile;
     use quote::format_ident;
-    use test_utils::{quote_as_pretty_string, quote_as_str, TestResultAction, TestResultTextRange};
+    use test_utils::{
+        parse_offset_at, quote_as_pretty_string, quote_as_str, TestResultAction,
+        TestResultTextRange,
+    };
+
+    #[test]
+    fn units_works() {
+        let file = InkFile::parse(quote_as_str! {
+            #[ink::contract]
+            mod contract_a {}
+
+            #[ink::trait_definition]
+            pub trait MyTrait {}
+
+            #[ink::contract]
+            mod contract_b {}
+
+            #[ink::chain_extension]
+            pub trait MyChainExtension {}
+
+            #[ink::storage_item]
+            struct MyStorageItem {}
+
+            #[ink::test]
+            fn test_a() {}
+
+            #[ink_e2e::test]
+            async fn e2e_test_a() {}
+
+            #[ink::test]
+            fn test_b() {}
+        });
+
+        // Units for each type of root ink! entity are indexed in source order.
+        assert_eq!(
+            units(&file),
+            vec![
+                Unit::File,
+                Unit::Contract(0),
+                Unit::Contract(1),
+                Unit::TraitDefinition(0),
+                Unit::ChainExtension(0),
+                Unit::StorageItem(0),
+                Unit::Test(0),
+                Unit::Test(1),
+                Unit::E2ETest(0),
+                Unit::QuasiDirectDescendants,
+            ]
+        );
+    }
 
     #[test]
     fn one_contract_definition_works() {
@@ -146,6 +704,244 @@ mod tests {
         }
     }
 
+    #[test]
+    fn no_deprecated_crate_paths_works() {
+        let file = InkFile::parse(quote_as_str! {
+            use ink::env::DefaultEnvironment;
+
+            #[ink::contract]
+            mod my_contract {}
+        });
+
+        let mut results = Vec::new();
+        ensure_no_deprecated_crate_paths(&mut results, &file);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn deprecated_crate_paths_fails() {
+        for (code, expected_quickfix) in [
+            (
+                quote_as_pretty_string! {
+                    #[ink_lang::contract]
+                    mod my_contract {}
+                },
+                TestResultAction {
+                    label: "Replace `ink_lang`",
+                    edits: vec![TestResultTextRange {
+                        text: "ink",
+                        start_pat: Some("#["),
+                        end_pat: Some("#[ink_lang"),
+                    }],
+                },
+            ),
+            (
+                quote_as_pretty_string! {
+                    use ink_lang::codegen::Env;
+                },
+                TestResultAction {
+                    label: "Replace `ink_lang`",
+                    edits: vec![TestResultTextRange {
+                        text: "ink",
+                        start_pat: Some("use "),
+                        end_pat: Some("use ink_lang"),
+                    }],
+                },
+            ),
+            (
+                quote_as_pretty_string! {
+                    use ink_lang as ink;
+                },
+                TestResultAction {
+                    label: "Remove `use ink_lang as ink;`",
+                    edits: vec![TestResultTextRange {
+                        text: "",
+                        start_pat: Some("<-use ink_lang"),
+                        end_pat: Some("as ink;"),
+                    }],
+                },
+            ),
+        ] {
+            let file = InkFile::parse(&code);
+
+            let mut results = Vec::new();
+            ensure_no_deprecated_crate_paths(&mut results, &file);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "code: {code}");
+            assert_eq!(results[0].severity, Severity::Deprecation, "code: {code}");
+            assert_eq!(
+                results[0].code,
+                Some(DiagnosticCode::VersionIncompatible),
+                "code: {code}"
+            );
+            // Verifies quickfixes.
+            verify_actions(
+                &code,
+                results[0].quickfixes.as_ref().unwrap(),
+                &[expected_quickfix],
+            );
+        }
+    }
+
+    #[test]
+    fn used_trait_definitions_and_extensions_works() {
+        for code in [
+            // Implemented ink! trait definition and called ink! extension.
+            quote_as_str! {
+                #[ink::trait_definition]
+                pub trait MyTrait {
+                    #[ink(message)]
+                    fn my_message(&self);
+                }
+
+                #[ink::chain_extension]
+                pub trait MyChainExtension {
+                    type ErrorCode = MyErrorCode;
+
+                    #[ink(extension = 1)]
+                    fn my_extension();
+                }
+
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+
+                    impl super::MyTrait for MyContract {
+                        #[ink(message)]
+                        fn my_message(&self) {
+                            self.env().extension().my_extension();
+                        }
+                    }
+                }
+            },
+            // Public ink! trait definition (i.e. implementations are typically in other crates).
+            quote_as_str! {
+                #[ink::trait_definition]
+                pub trait MyTrait {
+                    #[ink(message)]
+                    fn my_message(&self);
+                }
+
+                #[ink::contract]
+                mod my_contract {
+                    #[ink(storage)]
+                    pub struct MyContract {}
+
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn my_message(&self) {}
+                    }
+                }
+            },
+            // No ink! contract (i.e. usages are typically in other crates).
+            quote_as_str! {
+                #[ink::trait_definition]
+                pub trait MyTrait {
+                    #[ink(message)]
+                    fn my_message(&self);
+                }
+
+                #[ink::chain_extension]
+                pub trait MyChainExtension {
+                    type ErrorCode = MyErrorCode;
+
+                    #[ink(extension = 1)]
+                    fn my_extension();
+                }
+            },
+        ] {
+            let file = InkFile::parse(code);
+
+            let mut results = Vec::new();
+            for item in file.trait_definitions() {
+                results.extend(ensure_implemented_trait_definition(&file, item));
+            }
+            for item in file.chain_extensions() {
+                ensure_called_extensions(&mut results, &file, item);
+            }
+            assert!(results.is_empty(), "code: {code}");
+        }
+    }
+
+    #[test]
+    fn unused_trait_definitions_and_extensions_fails() {
+        let code = quote_as_pretty_string! {
+            #[ink::trait_definition]
+            trait MyTrait {
+                #[ink(message)]
+                fn my_message(&self);
+            }
+
+            #[ink::chain_extension]
+            pub trait MyChainExtension {
+                type ErrorCode = MyErrorCode;
+
+                #[ink(extension = 1)]
+                fn my_extension();
+
+                #[ink(extension = 2)]
+                fn my_extension2();
+            }
+
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn my_message(&self) {
+                        self.env().extension().my_extension();
+                    }
+                }
+            }
+        };
+        let file = InkFile::parse(&code);
+
+        let mut results = Vec::new();
+        for item in file.trait_definitions() {
+            results.extend(ensure_implemented_trait_definition(&file, item));
+        }
+        for item in file.chain_extensions() {
+            ensure_called_extensions(&mut results, &file, item);
+        }
+
+        // There should be 2 warnings (i.e. one for the ink! trait definition and one for the uncalled ink! extension).
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|item| item.severity == Severity::Warning
+            && item.code == Some(DiagnosticCode::UnusedEntity)));
+        assert!(results[0].message.contains("`MyTrait`"));
+        assert!(results[1].message.contains("`my_extension2`"));
+
+        // Verifies quickfixes.
+        let expected_quickfixes = [
+            vec![TestResultAction {
+                label: "Remove item",
+                edits: vec![TestResultTextRange {
+                    text: "",
+                    start_pat: Some("<-#[ink::trait_definition]"),
+                    end_pat: Some("fn my_message(&self);\n}"),
+                }],
+            }],
+            vec![TestResultAction {
+                label: "Remove item",
+                edits: vec![TestResultTextRange {
+                    text: "",
+                    start_pat: Some("<-#[ink(extension = 2)]"),
+                    end_pat: Some("fn my_extension2();"),
+                }],
+            }],
+        ];
+        for (idx, item) in results.iter().enumerate() {
+            let quickfixes = item.quickfixes.as_ref().unwrap();
+            verify_actions(&code, quickfixes, &expected_quickfixes[idx]);
+            // Removal quickfixes aren't safe to auto-apply.
+            assert!(quickfixes.iter().all(|action| !action.is_preferred));
+        }
+    }
+
     #[test]
     fn valid_quasi_direct_descendant_works() {
         let contract = InkFile::parse(quote_as_str! {
@@ -175,12 +971,13 @@ mod tests {
         });
 
         let mut results = Vec::new();
-        ensure_valid_quasi_direct_ink_descendants(&mut results, &contract);
+        ensure_valid_quasi_direct_ink_descendants(&mut results, &contract, false);
         assert!(results.is_empty());
     }
 
     #[test]
     fn invalid_quasi_direct_descendant_fails() {
+        // NOTE: ink! constructors and ink! messages in `impl` blocks are handled by `ensure_callables_in_contract`.
         let code = quote_as_pretty_string! {
             #[ink(storage)]
             struct MyContract {}
@@ -191,18 +988,16 @@ mod tests {
                 value: bool,
             }
 
-            impl MyContract {
-                #[ink(constructor)]
-                pub fn my_constructor() -> Self {}
+            #[ink(constructor)]
+            pub fn my_constructor() -> Self {}
 
-                #[ink(message)]
-                pub fn my_message(&mut self) {}
-            }
+            #[ink(message)]
+            pub fn my_message() {}
         };
         let contract = InkFile::parse(&code);
 
         let mut results = Vec::new();
-        ensure_valid_quasi_direct_ink_descendants(&mut results, &contract);
+        ensure_valid_quasi_direct_ink_descendants(&mut results, &contract, false);
 
         // There should be 4 errors (i.e `storage`, `event`, `constructor` and `message`,
         // `topic` is not a quasi-direct dependant because it has `event` as a parent).
@@ -285,7 +1080,7 @@ mod tests {
                     edits: vec![TestResultTextRange {
                         text: "",
                         start_pat: Some("<-#[ink(message)]"),
-                        end_pat: Some("pub fn my_message(&mut self) {}"),
+                        end_pat: Some("pub fn my_message() {}"),
                     }],
                 },
             ],
@@ -295,4 +1090,145 @@ mod tests {
             verify_actions(&code, quickfixes, &expected_quickfixes[idx]);
         }
     }
+
+    #[test]
+    fn contract_child_module_quasi_direct_descendant_works() {
+        let file = InkFile::parse(quote_as_str! {
+            #[ink(storage)]
+            struct MyContract {}
+
+            #[ink(impl)]
+            impl MyContract {
+                #[ink(constructor, default)]
+                pub fn my_constructor() -> Self {}
+            }
+
+            impl MyContract {
+                #[ink(message, payable, selector = 1)]
+                pub fn my_message(&mut self) {}
+            }
+        });
+
+        let mut results = Vec::new();
+        ensure_valid_quasi_direct_ink_descendants(&mut results, &file, true);
+
+        // Only ink! storage is invalid in out-of-line child modules of ink! contracts.
+        assert_eq!(results.len(), 1);
+        // NOTE: `quote_as_str` adds whitespace between the tokens of the attribute.
+        assert_eq!(
+            results[0].message,
+            "Invalid scope for an `# [ink (storage)]` item."
+        );
+    }
+
+    #[test]
+    fn callables_in_contract_works() {
+        let file = InkFile::parse(quote_as_str! {
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                impl MyContract {
+                    #[ink(constructor)]
+                    pub fn my_constructor() -> Self {}
+
+                    #[ink(message)]
+                    pub fn my_message(&self) {}
+                }
+            }
+
+            #[ink::trait_definition]
+            pub trait MyTrait {
+                #[ink(message)]
+                fn my_message(&self);
+            }
+        });
+
+        let mut results = Vec::new();
+        ensure_callables_in_contract(&mut results, &file);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn callables_outside_contract_fails() {
+        let code = quote_as_pretty_string! {
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+            }
+
+            impl my_contract::MyContract {
+                #[ink(message)]
+                pub fn my_message(&self) {}
+            }
+
+            mod outer {
+                mod other {
+                    impl super::super::my_contract::MyContract {
+                        #[ink(constructor, payable)]
+                        pub fn my_constructor() -> Self {}
+                    }
+                }
+            }
+        };
+        let file = InkFile::parse(&code);
+
+        let mut results = Vec::new();
+        ensure_callables_in_contract(&mut results, &file);
+
+        // There should be 2 errors (i.e. one for the ink! message and one for the ink! constructor).
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|item| item.severity == Severity::Error
+            && item.code == Some(DiagnosticCode::InvalidScope)));
+        assert!(results[0].message.contains("ink! messages"));
+        assert!(results[1].message.contains("ink! constructors"));
+
+        // Verifies quickfixes.
+        let labels = |idx: usize| -> Vec<String> {
+            results[idx]
+                .quickfixes
+                .iter()
+                .flatten()
+                .map(|action| action.label.clone())
+                .collect()
+        };
+        assert_eq!(
+            labels(0),
+            vec![
+                "Move `impl` block into the `my_contract` ink! contract `mod`.",
+                "Remove `#[ink(message)]` attribute.",
+                "Remove item.",
+            ]
+        );
+        assert_eq!(
+            labels(1),
+            vec![
+                "Move `impl` block into the `my_contract` ink! contract `mod`.",
+                "Add ink! contract attribute macro to the parent `mod`.",
+                "Remove `#[ink(constructor, payable)]` attribute.",
+                "Remove item.",
+            ]
+        );
+        // Moves the `impl` block after the ink! storage `struct`.
+        let offset = TextSize::from(
+            parse_offset_at(&code, Some("pub struct MyContract {}")).unwrap() as u32,
+        );
+        let move_action = &results[0].quickfixes.as_ref().unwrap()[0];
+        assert_eq!(move_action.edits[0].range, TextRange::new(offset, offset));
+        // Makes the self type path relative to the ink! contract `mod`.
+        assert!(move_action.edits[0].text.contains("impl MyContract {"));
+        assert!(!move_action.edits[0].text.contains("my_contract::"));
+        let move_action = &results[1].quickfixes.as_ref().unwrap()[0];
+        assert!(move_action.edits[0].text.contains("impl MyContract {"));
+        // Adds the ink! contract attribute macro to `mod other` (on its own line).
+        let offset = TextSize::from(parse_offset_at(&code, Some("<-mod other")).unwrap() as u32);
+        let add_contract_action = &results[1].quickfixes.as_ref().unwrap()[1];
+        assert_eq!(add_contract_action.edits[0].text, "#[ink::contract]\n    ");
+        assert_eq!(
+            add_contract_action.edits[0].range,
+            TextRange::new(offset, offset)
+        );
+    }
 }
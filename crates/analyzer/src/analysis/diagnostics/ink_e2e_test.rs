@@ -1,9 +1,15 @@
 //! ink! e2e test diagnostics.
 
-use ink_analyzer_ir::InkE2ETest;
+use ink_analyzer_ir::ast::{AstNode, HasAttrs, HasName};
+use ink_analyzer_ir::syntax::SyntaxNode;
+use ink_analyzer_ir::{ast, FromInkAttribute, FromSyntax, InkArgKind, InkE2ETest, IsInkFn};
+use std::collections::HashSet;
+use std::path::Path;
 
-use super::utils;
-use crate::Diagnostic;
+use super::{utils, DiagnosticsConfig};
+use crate::analysis::text_edit::TextEdit;
+use crate::analysis::utils as analysis_utils;
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, Severity};
 
 const E2E_TEST_SCOPE_NAME: &str = "e2e test";
 
@@ -12,7 +18,11 @@ const E2E_TEST_SCOPE_NAME: &str = "e2e test";
 /// The entry point for finding ink! e2e test semantic rules is the `ir` module of the `ink_e2e_macro` crate.
 ///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.2.1/crates/e2e/macro/src/ir.rs#L37-L48>.
-pub fn diagnostics(results: &mut Vec<Diagnostic>, ink_test: &InkE2ETest) {
+pub fn diagnostics(
+    results: &mut Vec<Diagnostic>,
+    ink_test: &InkE2ETest,
+    config: &DiagnosticsConfig,
+) {
     // Runs generic diagnostics, see `utils::run_generic_diagnostics` doc.
     utils::run_generic_diagnostics(results, ink_test);
 
@@ -24,6 +34,160 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, ink_test: &InkE2ETest) {
 
     // Ensures that ink! e2e test has no ink! descendants, see `utils::ensure_no_ink_descendants` doc.
     utils::ensure_no_ink_descendants(results, ink_test, E2E_TEST_SCOPE_NAME);
+
+    // Ensures that ink! e2e test is gated behind the `e2e-tests` feature, see `ensure_cfg_e2e_tests` doc.
+    if let Some(diagnostic) = ensure_cfg_e2e_tests(ink_test) {
+        results.push(diagnostic);
+    }
+
+    // Ensures that the `Cargo.toml` of additional contracts exist (if the workspace root is known),
+    // see `ensure_additional_contracts_exist` doc.
+    if let Some(workspace_root) = config.workspace_root.as_deref() {
+        ensure_additional_contracts_exist(results, ink_test, workspace_root);
+    }
+
+    // Ensures that the environment type (if any) is valid, see `utils::ensure_valid_environment` doc.
+    if let Some(env_arg) = ink_test
+        .ink_attr()
+        .args()
+        .iter()
+        .find(|arg| *arg.kind() == InkArgKind::Environment)
+    {
+        utils::ensure_valid_environment(results, env_arg, ink_test.syntax());
+    }
+}
+
+/// Ensures that ink! e2e test is gated behind the `e2e-tests` feature
+/// (i.e. it's defined in a `#[cfg(all(test, feature = "e2e-tests"))]` module).
+///
+/// ink! e2e tests require a running node (and build the contract), so by convention,
+/// they're only compiled when the `e2e-tests` feature is enabled.
+///
+/// Ref: <https://use.ink/basics/contract-testing#end-to-end-e2e-tests>.
+fn ensure_cfg_e2e_tests(ink_test: &InkE2ETest) -> Option<Diagnostic> {
+    let fn_item = ink_test.fn_item()?;
+    if analysis_utils::is_cfg_e2e_tests_gated(fn_item.syntax()) {
+        return None;
+    }
+
+    let mut quickfixes = Vec::new();
+    // Moves the ink! e2e test to an existing ink! e2e tests `mod` (if any).
+    let e2e_module = fn_item.syntax().ancestors().last().and_then(|root| {
+        root.descendants()
+            .filter_map(ast::Module::cast)
+            .find(|module| {
+                module
+                    .attrs()
+                    .any(|attr| analysis_utils::is_cfg_e2e_tests_attr(&attr))
+            })
+    });
+    if let Some((module, item_list)) =
+        e2e_module.and_then(|module| module.item_list().map(|item_list| (module, item_list)))
+    {
+        quickfixes.push(Action::move_item(
+            fn_item.syntax(),
+            analysis_utils::item_insert_offset_end(&item_list),
+            format!(
+                "Move ink! e2e test to the `{}` mod.",
+                module
+                    .name()
+                    .map(|name| name.to_string())
+                    .unwrap_or_default()
+            ),
+            Some(analysis_utils::item_children_indenting(module.syntax()).as_str()),
+        ));
+    }
+    // Wraps the ink! e2e test in a new ink! e2e tests `mod`.
+    quickfixes.push(wrap_in_e2e_tests_module(fn_item.syntax()));
+
+    Some(Diagnostic {
+        message: "ink! e2e tests should be defined in a `mod` that's gated behind the `e2e-tests` feature \
+        (i.e. `#[cfg(all(test, feature = \"e2e-tests\"))]`), because they require a running node."
+            .to_string(),
+        range: ink_test.ink_attr().syntax().text_range(),
+        severity: Severity::Warning,
+        code: Some(DiagnosticCode::UngatedE2ETest),
+        quickfixes: Some(quickfixes),
+        related_information: None,
+    })
+}
+
+/// Ensures that the `Cargo.toml` of each additional contract (i.e. entries of the `additional_contracts` argument)
+/// exists, where paths are relative to the root directory of the ink! contract crate.
+fn ensure_additional_contracts_exist(
+    results: &mut Vec<Diagnostic>,
+    ink_test: &InkE2ETest,
+    workspace_root: &Path,
+) {
+    let Some(ink_arg) = ink_test
+        .ink_attr()
+        .args()
+        .iter()
+        .find(|arg| *arg.kind() == InkArgKind::AdditionalContracts)
+    else {
+        return;
+    };
+    for (entry, range) in analysis_utils::additional_contracts_entries(ink_arg) {
+        if workspace_root.join(&entry).is_file() {
+            continue;
+        }
+        results.push(Diagnostic {
+            message: format!(
+                "The `Cargo.toml` of the additional contract `{entry}` doesn't exist \
+                (paths are relative to the root directory of the ink! contract crate)."
+            ),
+            range,
+            severity: Severity::Error,
+            code: Some(DiagnosticCode::UnresolvedAdditionalContract),
+            quickfixes: None,
+            related_information: None,
+        });
+    }
+}
+
+/// Returns an action that wraps an item in a new ink! e2e tests `mod`.
+fn wrap_in_e2e_tests_module(node: &SyntaxNode) -> Action {
+    // Suggests a unique `mod` name.
+    let sibling_names: HashSet<String> = node
+        .parent()
+        .map(|parent| {
+            parent
+                .children()
+                .filter_map(ast::Item::cast)
+                .filter_map(|item| item.syntax().children().find_map(ast::Name::cast))
+                .map(|name| name.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let module_name = analysis_utils::suggest_unique_name("e2e_tests", &sibling_names);
+
+    // Composes the `mod` (the first line is not indented because it replaces the item).
+    let indent = analysis_utils::item_indenting(node).unwrap_or_default();
+    let item_text = analysis_utils::reduce_indenting(&analysis_utils::item_text(node), &indent);
+    let module_body = ["use super::*;".to_string(), item_text]
+        .iter()
+        .map(|item| analysis_utils::apply_indenting(item, "    "))
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    let module_text = analysis_utils::apply_indenting(
+        &format!(
+            "#[cfg(all(test, feature = \"e2e-tests\"))]\nmod {module_name} {{\n{module_body}\n}}"
+        ),
+        &indent,
+    );
+
+    Action {
+        label: format!("Wrap ink! e2e test in an ink! e2e tests `mod` (i.e. `{module_name}`)."),
+        kind: ActionKind::QuickFix,
+        range: node.text_range(),
+        edits: vec![TextEdit::replace(
+            module_text.trim_start().to_string(),
+            node.text_range(),
+        )],
+        document_edits: Vec::new(),
+        is_preferred: true,
+        priority: 0,
+    }
 }
 
 #[cfg(test)]
@@ -192,19 +356,215 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cfg_e2e_tests_works() {
+        for code in [
+            quote! {
+                #[cfg(all(test, feature = "e2e-tests"))]
+                mod e2e_tests {
+                    #[ink_e2e::test]
+                    async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {
+                    }
+                }
+            },
+            quote! {
+                #[cfg(all(test, feature = "e2e-tests"))]
+                mod e2e_tests {
+                    mod nested {
+                        #[ink_e2e::test]
+                        async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {
+                        }
+                    }
+                }
+            },
+            quote! {
+                #[cfg(all(test, feature = "e2e-tests"))]
+                #[ink_e2e::test]
+                async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {
+                }
+            },
+        ] {
+            let code = quote_as_pretty_string! { #code };
+            let ink_e2e_test = parse_first_ink_e2e_test(&code);
+
+            let result = ensure_cfg_e2e_tests(&ink_e2e_test);
+            assert!(result.is_none(), "code: {code}");
+        }
+    }
+
+    #[test]
+    fn ungated_e2e_test_fails() {
+        for (code, expected_quickfixes) in [
+            // No e2e tests `mod`.
+            (
+                quote_as_pretty_string! {
+                    #[ink_e2e::test]
+                    async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {}
+                },
+                vec![TestResultAction {
+                    label: "Wrap ink! e2e test",
+                    edits: vec![TestResultTextRange {
+                        text: "#[cfg(all(test, feature = \"e2e-tests\"))]\nmod e2e_tests {\n    use super::*;\n\n    #[ink_e2e::test]",
+                        start_pat: Some("<-#[ink_e2e::test]"),
+                        end_pat: Some("}"),
+                    }],
+                }],
+            ),
+            // Only unit tests `mod`.
+            (
+                quote_as_pretty_string! {
+                    #[cfg(test)]
+                    mod tests {
+                        #[ink_e2e::test]
+                        async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {}
+                    }
+                },
+                vec![TestResultAction {
+                    label: "Wrap ink! e2e test",
+                    edits: vec![TestResultTextRange {
+                        text: "#[cfg(all(test, feature = \"e2e-tests\"))]\n    mod e2e_tests {\n        use super::*;",
+                        start_pat: Some("<-#[ink_e2e::test]"),
+                        end_pat: Some("{}"),
+                    }],
+                }],
+            ),
+            // Existing e2e tests `mod`.
+            (
+                quote_as_pretty_string! {
+                    #[ink_e2e::test]
+                    async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {}
+
+                    #[cfg(all(test, feature = "e2e-tests"))]
+                    mod e2e_tests {
+                        use super::*;
+                    }
+                },
+                vec![
+                    TestResultAction {
+                        label: "Move ink! e2e test to the `e2e_tests` mod",
+                        edits: vec![
+                            TestResultTextRange {
+                                text: "#[ink_e2e::test]",
+                                start_pat: Some("use super::*;"),
+                                end_pat: Some("use super::*;"),
+                            },
+                            TestResultTextRange {
+                                text: "",
+                                start_pat: Some("<-#[ink_e2e::test]"),
+                                end_pat: Some("}"),
+                            },
+                        ],
+                    },
+                    TestResultAction {
+                        label: "Wrap ink! e2e test",
+                        edits: vec![TestResultTextRange {
+                            text: "mod e2e_tests2 {",
+                            start_pat: Some("<-#[ink_e2e::test]"),
+                            end_pat: Some("}"),
+                        }],
+                    },
+                ],
+            ),
+        ] {
+            let ink_e2e_test = parse_first_ink_e2e_test(&code);
+
+            let result = ensure_cfg_e2e_tests(&ink_e2e_test);
+
+            // Verifies diagnostics.
+            assert!(result.is_some(), "code: {code}");
+            assert_eq!(
+                result.as_ref().unwrap().severity,
+                Severity::Warning,
+                "code: {code}"
+            );
+            // Verifies quickfixes.
+            verify_actions(
+                &code,
+                result.as_ref().unwrap().quickfixes.as_ref().unwrap(),
+                &expected_quickfixes,
+            );
+        }
+    }
+
+    #[test]
+    fn additional_contracts_works() {
+        // Creates an ink! contract crate with an additional contract.
+        let root = std::env::temp_dir().join("ink_analyzer_additional_contracts_works");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("adder")).unwrap();
+        std::fs::write(root.join("adder/Cargo.toml"), "[package]").unwrap();
+
+        for (code, expected_entries) in [
+            (
+                quote_as_pretty_string! {
+                    #[ink_e2e::test(additional_contracts = "adder/Cargo.toml")]
+                    async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {
+                    }
+                },
+                vec![],
+            ),
+            (
+                quote_as_pretty_string! {
+                    #[ink_e2e::test]
+                    async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {
+                    }
+                },
+                vec![],
+            ),
+            (
+                quote_as_pretty_string! {
+                    #[ink_e2e::test(additional_contracts = "adder/Cargo.toml flipper/Cargo.toml")]
+                    async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {
+                    }
+                },
+                vec!["flipper/Cargo.toml"],
+            ),
+        ] {
+            let ink_e2e_test = parse_first_ink_e2e_test(&code);
+
+            let mut results = Vec::new();
+            ensure_additional_contracts_exist(&mut results, &ink_e2e_test, &root);
+
+            // Verifies diagnostics.
+            assert_eq!(
+                results
+                    .iter()
+                    .map(|result| (result.range, result.code))
+                    .collect::<Vec<_>>(),
+                expected_entries
+                    .into_iter()
+                    .map(|entry| (
+                        TextRange::new(
+                            TextSize::from(
+                                parse_offset_at(&code, Some(format!("<-{entry}").as_str())).unwrap()
+                                    as u32
+                            ),
+                            TextSize::from(parse_offset_at(&code, Some(entry)).unwrap() as u32),
+                        ),
+                        Some(DiagnosticCode::UnresolvedAdditionalContract)
+                    ))
+                    .collect::<Vec<_>>(),
+                "code: {code}"
+            );
+        }
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
     #[test]
     // Ref: <https://github.com/paritytech/ink/blob/v4.2.1/crates/e2e/macro/src/lib.rs#L46-L85>.
     fn compound_diagnostic_works() {
         let ink_e2e_test = parse_first_ink_e2e_test(quote_as_str! {
             type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
+            #[cfg(all(test, feature = "e2e-tests"))]
             #[ink_e2e::test]
             async fn it_works(mut client: ::ink_e2e::Client<C,E>) -> E2EResult<()> {
             }
         });
 
         let mut results = Vec::new();
-        diagnostics(&mut results, &ink_e2e_test);
+        diagnostics(&mut results, &ink_e2e_test, &DiagnosticsConfig::default());
         assert!(results.is_empty());
     }
 }
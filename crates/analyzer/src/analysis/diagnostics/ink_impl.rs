@@ -11,9 +11,11 @@ use std::collections::{HashMap, HashSet};
 
 use super::{constructor, message, utils};
 use crate::analysis::actions::entity as entity_actions;
-use crate::analysis::text_edit::TextEdit;
+use crate::analysis::text_edit::{EditBuilder, TextEdit};
 use crate::analysis::utils as analysis_utils;
-use crate::{Action, ActionKind, Diagnostic, Severity};
+use crate::{
+    Action, ActionKind, Diagnostic, DiagnosticCode, RelatedInformation, Severity, Version,
+};
 
 const IMPL_SCOPE_NAME: &str = "impl";
 
@@ -26,6 +28,7 @@ pub fn diagnostics(
     results: &mut Vec<Diagnostic>,
     ink_impl: &InkImpl,
     skip_callable_diagnostics: bool,
+    version: Version,
 ) {
     // Runs generic diagnostics, see `utils::run_generic_diagnostics` doc.
     utils::run_generic_diagnostics(results, ink_impl);
@@ -48,7 +51,7 @@ pub fn diagnostics(
     if !skip_callable_diagnostics {
         // Runs ink! constructor diagnostics, see `constructor::diagnostics` doc.
         for item in ink_impl.constructors() {
-            constructor::diagnostics(results, item);
+            constructor::diagnostics(results, item, version);
         }
 
         // Runs ink! message diagnostics, see `message::diagnostics` doc.
@@ -64,8 +67,11 @@ pub fn diagnostics(
     // Ensures that ink! impl is defined in the root of an ink! contract, see `utils::ensure_contract_parent` doc.
     // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_mod.rs#L410-L469>.
     // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item/mod.rs#L88-L97>.
-    if let Some(diagnostic) = utils::ensure_contract_parent(ink_impl, IMPL_SCOPE_NAME) {
-        results.push(diagnostic);
+    // NOTE: ink! impls can also be split across non-ink! inline child `mod` items of the ink! contract.
+    if analysis_utils::parent_contract(ink_impl.syntax()).is_none() {
+        if let Some(diagnostic) = utils::ensure_contract_parent(ink_impl, IMPL_SCOPE_NAME) {
+            results.push(diagnostic);
+        }
     }
 
     // Ensures that `impl` satisfies all invariants of the ink! trait definition it implements (if any).
@@ -84,9 +90,11 @@ fn ensure_impl(ink_impl: &InkImpl) -> Option<Diagnostic> {
         message: "ink! impl must be an `impl` item.".to_string(),
         range: analysis_utils::ink_impl_declaration_range(ink_impl),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidItemKind),
         quickfixes: ink_impl
             .impl_attr()
             .map(|attr| vec![Action::remove_attribute(&attr)]),
+        related_information: None,
     })
 }
 
@@ -104,12 +112,17 @@ pub fn ensure_impl_invariants(results: &mut Vec<Diagnostic>, ink_impl: &InkImpl)
                 message: "ink! impl must not be `default`.".to_string(),
                 range: default_token.text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::InvalidModifier),
                 quickfixes: Some(vec![Action {
                     label: "Remove `default` keyword.".to_string(),
                     kind: ActionKind::QuickFix,
                     range,
                     edits: vec![TextEdit::delete(range)],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }]),
+                related_information: None,
             });
         }
 
@@ -120,12 +133,17 @@ pub fn ensure_impl_invariants(results: &mut Vec<Diagnostic>, ink_impl: &InkImpl)
                 message: "ink! impl must not be `unsafe`.".to_string(),
                 range: unsafe_token.text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::InvalidModifier),
                 quickfixes: Some(vec![Action {
                     label: "Remove `unsafe` keyword.".to_string(),
                     kind: ActionKind::QuickFix,
                     range,
                     edits: vec![TextEdit::delete(range)],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }]),
+                related_information: None,
             });
         }
 
@@ -144,6 +162,7 @@ pub fn ensure_impl_invariants(results: &mut Vec<Diagnostic>, ink_impl: &InkImpl)
                                     .to_string(),
                                 range: generic_arg_list.syntax().text_range(),
                                 severity: Severity::Error,
+                                code: Some(DiagnosticCode::UnsupportedGenerics),
                                 quickfixes: Some(vec![Action {
                                     label: "Remove generic types.".to_string(),
                                     kind: ActionKind::QuickFix,
@@ -151,7 +170,11 @@ pub fn ensure_impl_invariants(results: &mut Vec<Diagnostic>, ink_impl: &InkImpl)
                                     edits: vec![TextEdit::delete(
                                         generic_arg_list.syntax().text_range(),
                                     )],
+                                    document_edits: Vec::new(),
+                                    is_preferred: false,
+                                    priority: 0,
                                 }]),
+                                related_information: None,
                             })
                         })
                         .collect(),
@@ -167,12 +190,17 @@ pub fn ensure_impl_invariants(results: &mut Vec<Diagnostic>, ink_impl: &InkImpl)
                     .to_string(),
                 range: arg.text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::InvalidArgument),
                 quickfixes: Some(vec![Action {
                     label: "Remove ink! namespace argument.".to_string(),
                     kind: ActionKind::QuickFix,
                     range,
                     edits: vec![TextEdit::delete(range)],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }]),
+                related_information: None,
             });
         }
 
@@ -197,12 +225,17 @@ pub fn ensure_impl_invariants(results: &mut Vec<Diagnostic>, ink_impl: &InkImpl)
                             message: format!("ink! {name}s in trait ink! impl blocks must have inherited visibility."),
                             range: visibility.syntax().text_range(),
                             severity: Severity::Error,
+                            code: Some(DiagnosticCode::InvalidVisibility),
                             quickfixes: Some(vec![Action {
                                 label: format!("Remove visibility `{}`.", visibility.syntax()),
                                 kind: ActionKind::QuickFix,
                                 range,
                                 edits: vec![TextEdit::delete(range)],
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
                             }]),
+                            related_information: None,
                         });
                     }
                 } else {
@@ -230,6 +263,7 @@ pub fn ensure_impl_invariants(results: &mut Vec<Diagnostic>, ink_impl: &InkImpl)
                                 .as_ref()
                                 .map_or(fn_declaration_range, |it| it.syntax().text_range()),
                             severity: Severity::Error,
+                            code: Some(DiagnosticCode::InvalidVisibility),
                             quickfixes: visibility
                                 .as_ref()
                                 .map(|vis| vis.syntax().text_range())
@@ -262,8 +296,12 @@ pub fn ensure_impl_invariants(results: &mut Vec<Diagnostic>, ink_impl: &InkImpl)
                                             ),
                                             range,
                                         )],
+                                        document_edits: Vec::new(),
+                                        is_preferred: false,
+                                        priority: 0,
                                     }]
                                 }),
+                            related_information: None,
                         });
                     }
                 }
@@ -287,6 +325,7 @@ fn ensure_annotation_or_contains_callable(ink_impl: &InkImpl) -> Option<Diagnost
             .to_string(),
         range,
         severity: Severity::Error,
+        code: Some(DiagnosticCode::EmptyImpl),
         quickfixes: ink_impl.impl_item().as_ref().map(|impl_item| {
             // Adds ink! callables if possible.
             [
@@ -297,6 +336,7 @@ fn ensure_annotation_or_contains_callable(ink_impl: &InkImpl) -> Option<Diagnost
             .flatten()
             .collect()
         }),
+        related_information: None,
     })
 }
 
@@ -315,6 +355,7 @@ where
         ),
         range: analysis_utils::ink_impl_declaration_range(ink_impl),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidScope),
         quickfixes: ink_impl
             .impl_item()
             .and_then(|it| it.assoc_item_list())
@@ -327,6 +368,7 @@ where
                     Some(analysis_utils::item_children_indenting(ink_impl.syntax()).as_str()),
                 )]
             }),
+        related_information: None,
     })
 }
 
@@ -407,7 +449,9 @@ fn ensure_trait_definition_impl_invariants(results: &mut Vec<Diagnostic>, ink_im
                                             message: format!("A `{fn_name_text}` method isn't declared in the ink! trait definition."),
                                             range: item.syntax().text_range(),
                                             severity: Severity::Error,
+                                            code: Some(DiagnosticCode::TraitImplMismatch),
                                             quickfixes: Some(vec![Action::remove_item(item.syntax())]),
+                                            related_information: None,
                                         });
                                     }
                                 }
@@ -436,6 +480,12 @@ fn ensure_trait_definition_impl_invariants(results: &mut Vec<Diagnostic>, ink_im
                                             ),
                                             |it| it.syntax().text_range(),
                                         );
+                                        // Fallback range for the declaration (i.e. the name of the declared method).
+                                        let declaration_range = fn_declaration
+                                            .name()
+                                            .map_or(fn_declaration.syntax().text_range(), |it| {
+                                                it.syntax().text_range()
+                                            });
                                         verify_signature_part_match(
                                             results,
                                             fn_declaration
@@ -443,10 +493,12 @@ fn ensure_trait_definition_impl_invariants(results: &mut Vec<Diagnostic>, ink_im
                                                 .as_ref()
                                                 .map(|it| it.syntax()),
                                             fn_item.param_list().as_ref().map(|it| it.syntax()),
+                                            declaration_range,
                                             diagnostic_range,
                                             replace_range,
                                             "parameter list",
                                             Some("parameters"),
+                                            analysis_utils::is_trivia_insensitive_eq,
                                         );
 
                                         // Verifies that return type matches the declaration.
@@ -477,10 +529,12 @@ fn ensure_trait_definition_impl_invariants(results: &mut Vec<Diagnostic>, ink_im
                                                 .as_ref()
                                                 .map(|it| it.syntax()),
                                             fn_item.ret_type().as_ref().map(|it| it.syntax()),
+                                            declaration_range,
                                             diagnostic_range,
                                             replace_range,
                                             "return type",
                                             None,
+                                            is_equivalent_ret_type,
                                         );
                                     }
                                 }
@@ -495,7 +549,9 @@ fn ensure_trait_definition_impl_invariants(results: &mut Vec<Diagnostic>, ink_im
                                 .to_string(),
                             range: item.syntax().text_range(),
                             severity: Severity::Error,
+                            code: Some(DiagnosticCode::TraitImplMismatch),
                             quickfixes: Some(vec![Action::remove_item(item.syntax())]),
+                            related_information: None,
                         });
                     }
                 }
@@ -503,28 +559,25 @@ fn ensure_trait_definition_impl_invariants(results: &mut Vec<Diagnostic>, ink_im
         }
 
         // Computes diagnostic for missing messages (if any).
-        let (insert_offset, indent_option, prefix_option, suffix_option) =
-            impl_item.assoc_item_list().map_or(
+        let (insert_offset, prefix_option, suffix_option) = impl_item.assoc_item_list().map_or(
+            (
+                impl_item.syntax().text_range().end(),
+                Some(" {\n".to_string()),
+                Some(format!(
+                    "\n{}}}",
+                    analysis_utils::item_indenting(impl_item.syntax())
+                        .as_deref()
+                        .unwrap_or_default()
+                )),
+            ),
+            |assoc_item_list| {
                 (
-                    impl_item.syntax().text_range().end(),
-                    Some(analysis_utils::item_children_indenting(impl_item.syntax())),
-                    Some(" {"),
-                    Some(format!(
-                        "{}}}",
-                        analysis_utils::item_indenting(impl_item.syntax())
-                            .as_deref()
-                            .unwrap_or_default()
-                    )),
-                ),
-                |assoc_item_list| {
-                    (
-                        analysis_utils::assoc_item_insert_offset_end(&assoc_item_list),
-                        None,
-                        None,
-                        None,
-                    )
-                },
-            );
+                    analysis_utils::assoc_item_insert_offset_end(&assoc_item_list),
+                    None,
+                    None,
+                )
+            },
+        );
         // Determines missing messages (with valid names).
         let mut missing_messages = message_index.values().filter_map(|message| {
             message.fn_item().and_then(|fn_item| {
@@ -556,7 +609,7 @@ fn ensure_trait_definition_impl_invariants(results: &mut Vec<Diagnostic>, ink_im
 
                 if fn_item.body().is_none() {
                     edit.push_str(" {\n    todo!()\n}");
-                    snippet.push_str(&format!(" {{\n${{    {snippet_idx}:todo!()}}\n}}"));
+                    snippet.push_str(&format!(" {{\n    ${{{snippet_idx}:todo!()}}\n}}"));
                     snippet_idx += 1;
                 }
             }
@@ -564,52 +617,59 @@ fn ensure_trait_definition_impl_invariants(results: &mut Vec<Diagnostic>, ink_im
                 message: "Missing message(s) for ink! trait definition implementation.".to_string(),
                 range,
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::TraitImplMismatch),
                 quickfixes: Some(vec![Action {
                     label: "Add missing message(s) to ink! trait definition implementation."
                         .to_string(),
                     kind: ActionKind::QuickFix,
                     range,
-                    edits: vec![TextEdit::insert_with_snippet(
-                        format!(
-                            "{}{}{}",
-                            prefix_option.unwrap_or_default(),
-                            indent_option.as_ref().map_or(edit.clone(), |indent| {
-                                analysis_utils::apply_indenting(&edit, indent)
-                            }),
-                            suffix_option.as_deref().unwrap_or_default()
-                        ),
-                        insert_offset,
-                        Some(format!(
-                            "{}{}{}",
-                            prefix_option.unwrap_or_default(),
-                            indent_option.as_ref().map_or(snippet.clone(), |indent| {
-                                analysis_utils::apply_indenting(&snippet, indent)
-                            }),
-                            suffix_option.as_deref().unwrap_or_default()
-                        )),
-                    )],
+                    edits: vec![EditBuilder::insert(edit, insert_offset)
+                        .snippet(snippet)
+                        .indent_as_child_of(impl_item.syntax())
+                        .affixes(prefix_option, suffix_option)
+                        .build()],
+                    document_edits: Vec::new(),
+                    is_preferred: true,
+                    priority: 0,
                 }]),
+                related_information: None,
             })
         }
     }
 }
 
 /// Verifies that two signature parts "match" or creates an appropriate diagnostic and quickfix.
-fn verify_signature_part_match(
+///
+/// The diagnostic includes related information pointing to both the declaration
+/// (or the `declaration_range` if the part isn't declared) and the implementation.
+#[allow(clippy::too_many_arguments)]
+fn verify_signature_part_match<F>(
     results: &mut Vec<Diagnostic>,
     declared_option: Option<&SyntaxNode>,
     implemented_option: Option<&SyntaxNode>,
+    declaration_range: TextRange,
     diagnostic_range: TextRange,
     replace_range: TextRange,
     replace_label: &str,
     remove_label_option: Option<&str>,
-) {
+    is_match: F,
+) where
+    F: Fn(&SyntaxNode, &SyntaxNode) -> bool,
+{
+    let related_information = Some(vec![
+        RelatedInformation {
+            message: format!("The {replace_label} declared by the ink! trait definition."),
+            range: declared_option.map_or(declaration_range, SyntaxNode::text_range),
+        },
+        RelatedInformation {
+            message: format!("The {replace_label} of the implementation."),
+            range: implemented_option.map_or(diagnostic_range, SyntaxNode::text_range),
+        },
+    ]);
     match (declared_option, implemented_option) {
         // Handles all cases with a declared option.
         (Some(declared), _) => {
-            if implemented_option.map_or(true, |implemented| {
-                !analysis_utils::is_trivia_insensitive_eq(implemented, declared)
-            }) {
+            if implemented_option.map_or(true, |implemented| !is_match(implemented, declared)) {
                 results.push(Diagnostic {
                     message: format!(
                         "The {replace_label} for this method doesn't match \
@@ -617,6 +677,7 @@ fn verify_signature_part_match(
                     ),
                     range: diagnostic_range,
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::TraitImplMismatch),
                     quickfixes: Some(vec![Action {
                         label: format!(
                             "Change {replace_label} to match the \
@@ -625,7 +686,11 @@ fn verify_signature_part_match(
                         kind: ActionKind::QuickFix,
                         range: diagnostic_range,
                         edits: vec![TextEdit::replace(declared.to_string(), replace_range)],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     }]),
+                    related_information,
                 });
             }
         }
@@ -645,6 +710,7 @@ fn verify_signature_part_match(
                 ),
                 range,
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::TraitImplMismatch),
                 quickfixes: Some(vec![Action {
                     label: format!(
                         "Remove {remove_label} to match \
@@ -653,7 +719,11 @@ fn verify_signature_part_match(
                     kind: ActionKind::QuickFix,
                     range,
                     edits: vec![TextEdit::delete(range)],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }]),
+                related_information,
             });
         }
         // Only other case is a match of no option in both the declaration and implementation.
@@ -661,6 +731,17 @@ fn verify_signature_part_match(
     }
 }
 
+/// Compares two return types for equivalence (see [`analysis_utils::is_equivalent_type`] doc).
+fn is_equivalent_ret_type(a: &SyntaxNode, b: &SyntaxNode) -> bool {
+    match ast::RetType::cast(a.clone())
+        .and_then(|it| it.ty())
+        .zip(ast::RetType::cast(b.clone()).and_then(|it| it.ty()))
+    {
+        Some((a_ty, b_ty)) => analysis_utils::is_equivalent_type(&a_ty, &b_ty),
+        None => analysis_utils::is_trivia_insensitive_eq(a, b),
+    }
+}
+
 /// Ensures that `fn` item has attributes that match the equivalent ink! trait definition method.
 fn ensure_trait_definition_impl_message_args(
     results: &mut Vec<Diagnostic>,
@@ -690,7 +771,9 @@ fn ensure_trait_definition_impl_message_args(
                     .to_string(),
                 range: attr.syntax().text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::TraitImplMismatch),
                 quickfixes: Some(vec![Action::remove_attribute(&attr)]),
+                related_information: None,
             });
         }
 
@@ -716,6 +799,7 @@ fn ensure_trait_definition_impl_message_args(
                                     ),
                                     range: arg.text_range(),
                                     severity: Severity::Error,
+                                    code: Some(DiagnosticCode::TraitImplMismatch),
                                     quickfixes: Some(vec![Action {
                                         label: format!("Add missing value: {value_declaration}."),
                                         kind: ActionKind::QuickFix,
@@ -724,7 +808,11 @@ fn ensure_trait_definition_impl_message_args(
                                             arg_declaration.to_string(),
                                             arg.text_range(),
                                         )],
+                                        document_edits: Vec::new(),
+                                        is_preferred: false,
+                                        priority: 0,
                                     }]),
+                                    related_information: None,
                                 });
                             }
                             // Replaces value that doesn't match declaration.
@@ -740,6 +828,7 @@ fn ensure_trait_definition_impl_message_args(
                                         based on the trait definition declaration for this method.", arg.kind()),
                                         range: arg.text_range(),
                                         severity: Severity::Error,
+                                        code: Some(DiagnosticCode::TraitImplMismatch),
                                         quickfixes: Some(vec![
                                             Action {
                                                 label: format!("Replace missing value (i.e. changes {value} to {value_declaration}."),
@@ -748,8 +837,12 @@ fn ensure_trait_definition_impl_message_args(
                                                 edits: vec![
                                                     TextEdit::replace(arg_declaration.to_string(), arg.text_range())
                                                 ],
+                                                document_edits: Vec::new(),
+                                                is_preferred: false,
+                                                priority: 0,
                                             }
                                         ]),
+                                        related_information: None,
                                     });
                                 }
                             }
@@ -768,12 +861,17 @@ fn ensure_trait_definition_impl_message_args(
                     ),
                     range: arg.text_range(),
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::TraitImplMismatch),
                     quickfixes: Some(vec![Action {
                         label: format!("Remove `{}` argument.", arg),
                         kind: ActionKind::QuickFix,
                         range,
                         edits: vec![TextEdit::delete(range)],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     }]),
+                    related_information: None,
                 });
             }
         }
@@ -882,12 +980,17 @@ fn ensure_trait_definition_impl_message_args(
             ),
             range,
             severity: Severity::Error,
+            code: Some(DiagnosticCode::TraitImplMismatch),
             quickfixes: (!missing_arg_edits.is_empty()).then_some(vec![Action {
                 label: format!("Add missing ink! argument(s): {missing_args_help}."),
                 kind: ActionKind::QuickFix,
                 range,
                 edits: missing_arg_edits,
+                document_edits: Vec::new(),
+                is_preferred: true,
+                priority: 0,
             }]),
+            related_information: None,
         });
     }
 }
@@ -1545,6 +1648,29 @@ mod tests {
                     }],
                 }],
             ),
+            // Missing methods for ink! trait definition in a (non-root) module.
+            (
+                quote! {
+                    mod traits {
+                        #[ink::trait_definition]
+                        pub trait MyTrait {
+                            #[ink(message)]
+                            fn my_message(&self);
+                        }
+                    }
+
+                    #[ink(impl)] // needed for this to be parsed as an ink! impl without messages and constructors.
+                    impl traits::MyTrait for MyContract {}
+                },
+                vec![TestResultAction {
+                    label: "missing message",
+                    edits: vec![TestResultTextRange {
+                        text: "fn my_message(&self) {",
+                        start_pat: Some("impl traits::MyTrait for MyContract {"),
+                        end_pat: Some("impl traits::MyTrait for MyContract {"),
+                    }],
+                }],
+            ),
             // Undeclared method.
             (
                 quote! {
@@ -1661,6 +1787,34 @@ mod tests {
                     }],
                 }],
             ),
+            (
+                quote! {
+                    use crate::errors::Error;
+
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self) -> Result<(), Error>;
+                    }
+
+                    mod my_contract {
+                        use crate::other::Error;
+
+                        impl MyTrait for MyContract {
+                            #[ink(message)]
+                            fn my_message(&self) -> Result<(), Error> {}
+                        }
+                    }
+                },
+                vec![TestResultAction {
+                    label: "return type",
+                    edits: vec![TestResultTextRange {
+                        text: "-> Result<(), Error>",
+                        start_pat: Some("<--> Result<(), Error> {"),
+                        end_pat: Some("-> Result<(), Error>->"),
+                    }],
+                }],
+            ),
             // Mismatching ink! attribute arguments.
             (
                 quote! {
@@ -1727,6 +1881,112 @@ mod tests {
         }
     }
 
+    #[test]
+    fn trait_definition_impl_ret_type_aliases_works() {
+        for (code, expect_match) in [
+            // Equivalent paths via `use` aliases.
+            (
+                quote! {
+                    use crate::errors::Error;
+
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self) -> Result<(), Error>;
+                    }
+
+                    mod my_contract {
+                        use super::errors::{self, Error as MyError};
+
+                        impl MyTrait for MyContract {
+                            #[ink(message)]
+                            fn my_message(&self) -> Result<(), MyError> {}
+                        }
+                    }
+                },
+                true,
+            ),
+            (
+                quote! {
+                    use crate::errors;
+
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self) -> Result<Vec<u8>, errors::Error>;
+                    }
+
+                    use ink::prelude::vec::Vec;
+                    use crate::errors::Error;
+
+                    impl MyTrait for MyContract {
+                        #[ink(message)]
+                        fn my_message(&self) -> Result<ink::prelude::vec::Vec<u8>, Error> {}
+                    }
+                },
+                true,
+            ),
+            // Different paths.
+            (
+                quote! {
+                    use crate::errors::Error;
+
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self) -> Result<(), Error>;
+                    }
+
+                    mod my_contract {
+                        use crate::errors::OtherError as Error;
+
+                        impl MyTrait for MyContract {
+                            #[ink(message)]
+                            fn my_message(&self) -> Result<(), Error> {}
+                        }
+                    }
+                },
+                false,
+            ),
+        ] {
+            let code = quote_as_pretty_string! {
+                #code
+            };
+            let ink_impl = parse_first_ink_impl(&code);
+
+            let mut results = Vec::new();
+            ensure_trait_definition_impl_invariants(&mut results, &ink_impl);
+
+            if expect_match {
+                assert!(results.is_empty(), "impl: {code}");
+                continue;
+            }
+            assert_eq!(results.len(), 1, "impl: {code}");
+            // Verifies related information (i.e. points to both the declaration and the implementation).
+            let related_ranges: Vec<TextRange> = results[0]
+                .related_information
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|info| info.range)
+                .collect();
+            let range_of = |start_pat, end_pat| {
+                TextRange::new(
+                    TextSize::from(parse_offset_at(&code, Some(start_pat)).unwrap() as u32),
+                    TextSize::from(parse_offset_at(&code, Some(end_pat)).unwrap() as u32),
+                )
+            };
+            assert_eq!(
+                related_ranges,
+                vec![
+                    range_of("<--> Result<(), Error>;", "-> Result<(), Error>"),
+                    range_of("<--> Result<(), Error> {", "-> Result<(), Error>->"),
+                ],
+                "impl: {code}"
+            );
+        }
+    }
+
     #[test]
     fn valid_quasi_direct_descendant_works() {
         for code in valid_ink_impls!() {
@@ -1886,7 +2146,7 @@ mod tests {
             });
 
             let mut results = Vec::new();
-            diagnostics(&mut results, &ink_impl, false);
+            diagnostics(&mut results, &ink_impl, false, Version::V4);
             assert!(results.is_empty(), "impl: {code}");
         }
     }
@@ -1,9 +1,13 @@
 //! ink! test diagnostics.
 
-use ink_analyzer_ir::InkTest;
+use ink_analyzer_ir::ast::HasAttrs;
+use ink_analyzer_ir::syntax::AstNode;
+use ink_analyzer_ir::{ast, FromSyntax, InkAttribute, InkFile, InkTest};
 
 use super::utils;
-use crate::Diagnostic;
+use crate::analysis::text_edit::TextEdit;
+use crate::analysis::utils as analysis_utils;
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, Severity};
 
 const TEST_SCOPE_NAME: &str = "test";
 
@@ -28,6 +32,69 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, ink_test: &InkTest) {
     utils::ensure_no_ink_descendants(results, ink_test, TEST_SCOPE_NAME);
 }
 
+/// Ensures that tests (i.e. `#[test]` functions in `#[cfg(test)]` modules) that use
+/// ink! off-chain environment test helpers (i.e. `ink::env::test::*` APIs) are annotated with `#[ink::test]`.
+///
+/// `#[ink::test]` sets up the off-chain environment for the test,
+/// so ink! off-chain environment test helpers panic at runtime without it.
+pub fn ensure_env_helper_tests_annotated(results: &mut Vec<Diagnostic>, file: &InkFile) {
+    for fn_item in file.syntax().descendants().filter_map(ast::Fn::cast) {
+        let Some(test_attr) = fn_item
+            .attrs()
+            .find(|attr| attr.path().map_or(false, |path| path.to_string() == "test"))
+        else {
+            continue;
+        };
+        let has_ink_attrs = fn_item
+            .attrs()
+            .any(|attr| InkAttribute::cast(attr).is_some());
+        let is_cfg_test = fn_item.syntax().ancestors().any(|node| {
+            ast::Module::cast(node).map_or(false, |module| {
+                module
+                    .attrs()
+                    .any(|attr| analysis_utils::is_cfg_test_attr(&attr))
+            })
+        });
+        let uses_env_test_helpers = fn_item.body().map_or(false, |body| {
+            body.syntax()
+                .descendants()
+                .filter_map(ast::Path::cast)
+                // Only checks outermost paths (i.e. not qualifiers of other paths).
+                .filter(|path| path.syntax().parent().and_then(ast::Path::cast).is_none())
+                .any(|path| {
+                    let mut path = path.to_string();
+                    path.retain(|it| !it.is_whitespace());
+                    ["ink::env::test::", "ink_env::test::"]
+                        .iter()
+                        .any(|prefix| path.trim_start_matches("::").starts_with(prefix))
+                })
+        });
+        if has_ink_attrs || !is_cfg_test || !uses_env_test_helpers {
+            continue;
+        }
+
+        let range = test_attr.syntax().text_range();
+        results.push(Diagnostic {
+            message: "Tests that use ink! off-chain environment test helpers (i.e. `ink::env::test::*` APIs) \
+            should be annotated with `#[ink::test]`, because the off-chain environment isn't set up otherwise."
+                .to_string(),
+            range,
+            severity: Severity::Warning,
+            code: Some(DiagnosticCode::MissingInkTest),
+            quickfixes: Some(vec![Action {
+                label: "Replace `#[test]` with `#[ink::test]`.".to_string(),
+                kind: ActionKind::QuickFix,
+                range,
+                edits: vec![TextEdit::replace("#[ink::test]".to_string(), range)],
+                document_edits: Vec::new(),
+                is_preferred: true,
+                priority: 0,
+            }]),
+            related_information: None,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,4 +281,97 @@ mod tests {
             assert!(results.is_empty(), "ink test: {code}");
         }
     }
+
+    #[test]
+    fn env_helper_tests_annotated_works() {
+        for code in [
+            // ink! test.
+            quote! {
+                #[cfg(test)]
+                mod tests {
+                    #[ink::test]
+                    fn it_works() {
+                        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+                    }
+                }
+            },
+            // No ink! off-chain environment test helpers.
+            quote! {
+                #[cfg(test)]
+                mod tests {
+                    #[test]
+                    fn it_works() {
+                        assert_eq!(1 + 1, 2);
+                    }
+                }
+            },
+            // Not in a `#[cfg(test)]` module.
+            quote! {
+                mod tests {
+                    #[test]
+                    fn it_works() {
+                        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                    }
+                }
+            },
+        ] {
+            let code = quote_as_pretty_string! { #code };
+            let file = InkFile::parse(&code);
+
+            let mut results = Vec::new();
+            ensure_env_helper_tests_annotated(&mut results, &file);
+            assert!(results.is_empty(), "code: {code}");
+        }
+    }
+
+    #[test]
+    fn unannotated_env_helper_tests_fails() {
+        for code in [
+            quote! {
+                let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            },
+            quote! {
+                ::ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+            },
+            quote! {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            },
+        ] {
+            let code = quote_as_pretty_string! {
+                #[cfg(test)]
+                mod tests {
+                    #[test]
+                    fn it_works() {
+                        #code
+                    }
+                }
+            };
+            let file = InkFile::parse(&code);
+
+            let mut results = Vec::new();
+            ensure_env_helper_tests_annotated(&mut results, &file);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "code: {code}");
+            assert_eq!(results[0].severity, Severity::Warning, "code: {code}");
+            assert_eq!(
+                results[0].code,
+                Some(DiagnosticCode::MissingInkTest),
+                "code: {code}"
+            );
+            // Verifies quickfixes.
+            verify_actions(
+                &code,
+                results[0].quickfixes.as_ref().unwrap(),
+                &[TestResultAction {
+                    label: "Replace `#[test]` with `#[ink::test]`",
+                    edits: vec![TestResultTextRange {
+                        text: "#[ink::test]",
+                        start_pat: Some("<-#[test]"),
+                        end_pat: Some("#[test]"),
+                    }],
+                }],
+            );
+        }
+    }
 }
@@ -6,7 +6,7 @@ use ink_analyzer_ir::{ast, IsInkFn, Message};
 use super::utils;
 use crate::analysis::text_edit::TextEdit;
 use crate::analysis::utils as analysis_utils;
-use crate::{Action, ActionKind, Diagnostic, Severity};
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, Severity};
 
 const MESSAGE_SCOPE_NAME: &str = "message";
 
@@ -69,6 +69,7 @@ fn ensure_receiver_is_self_ref(fn_item: &ast::Fn) -> Option<Diagnostic> {
             .to_string(),
         range,
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidSignature),
         quickfixes: fn_item
             .param_list()
             .and_then(|param_list| param_list.l_paren_token())
@@ -87,6 +88,9 @@ fn ensure_receiver_is_self_ref(fn_item: &ast::Fn) -> Option<Diagnostic> {
                             format!("&self{insert_suffix}"),
                             insert_offset,
                         )],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     },
                     Action {
                         label: "Add mutable self reference receiver".to_string(),
@@ -96,9 +100,13 @@ fn ensure_receiver_is_self_ref(fn_item: &ast::Fn) -> Option<Diagnostic> {
                             format!("&mut self{insert_suffix}"),
                             insert_offset,
                         )],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     },
                 ]
             }),
+        related_information: None,
     })
 }
 
@@ -115,12 +123,17 @@ fn ensure_not_return_self(fn_item: &ast::Fn) -> Option<Diagnostic> {
         message: "ink! message must not return `Self`.".to_string(),
         range: return_type.syntax().text_range(),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidSignature),
         quickfixes: Some(vec![Action {
             label: "Remove `Self` return type.".to_string(),
             kind: ActionKind::QuickFix,
             range,
             edits: vec![TextEdit::delete(range)],
+            document_edits: Vec::new(),
+            is_preferred: false,
+            priority: 0,
         }]),
+        related_information: None,
     })
 }
 
@@ -0,0 +1,455 @@
+//! ink! contract crate `no_std` compatibility diagnostics.
+//!
+//! These are project-level diagnostics, so they're only run when the project's `Cargo.toml` is known
+//! (i.e. the analyzed file is assumed to be the crate root of an ink! contract crate).
+
+use ink_analyzer_ir::syntax::{AstNode, SyntaxNode, TextRange, TextSize};
+use ink_analyzer_ir::{ast, Contract, FromInkAttribute, FromSyntax, InkFile};
+
+use crate::analysis::text_edit::TextEdit;
+use crate::analysis::utils as analysis_utils;
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, DocumentEdit, Severity};
+
+/// The conventional `no_std` prelude for ink! contract crates.
+const NO_STD_PRELUDE: &str = r#"#![cfg_attr(not(feature = "std"), no_std, no_main)]"#;
+
+/// Runs all ink! contract crate `no_std` compatibility diagnostics.
+pub fn diagnostics(results: &mut Vec<Diagnostic>, file: &InkFile, cargo_toml: &str) {
+    // Ignores files without ink! contracts.
+    let Some(contract) = file.contracts().first() else {
+        return;
+    };
+
+    // Ensures that the crate has the `no_std` prelude, see `ensure_no_std_prelude` doc.
+    if let Some(diagnostic) = ensure_no_std_prelude(file, contract, cargo_toml) {
+        results.push(diagnostic);
+    }
+
+    // Ensures that there are no `std`-only constructs in ink! contract code paths,
+    // see `ensure_no_std_only_constructs` doc.
+    for contract in file.contracts() {
+        ensure_no_std_only_constructs(results, contract);
+    }
+}
+
+/// Ensures that the ink! contract crate has the conventional `no_std` prelude
+/// (i.e. `#![cfg_attr(not(feature = "std"), no_std, no_main)]`).
+///
+/// The quickfix inserts the prelude at the start of the file
+/// and declares the `std` feature in the project's `Cargo.toml` (if necessary).
+///
+/// Ref: <https://use.ink/basics/contract-template>.
+fn ensure_no_std_prelude(
+    file: &InkFile,
+    contract: &Contract,
+    cargo_toml: &str,
+) -> Option<Diagnostic> {
+    // Either `#![no_std]` or `#![cfg_attr(<condition>, no_std, ...)]` is `no_std` compatible.
+    let has_no_std_attr = file
+        .syntax()
+        .children()
+        .filter_map(ast::Attr::cast)
+        .filter(|attr| attr.excl_token().is_some())
+        .any(|attr| {
+            let mut meta = attr
+                .meta()
+                .map(|meta| meta.syntax().to_string())
+                .unwrap_or_default();
+            meta.retain(|it| !it.is_whitespace());
+            meta == "no_std"
+                || (meta.starts_with("cfg_attr(")
+                    && meta.split([',', ')']).any(|it| it == "no_std"))
+        });
+    if has_no_std_attr {
+        return None;
+    }
+
+    Some(Diagnostic {
+        message: format!(
+            "ink! contract crates must be `no_std` compatible, \
+            i.e. the crate root should start with `{NO_STD_PRELUDE}`."
+        ),
+        range: contract.ink_attr().syntax().text_range(),
+        severity: Severity::Error,
+        code: Some(DiagnosticCode::MissingNoStd),
+        quickfixes: Some(vec![Action {
+            label: "Add `no_std` prelude.".to_string(),
+            kind: ActionKind::QuickFix,
+            range: contract.ink_attr().syntax().text_range(),
+            edits: vec![TextEdit::insert(
+                format!("{NO_STD_PRELUDE}\n\n"),
+                TextSize::from(0),
+            )],
+            // Declares the `std` feature in the `Cargo.toml` (if necessary).
+            document_edits: analysis_utils::cargo_feature_edit(cargo_toml, "std", r#"["ink/std"]"#)
+                .map(|edit| DocumentEdit {
+                    path: "Cargo.toml".to_string(),
+                    edits: vec![edit],
+                })
+                .into_iter()
+                .collect(),
+            is_preferred: true,
+            priority: 0,
+        }]),
+        related_information: None,
+    })
+}
+
+/// Ensures that there are no `std`-only constructs (i.e. `std` paths and printing macros)
+/// in ink! contract code paths (i.e. code that's not gated behind the `std` feature or only compiled for tests).
+///
+/// Quickfixes replace `std` paths with their `no_std` equivalents (i.e. `ink::prelude` or `core`),
+/// and printing macros with their ink! debug equivalents (i.e. `ink::env::debug_println!`), if possible.
+///
+/// Ref: <https://use.ink/faq#what-does-the-contract-is-no_std-mean>.
+fn ensure_no_std_only_constructs(results: &mut Vec<Diagnostic>, contract: &Contract) {
+    for node in contract.syntax().descendants() {
+        let Some((message, range, replacement)) = std_path(&node).or_else(|| print_macro(&node))
+        else {
+            continue;
+        };
+        if is_std_gated(&node) {
+            continue;
+        }
+
+        results.push(Diagnostic {
+            message,
+            range,
+            severity: Severity::Error,
+            code: Some(DiagnosticCode::StdOnlyConstruct),
+            quickfixes: replacement.map(|replacement| {
+                vec![Action {
+                    label: format!("Replace with `{replacement}`."),
+                    kind: ActionKind::QuickFix,
+                    range,
+                    edits: vec![TextEdit::replace(replacement.to_string(), range)],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
+                }]
+            }),
+            related_information: None,
+        });
+    }
+}
+
+/// Returns the diagnostic message, text range and `no_std` replacement (if any)
+/// for the root segment of a path that starts with `std` (e.g. `std::vec::Vec`).
+fn std_path(node: &SyntaxNode) -> Option<(String, TextRange, Option<&'static str>)> {
+    let path = ast::Path::cast(node.clone())?;
+    let name_ref = path.segment()?.name_ref()?;
+    if path.qualifier().is_some() || name_ref.text() != "std" {
+        return None;
+    }
+
+    // Determines the `no_std` equivalent (if any) based on the next path segments.
+    let segment_names: Vec<String> =
+        std::iter::successors(path.syntax().parent().and_then(ast::Path::cast), |path| {
+            path.syntax().parent().and_then(ast::Path::cast)
+        })
+        .filter_map(|path| path.segment()?.name_ref())
+        .map(|name_ref| name_ref.text().to_string())
+        .collect();
+    let replacement = match segment_names.first().map(String::as_str) {
+        Some("vec" | "string" | "boxed" | "borrow" | "format") => Some("ink::prelude"),
+        Some("collections") => segment_names
+            .get(1)
+            .filter(|name| {
+                matches!(
+                    name.as_str(),
+                    "BTreeMap"
+                        | "BTreeSet"
+                        | "BinaryHeap"
+                        | "LinkedList"
+                        | "VecDeque"
+                        | "btree_map"
+                        | "btree_set"
+                )
+            })
+            .map(|_| "ink::prelude"),
+        Some(
+            "any" | "cell" | "clone" | "cmp" | "convert" | "default" | "fmt" | "hash" | "iter"
+            | "marker" | "mem" | "num" | "ops" | "option" | "primitive" | "ptr" | "result"
+            | "slice" | "str",
+        ) => Some("core"),
+        _ => None,
+    };
+
+    Some((
+        format!(
+            "`std` is not available in `no_std` environments (i.e. when the contract is compiled for on-chain deployment), {}.",
+            match replacement {
+                Some(replacement) => format!("consider using `{replacement}` instead"),
+                None => "consider gating this code behind the `std` feature \
+                (i.e. `#[cfg(feature = \"std\")]`)"
+                    .to_string(),
+            }
+        ),
+        name_ref.syntax().text_range(),
+        replacement,
+    ))
+}
+
+/// Returns the diagnostic message, text range and ink! debug equivalent (if any)
+/// for the path of an unqualified printing macro call (e.g. `println!`).
+fn print_macro(node: &SyntaxNode) -> Option<(String, TextRange, Option<&'static str>)> {
+    let path = ast::MacroCall::cast(node.clone())?.path()?;
+    let name_ref = path.segment()?.name_ref()?;
+    if path.qualifier().is_some() {
+        return None;
+    }
+    let replacement = match name_ref.text().as_str() {
+        "print" | "eprint" => Some("ink::env::debug_print"),
+        "println" | "eprintln" => Some("ink::env::debug_println"),
+        "dbg" => None,
+        _ => return None,
+    };
+
+    Some((
+        format!(
+            "`{}!` is not available in `no_std` environments (i.e. when the contract is compiled for on-chain deployment){}.",
+            name_ref.text(),
+            replacement
+                .map(|replacement| format!(", consider using `{replacement}!` instead"))
+                .unwrap_or_default()
+        ),
+        path.syntax().text_range(),
+        replacement,
+    ))
+}
+
+/// Returns true if the node (or one of its ancestors) is gated behind the `std` feature
+/// (e.g. `#[cfg(feature = "std")]`) or only compiled for tests (e.g. `#[cfg(test)]`).
+fn is_std_gated(node: &SyntaxNode) -> bool {
+    node.ancestors().any(|node| {
+        node.children().filter_map(ast::Attr::cast).any(|attr| {
+            analysis_utils::is_cfg_test_attr(&attr)
+                || (attr.path().map_or(false, |path| path.to_string() == "cfg")
+                    && attr.token_tree().map_or(false, |token_tree| {
+                        let mut meta = token_tree.syntax().to_string();
+                        meta.retain(|it| !it.is_whitespace());
+                        meta.contains(r#"feature="std""#) && !meta.contains(r#"not(feature="std")"#)
+                    }))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::verify_actions;
+    use test_utils::{TestResultAction, TestResultTextRange};
+
+    const CARGO_TOML: &str = "[package]\nname = \"my_contract\"\n\n[features]\ndefault = [\"std\"]\nstd = [\n    \"ink/std\",\n]\n";
+
+    const CONTRACT: &str = r#"
+#[ink::contract]
+mod my_contract {
+    #[ink(storage)]
+    pub struct MyContract {}
+
+    impl MyContract {
+        #[ink(constructor)]
+        pub fn new() -> Self {}
+
+        #[ink(message)]
+        pub fn my_message(&self) {}
+    }
+}
+"#;
+
+    #[test]
+    fn no_std_prelude_works() {
+        for prelude in [
+            r#"#![cfg_attr(not(feature = "std"), no_std, no_main)]"#,
+            r#"#![cfg_attr(not(feature = "std"), no_std)]"#,
+            "#![no_std]",
+        ] {
+            let code = format!("{prelude}\n{CONTRACT}");
+            let file = InkFile::parse(&code);
+
+            let mut results = Vec::new();
+            diagnostics(&mut results, &file, CARGO_TOML);
+            assert!(results.is_empty(), "code: {code}");
+        }
+    }
+
+    #[test]
+    fn missing_no_std_prelude_fails() {
+        for (prelude, cargo_toml, expected_cargo_edit) in [
+            ("", CARGO_TOML, None),
+            (
+                "#![cfg_attr(not(feature = \"std\"), no_main)]",
+                CARGO_TOML,
+                None,
+            ),
+            (
+                "",
+                "[package]\nname = \"my_contract\"\n",
+                Some(("\n[features]\nstd = [\"ink/std\"]\n", 31)),
+            ),
+        ] {
+            let code = format!("{prelude}\n{CONTRACT}");
+            let file = InkFile::parse(&code);
+
+            let mut results = Vec::new();
+            diagnostics(&mut results, &file, cargo_toml);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "code: {code}");
+            assert_eq!(results[0].severity, Severity::Error, "code: {code}");
+            assert_eq!(
+                results[0].code,
+                Some(DiagnosticCode::MissingNoStd),
+                "code: {code}"
+            );
+            // Verifies quickfixes.
+            let quickfixes = results[0].quickfixes.as_ref().unwrap();
+            verify_actions(
+                &code,
+                quickfixes,
+                &[TestResultAction {
+                    label: "`no_std` prelude",
+                    edits: vec![TestResultTextRange {
+                        text: NO_STD_PRELUDE,
+                        start_pat: Some(""),
+                        end_pat: Some(""),
+                    }],
+                }],
+            );
+            // Verifies `Cargo.toml` edits.
+            let cargo_edits: Vec<(&str, TextRange)> = quickfixes[0]
+                .document_edits
+                .iter()
+                .flat_map(|document_edit| &document_edit.edits)
+                .map(|edit| (edit.text.as_str(), edit.range))
+                .collect();
+            let expected_cargo_edits: Vec<(&str, TextRange)> = expected_cargo_edit
+                .map(|(text, offset)| (text, TextRange::empty(TextSize::from(offset))))
+                .into_iter()
+                .collect();
+            assert_eq!(cargo_edits, expected_cargo_edits, "code: {code}");
+        }
+    }
+
+    #[test]
+    fn no_std_only_constructs_works() {
+        let code = r#"
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod my_contract {
+    use ink::prelude::vec::Vec;
+    use core::fmt::Debug;
+
+    #[ink(storage)]
+    pub struct MyContract {
+        values: Vec<u8>,
+    }
+
+    impl MyContract {
+        #[ink(constructor)]
+        pub fn new() -> Self {}
+
+        #[ink(message)]
+        pub fn my_message(&self) {
+            ink::env::debug_println!("my_message");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::fmt::Display for MyContract {}
+
+    #[cfg(test)]
+    mod tests {
+        #[test]
+        fn it_works() {
+            println!("{:?}", std::vec::Vec::<u8>::new());
+        }
+    }
+}
+"#;
+        let file = InkFile::parse(code);
+
+        let mut results = Vec::new();
+        diagnostics(&mut results, &file, CARGO_TOML);
+        assert!(results.is_empty(), "code: {code}");
+    }
+
+    #[test]
+    fn std_only_constructs_fails() {
+        let code = r#"
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod my_contract {
+    use std::vec::Vec;
+    use std::collections::HashMap;
+
+    #[ink(storage)]
+    pub struct MyContract {}
+
+    impl MyContract {
+        #[ink(constructor)]
+        pub fn new() -> Self {}
+
+        #[ink(message)]
+        pub fn my_message(&self) {
+            println!("my_message");
+            dbg!(self);
+            let ordering: std::cmp::Ordering = 1.cmp(&2);
+        }
+    }
+}
+"#;
+        let file = InkFile::parse(code);
+
+        let mut results = Vec::new();
+        diagnostics(&mut results, &file, CARGO_TOML);
+
+        // Verifies diagnostics.
+        assert_eq!(results.len(), 5, "code: {code}");
+        for result in &results {
+            assert_eq!(result.severity, Severity::Error);
+            assert_eq!(result.code, Some(DiagnosticCode::StdOnlyConstruct));
+        }
+        // Verifies quickfixes.
+        for (idx, expected_quickfixes) in [
+            vec![TestResultAction {
+                label: "`ink::prelude`",
+                edits: vec![TestResultTextRange {
+                    text: "ink::prelude",
+                    start_pat: Some("<-std::vec"),
+                    end_pat: Some("use std"),
+                }],
+            }],
+            vec![],
+            vec![TestResultAction {
+                label: "`ink::env::debug_println`",
+                edits: vec![TestResultTextRange {
+                    text: "ink::env::debug_println",
+                    start_pat: Some("<-println!"),
+                    end_pat: Some("println"),
+                }],
+            }],
+            vec![],
+            vec![TestResultAction {
+                label: "`core`",
+                edits: vec![TestResultTextRange {
+                    text: "core",
+                    start_pat: Some("<-std::cmp"),
+                    end_pat: Some("ordering: std"),
+                }],
+            }],
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            verify_actions(
+                code,
+                results[idx].quickfixes.as_deref().unwrap_or_default(),
+                &expected_quickfixes,
+            );
+        }
+    }
+}
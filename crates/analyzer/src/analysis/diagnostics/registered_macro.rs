@@ -0,0 +1,211 @@
+//! Diagnostics for registered ink! attribute macros (see [`MacroRegistry`]).
+
+use ink_analyzer_ir::syntax::{AstNode, SyntaxKind, TextRange};
+use ink_analyzer_ir::{FromSyntax, InkAttribute, InkFile, IsInkEntity};
+use itertools::Itertools;
+
+use super::{Diagnostic, DiagnosticCode, Severity};
+use crate::analysis::registry::MacroRegistry;
+use crate::analysis::text_edit::TextEdit;
+use crate::analysis::utils;
+use crate::{Action, ActionKind};
+
+/// Runs all diagnostics for registered ink! attribute macros.
+///
+/// Registered ink! attribute macros are unknown to ink! analyzer, so all other diagnostics
+/// for the attribute (e.g. unknown ink! attribute and unknown ink! attribute argument diagnostics)
+/// are replaced by diagnostics based on the registered definition.
+pub fn diagnostics(results: &mut Vec<Diagnostic>, file: &InkFile, registry: &MacroRegistry) {
+    for attr in file.tree().ink_attrs_in_scope() {
+        let Some(definition) = registry.resolve(&attr) else {
+            continue;
+        };
+
+        // Removes diagnostics for the registered ink! attribute macro
+        // that are based on it being unknown to ink! analyzer.
+        let attr_range = attr.syntax().text_range();
+        results.retain(|diagnostic| !attr_range.contains_range(diagnostic.range));
+
+        // Ensures that the registered ink! attribute macro is applied to a valid item,
+        // see `ensure_valid_scope` doc.
+        if let Some(diagnostic) = ensure_valid_scope(&attr, &definition.path(), |kind| {
+            definition.is_valid_scope(kind)
+        }) {
+            results.push(diagnostic);
+        }
+
+        // Ensures that the registered ink! attribute macro only has registered arguments,
+        // see `ensure_valid_arguments` doc.
+        ensure_valid_arguments(results, &attr, &definition.path(), &definition.args);
+    }
+}
+
+/// Returns the text ranges of registered ink! attribute macros
+/// (i.e. text ranges for which all other diagnostics are replaced, see `diagnostics` doc).
+pub fn registered_attr_ranges(file: &InkFile, registry: &MacroRegistry) -> Vec<TextRange> {
+    file.tree()
+        .ink_attrs_in_scope()
+        .filter(|attr| registry.resolve(attr).is_some())
+        .map(|attr| attr.syntax().text_range())
+        .collect()
+}
+
+/// Ensures that the registered ink! attribute macro is applied to an item with a registered scope.
+fn ensure_valid_scope<F>(attr: &InkAttribute, path: &str, is_valid_scope: F) -> Option<Diagnostic>
+where
+    F: Fn(SyntaxKind) -> bool,
+{
+    let is_valid = ink_analyzer_ir::parent_ast_item(attr.syntax())
+        .map_or(false, |item| is_valid_scope(item.syntax().kind()));
+    (!is_valid).then(|| Diagnostic {
+        message: format!("Invalid scope for a `#[{path}]` attribute."),
+        range: attr.syntax().text_range(),
+        severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidScope),
+        quickfixes: Some(vec![Action::remove_attribute(attr)]),
+        related_information: None,
+    })
+}
+
+/// Ensures that the registered ink! attribute macro only has registered and non-duplicate arguments.
+fn ensure_valid_arguments(
+    results: &mut Vec<Diagnostic>,
+    attr: &InkAttribute,
+    path: &str,
+    valid_args: &[String],
+) {
+    let mut seen_args = Vec::new();
+    for arg in attr.args() {
+        let arg_name = arg.meta().name().to_string();
+        let is_valid = valid_args.contains(&arg_name);
+        let is_duplicate = is_valid && seen_args.contains(&arg_name);
+        if is_valid && !is_duplicate {
+            seen_args.push(arg_name);
+            continue;
+        }
+
+        // Edit range for quickfix.
+        let range = utils::ink_arg_and_delimiter_removal_range(arg, Some(attr));
+        results.push(Diagnostic {
+            message: if is_duplicate {
+                format!("Duplicate `{arg_name}` argument for `#[{path}]` attribute.")
+            } else if arg_name.is_empty() {
+                format!("Missing argument for `#[{path}]` attribute.")
+            } else {
+                format!(
+                    "Unknown argument for `#[{path}]` attribute: '{arg_name}', expected one of: {}.",
+                    valid_args.iter().map(|arg| format!("`{arg}`")).join(", ")
+                )
+            },
+            range: arg.text_range(),
+            severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidArgument),
+            quickfixes: Some(vec![Action {
+                label: format!("Remove `{arg_name}` argument."),
+                kind: ActionKind::QuickFix,
+                range,
+                edits: vec![TextEdit::delete(range)],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
+            }]),
+            related_information: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::registry::{MacroDefinition, MacroScope};
+    use crate::{DiagnosticsConfig, Version};
+
+    fn registry() -> MacroRegistry {
+        let mut registry = MacroRegistry::new();
+        registry.register(MacroDefinition {
+            crate_name: "ink".to_string(),
+            name: "precompile".to_string(),
+            scopes: vec![MacroScope::Trait],
+            args: vec!["address".to_string()],
+        });
+        registry
+    }
+
+    #[test]
+    fn registered_macro_works() {
+        for (code, expected_messages) in [
+            // Valid usage.
+            (
+                r#"
+                #[ink::precompile]
+                pub trait MyPrecompile {}
+                "#,
+                vec![],
+            ),
+            (
+                r#"
+                #[ink::precompile(address = 0x900)]
+                pub trait MyPrecompile {}
+                "#,
+                vec![],
+            ),
+            // Invalid scope.
+            (
+                r#"
+                #[ink::precompile]
+                pub struct MyPrecompile;
+                "#,
+                vec!["Invalid scope"],
+            ),
+            // Unknown argument.
+            (
+                r#"
+                #[ink::precompile(xyz)]
+                pub trait MyPrecompile {}
+                "#,
+                vec!["Unknown argument"],
+            ),
+            // Duplicate argument.
+            (
+                r#"
+                #[ink::precompile(address = 0x900, address = 0x901)]
+                pub trait MyPrecompile {}
+                "#,
+                vec!["Duplicate `address` argument"],
+            ),
+        ] {
+            let file = InkFile::parse(code);
+            let results = super::super::diagnostics(
+                &file,
+                Version::default(),
+                &DiagnosticsConfig::default(),
+                &registry(),
+            );
+
+            assert_eq!(results.len(), expected_messages.len(), "code: {code}");
+            for (result, expected_message) in results.iter().zip(expected_messages) {
+                assert!(result.message.contains(expected_message), "code: {code}");
+            }
+        }
+    }
+
+    #[test]
+    fn unregistered_macro_is_unknown() {
+        let file = InkFile::parse(
+            r#"
+            #[ink::precompile]
+            pub trait MyPrecompile {}
+            "#,
+        );
+        let results = super::super::diagnostics(
+            &file,
+            Version::default(),
+            &DiagnosticsConfig::default(),
+            &MacroRegistry::new(),
+        );
+
+        assert!(results
+            .iter()
+            .any(|result| result.message.contains("Unknown ink! attribute")));
+    }
+}
@@ -1,9 +1,11 @@
 //! ink! storage diagnostics.
 
-use ink_analyzer_ir::Storage;
+use ink_analyzer_ir::ast::AstNode;
+use ink_analyzer_ir::{ast, IsInkStruct, Storage};
 
 use super::utils;
-use crate::Diagnostic;
+use crate::analysis::text_edit::TextEdit;
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, Severity};
 
 const STORAGE_SCOPE_NAME: &str = "storage";
 
@@ -33,6 +35,162 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, storage: &Storage) {
 
     // Ensures that ink! storage has no ink! descendants, see `utils::ensure_no_ink_descendants` doc.
     utils::ensure_no_ink_descendants(results, storage, STORAGE_SCOPE_NAME);
+
+    // Ensures that ink! storage fields don't use types that are known to be problematic for ink! storage,
+    // see `ensure_storage_compatible_field_types` doc.
+    ensure_storage_compatible_field_types(results, storage);
+}
+
+/// Ensures that ink! storage fields don't use types that are known to be problematic for ink! storage
+/// (i.e. `std` only collections and non-packed types (e.g. `Mapping` and `Lazy`) nested in packed types (e.g. `Vec`)).
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/storage/src/lazy/mapping.rs>.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/storage/traits/src/storage.rs>.
+fn ensure_storage_compatible_field_types(results: &mut Vec<Diagnostic>, storage: &Storage) {
+    let field_types: Vec<ast::Type> = match storage.struct_item().and_then(ast::Struct::field_list)
+    {
+        Some(ast::FieldList::RecordFieldList(field_list)) => {
+            field_list.fields().filter_map(|field| field.ty()).collect()
+        }
+        Some(ast::FieldList::TupleFieldList(field_list)) => {
+            field_list.fields().filter_map(|field| field.ty()).collect()
+        }
+        None => Vec::new(),
+    };
+
+    for ty in field_types {
+        let range = ty.syntax().text_range();
+        match path_type_name_and_args(&ty) {
+            // `std` only collections.
+            Some((name, args)) if matches!(name.as_str(), "HashMap" | "HashSet") => {
+                // Suggests `Mapping` as an alternative (if possible).
+                let mapping_args = match (name.as_str(), args.as_slice()) {
+                    ("HashMap", [key, value]) => Some(format!("{key}, {value}")),
+                    ("HashSet", [key]) => Some(format!("{key}, ()")),
+                    _ => None,
+                };
+                results.push(Diagnostic {
+                    message: format!(
+                        "`{name}` is not available in `no_std` environments and can't be used in ink! storage, \
+                        consider using `ink::storage::Mapping` instead."
+                    ),
+                    range,
+                    severity: Severity::Warning,
+                    code: Some(DiagnosticCode::StorageIncompatibleType),
+                    quickfixes: mapping_args.map(|mapping_args| {
+                        vec![Action {
+                            label: format!(
+                                "Replace `{name}` with `ink::storage::Mapping<{mapping_args}>`."
+                            ),
+                            kind: ActionKind::QuickFix,
+                            range,
+                            edits: vec![TextEdit::replace(
+                                format!("ink::storage::Mapping<{mapping_args}>"),
+                                range,
+                            )],
+                            document_edits: Vec::new(),
+                            is_preferred: false,
+                            priority: 0,
+                        }]
+                    }),
+                    related_information: None,
+                });
+            }
+            // Non-packed types nested in packed types.
+            _ => {
+                if let Some(non_packed_ty) = nested_types(&ty).into_iter().find(is_non_packed_type)
+                {
+                    // Suggests replacing `Vec<Lazy<T>>` with `Mapping<u32, T>` (if possible).
+                    let mapping_args = path_type_name_and_args(&ty)
+                        .filter(|(name, _)| name == "Vec")
+                        .zip(path_type_name_and_args(&non_packed_ty))
+                        .and_then(|(_, (name, args))| match (name.as_str(), args.as_slice()) {
+                            ("Lazy", [value]) => Some(format!("u32, {value}")),
+                            _ => None,
+                        });
+                    results.push(Diagnostic {
+                        message: format!(
+                            "`{non_packed_ty}` is a non-packed type and can't be nested in `{ty}`, \
+                            non-packed types (e.g. `Mapping` and `Lazy`) can only be used as the types \
+                            of top-level fields of `#[ink(storage)]` and `#[ink::storage_item]` types."
+                        ),
+                        range,
+                        severity: Severity::Warning,
+                        code: Some(DiagnosticCode::StorageIncompatibleType),
+                        quickfixes: mapping_args.map(|mapping_args| {
+                            vec![Action {
+                                label: format!(
+                                    "Replace `{ty}` with `ink::storage::Mapping<{mapping_args}>`."
+                                ),
+                                kind: ActionKind::QuickFix,
+                                range,
+                                edits: vec![TextEdit::replace(
+                                    format!("ink::storage::Mapping<{mapping_args}>"),
+                                    range,
+                                )],
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
+                            }]
+                        }),
+                        related_information: None,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns the name of the last path segment and its generic type arguments (if any) for a path type.
+fn path_type_name_and_args(ty: &ast::Type) -> Option<(String, Vec<ast::Type>)> {
+    match ty {
+        ast::Type::PathType(path_type) => {
+            let segment = path_type.path()?.segment()?;
+            let name = segment.name_ref()?.to_string();
+            let args = segment
+                .generic_arg_list()
+                .map(|arg_list| {
+                    arg_list
+                        .generic_args()
+                        .filter_map(|arg| match arg {
+                            ast::GenericArg::TypeArg(type_arg) => type_arg.ty(),
+                            _ => None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some((name, args))
+        }
+        _ => None,
+    }
+}
+
+/// Returns all types nested in a type (e.g. `T` for `Vec<T>`, `[T; N]` and `(T, U)`), excluding the type itself.
+fn nested_types(ty: &ast::Type) -> Vec<ast::Type> {
+    let children: Vec<ast::Type> = match ty {
+        ast::Type::PathType(_) => path_type_name_and_args(ty)
+            .map(|(_, args)| args)
+            .unwrap_or_default(),
+        ast::Type::ArrayType(array_type) => array_type.ty().into_iter().collect(),
+        ast::Type::SliceType(slice_type) => slice_type.ty().into_iter().collect(),
+        ast::Type::TupleType(tuple_type) => tuple_type.fields().collect(),
+        ast::Type::ParenType(paren_type) => paren_type.ty().into_iter().collect(),
+        _ => Vec::new(),
+    };
+    children
+        .iter()
+        .flat_map(|child| [child.clone()].into_iter().chain(nested_types(child)))
+        .collect()
+}
+
+/// Returns true if the type is a (known) non-packed type (i.e. `Mapping` and `Lazy`).
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/storage/src/lazy/mod.rs>.
+fn is_non_packed_type(ty: &ast::Type) -> bool {
+    path_type_name_and_args(ty).map_or(false, |(name, _)| {
+        matches!(name.as_str(), "Mapping" | "Lazy")
+    })
 }
 
 #[cfg(test)]
@@ -228,6 +386,66 @@ mod tests {
                     ],
                 }],
             ),
+            // Function ancestor.
+            (
+                quote_as_pretty_string! {
+                    #[ink::contract]
+                    mod my_contract {
+                        fn my_fn() {
+                            #[ink(storage)]
+                            pub struct MyContract {
+                                value: bool,
+                            }
+                        }
+                    }
+                },
+                vec![TestResultAction {
+                    label: "Move item",
+                    edits: vec![
+                        TestResultTextRange {
+                            text: "pub struct MyContract",
+                            start_pat: Some("my_contract {"),
+                            end_pat: Some("my_contract {"),
+                        },
+                        TestResultTextRange {
+                            text: "",
+                            start_pat: Some("<-#[ink(storage)]"),
+                            end_pat: Some("}"),
+                        },
+                    ],
+                }],
+            ),
+            // Associated function ancestor.
+            (
+                quote_as_pretty_string! {
+                    #[ink::contract]
+                    mod my_contract {
+                        impl MyContract {
+                            pub fn my_fn() {
+                                #[ink(storage)]
+                                pub struct MyContract {
+                                    value: bool,
+                                }
+                            }
+                        }
+                    }
+                },
+                vec![TestResultAction {
+                    label: "Move item",
+                    edits: vec![
+                        TestResultTextRange {
+                            text: "pub struct MyContract",
+                            start_pat: Some("my_contract {"),
+                            end_pat: Some("my_contract {"),
+                        },
+                        TestResultTextRange {
+                            text: "",
+                            start_pat: Some("<-#[ink(storage)]"),
+                            end_pat: Some("}"),
+                        },
+                    ],
+                }],
+            ),
         ] {
             let storage = parse_first_storage_definition(&code);
 
@@ -301,6 +519,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn storage_compatible_field_types_works() {
+        for code in valid_storage!().into_iter().chain([quote! {
+            #[ink(storage)]
+            pub struct MyContract {
+                field_1: Mapping<AccountId, Balance>,
+                field_2: ink::storage::Lazy<Vec<u8>>,
+                field_3: Vec<(AccountId, Balance)>,
+                field_4: BTreeMap<u8, Option<bool>>,
+                field_5: [u8; 32],
+            }
+        }]) {
+            let storage = parse_first_storage_definition(quote_as_str! {
+                #code
+            });
+
+            let mut results = Vec::new();
+            ensure_storage_compatible_field_types(&mut results, &storage);
+            assert!(results.is_empty(), "storage: {code}");
+        }
+    }
+
+    #[test]
+    fn storage_incompatible_field_types_fails() {
+        for (code, expected_quickfixes) in [
+            // `std` only collections.
+            (
+                quote! {
+                    field: std::collections::HashMap<AccountId, Balance>,
+                },
+                vec![TestResultAction {
+                    label: "Replace `HashMap`",
+                    edits: vec![TestResultTextRange {
+                        text: "ink::storage::Mapping<AccountId, Balance>",
+                        start_pat: Some("<-std::collections::HashMap"),
+                        end_pat: Some("HashMap<AccountId, Balance>"),
+                    }],
+                }],
+            ),
+            (
+                quote! {
+                    field: HashSet<AccountId>,
+                },
+                vec![TestResultAction {
+                    label: "Replace `HashSet`",
+                    edits: vec![TestResultTextRange {
+                        text: "ink::storage::Mapping<AccountId, ()>",
+                        start_pat: Some("<-HashSet"),
+                        end_pat: Some("HashSet<AccountId>"),
+                    }],
+                }],
+            ),
+            // Non-packed types nested in packed types.
+            (
+                quote! {
+                    field: Vec<Lazy<Balance>>,
+                },
+                vec![TestResultAction {
+                    label: "Replace `Vec<Lazy<Balance>>`",
+                    edits: vec![TestResultTextRange {
+                        text: "ink::storage::Mapping<u32, Balance>",
+                        start_pat: Some("<-Vec<Lazy<Balance>>"),
+                        end_pat: Some("Vec<Lazy<Balance>>"),
+                    }],
+                }],
+            ),
+            (
+                quote! {
+                    field: Option<Mapping<AccountId, Balance>>,
+                },
+                vec![],
+            ),
+            (
+                quote! {
+                    field: (u8, ink::storage::Lazy<Balance>),
+                },
+                vec![],
+            ),
+        ] {
+            let code = quote_as_pretty_string! {
+                #[ink(storage)]
+                pub struct MyContract {
+                    #code
+                }
+            };
+            let storage = parse_first_storage_definition(&code);
+
+            let mut results = Vec::new();
+            ensure_storage_compatible_field_types(&mut results, &storage);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "storage: {code}");
+            assert_eq!(results[0].severity, Severity::Warning, "storage: {code}");
+            // Verifies quickfixes.
+            match results[0].quickfixes.as_ref() {
+                Some(quickfixes) => verify_actions(&code, quickfixes, &expected_quickfixes),
+                None => assert!(expected_quickfixes.is_empty(), "storage: {code}"),
+            }
+        }
+    }
+
     #[test]
     // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item/storage.rs#L130-L140>.
     fn compound_diagnostic_works() {
@@ -1,12 +1,32 @@
 //! ink! storage item diagnostics.
 
-use ink_analyzer_ir::{FromInkAttribute, FromSyntax, StorageItem};
+use ink_analyzer_ir::ast::{AstNode, HasAttrs, HasGenericParams, HasName, HasTypeBounds};
+use ink_analyzer_ir::syntax::{SyntaxKind, SyntaxToken};
+use ink_analyzer_ir::{ast, FromInkAttribute, FromSyntax, InkArgKind, StorageItem};
 
 use super::utils;
-use crate::{Action, Diagnostic, Severity};
+use crate::analysis::text_edit::TextEdit;
+use crate::analysis::utils as analysis_utils;
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, Severity};
 
 const STORAGE_ITEM_SCOPE_NAME: &str = "storage_item";
 
+/// Storage traits that satisfy the storage requirements of generic type parameters.
+const STORAGE_BOUNDS: [&str; 5] = [
+    "Packed",
+    "Storable",
+    "StorableHint",
+    "AutoStorableHint",
+    "StorageKey",
+];
+
+/// Storage traits derived by ink! storage items (when `derive` is enabled).
+const DERIVED_STORAGE_TRAITS: [&str; 3] = ["Storable", "StorableHint", "StorageKey"];
+
+/// Storage traits implemented by ink! storage items (when `derive` is enabled).
+const GENERATED_STORAGE_TRAITS: [&str; 4] =
+    ["Storable", "StorableHint", "StorageKey", "StorageLayout"];
+
 /// Runs all ink! storage item diagnostics.
 ///
 /// The entry point for finding ink! storage item semantic rules is the `storage_item` module of the `ink_ir` crate.
@@ -23,6 +43,13 @@ pub fn diagnostics(results: &mut Vec<Diagnostic>, storage_item: &StorageItem) {
 
     // Ensures that ink! storage item has no ink! descendants, see `utils::ensure_no_ink_descendants` doc.
     utils::ensure_no_ink_descendants(results, storage_item, STORAGE_ITEM_SCOPE_NAME);
+
+    // Ensures that ink! storage item generic type parameters have storage trait bounds, see `ensure_valid_generics` doc.
+    ensure_valid_generics(results, storage_item);
+
+    // Ensures that ink! storage item has no derives or implementations that conflict with the `derive` argument,
+    // see `ensure_no_conflicting_derives` doc.
+    ensure_no_conflicting_derives(results, storage_item);
 }
 
 /// Ensures that ink! storage item is an `adt` (i.e `enum`, `struct` or `union`) item.
@@ -44,7 +71,270 @@ fn ensure_adt(storage_item: &StorageItem) -> Option<Diagnostic> {
         ),
         range: storage_item.syntax().text_range(),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidItemKind),
         quickfixes: Some(vec![Action::remove_attribute(storage_item.ink_attr())]),
+        related_information: None,
+    })
+}
+
+/// Ensures that generic type parameters of the ink! storage item are bounded by a storage trait
+/// (e.g. `Packed` or `StorageKey`) when storage traits are auto derived (i.e. `derive = true`, the default).
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/storage_item/mod.rs#L83-L97>.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/macro/src/lib.rs#L673-L770>.
+fn ensure_valid_generics(results: &mut Vec<Diagnostic>, storage_item: &StorageItem) {
+    if !is_derive_enabled(storage_item) {
+        return;
+    }
+    let Some(adt) = storage_item.adt() else {
+        return;
+    };
+    let Some(generic_param_list) = adt.generic_param_list() else {
+        return;
+    };
+    let where_clause = adt.where_clause();
+    for type_param in generic_param_list
+        .generic_params()
+        .filter_map(|param| match param {
+            ast::GenericParam::TypeParam(type_param) => Some(type_param),
+            _ => None,
+        })
+    {
+        let Some(name) = type_param.name() else {
+            continue;
+        };
+        let name = name.to_string();
+        let where_bound_lists = where_clause
+            .iter()
+            .flat_map(ast::WhereClause::predicates)
+            .filter(|pred| {
+                pred.ty()
+                    .map_or(false, |ty| ty.syntax().to_string() == name)
+            })
+            .filter_map(|pred| pred.type_bound_list());
+        let has_storage_bound = type_param
+            .type_bound_list()
+            .into_iter()
+            .chain(where_bound_lists)
+            .flat_map(|type_bound_list| type_bound_list.bounds())
+            .any(|type_bound| {
+                type_bound
+                    .ty()
+                    .as_ref()
+                    .and_then(trait_name)
+                    .map_or(false, |trait_name| {
+                        STORAGE_BOUNDS.contains(&trait_name.as_str())
+                    })
+            });
+        if !has_storage_bound {
+            // Adds `Packed` bound after the last bound (if any) or the name of the type parameter.
+            let (insert_offset, insert_text) = match type_param.type_bound_list() {
+                Some(type_bound_list) => (
+                    type_bound_list.syntax().text_range().end(),
+                    " + ink::storage::traits::Packed",
+                ),
+                None => (
+                    type_param
+                        .name()
+                        .map_or(type_param.syntax().text_range(), |name| {
+                            name.syntax().text_range()
+                        })
+                        .end(),
+                    ": ink::storage::traits::Packed",
+                ),
+            };
+            let range = type_param.syntax().text_range();
+            results.push(Diagnostic {
+                message: format!(
+                    "Generic type parameter `{name}` of an ink! storage item must be bounded by a storage trait \
+                    (e.g. `ink::storage::traits::Packed`) when `derive` is enabled."
+                ),
+                range,
+                severity: Severity::Error,
+                code: Some(DiagnosticCode::UnsupportedGenerics),
+                quickfixes: Some(vec![Action {
+                    label: format!("Add `ink::storage::traits::Packed` bound to `{name}`."),
+                    kind: ActionKind::QuickFix,
+                    range,
+                    edits: vec![TextEdit::insert(insert_text.to_string(), insert_offset)],
+                    document_edits: Vec::new(),
+                    is_preferred: true,
+                    priority: 0,
+                }]),
+                related_information: None,
+            });
+        }
+    }
+}
+
+/// Ensures that the ink! storage item has no derives or manual implementations of storage traits
+/// that are auto derived (i.e. `derive = true`, the default).
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/storage_item/config.rs#L36-L59>.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/codegen/src/generator/storage_item.rs#L68-L91>.
+fn ensure_no_conflicting_derives(results: &mut Vec<Diagnostic>, storage_item: &StorageItem) {
+    if !is_derive_enabled(storage_item) {
+        return;
+    }
+    let Some(adt) = storage_item.adt() else {
+        return;
+    };
+    let Some(name) = adt.name() else {
+        return;
+    };
+    let name = name.to_string();
+    let conflict = |range, message: String| Diagnostic {
+        message,
+        range,
+        severity: Severity::Error,
+        code: Some(DiagnosticCode::ConflictingAttribute),
+        quickfixes: disable_derive_quickfix(storage_item).map(|action| vec![action]),
+        related_information: None,
+    };
+
+    // Derive attributes (including `cfg_attr` derives).
+    for attr in adt.attrs() {
+        for token in derived_trait_tokens(&attr) {
+            if DERIVED_STORAGE_TRAITS.contains(&token.text()) {
+                results.push(conflict(
+                    token.text_range(),
+                    format!(
+                        "`{}` is already derived by `{}` (i.e. unless `derive = false`).",
+                        token.text(),
+                        storage_item.ink_attr().syntax()
+                    ),
+                ));
+            }
+        }
+    }
+
+    // Manual trait implementations.
+    let Some(root) = storage_item.syntax().ancestors().last() else {
+        return;
+    };
+    for impl_item in root.descendants().filter_map(ast::Impl::cast) {
+        let Some(impl_trait_name) = impl_item.trait_().as_ref().and_then(trait_name) else {
+            continue;
+        };
+        let is_self_ty = impl_item
+            .self_ty()
+            .as_ref()
+            .and_then(trait_name)
+            .map_or(false, |self_ty_name| self_ty_name == name);
+        if is_self_ty && GENERATED_STORAGE_TRAITS.contains(&impl_trait_name.as_str()) {
+            let range =
+                analysis_utils::ast_item_declaration_range(&ast::Item::Impl(impl_item.clone()))
+                    .unwrap_or(impl_item.syntax().text_range());
+            results.push(conflict(
+                range,
+                format!(
+                    "`{impl_trait_name}` is already implemented by `{}` for `{name}` (i.e. unless `derive = false`).",
+                    storage_item.ink_attr().syntax()
+                ),
+            ));
+        }
+    }
+}
+
+/// Returns true if storage traits are auto derived for the ink! storage item (i.e. unless `derive = false`).
+fn is_derive_enabled(storage_item: &StorageItem) -> bool {
+    storage_item
+        .derive_arg()
+        .map_or(true, |arg| arg.as_bool() != Ok(false))
+}
+
+/// Returns the name (i.e. the last path segment) of a trait or type path (if any).
+fn trait_name(ty: &ast::Type) -> Option<String> {
+    match ty {
+        ast::Type::PathType(path_type) => path_type
+            .path()?
+            .segment()?
+            .name_ref()
+            .map(|name_ref| name_ref.to_string()),
+        _ => None,
+    }
+}
+
+/// Returns the name tokens (i.e. the last path segment) of traits derived by the attribute
+/// (including derives in `cfg_attr` attributes).
+fn derived_trait_tokens(attr: &ast::Attr) -> Vec<SyntaxToken> {
+    let non_trivia_token =
+        |token: Option<SyntaxToken>, next: fn(&SyntaxToken) -> Option<SyntaxToken>| {
+            std::iter::successors(token, next).find(|token| !token.kind().is_trivia())
+        };
+    let Some(token_tree) = attr.token_tree() else {
+        return Vec::new();
+    };
+    let derive_token_trees: Vec<ast::TokenTree> =
+        match attr.path().map(|path| path.to_string()).as_deref() {
+            Some("derive") => vec![token_tree],
+            Some("cfg_attr") => token_tree
+                .syntax()
+                .children()
+                .filter_map(ast::TokenTree::cast)
+                .filter(|it| {
+                    non_trivia_token(
+                        it.l_paren_token().and_then(|l_paren| l_paren.prev_token()),
+                        SyntaxToken::prev_token,
+                    )
+                    .map_or(false, |token| {
+                        token.kind() == SyntaxKind::IDENT && token.text() == "derive"
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+    derive_token_trees
+        .iter()
+        .flat_map(|token_tree| {
+            token_tree
+                .syntax()
+                .children_with_tokens()
+                .filter_map(|elem| elem.into_token())
+        })
+        .filter(|token| {
+            token.kind() == SyntaxKind::IDENT
+                && non_trivia_token(token.next_token(), SyntaxToken::next_token).map_or(
+                    false,
+                    |next_token| {
+                        matches!(next_token.kind(), SyntaxKind::COMMA | SyntaxKind::R_PAREN)
+                    },
+                )
+        })
+        .collect()
+}
+
+/// Returns a quickfix for disabling auto deriving of storage traits (i.e. `derive = false`).
+fn disable_derive_quickfix(storage_item: &StorageItem) -> Option<Action> {
+    let ink_attr = storage_item.ink_attr();
+    let edit = match storage_item.derive_arg() {
+        Some(arg) => TextEdit::replace("derive = false".to_string(), arg.text_range()),
+        None => {
+            let (insert_offset, prefix, suffix) =
+                analysis_utils::ink_arg_insert_offset_and_affixes(
+                    ink_attr,
+                    Some(InkArgKind::Derive),
+                )?;
+            TextEdit::insert(
+                format!(
+                    "{}derive = false{}",
+                    prefix.unwrap_or_default(),
+                    suffix.unwrap_or_default()
+                ),
+                insert_offset,
+            )
+        }
+    };
+    Some(Action {
+        label: "Set `derive = false`.".to_string(),
+        kind: ActionKind::QuickFix,
+        range: ink_attr.syntax().text_range(),
+        edits: vec![edit],
+        document_edits: Vec::new(),
+        is_preferred: false,
+        priority: 0,
     })
 }
 
@@ -208,6 +498,265 @@ mod tests {
         }
     }
 
+    #[test]
+    fn valid_generics_works() {
+        for code in [
+            quote! {
+                #[ink::storage_item]
+                struct MyStorageItem {
+                }
+            },
+            quote! {
+                #[ink::storage_item]
+                struct MyStorageItem<KEY: StorageKey = AutoKey> {
+                }
+            },
+            quote! {
+                #[ink::storage_item]
+                struct MyStorageItem<T: ink::storage::traits::Packed> {
+                    a: T,
+                }
+            },
+            quote! {
+                #[ink::storage_item]
+                struct MyStorageItem<T>
+                where
+                    T: Default,
+                    T: Packed,
+                {
+                    a: T,
+                }
+            },
+            quote! {
+                #[ink::storage_item]
+                struct MyStorageItem<'a, T: Default + Packed, const N: usize> {
+                    a: &'a [T; N],
+                }
+            },
+            // Generics aren't checked when storage traits aren't auto derived.
+            quote! {
+                #[ink::storage_item(derive = false)]
+                struct MyStorageItem<T> {
+                    a: T,
+                }
+            },
+        ] {
+            let code = quote_as_pretty_string! { #code };
+            let storage_item = parse_first_storage_item(&code);
+
+            let mut results = Vec::new();
+            ensure_valid_generics(&mut results, &storage_item);
+            assert!(results.is_empty(), "storage item: {code}");
+        }
+    }
+
+    #[test]
+    fn invalid_generics_fails() {
+        for (code, expected_quickfixes) in [
+            (
+                quote! {
+                    struct MyStorageItem<T> {
+                        a: T,
+                    }
+                },
+                vec![TestResultAction {
+                    label: "Add `ink::storage::traits::Packed` bound",
+                    edits: vec![TestResultTextRange {
+                        text: ": ink::storage::traits::Packed",
+                        start_pat: Some("<T"),
+                        end_pat: Some("<T"),
+                    }],
+                }],
+            ),
+            (
+                quote! {
+                    struct MyStorageItem<T: Default> {
+                        a: T,
+                    }
+                },
+                vec![TestResultAction {
+                    label: "Add `ink::storage::traits::Packed` bound",
+                    edits: vec![TestResultTextRange {
+                        text: " + ink::storage::traits::Packed",
+                        start_pat: Some("T: Default"),
+                        end_pat: Some("T: Default"),
+                    }],
+                }],
+            ),
+            (
+                quote! {
+                    struct MyStorageItem<T>
+                    where
+                        T: Default,
+                    {
+                        a: T,
+                    }
+                },
+                vec![TestResultAction {
+                    label: "Add `ink::storage::traits::Packed` bound",
+                    edits: vec![TestResultTextRange {
+                        text: ": ink::storage::traits::Packed",
+                        start_pat: Some("<T"),
+                        end_pat: Some("<T"),
+                    }],
+                }],
+            ),
+        ] {
+            let code = quote_as_pretty_string! {
+                #[ink::storage_item]
+                #code
+            };
+            let storage_item = parse_first_storage_item(&code);
+
+            let mut results = Vec::new();
+            ensure_valid_generics(&mut results, &storage_item);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "storage item: {code}");
+            assert_eq!(results[0].severity, Severity::Error, "storage item: {code}");
+            // Verifies quickfixes.
+            verify_actions(
+                &code,
+                results[0].quickfixes.as_ref().unwrap(),
+                &expected_quickfixes,
+            );
+        }
+    }
+
+    #[test]
+    fn no_conflicting_derives_works() {
+        for code in [
+            quote! {
+                #[ink::storage_item]
+                #[derive(Default, Debug)]
+                struct MyStorageItem {
+                }
+            },
+            quote! {
+                #[ink::storage_item]
+                #[cfg_attr(
+                    feature = "std",
+                    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+                )]
+                struct MyStorageItem {
+                }
+            },
+            quote! {
+                #[ink::storage_item]
+                struct MyStorageItem {
+                }
+
+                impl Storable for MyOtherStorageItem {
+                }
+            },
+            // Storage traits can be derived or implemented when they aren't auto derived.
+            quote! {
+                #[ink::storage_item(derive = false)]
+                #[derive(Storable, StorableHint, StorageKey)]
+                struct MyStorageItem {
+                }
+
+                impl StorageLayout for MyStorageItem {
+                }
+            },
+        ] {
+            let code = quote_as_pretty_string! { #code };
+            let storage_item = parse_first_storage_item(&code);
+
+            let mut results = Vec::new();
+            ensure_no_conflicting_derives(&mut results, &storage_item);
+            assert!(results.is_empty(), "storage item: {code}");
+        }
+    }
+
+    #[test]
+    fn conflicting_derives_fails() {
+        for (code, expected_quickfixes) in [
+            (
+                quote! {
+                    #[ink::storage_item]
+                    #[derive(Default, Storable)]
+                    struct MyStorageItem {
+                    }
+                },
+                vec![TestResultAction {
+                    label: "`derive = false`",
+                    edits: vec![TestResultTextRange {
+                        text: "(derive = false)",
+                        start_pat: Some("#[ink::storage_item"),
+                        end_pat: Some("#[ink::storage_item"),
+                    }],
+                }],
+            ),
+            (
+                quote! {
+                    #[ink::storage_item(derive = true)]
+                    #[derive(ink::storage::traits::StorableHint)]
+                    struct MyStorageItem {
+                    }
+                },
+                vec![TestResultAction {
+                    label: "`derive = false`",
+                    edits: vec![TestResultTextRange {
+                        text: "derive = false",
+                        start_pat: Some("<-derive = true"),
+                        end_pat: Some("derive = true"),
+                    }],
+                }],
+            ),
+            (
+                quote! {
+                    #[ink::storage_item]
+                    #[cfg_attr(feature = "std", derive(StorageKey))]
+                    struct MyStorageItem {
+                    }
+                },
+                vec![TestResultAction {
+                    label: "`derive = false`",
+                    edits: vec![TestResultTextRange {
+                        text: "(derive = false)",
+                        start_pat: Some("#[ink::storage_item"),
+                        end_pat: Some("#[ink::storage_item"),
+                    }],
+                }],
+            ),
+            (
+                quote! {
+                    #[ink::storage_item]
+                    struct MyStorageItem {
+                    }
+
+                    impl ink::storage::traits::StorageLayout for MyStorageItem {
+                    }
+                },
+                vec![TestResultAction {
+                    label: "`derive = false`",
+                    edits: vec![TestResultTextRange {
+                        text: "(derive = false)",
+                        start_pat: Some("#[ink::storage_item"),
+                        end_pat: Some("#[ink::storage_item"),
+                    }],
+                }],
+            ),
+        ] {
+            let code = quote_as_pretty_string! { #code };
+            let storage_item = parse_first_storage_item(&code);
+
+            let mut results = Vec::new();
+            ensure_no_conflicting_derives(&mut results, &storage_item);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "storage item: {code}");
+            assert_eq!(results[0].severity, Severity::Error, "storage item: {code}");
+            // Verifies quickfixes.
+            verify_actions(
+                &code,
+                results[0].quickfixes.as_ref().unwrap(),
+                &expected_quickfixes,
+            );
+        }
+    }
+
     #[test]
     // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/macro/src/lib.rs#L673-L770>.
     fn compound_diagnostic_works() {
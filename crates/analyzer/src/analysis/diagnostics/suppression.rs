@@ -0,0 +1,336 @@
+//! Suppression comments for diagnostics (i.e. `// ink-analyzer: ignore(<code>)`).
+//!
+//! A suppression comment at the end of a line suppresses matching diagnostics that start on that line,
+//! while a suppression comment on its own line suppresses matching diagnostics for the next item
+//! (or the next statement, expression e.t.c if it's not followed by an item).
+//!
+//! Codes can be either the full code (e.g. `E0017-missing-storage`),
+//! only the number part (e.g. `E0017`) or only the name (e.g. `missing-storage`),
+//! and multiple codes are separated by commas (e.g. `// ink-analyzer: ignore(E0017, missing-message)`).
+
+use ink_analyzer_ir::syntax::{
+    Direction, NodeOrToken, SyntaxKind, SyntaxToken, TextRange, TextSize,
+};
+use ink_analyzer_ir::{FromSyntax, InkFile};
+use std::collections::HashSet;
+
+use super::{Diagnostic, DiagnosticCode, Severity};
+use crate::analysis::text_edit::TextEdit;
+use crate::{Action, ActionKind};
+
+/// Prefix of suppression comments.
+const PREFIX: &str = "ink-analyzer:";
+
+/// A suppression comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// Text range of the comment.
+    range: TextRange,
+    /// Text range of the line or item to which the suppression applies (if any).
+    scope: Option<TextRange>,
+    /// Suppressed codes (i.e. `None` for unknown codes), their text and text ranges.
+    codes: Vec<(Option<DiagnosticCode>, String, TextRange)>,
+}
+
+impl Suppression {
+    /// Returns the index of the suppressed code that matches the diagnostic (if any).
+    pub fn matches(&self, diagnostic: &Diagnostic) -> Option<usize> {
+        let code = diagnostic.code?;
+        if !self.scope?.contains(diagnostic.range.start()) {
+            return None;
+        }
+        self.codes
+            .iter()
+            .position(|(suppressed_code, ..)| *suppressed_code == Some(code))
+    }
+}
+
+/// Returns all suppression comments in the file.
+pub fn suppressions(file: &InkFile) -> Vec<Suppression> {
+    file.syntax()
+        .descendants_with_tokens()
+        .filter_map(|elem| {
+            let token = elem.into_token()?;
+            (token.kind() == SyntaxKind::COMMENT).then_some(token)
+        })
+        .filter_map(|token| {
+            let codes = parse_codes(&token)?;
+            Some(Suppression {
+                range: token.text_range(),
+                scope: scope(&token),
+                codes,
+            })
+        })
+        .collect()
+}
+
+/// Ensures that there are no unused suppression comments
+/// (i.e. suppressed codes that are either unknown or don't match any diagnostics).
+///
+/// `used` contains the indices of suppressions and their suppressed codes that matched a diagnostic.
+pub fn unused_suppressions(
+    results: &mut Vec<Diagnostic>,
+    suppressions: &[Suppression],
+    used: &HashSet<(usize, usize)>,
+) {
+    for (idx, suppression) in suppressions.iter().enumerate() {
+        for (code_idx, (code, text, range)) in suppression.codes.iter().enumerate() {
+            if used.contains(&(idx, code_idx)) {
+                continue;
+            }
+
+            // Removes the entire comment if it only suppresses one code,
+            // otherwise only removes the code (and its delimiter).
+            let (label, remove_range) = if suppression.codes.len() == 1 {
+                ("Remove suppression comment.".to_string(), suppression.range)
+            } else {
+                (
+                    format!("Remove `{text}` from suppression comment."),
+                    match suppression.codes.get(code_idx + 1) {
+                        Some((.., next_range)) => TextRange::new(range.start(), next_range.start()),
+                        None => {
+                            TextRange::new(suppression.codes[code_idx - 1].2.end(), range.end())
+                        }
+                    },
+                )
+            };
+            results.push(Diagnostic {
+                message: match code {
+                    Some(code) => {
+                        format!("Unused suppression: there are no `{code}` diagnostics to ignore.")
+                    }
+                    None => format!("Unknown diagnostic code in suppression comment: `{text}`."),
+                },
+                range: *range,
+                severity: Severity::Warning,
+                code: Some(DiagnosticCode::UnusedSuppression),
+                quickfixes: Some(vec![Action {
+                    label,
+                    kind: ActionKind::QuickFix,
+                    range: remove_range,
+                    edits: vec![TextEdit::delete(remove_range)],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
+                }]),
+                related_information: None,
+            });
+        }
+    }
+}
+
+/// Returns the suppressed codes for a suppression comment (if the token is a suppression comment).
+fn parse_codes(token: &SyntaxToken) -> Option<Vec<(Option<DiagnosticCode>, String, TextRange)>> {
+    let text = token.text();
+    // Ignores doc comments.
+    if text.starts_with("///") || text.starts_with("//!") {
+        return None;
+    }
+    let args = text
+        .strip_prefix("//")?
+        .trim_start()
+        .strip_prefix(PREFIX)?
+        .trim_start()
+        .strip_prefix("ignore(")?;
+    let args_end = args.find(')')?;
+
+    let mut codes = Vec::new();
+    let mut offset = token.text_range().start() + TextSize::from((text.len() - args.len()) as u32);
+    for part in args[..args_end].split(',') {
+        let code_text = part.trim();
+        if !code_text.is_empty() {
+            let start = offset + TextSize::from((part.len() - part.trim_start().len()) as u32);
+            codes.push((
+                DiagnosticCode::parse(code_text),
+                code_text.to_string(),
+                TextRange::at(start, TextSize::from(code_text.len() as u32)),
+            ));
+        }
+        offset += TextSize::from(part.len() as u32 + 1);
+    }
+    (!codes.is_empty()).then_some(codes)
+}
+
+/// Returns the text range of the line (for comments at the end of a line)
+/// or item (for comments on their own line) to which a suppression comment applies (if any).
+fn scope(token: &SyntaxToken) -> Option<TextRange> {
+    let is_own_line = token.prev_token().map_or(true, |prev_token| {
+        prev_token.kind() == SyntaxKind::WHITESPACE && prev_token.text().contains('\n')
+    });
+    if !is_own_line {
+        // Line scope (i.e. from the start of the line to the end of the comment).
+        let mut line_start = token.text_range().start();
+        let mut prev_token = token.prev_token();
+        while let Some(current_token) = prev_token {
+            if let Some(newline_offset) = current_token.text().rfind('\n') {
+                line_start =
+                    current_token.text_range().start() + TextSize::from(newline_offset as u32 + 1);
+                break;
+            }
+            line_start = current_token.text_range().start();
+            prev_token = current_token.prev_token();
+        }
+        return Some(TextRange::new(line_start, token.text_range().end()));
+    }
+
+    // Item scope for comments that are part of an item's leading trivia and attributes.
+    let parent = token.parent()?;
+    let is_leading_trivia = parent.kind() != SyntaxKind::SOURCE_FILE
+        && token
+            .siblings_with_tokens(Direction::Prev)
+            .skip(1)
+            .all(|elem| elem.kind().is_trivia() || elem.kind() == SyntaxKind::ATTR);
+    if is_leading_trivia {
+        return Some(parent.text_range());
+    }
+
+    // Otherwise, the next non-trivia sibling.
+    token
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .find(|elem| !elem.kind().is_trivia())
+        .map(|elem| match elem {
+            NodeOrToken::Node(node) => node.text_range(),
+            NodeOrToken::Token(token) => token.text_range(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiagnosticsConfig, MacroRegistry, Version};
+
+    fn codes(code: &str) -> Vec<(Option<DiagnosticCode>, Severity)> {
+        let file = InkFile::parse(code);
+        super::super::diagnostics(
+            &file,
+            Version::default(),
+            &DiagnosticsConfig::default(),
+            &MacroRegistry::default(),
+        )
+        .into_iter()
+        .map(|diagnostic| (diagnostic.code, diagnostic.severity))
+        .collect()
+    }
+
+    #[test]
+    fn suppression_works() {
+        for code in [
+            // Item scope.
+            r#"
+                // ink-analyzer: ignore(missing-storage)
+                #[ink::contract]
+                mod my_contract {
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {}
+
+                        #[ink(message)]
+                        pub fn my_message(&self) {}
+                    }
+                }
+            "#,
+            // Item scope (after attributes).
+            r#"
+                #[ink::contract]
+                // ink-analyzer: ignore(E0017)
+                mod my_contract {
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {}
+
+                        #[ink(message)]
+                        pub fn my_message(&self) {}
+                    }
+                }
+            "#,
+            // Line scope.
+            r#"
+                #[ink::contract]
+                mod my_contract { // ink-analyzer: ignore(E0017-missing-storage)
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {}
+
+                        #[ink(message)]
+                        pub fn my_message(&self) {}
+                    }
+                }
+            "#,
+            // Multiple codes.
+            r#"
+                // ink-analyzer: ignore(missing-storage, missing-message)
+                #[ink::contract]
+                mod my_contract {
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {}
+                    }
+                }
+            "#,
+        ] {
+            assert!(codes(code).is_empty(), "code: {code}");
+        }
+    }
+
+    #[test]
+    fn unused_suppression_fails() {
+        for (code, expected_results) in [
+            // Wrong scope.
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        // ink-analyzer: ignore(missing-storage)
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn new() -> Self {}
+
+                            #[ink(message)]
+                            pub fn my_message(&self) {}
+                        }
+                    }
+                "#,
+                vec![
+                    (Some(DiagnosticCode::MissingStorage), Severity::Error),
+                    (Some(DiagnosticCode::UnusedSuppression), Severity::Warning),
+                ],
+            ),
+            // Unknown code.
+            (
+                r#"
+                    // ink-analyzer: ignore(missing-storage, unknown-code)
+                    #[ink::contract]
+                    mod my_contract {
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn new() -> Self {}
+
+                            #[ink(message)]
+                            pub fn my_message(&self) {}
+                        }
+                    }
+                "#,
+                vec![(Some(DiagnosticCode::UnusedSuppression), Severity::Warning)],
+            ),
+        ] {
+            assert_eq!(codes(code), expected_results, "code: {code}");
+        }
+    }
+
+    #[test]
+    fn unused_suppression_quickfix_works() {
+        let code = "// ink-analyzer: ignore(missing-storage, E0020)\nfn f() {}";
+        let file = InkFile::parse(code);
+        let suppressions = suppressions(&file);
+        let mut results = Vec::new();
+        unused_suppressions(&mut results, &suppressions, &HashSet::from([(0, 1)]));
+
+        // Removes the unused code and its delimiter.
+        assert_eq!(results.len(), 1);
+        let quickfixes = results[0].quickfixes.as_ref().unwrap();
+        let range = quickfixes[0].edits[0].range;
+        assert_eq!(&code[range], "missing-storage, ");
+        assert_eq!(&code[results[0].range], "missing-storage");
+    }
+}
@@ -3,7 +3,7 @@
 use ink_analyzer_ir::{FromInkAttribute, FromSyntax, Topic};
 
 use super::utils;
-use crate::{Action, Diagnostic, Severity};
+use crate::{Action, Diagnostic, DiagnosticCode, Severity};
 
 const TOPIC_SCOPE_NAME: &str = "topic";
 
@@ -37,7 +37,9 @@ fn ensure_struct_field(topic: &Topic) -> Option<Diagnostic> {
         ),
         range: topic.syntax().text_range(),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidItemKind),
         quickfixes: Some(vec![Action::remove_attribute(ink_attr)]),
+        related_information: None,
     })
 }
 
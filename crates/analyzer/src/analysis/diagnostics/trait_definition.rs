@@ -10,7 +10,7 @@ use super::{message, utils};
 use crate::analysis::actions::entity as entity_actions;
 use crate::analysis::text_edit::TextEdit;
 use crate::analysis::utils as analysis_utils;
-use crate::{Action, ActionKind, Diagnostic, Severity};
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, Severity};
 
 const TRAIT_DEFINITION_SCOPE_NAME: &str = "trait definition";
 
@@ -92,6 +92,7 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, trait_item: &ast:
                     message: "All ink! trait definition methods must be ink! messages.".to_string(),
                     range,
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::InvalidTraitItem),
                     quickfixes: Some(vec![Action {
                         label: "Add ink! message attribute.".to_string(),
                         kind: ActionKind::QuickFix,
@@ -116,7 +117,11 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, trait_item: &ast:
                             }),
                         )
                         .collect(),
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     }]),
+                    related_information: None,
                 });
             }
 
@@ -139,12 +144,17 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, trait_item: &ast:
                                     .to_string(),
                                 range: arg.text_range(),
                                 severity: Severity::Error,
+                                code: Some(DiagnosticCode::InvalidSelector),
                                 quickfixes: Some(vec![Action {
                                     label: "Remove wildcard selector.".to_string(),
                                     kind: ActionKind::QuickFix,
                                     range,
                                     edits: vec![TextEdit::delete(range)],
+                                    document_edits: Vec::new(),
+                                    is_preferred: false,
+                                    priority: 0,
                                 }]),
+                                related_information: None,
                             });
                         }
                     }
@@ -157,12 +167,17 @@ fn ensure_trait_item_invariants(results: &mut Vec<Diagnostic>, trait_item: &ast:
                     .to_string(),
                 range: type_alias.syntax().text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::InvalidTraitItem),
                 quickfixes: Some(vec![Action {
                     label: "Remove associated type.".to_string(),
                     kind: ActionKind::QuickFix,
                     range: type_alias.syntax().text_range(),
                     edits: vec![TextEdit::delete(type_alias.syntax().text_range())],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
                 }]),
+                related_information: None,
             });
         },
     );
@@ -184,12 +199,19 @@ fn ensure_contains_message(trait_definition: &TraitDefinition) -> Option<Diagnos
                 .to_string(),
             range,
             severity: Severity::Error,
+            code: Some(DiagnosticCode::MissingMessage),
             quickfixes: entity_actions::add_message_to_trait_definition(
                 trait_definition,
                 ActionKind::QuickFix,
                 None,
             )
-            .map(|action| vec![action]),
+            .map(|action| {
+                vec![Action {
+                    is_preferred: true,
+                    ..action
+                }]
+            }),
+            related_information: None,
         },
     )
 }
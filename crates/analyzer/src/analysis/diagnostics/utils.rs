@@ -1,10 +1,10 @@
 //! Utilities for ink! diagnostics.
 
-use ink_analyzer_ir::ast::{AstNode, AstToken, HasGenericParams, HasTypeBounds, HasVisibility};
-use ink_analyzer_ir::meta::{MetaOption, MetaValue};
-use ink_analyzer_ir::syntax::{
-    SourceFile, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, TextRange,
+use ink_analyzer_ir::ast::{
+    AstNode, AstToken, HasGenericParams, HasModuleItem, HasName, HasTypeBounds, HasVisibility,
 };
+use ink_analyzer_ir::meta::MetaValue;
+use ink_analyzer_ir::syntax::{SyntaxKind, SyntaxNode, SyntaxToken, TextRange};
 use ink_analyzer_ir::{
     ast, Contract, FromInkAttribute, FromSyntax, InkArg, InkArgKind, InkArgValueKind,
     InkArgValueStringKind, InkAttribute, InkAttributeKind, InkMacroKind, IsInkEntity, IsInkFn,
@@ -13,13 +13,14 @@ use ink_analyzer_ir::{
 use itertools::Itertools;
 use std::collections::HashSet;
 
-use crate::analysis::text_edit::TextEdit;
+use crate::analysis::text_edit::{EditBuilder, TextEdit};
 use crate::analysis::utils;
-use crate::{Action, ActionKind, Diagnostic, Severity};
+use crate::{Action, ActionKind, Diagnostic, DiagnosticCode, RelatedInformation, Severity};
 
 /// Runs generic diagnostics that apply to all ink! entities.
 /// (e.g `ensure_no_unknown_ink_attributes`, `ensure_no_ink_identifiers`,
-/// `ensure_no_duplicate_attributes_and_arguments`, `ensure_valid_attribute_arguments`).
+/// `ensure_no_duplicate_attributes_and_arguments`, `ensure_valid_attribute_arguments`,
+/// `ensure_valid_keep_attr_entries`).
 pub fn run_generic_diagnostics<T: FromSyntax>(results: &mut Vec<Diagnostic>, item: &T) {
     // Ensures that no `__ink_` prefixed identifiers, see `ensure_no_ink_identifiers` doc.
     ensure_no_ink_identifiers(results, item);
@@ -38,6 +39,10 @@ pub fn run_generic_diagnostics<T: FromSyntax>(results: &mut Vec<Diagnostic>, ite
     // See `ensure_valid_attribute_arguments` doc.
     for attr in item.tree().ink_attrs_in_scope() {
         ensure_valid_attribute_arguments(results, &attr);
+
+        // Ensures that `keep_attr` entries are valid attribute paths without duplicates,
+        // see `ensure_valid_keep_attr_entries` doc.
+        ensure_valid_keep_attr_entries(results, &attr);
     }
 
     // Iterates over all ink! parent nodes in scope.
@@ -76,6 +81,7 @@ fn ensure_no_ink_identifiers<T: FromSyntax>(results: &mut Vec<Diagnostic>, item:
                     message: format!("Invalid identifier starting with __ink_: {}", ident.text()),
                     range: ident.syntax().text_range(),
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::InvalidIdentifier),
                     quickfixes: (!suggested_name.is_empty()).then_some(vec![Action {
                         label: format!("Rename identifier to `{suggested_name}`"),
                         kind: ActionKind::QuickFix,
@@ -85,7 +91,11 @@ fn ensure_no_ink_identifiers<T: FromSyntax>(results: &mut Vec<Diagnostic>, item:
                             ident.syntax().text_range(),
                             Some(format!("${{1:{suggested_name}}}")),
                         )],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     }]),
+                    related_information: None,
                 });
             }
         }
@@ -121,7 +131,9 @@ fn ensure_no_unknown_ink_attributes(results: &mut Vec<Diagnostic>, attrs: &[InkA
                     .unwrap_or(attr.syntax().text_range()),
                 // warning because it's possible ink! analyzer is just outdated.
                 severity: Severity::Warning,
+                code: Some(DiagnosticCode::UnknownAttribute),
                 quickfixes: Some(vec![Action::remove_attribute(attr)]),
+                related_information: None,
             });
         }
     }
@@ -162,6 +174,7 @@ fn ensure_valid_attribute_arguments(results: &mut Vec<Diagnostic>, attr: &InkAtt
                         // warning because it's possible ink! analyzer is just outdated.
                         Severity::Warning
                     },
+                    code: Some(DiagnosticCode::InvalidArgument),
                     quickfixes: Some(vec![Action {
                         label: format!(
                             "Remove unknown ink! attribute argument: '{arg_name_text}'."
@@ -169,7 +182,11 @@ fn ensure_valid_attribute_arguments(results: &mut Vec<Diagnostic>, attr: &InkAtt
                         kind: ActionKind::QuickFix,
                         range,
                         edits: vec![TextEdit::delete(range)],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     }]),
+                    related_information: None,
                 });
             }
             arg_kind => {
@@ -184,70 +201,106 @@ fn ensure_valid_attribute_arguments(results: &mut Vec<Diagnostic>, attr: &InkAtt
                                 ),
                                 range: arg.text_range(),
                                 severity: Severity::Error,
+                                code: Some(DiagnosticCode::InvalidArgument),
                                 quickfixes: Some(vec![Action {
                                     label: format!("Remove `{arg_name_text}` argument value"),
                                     kind: ActionKind::QuickFix,
                                     range: arg.text_range(),
                                     edits: vec![TextEdit::replace(arg_name_text, arg.text_range())],
+                                    document_edits: Vec::new(),
+                                    is_preferred: false,
+                                    priority: 0,
                                 }]),
+                                related_information: None,
                             });
                         }
                     }
                     // Arguments that should have an integer (`u32` to be specific) value.
                     InkArgValueKind::U32 | InkArgValueKind::U32OrWildcard => {
                         let can_be_wildcard = arg_value_type == InkArgValueKind::U32OrWildcard;
-                        if !ensure_valid_attribute_arg_value(
-                            arg,
-                            |meta_value| {
-                                // Ensures that the meta value is either a decimal or hex encoded `u32`.
-                                // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L903-L910>.
-                                // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L938-L943>.
-                                meta_value.as_u32().is_some()
-                                        // A wildcard/underscore (`_`) is also valid value for selectors.
-                                        // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L884-L900>.
-                                        || (can_be_wildcard
-                                        && meta_value.is_wildcard())
-                            },
-                            |_| false,
-                            false,
-                        ) {
+                        // Ensures that the meta value is either a decimal or hex encoded `u32`.
+                        // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L903-L910>.
+                        // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L938-L943>.
+                        let is_valid = arg.as_u32().is_ok()
+                            // A wildcard/underscore (`_`) is also valid value for selectors.
+                            // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L884-L900>.
+                            || (can_be_wildcard && arg.value().map_or(false, MetaValue::is_wildcard));
+                        if !is_valid {
+                            // Negative, overflowing, suffixed and malformed hex integer literals
+                            // have more specific diagnostics and quickfixes (if possible).
+                            let literal_issue = u32_literal_issue(&arg.meta().value().to_string());
                             results.push(Diagnostic {
-                                message: format!(
-                                    "`{arg_name_text}` argument should have an `integer` (`u32`) {} value.",
-                                    if can_be_wildcard {
-                                        "or wildcard/underscore (`_`)"
-                                    } else {
-                                        ""
-                                    }
-                                ),
+                                message: match literal_issue {
+                                    Some((issue, _)) => format!(
+                                        "`{arg_name_text}` argument value {}.",
+                                        match issue {
+                                            U32LiteralIssue::Negative => "can't be negative",
+                                            U32LiteralIssue::Overflow => "overflows `u32`",
+                                            U32LiteralIssue::Suffixed =>
+                                                "shouldn't have a type suffix",
+                                            U32LiteralIssue::MalformedHex =>
+                                                "is a malformed hexadecimal integer",
+                                        }
+                                    ),
+                                    None => format!(
+                                        "`{arg_name_text}` argument should have an `integer` (`u32`) {} value.",
+                                        if can_be_wildcard {
+                                            "or wildcard/underscore (`_`)"
+                                        } else {
+                                            ""
+                                        }
+                                    ),
+                                },
                                 range: arg.text_range(),
                                 severity: Severity::Error,
-                                quickfixes: Some(vec![Action {
-                                    label: format!("Add `{arg_name_text}` argument value"),
-                                    kind: ActionKind::QuickFix,
-                                    range: arg.text_range(),
-                                    edits: vec![TextEdit::replace_with_snippet(
-                                        format!("{arg_name_text} = 1"),
-                                        arg.text_range(),
-                                        Some(format!("{arg_name_text} = ${{1:1}}")),
-                                    )],
+                                code: Some(DiagnosticCode::InvalidArgument),
+                                quickfixes: Some(vec![match literal_issue {
+                                    Some((issue, Some(value))) => Action {
+                                        label: format!(
+                                            "{} `{arg_name_text}` argument value to `{value}`.",
+                                            if issue == U32LiteralIssue::Overflow {
+                                                "Truncate"
+                                            } else {
+                                                "Change"
+                                            }
+                                        ),
+                                        kind: ActionKind::QuickFix,
+                                        range: arg.text_range(),
+                                        edits: vec![TextEdit::replace(
+                                            format!("{arg_name_text} = {value}"),
+                                            arg.text_range(),
+                                        )],
+                                        document_edits: Vec::new(),
+                                        is_preferred: false,
+                                        priority: 0,
+                                    },
+                                    _ => Action {
+                                        label: if arg.value().is_some() {
+                                            format!("Replace `{arg_name_text}` argument value.")
+                                        } else {
+                                            format!("Add `{arg_name_text}` argument value")
+                                        },
+                                        kind: ActionKind::QuickFix,
+                                        range: arg.text_range(),
+                                        edits: vec![TextEdit::replace_with_snippet(
+                                            format!("{arg_name_text} = 1"),
+                                            arg.text_range(),
+                                            Some(format!("{arg_name_text} = ${{1:1}}")),
+                                        )],
+                                        document_edits: Vec::new(),
+                                        is_preferred: false,
+                                        priority: 0,
+                                    },
                                 }]),
+                                related_information: None,
                             });
                         }
                     }
                     // Arguments that should have a string value.
                     InkArgValueKind::String(str_kind) => {
-                        if !ensure_valid_attribute_arg_value(
-                            arg,
-                            |meta_value| {
-                                meta_value.as_string().is_some()
-                                    // For namespace arguments, ensure the meta value is a valid Rust identifier.
-                                    // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L922-L926>.
-                                    && (str_kind != InkArgValueStringKind::Identifier || meta_value.as_string().and_then(|value| parse_ident(value.as_str())).is_some())
-                            },
-                            |_| false,
-                            false,
-                        ) {
+                        // For namespace arguments, the string value must also be a valid Rust identifier.
+                        // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L922-L926>.
+                        if arg.as_string().is_err() {
                             results.push(Diagnostic {
                                 message: format!(
                                     "`{arg_name_text}` argument should have a {} `string` (`&str`) value.",
@@ -259,6 +312,7 @@ fn ensure_valid_attribute_arguments(results: &mut Vec<Diagnostic>, attr: &InkAtt
                                 ),
                                 range: arg.text_range(),
                                 severity: Severity::Error,
+                                code: Some(DiagnosticCode::InvalidArgument),
                                 quickfixes: Some(vec![Action {
                                     label: format!("Add `{arg_name_text}` argument value"),
                                     kind: ActionKind::QuickFix,
@@ -282,24 +336,24 @@ fn ensure_valid_attribute_arguments(results: &mut Vec<Diagnostic>, attr: &InkAtt
                                             }
                                         )),
                                     )],
+                                    document_edits: Vec::new(),
+                                    is_preferred: false,
+                                    priority: 0,
                                 }]),
+                                related_information: None,
                             });
                         }
                     }
                     // Arguments that should have a boolean value.
                     InkArgValueKind::Bool => {
-                        if !ensure_valid_attribute_arg_value(
-                            arg,
-                            |meta_value| meta_value.as_boolean().is_some(),
-                            |_| false,
-                            false,
-                        ) {
+                        if arg.as_bool().is_err() {
                             results.push(Diagnostic {
                                 message: format!(
                                     "`{arg_name_text}` argument should have a `boolean` (`bool`) value."
                                 ),
                                 range: arg.text_range(),
                                 severity: Severity::Error,
+                                code: Some(DiagnosticCode::InvalidArgument),
                                 quickfixes: Some(vec![Action {
                                     label: format!("Add `{arg_name_text}` argument value"),
                                     kind: ActionKind::QuickFix,
@@ -309,29 +363,24 @@ fn ensure_valid_attribute_arguments(results: &mut Vec<Diagnostic>, attr: &InkAtt
                                         arg.text_range(),
                                         Some(format!("{arg_name_text} = ${{1:true}}")),
                                     )],
+                                    document_edits: Vec::new(),
+                                    is_preferred: false,
+                                    priority: 0,
                                 }]),
+                                related_information: None,
                             });
                         }
                     }
                     // Arguments that should have a path value.
                     InkArgValueKind::Path(_) => {
-                        if !ensure_valid_attribute_arg_value(
-                            arg,
-                            |meta_value| {
-                                matches!(
-                                    meta_value.kind(),
-                                    SyntaxKind::PATH | SyntaxKind::PATH_EXPR
-                                )
-                            },
-                            |_| false,
-                            false,
-                        ) {
+                        if arg.as_path().is_err() {
                             results.push(Diagnostic {
                                 message: format!(
                                     "`{arg_name_text}` argument should have a `path` (e.g `my::env::Types`) value."
                                 ),
                                 range: arg.text_range(),
                                 severity: Severity::Error,
+                                code: Some(DiagnosticCode::InvalidArgument),
                                 quickfixes: Some(vec![Action {
                                     label: format!("Add `{arg_name_text}` argument value"),
                                     kind: ActionKind::QuickFix,
@@ -341,7 +390,11 @@ fn ensure_valid_attribute_arguments(results: &mut Vec<Diagnostic>, attr: &InkAtt
                                         arg.text_range(),
                                         Some(format!("{arg_name_text} = ${{1:crate::}}")),
                                     )],
+                                    document_edits: Vec::new(),
+                                    is_preferred: false,
+                                    priority: 0,
                                 }]),
+                                related_information: None,
                             });
                         }
                     }
@@ -351,41 +404,173 @@ fn ensure_valid_attribute_arguments(results: &mut Vec<Diagnostic>, attr: &InkAtt
     }
 }
 
-/// Casts a string to an Rust identifier (`Ident`) (if possible).
-fn parse_ident(value: &str) -> Option<ast::Ident> {
-    // Parse sanitized value and find the first identifier.
-    let file = SourceFile::parse(value).tree();
+/// An issue with an integer literal value for an ink! attribute argument that takes a `u32` value
+/// (e.g. `selector` and `extension` arguments).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum U32LiteralIssue {
+    /// A negative integer (e.g. `-1`).
+    Negative,
+    /// An integer that's greater than `u32::MAX` (e.g. `0x1_0000_0000`).
+    Overflow,
+    /// An integer with a type suffix (e.g. `0x1u32`).
+    Suffixed,
+    /// A malformed hexadecimal integer (e.g. `0XA`, `0x` or `0xG`).
+    MalformedHex,
+}
 
-    // Retrieve the first `ident` in the syntax tree.
-    let ident = file
-        .syntax()
-        .descendants_with_tokens()
-        .find_map(|elem| ast::Ident::cast(elem.into_token()?))?;
+/// Returns the issue (if any) with an integer literal value for an ink! attribute argument that takes a `u32` value,
+/// and a valid replacement value (if possible) in the same format (i.e. decimal or hexadecimal).
+///
+/// Negative values are replaced with their absolute value,
+/// overflowing values are truncated to `u32` (i.e. their 32 least significant bits are kept)
+/// unless the truncated value is zero, and type suffixes are removed.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L903-L910>.
+fn u32_literal_issue(text: &str) -> Option<(U32LiteralIssue, Option<String>)> {
+    let text: String = text.chars().filter(|char| !char.is_whitespace()).collect();
+    let (is_negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text.as_str()),
+    };
+    let hex_digits = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X"));
+    let is_hex = hex_digits.is_some();
+    // Integer type suffixes (e.g. `u32`) can't be hex digits, so they're unambiguous.
+    let (digits, is_suffixed) = strip_integer_suffix(hex_digits.unwrap_or(text));
+    let digits = digits.replace('_', "");
+    // Only (possibly malformed) integer literals are checked (i.e. not other kinds of values).
+    if !is_hex && (digits.is_empty() || !digits.chars().all(|char| char.is_ascii_digit())) {
+        return None;
+    }
 
-    // Parsed identifier must be equal to the sanitized meta value.
-    (ident.text() == value).then_some(ident)
+    // Computes the value truncated to `u32` (i.e. wrapping arithmetic), and whether it overflows.
+    let radix = if is_hex { 16 } else { 10 };
+    let mut value: u32 = 0;
+    let mut is_overflow = false;
+    let mut is_malformed = digits.is_empty() || text.starts_with("0X");
+    for char in digits.chars() {
+        match char.to_digit(radix) {
+            Some(digit) => {
+                let (shifted, mul_overflow) = value.overflowing_mul(radix);
+                let (sum, add_overflow) = shifted.overflowing_add(digit);
+                value = sum;
+                is_overflow |= mul_overflow || add_overflow;
+            }
+            None => is_malformed = true,
+        }
+    }
+
+    let issue = if is_malformed {
+        U32LiteralIssue::MalformedHex
+    } else if is_negative {
+        U32LiteralIssue::Negative
+    } else if is_overflow {
+        U32LiteralIssue::Overflow
+    } else if is_suffixed {
+        U32LiteralIssue::Suffixed
+    } else {
+        return None;
+    };
+    // Malformed hex digits can't be reformatted (i.e. only the prefix can be fixed),
+    // and overflowing values that truncate to zero aren't replaced (i.e. zero is rarely the intended value).
+    let replacement = (!digits.is_empty()
+        && digits.chars().all(|char| char.is_ascii_hexdigit())
+        && !(issue == U32LiteralIssue::Overflow && value == 0))
+        .then(|| {
+            if is_hex {
+                format!("{value:#X}")
+            } else {
+                value.to_string()
+            }
+        });
+    Some((issue, replacement))
 }
 
-/// Ensures the validity of an ink! argument value using provided ok and err handlers and none outcome.
-fn ensure_valid_attribute_arg_value<F, G>(
-    arg: &InkArg,
-    ok_handler: F,
-    err_handler: G,
-    none_outcome: bool,
-) -> bool
-where
-    F: Fn(&MetaValue) -> bool,
-    G: Fn(&[SyntaxElement]) -> bool,
-{
-    match &arg.meta().value() {
-        MetaOption::Ok(meta_value) => ok_handler(meta_value),
-        MetaOption::Err(items) => err_handler(items),
-        MetaOption::None => none_outcome,
+/// Returns the text without its integer type suffix (if any) (e.g. `1` for `1u32`),
+/// and whether the text had a suffix.
+fn strip_integer_suffix(text: &str) -> (&str, bool) {
+    const SUFFIXES: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    SUFFIXES
+        .iter()
+        .find_map(|suffix| text.strip_suffix(suffix))
+        .map_or((text, false), |text| {
+            (text.strip_suffix('_').unwrap_or(text), true)
+        })
+}
+
+/// Ensures that the entries of `keep_attr` arguments are syntactically valid attribute paths
+/// (e.g. `cfg` or `rustfmt::skip`) and that there are no duplicate entries.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/utils.rs#L92-L107>.
+fn ensure_valid_keep_attr_entries(results: &mut Vec<Diagnostic>, attr: &InkAttribute) {
+    for arg in attr.args() {
+        let Some(entries) = arg.as_keep_attr_list() else {
+            continue;
+        };
+        let mut seen_entries = HashSet::new();
+        for (idx, entry) in entries.iter().enumerate() {
+            // Removes the entry and its delimiter (i.e. the preceding comma for all entries except the first,
+            // and the succeeding comma for the first entry, if there are other entries).
+            let removal_range = match (
+                idx.checked_sub(1).and_then(|prev| entries.get(prev)),
+                entries.get(idx + 1),
+            ) {
+                (Some(prev_entry), _) => {
+                    TextRange::new(prev_entry.text_range().end(), entry.text_range().end())
+                }
+                (None, Some(next_entry)) => {
+                    TextRange::new(entry.text_range().start(), next_entry.text_range().start())
+                }
+                (None, None) => entry.text_range(),
+            };
+            let (message, severity, code) = if !entry.is_valid_path() {
+                (
+                    format!(
+                        "`{}` is not a valid attribute path for a `keep_attr` argument.",
+                        entry.text()
+                    ),
+                    Severity::Error,
+                    DiagnosticCode::InvalidArgument,
+                )
+            } else if !seen_entries.insert(entry.text()) {
+                (
+                    format!(
+                        "Duplicate `{}` entry in `keep_attr` argument.",
+                        entry.text()
+                    ),
+                    Severity::Warning,
+                    DiagnosticCode::DuplicateAttribute,
+                )
+            } else {
+                continue;
+            };
+            results.push(Diagnostic {
+                message,
+                range: entry.text_range(),
+                severity,
+                code: Some(code),
+                quickfixes: Some(vec![Action {
+                    label: format!("Remove `{}` entry.", entry.text()),
+                    kind: ActionKind::QuickFix,
+                    range: entry.text_range(),
+                    edits: vec![TextEdit::delete(removal_range)],
+                    document_edits: Vec::new(),
+                    is_preferred: false,
+                    priority: 0,
+                }]),
+                related_information: None,
+            });
+        }
     }
 }
 
 /// Ensures that no duplicate ink! attributes and/or arguments.
 ///
+/// Duplicate ink! attribute arguments with conflicting values across sibling attributes
+/// (e.g. `#[ink(selector = 1)]` and `#[ink(selector = 2)]` on the same `fn` item)
+/// are reported as conflicts with quickfixes for keeping one of the values (and removing the rest).
+///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L169-L208>.
 fn ensure_no_duplicate_attributes_and_arguments(
     results: &mut Vec<Diagnostic>,
@@ -393,6 +578,9 @@ fn ensure_no_duplicate_attributes_and_arguments(
 ) {
     let mut seen_macros: HashSet<&InkMacroKind> = HashSet::new();
     let mut seen_args: HashSet<&InkArgKind> = HashSet::new();
+    // Conflicting values are only reported once per argument kind,
+    // because the "keep only" quickfixes already resolve all conflicts.
+    let mut conflicting_args: HashSet<&InkArgKind> = HashSet::new();
 
     for attr in attrs {
         if let InkAttributeKind::Macro(macro_kind) = attr.kind() {
@@ -402,7 +590,9 @@ fn ensure_no_duplicate_attributes_and_arguments(
                     message: format!("Duplicate ink! attribute macro: `{}`", attr.syntax()),
                     range: attr.syntax().text_range(),
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::DuplicateAttribute),
                     quickfixes: Some(vec![Action::remove_attribute(attr)]),
+                    related_information: None,
                 });
             }
             seen_macros.insert(macro_kind);
@@ -412,19 +602,70 @@ fn ensure_no_duplicate_attributes_and_arguments(
             let arg_kind = arg.kind();
             // Unknown ink! attribute arguments are ignored.
             if *arg_kind != InkArgKind::Unknown && seen_args.get(arg_kind).is_some() {
-                // Edit range for quickfix.
-                let range = utils::ink_arg_and_delimiter_removal_range(arg, Some(attr));
-                results.push(Diagnostic {
-                    message: format!("Duplicate ink! attribute argument: `{}`", arg.meta().name()),
-                    range: arg.text_range(),
-                    severity: Severity::Error,
-                    quickfixes: Some(vec![Action {
-                        label: format!("Remove ink! `{}` attribute argument.", arg.meta().name()),
-                        kind: ActionKind::QuickFix,
-                        range,
-                        edits: vec![TextEdit::delete(range)],
-                    }]),
-                });
+                // Gets all sibling ink! attribute arguments of the same kind.
+                let same_kind_args: Vec<(&InkAttribute, &InkArg)> = attrs
+                    .iter()
+                    .flat_map(|attr| attr.args().iter().map(move |it| (attr, it)))
+                    .filter(|(_, it)| it.kind() == arg_kind)
+                    .collect();
+                let value = normalized_arg_value(arg);
+                let conflicting_arg = same_kind_args
+                    .iter()
+                    .map(|(_, it)| *it)
+                    .take_while(|it| it.text_range() != arg.text_range())
+                    .find(|it| normalized_arg_value(it) != value);
+
+                match conflicting_arg {
+                    Some(_) if conflicting_args.contains(arg_kind) => (),
+                    Some(conflicting_arg) => {
+                        conflicting_args.insert(arg_kind);
+                        results.push(Diagnostic {
+                            message: format!(
+                                "Conflicting values for ink! attribute argument `{}`: `{conflicting_arg}` and `{arg}`",
+                                arg.meta().name(),
+                            ),
+                            range: arg.text_range(),
+                            severity: Severity::Error,
+                            code: Some(DiagnosticCode::DuplicateAttribute),
+                            quickfixes: Some(keep_one_arg_value_quickfixes(
+                                &same_kind_args,
+                                arg.text_range(),
+                            )),
+                            related_information: Some(vec![RelatedInformation {
+                                message: format!(
+                                    "Conflicting ink! attribute argument: `{conflicting_arg}`"
+                                ),
+                                range: conflicting_arg.text_range(),
+                            }]),
+                        });
+                    }
+                    None => {
+                        // Edit range for quickfix.
+                        let range = utils::ink_arg_and_delimiter_removal_range(arg, Some(attr));
+                        results.push(Diagnostic {
+                            message: format!(
+                                "Duplicate ink! attribute argument: `{}`",
+                                arg.meta().name()
+                            ),
+                            range: arg.text_range(),
+                            severity: Severity::Error,
+                            code: Some(DiagnosticCode::DuplicateAttribute),
+                            quickfixes: Some(vec![Action {
+                                label: format!(
+                                    "Remove ink! `{}` attribute argument.",
+                                    arg.meta().name()
+                                ),
+                                kind: ActionKind::QuickFix,
+                                range,
+                                edits: vec![TextEdit::delete(range)],
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
+                            }]),
+                            related_information: None,
+                        });
+                    }
+                }
             }
 
             seen_args.insert(arg_kind);
@@ -432,6 +673,68 @@ fn ensure_no_duplicate_attributes_and_arguments(
     }
 }
 
+/// Returns a normalized representation of an ink! attribute argument's value (if any)
+/// for comparing argument values (e.g. `1` and `0x1` are the same `selector` value).
+fn normalized_arg_value(arg: &InkArg) -> Option<String> {
+    let value = arg.value()?;
+    Some(match (arg.as_u32(), arg.as_bool()) {
+        (Ok(value), _) => value.to_string(),
+        (_, Ok(value)) => value.to_string(),
+        _ => value.to_string().split_whitespace().collect(),
+    })
+}
+
+/// Returns quickfixes for keeping one of the (distinct) values of duplicate ink! attribute arguments
+/// (i.e. the first argument with each value is kept, and all other arguments are removed).
+fn keep_one_arg_value_quickfixes(
+    args: &[(&InkAttribute, &InkArg)],
+    range: TextRange,
+) -> Vec<Action> {
+    args.iter()
+        .unique_by(|(_, arg)| normalized_arg_value(arg))
+        .filter_map(|(_, kept_arg)| {
+            // Merges overlapping removal ranges (e.g. for adjacent arguments that share a delimiter).
+            let is_removed = |arg: &InkArg| {
+                arg.text_range() != kept_arg.text_range()
+                    && args
+                        .iter()
+                        .any(|(_, it)| it.text_range() == arg.text_range())
+            };
+            let mut removal_ranges: Vec<TextRange> = args
+                .iter()
+                .filter(|(_, arg)| is_removed(arg))
+                .map(|(attr, arg)| match attr.kind() {
+                    // Removes the whole attribute if all its arguments are removed.
+                    InkAttributeKind::Arg(_) if attr.args().iter().all(is_removed) => {
+                        attr.syntax().text_range()
+                    }
+                    _ => utils::ink_arg_and_delimiter_removal_range(arg, Some(attr)),
+                })
+                .collect();
+            removal_ranges.sort_by_key(|range| range.start());
+            let removal_ranges =
+                removal_ranges
+                    .into_iter()
+                    .fold(Vec::<TextRange>::new(), |mut acc, range| {
+                        match acc.last_mut() {
+                            Some(last) if last.end() >= range.start() => *last = last.cover(range),
+                            _ => acc.push(range),
+                        }
+                        acc
+                    });
+            (!removal_ranges.is_empty()).then(|| Action {
+                label: format!("Keep only `{kept_arg}`."),
+                kind: ActionKind::QuickFix,
+                range,
+                edits: removal_ranges.into_iter().map(TextEdit::delete).collect(),
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
+            })
+        })
+        .collect()
+}
+
 /// Ensures that no conflicting ink! attributes and/or arguments.
 ///
 /// In addition to straight forward conflicts
@@ -493,6 +796,7 @@ fn ensure_no_conflicting_attributes_and_arguments(
                 ),
                 range: primary_ink_attr_candidate.syntax().text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::AttributeOrder),
                 quickfixes: primary_attr_insert_offset_option().map(|insert_offset| {
                     vec![Action::move_item(
                         primary_ink_attr_candidate.syntax(),
@@ -504,6 +808,7 @@ fn ensure_no_conflicting_attributes_and_arguments(
                         None,
                     )]
                 }),
+                related_information: None,
             });
         }
 
@@ -543,6 +848,7 @@ fn ensure_no_conflicting_attributes_and_arguments(
                             primary_ink_attr_candidate.syntax().text_range()
                         },
                         severity: Severity::Error,
+                        code: Some(DiagnosticCode::AttributeOrder),
                         quickfixes: primary_arg.and_then(|arg| {
                             // Determines the insertion offset and affixes for the quickfix.
                             utils::first_ink_arg_insert_offset_and_affixes(
@@ -579,10 +885,14 @@ fn ensure_no_conflicting_attributes_and_arguments(
                                                 ),
                                             ),
                                         ],
+                                        document_edits: Vec::new(),
+                                        is_preferred: false,
+                                        priority: 0,
                                     }]
                                 },
                             )
                         }),
+                        related_information: None,
                     });
                 }
             }
@@ -620,6 +930,9 @@ fn ensure_no_conflicting_attributes_and_arguments(
                             insert_offset,
                             snippet,
                         )],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     }
                 })
             };
@@ -659,6 +972,9 @@ fn ensure_no_conflicting_attributes_and_arguments(
                                                 )
                                             }),
                                         )],
+                                        document_edits: Vec::new(),
+                                        is_preferred: false,
+                                        priority: 0,
                                     }),
                                     // Defaults to adding the suggested ink! attribute argument as the first attribute.
                                     None => add_primary_ink_attribute(attr_kind),
@@ -686,6 +1002,9 @@ fn ensure_no_conflicting_attributes_and_arguments(
                                             ),
                                             primary_ink_attr_candidate.syntax().text_range(),
                                         )],
+                                        document_edits: Vec::new(),
+                                        is_preferred: false,
+                                        priority: 0,
                                     }),
                                     // Adds the suggested ink! attribute macro as the first attribute.
                                     InkAttributeKind::Macro(_) => {
@@ -716,9 +1035,11 @@ fn ensure_no_conflicting_attributes_and_arguments(
                 ),
                 range: primary_ink_attr_candidate.syntax().text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::AttributeOrder),
                 quickfixes: possible_quickfixes
                     .next()
                     .map(|quickfix| [quickfix].into_iter().chain(possible_quickfixes).collect()),
+                related_information: None,
             });
         }
 
@@ -768,6 +1089,7 @@ fn ensure_no_conflicting_attributes_and_arguments(
                     ),
                     range: attr.syntax().text_range(),
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::ConflictingAttribute),
                     quickfixes: Some(vec![
                         match (primary_ink_attr_candidate.kind(), attr.kind()) {
                             // Removes only conflicting arguments (or entire attribute if necessary).
@@ -801,6 +1123,9 @@ fn ensure_no_conflicting_attributes_and_arguments(
                                                 )
                                             })
                                             .collect(),
+                                        document_edits: Vec::new(),
+                                        is_preferred: false,
+                                        priority: 0,
                                     }
                                 }
                             }
@@ -808,6 +1133,7 @@ fn ensure_no_conflicting_attributes_and_arguments(
                             _ => Action::remove_attribute(attr),
                         },
                     ]),
+                    related_information: None,
                 });
             } else {
                 // Handle argument level conflicts if the top level attribute kind doesn't conflict.
@@ -847,6 +1173,7 @@ fn ensure_no_conflicting_attributes_and_arguments(
                             ),
                             range: arg.text_range(),
                             severity: Severity::Error,
+                            code: Some(DiagnosticCode::ConflictingAttribute),
                             quickfixes: Some(vec![Action {
                                 label: format!(
                                     "Remove ink! `{}` attribute argument.",
@@ -855,7 +1182,11 @@ fn ensure_no_conflicting_attributes_and_arguments(
                                 kind: ActionKind::QuickFix,
                                 range,
                                 edits: vec![TextEdit::delete(range)],
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
                             }]),
+                            related_information: None,
                         });
                     }
                 }
@@ -879,13 +1210,20 @@ pub fn ensure_exactly_one_item<T>(
     empty_diagnostic: Diagnostic,
     error_too_many: &str,
     severity_too_many: Severity,
+    code_too_many: DiagnosticCode,
 ) where
     T: FromSyntax + FromInkAttribute,
 {
     if items.is_empty() {
         results.push(empty_diagnostic);
     } else {
-        ensure_at_most_one_item(results, items, error_too_many, severity_too_many);
+        ensure_at_most_one_item(
+            results,
+            items,
+            error_too_many,
+            severity_too_many,
+            code_too_many,
+        );
     }
 }
 
@@ -895,6 +1233,7 @@ pub fn ensure_at_most_one_item<T>(
     items: &[T],
     message: &str,
     severity: Severity,
+    code: DiagnosticCode,
 ) where
     T: FromSyntax + FromInkAttribute,
 {
@@ -904,10 +1243,12 @@ pub fn ensure_at_most_one_item<T>(
                 message: message.to_string(),
                 range: item.syntax().text_range(),
                 severity,
+                code: Some(code),
                 quickfixes: Some(vec![
                     Action::remove_attribute(item.ink_attr()),
                     Action::remove_item(item.syntax()),
                 ]),
+                related_information: None,
             });
         }
     }
@@ -932,6 +1273,7 @@ where
                     .map_or(item.syntax(), AstNode::syntax)
                     .text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::InvalidVisibility),
                 quickfixes: visibility
                     .as_ref()
                     .map(|vis| vis.syntax().text_range())
@@ -947,15 +1289,21 @@ where
                                 format!("pub{}", if visibility.is_none() { " " } else { "" }),
                                 range,
                             )],
+                            document_edits: Vec::new(),
+                            is_preferred: false,
+                            priority: 0,
                         }]
                     }),
+                related_information: None,
             })
         }
         None => Some(Diagnostic {
             message: format!("ink! {ink_scope_name} must be a `struct` item.",),
             range: item.syntax().text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidItemKind),
             quickfixes: Some(vec![Action::remove_attribute(item.ink_attr())]),
+            related_information: None,
         }),
     }
 }
@@ -969,7 +1317,9 @@ where
         message: format!("ink! {ink_scope_name} must be an `fn` item.",),
         range: item.syntax().text_range(),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidItemKind),
         quickfixes: Some(vec![Action::remove_attribute(item.ink_attr())]),
+        related_information: None,
     })
 }
 
@@ -982,7 +1332,9 @@ where
         message: format!("ink! {ink_scope_name} must be a `trait` item.",),
         range: item.syntax().text_range(),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidItemKind),
         quickfixes: Some(vec![Action::remove_attribute(item.ink_attr())]),
+        related_information: None,
     })
 }
 
@@ -995,12 +1347,17 @@ pub fn ensure_no_self_receiver(fn_item: &ast::Fn, ink_scope_name: &str) -> Optio
             message: format!("ink! {ink_scope_name} must not have a self receiver (i.e no `&self`, `&mut self`, self or mut self)."),
             range: self_param.syntax().text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidSignature),
             quickfixes: Some(vec![Action {
                 label: "Remove self receiver.".to_string(),
                 kind: ActionKind::QuickFix,
                 range,
                 edits: vec![TextEdit::delete(range)],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }]),
+            related_information: None,
         }
     })
 }
@@ -1016,12 +1373,17 @@ where
         ),
         range: generics.syntax().text_range(),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::UnsupportedGenerics),
         quickfixes: Some(vec![Action {
             label: "Remove generic parameters.".to_string(),
             kind: ActionKind::QuickFix,
             range: generics.syntax().text_range(),
             edits: vec![TextEdit::delete(generics.syntax().text_range())],
+            document_edits: Vec::new(),
+            is_preferred: false,
+            priority: 0,
         }]),
+        related_information: None,
     })
 }
 
@@ -1046,12 +1408,17 @@ where
             message: message.to_string(),
             range,
             severity: Severity::Error,
+            code: Some(DiagnosticCode::UnsupportedGenerics),
             quickfixes: Some(vec![Action {
                 label: "Remove type bounds.".to_string(),
                 kind: ActionKind::QuickFix,
                 range,
                 edits: vec![TextEdit::delete(range)],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }]),
+            related_information: None,
         }
     })
 }
@@ -1080,12 +1447,17 @@ pub fn ensure_fn_invariants(
             message: format!("ink! {ink_scope_name} must not be `const`."),
             range: const_token.text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidModifier),
             quickfixes: Some(vec![Action {
                 label: "Remove `const` keyword.".to_string(),
                 kind: ActionKind::QuickFix,
                 range,
                 edits: vec![TextEdit::delete(range)],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }]),
+            related_information: None,
         });
     }
 
@@ -1096,12 +1468,17 @@ pub fn ensure_fn_invariants(
             message: format!("ink! {ink_scope_name} must not be `async`."),
             range: async_token.text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidModifier),
             quickfixes: Some(vec![Action {
                 label: "Remove `async` keyword.".to_string(),
                 kind: ActionKind::QuickFix,
                 range,
                 edits: vec![TextEdit::delete(range)],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }]),
+            related_information: None,
         });
     }
 
@@ -1112,12 +1489,17 @@ pub fn ensure_fn_invariants(
             message: format!("ink! {ink_scope_name} must not be `unsafe`."),
             range: unsafe_token.text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidModifier),
             quickfixes: Some(vec![Action {
                 label: "Remove `unsafe` keyword.".to_string(),
                 kind: ActionKind::QuickFix,
                 range,
                 edits: vec![TextEdit::delete(range)],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }]),
+            related_information: None,
         });
     }
 
@@ -1128,12 +1510,17 @@ pub fn ensure_fn_invariants(
             message: format!("ink! {ink_scope_name} must not have explicit ABI."),
             range: abi.syntax().text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidModifier),
             quickfixes: Some(vec![Action {
                 label: "Remove explicit ABI.".to_string(),
                 kind: ActionKind::QuickFix,
                 range,
                 edits: vec![TextEdit::delete(range)],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }]),
+            related_information: None,
         });
     }
 
@@ -1149,12 +1536,17 @@ pub fn ensure_fn_invariants(
                             message: format!("ink! {ink_scope_name} must not be variadic."),
                             range: dotdotdot.text_range(),
                             severity: Severity::Error,
+                            code: Some(DiagnosticCode::InvalidModifier),
                             quickfixes: Some(vec![Action {
                                 label: "Make function un-variadic.".to_string(),
                                 kind: ActionKind::QuickFix,
                                 range,
                                 edits: vec![TextEdit::delete(range)],
+                                document_edits: Vec::new(),
+                                is_preferred: false,
+                                priority: 0,
                             }]),
+                            related_information: None,
                         }
                     })
                 })
@@ -1189,6 +1581,7 @@ pub fn ensure_callable_invariants(
                 .map_or(fn_item.syntax(), AstNode::syntax)
                 .text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidVisibility),
             quickfixes: visibility
                 .as_ref()
                 .map(|vis| vis.syntax().text_range())
@@ -1213,15 +1606,22 @@ pub fn ensure_callable_invariants(
                                 format!("pub{}", if visibility.is_none() { " " } else { "" }),
                                 range,
                             )],
+                            document_edits: Vec::new(),
+                            is_preferred: false,
+                            priority: 0,
                         },
                         Action {
                             label: "Remove visibility.".to_string(),
                             kind: ActionKind::QuickFix,
                             range: remove_range,
                             edits: vec![TextEdit::delete(remove_range)],
+                            document_edits: Vec::new(),
+                            is_preferred: false,
+                            priority: 0,
                         },
                     ]
                 }),
+            related_information: None,
         });
     }
 
@@ -1249,12 +1649,17 @@ pub fn ensure_trait_invariants(
             message: format!("ink! {ink_scope_name} must not be `unsafe`."),
             range: unsafe_token.text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidModifier),
             quickfixes: Some(vec![Action {
                 label: "Remove `unsafe` keyword.".to_string(),
                 kind: ActionKind::QuickFix,
                 range,
                 edits: vec![TextEdit::delete(range)],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }]),
+            related_information: None,
         });
     }
 
@@ -1265,12 +1670,17 @@ pub fn ensure_trait_invariants(
             message: format!("ink! {ink_scope_name} must not be `auto` implemented."),
             range: auto_token.text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidModifier),
             quickfixes: Some(vec![Action {
                 label: "Remove `auto` keyword.".to_string(),
                 kind: ActionKind::QuickFix,
                 range,
                 edits: vec![TextEdit::delete(range)],
+                document_edits: Vec::new(),
+                is_preferred: false,
+                priority: 0,
             }]),
+            related_information: None,
         });
     }
 
@@ -1293,6 +1703,7 @@ pub fn ensure_trait_invariants(
                 .map_or(trait_item.syntax(), AstNode::syntax)
                 .text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidVisibility),
             quickfixes: visibility
                 .as_ref()
                 .map(|vis| vis.syntax().text_range())
@@ -1310,8 +1721,12 @@ pub fn ensure_trait_invariants(
                             format!("pub{}", if visibility.is_none() { " " } else { "" }),
                             range,
                         )],
+                        document_edits: Vec::new(),
+                        is_preferred: false,
+                        priority: 0,
                     }]
                 }),
+            related_information: None,
         });
     }
 
@@ -1351,14 +1766,19 @@ pub fn ensure_trait_item_invariants<F, G>(
                     ),
                     range: const_item.syntax().text_range(),
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::InvalidTraitItem),
                     quickfixes: Some(vec![
                         Action {
                             label: "Remove `const` item.".to_string(),
                             kind: ActionKind::QuickFix,
                             range: const_item.syntax().text_range(),
                             edits: vec![TextEdit::delete(const_item.syntax().text_range())],
+                            document_edits: Vec::new(),
+                            is_preferred: false,
+                            priority: 0,
                         }
                     ]),
+                    related_information: None,
                 }),
                 ast::AssocItem::MacroCall(macro_call) => results.push(Diagnostic {
                     message: format!(
@@ -1366,14 +1786,19 @@ pub fn ensure_trait_item_invariants<F, G>(
                     ),
                     range: macro_call.syntax().text_range(),
                     severity: Severity::Error,
+                    code: Some(DiagnosticCode::InvalidTraitItem),
                     quickfixes: Some(vec![
                         Action {
                             label: "Remove macro call.".to_string(),
                             kind: ActionKind::QuickFix,
                             range: macro_call.syntax().text_range(),
                             edits: vec![TextEdit::delete(macro_call.syntax().text_range())],
+                            document_edits: Vec::new(),
+                            is_preferred: false,
+                            priority: 0,
                         }
                     ]),
+                    related_information: None,
                 }),
                 ast::AssocItem::TypeAlias(type_alias) => assoc_type_handler(results, &type_alias),
                 ast::AssocItem::Fn(fn_item) => {
@@ -1383,14 +1808,19 @@ pub fn ensure_trait_item_invariants<F, G>(
                             message: format!("ink! {ink_scope_name} functions with a default implementation are not currently supported."),
                             range: body.syntax().text_range(),
                             severity: Severity::Error,
+                            code: Some(DiagnosticCode::InvalidTraitItem),
                             quickfixes: Some(vec![
                                 Action {
                                     label: "Remove function body.".to_string(),
                                     kind: ActionKind::QuickFix,
                                     range: body.syntax().text_range(),
                                     edits: vec![TextEdit::delete(body.syntax().text_range())],
+                                    document_edits: Vec::new(),
+                                    is_preferred: false,
+                                    priority: 0,
                                 }
                             ]),
+                            related_information: None,
                         });
                     }
 
@@ -1413,6 +1843,7 @@ where
         ),
         range: item.syntax().text_range(),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidScope),
         // Moves the item to the root of the closest ink! contract's `mod` item.
         quickfixes: ink_analyzer_ir::ink_ancestors::<Contract>(item.syntax())
             .next()
@@ -1427,6 +1858,7 @@ where
                     Some(utils::item_children_indenting(mod_item.syntax()).as_str()),
                 )]
             }),
+        related_information: None,
     })
 }
 
@@ -1439,6 +1871,7 @@ where
         message: format!("ink! {ink_scope_name} must be defined in the root of an `impl` block."),
         range: item.syntax().text_range(),
         severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidScope),
         quickfixes: ink_analyzer_ir::closest_ancestor_ast_type::<SyntaxNode, ast::Impl>(
             item.syntax(),
         )
@@ -1478,6 +1911,7 @@ where
                     },
                 )
             })),
+        related_information: None,
     })
 }
 
@@ -1514,6 +1948,7 @@ pub fn ensure_valid_quasi_direct_ink_descendants<T, F>(
                 message: format!("Invalid scope for an `{}` item.", attr.syntax()),
                 range: attr.syntax().text_range(),
                 severity: Severity::Error,
+                code: Some(DiagnosticCode::InvalidScope),
                 quickfixes: Some(ink_analyzer_ir::parent_ast_item(attr.syntax()).map_or(
                     vec![Action::remove_attribute(&attr)],
                     |item| {
@@ -1523,6 +1958,7 @@ pub fn ensure_valid_quasi_direct_ink_descendants<T, F>(
                         ]
                     },
                 )),
+                related_information: None,
             });
         }
     }
@@ -1547,6 +1983,7 @@ where
             ),
             range: attr.syntax().text_range(),
             severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidScope),
             quickfixes: Some(ink_analyzer_ir::parent_ast_item(attr.syntax()).map_or(
                 vec![Action::remove_attribute(&attr)],
                 |item| {
@@ -1556,10 +1993,290 @@ where
                     ]
                 },
             )),
+            related_information: None,
+        });
+    }
+}
+
+/// Associated constants of the `ink::env::Environment` trait.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/env/src/types.rs#L166-L234>.
+const ENVIRONMENT_CONSTS: [&str; 1] = ["MAX_EVENT_TOPICS"];
+
+/// Associated types of the `ink::env::Environment` trait.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/env/src/types.rs#L166-L234>.
+const ENVIRONMENT_TYPES: [&str; 6] = [
+    "AccountId",
+    "Balance",
+    "Hash",
+    "Timestamp",
+    "BlockNumber",
+    "ChainExtension",
+];
+
+/// Ensures that the environment type (i.e. the value of an `env` or `environment` argument)
+/// can be resolved and implements all the associated items of the `ink::env::Environment` trait.
+///
+/// **NOTE**: Paths are resolved syntactically (relative to the scope of the given syntax node),
+/// so paths that point outside the file (e.g. to external crates, out-of-line modules or `use` imports)
+/// are ignored.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/config.rs#L39-L70>.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.2.1/crates/e2e/macro/src/config.rs#L49-L85>.
+pub fn ensure_valid_environment(results: &mut Vec<Diagnostic>, arg: &InkArg, scope: &SyntaxNode) {
+    let (Ok(path), Some(value)) = (arg.as_path(), arg.value()) else {
+        return;
+    };
+    let range = value.text_range();
+    let env_item = match resolve_item_path(&path, scope) {
+        PathResolution::Item(item) => item,
+        PathResolution::Unresolved => {
+            results.push(Diagnostic {
+                message: format!("Environment type `{value}` couldn't be resolved."),
+                range,
+                severity: Severity::Error,
+                code: Some(DiagnosticCode::InvalidArgument),
+                quickfixes: None,
+                related_information: None,
+            });
+            return;
+        }
+        PathResolution::Unknown => return,
+    };
+    // Type aliases (and other items) aren't resolved any further.
+    let Some(name) = (match &env_item {
+        ast::Item::Struct(it) => it.name(),
+        ast::Item::Enum(it) => it.name(),
+        ast::Item::Union(it) => it.name(),
+        _ => None,
+    }) else {
+        return;
+    };
+    let name = name.to_string();
+    let env_item_info = Some(vec![RelatedInformation {
+        message: format!("The definition of `{name}`."),
+        range: env_item.syntax().text_range(),
+    }]);
+    let env_impl = scope.ancestors().last().and_then(|root| {
+        root.descendants()
+            .filter_map(ast::Impl::cast)
+            .find(|impl_item| {
+                impl_item.trait_().and_then(path_type_name).as_deref() == Some("Environment")
+                    && impl_item.self_ty().and_then(path_type_name).as_deref()
+                        == Some(name.as_str())
+            })
+    });
+    let Some(env_impl) = env_impl else {
+        // Suggests an implementation that uses the default environment types.
+        let items = environment_items_text(
+            ENVIRONMENT_CONSTS
+                .iter()
+                .chain(ENVIRONMENT_TYPES.iter())
+                .copied(),
+        );
+        results.push(Diagnostic {
+            message: format!(
+                "Environment type `{value}` must implement the `ink::env::Environment` trait."
+            ),
+            range,
+            severity: Severity::Error,
+            code: Some(DiagnosticCode::InvalidArgument),
+            quickfixes: Some(vec![Action {
+                label: format!("Add `ink::env::Environment` implementation for `{name}`."),
+                kind: ActionKind::QuickFix,
+                range,
+                edits: vec![EditBuilder::insert(
+                    format!(
+                        "impl ink::env::Environment for {name} {{\n{}\n}}",
+                        utils::apply_indenting(&items, "    ")
+                    ),
+                    env_item.syntax().text_range().end(),
+                )
+                .indent_as_sibling_of(env_item.syntax())
+                .build()],
+                document_edits: Vec::new(),
+                is_preferred: true,
+                priority: 0,
+            }]),
+            related_information: env_item_info,
         });
+        return;
+    };
+
+    // Ensures that all associated items are implemented.
+    let implemented_items: HashSet<String> = env_impl
+        .assoc_item_list()
+        .iter()
+        .flat_map(ast::AssocItemList::assoc_items)
+        .filter_map(|item| match item {
+            ast::AssocItem::Const(it) => it.name(),
+            ast::AssocItem::TypeAlias(it) => it.name(),
+            _ => None,
+        })
+        .map(|name| name.to_string())
+        .collect();
+    let missing_items: Vec<&str> = ENVIRONMENT_CONSTS
+        .iter()
+        .chain(ENVIRONMENT_TYPES.iter())
+        .copied()
+        .filter(|item| !implemented_items.contains(*item))
+        .collect();
+    if missing_items.is_empty() {
+        return;
+    }
+    let missing_items_help = missing_items
+        .iter()
+        .map(|item| format!("`{item}`"))
+        .join(", ");
+    results.push(Diagnostic {
+        message: format!(
+            "The `ink::env::Environment` implementation for environment type `{value}` \
+            is missing the following associated items: {missing_items_help}."
+        ),
+        range,
+        severity: Severity::Error,
+        code: Some(DiagnosticCode::InvalidArgument),
+        quickfixes: env_impl.assoc_item_list().map(|assoc_item_list| {
+            vec![Action {
+                label: format!("Add missing associated items to the `ink::env::Environment` implementation for `{name}`."),
+                kind: ActionKind::QuickFix,
+                range,
+                edits: vec![EditBuilder::insert(
+                    environment_items_text(missing_items.iter().copied()),
+                    utils::assoc_item_insert_offset_end(&assoc_item_list),
+                )
+                .indent_as_child_of(env_impl.syntax())
+                .build()],
+                document_edits: Vec::new(),
+                is_preferred: true,
+                priority: 0,
+            }]
+        }),
+        related_information: Some(vec![RelatedInformation {
+            message: format!("The `ink::env::Environment` implementation for `{name}`."),
+            range: utils::ast_item_declaration_range(&ast::Item::Impl(env_impl.clone()))
+                .unwrap_or(env_impl.syntax().text_range()),
+        }]),
+    });
+}
+
+/// Returns the definitions of the given associated items of the `ink::env::Environment` trait
+/// (i.e. using the types and values of the default environment).
+fn environment_items_text<'a>(items: impl Iterator<Item = &'a str>) -> String {
+    items
+        .map(|item| {
+            if ENVIRONMENT_CONSTS.contains(&item) {
+                format!("const {item}: usize = <ink::env::DefaultEnvironment as ink::env::Environment>::{item};")
+            } else {
+                format!("type {item} = <ink::env::DefaultEnvironment as ink::env::Environment>::{item};")
+            }
+        })
+        .join("\n")
+}
+
+/// Returns the name (i.e. the last path segment) of a path type (if any).
+fn path_type_name(ty: ast::Type) -> Option<String> {
+    match ty {
+        ast::Type::PathType(path_type) => path_type
+            .path()?
+            .segment()?
+            .name_ref()
+            .map(|name_ref| name_ref.to_string()),
+        _ => None,
     }
 }
 
+/// The result of resolving an item path in a file (see [`resolve_item_path`] doc).
+enum PathResolution {
+    /// The path resolves to an item in the file.
+    Item(ast::Item),
+    /// The path should resolve to an item in the file, but the item doesn't exist.
+    Unresolved,
+    /// The path can't be resolved in the file
+    /// (e.g. paths to external crates, out-of-line modules or `use` imports).
+    Unknown,
+}
+
+/// Resolves an item path (e.g. `crate::env::MyEnvironment`) relative to the scope of the syntax node.
+///
+/// **NOTE**: The file is assumed to be the crate root (i.e. for `crate` paths).
+fn resolve_item_path(path: &ast::Path, scope: &SyntaxNode) -> PathResolution {
+    let Some(segments) = path
+        .segments()
+        .map(|segment| segment.name_ref().map(|name_ref| name_ref.to_string()))
+        .collect::<Option<Vec<String>>>()
+    else {
+        return PathResolution::Unknown;
+    };
+    // Item scopes (i.e. `mod` item lists and the file), from the innermost to the root.
+    let item_scopes: Vec<SyntaxNode> = scope
+        .ancestors()
+        .filter(|node| {
+            ast::ItemList::can_cast(node.kind()) || ast::SourceFile::can_cast(node.kind())
+        })
+        .collect();
+    let n_supers = segments
+        .iter()
+        .take_while(|segment| segment.as_str() == "super")
+        .count();
+    let (mut current_scope, names, is_anchored) = match segments.first().map(String::as_str) {
+        Some("crate") => (item_scopes.last().cloned(), &segments[1..], true),
+        Some("self") => (item_scopes.first().cloned(), &segments[1..], true),
+        Some("super") => (
+            item_scopes.get(n_supers).cloned(),
+            &segments[n_supers..],
+            true,
+        ),
+        _ => (item_scopes.first().cloned(), &segments[..], false),
+    };
+    for (idx, name) in names.iter().enumerate() {
+        let Some(scope_node) = current_scope.take() else {
+            return PathResolution::Unknown;
+        };
+        let items: Vec<ast::Item> = match ast::ItemList::cast(scope_node.clone()) {
+            Some(item_list) => item_list.items().collect(),
+            None => ast::SourceFile::cast(scope_node)
+                .map(|file| file.items().collect())
+                .unwrap_or_default(),
+        };
+        let is_last = idx + 1 == names.len();
+        let item = items.iter().find(|item| {
+            let item_name = match item {
+                ast::Item::Module(it) => it.name(),
+                ast::Item::Struct(it) => it.name(),
+                ast::Item::Enum(it) => it.name(),
+                ast::Item::Union(it) => it.name(),
+                ast::Item::TypeAlias(it) => it.name(),
+                _ => None,
+            };
+            item_name.map_or(false, |item_name| item_name.to_string() == *name)
+        });
+        match item {
+            Some(ast::Item::Module(module)) if !is_last => match module.item_list() {
+                Some(item_list) => current_scope = Some(item_list.syntax().clone()),
+                // Out-of-line modules can't be resolved.
+                None => return PathResolution::Unknown,
+            },
+            Some(item) if is_last => return PathResolution::Item(item.clone()),
+            _ => {
+                // Items can also be imported (i.e. by `use` declarations) or generated (i.e. by macros),
+                // and unanchored paths can also start with an external crate.
+                let is_imported_or_generated = items
+                    .iter()
+                    .any(|item| matches!(item, ast::Item::Use(_) | ast::Item::MacroCall(_)));
+                return if is_imported_or_generated || (!is_anchored && idx == 0 && !is_last) {
+                    PathResolution::Unknown
+                } else {
+                    PathResolution::Unresolved
+                };
+            }
+        }
+    }
+    PathResolution::Unknown
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1657,6 +2374,9 @@ mod tests {
                 quote_as_str! {
                     #[ink(message, selector=0xA)] // message is required, otherwise this would be incomplete.
                 },
+                quote_as_str! {
+                    #[ink(message, selector=0xFFFF_FFFF)] // digit separators are allowed.
+                },
                 // Arguments that can have a wildcard/underscore value.
                 quote_as_str! {
                     #[ink(message, selector=_)] // message is required, otherwise this would be incomplete.
@@ -2029,14 +2749,102 @@ mod tests {
             (
                 "#[ink(selector=0xFFFF_FFFF_FFFF_FFFF)]",
                 vec![TestResultAction {
-                    label: "argument value",
+                    label: "Truncate",
                     edits: vec![TestResultTextRange {
-                        text: "selector = 1",
+                        text: "selector = 0xFFFFFFFF",
                         start_pat: Some("<-selector=0xFFFF_FFFF_FFFF_FFFF"),
                         end_pat: Some("selector=0xFFFF_FFFF_FFFF_FFFF"),
                     }],
                 }],
             ),
+            (
+                "#[ink(extension=4294967297)]",
+                vec![TestResultAction {
+                    label: "Truncate",
+                    edits: vec![TestResultTextRange {
+                        text: "extension = 1",
+                        start_pat: Some("<-extension=4294967297"),
+                        end_pat: Some("extension=4294967297"),
+                    }],
+                }],
+            ),
+            (
+                "#[ink(selector=4294967297)]",
+                vec![TestResultAction {
+                    label: "Truncate",
+                    edits: vec![TestResultTextRange {
+                        text: "selector = 1",
+                        start_pat: Some("<-selector=4294967297"),
+                        end_pat: Some("selector=4294967297"),
+                    }],
+                }],
+            ),
+            (
+                "#[ink(selector=-0xA)]",
+                vec![TestResultAction {
+                    label: "Change",
+                    edits: vec![TestResultTextRange {
+                        text: "selector = 0xA",
+                        start_pat: Some("<-selector=-0xA"),
+                        end_pat: Some("selector=-0xA"),
+                    }],
+                }],
+            ),
+            (
+                "#[ink(selector=0XA)]",
+                vec![TestResultAction {
+                    label: "Change",
+                    edits: vec![TestResultTextRange {
+                        text: "selector = 0xA",
+                        start_pat: Some("<-selector=0XA"),
+                        end_pat: Some("selector=0XA"),
+                    }],
+                }],
+            ),
+            (
+                "#[ink(selector=0xZZ)]",
+                vec![TestResultAction {
+                    label: "Replace `selector` argument value.",
+                    edits: vec![TestResultTextRange {
+                        text: "selector = 1",
+                        start_pat: Some("<-selector=0xZZ"),
+                        end_pat: Some("selector=0xZZ"),
+                    }],
+                }],
+            ),
+            (
+                "#[ink(selector=4294967296)]",
+                vec![TestResultAction {
+                    label: "Replace `selector` argument value.",
+                    edits: vec![TestResultTextRange {
+                        text: "selector = 1",
+                        start_pat: Some("<-selector=4294967296"),
+                        end_pat: Some("selector=4294967296"),
+                    }],
+                }],
+            ),
+            (
+                "#[ink(selector=0x1u32)]",
+                vec![TestResultAction {
+                    label: "Change",
+                    edits: vec![TestResultTextRange {
+                        text: "selector = 0x1",
+                        start_pat: Some("<-selector=0x1u32"),
+                        end_pat: Some("selector=0x1u32"),
+                    }],
+                }],
+            ),
+            (
+                "#[ink(selector=10_u32)]",
+                vec![TestResultAction {
+                    label: "Change",
+                    edits: vec![TestResultTextRange {
+                        text: "selector = 10",
+                        start_pat: Some("<-selector=10_u32"),
+                        end_pat: Some("selector=10_u32"),
+                    }],
+                }],
+            ),
             (
                 r#"#[ink(selector="hello")]"#,
                 vec![TestResultAction {
@@ -2270,6 +3078,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn u32_literal_issue_works() {
+        for (text, expected_results) in [
+            ("1", None),
+            ("0xFFFF_FFFF", None),
+            ("-1", Some((U32LiteralIssue::Negative, Some("1")))),
+            ("4294967297", Some((U32LiteralIssue::Overflow, Some("1")))),
+            // Overflowing values that truncate to zero aren't replaced.
+            ("4294967296", Some((U32LiteralIssue::Overflow, None))),
+            ("0x1_0000_0000", Some((U32LiteralIssue::Overflow, None))),
+            ("0x1u32", Some((U32LiteralIssue::Suffixed, Some("0x1")))),
+            ("1_u8", Some((U32LiteralIssue::Suffixed, Some("1")))),
+            ("-1i32", Some((U32LiteralIssue::Negative, Some("1")))),
+            ("0XA", Some((U32LiteralIssue::MalformedHex, Some("0xA")))),
+            ("0xZZ", Some((U32LiteralIssue::MalformedHex, None))),
+            ("\"hello\"", None),
+        ] {
+            assert_eq!(
+                u32_literal_issue(text),
+                expected_results.map(|(issue, value)| (issue, value.map(ToString::to_string))),
+                "text: {text}"
+            );
+        }
+    }
+
     #[test]
     fn no_duplicate_attributes_and_arguments_works() {
         // NOTE: Unknown attributes are ignored by this test,
@@ -2289,13 +3122,34 @@ mod tests {
         // See `ensure_no_duplicate_attributes_and_arguments` doc.
         for (code, expected_quickfixes) in [
             (
-                r#"#[ink::contract(env=my::env::Types, keep_attr="foo,bar", keep_attr="hello")]"#, // duplicate `keep_attr`.
+                r#"#[ink::contract(env=my::env::Types, keep_attr="foo,bar", keep_attr="hello")]"#, // duplicate `keep_attr` with conflicting values.
+                vec![
+                    TestResultAction {
+                        label: "Keep only",
+                        edits: vec![TestResultTextRange {
+                            text: "",
+                            start_pat: Some(r#"<-, keep_attr="hello""#),
+                            end_pat: Some(r#"keep_attr="hello""#),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Keep only",
+                        edits: vec![TestResultTextRange {
+                            text: "",
+                            start_pat: Some(r#"<-keep_attr="foo,bar""#),
+                            end_pat: Some(r#"keep_attr="foo,bar","#),
+                        }],
+                    },
+                ],
+            ),
+            (
+                r#"#[ink::contract(env=my::env::Types, keep_attr="foo,bar", keep_attr="foo,bar")]"#, // duplicate `keep_attr`.
                 vec![TestResultAction {
                     label: "Remove",
                     edits: vec![TestResultTextRange {
                         text: "",
-                        start_pat: Some(r#"<-, keep_attr="hello""#),
-                        end_pat: Some(r#"keep_attr="hello""#),
+                        start_pat: Some(r#"<-, keep_attr="foo,bar")"#),
+                        end_pat: Some(r#"keep_attr="foo,bar"->"#),
                     }],
                 }],
             ),
@@ -2347,7 +3201,31 @@ mod tests {
                 #[ink(payable)]
                 #[ink(selector=2)]
                 #[ink(selector=0xA)]
-                "#, // duplicate `selector`.
+                "#, // duplicate `selector` with conflicting values.
+                vec![
+                    TestResultAction {
+                        label: "Keep only",
+                        edits: vec![TestResultTextRange {
+                            text: "",
+                            start_pat: Some("<-#[ink(selector=0xA)]"),
+                            end_pat: Some("#[ink(selector=0xA)]"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Keep only",
+                        edits: vec![TestResultTextRange {
+                            text: "",
+                            start_pat: Some("<-#[ink(selector=2)]"),
+                            end_pat: Some("#[ink(selector=2)]"),
+                        }],
+                    },
+                ],
+            ),
+            (
+                r#"
+                #[ink(message, selector=10)]
+                #[ink(selector=0xA)]
+                "#, // duplicate `selector` (i.e. same value in a different format).
                 vec![TestResultAction {
                     label: "Remove",
                     edits: vec![TestResultTextRange {
@@ -2357,6 +3235,52 @@ mod tests {
                     }],
                 }],
             ),
+            (
+                r#"
+                #[ink(message, selector=1)]
+                #[ink(selector=2, selector=3)]
+                "#, // duplicate `selector` with conflicting values in the same and sibling attributes.
+                vec![
+                    TestResultAction {
+                        label: "Keep only",
+                        edits: vec![TestResultTextRange {
+                            text: "",
+                            start_pat: Some("<-#[ink(selector=2, selector=3)]"),
+                            end_pat: Some("#[ink(selector=2, selector=3)]"),
+                        }],
+                    },
+                    TestResultAction {
+                        label: "Keep only",
+                        edits: vec![
+                            TestResultTextRange {
+                                text: "",
+                                start_pat: Some("<-, selector=1"),
+                                end_pat: Some("selector=1"),
+                            },
+                            TestResultTextRange {
+                                text: "",
+                                start_pat: Some("<-, selector=3"),
+                                end_pat: Some("selector=3"),
+                            },
+                        ],
+                    },
+                    TestResultAction {
+                        label: "Keep only",
+                        edits: vec![
+                            TestResultTextRange {
+                                text: "",
+                                start_pat: Some("<-, selector=1"),
+                                end_pat: Some("selector=1"),
+                            },
+                            TestResultTextRange {
+                                text: "",
+                                start_pat: Some("<-selector=2"),
+                                end_pat: Some("selector=2,"),
+                            },
+                        ],
+                    },
+                ],
+            ),
         ] {
             let attrs = parse_all_ink_attrs(code);
 
@@ -2925,4 +3849,240 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn valid_keep_attr_entries_works() {
+        for code in [
+            r#"#[ink::contract(keep_attr = "")]"#,
+            r#"#[ink::contract(keep_attr = "foo,bar")]"#,
+            r#"#[ink::contract(keep_attr = "cfg, derive, allow, rustfmt::skip,")]"#,
+            r#"#[ink::trait_definition(keep_attr = "foo")]"#,
+        ] {
+            let attr = parse_first_ink_attr(code);
+
+            let mut results = Vec::new();
+            ensure_valid_keep_attr_entries(&mut results, &attr);
+            assert!(results.is_empty(), "attribute: {code}");
+        }
+    }
+
+    #[test]
+    fn invalid_keep_attr_entries_fails() {
+        for (code, expected_severity, expected_quickfixes) in [
+            (
+                r#"#[ink::contract(keep_attr = "foo bar")]"#,
+                Severity::Error,
+                vec![TestResultAction {
+                    label: "Remove `foo bar`",
+                    edits: vec![TestResultTextRange {
+                        text: "",
+                        start_pat: Some("<-foo bar"),
+                        end_pat: Some("foo bar"),
+                    }],
+                }],
+            ),
+            (
+                r#"#[ink::contract(keep_attr = "::foo, bar")]"#,
+                Severity::Error,
+                vec![TestResultAction {
+                    label: "Remove `::foo`",
+                    edits: vec![TestResultTextRange {
+                        text: "",
+                        start_pat: Some("<-::foo"),
+                        end_pat: Some("<-bar"),
+                    }],
+                }],
+            ),
+            (
+                r#"#[ink::contract(keep_attr = "foo, bar, foo")]"#,
+                Severity::Warning,
+                vec![TestResultAction {
+                    label: "Remove `foo`",
+                    edits: vec![TestResultTextRange {
+                        text: "",
+                        start_pat: Some("bar"),
+                        end_pat: Some(r#"<-")"#),
+                    }],
+                }],
+            ),
+        ] {
+            let attr = parse_first_ink_attr(code);
+
+            let mut results = Vec::new();
+            ensure_valid_keep_attr_entries(&mut results, &attr);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "attribute: {code}");
+            assert_eq!(results[0].severity, expected_severity, "attribute: {code}");
+            // Verifies quickfixes.
+            verify_actions(
+                code,
+                results[0].quickfixes.as_ref().unwrap(),
+                &expected_quickfixes,
+            );
+        }
+    }
+
+    fn parse_env_arg_and_scope(code: &str) -> (InkArg, SyntaxNode) {
+        let contract = Contract::cast(parse_first_ink_attr(code)).unwrap();
+        let scope = contract
+            .module()
+            .and_then(ast::Module::item_list)
+            .unwrap()
+            .syntax()
+            .clone();
+        (contract.env_arg().unwrap(), scope)
+    }
+
+    #[test]
+    fn valid_environment_works() {
+        for code in [
+            // External environment types are ignored.
+            r#"
+            #[ink::contract(env = ink::env::DefaultEnvironment)]
+            mod my_contract {}
+            "#,
+            r#"
+            #[ink::contract(env = crate::MyEnvironment)]
+            mod my_contract {}
+
+            pub enum MyEnvironment {}
+
+            impl ink::env::Environment for MyEnvironment {
+                const MAX_EVENT_TOPICS: usize = 4;
+                type AccountId = [u8; 32];
+                type Balance = u128;
+                type Hash = [u8; 32];
+                type Timestamp = u64;
+                type BlockNumber = u32;
+                type ChainExtension = ink::env::NoChainExtension;
+            }
+            "#,
+            r#"
+            #[ink::contract(env = super::env::MyEnvironment)]
+            mod my_contract {}
+
+            mod env {
+                use ink::env::Environment;
+
+                pub struct MyEnvironment;
+
+                impl Environment for MyEnvironment {
+                    const MAX_EVENT_TOPICS: usize = 4;
+                    type AccountId = [u8; 32];
+                    type Balance = u128;
+                    type Hash = [u8; 32];
+                    type Timestamp = u64;
+                    type BlockNumber = u32;
+                    type ChainExtension = ink::env::NoChainExtension;
+                }
+            }
+            "#,
+            // Type aliases, out-of-line modules and imported items are ignored.
+            r#"
+            #[ink::contract(env = crate::MyEnvironment)]
+            mod my_contract {}
+
+            pub type MyEnvironment = ink::env::DefaultEnvironment;
+            "#,
+            r#"
+            #[ink::contract(env = crate::env::MyEnvironment)]
+            mod my_contract {}
+
+            mod env;
+            "#,
+            r#"
+            #[ink::contract(env = crate::MyEnvironment)]
+            mod my_contract {}
+
+            use my_env::MyEnvironment;
+            "#,
+        ] {
+            let (env_arg, scope) = parse_env_arg_and_scope(code);
+
+            let mut results = Vec::new();
+            ensure_valid_environment(&mut results, &env_arg, &scope);
+            assert!(results.is_empty(), "code: {code}");
+        }
+    }
+
+    #[test]
+    fn invalid_environment_fails() {
+        for (code, expected_quickfixes) in [
+            // Unresolved environment type.
+            (
+                r#"
+                #[ink::contract(env = crate::MyEnvironment)]
+                mod my_contract {}
+                "#,
+                vec![],
+            ),
+            (
+                r#"
+                #[ink::contract(env = crate::env::MyEnvironment)]
+                mod my_contract {}
+
+                mod env {}
+                "#,
+                vec![],
+            ),
+            // Missing `Environment` implementation.
+            (
+                r#"
+                #[ink::contract(env = crate::MyEnvironment)]
+                mod my_contract {}
+
+                pub enum MyEnvironment {}
+                "#,
+                vec![TestResultAction {
+                    label: "Add `ink::env::Environment` implementation",
+                    edits: vec![TestResultTextRange {
+                        text: "impl ink::env::Environment for MyEnvironment",
+                        start_pat: Some("pub enum MyEnvironment {}"),
+                        end_pat: Some("pub enum MyEnvironment {}"),
+                    }],
+                }],
+            ),
+            // Missing associated items.
+            (
+                r#"
+                #[ink::contract(env = crate::MyEnvironment)]
+                mod my_contract {}
+
+                pub enum MyEnvironment {}
+
+                impl ink::env::Environment for MyEnvironment {
+                    const MAX_EVENT_TOPICS: usize = 4;
+                    type AccountId = [u8; 32];
+                    type Hash = [u8; 32];
+                    type Timestamp = u64;
+                    type BlockNumber = u32;
+                }
+                "#,
+                vec![TestResultAction {
+                    label: "Add missing associated items",
+                    edits: vec![TestResultTextRange {
+                        text: "type Balance = <ink::env::DefaultEnvironment as ink::env::Environment>::Balance;",
+                        start_pat: Some("type BlockNumber = u32;"),
+                        end_pat: Some("type BlockNumber = u32;"),
+                    }],
+                }],
+            ),
+        ] {
+            let (env_arg, scope) = parse_env_arg_and_scope(code);
+
+            let mut results = Vec::new();
+            ensure_valid_environment(&mut results, &env_arg, &scope);
+
+            // Verifies diagnostics.
+            assert_eq!(results.len(), 1, "code: {code}");
+            assert_eq!(results[0].severity, Severity::Error, "code: {code}");
+            // Verifies quickfixes.
+            verify_actions(
+                code,
+                results[0].quickfixes.as_deref().unwrap_or_default(),
+                &expected_quickfixes,
+            );
+        }
+    }
 }
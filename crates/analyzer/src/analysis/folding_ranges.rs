@@ -0,0 +1,272 @@
+//! ink! entity folding ranges.
+
+use ink_analyzer_ir::syntax::{
+    AstNode, AstToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+use ink_analyzer_ir::{ast, FromAST, InkAttributeKind, InkFile, InkMacroKind, IsInkEntity};
+
+/// An ink! entity folding range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldingRange {
+    /// Range of the folding region.
+    pub range: TextRange,
+    /// Kind of the folding region.
+    pub kind: FoldingRangeKind,
+}
+
+/// The kind of an ink! entity folding range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldingRangeKind {
+    /// A block of code (e.g. an ink! contract module, an ink! impl block or an ink! test function body).
+    Region,
+    /// A block of doc comments (e.g. the docs of an ink! message).
+    Comment,
+}
+
+/// Minimum number of consecutive doc comment lines required to create a folding range.
+const MIN_DOC_COMMENT_LINES: usize = 3;
+
+/// Computes folding ranges for ink! entities
+/// (i.e. ink! contract modules, impl blocks containing ink! messages,
+/// ink! and ink! e2e test functions and long ink! entity doc comment blocks).
+pub fn folding_ranges(file: &InkFile) -> Vec<FoldingRange> {
+    let mut results = Vec::new();
+
+    // Computes folding ranges for ink! contract modules and impl blocks containing ink! messages.
+    for contract in file.contracts() {
+        if let Some(item_list) = contract.module().and_then(ast::Module::item_list) {
+            add_region(&mut results, item_list.syntax());
+        }
+
+        for ink_impl in contract.impls() {
+            if ink_impl.messages().is_empty() {
+                continue;
+            }
+            if let Some(assoc_item_list) = ink_impl
+                .impl_item()
+                .and_then(|impl_item| impl_item.assoc_item_list())
+            {
+                add_region(&mut results, assoc_item_list.syntax());
+            }
+        }
+    }
+
+    // Computes folding ranges for ink! and ink! e2e test functions,
+    // and for long doc comment blocks of all ink! attribute annotated items.
+    let mut items: Vec<SyntaxNode> = Vec::new();
+    for attr in file.tree().ink_attrs_in_scope() {
+        let Some(item) = attr.ast().syntax().parent() else {
+            continue;
+        };
+
+        if matches!(
+            attr.kind(),
+            InkAttributeKind::Macro(InkMacroKind::Test | InkMacroKind::E2ETest)
+        ) {
+            if let Some(body) = ast::Fn::cast(item.clone()).and_then(|fn_item| fn_item.body()) {
+                add_region(&mut results, body.syntax());
+            }
+        }
+
+        // Items can have multiple ink! attributes.
+        if !items.contains(&item) {
+            doc_comment_ranges(&mut results, &item);
+            items.push(item);
+        }
+    }
+
+    // Sorts folding ranges by position.
+    results.sort_by_key(|item| (item.range.start(), item.range.end()));
+    results
+}
+
+/// Adds a region folding range for the syntax node.
+fn add_region(results: &mut Vec<FoldingRange>, node: &SyntaxNode) {
+    results.push(FoldingRange {
+        range: node.text_range(),
+        kind: FoldingRangeKind::Region,
+    });
+}
+
+/// Adds comment folding ranges for blocks of consecutive doc comments (of the minimum length) of the item.
+fn doc_comment_ranges(results: &mut Vec<FoldingRange>, item: &SyntaxNode) {
+    let mut add_block = |block: &[TextRange]| {
+        if let (true, Some(first), Some(last)) = (
+            block.len() >= MIN_DOC_COMMENT_LINES,
+            block.first(),
+            block.last(),
+        ) {
+            results.push(FoldingRange {
+                range: TextRange::new(first.start(), last.end()),
+                kind: FoldingRangeKind::Comment,
+            });
+        }
+    };
+
+    // Only items that can have doc comments are considered.
+    if !ast::AnyHasDocComments::can_cast(item.kind()) {
+        return;
+    }
+    let mut block = Vec::new();
+    for elem in item.children_with_tokens() {
+        match elem {
+            SyntaxElement::Token(token)
+                if token.kind() == SyntaxKind::COMMENT
+                    && ast::Comment::cast(token.clone())
+                        .map_or(false, |comment| comment.is_doc()) =>
+            {
+                block.push(token.text_range());
+            }
+            // Whitespace and attributes don't break doc comment blocks.
+            SyntaxElement::Token(token) if token.kind() == SyntaxKind::WHITESPACE => (),
+            SyntaxElement::Node(node) if node.kind() == SyntaxKind::ATTR => (),
+            _ => {
+                add_block(&block);
+                block.clear();
+            }
+        }
+    }
+    add_block(&block);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_analyzer_ir::syntax::TextSize;
+    use test_utils::parse_offset_at;
+
+    #[test]
+    fn folding_ranges_works() {
+        for (code, expected_results) in [
+            // (code, [(kind, start_pat, end_pat)]) where:
+            // kind = the folding range kind,
+            // start_pat = substring used to find the start of the folding range,
+            // end_pat = substring used to find the end of the folding range.
+
+            // No ink! entities.
+            (
+                r#"
+                    mod my_module {
+                        fn my_fn() {}
+                    }
+                "#,
+                vec![],
+            ),
+            // Contract module.
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                    }
+                "#,
+                vec![(FoldingRangeKind::Region, Some("<-{"), Some("}->"))],
+            ),
+            // Contract module and impl block with an ink! message.
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        impl MyContract {
+                            #[ink(message)]
+                            pub fn my_message(&self) {}
+                        } // end impl
+                    }
+                "#,
+                vec![
+                    (FoldingRangeKind::Region, Some("<-{"), Some("}->")),
+                    (
+                        FoldingRangeKind::Region,
+                        Some("impl MyContract "),
+                        Some("<- // end impl"),
+                    ),
+                ],
+            ),
+            // Impl blocks without ink! messages are ignored.
+            (
+                r#"
+                    #[ink::contract]
+                    mod my_contract {
+                        impl MyContract {
+                            #[ink(constructor)]
+                            pub fn new() -> Self {}
+                        }
+                    }
+                "#,
+                vec![(FoldingRangeKind::Region, Some("<-{"), Some("}->"))],
+            ),
+            // ink! test and ink! e2e test.
+            (
+                r#"
+                    #[ink::test]
+                    fn it_works() {}
+
+                    #[ink_e2e::test]
+                    async fn it_works_e2e(mut client: ::ink_e2e::Client<C, E>) -> E2EResult<()> {}
+                "#,
+                vec![
+                    (
+                        FoldingRangeKind::Region,
+                        Some("it_works() "),
+                        Some("it_works() {}"),
+                    ),
+                    (
+                        FoldingRangeKind::Region,
+                        Some("E2EResult<()> "),
+                        Some("E2EResult<()> {}"),
+                    ),
+                ],
+            ),
+            // Long doc comment blocks.
+            (
+                r#"
+                    /// Line 1.
+                    /// Line 2.
+                    /// Line 3.
+                    #[ink::test]
+                    fn it_works() {}
+
+                    /// Line 1.
+                    /// Line 2.
+                    #[ink::test]
+                    fn it_works_again() {}
+                "#,
+                vec![
+                    (
+                        FoldingRangeKind::Comment,
+                        Some("<-/// Line 1."),
+                        Some("/// Line 3."),
+                    ),
+                    (
+                        FoldingRangeKind::Region,
+                        Some("it_works() "),
+                        Some("it_works() {}"),
+                    ),
+                    (
+                        FoldingRangeKind::Region,
+                        Some("it_works_again() "),
+                        Some("it_works_again() {}"),
+                    ),
+                ],
+            ),
+        ] {
+            let results = folding_ranges(&InkFile::parse(code));
+
+            assert_eq!(
+                results
+                    .into_iter()
+                    .map(|item| (item.kind, item.range))
+                    .collect::<Vec<(FoldingRangeKind, TextRange)>>(),
+                expected_results
+                    .into_iter()
+                    .map(|(kind, start_pat, end_pat)| (
+                        kind,
+                        TextRange::new(
+                            TextSize::from(parse_offset_at(code, start_pat).unwrap() as u32),
+                            TextSize::from(parse_offset_at(code, end_pat).unwrap() as u32)
+                        )
+                    ))
+                    .collect::<Vec<(FoldingRangeKind, TextRange)>>()
+            );
+        }
+    }
+}
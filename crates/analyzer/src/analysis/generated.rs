@@ -0,0 +1,325 @@
+//! A lightweight model of the items generated by the `#[ink::contract]` attribute macro
+//! (e.g. `Self::env()`, `EnvAccess` methods, the event emit API and contract references).
+//!
+//! Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/ink/src/env_access.rs>.
+//!
+//! Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/ink/codegen/src/generator/env.rs>.
+
+use ink_analyzer_ir::ast::HasName;
+use ink_analyzer_ir::{Contract, IsInkStruct};
+
+/// A method of the ink! environment (i.e. the `EnvAccess` value returned by the
+/// `self.env()` and `Self::env()` accessors generated by `#[ink::contract]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvMethod {
+    /// Name of the method.
+    pub name: &'static str,
+    /// Parameters of the method (excluding the `self` parameter).
+    pub params: &'static [&'static str],
+    /// Return type of the method (if any).
+    pub return_type: Option<&'static str>,
+    /// Short description of the method.
+    pub detail: &'static str,
+}
+
+impl EnvMethod {
+    /// Returns the signature of the method (e.g. `fn caller(self) -> AccountId`).
+    pub fn signature(&self) -> String {
+        let params = ["self"]
+            .into_iter()
+            .chain(self.params.iter().copied())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        match self.return_type {
+            Some(return_type) => format!("fn {}({params}) -> {return_type}", self.name),
+            None => format!("fn {}({params})", self.name),
+        }
+    }
+
+    /// Returns the plain text and snippet for a call to the method
+    /// (e.g. `transfer(destination, value)` and `transfer(${1:destination}, ${2:value})`).
+    pub fn call(&self) -> (String, String) {
+        let names: Vec<&str> = self
+            .params
+            .iter()
+            .map(|param| {
+                param
+                    .split_once(':')
+                    .map_or(*param, |(name, _)| name.trim())
+            })
+            .collect();
+        (
+            format!("{}({})", self.name, names.join(", ")),
+            format!(
+                "{}({})",
+                self.name,
+                names
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, name)| format!("${{{}:{name}}}", idx + 1))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        )
+    }
+
+    /// Returns the documentation (in markdown format) for the method.
+    pub fn doc(&self) -> String {
+        let (call, _) = self.call();
+        format!(
+            "# Function\n\n`{}`\n\n# Description\n\n{}\n\n# Usage\n\n\
+            Via the environment of an ink! contract (i.e. `self.env().{call}` or `Self::env().{call}`).",
+            self.signature(),
+            self.detail,
+        )
+    }
+}
+
+/// Methods of the ink! environment (i.e. `EnvAccess` methods).
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/ink/src/env_access.rs>.
+pub const ENV_METHODS: &[EnvMethod] = &[
+    EnvMethod {
+        name: "account_id",
+        params: &[],
+        return_type: Some("AccountId"),
+        detail: "Returns the account ID of the executed contract.",
+    },
+    EnvMethod {
+        name: "balance",
+        params: &[],
+        return_type: Some("Balance"),
+        detail: "Returns the balance of the executed contract.",
+    },
+    EnvMethod {
+        name: "block_number",
+        params: &[],
+        return_type: Some("BlockNumber"),
+        detail: "Returns the current block number.",
+    },
+    EnvMethod {
+        name: "block_timestamp",
+        params: &[],
+        return_type: Some("Timestamp"),
+        detail: "Returns the timestamp of the current block.",
+    },
+    EnvMethod {
+        name: "caller",
+        params: &[],
+        return_type: Some("AccountId"),
+        detail: "Returns the address of the caller of the executed contract.",
+    },
+    EnvMethod {
+        name: "caller_is_origin",
+        params: &[],
+        return_type: Some("bool"),
+        detail: "Checks whether the caller of the current contract is the origin of the whole call stack.",
+    },
+    EnvMethod {
+        name: "code_hash",
+        params: &["account_id: &AccountId"],
+        return_type: Some("Result<Hash>"),
+        detail: "Returns the code hash of the contract at the given account ID.",
+    },
+    EnvMethod {
+        name: "ecdsa_recover",
+        params: &["signature: &[u8; 65]", "message_hash: &[u8; 32]"],
+        return_type: Some("Result<[u8; 33]>"),
+        detail: "Recovers the compressed ECDSA public key for the given signature and message hash.",
+    },
+    EnvMethod {
+        name: "ecdsa_to_eth_address",
+        params: &["pubkey: &[u8; 33]"],
+        return_type: Some("Result<[u8; 20]>"),
+        detail: "Returns the Ethereum address from the given compressed ECDSA public key.",
+    },
+    EnvMethod {
+        name: "emit_event",
+        params: &["event: Event"],
+        return_type: None,
+        detail: "Emits the given ink! event.",
+    },
+    EnvMethod {
+        name: "gas_left",
+        params: &[],
+        return_type: Some("u64"),
+        detail: "Returns the amount of gas left for the contract execution.",
+    },
+    EnvMethod {
+        name: "hash_bytes",
+        params: &["input: &[u8]"],
+        return_type: Some("H::Type"),
+        detail: "Computes the hash of the given bytes using the cryptographic hash `H`.",
+    },
+    EnvMethod {
+        name: "hash_encoded",
+        params: &["value: &T"],
+        return_type: Some("H::Type"),
+        detail: "Computes the hash of the given SCALE encoded value using the cryptographic hash `H`.",
+    },
+    EnvMethod {
+        name: "instantiate_contract",
+        params: &["params: &CreateParams"],
+        return_type: Some("Result<ConstructorResult<ContractRef>>"),
+        detail: "Instantiates another contract.",
+    },
+    EnvMethod {
+        name: "invoke_contract",
+        params: &["params: &CallParams"],
+        return_type: Some("Result<MessageResult<R>>"),
+        detail: "Invokes a contract message and returns its result.",
+    },
+    EnvMethod {
+        name: "invoke_contract_delegate",
+        params: &["params: &CallParams"],
+        return_type: Some("Result<MessageResult<R>>"),
+        detail: "Invokes a contract message via delegate call and returns its result.",
+    },
+    EnvMethod {
+        name: "is_contract",
+        params: &["account_id: &AccountId"],
+        return_type: Some("bool"),
+        detail: "Checks whether the given account ID is a contract.",
+    },
+    EnvMethod {
+        name: "minimum_balance",
+        params: &[],
+        return_type: Some("Balance"),
+        detail: "Returns the minimum balance that is required for creating an account (i.e. the existential deposit).",
+    },
+    EnvMethod {
+        name: "own_code_hash",
+        params: &[],
+        return_type: Some("Result<Hash>"),
+        detail: "Returns the code hash of the executed contract.",
+    },
+    EnvMethod {
+        name: "set_code_hash",
+        params: &["code_hash: &Hash"],
+        return_type: Some("Result<()>"),
+        detail: "Replaces the code of the executed contract with the code at the given code hash.",
+    },
+    EnvMethod {
+        name: "terminate_contract",
+        params: &["beneficiary: AccountId"],
+        return_type: Some("!"),
+        detail: "Terminates the executed contract and transfers its remaining balance to the beneficiary.",
+    },
+    EnvMethod {
+        name: "transfer",
+        params: &["destination: AccountId", "value: Balance"],
+        return_type: Some("Result<()>"),
+        detail: "Transfers the value from the executed contract to the destination account ID.",
+    },
+    EnvMethod {
+        name: "transferred_value",
+        params: &[],
+        return_type: Some("Balance"),
+        detail: "Returns the transferred value for the contract execution.",
+    },
+    EnvMethod {
+        name: "weight_to_fee",
+        params: &["gas: u64"],
+        return_type: Some("Balance"),
+        detail: "Returns the price for the specified amount of gas.",
+    },
+];
+
+/// Returns the ink! environment method with the given name (if any).
+pub fn env_method(name: &str) -> Option<&'static EnvMethod> {
+    ENV_METHODS.iter().find(|method| method.name == name)
+}
+
+/// Returns the documentation (in markdown format) for the ink! environment accessors
+/// (i.e. `self.env()` and `Self::env()`) generated by `#[ink::contract]`.
+pub fn env_accessor_doc() -> &'static str {
+    "# Function\n\n`fn env() -> EnvAccess<'static, Environment>`\n\n# Description\n\n\
+    Returns the ink! environment of the contract (i.e. for accessing information about the \
+    contract execution and its environment, and for emitting ink! events).\n\n# Usage\n\n\
+    Generated by `#[ink::contract]` for the ink! storage struct (i.e. `self.env()` in methods \
+    and `Self::env()` in associated functions)."
+}
+
+/// Returns the name of the contract reference type generated by `#[ink::contract]`
+/// (i.e. the ink! storage struct name with a `Ref` suffix, e.g. `FlipperRef`) (if any).
+///
+/// Contract references are used for calling the ink! contract from other ink! contracts and e2e tests.
+pub fn contract_ref_name(contract: &Contract) -> Option<String> {
+    contract
+        .storage()?
+        .struct_item()?
+        .name()
+        .map(|name| format!("{name}Ref"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_analyzer_ir::InkFile;
+    use test_utils::quote_as_str;
+
+    #[test]
+    fn env_method_works() {
+        for (name, expected_signature, expected_call) in [
+            (
+                "caller",
+                Some("fn caller(self) -> AccountId"),
+                Some("caller()"),
+            ),
+            (
+                "transfer",
+                Some("fn transfer(self, destination: AccountId, value: Balance) -> Result<()>"),
+                Some("transfer(${1:destination}, ${2:value})"),
+            ),
+            (
+                "emit_event",
+                Some("fn emit_event(self, event: Event)"),
+                Some("emit_event(${1:event})"),
+            ),
+            ("flip", None, None),
+        ] {
+            let method = env_method(name);
+            assert_eq!(
+                method.map(EnvMethod::signature).as_deref(),
+                expected_signature,
+                "name: {name}"
+            );
+            assert_eq!(
+                method.map(|method| method.call().1).as_deref(),
+                expected_call,
+                "name: {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn contract_ref_name_works() {
+        for (code, expected_name) in [
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod flipper {
+                        #[ink(storage)]
+                        pub struct Flipper {}
+                    }
+                },
+                Some("FlipperRef"),
+            ),
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod flipper {}
+                },
+                None,
+            ),
+        ] {
+            let file = InkFile::parse(code);
+            let contract = file.contracts().first().unwrap();
+            assert_eq!(
+                contract_ref_name(contract).as_deref(),
+                expected_name,
+                "code: {code}"
+            );
+        }
+    }
+}
@@ -1,28 +1,53 @@
-//! ink! attribute hover content.
+//! ink! attribute, ink! type and ink! environment function hover content.
 
-use ink_analyzer_ir::syntax::{AstNode, AstToken, TextRange};
-use ink_analyzer_ir::{FromSyntax, InkAttributeKind, InkFile};
+use ink_analyzer_ir::ast::HasName;
+use ink_analyzer_ir::meta::MetaValue;
+use ink_analyzer_ir::syntax::{AstNode, AstToken, SyntaxElement, SyntaxNode, TextRange};
+use ink_analyzer_ir::{
+    ast, Constructor, Contract, FromInkAttribute, FromSyntax, InkArg, InkArgKind, InkAttribute,
+    InkAttributeKind, InkFile, InkMacroKind, IsInkCallable, Message, TraitDefinition,
+};
 
-use crate::analysis::utils;
+use crate::analysis::queries::Queries;
+use crate::analysis::{generated, utils};
+use crate::Version;
+use std::path::Path;
 
-mod content;
+pub mod content;
 
-/// An ink! attribute hover result.
+/// An ink! hover result.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hover {
     /// Range the hover content applies to.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
     pub range: TextRange,
     /// Hover text.
     pub content: String,
 }
 
-/// Returns descriptive/informational text for the ink! attribute at the given position (if any).
-pub fn hover(file: &InkFile, range: TextRange) -> Option<Hover> {
-    // Finds the covering ink! attribute for the text range (if any).
-    let covering_ink_attr = utils::covering_ink_attribute(file, range);
+/// Configuration for ink! attribute hover content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HoverConfig {
+    /// Whether the hover range should cover the whole ink! attribute
+    /// instead of the smallest meaningful element (i.e. the argument name, argument value or macro path segment).
+    pub whole_attribute: bool,
+}
+
+/// Returns descriptive/informational text for the ink! attribute, ink! type or ink! environment function
+/// at the given position (if any).
+pub fn hover(
+    file: &InkFile,
+    queries: &Queries,
+    range: TextRange,
+    version: Version,
+    config: &HoverConfig,
+) -> Option<Hover> {
+    // Finds the covering ink! attribute for the text range (if any) using the ink! entity index.
+    let covering_ink_attr = utils::indexed_covering_ink_attribute(file, queries, range);
 
-    // Returns hover content only if the text range is covered by an ink! attribute.
-    covering_ink_attr.and_then(|ink_attr| {
+    // Returns ink! attribute hover content if the text range is covered by an ink! attribute.
+    let ink_attr_hover = covering_ink_attr.and_then(|ink_attr| {
         // Finds the covered ink! attribute argument (if any).
         let ink_arg = ink_attr
             .args()
@@ -34,10 +59,17 @@ pub fn hover(file: &InkFile, range: TextRange) -> Option<Hover> {
                 let attr_kind = InkAttributeKind::Arg(*ink_arg.kind());
                 let doc = content::doc(&attr_kind);
                 (!doc.is_empty()).then_some(Hover {
-                    range: ink_arg.name().map_or(ink_arg.text_range(), |ink_arg_name| {
-                        ink_arg_name.syntax().text_range()
-                    }),
-                    content: doc.to_string(),
+                    range: if config.whole_attribute {
+                        ink_attr.syntax().text_range()
+                    } else {
+                        ink_arg_hover_range(ink_arg, range)
+                    },
+                    content: format!(
+                        "{doc}{}{}{}",
+                        version_doc(&ink_attr, &attr_kind, version),
+                        value_doc(ink_arg, range),
+                        dispatch_doc(&ink_attr, ink_arg)
+                    ),
                 })
             }
             // Returns hover content based on the ink! attribute macro, ink! e2e attribute macro
@@ -47,6 +79,7 @@ pub fn hover(file: &InkFile, range: TextRange) -> Option<Hover> {
                 let doc = content::doc(ink_attr.kind());
                 (!doc.is_empty()).then_some(Hover {
                     range: match ink_attr.kind() {
+                        _ if config.whole_attribute => ink_attr.syntax().text_range(),
                         InkAttributeKind::Arg(_) => ink_attr
                             .ink_arg_name()
                             .map_or(ink_attr.syntax().text_range(), |ink_arg_name| {
@@ -58,18 +91,371 @@ pub fn hover(file: &InkFile, range: TextRange) -> Option<Hover> {
                                 path_segment.syntax().text_range()
                             }),
                     },
-                    content: doc.to_string(),
+                    content: format!(
+                        "{doc}{}{}",
+                        version_doc(&ink_attr, ink_attr.kind(), version),
+                        cfg_doc(&ink_attr)
+                    ),
                 })
             }
         }
+    });
+
+    // Otherwise, returns hover content for ink! types, ink! environment types and ink! environment functions
+    // or the `e2e-tests` feature `cfg` attribute (if any).
+    ink_attr_hover
+        .or_else(|| path_hover(file, range))
+        .or_else(|| cfg_hover(file, range))
+}
+
+/// Returns descriptive/informational text for the ink! type (e.g. `ink::storage::Mapping`),
+/// ink! environment type (e.g. `AccountId` and `Balance`) or
+/// ink! environment function (e.g. `caller` and `transferred_value`) reference at the given text range (if any).
+fn path_hover(file: &InkFile, range: TextRange) -> Option<Hover> {
+    let name_ref = utils::focused_element(file, range).and_then(|covering_element| {
+        ink_analyzer_ir::closest_ancestor_ast_type::<SyntaxElement, ast::NameRef>(&covering_element)
+    })?;
+    let name = name_ref.text();
+    let is_in_contract = |node: &SyntaxNode| {
+        ink_analyzer_ir::ink_ancestors::<Contract>(node)
+            .next()
+            .is_some()
+    };
+    let doc = match name_ref.syntax().parent()? {
+        // Path references (e.g. `Mapping`, `ink::storage::Mapping`, `AccountId` or `ink::env::caller`).
+        parent if ast::PathSegment::can_cast(parent.kind()) => {
+            let path = ast::PathSegment::cast(parent)?.parent_path();
+            match path.qualifier() {
+                // Generated ink! environment accessor for associated functions (i.e. `Self::env()`).
+                Some(qualifier)
+                    if name == "env"
+                        && qualifier.syntax().to_string() == "Self"
+                        && is_in_contract(path.syntax()) =>
+                {
+                    Some(generated::env_accessor_doc().to_string())
+                }
+                // Qualified paths must be rooted at an ink! crate.
+                Some(qualifier) => is_ink_crate_path(&qualifier).then(|| {
+                    let type_doc = content::type_doc(name.as_str());
+                    if type_doc.is_empty() {
+                        content::env_fn_doc(name.as_str()).to_string()
+                    } else {
+                        type_doc.to_string()
+                    }
+                }),
+                // Unqualified ink! types and ink! environment types are only meaningful inside an ink! contract.
+                None => is_in_contract(path.syntax())
+                    .then(|| content::type_doc(name.as_str()).to_string()),
+            }
+        }
+        // Environment method calls (e.g. `self.env().caller()` or `Self::env().transferred_value()`).
+        parent => ast::MethodCallExpr::cast(parent).and_then(|call| {
+            if call.name_ref().as_ref() != Some(&name_ref) {
+                return None;
+            }
+            if call.receiver().as_ref().map_or(false, utils::is_env_call) {
+                // Prefers curated documentation, and falls back to the generated items model.
+                let doc = content::env_fn_doc(name.as_str());
+                if doc.is_empty() {
+                    generated::env_method(name.as_str()).map(generated::EnvMethod::doc)
+                } else {
+                    Some(doc.to_string())
+                }
+            } else {
+                // Generated ink! environment accessor for methods (i.e. `self.env()`).
+                (utils::is_env_call(&ast::Expr::MethodCallExpr(call.clone()))
+                    && is_in_contract(call.syntax()))
+                .then(|| generated::env_accessor_doc().to_string())
+            }
+        }),
+    }?;
+
+    (!doc.is_empty()).then_some(Hover {
+        range: name_ref.syntax().text_range(),
+        content: doc,
     })
 }
 
+/// Returns descriptive/informational text for the `cfg` attribute that gates ink! e2e tests
+/// behind the `e2e-tests` feature (i.e. `#[cfg(all(test, feature = "e2e-tests"))]`) at the given text range (if any).
+fn cfg_hover(file: &InkFile, range: TextRange) -> Option<Hover> {
+    let attr = utils::focused_element(file, range).and_then(|covering_element| {
+        ink_analyzer_ir::closest_ancestor_ast_type::<SyntaxElement, ast::Attr>(&covering_element)
+    })?;
+    utils::is_cfg_e2e_tests_attr(&attr).then(|| Hover {
+        range: attr.syntax().text_range(),
+        content: content::e2e_tests_cfg_doc().to_string(),
+    })
+}
+
+/// Returns true if the path is rooted at an ink! crate (e.g. `ink::storage` or `ink_env`).
+fn is_ink_crate_path(path: &ast::Path) -> bool {
+    std::iter::successors(Some(path.clone()), ast::Path::qualifier)
+        .last()
+        .and_then(|root| root.segment())
+        .and_then(|segment| segment.name_ref())
+        .map_or(false, |name_ref| {
+            matches!(
+                name_ref.text().as_str(),
+                "ink" | "ink_env" | "ink_storage" | "ink_primitives"
+            )
+        })
+}
+
+/// Returns the smallest meaningful text range (i.e. the value or the name) of the ink! attribute argument
+/// for the given focus range.
+fn ink_arg_hover_range(ink_arg: &InkArg, range: TextRange) -> TextRange {
+    ink_arg
+        .value()
+        .map(MetaValue::text_range)
+        .filter(|value_range| value_range.contains_range(range))
+        .or(ink_arg
+            .name()
+            .map(|ink_arg_name| ink_arg_name.syntax().text_range()))
+        .unwrap_or(ink_arg.text_range())
+}
+
+/// Returns ink! version specific documentation (if any) for the ink! attribute kind in the context of the ink! attribute.
+fn version_doc(
+    ink_attr: &InkAttribute,
+    attr_kind: &InkAttributeKind,
+    version: Version,
+) -> &'static str {
+    match attr_kind {
+        // How `payable` ink! constructors can reject an instantiation differs between ink! versions
+        // (i.e. ink! 4.x constructors can be fallible).
+        InkAttributeKind::Arg(InkArgKind::Payable) => {
+            let is_constructor = ink_attr.syntax().parent().map_or(false, |parent| {
+                ink_analyzer_ir::ink_arg_by_kind(&parent, InkArgKind::Constructor).is_some()
+            });
+            if is_constructor {
+                content::payable_constructor_doc(version)
+            } else {
+                ""
+            }
+        }
+        _ => "",
+    }
+}
+
+/// Returns documentation (if any) for the value of the ink! attribute argument
+/// if the given text range is covered by the value
+/// (i.e. the decimal and hexadecimal representations of `selector` values).
+fn value_doc(ink_arg: &InkArg, range: TextRange) -> String {
+    match ink_arg.kind() {
+        InkArgKind::Selector => ink_arg
+            .value()
+            .filter(|value| value.text_range().contains_range(range))
+            .and_then(|_| ink_arg.as_u32().ok())
+            .map(|value| format!("\n\nValue: `{value}` (decimal), `{value:#010X}` (hexadecimal)."))
+            .unwrap_or_default(),
+        InkArgKind::AdditionalContracts => {
+            let is_value_covered = ink_arg
+                .value()
+                .map_or(false, |value| value.text_range().contains_range(range));
+            if !is_value_covered {
+                return String::new();
+            }
+
+            // Describes the focused entry (if any), otherwise all entries.
+            let entries = utils::additional_contracts_entries(ink_arg);
+            let focused_entries: Vec<&(String, TextRange)> = entries
+                .iter()
+                .filter(|(_, entry_range)| entry_range.contains_range(range))
+                .collect();
+            let entries: Vec<&(String, TextRange)> = if focused_entries.is_empty() {
+                entries.iter().collect()
+            } else {
+                focused_entries
+            };
+            if entries.is_empty() {
+                return String::new();
+            }
+            let entries_doc = entries
+                .iter()
+                .map(|(entry, _)| {
+                    let path = Path::new(entry);
+                    let is_manifest = path.file_name().map_or(false, |name| name == "Cargo.toml");
+                    let contract_name = path
+                        .parent()
+                        .and_then(Path::file_name)
+                        .and_then(|name| name.to_str())
+                        .filter(|name| !name.is_empty());
+                    match contract_name {
+                        Some(contract_name) if is_manifest => {
+                            format!("- `{entry}` (i.e. the `{contract_name}` contract)")
+                        }
+                        _ if is_manifest => format!("- `{entry}`"),
+                        _ => format!("- `{entry}` (not a `Cargo.toml` path)"),
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+            format!(
+                "\n\nAdditional contracts (relative to the root directory of the ink! contract crate):\n{entries_doc}"
+            )
+        }
+        _ => String::new(),
+    }
+}
+
+/// Returns dispatch related documentation (if any) for the `selector` argument of an ink! constructor
+/// or ink! message in an ink! contract (i.e. the composed selector used for dispatch,
+/// whether it collides with the selector of another ink! constructor or ink! message
+/// and whether wildcard semantics apply),
+/// or for the `message` and `selector` arguments of an ink! message in an ink! trait definition
+/// (i.e. the selector the ink! message has in implementations of the ink! trait definition).
+fn dispatch_doc(ink_attr: &InkAttribute, ink_arg: &InkArg) -> String {
+    if !matches!(ink_arg.kind(), InkArgKind::Selector | InkArgKind::Message) {
+        return String::new();
+    }
+    let Some(fn_node) = ink_attr
+        .syntax()
+        .parent()
+        .filter(|parent| ast::Fn::can_cast(parent.kind()))
+    else {
+        return String::new();
+    };
+    if ink_analyzer_ir::ink_ancestors::<TraitDefinition>(&fn_node)
+        .next()
+        .is_some()
+    {
+        return ink_analyzer_ir::ink_attrs(&fn_node)
+            .find_map(Message::cast)
+            .map_or(String::new(), |message| {
+                trait_definition_message_doc(&message)
+            });
+    }
+    if *ink_arg.kind() != InkArgKind::Selector {
+        return String::new();
+    }
+    let Some(contract) = ink_analyzer_ir::ink_ancestors::<Contract>(&fn_node).next() else {
+        return String::new();
+    };
+    if let Some(message) = ink_analyzer_ir::ink_attrs(&fn_node).find_map(Message::cast) {
+        callable_dispatch_doc(&message, contract.messages(), "message")
+    } else if let Some(constructor) =
+        ink_analyzer_ir::ink_attrs(&fn_node).find_map(Constructor::cast)
+    {
+        callable_dispatch_doc(&constructor, contract.constructors(), "constructor")
+    } else {
+        String::new()
+    }
+}
+
+/// Returns dispatch related documentation for an ink! callable
+/// given its sibling ink! callables of the same kind (i.e. ink! constructors or ink! messages).
+fn callable_dispatch_doc<T>(callable: &T, siblings: &[T], ink_scope_name: &str) -> String
+where
+    T: IsInkCallable,
+{
+    let Some(fn_range) = callable
+        .fn_item()
+        .map(|fn_item| fn_item.syntax().text_range())
+    else {
+        return String::new();
+    };
+    let is_wildcard = |callable: &T| {
+        callable
+            .selector_arg()
+            .map_or(false, |selector_arg| selector_arg.is_wildcard())
+    };
+    let others = siblings.iter().filter(|sibling| {
+        sibling
+            .fn_item()
+            .map_or(false, |fn_item| fn_item.syntax().text_range() != fn_range)
+    });
+    let fn_names = |callables: Vec<&T>| {
+        callables
+            .into_iter()
+            .filter_map(|callable| callable.fn_item().and_then(HasName::name))
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    if is_wildcard(callable) {
+        let other_wildcards: Vec<&T> = others.filter(|other| is_wildcard(other)).collect();
+        let collision_doc = if other_wildcards.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "\n\n**Collides** with the wildcard selector of the {} ink! {ink_scope_name}(s) \
+                (i.e. at most one wildcard selector is allowed).",
+                fn_names(other_wildcards)
+            )
+        };
+        format!(
+            "\n\nWildcard selector: calls with a selector that doesn't match \
+            any other ink! {ink_scope_name} are dispatched to this ink! {ink_scope_name}.{collision_doc}"
+        )
+    } else if let Some(selector) = callable.composed_selector() {
+        let collisions: Vec<&T> = others
+            .filter(|other| other.composed_selector() == Some(selector))
+            .collect();
+        let collision_doc = if collisions.is_empty() {
+            format!("It's unique among the ink! {ink_scope_name}s of the ink! contract.")
+        } else {
+            format!(
+                "**Collides** with the selector of the {} ink! {ink_scope_name}(s).",
+                fn_names(collisions)
+            )
+        };
+        format!(
+            "\n\nDispatch selector: `0x{:08x}`.\n\n{collision_doc}",
+            selector.into_be_u32()
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Returns selector related documentation for an ink! message in an ink! trait definition
+/// (i.e. the selector the ink! message has in implementations of the ink! trait definition,
+/// which is computed from the trait identifier and the namespace of the ink! trait definition
+/// unless a selector is provided explicitly).
+fn trait_definition_message_doc(message: &Message) -> String {
+    match message.composed_selector() {
+        Some(selector) => {
+            let source_doc = if message.selector_arg().is_some() {
+                "provided explicitly"
+            } else {
+                "computed from the trait identifier and the namespace of the ink! trait definition"
+            };
+            format!(
+                "\n\nSelector in implementations of the ink! trait definition: `0x{:08x}` ({source_doc}).",
+                selector.into_be_u32()
+            )
+        }
+        None => String::new(),
+    }
+}
+
+/// Returns conditional compilation related documentation (if any) for the ink! attribute.
+fn cfg_doc(ink_attr: &InkAttribute) -> &'static str {
+    match ink_attr.kind() {
+        // ink! e2e tests should be gated behind the `e2e-tests` feature.
+        InkAttributeKind::Macro(InkMacroKind::E2ETest) => {
+            let is_ungated_fn = ink_attr
+                .syntax()
+                .parent()
+                .filter(|parent| ast::Fn::can_cast(parent.kind()))
+                .map_or(false, |parent| !utils::is_cfg_e2e_tests_gated(&parent));
+            if is_ungated_fn {
+                content::ungated_e2e_test_doc()
+            } else {
+                ""
+            }
+        }
+        _ => "",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use ink_analyzer_ir::syntax::TextSize;
-    use ink_analyzer_ir::{InkArgKind, InkMacroKind};
+    use ink_analyzer_ir::InkArgValueKind;
     use test_utils::parse_offset_at;
 
     #[test]
@@ -179,8 +565,8 @@ mod tests {
                         Some("my::env::Types"),
                         Some((
                             content::doc(&InkAttributeKind::Arg(InkArgKind::Env)),
-                            Some("<-env="),
-                            Some("(env"),
+                            Some("<-my::env::Types"),
+                            Some("my::env::Types"),
                         )),
                     ),
                     (
@@ -206,8 +592,8 @@ mod tests {
                         Some(r#""foo,bar""#),
                         Some((
                             content::doc(&InkAttributeKind::Arg(InkArgKind::KeepAttr)),
-                            Some("<-keep_attr"),
-                            Some("keep_attr"),
+                            Some(r#"<-"foo,bar""#),
+                            Some(r#""foo,bar""#),
                         )),
                     ),
                 ],
@@ -357,8 +743,8 @@ mod tests {
                         Some("_"),
                         Some((
                             content::doc(&InkAttributeKind::Arg(InkArgKind::Selector)),
-                            Some("<-selector"),
-                            Some("selector"),
+                            Some("<-_"),
+                            Some("_"),
                         )),
                     ),
                 ],
@@ -407,8 +793,8 @@ mod tests {
                         Some("1"),
                         Some((
                             content::doc(&InkAttributeKind::Arg(InkArgKind::Extension)),
-                            Some("<-extension"),
-                            Some("extension"),
+                            Some("<-1"),
+                            Some("1"),
                         )),
                     ),
                     (
@@ -425,8 +811,8 @@ mod tests {
                         Some("true"),
                         Some((
                             content::doc(&InkAttributeKind::Arg(InkArgKind::HandleStatus)),
-                            Some("<-handle_status"),
-                            Some("handle_status"),
+                            Some("<-true"),
+                            Some("true"),
                         )),
                     ),
                 ],
@@ -438,7 +824,13 @@ mod tests {
                     TextSize::from(parse_offset_at(code, pat_end).unwrap() as u32),
                 );
 
-                let result = hover(&InkFile::parse(code), range);
+                let result = hover(
+                    &InkFile::parse(code),
+                    &Queries::default(),
+                    range,
+                    Version::V4,
+                    &HoverConfig::default(),
+                );
 
                 assert_eq!(
                     result
@@ -455,4 +847,606 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn type_and_env_fn_hover_works() {
+        let code = r#"
+            use ink::storage::Mapping;
+
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {
+                    balances: Mapping<AccountId, Balance>,
+                    data: ink::storage::Lazy<Hash>,
+                }
+
+                impl MyContract {
+                    #[ink(message, payable)]
+                    pub fn my_message(&mut self) {
+                        let caller = self.env().caller();
+                        let value = Self::env().transferred_value();
+                        let other_caller = ink::env::caller::<Environment>();
+                        let not_env = self.caller();
+                        let unknown = self.env().unknown();
+                        let number = self.env().block_number();
+                    }
+                }
+            }
+
+            fn my_fn(value: Balance) -> my::storage::Mapping {}
+        "#;
+        let block_number_doc = generated::env_method("block_number").unwrap().doc();
+
+        for (pat_start, pat_end, expected_doc) in [
+            // (pat_start, pat_end, doc) where:
+            // pat_start = substring used to find the start of the focus and expected hover offset (see `test_utils::parse_offset_at` doc),
+            // pat_end = substring used to find the end of the focus and expected hover offset (see `test_utils::parse_offset_at` doc),
+            // doc = expected hover content (if any).
+
+            // ink! types.
+            ("<-Mapping;", "Mapping", Some(content::type_doc("Mapping"))),
+            (
+                "<-Mapping<AccountId",
+                "balances: Mapping",
+                Some(content::type_doc("Mapping")),
+            ),
+            ("<-Lazy", "Lazy", Some(content::type_doc("Lazy"))),
+            // ink! environment types.
+            (
+                "<-AccountId,",
+                "AccountId",
+                Some(content::type_doc("AccountId")),
+            ),
+            ("<-Balance>", "Balance", Some(content::type_doc("Balance"))),
+            ("<-Hash>", "Hash", Some(content::type_doc("Hash"))),
+            // ink! environment functions.
+            (
+                "<-caller();",
+                "env().caller",
+                Some(content::env_fn_doc("caller")),
+            ),
+            (
+                "<-transferred_value",
+                "transferred_value",
+                Some(content::env_fn_doc("transferred_value")),
+            ),
+            (
+                "<-caller::<Environment>",
+                "ink::env::caller",
+                Some(content::env_fn_doc("caller")),
+            ),
+            // Generated ink! environment methods and accessors.
+            (
+                "<-block_number",
+                "block_number",
+                Some(block_number_doc.as_str()),
+            ),
+            (
+                "<-env().caller",
+                "caller = self.env",
+                Some(generated::env_accessor_doc()),
+            ),
+            (
+                "<-env().transferred_value",
+                "Self::env",
+                Some(generated::env_accessor_doc()),
+            ),
+            // Not ink! environment calls.
+            ("not_env = self.", "not_env = self.caller", None),
+            ("unknown = self.env().", "env().unknown", None),
+            // Outside of an ink! contract.
+            ("<-Balance)", "value: Balance", None),
+            // Not an ink! crate path.
+            ("my::storage::", "my::storage::Mapping", None),
+        ] {
+            let range = TextRange::new(
+                TextSize::from(parse_offset_at(code, Some(pat_start)).unwrap() as u32),
+                TextSize::from(parse_offset_at(code, Some(pat_end)).unwrap() as u32),
+            );
+
+            let result = hover(
+                &InkFile::parse(code),
+                &Queries::default(),
+                range,
+                Version::V4,
+                &HoverConfig::default(),
+            );
+
+            assert_eq!(
+                result
+                    .as_ref()
+                    .map(|hover_result| (hover_result.content.as_str(), hover_result.range)),
+                expected_doc.map(|doc| (doc, range)),
+                "code: {code}, pat_start: {pat_start}, pat_end: {pat_end}"
+            );
+        }
+    }
+
+    #[test]
+    fn e2e_tests_cfg_hover_works() {
+        let code = r#"
+            #[ink_e2e::test]
+            async fn ungated_e2e_test(mut client: ink_e2e::Client<C, E>) {}
+
+            #[cfg(all(test, feature = "e2e-tests"))]
+            mod e2e_tests {
+                #[ink_e2e::test]
+                async fn gated_e2e_test(mut client: ink_e2e::Client<C, E>) {}
+            }
+
+            #[cfg(test)]
+            mod tests {}
+        "#;
+        let e2e_test_doc = content::doc(&InkAttributeKind::Macro(InkMacroKind::E2ETest));
+
+        for (pat_start, pat_end, expected_result) in [
+            // (pat_start, pat_end, expected_result) where:
+            // pat_start = substring used to find the start of the focus (see `test_utils::parse_offset_at` doc),
+            // pat_end = substring used to find the end of the focus (see `test_utils::parse_offset_at` doc),
+            // expected_result = expected hover content and range (if any).
+
+            // Ungated ink! e2e test.
+            (
+                "<-test]",
+                "test",
+                Some((
+                    format!("{e2e_test_doc}{}", content::ungated_e2e_test_doc()),
+                    "<-test]",
+                    "test",
+                )),
+            ),
+            // Gated ink! e2e test.
+            (
+                "<-test]\n                async",
+                "e2e_tests {\n                #[ink_e2e::test",
+                Some((
+                    e2e_test_doc.to_string(),
+                    "<-test]\n                async",
+                    "e2e_tests {\n                #[ink_e2e::test",
+                )),
+            ),
+            // `e2e-tests` feature `cfg` attribute.
+            (
+                "<-e2e-tests",
+                "e2e-tests",
+                Some((
+                    content::e2e_tests_cfg_doc().to_string(),
+                    r#"<-#[cfg(all(test, feature = "e2e-tests"))]"#,
+                    r#"#[cfg(all(test, feature = "e2e-tests"))]"#,
+                )),
+            ),
+            // Other `cfg` attributes.
+            ("<-test)]", "test)]", None),
+        ] {
+            let range = TextRange::new(
+                TextSize::from(parse_offset_at(code, Some(pat_start)).unwrap() as u32),
+                TextSize::from(parse_offset_at(code, Some(pat_end)).unwrap() as u32),
+            );
+
+            let result = hover(
+                &InkFile::parse(code),
+                &Queries::default(),
+                range,
+                Version::V4,
+                &HoverConfig::default(),
+            );
+
+            assert_eq!(
+                result.map(|hover_result| (hover_result.content, hover_result.range)),
+                expected_result.map(|(content, pat_start, pat_end)| (
+                    content,
+                    TextRange::new(
+                        TextSize::from(parse_offset_at(code, Some(pat_start)).unwrap() as u32),
+                        TextSize::from(parse_offset_at(code, Some(pat_end)).unwrap() as u32),
+                    )
+                )),
+                "pat_start: {pat_start}, pat_end: {pat_end}"
+            );
+        }
+    }
+
+    #[test]
+    fn payable_constructor_version_hover_works() {
+        // ink! version specific docs are distinct.
+        assert_ne!(
+            content::payable_constructor_doc(Version::V3),
+            content::payable_constructor_doc(Version::V4)
+        );
+
+        for (code, version, pat_start, pat_end, expected_version_doc) in [
+            // ink! constructors.
+            (
+                r#"
+                    #[ink(constructor, payable)]
+                    pub fn my_constructor() -> Self {}
+                "#,
+                Version::V4,
+                Some("<-payable"),
+                Some("payable"),
+                content::payable_constructor_doc(Version::V4),
+            ),
+            (
+                r#"
+                    #[ink(constructor, payable)]
+                    pub fn my_constructor() -> Self {}
+                "#,
+                Version::V3,
+                Some("<-payable"),
+                Some("payable"),
+                content::payable_constructor_doc(Version::V3),
+            ),
+            (
+                r#"
+                    #[ink(constructor)]
+                    #[ink(payable)]
+                    pub fn my_constructor() -> Self {}
+                "#,
+                Version::V3,
+                Some("<-payable"),
+                Some("payable"),
+                content::payable_constructor_doc(Version::V3),
+            ),
+            // ink! messages.
+            (
+                r#"
+                    #[ink(message, payable)]
+                    pub fn my_message(&self) {}
+                "#,
+                Version::V3,
+                Some("<-payable"),
+                Some("payable"),
+                "",
+            ),
+        ] {
+            let range = TextRange::new(
+                TextSize::from(parse_offset_at(code, pat_start).unwrap() as u32),
+                TextSize::from(parse_offset_at(code, pat_end).unwrap() as u32),
+            );
+
+            let result = hover(
+                &InkFile::parse(code),
+                &Queries::default(),
+                range,
+                version,
+                &HoverConfig::default(),
+            );
+
+            assert_eq!(
+                result.map(|hover_result| hover_result.content),
+                Some(format!(
+                    "{}{expected_version_doc}",
+                    content::doc(&InkAttributeKind::Arg(InkArgKind::Payable))
+                )),
+                "code: {code}, version: {version:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn selector_value_hover_works() {
+        for (code, pat_start, pat_end, expected_value_doc) in [
+            (
+                "#[ink(message, selector=10)]",
+                Some("<-10"),
+                Some("10"),
+                "\n\nValue: `10` (decimal), `0x0000000A` (hexadecimal).",
+            ),
+            (
+                "#[ink(message, selector=0xCAFE_BABE)]",
+                Some("<-0xCAFE_BABE"),
+                Some("0xCAFE_BABE"),
+                "\n\nValue: `3405691582` (decimal), `0xCAFEBABE` (hexadecimal).",
+            ),
+            // Argument name.
+            (
+                "#[ink(message, selector=10)]",
+                Some("<-selector"),
+                Some("selector"),
+                "",
+            ),
+            // Wildcard and invalid values.
+            ("#[ink(message, selector=_)]", Some("<-_"), Some("_"), ""),
+            (
+                "#[ink(message, selector=0x1_0000_0000)]",
+                Some("<-0x1_0000_0000"),
+                Some("0x1_0000_0000"),
+                "",
+            ),
+        ] {
+            let range = TextRange::new(
+                TextSize::from(parse_offset_at(code, pat_start).unwrap() as u32),
+                TextSize::from(parse_offset_at(code, pat_end).unwrap() as u32),
+            );
+
+            let result = hover(
+                &InkFile::parse(code),
+                &Queries::default(),
+                range,
+                Version::V4,
+                &HoverConfig::default(),
+            );
+
+            assert_eq!(
+                result.map(|hover_result| hover_result.content),
+                Some(format!(
+                    "{}{expected_value_doc}",
+                    content::doc(&InkAttributeKind::Arg(InkArgKind::Selector))
+                )),
+                "code: {code}"
+            );
+        }
+    }
+
+    #[test]
+    fn additional_contracts_value_hover_works() {
+        let code = r#"#[ink_e2e::test(additional_contracts = "adder/Cargo.toml flipper/Cargo.toml adder")]"#;
+        for (pat_start, pat_end, expected_value_doc) in [
+            // Whole value.
+            (
+                Some(r#"<-""#),
+                Some(r#"adder""#),
+                "\n\nAdditional contracts (relative to the root directory of the ink! contract crate):\n\
+                - `adder/Cargo.toml` (i.e. the `adder` contract)\n\
+                - `flipper/Cargo.toml` (i.e. the `flipper` contract)\n\
+                - `adder` (not a `Cargo.toml` path)",
+            ),
+            // Focused entry.
+            (
+                Some("<-flipper"),
+                Some("flipper"),
+                "\n\nAdditional contracts (relative to the root directory of the ink! contract crate):\n\
+                - `flipper/Cargo.toml` (i.e. the `flipper` contract)",
+            ),
+            // Argument name.
+            (
+                Some("<-additional_contracts"),
+                Some("additional_contracts"),
+                "",
+            ),
+        ] {
+            let range = TextRange::new(
+                TextSize::from(parse_offset_at(code, pat_start).unwrap() as u32),
+                TextSize::from(parse_offset_at(code, pat_end).unwrap() as u32),
+            );
+
+            let result = hover(&InkFile::parse(code), &Queries::default(),
+                range,
+                Version::V4,
+                &HoverConfig::default(),
+            );
+
+            assert_eq!(
+                result.map(|hover_result| hover_result.content),
+                Some(format!(
+                    "{}{expected_value_doc}",
+                    content::doc(&InkAttributeKind::Arg(InkArgKind::AdditionalContracts))
+                )),
+                "pat_start: {pat_start:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn selector_dispatch_hover_works() {
+        let code = r#"
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                impl MyContract {
+                    #[ink(constructor, selector=1)]
+                    pub fn new() -> Self {}
+
+                    #[ink(message, selector=1)]
+                    pub fn first(&self) {}
+
+                    #[ink(message, selector=0x1)]
+                    pub fn second(&self) {}
+
+                    #[ink(message, selector=2)]
+                    pub fn third(&self) {}
+
+                    #[ink(message, selector=_)]
+                    pub fn fallback(&self) {}
+                }
+            }
+        "#;
+        for (pat, expected_substrings) in [
+            // (pat, [substring]) where:
+            // pat = substring used to find the hover offset (see `test_utils::parse_offset_at` doc),
+            // substring = substring expected to be present in the hover content.
+            (
+                "#[ink(constructor, sel",
+                vec![
+                    "Dispatch selector: `0x00000001`.",
+                    "unique among the ink! constructors",
+                ],
+            ),
+            (
+                "#[ink(message, sel",
+                vec![
+                    "Dispatch selector: `0x00000001`.",
+                    "**Collides** with the selector of the `second` ink! message(s).",
+                ],
+            ),
+            (
+                "#[ink(message, selector=0x",
+                vec![
+                    "Dispatch selector: `0x00000001`.",
+                    "**Collides** with the selector of the `first` ink! message(s).",
+                ],
+            ),
+            (
+                "#[ink(message, selector=2",
+                vec![
+                    "Dispatch selector: `0x00000002`.",
+                    "unique among the ink! messages",
+                ],
+            ),
+            (
+                "#[ink(message, selector=_",
+                vec!["Wildcard selector: calls with a selector that doesn't match any other ink! message"],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, Some(pat)).unwrap() as u32);
+
+            let content = hover(&InkFile::parse(code), &Queries::default(),
+                TextRange::new(offset, offset),
+                Version::V4,
+                &HoverConfig::default(),
+            )
+            .unwrap()
+            .content;
+
+            for expected_substring in expected_substrings {
+                assert!(
+                    content.contains(expected_substring),
+                    "pat: {pat}, content: {content}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn trait_definition_selector_hover_works() {
+        let code = r#"
+            #[ink::trait_definition(namespace="my_namespace")]
+            pub trait MyTrait {
+                #[ink(message)]
+                fn my_message(&self);
+
+                #[ink(message, selector=0xA)]
+                fn my_message_mut(&mut self);
+            }
+        "#;
+        for (pat, expected_substring) in [
+            // (pat, substring) where:
+            // pat = substring used to find the hover offset (see `test_utils::parse_offset_at` doc),
+            // substring = substring expected to be present in the hover content.
+            (
+                "#[ink(mess",
+                "Selector in implementations of the ink! trait definition: `0x84452b5e` \
+                (computed from the trait identifier and the namespace of the ink! trait definition).",
+            ),
+            (
+                "#[ink(message, sel",
+                "Selector in implementations of the ink! trait definition: `0x0000000a` \
+                (provided explicitly).",
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, Some(pat)).unwrap() as u32);
+
+            let content = hover(&InkFile::parse(code), &Queries::default(),
+                TextRange::new(offset, offset),
+                Version::V4,
+                &HoverConfig::default(),
+            )
+            .unwrap()
+            .content;
+
+            assert!(
+                content.contains(expected_substring),
+                "pat: {pat}, content: {content}"
+            );
+        }
+    }
+
+    #[test]
+    fn hover_ranges_for_all_arg_kinds_works() {
+        for arg_kind in [
+            InkArgKind::AdditionalContracts,
+            InkArgKind::Anonymous,
+            InkArgKind::Constructor,
+            InkArgKind::Default,
+            InkArgKind::Derive,
+            InkArgKind::Env,
+            InkArgKind::Environment,
+            InkArgKind::Event,
+            InkArgKind::Extension,
+            InkArgKind::HandleStatus,
+            InkArgKind::Impl,
+            InkArgKind::KeepAttr,
+            InkArgKind::Message,
+            InkArgKind::Namespace,
+            InkArgKind::Payable,
+            InkArgKind::Selector,
+            InkArgKind::Storage,
+            InkArgKind::Topic,
+        ] {
+            let value = match InkArgValueKind::from(arg_kind) {
+                InkArgValueKind::None => None,
+                InkArgValueKind::U32 | InkArgValueKind::U32OrWildcard => Some("1"),
+                InkArgValueKind::String(_) => Some(r#""my_value""#),
+                InkArgValueKind::Bool => Some("true"),
+                InkArgValueKind::Path(_) => Some("my::env::Types"),
+            };
+            let code = match value {
+                Some(value) => format!("#[ink({arg_kind}={value})]"),
+                None => format!("#[ink({arg_kind})]"),
+            };
+            let file = InkFile::parse(&code);
+            let text_range = |pat_start: &str, pat_end: &str| {
+                TextRange::new(
+                    TextSize::from(parse_offset_at(&code, Some(pat_start)).unwrap() as u32),
+                    TextSize::from(parse_offset_at(&code, Some(pat_end)).unwrap() as u32),
+                )
+            };
+            let attr_range = TextRange::new(TextSize::from(0), TextSize::of(code.as_str()));
+            let arg_name = arg_kind.to_string();
+            let name_range = text_range(&format!("<-{arg_name}"), &arg_name);
+
+            // (focus range, expected range with default config).
+            let mut test_cases = vec![
+                // Argument name.
+                (name_range, name_range),
+                // Start of argument name.
+                (TextRange::empty(name_range.start()), name_range),
+                // Whole attribute.
+                (attr_range, name_range),
+            ];
+            if let Some(value) = value {
+                let value_range = text_range(&format!("<-{value}"), value);
+                test_cases.extend([
+                    // Argument value.
+                    (value_range, value_range),
+                    // End of argument value.
+                    (TextRange::empty(value_range.end()), value_range),
+                ]);
+            }
+
+            for (focus_range, expected_range) in test_cases {
+                // Default config returns the smallest meaningful element.
+                let result = hover(
+                    &file,
+                    &Queries::default(),
+                    focus_range,
+                    Version::V4,
+                    &HoverConfig::default(),
+                );
+                assert_eq!(
+                    result.map(|hover_result| hover_result.range),
+                    Some(expected_range),
+                    "code: {code}, focus: {focus_range:?}"
+                );
+
+                // Whole attribute config returns the whole attribute.
+                let result = hover(
+                    &file,
+                    &Queries::default(),
+                    focus_range,
+                    Version::V4,
+                    &HoverConfig {
+                        whole_attribute: true,
+                    },
+                );
+                assert_eq!(
+                    result.map(|hover_result| hover_result.range),
+                    Some(attr_range),
+                    "code: {code}, focus: {focus_range:?}"
+                );
+            }
+        }
+    }
 }
@@ -1,9 +1,12 @@
-//! Hover content for ink! attributes.
+//! Hover content for ink! attributes, types and environment functions.
 
 use ink_analyzer_ir::{InkArgKind, InkAttributeKind, InkMacroKind};
 
+use crate::Version;
+
 mod args;
 mod macros;
+mod types;
 
 /// Returns documentation for the ink! attribute kind.
 pub fn doc(attr_kind: &InkAttributeKind) -> &str {
@@ -39,3 +42,42 @@ pub fn doc(attr_kind: &InkAttributeKind) -> &str {
         },
     }
 }
+
+/// Returns documentation for the ink! type or ink! environment type with the given name (if any).
+pub fn type_doc(name: &str) -> &'static str {
+    match name {
+        "Mapping" => types::MAPPING_DOC,
+        "Lazy" => types::LAZY_DOC,
+        "AccountId" => types::ACCOUNT_ID_DOC,
+        "Balance" => types::BALANCE_DOC,
+        "Hash" => types::HASH_DOC,
+        _ => "",
+    }
+}
+
+/// Returns documentation for the ink! environment function with the given name (if any).
+pub fn env_fn_doc(name: &str) -> &'static str {
+    match name {
+        "caller" => types::CALLER_DOC,
+        "transferred_value" => types::TRANSFERRED_VALUE_DOC,
+        _ => "",
+    }
+}
+
+/// Returns ink! version specific documentation for `payable` ink! constructors.
+pub fn payable_constructor_doc(version: Version) -> &'static str {
+    match version {
+        Version::V3 => args::PAYABLE_CONSTRUCTOR_V3_DOC,
+        Version::V4 => args::PAYABLE_CONSTRUCTOR_V4_DOC,
+    }
+}
+
+/// Returns documentation for the `cfg` attribute that gates ink! e2e tests behind the `e2e-tests` feature.
+pub fn e2e_tests_cfg_doc() -> &'static str {
+    macros::E2E_TESTS_CFG_DOC
+}
+
+/// Returns documentation for ink! e2e tests that aren't gated behind the `e2e-tests` feature.
+pub fn ungated_e2e_test_doc() -> &'static str {
+    macros::UNGATED_E2E_TEST_DOC
+}
@@ -608,6 +608,33 @@ mod my_contract {
 ```
 "#;
 
+/// Ref: <https://github.com/paritytech/ink/pull/1065>.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v3.4.0/crates/lang/ir/src/ir/item_impl/constructor.rs>.
+pub const PAYABLE_CONSTRUCTOR_V3_DOC: &str = r#"
+# ink! 3.x
+
+ink! constructors are NOT payable by default (since ink! 3.0),
+so they must be annotated with `payable` to receive value as part of the instantiation.
+
+ink! 3.x constructors must return `Self` (i.e. they can't be fallible),
+so a payable instantiation can only be rejected by panicking (which traps and reverts the value transfer).
+"#;
+
+/// Ref: <https://github.com/paritytech/ink/pull/1065>.
+///
+/// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_impl/constructor.rs#L91-L105>.
+pub const PAYABLE_CONSTRUCTOR_V4_DOC: &str = r#"
+# ink! 4.x
+
+ink! constructors are NOT payable by default,
+so they must be annotated with `payable` to receive value as part of the instantiation.
+
+ink! 4.x constructors can be fallible (i.e. return `Result<Self, E>`),
+so a payable instantiation can be rejected by returning an `Err`
+(which reverts the instantiation, including the value transfer, and returns the error to the caller).
+"#;
+
 /// Ref: <https://github.com/paritytech/ink/tree/v4.2.0#ink-macros--attributes-overview>.
 ///
 /// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/ink/macro/src/lib.rs#L347-L384>.
@@ -1305,3 +1305,46 @@ let mut bob = ::ink_e2e::PairSigner::new(
 );
 ```
 "#;
+
+/// Ref: <https://use.ink/basics/contract-testing#end-to-end-e2e-tests>.
+pub const E2E_TESTS_CFG_DOC: &str = r#"
+# Attribute
+
+`#[cfg(all(test, feature = "e2e-tests"))]`
+
+# Description
+
+Gates ink! e2e tests behind the `e2e-tests` feature.
+
+ink! e2e tests require a running node (and build the contract before each test),
+so by convention, they're defined in a `mod` that's only compiled for tests
+when the `e2e-tests` feature is enabled (i.e. `cargo test --features e2e-tests`).
+
+The `e2e-tests` feature must be declared in the `[features]` table of the contract's `Cargo.toml`.
+
+# Example
+
+```
+#[cfg(all(test, feature = "e2e-tests"))]
+mod e2e_tests {
+    use super::*;
+
+    type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[ink_e2e::test]
+    async fn it_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+        // --snip--
+        Ok(())
+    }
+}
+```
+"#;
+
+/// Ref: <https://use.ink/basics/contract-testing#end-to-end-e2e-tests>.
+pub const UNGATED_E2E_TEST_DOC: &str = r#"
+# Conditional compilation
+
+This ink! e2e test is not gated behind the `e2e-tests` feature,
+by convention, ink! e2e tests are defined in a `#[cfg(all(test, feature = "e2e-tests"))]` `mod`
+because they require a running node.
+"#;
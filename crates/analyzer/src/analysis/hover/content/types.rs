@@ -0,0 +1,212 @@
+//! Hover content for ink! types, environment types and environment functions.
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/storage/src/lazy/mapping.rs>.
+///
+/// Ref: <https://paritytech.github.io/ink/ink_storage/struct.Mapping.html>.
+pub const MAPPING_DOC: &str = r#"
+# Type
+
+`ink::storage::Mapping<K, V>`
+
+# Description
+
+A mapping of key-value pairs directly into contract storage.
+
+Each value is stored under its own storage key (i.e. values are lazily loaded and stored individually),
+so the whole mapping is never loaded or written at once.
+
+# Usage
+
+As a field of the ink! storage `struct` (or of a non-packed storage item).
+
+# Example
+
+```
+use ink::storage::Mapping;
+
+#[ink(storage)]
+pub struct MyContract {
+    balances: Mapping<AccountId, Balance>,
+}
+
+// --snip--
+let balance = self.balances.get(caller).unwrap_or_default();
+self.balances.insert(caller, &(balance + value));
+```
+"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/storage/src/lazy/mod.rs>.
+///
+/// Ref: <https://paritytech.github.io/ink/ink_storage/struct.Lazy.html>.
+pub const LAZY_DOC: &str = r#"
+# Type
+
+`ink::storage::Lazy<V>`
+
+# Description
+
+A simple wrapper around a type to store it in a separate storage cell under its own storage key.
+
+The value is only loaded from storage when it's explicitly read (i.e. it's not loaded together with the rest of the ink! storage `struct`).
+
+# Usage
+
+As a field of the ink! storage `struct` (or of a non-packed storage item).
+
+# Example
+
+```
+use ink::storage::Lazy;
+
+#[ink(storage)]
+pub struct MyContract {
+    data: Lazy<Vec<u8>>,
+}
+
+// --snip--
+let data = self.data.get_or_default();
+self.data.set(&data);
+```
+"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/env/src/types.rs>.
+///
+/// Ref: <https://paritytech.github.io/ink/ink_env/trait.Environment.html#associatedtype.AccountId>.
+pub const ACCOUNT_ID_DOC: &str = r#"
+# Type
+
+`AccountId`
+
+# Description
+
+The account id type of the chain environment (i.e. `<Environment as ink::env::Environment>::AccountId`).
+
+For the default environment (i.e. `ink::env::DefaultEnvironment`), this is a 32 byte array.
+
+# Usage
+
+Type alias available in the root of an ink! contract `mod`.
+
+# Example
+
+```
+#[ink(message)]
+pub fn balance_of(&self, owner: AccountId) -> Balance {
+    // --snip--
+}
+```
+"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/env/src/types.rs>.
+///
+/// Ref: <https://paritytech.github.io/ink/ink_env/trait.Environment.html#associatedtype.Balance>.
+pub const BALANCE_DOC: &str = r#"
+# Type
+
+`Balance`
+
+# Description
+
+The balance type of the chain environment (i.e. `<Environment as ink::env::Environment>::Balance`).
+
+For the default environment (i.e. `ink::env::DefaultEnvironment`), this is a `u128`.
+
+# Usage
+
+Type alias available in the root of an ink! contract `mod`.
+
+# Example
+
+```
+#[ink(message)]
+pub fn balance_of(&self, owner: AccountId) -> Balance {
+    // --snip--
+}
+```
+"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/env/src/types.rs>.
+///
+/// Ref: <https://paritytech.github.io/ink/ink_env/trait.Environment.html#associatedtype.Hash>.
+pub const HASH_DOC: &str = r#"
+# Type
+
+`Hash`
+
+# Description
+
+The hash type of the chain environment (i.e. `<Environment as ink::env::Environment>::Hash`).
+
+For the default environment (i.e. `ink::env::DefaultEnvironment`), this is a 32 byte array.
+
+# Usage
+
+Type alias available in the root of an ink! contract `mod`.
+
+# Example
+
+```
+#[ink(message)]
+pub fn code_hash(&self) -> Hash {
+    // --snip--
+}
+```
+"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/env/src/api.rs>.
+///
+/// Ref: <https://paritytech.github.io/ink/ink_env/fn.caller.html>.
+pub const CALLER_DOC: &str = r#"
+# Function
+
+`ink::env::caller::<E>() -> E::AccountId`
+
+# Description
+
+Returns the address of the caller of the executed contract.
+
+# Usage
+
+Via the environment of an ink! contract (i.e. `self.env().caller()` or `Self::env().caller()`),
+or directly via `ink::env::caller::<Environment>()`.
+
+# Example
+
+```
+#[ink(message)]
+pub fn my_message(&self) {
+    let caller = self.env().caller();
+    // --snip--
+}
+```
+"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/env/src/api.rs>.
+///
+/// Ref: <https://paritytech.github.io/ink/ink_env/fn.transferred_value.html>.
+pub const TRANSFERRED_VALUE_DOC: &str = r#"
+# Function
+
+`ink::env::transferred_value::<E>() -> E::Balance`
+
+# Description
+
+Returns the transferred value for the contract execution.
+
+The transferred value is always zero for ink! messages that aren't `payable`.
+
+# Usage
+
+Via the environment of an ink! contract (i.e. `self.env().transferred_value()` or `Self::env().transferred_value()`),
+or directly via `ink::env::transferred_value::<Environment>()`.
+
+# Example
+
+```
+#[ink(message, payable)]
+pub fn my_message(&self) {
+    let value = self.env().transferred_value();
+    // --snip--
+}
+```
+"#;
@@ -0,0 +1,223 @@
+//! Indentation style for generated code.
+//!
+//! NOTE: All code generators (i.e. snippets, entity templates and action/quickfix edits) emit
+//! "canonical" indenting (i.e. 4 spaces per indent level), which is then converted
+//! to the target indentation style by the [`Analysis`](crate::Analysis) entry points.
+
+use ink_analyzer_ir::syntax::SyntaxNode;
+
+/// Number of spaces per indent level for canonical indenting.
+const CANONICAL_WIDTH: usize = 4;
+
+/// The indentation style for generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndentStyle {
+    /// Indents with the given number of spaces per indent level.
+    Spaces(usize),
+    /// Indents with a tab per indent level.
+    Tabs,
+}
+
+impl Default for IndentStyle {
+    fn default() -> Self {
+        IndentStyle::Spaces(CANONICAL_WIDTH)
+    }
+}
+
+impl IndentStyle {
+    /// Returns the whitespace for a single indent level.
+    pub fn unit(&self) -> String {
+        match self {
+            IndentStyle::Spaces(width) => " ".repeat(*width),
+            IndentStyle::Tabs => "\t".to_string(),
+        }
+    }
+
+    /// Detects the indentation style of the given code (if any)
+    /// (i.e. tabs if most indented lines start with a tab, otherwise the most common
+    /// increase in leading spaces between consecutive lines).
+    pub fn detect(code: &str) -> Option<Self> {
+        let mut tab_lines = 0;
+        let mut space_lines = 0;
+        // Tally of increases in leading spaces between consecutive lines.
+        let mut deltas = [0usize; 9];
+        let mut prev_width = 0;
+        for line in code.lines().filter(|line| !line.trim().is_empty()) {
+            if line.starts_with('\t') {
+                tab_lines += 1;
+                continue;
+            }
+            let width = line.chars().take_while(|char| *char == ' ').count();
+            if width > 0 {
+                space_lines += 1;
+            }
+            if let Some(count) = width
+                .checked_sub(prev_width)
+                .and_then(|delta| deltas.get_mut(delta))
+            {
+                *count += 1;
+            }
+            prev_width = width;
+        }
+
+        if tab_lines == 0 && space_lines == 0 {
+            return None;
+        }
+        if tab_lines > space_lines {
+            return Some(IndentStyle::Tabs);
+        }
+        // Ties are resolved in favor of the larger width.
+        deltas
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, count)| **count > 0)
+            .max_by_key(|(width, count)| (**count, *width))
+            .map(|(width, _)| IndentStyle::Spaces(width))
+    }
+
+    /// Detects the indentation style of the file containing the syntax node (if any).
+    pub(crate) fn detect_for_node(node: &SyntaxNode) -> Option<Self> {
+        node.ancestors()
+            .last()
+            .and_then(|root| Self::detect(&root.to_string()))
+    }
+
+    /// Converts indenting (i.e. leading whitespace) in this indentation style to canonical indenting.
+    pub(crate) fn normalize(&self, indenting: &str) -> String {
+        let width = match self {
+            IndentStyle::Spaces(width) => (*width).max(1),
+            IndentStyle::Tabs => CANONICAL_WIDTH,
+        };
+        let spaces = indenting.chars().filter(|char| *char == ' ').count();
+        let tabs = indenting.chars().filter(|char| *char == '\t').count();
+        let (levels, remainder) = (tabs + spaces / width, spaces % width);
+        format!(
+            "{}{}",
+            " ".repeat(levels * CANONICAL_WIDTH),
+            " ".repeat(remainder)
+        )
+    }
+
+    /// Converts indenting of all lines (except the first one) in the given text
+    /// from this indentation style to canonical indenting.
+    pub(crate) fn normalize_lines(&self, text: &str) -> String {
+        let mut lines = text.split('\n');
+        let mut result = lines.next().unwrap_or_default().to_string();
+        for line in lines {
+            let indenting: String = line
+                .chars()
+                .take_while(|char| *char == ' ' || *char == '\t')
+                .collect();
+            result.push('\n');
+            result.push_str(&self.normalize(&indenting));
+            result.push_str(&line[indenting.len()..]);
+        }
+        result
+    }
+
+    /// Converts canonical indenting of all lines (except the first one)
+    /// in the given text to this indentation style.
+    pub(crate) fn apply(&self, text: &str) -> String {
+        if *self == IndentStyle::default() {
+            return text.to_string();
+        }
+        let unit = self.unit();
+        let mut lines = text.split('\n');
+        let mut result = lines.next().unwrap_or_default().to_string();
+        for line in lines {
+            let width = line.chars().take_while(|char| *char == ' ').count();
+            result.push('\n');
+            result.push_str(&unit.repeat(width / CANONICAL_WIDTH));
+            result.push_str(&" ".repeat(width % CANONICAL_WIDTH));
+            result.push_str(&line[width..]);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Analysis;
+    use ink_analyzer_ir::syntax::{TextRange, TextSize};
+    use test_utils::parse_offset_at;
+
+    #[test]
+    fn detect_works() {
+        for (code, expected_style) in [
+            ("", None),
+            ("mod my_module {}", None),
+            (
+                "mod my_module {\n    fn my_fn() {\n        todo!()\n    }\n}",
+                Some(IndentStyle::Spaces(4)),
+            ),
+            (
+                "mod my_module {\n  fn my_fn() {\n    todo!()\n  }\n}",
+                Some(IndentStyle::Spaces(2)),
+            ),
+            (
+                "mod my_module {\n\tfn my_fn() {\n\t\ttodo!()\n\t}\n}",
+                Some(IndentStyle::Tabs),
+            ),
+        ] {
+            assert_eq!(IndentStyle::detect(code), expected_style, "code: {code}");
+        }
+    }
+
+    #[test]
+    fn normalize_and_apply_works() {
+        for (style, indenting, text, expected_text) in [
+            (
+                IndentStyle::Spaces(4),
+                "        ",
+                "fn my_fn() {\n    todo!()\n}",
+                "fn my_fn() {\n    todo!()\n}",
+            ),
+            (
+                IndentStyle::Spaces(2),
+                "    ",
+                "fn my_fn() {\n    todo!()\n}",
+                "fn my_fn() {\n  todo!()\n}",
+            ),
+            (
+                IndentStyle::Tabs,
+                "\t\t",
+                "fn my_fn() {\n    todo!()\n}",
+                "fn my_fn() {\n\ttodo!()\n}",
+            ),
+        ] {
+            // Round-trips indenting in the given style.
+            assert_eq!(
+                style.apply(&format!("\n{}", style.normalize(indenting))),
+                format!("\n{indenting}")
+            );
+            // Converts canonical indenting to the given style.
+            assert_eq!(style.apply(text), expected_text);
+        }
+    }
+
+    #[test]
+    fn actions_respect_indent_style_works() {
+        let code = "#[ink::contract]\nmod my_contract {\n\timpl MyContract {\n\t\t#[ink(constructor)]\n\t\tpub fn new() -> Self {}\n\t}\n}";
+        let offset = TextSize::from(parse_offset_at(code, Some("<-impl")).unwrap() as u32);
+        let range = TextRange::new(offset, offset);
+
+        for (analysis, expected_body) in [
+            // Detected indentation style.
+            (Analysis::new(code), "\n\t\t\ttodo!()"),
+            // Configured indentation style.
+            (
+                Analysis::new(code).with_indent_style(IndentStyle::Spaces(2)),
+                "\n      todo!()",
+            ),
+        ] {
+            let action = analysis
+                .actions(range)
+                .into_iter()
+                .find(|action| action.label.contains("Add ink! message"))
+                .unwrap();
+            assert!(action.edits[0].text.contains(expected_body));
+        }
+    }
+}
@@ -1,26 +1,113 @@
-//! ink! attribute argument inlay hints.
+//! ink! attribute argument, ink! selector and ink! event signature topic inlay hints.
 
-use ink_analyzer_ir::syntax::{AstToken, TextRange, TextSize};
-use ink_analyzer_ir::{InkArgValueKind, InkFile, IsInkEntity};
+use ink_analyzer_ir::ast::HasName;
+use ink_analyzer_ir::syntax::{AstNode, AstToken, SyntaxKind, SyntaxNode, TextRange, TextSize};
+use ink_analyzer_ir::{
+    ast, Contract, FromInkAttribute, FromSyntax, InkArgKind, InkArgValueKind, InkFile, InkIndex,
+    IsInkCallable, IsInkEntity, IsInkFn, IsInkImplItem, IsInkStruct, Message, TraitDefinition,
+};
 
-/// An ink! attribute argument inlay hint.
+use super::queries::{Queries, SelectorTable};
+use super::utils;
+
+/// An ink! attribute argument, ink! selector or ink! event signature topic inlay hint.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InlayHint {
     /// Text of the inlay hint.
     pub label: String,
     /// Position of the inlay hint.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_size"))]
     pub position: TextSize,
     /// Range to which the inlay hint applies.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
     pub range: TextRange,
     /// Extra details about the inlay hint.
     pub detail: Option<String>,
 }
 
+/// Configuration for ink! inlay hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InlayHintsConfig {
+    /// Whether to show the value kinds of ink! attribute arguments (e.g. `u32 | _` for `selector`).
+    pub argument_kinds: bool,
+    /// Whether to show the computed selectors of ink! constructors and ink! messages
+    /// that don't specify a selector explicitly.
+    pub selectors: bool,
+    /// Whether to show the signature topics of ink! events.
+    pub signature_topics: bool,
+    /// Whether to show the default values of omitted complementary ink! attribute arguments
+    /// (e.g. `handle_status: true` for ink! extensions and `derive: true` for ink! storage items).
+    pub default_values: bool,
+    /// Maximum length (in characters) of inlay hint labels (if any),
+    /// longer labels are truncated and end with an ellipsis (i.e. `…`).
+    pub max_length: Option<usize>,
+}
+
+impl Default for InlayHintsConfig {
+    fn default() -> Self {
+        Self {
+            argument_kinds: true,
+            selectors: true,
+            signature_topics: true,
+            default_values: false,
+            max_length: None,
+        }
+    }
+}
+
+/// Computes ink! attribute argument, ink! selector, ink! event signature topic
+/// and default value inlay hints (as enabled by the [`InlayHintsConfig`]) for the given text range (if any).
+pub fn inlay_hints(
+    file: &InkFile,
+    queries: &Queries,
+    range: Option<TextRange>,
+    config: &InlayHintsConfig,
+) -> Vec<InlayHint> {
+    // Uses the (memoized) ink! entity index for all the inlay hint kinds.
+    let index = queries.index(file);
+
+    let mut results = Vec::new();
+
+    // Computes ink! attribute argument inlay hints (if enabled).
+    if config.argument_kinds {
+        results.extend(argument_hints(index, range));
+    }
+
+    // Computes selector inlay hints (if enabled).
+    if config.selectors {
+        selector_hints(&mut results, index, queries.selectors(file), range);
+    }
+
+    // Computes event signature topic inlay hints (if enabled).
+    if config.signature_topics {
+        event_hints(&mut results, index, range);
+    }
+
+    // Computes default value inlay hints (if enabled).
+    if config.default_values {
+        default_value_hints(&mut results, index, range);
+    }
+
+    // Truncates long labels (if a length cap is set).
+    if let Some(max_length) = config.max_length {
+        for hint in &mut results {
+            truncate_label(&mut hint.label, max_length);
+        }
+    }
+
+    // Sorts inlay hints by position.
+    results.sort_by_key(|hint| hint.position);
+
+    results
+}
+
 /// Computes ink! attribute argument inlay hints for the given text range (if any).
-pub fn inlay_hints(file: &InkFile, range: Option<TextRange>) -> Vec<InlayHint> {
+fn argument_hints(index: &InkIndex, range: Option<TextRange>) -> Vec<InlayHint> {
     // Iterates over all ink! attributes in the file.
-    file.tree()
-        .ink_attrs_in_scope()
+    index
+        .attrs()
+        .iter()
         .flat_map(|attr| {
             // Returns inlay hints for all ink! attribute arguments with values in the selection range.
             attr.args()
@@ -54,6 +141,351 @@ pub fn inlay_hints(file: &InkFile, range: Option<TextRange>) -> Vec<InlayHint> {
         .collect()
 }
 
+/// Computes inlay hints for the computed selectors of ink! constructors and ink! messages
+/// that don't specify a selector explicitly for the given text range (if any).
+fn selector_hints(
+    results: &mut Vec<InlayHint>,
+    index: &InkIndex,
+    selectors: &SelectorTable,
+    range: Option<TextRange>,
+) {
+    results.extend(
+        index
+            .messages()
+            .iter()
+            .filter_map(|message| selector_hint(message, "message", selectors, range))
+            .chain(index.constructors().iter().filter_map(|constructor| {
+                selector_hint(constructor, "constructor", selectors, range)
+            })),
+    );
+}
+
+/// Returns the computed selector inlay hint (if any) for an ink! callable
+/// (i.e. an ink! constructor or ink! message) positioned at the end of its name.
+fn selector_hint<T>(
+    callable: &T,
+    ink_scope_name: &str,
+    selectors: &SelectorTable,
+    range: Option<TextRange>,
+) -> Option<InlayHint>
+where
+    T: IsInkCallable,
+{
+    // Ignores callables with explicit selectors.
+    if callable.selector_arg().is_some() {
+        return None;
+    }
+    let detail = if callable.impl_item().is_some() {
+        format!("Computed selector of the ink! {ink_scope_name}.")
+    } else if ink_analyzer_ir::ink_ancestors::<TraitDefinition>(callable.syntax())
+        .next()
+        .is_some()
+    {
+        // ink! trait definition messages get the (namespaced) selector of their implementations.
+        format!(
+            "Computed selector of the ink! {ink_scope_name} in implementations of the ink! trait definition."
+        )
+    } else {
+        // Ignores callables that are neither in an `impl` block nor in an ink! trait definition.
+        return None;
+    };
+
+    let fn_item = callable.fn_item()?;
+    let name_range = fn_item.name()?.syntax().text_range();
+    // Filters out callables whose name isn't in the selection range.
+    if range.map_or(false, |range| !range.contains_range(name_range)) {
+        return None;
+    }
+
+    let selector = selectors.get(&fn_item.syntax().text_range())?;
+    Some(InlayHint {
+        label: format!("0x{:08x}", selector.into_be_u32()),
+        position: name_range.end(),
+        range: name_range,
+        detail: Some(detail),
+    })
+}
+
+/// Computes signature topic inlay hints for ink! events
+/// positioned at the end of the event `struct` name for the given text range (if any).
+fn event_hints(results: &mut Vec<InlayHint>, index: &InkIndex, range: Option<TextRange>) {
+    for event in index.events() {
+        let Some(name) = event.struct_item().and_then(HasName::name) else {
+            continue;
+        };
+        let name_range = name.syntax().text_range();
+        // Filters out events whose name isn't in the selection range.
+        if range.map_or(false, |range| !range.contains_range(name_range)) {
+            continue;
+        }
+
+        let hint = if event.anonymous_arg().is_some() {
+            Some((
+                "anonymous".to_string(),
+                "Anonymous ink! events have no signature topic.".to_string(),
+            ))
+        } else {
+            event.signature_topic().map(|topic| {
+                (
+                    format!(
+                        "0x{}",
+                        topic
+                            .iter()
+                            .map(|byte| format!("{byte:02x}"))
+                            .collect::<String>()
+                    ),
+                    "Signature topic of the ink! event.".to_string(),
+                )
+            })
+        };
+        if let Some((label, detail)) = hint {
+            results.push(InlayHint {
+                label,
+                position: name_range.end(),
+                range: name_range,
+                detail: Some(detail),
+            });
+        }
+    }
+}
+
+/// Computes inlay hints for the default values of omitted complementary ink! attribute arguments
+/// (i.e. `handle_status` for ink! extensions and `derive` for ink! storage items)
+/// positioned at the end of the ink! attribute for the given text range (if any).
+fn default_value_hints(results: &mut Vec<InlayHint>, index: &InkIndex, range: Option<TextRange>) {
+    let extension_hints = index
+        .extensions()
+        .iter()
+        .filter(|extension| extension.handle_status_arg().is_none())
+        .map(|extension| (extension.ink_attr(), InkArgKind::HandleStatus));
+    let storage_item_hints = index
+        .storage_items()
+        .iter()
+        .filter(|storage_item| storage_item.derive_arg().is_none())
+        .map(|storage_item| (storage_item.ink_attr(), InkArgKind::Derive));
+    for (attr, arg_kind) in extension_hints.chain(storage_item_hints) {
+        let attr_range = attr.syntax().text_range();
+        // Filters out ink! attributes that aren't in the selection range.
+        if range.map_or(false, |range| !range.contains_range(attr_range)) {
+            continue;
+        }
+        results.push(InlayHint {
+            label: format!("{arg_kind}: true"),
+            position: attr_range.end(),
+            range: attr_range,
+            detail: Some(format!(
+                "Default value of the omitted `{arg_kind}` argument."
+            )),
+        });
+    }
+}
+
+/// Truncates the label to the given maximum length (in characters),
+/// replacing the last character with an ellipsis (i.e. `…`) if the label is too long.
+fn truncate_label(label: &mut String, max_length: usize) {
+    if let Some((offset, _)) = label.char_indices().nth(max_length) {
+        let end = label[..offset]
+            .char_indices()
+            .last()
+            .map_or(0, |(offset, _)| offset);
+        label.truncate(end);
+        if max_length > 0 {
+            label.push('…');
+        }
+    }
+}
+
+/// Computes experimental (heuristic) relative execution cost inlay hints for ink! messages
+/// positioned at the end of their signature for the given text range (if any).
+///
+/// The cost is estimated by counting storage reads and writes, loops and cross-contract calls,
+/// so it's only a coarse relative indication (i.e. `low`, `medium` or `high`) and NOT an actual gas/weight estimate.
+pub fn cost_hints(file: &InkFile, range: Option<TextRange>) -> Vec<InlayHint> {
+    let mut results = Vec::new();
+    for message in file.tree().ink_attrs_in_scope().filter_map(Message::cast) {
+        let Some(fn_item) = message.fn_item() else {
+            continue;
+        };
+        let Some(body) = fn_item.body() else {
+            continue;
+        };
+        let Some(signature_end) = fn_item
+            .ret_type()
+            .map(|ret_type| ret_type.syntax().text_range().end())
+            .or(fn_item
+                .param_list()
+                .map(|param_list| param_list.syntax().text_range().end()))
+        else {
+            continue;
+        };
+        let signature_range = TextRange::new(fn_item.syntax().text_range().start(), signature_end);
+        // Filters out messages whose signature isn't in the selection range.
+        if range.map_or(false, |range| !range.contains_range(signature_range)) {
+            continue;
+        }
+
+        // Collects storage field names (if known).
+        let contract = ink_analyzer_ir::ink_ancestors::<Contract>(message.syntax()).next();
+        let storage_fields: Option<Vec<String>> = contract
+            .as_ref()
+            .and_then(Contract::storage)
+            .and_then(IsInkStruct::struct_item)
+            .and_then(ast::Struct::field_list)
+            .map(|field_list| match field_list {
+                ast::FieldList::RecordFieldList(field_list) => field_list
+                    .fields()
+                    .filter_map(|field| field.name().map(|name| name.to_string()))
+                    .collect(),
+                ast::FieldList::TupleFieldList(_) => Vec::new(),
+            });
+
+        let cost = Cost::estimate(body.syntax(), storage_fields.as_deref());
+        results.push(InlayHint {
+            label: format!("cost: {}", cost.level()),
+            position: signature_end,
+            range: signature_range,
+            detail: Some(format!(
+                "Heuristic relative execution cost (NOT a gas/weight estimate): \
+                {} storage read(s), {} storage write(s), {} loop(s), {} cross-contract call(s).",
+                cost.reads, cost.writes, cost.loops, cost.calls
+            )),
+        });
+    }
+    results
+}
+
+/// Counts of expensive operations used for estimating the relative execution cost of an ink! message.
+#[derive(Debug, Default)]
+struct Cost {
+    /// Number of storage field reads.
+    reads: usize,
+    /// Number of storage field writes.
+    writes: usize,
+    /// Number of loops.
+    loops: usize,
+    /// Number of cross-contract calls.
+    calls: usize,
+}
+
+/// Names of methods that perform cross-contract calls
+/// (i.e. `ink::env::call::CallBuilder` and contract reference methods).
+const CROSS_CONTRACT_CALL_METHODS: [&str; 6] = [
+    "invoke",
+    "try_invoke",
+    "invoke_contract",
+    "invoke_contract_delegate",
+    "instantiate_contract",
+    "call_mut",
+];
+
+impl Cost {
+    /// Estimates the cost of the body of an ink! message.
+    ///
+    /// Accesses of all `self` fields are counted as storage accesses if the storage fields are unknown.
+    fn estimate(body: &SyntaxNode, storage_fields: Option<&[String]>) -> Self {
+        let mut cost = Cost::default();
+        for node in body.descendants() {
+            match node.kind() {
+                SyntaxKind::FOR_EXPR | SyntaxKind::WHILE_EXPR | SyntaxKind::LOOP_EXPR => {
+                    cost.loops += 1;
+                }
+                SyntaxKind::METHOD_CALL_EXPR => {
+                    let is_call = ast::MethodCallExpr::cast(node)
+                        .and_then(|method_call| method_call.name_ref())
+                        .map_or(false, |name| {
+                            CROSS_CONTRACT_CALL_METHODS.contains(&name.text().as_str())
+                        });
+                    if is_call {
+                        cost.calls += 1;
+                    }
+                }
+                SyntaxKind::FIELD_EXPR => {
+                    let Some(field_expr) = ast::FieldExpr::cast(node) else {
+                        continue;
+                    };
+                    // Only direct `self` field accesses are considered (i.e. not nested fields).
+                    let is_self_field = matches!(
+                        field_expr.expr(),
+                        Some(ast::Expr::PathExpr(path_expr)) if path_expr.to_string() == "self"
+                    );
+                    let Some(name) = field_expr.name_ref().map(|name| name.to_string()) else {
+                        continue;
+                    };
+                    if !is_self_field
+                        || storage_fields.map_or(false, |fields| !fields.contains(&name))
+                    {
+                        continue;
+                    }
+                    let (is_read, is_write) = storage_access(&field_expr);
+                    if is_read {
+                        cost.reads += 1;
+                    }
+                    if is_write {
+                        cost.writes += 1;
+                    }
+                }
+                _ => (),
+            }
+        }
+        cost
+    }
+
+    /// Returns the coarse cost level (i.e. `low`, `medium` or `high`).
+    ///
+    /// Storage writes and loops are weighted more than storage reads,
+    /// and cross-contract calls are weighted the most.
+    fn level(&self) -> &'static str {
+        let score = self.reads + 4 * self.writes + 8 * self.loops + 16 * self.calls;
+        if score <= 4 {
+            "low"
+        } else if score <= 16 {
+            "medium"
+        } else {
+            "high"
+        }
+    }
+}
+
+/// Returns whether a `self` field access is a read and/or a write
+/// (e.g. `self.value = 1` is a write, `self.value += 1` is both a read and a write,
+/// `self.values.insert(..)` is a write and `self.value` is a read).
+fn storage_access(field_expr: &ast::FieldExpr) -> (bool, bool) {
+    // Finds the outermost (possibly nested) field or index expression.
+    let mut target = field_expr.syntax().clone();
+    while let Some(parent) = target.parent().filter(|parent| {
+        matches!(
+            parent.kind(),
+            SyntaxKind::FIELD_EXPR | SyntaxKind::INDEX_EXPR
+        )
+    }) {
+        target = parent;
+    }
+
+    match target.parent() {
+        Some(parent) => {
+            if let Some(bin_expr) = ast::BinExpr::cast(parent.clone()) {
+                if let Some(ast::BinaryOp::Assignment { op }) = bin_expr.op_kind() {
+                    if bin_expr.lhs().map_or(false, |lhs| *lhs.syntax() == target) {
+                        return (op.is_some(), true);
+                    }
+                }
+            } else if let Some(method_call) = ast::MethodCallExpr::cast(parent) {
+                let is_receiver = method_call
+                    .receiver()
+                    .map_or(false, |receiver| *receiver.syntax() == target);
+                let is_mutating = method_call.name_ref().map_or(false, |name| {
+                    utils::MUTATING_METHODS.contains(&name.text().as_str())
+                });
+                if is_receiver && is_mutating {
+                    return (false, true);
+                }
+            }
+            (true, false)
+        }
+        None => (true, false),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +660,116 @@ mod tests {
                     ),
                 ],
             ),
+            // Computed selectors.
+            (
+                r#"
+                    impl MyContract {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {}
+
+                        #[ink(message, selector=1)]
+                        pub fn flip(&mut self) {}
+
+                        #[ink(message)]
+                        pub fn get(&self) {}
+                    }
+                "#,
+                None,
+                vec![
+                    (
+                        "0x9bae9d5e",
+                        Some("fn new"),
+                        (Some("<-new"), Some("fn new")),
+                    ),
+                    (
+                        "u32 | _",
+                        Some("selector"),
+                        (Some("<-selector"), Some("selector")),
+                    ),
+                    (
+                        "0x2f865bd9",
+                        Some("fn get"),
+                        (Some("<-get"), Some("fn get")),
+                    ),
+                ],
+            ),
+            (
+                r#"
+                    #[ink(namespace="my_namespace")]
+                    impl MyContract {
+                        #[ink(message)]
+                        pub fn my_message(&self) {}
+                    }
+                "#,
+                Some((Some("<-#[ink(message)]"), Some("->"))),
+                vec![(
+                    "0xabe89c04",
+                    Some("fn my_message"),
+                    (Some("<-my_message"), Some("fn my_message")),
+                )],
+            ),
+            (
+                r#"
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self);
+                    }
+                "#,
+                None,
+                vec![(
+                    "0x04c49446",
+                    Some("fn my_message"),
+                    (Some("<-my_message"), Some("fn my_message")),
+                )],
+            ),
+            (
+                r#"
+                    #[ink::trait_definition(namespace="my_namespace")]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self);
+
+                        #[ink(message, selector=1)]
+                        fn my_message_mut(&mut self);
+                    }
+                "#,
+                Some((Some("<-#[ink(message)]"), Some("fn my_message("))),
+                vec![(
+                    "0x84452b5e",
+                    Some("fn my_message"),
+                    (Some("<-my_message"), Some("fn my_message")),
+                )],
+            ),
+            // Event signature topics.
+            (
+                r#"
+                    #[ink::contract]
+                    mod erc20 {
+                        #[ink(storage)]
+                        pub struct Erc20 {}
+
+                        #[ink(event)]
+                        pub struct Transfer {}
+
+                        #[ink(event, anonymous)]
+                        pub struct Approval {}
+                    }
+                "#,
+                None,
+                vec![
+                    (
+                        "0x0045726332303a3a5472616e7366657200000000000000000000000000000000",
+                        Some("struct Transfer"),
+                        (Some("<-Transfer"), Some("struct Transfer")),
+                    ),
+                    (
+                        "anonymous",
+                        Some("struct Approval"),
+                        (Some("<-Approval"), Some("struct Approval")),
+                    ),
+                ],
+            ),
         ] {
             let range = selection_range_pat.map(|(pat_start, pat_end)| {
                 TextRange::new(
@@ -235,7 +777,12 @@ mod tests {
                     TextSize::from(parse_offset_at(code, pat_end).unwrap() as u32),
                 )
             });
-            let results = inlay_hints(&InkFile::parse(code), range);
+            let results = inlay_hints(
+                &InkFile::parse(code),
+                &Queries::default(),
+                range,
+                &InlayHintsConfig::default(),
+            );
 
             assert_eq!(
                 results
@@ -257,4 +804,210 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn default_value_hints_works() {
+        let code = r#"
+            #[ink::chain_extension]
+            pub trait MyChainExtension {
+                type ErrorCode = ();
+
+                #[ink(extension = 1)]
+                fn my_extension();
+
+                #[ink(extension = 2, handle_status = false)]
+                fn my_extension2();
+            }
+
+            #[ink::storage_item]
+            struct MyStorageItem {}
+
+            #[ink::storage_item(derive = false)]
+            struct MyStorageItem2 {}
+        "#;
+        let file = InkFile::parse(code);
+        let default_value_hints = |config: &InlayHintsConfig| {
+            inlay_hints(&file, &Queries::default(), None, config)
+                .into_iter()
+                .filter(|hint| hint.label.ends_with(": true"))
+                .map(|hint| (hint.label, hint.position, hint.range))
+                .collect::<Vec<_>>()
+        };
+        let attr_range = |pat: &str| {
+            TextRange::new(
+                TextSize::from(
+                    parse_offset_at(code, Some(format!("<-{pat}").as_str())).unwrap() as u32,
+                ),
+                TextSize::from(parse_offset_at(code, Some(pat)).unwrap() as u32),
+            )
+        };
+
+        // Default value inlay hints are disabled by default.
+        assert!(default_value_hints(&InlayHintsConfig::default()).is_empty());
+
+        // Only omitted arguments have default value inlay hints.
+        let extension_attr_range = attr_range("#[ink(extension = 1)]");
+        let storage_item_attr_range = attr_range("#[ink::storage_item]");
+        assert_eq!(
+            default_value_hints(&InlayHintsConfig {
+                default_values: true,
+                ..Default::default()
+            }),
+            vec![
+                (
+                    "handle_status: true".to_string(),
+                    extension_attr_range.end(),
+                    extension_attr_range
+                ),
+                (
+                    "derive: true".to_string(),
+                    storage_item_attr_range.end(),
+                    storage_item_attr_range
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn inlay_hints_config_works() {
+        let code = r#"
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                #[ink(event)]
+                pub struct MyEvent {}
+
+                impl MyContract {
+                    #[ink(constructor, selector = 1)]
+                    pub fn new() -> Self {}
+
+                    #[ink(message, payable)]
+                    pub fn my_message(&self) {}
+                }
+            }
+        "#;
+        let file = InkFile::parse(code);
+        let labels = |config: &InlayHintsConfig| {
+            inlay_hints(&file, &Queries::default(), None, config)
+                .into_iter()
+                .map(|hint| hint.label)
+                .collect::<Vec<_>>()
+        };
+        let config = InlayHintsConfig::default();
+
+        // All default hint classes are enabled by default.
+        let all = labels(&config);
+        assert!(all.iter().any(|label| label == "u32 | _"));
+        assert!(all.iter().any(|label| label.starts_with("0x")));
+
+        // Each hint class can be disabled.
+        assert!(labels(&InlayHintsConfig {
+            argument_kinds: false,
+            selectors: false,
+            signature_topics: false,
+            ..config
+        })
+        .is_empty());
+        let without_selectors = labels(&InlayHintsConfig {
+            selectors: false,
+            ..config
+        });
+        assert_eq!(without_selectors.len(), all.len() - 1);
+        let without_topics = labels(&InlayHintsConfig {
+            signature_topics: false,
+            ..config
+        });
+        assert_eq!(without_topics.len(), all.len() - 1);
+
+        // Long labels are truncated.
+        for label in labels(&InlayHintsConfig {
+            max_length: Some(6),
+            ..config
+        }) {
+            assert!(label.chars().count() <= 6);
+        }
+    }
+
+    #[test]
+    fn truncate_label_works() {
+        for (label, max_length, expected) in [
+            ("u32 | _", 10, "u32 | _"),
+            ("u32 | _", 7, "u32 | _"),
+            ("u32 | _", 6, "u32 |…"),
+            ("0x1234abcd", 4, "0x1…"),
+            ("ab", 1, "…"),
+            ("ab", 0, ""),
+        ] {
+            let mut label = label.to_string();
+            truncate_label(&mut label, max_length);
+            assert_eq!(label, expected);
+        }
+    }
+
+    #[test]
+    fn cost_hints_works() {
+        let code = r#"
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {
+                    value: u32,
+                    values: Mapping<u32, u32>,
+                }
+
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn get(&self) -> u32 {
+                        self.value
+                    }
+
+                    #[ink(message)]
+                    pub fn set(&mut self, value: u32) {
+                        self.value += value;
+                        self.values.insert(value, &value);
+                    }
+
+                    #[ink(message)]
+                    pub fn reset(&mut self) {
+                        for idx in 0..self.value {
+                            self.values.remove(idx);
+                        }
+                        self.value = 0;
+                    }
+                }
+            }
+        "#;
+
+        let results = cost_hints(&InkFile::parse(code), None);
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|item| (item.label.as_str(), item.position))
+                .collect::<Vec<(&str, TextSize)>>(),
+            [
+                ("cost: low", "-> u32"),
+                ("cost: medium", "set(&mut self, value: u32)"),
+                ("cost: high", "reset(&mut self)"),
+            ]
+            .into_iter()
+            .map(|(label, pos_pat)| (
+                label,
+                TextSize::from(parse_offset_at(code, Some(pos_pat)).unwrap() as u32)
+            ))
+            .collect::<Vec<(&str, TextSize)>>()
+        );
+        assert!(results[1]
+            .detail
+            .as_ref()
+            .unwrap()
+            .contains("1 storage read(s), 2 storage write(s), 0 loop(s)"));
+        assert!(results[2]
+            .detail
+            .as_ref()
+            .unwrap()
+            .contains("1 storage read(s), 2 storage write(s), 1 loop(s)"));
+    }
 }
@@ -0,0 +1,674 @@
+//! Verification of ink! smart contract code against ink! contract metadata
+//! (i.e. the `metadata.json` file generated by `cargo contract build`).
+
+use ink_analyzer_ir::ast::{AstNode, HasName};
+use ink_analyzer_ir::syntax::TextRange;
+use ink_analyzer_ir::{ast, Contract, InkFile, IsInkCallable, IsInkImplItem};
+
+use crate::analysis::utils;
+use crate::{Diagnostic, DiagnosticCode, Severity};
+
+/// ink! contract metadata (i.e. the contract API described by a `metadata.json` file).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ContractMetadata {
+    /// Name of the contract (if any) (i.e. the crate name of the contract).
+    pub name: Option<String>,
+    /// ink! constructors.
+    pub constructors: Vec<CallableMetadata>,
+    /// ink! messages.
+    pub messages: Vec<CallableMetadata>,
+}
+
+impl ContractMetadata {
+    /// Parses the contents of a `metadata.json` file (generated by `cargo contract build`)
+    /// into ink! contract metadata (if valid).
+    ///
+    /// Both the current (i.e. ink! >= 4.x) and the versioned ink! 3.x metadata formats are supported.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(json).ok()?;
+        // ink! 3.x metadata is nested under a version key.
+        let spec = value
+            .get("spec")
+            .or_else(|| value.get("V3").and_then(|it| it.get("spec")))?;
+        let callables = |key: &str| -> Option<Vec<CallableMetadata>> {
+            spec.get(key)?
+                .as_array()?
+                .iter()
+                .map(|callable| {
+                    Some(CallableMetadata {
+                        label: callable.get("label")?.as_str()?.to_string(),
+                        selector: u32::from_str_radix(
+                            callable.get("selector")?.as_str()?.trim_start_matches("0x"),
+                            16,
+                        )
+                        .ok()?,
+                        args: callable
+                            .get("args")
+                            .and_then(serde_json::Value::as_array)
+                            .map(|args| {
+                                args.iter()
+                                    .filter_map(|arg| {
+                                        Some(ArgMetadata {
+                                            label: arg.get("label")?.as_str()?.to_string(),
+                                            // Uses the last segment of the type's display name (if any).
+                                            type_name: arg
+                                                .get("type")
+                                                .and_then(|it| it.get("displayName"))
+                                                .and_then(serde_json::Value::as_array)
+                                                .and_then(|it| it.last())
+                                                .and_then(serde_json::Value::as_str)
+                                                .map(ToString::to_string),
+                                        })
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    })
+                })
+                .collect()
+        };
+
+        Some(Self {
+            name: value
+                .get("contract")
+                .and_then(|it| it.get("name"))
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string),
+            constructors: callables("constructors")?,
+            messages: callables("messages")?,
+        })
+    }
+}
+
+/// ink! constructor or ink! message metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallableMetadata {
+    /// Label of the callable (e.g. `flip` or `Flip::flip` for trait implementations).
+    pub label: String,
+    /// Selector of the callable.
+    pub selector: u32,
+    /// Arguments of the callable.
+    pub args: Vec<ArgMetadata>,
+}
+
+/// ink! constructor or ink! message argument metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgMetadata {
+    /// Label (i.e. name) of the argument.
+    pub label: String,
+    /// Display name of the argument type (if any) (e.g. `AccountId` or `Vec`).
+    pub type_name: Option<String>,
+}
+
+/// Returns diagnostics for drift between the ink! contract code and the ink! contract metadata (if any).
+///
+/// The metadata is matched against the crate name (if known) or the names of the ink! contracts in the file,
+/// a single diagnostic is returned if the metadata doesn't match either.
+pub fn verify(
+    file: &InkFile,
+    metadata: &ContractMetadata,
+    crate_name: Option<&str>,
+) -> Vec<Diagnostic> {
+    let mut results = Vec::new();
+
+    // Determines the ink! contracts described by the metadata.
+    // NOTE: Crate names can use `-` while the metadata name (like the mod name) uses `_`.
+    let contracts: Vec<&Contract> = match metadata.name.as_deref() {
+        // Verifies all ink! contracts if the metadata doesn't have a name.
+        None => file.contracts().iter().collect(),
+        Some(name) => {
+            let name = name.replace('-', "_");
+            match crate_name.map(|crate_name| crate_name.replace('-', "_")) {
+                // Verifies all ink! contracts in the crate named by the metadata.
+                Some(crate_name) if crate_name == name => file.contracts().iter().collect(),
+                // Otherwise, only verifies the ink! contract named by the metadata (if any).
+                Some(_) => Vec::new(),
+                None => file
+                    .contracts()
+                    .iter()
+                    .filter(|contract| contract_name(contract).as_deref() == Some(name.as_str()))
+                    .collect(),
+            }
+        }
+    };
+
+    // Reports unmatched metadata once (i.e. instead of drift for every callable).
+    if contracts.is_empty() {
+        if let Some((name, contract)) = metadata.name.as_ref().zip(file.contracts().first()) {
+            results.push(Diagnostic {
+                message: match crate_name {
+                    Some(crate_name) => format!(
+                        "The contract metadata is for `{name}`, \
+                        but this file belongs to the `{crate_name}` crate."
+                    ),
+                    None => format!(
+                        "The contract metadata is for `{name}`, \
+                        which doesn't match any ink! contract in this file."
+                    ),
+                },
+                range: utils::contract_declaration_range(contract),
+                severity: Severity::Warning,
+                code: Some(DiagnosticCode::MetadataMismatch),
+                quickfixes: None,
+                related_information: None,
+            });
+        }
+        return results;
+    }
+
+    for contract in contracts {
+        // Verifies ink! constructors.
+        verify_callables(
+            &mut results,
+            contract,
+            contract.constructors(),
+            &metadata.constructors,
+            "constructor",
+        );
+
+        // Verifies ink! messages.
+        verify_callables(
+            &mut results,
+            contract,
+            contract.messages(),
+            &metadata.messages,
+            "message",
+        );
+    }
+
+    results
+}
+
+/// Verifies ink! callables (i.e. ink! constructors or ink! messages) against their metadata.
+fn verify_callables<T>(
+    results: &mut Vec<Diagnostic>,
+    contract: &Contract,
+    callables: &[T],
+    metadata: &[CallableMetadata],
+    ink_scope_name: &str,
+) where
+    T: IsInkCallable,
+{
+    let mut verified_labels = Vec::new();
+    for callable in callables {
+        if let Some((label, fn_item)) = callable_label(callable).zip(callable.fn_item()) {
+            // Gets the declaration range for the item.
+            let range = utils::ast_item_declaration_range(&ast::Item::Fn(fn_item.clone()))
+                .unwrap_or(callable.syntax().text_range());
+
+            match metadata.iter().find(|it| it.label == label) {
+                Some(callable_metadata) => {
+                    // Verifies selector.
+                    if let Some(selector) = callable.composed_selector() {
+                        let selector = selector.into_be_u32();
+                        if selector != callable_metadata.selector {
+                            results.push(Diagnostic {
+                                message: format!(
+                                    "Selector for ink! {ink_scope_name} `{label}` is `0x{selector:08x}`, \
+                                    but the contract metadata expects `0x{:08x}`.",
+                                    callable_metadata.selector
+                                ),
+                                range: callable
+                                    .selector_arg()
+                                    .map_or(range, |arg| arg.text_range()),
+                                severity: Severity::Warning,
+                                code: Some(DiagnosticCode::MetadataMismatch),
+                                quickfixes: None,
+                                related_information: None,
+                            });
+                        }
+                    }
+
+                    // Verifies arguments.
+                    verify_args(results, fn_item, range, &label, callable_metadata);
+
+                    verified_labels.push(label);
+                }
+                None => results.push(Diagnostic {
+                    message: format!(
+                        "ink! {ink_scope_name} `{label}` is not defined in the contract metadata."
+                    ),
+                    range,
+                    severity: Severity::Warning,
+                    code: Some(DiagnosticCode::MetadataMismatch),
+                    quickfixes: None,
+                    related_information: None,
+                }),
+            }
+        }
+    }
+
+    // Verifies that all callables in the metadata are defined in code.
+    for callable_metadata in metadata
+        .iter()
+        .filter(|it| !verified_labels.contains(&it.label))
+    {
+        results.push(Diagnostic {
+            message: format!(
+                "ink! {ink_scope_name} `{}` from the contract metadata is not defined in code.",
+                callable_metadata.label
+            ),
+            range: utils::contract_declaration_range(contract),
+            severity: Severity::Warning,
+            code: Some(DiagnosticCode::MetadataMismatch),
+            quickfixes: None,
+            related_information: None,
+        });
+    }
+}
+
+/// Verifies the arguments of an ink! callable against its metadata.
+fn verify_args(
+    results: &mut Vec<Diagnostic>,
+    fn_item: &ast::Fn,
+    range: TextRange,
+    label: &str,
+    metadata: &CallableMetadata,
+) {
+    let params: Vec<ast::Param> = fn_item
+        .param_list()
+        .map(|param_list| param_list.params().collect())
+        .unwrap_or_default();
+
+    if params.len() != metadata.args.len() {
+        results.push(Diagnostic {
+            message: format!(
+                "`{label}` has {} argument(s), but the contract metadata expects {}.",
+                params.len(),
+                metadata.args.len()
+            ),
+            range: fn_item
+                .param_list()
+                .map_or(range, |param_list| param_list.syntax().text_range()),
+            severity: Severity::Warning,
+            code: Some(DiagnosticCode::MetadataMismatch),
+            quickfixes: None,
+            related_information: None,
+        });
+        return;
+    }
+
+    for (param, arg_metadata) in params.iter().zip(metadata.args.iter()) {
+        let name = param.pat().map(|pat| pat.syntax().to_string());
+        if name.as_deref() != Some(arg_metadata.label.as_str()) {
+            results.push(Diagnostic {
+                message: format!(
+                    "Argument `{}` of `{label}` is named `{}` in the contract metadata.",
+                    name.unwrap_or_default(),
+                    arg_metadata.label
+                ),
+                range: param.syntax().text_range(),
+                severity: Severity::Warning,
+                code: Some(DiagnosticCode::MetadataMismatch),
+                quickfixes: None,
+                related_information: None,
+            });
+        }
+
+        // Only path types have a comparable display name (e.g. `AccountId` for `AccountId` or `Vec` for `Vec<u8>`).
+        let type_name = param.ty().as_ref().and_then(path_type_name);
+        if let Some((type_name, expected_type_name)) =
+            type_name.zip(arg_metadata.type_name.as_ref())
+        {
+            if type_name != *expected_type_name {
+                results.push(Diagnostic {
+                    message: format!(
+                        "Argument `{}` of `{label}` has type `{type_name}`, \
+                        but the contract metadata expects `{expected_type_name}`.",
+                        arg_metadata.label
+                    ),
+                    range: param
+                        .ty()
+                        .map_or(param.syntax().text_range(), |ty| ty.syntax().text_range()),
+                    severity: Severity::Warning,
+                    code: Some(DiagnosticCode::MetadataMismatch),
+                    quickfixes: None,
+                    related_information: None,
+                });
+            }
+        }
+    }
+}
+
+/// Returns the name of the ink! contract's `mod` item (if any).
+fn contract_name(contract: &Contract) -> Option<String> {
+    contract.module()?.name().map(|name| name.to_string())
+}
+
+/// Returns the metadata label for an ink! callable
+/// (i.e. the `fn` name or `TraitName::fn_name` for trait implementations).
+pub fn callable_label<T>(callable: &T) -> Option<String>
+where
+    T: IsInkCallable,
+{
+    let name = callable.fn_item()?.name()?.to_string();
+    Some(
+        match callable
+            .impl_item()
+            .and_then(|impl_item| impl_item.trait_())
+            .as_ref()
+            .and_then(path_type_name)
+        {
+            Some(trait_name) => format!("{trait_name}::{name}"),
+            None => name,
+        },
+    )
+}
+
+/// Returns the name of the last path segment (without generic arguments) for a path type (if any).
+fn path_type_name(ty: &ast::Type) -> Option<String> {
+    match ty {
+        ast::Type::PathType(path_type) => path_type
+            .path()?
+            .segment()?
+            .name_ref()
+            .map(|name_ref| name_ref.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::quote_as_str;
+
+    fn flipper_metadata() -> ContractMetadata {
+        ContractMetadata {
+            name: Some("flipper".to_string()),
+            constructors: vec![CallableMetadata {
+                label: "new".to_string(),
+                selector: 0x9bae9d5e,
+                args: vec![ArgMetadata {
+                    label: "init_value".to_string(),
+                    type_name: Some("bool".to_string()),
+                }],
+            }],
+            messages: vec![
+                CallableMetadata {
+                    label: "flip".to_string(),
+                    selector: 0x633aa551,
+                    args: Vec::new(),
+                },
+                CallableMetadata {
+                    label: "get".to_string(),
+                    selector: 0x2f865bd9,
+                    args: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn matching_metadata_works() {
+        let file = InkFile::parse(quote_as_str! {
+            #[ink::contract]
+            mod flipper {
+                #[ink(storage)]
+                pub struct Flipper {
+                    value: bool,
+                }
+
+                impl Flipper {
+                    #[ink(constructor)]
+                    pub fn new(init_value: bool) -> Self {
+                        Self { value: init_value }
+                    }
+
+                    #[ink(message)]
+                    pub fn flip(&mut self) {
+                        self.value = !self.value;
+                    }
+
+                    #[ink(message)]
+                    pub fn get(&self) -> bool {
+                        self.value
+                    }
+                }
+            }
+        });
+
+        let results = verify(&file, &flipper_metadata(), None);
+        assert!(results.is_empty(), "{results:?}");
+    }
+
+    #[test]
+    fn drifting_metadata_fails() {
+        for (code, expected_messages) in [
+            // Custom selector.
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod flipper {
+                        impl Flipper {
+                            #[ink(constructor)]
+                            pub fn new(init_value: bool) -> Self {}
+
+                            #[ink(message, selector = 1)]
+                            pub fn flip(&mut self) {}
+
+                            #[ink(message)]
+                            pub fn get(&self) -> bool {}
+                        }
+                    }
+                },
+                vec!["Selector for ink! message `flip`"],
+            ),
+            // Renamed message.
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod flipper {
+                        impl Flipper {
+                            #[ink(constructor)]
+                            pub fn new(init_value: bool) -> Self {}
+
+                            #[ink(message)]
+                            pub fn toggle(&mut self) {}
+
+                            #[ink(message)]
+                            pub fn get(&self) -> bool {}
+                        }
+                    }
+                },
+                vec![
+                    "ink! message `toggle` is not defined in the contract metadata",
+                    "ink! message `flip` from the contract metadata is not defined in code",
+                ],
+            ),
+            // Argument drift.
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod flipper {
+                        impl Flipper {
+                            #[ink(constructor)]
+                            pub fn new(value: u8) -> Self {}
+
+                            #[ink(message)]
+                            pub fn flip(&mut self) {}
+
+                            #[ink(message)]
+                            pub fn get(&self) -> bool {}
+                        }
+                    }
+                },
+                vec![
+                    "Argument `value` of `new` is named `init_value`",
+                    "Argument `init_value` of `new` has type `u8`",
+                ],
+            ),
+            // Argument count drift.
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod flipper {
+                        impl Flipper {
+                            #[ink(constructor)]
+                            pub fn new() -> Self {}
+
+                            #[ink(message)]
+                            pub fn flip(&mut self) {}
+
+                            #[ink(message)]
+                            pub fn get(&self) -> bool {}
+                        }
+                    }
+                },
+                vec!["`new` has 0 argument(s), but the contract metadata expects 1"],
+            ),
+        ] {
+            let results = verify(&InkFile::parse(code), &flipper_metadata(), None);
+
+            assert_eq!(results.len(), expected_messages.len(), "code: {code}");
+            for (idx, expected_message) in expected_messages.iter().enumerate() {
+                assert!(
+                    results[idx].message.contains(expected_message),
+                    "code: {code}, message: {}",
+                    results[idx].message
+                );
+                assert_eq!(results[idx].severity, Severity::Warning, "code: {code}");
+            }
+        }
+    }
+
+    #[test]
+    fn verify_named_or_all_contracts_works() {
+        let file = InkFile::parse(quote_as_str! {
+            #[ink::contract]
+            mod other {
+                impl Other {
+                    #[ink(constructor)]
+                    pub fn default() -> Self {}
+                }
+            }
+
+            #[ink::contract]
+            mod flipper {
+                impl Flipper {
+                    #[ink(constructor)]
+                    pub fn new(init_value: bool) -> Self {}
+
+                    #[ink(message)]
+                    pub fn flip(&mut self) {}
+
+                    #[ink(message)]
+                    pub fn get(&self) -> bool {}
+                }
+            }
+        });
+
+        // Only verifies the ink! contract named by the metadata.
+        let results = verify(&file, &flipper_metadata(), None);
+        assert!(results.is_empty(), "{results:?}");
+
+        // Verifies all ink! contracts if the metadata doesn't have a name,
+        // or if it names the crate (i.e. instead of one of the ink! contracts).
+        for (name, crate_name) in [
+            (None, None),
+            (None, Some("my-contracts")),
+            (Some("my-contracts"), Some("my-contracts")),
+            (Some("my_contracts"), Some("my-contracts")),
+        ] {
+            let metadata = ContractMetadata {
+                name: name.map(ToString::to_string),
+                ..flipper_metadata()
+            };
+            let results = verify(&file, &metadata, crate_name);
+            // `default` isn't in the metadata, and `new`, `flip` and `get` aren't defined in `other`.
+            assert_eq!(results.len(), 4, "{results:?}");
+        }
+
+        // Reports unmatched metadata once.
+        for (name, crate_name, expected_message) in [
+            (
+                "unknown",
+                None,
+                "doesn't match any ink! contract in this file",
+            ),
+            ("unknown", Some("flipper"), "belongs to the `flipper` crate"),
+            // The crate name takes precedence over the ink! contract names.
+            (
+                "flipper",
+                Some("my-contracts"),
+                "belongs to the `my-contracts` crate",
+            ),
+        ] {
+            let metadata = ContractMetadata {
+                name: Some(name.to_string()),
+                ..flipper_metadata()
+            };
+            let results = verify(&file, &metadata, crate_name);
+            assert_eq!(results.len(), 1, "{results:?}");
+            assert!(
+                results[0].message.contains(expected_message),
+                "{}",
+                results[0].message
+            );
+            assert_eq!(results[0].code, Some(DiagnosticCode::MetadataMismatch));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn from_json_works() {
+        for (json, expected_name) in [
+            // ink! >= 4.x metadata.
+            (
+                r#"{
+                    "contract": { "name": "flipper", "version": "0.1.0" },
+                    "spec": {
+                        "constructors": [{
+                            "label": "new",
+                            "selector": "0x9bae9d5e",
+                            "args": [{
+                                "label": "init_value",
+                                "type": { "displayName": ["bool"], "type": 0 }
+                            }]
+                        }],
+                        "messages": [
+                            { "label": "flip", "selector": "0x633aa551", "args": [] },
+                            { "label": "get", "selector": "0x2f865bd9", "args": [] }
+                        ]
+                    }
+                }"#,
+                Some("flipper"),
+            ),
+            // ink! 3.x metadata.
+            (
+                r#"{
+                    "V3": {
+                        "spec": {
+                            "constructors": [{
+                                "label": "new",
+                                "selector": "0x9bae9d5e",
+                                "args": [{
+                                    "label": "init_value",
+                                    "type": { "displayName": ["bool"], "type": 0 }
+                                }]
+                            }],
+                            "messages": [
+                                { "label": "flip", "selector": "0x633aa551", "args": [] },
+                                { "label": "get", "selector": "0x2f865bd9", "args": [] }
+                            ]
+                        }
+                    }
+                }"#,
+                None,
+            ),
+        ] {
+            assert_eq!(
+                ContractMetadata::from_json(json),
+                Some(ContractMetadata {
+                    name: expected_name.map(ToString::to_string),
+                    ..flipper_metadata()
+                }),
+                "json: {json}"
+            );
+        }
+
+        // Invalid metadata.
+        assert!(ContractMetadata::from_json("{}").is_none());
+        assert!(ContractMetadata::from_json("not json").is_none());
+    }
+}
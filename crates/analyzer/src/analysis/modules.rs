@@ -0,0 +1,181 @@
+//! Utilities for ink! contracts split across multiple files (i.e. via out-of-line child modules).
+
+use ink_analyzer_ir::ast::{HasModuleItem, HasName};
+use ink_analyzer_ir::{ast, InkFile};
+use std::path::{Path, PathBuf};
+
+/// Returns the names of the out-of-line child modules (i.e. `mod x;` declarations)
+/// in the root of the ink! contracts in the file.
+pub fn contract_child_modules(file: &InkFile) -> Vec<String> {
+    file.contracts()
+        .iter()
+        .filter_map(|contract| contract.module()?.item_list())
+        .flat_map(|item_list| item_list.items())
+        .filter_map(|item| match item {
+            ast::Item::Module(module) if module.item_list().is_none() => {
+                module.name().map(|name| name.to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the candidate paths of the parent module files of the Rust file at the given path,
+/// along with the name of the module in the parent module file
+/// (i.e. the module name a `mod x;` declaration in the parent module file would use).
+///
+/// Crate roots (i.e. `lib.rs` and `main.rs`) have no parent module.
+///
+/// Ref: <https://doc.rust-lang.org/reference/items/modules.html#module-source-filenames>.
+pub fn parent_module_candidates(path: &Path) -> Vec<(PathBuf, String)> {
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return Vec::new();
+    };
+    let (dir, name) = match stem {
+        "lib" | "main" => return Vec::new(),
+        // `x/mod.rs` is the module `x` of the parent directory's module.
+        "mod" => match path
+            .parent()
+            .and_then(|dir| dir.parent().zip(dir.file_name()?.to_str()))
+        {
+            Some((dir, name)) => (dir, name),
+            None => return Vec::new(),
+        },
+        // `x.rs` is the module `x` of the current directory's module.
+        name => match path.parent() {
+            Some(dir) => (dir, name),
+            None => return Vec::new(),
+        },
+    };
+
+    ["lib.rs", "main.rs", "mod.rs"]
+        .into_iter()
+        .map(|file_name| dir.join(file_name))
+        // `y/x.rs` can also be a child module of `y.rs`.
+        .chain(
+            dir.file_name().and_then(|dir_name| {
+                Some(dir.parent()?.join(format!("{}.rs", dir_name.to_str()?)))
+            }),
+        )
+        .map(|parent_path| (parent_path, name.to_string()))
+        .collect()
+}
+
+/// Returns the candidate paths of the file of the out-of-line child module with the given name
+/// declared in the Rust file at the given path (i.e. the file a `mod x;` declaration refers to).
+///
+/// Ref: <https://doc.rust-lang.org/reference/items/modules.html#module-source-filenames>.
+pub fn child_module_candidates(path: &Path, name: &str) -> Vec<PathBuf> {
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    let dir = match path.file_stem().and_then(|stem| stem.to_str()) {
+        // Child modules of `lib.rs`, `main.rs` and `x/mod.rs` are in the same directory.
+        Some("lib" | "main" | "mod") | None => dir.to_path_buf(),
+        // Child modules of `x.rs` are in the `x` directory.
+        Some(stem) => dir.join(stem),
+    };
+    vec![
+        dir.join(format!("{name}.rs")),
+        dir.join(name).join("mod.rs"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::quote_as_str;
+
+    #[test]
+    fn contract_child_modules_works() {
+        for (code, expected_results) in [
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod my_contract {
+                        mod messages;
+                        mod inline {}
+                        pub mod events;
+                    }
+
+                    mod not_a_child;
+                },
+                vec!["messages", "events"],
+            ),
+            (
+                quote_as_str! {
+                    mod my_module;
+                },
+                vec![],
+            ),
+        ] {
+            assert_eq!(
+                contract_child_modules(&InkFile::parse(code)),
+                expected_results
+            );
+        }
+    }
+
+    #[test]
+    fn parent_module_candidates_works() {
+        for (path, expected_results) in [
+            ("src/lib.rs", vec![]),
+            ("src/main.rs", vec![]),
+            (
+                "src/messages.rs",
+                vec![
+                    ("src/lib.rs", "messages"),
+                    ("src/main.rs", "messages"),
+                    ("src/mod.rs", "messages"),
+                    ("src.rs", "messages"),
+                ],
+            ),
+            (
+                "src/messages/mod.rs",
+                vec![
+                    ("src/lib.rs", "messages"),
+                    ("src/main.rs", "messages"),
+                    ("src/mod.rs", "messages"),
+                    ("src.rs", "messages"),
+                ],
+            ),
+        ] {
+            assert_eq!(
+                parent_module_candidates(Path::new(path)),
+                expected_results
+                    .into_iter()
+                    .map(|(path, name)| (PathBuf::from(path), name.to_string()))
+                    .collect::<Vec<_>>(),
+                "path: {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn child_module_candidates_works() {
+        for (path, expected_results) in [
+            ("src/lib.rs", vec!["src/messages.rs", "src/messages/mod.rs"]),
+            (
+                "src/main.rs",
+                vec!["src/messages.rs", "src/messages/mod.rs"],
+            ),
+            (
+                "src/contract/mod.rs",
+                vec!["src/contract/messages.rs", "src/contract/messages/mod.rs"],
+            ),
+            (
+                "src/contract.rs",
+                vec!["src/contract/messages.rs", "src/contract/messages/mod.rs"],
+            ),
+        ] {
+            assert_eq!(
+                child_module_candidates(Path::new(path), "messages"),
+                expected_results
+                    .into_iter()
+                    .map(PathBuf::from)
+                    .collect::<Vec<_>>(),
+                "path: {path}"
+            );
+        }
+    }
+}
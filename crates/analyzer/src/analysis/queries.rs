@@ -0,0 +1,463 @@
+//! Memoized analysis queries.
+//!
+//! Intermediate results (i.e. the ink! entity index, the selector table and diagnostics)
+//! are computed lazily (i.e. on first use) and then shared by all analysis calls on the same file snapshot
+//! (e.g. diagnostics are computed once for both diagnostics requests and code action requests).
+//!
+//! The symbols and selectors of a file can also be exported as a [`FileIndex`]
+//! that's keyed by a hash of the file contents (e.g. for persisting a [`SymbolIndex`] between sessions,
+//! and checking for overlapping selectors across the files of an ink! contract without re-analyzing them).
+
+use ink_analyzer_ir::syntax::{AstNode, TextRange};
+use ink_analyzer_ir::{FromSyntax, InkFile, InkIndex, IsInkCallable, IsInkImplItem, Selector};
+use once_cell::unsync::OnceCell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::diagnostics;
+use super::modules;
+use super::registry::MacroRegistry;
+use super::symbols::{self, Symbol, SymbolKind};
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsConfig, Severity, Version};
+
+/// Composed selectors of ink! constructors and ink! messages keyed by the text range of their `fn` item.
+pub type SelectorTable = HashMap<TextRange, Selector>;
+
+/// A persistable index of the ink! entity symbols and selectors of a file.
+///
+/// Indexes are only valid for file contents with the same hash (see [`content_hash`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileIndex {
+    /// Hash of the indexed file contents.
+    pub hash: u64,
+    /// Symbols for ink! entities in the file.
+    pub symbols: Vec<Symbol>,
+    /// Composed selectors of ink! constructors and ink! messages in `impl` blocks in the file
+    /// (i.e. excluding ink! trait definition messages) (sorted by range).
+    pub selectors: Vec<IndexedSelector>,
+    /// Names of the out-of-line child modules in the root of the ink! contracts in the file
+    /// (see [`Analysis::contract_child_modules`](crate::Analysis::contract_child_modules)).
+    pub contract_child_modules: Vec<String>,
+}
+
+impl FileIndex {
+    /// Returns diagnostics for composed selectors in the file that overlap with the composed selectors
+    /// of the same kind in the other files (i.e. the other files of an ink! contract that's split
+    /// across multiple files via out-of-line child modules), paired with the id of the other file.
+    ///
+    /// Overlaps between ink! constructor and message selectors are allowed.
+    pub fn overlapping_selectors<'a, I>(&self, others: I) -> Vec<Diagnostic>
+    where
+        I: IntoIterator<Item = (&'a str, &'a FileIndex)>,
+    {
+        let others: Vec<(&str, &FileIndex)> = others.into_iter().collect();
+        self.selectors
+            .iter()
+            .filter_map(|item| {
+                let (id, _) = others.iter().find(|(_, other)| {
+                    other
+                        .selectors
+                        .iter()
+                        .any(|it| it.kind == item.kind && it.selector == item.selector)
+                })?;
+                Some(Diagnostic {
+                    message: format!(
+                        "Selectors must be unique across all ink! {}s in an ink! contract, \
+                        but `0x{:08x}` is also used in `{id}`.",
+                        item.kind.name(),
+                        item.selector
+                    ),
+                    range: item.range,
+                    severity: Severity::Error,
+                    code: Some(DiagnosticCode::OverlappingSelectors),
+                    quickfixes: None,
+                    related_information: None,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The composed selector of an ink! constructor or ink! message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedSelector {
+    /// Text range of the `fn` item.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
+    pub range: TextRange,
+    /// The composed selector (as a big-endian `u32`).
+    pub selector: u32,
+    /// The kind of the ink! entity (i.e. [`SymbolKind::Constructor`] or [`SymbolKind::Message`]).
+    pub kind: SymbolKind,
+}
+
+/// Returns a stable hash of the file contents (i.e. a 64-bit FNV-1a hash),
+/// used to invalidate persisted file indexes (see [`FileIndex`]).
+pub fn content_hash(code: &str) -> u64 {
+    fnv1a(FNV_OFFSET_BASIS, code)
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Updates the FNV-1a hash with the text.
+fn fnv1a(hash: u64, text: &str) -> u64 {
+    text.bytes().fold(hash, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Maximum number of memoized diagnostics results (i.e. for distinct diagnostics configurations).
+const MAX_MEMOIZED_DIAGNOSTICS: usize = 4;
+
+/// Memoized queries for a single file snapshot.
+///
+/// **NOTE**: Query results are never invalidated,
+/// so an instance must only ever be used with the same file, ink! version and macro registry
+/// (i.e. a new instance is created for each new file snapshot).
+#[derive(Debug, Default)]
+pub struct Queries {
+    /// ink! entity index.
+    index: OnceCell<InkIndex>,
+    /// Selector table.
+    selectors: OnceCell<SelectorTable>,
+    /// Diagnostics for recently used configurations (least recently computed first).
+    diagnostics: RefCell<Vec<(DiagnosticsConfig, Vec<Diagnostic>)>>,
+}
+
+impl Queries {
+    /// Returns the ink! entity index for the file.
+    pub fn index(&self, file: &InkFile) -> &InkIndex {
+        self.index.get_or_init(|| InkIndex::new(file))
+    }
+
+    /// Returns the selector table for the file.
+    pub fn selectors(&self, file: &InkFile) -> &SelectorTable {
+        self.selectors.get_or_init(|| {
+            let index = self.index(file);
+            let mut table = SelectorTable::new();
+            add_selectors(&mut table, index.constructors());
+            add_selectors(&mut table, index.messages());
+            table
+        })
+    }
+
+    /// Returns a persistable index of the symbols and selectors for the file
+    /// (i.e. built on top of the ink! entity index and the selector table).
+    pub fn file_index(&self, file: &InkFile) -> FileIndex {
+        let mut hash = FNV_OFFSET_BASIS;
+        file.syntax()
+            .text()
+            .for_each_chunk(|chunk| hash = fnv1a(hash, chunk));
+        let index = self.index(file);
+        let table = self.selectors(file);
+        let mut selectors: Vec<IndexedSelector> =
+            indexed_selectors(table, index.constructors(), SymbolKind::Constructor)
+                .chain(indexed_selectors(
+                    table,
+                    index.messages(),
+                    SymbolKind::Message,
+                ))
+                .collect();
+        selectors.sort_by_key(|item| (item.range.start(), item.range.end()));
+        FileIndex {
+            hash,
+            symbols: symbols::symbols(file),
+            selectors,
+            contract_child_modules: modules::contract_child_modules(file),
+        }
+    }
+
+    /// Returns the (unsorted and unindented) diagnostics for the file and the configuration.
+    pub fn diagnostics(
+        &self,
+        file: &InkFile,
+        version: Version,
+        config: &DiagnosticsConfig,
+        registry: &MacroRegistry,
+    ) -> Vec<Diagnostic> {
+        let mut results = Vec::new();
+        self.diagnostics_with(file, version, config, registry, |diagnostic| {
+            results.push(diagnostic);
+        });
+        results
+    }
+
+    /// Passes each (unindented) diagnostic for the file and the configuration to the callback
+    /// as soon as it's available (i.e. either from the memo, or while the diagnostics are computed).
+    ///
+    /// Streamed diagnostics are memoized once complete (i.e. they're not recomputed by subsequent calls).
+    pub fn diagnostics_with<F>(
+        &self,
+        file: &InkFile,
+        version: Version,
+        config: &DiagnosticsConfig,
+        registry: &MacroRegistry,
+        mut callback: F,
+    ) where
+        F: FnMut(Diagnostic),
+    {
+        let memoized = self
+            .diagnostics
+            .borrow()
+            .iter()
+            .find(|(memo_config, _)| memo_config == config)
+            .map(|(_, results)| results.clone());
+        if let Some(results) = memoized {
+            results.into_iter().for_each(callback);
+            return;
+        }
+
+        let mut results = Vec::new();
+        diagnostics::diagnostics_with(file, version, config, registry, |diagnostic| {
+            results.push(diagnostic.clone());
+            callback(diagnostic);
+        });
+        let mut memo = self.diagnostics.borrow_mut();
+        if memo.len() >= MAX_MEMOIZED_DIAGNOSTICS {
+            memo.remove(0);
+        }
+        memo.push((config.clone(), results));
+    }
+}
+
+/// Adds the composed selectors of the ink! callables to the selector table.
+fn add_selectors<T>(table: &mut SelectorTable, callables: &[T])
+where
+    T: IsInkCallable,
+{
+    for callable in callables {
+        if let (Some(fn_item), Some(selector)) = (callable.fn_item(), callable.composed_selector())
+        {
+            table.insert(fn_item.syntax().text_range(), selector);
+        }
+    }
+}
+
+/// Returns the indexed selectors of the ink! callables in `impl` blocks
+/// (i.e. excluding ink! trait definition messages).
+fn indexed_selectors<'a, T>(
+    table: &'a SelectorTable,
+    callables: &'a [T],
+    kind: SymbolKind,
+) -> impl Iterator<Item = IndexedSelector> + 'a
+where
+    T: IsInkCallable,
+{
+    callables
+        .iter()
+        .filter(|callable| callable.impl_item().is_some())
+        .filter_map(move |callable| {
+            let range = callable.fn_item()?.syntax().text_range();
+            table.get(&range).map(|selector| IndexedSelector {
+                range,
+                selector: selector.into_be_u32(),
+                kind,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::utils;
+    use ink_analyzer_ir::syntax::TextSize;
+    use ink_analyzer_ir::IsInkFn;
+
+    #[test]
+    fn queries_works() {
+        let code = r#"
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                impl MyContract {
+                    #[ink(constructor)]
+                    pub fn new() -> Self {}
+
+                    #[ink(message, selector = 0xA)]
+                    pub fn my_message(&self) {}
+                }
+            }
+        "#;
+        let file = InkFile::parse(code);
+        let queries = Queries::default();
+        let registry = MacroRegistry::default();
+
+        // Index and selector table are computed once.
+        let index = queries.index(&file);
+        assert_eq!(index.messages().len(), 1);
+        assert!(std::ptr::eq(index, queries.index(&file)));
+        let selectors = queries.selectors(&file);
+        assert_eq!(selectors.len(), 2);
+        let message_range = index.messages()[0].fn_item().unwrap().syntax().text_range();
+        assert_eq!(
+            selectors
+                .get(&message_range)
+                .map(|selector| selector.into_be_u32()),
+            Some(0xA)
+        );
+
+        // Diagnostics are memoized per configuration.
+        let config = DiagnosticsConfig::default();
+        let results = queries.diagnostics(&file, Version::V4, &config, &registry);
+        assert_eq!(
+            results,
+            diagnostics::diagnostics(&file, Version::V4, &config, &registry)
+        );
+        assert_eq!(
+            queries.diagnostics(&file, Version::V4, &config, &registry),
+            results
+        );
+        let audit_config = DiagnosticsConfig {
+            audit: true,
+            ..Default::default()
+        };
+        queries.diagnostics(&file, Version::V4, &audit_config, &registry);
+        assert_eq!(queries.diagnostics.borrow().len(), 2);
+
+        // Streamed diagnostics are memoized.
+        let streaming_config = DiagnosticsConfig {
+            max_event_topics: 2,
+            ..Default::default()
+        };
+        let mut streamed = Vec::new();
+        queries.diagnostics_with(
+            &file,
+            Version::V4,
+            &streaming_config,
+            &registry,
+            |diagnostic| streamed.push(diagnostic),
+        );
+        assert_eq!(queries.diagnostics.borrow().len(), 3);
+        assert_eq!(
+            queries.diagnostics(&file, Version::V4, &streaming_config, &registry),
+            streamed
+        );
+        assert_eq!(queries.diagnostics.borrow().len(), 3);
+
+        // Memoized diagnostics are bounded.
+        for max_event_topics in 0..MAX_MEMOIZED_DIAGNOSTICS {
+            let config = DiagnosticsConfig {
+                max_event_topics,
+                ..Default::default()
+            };
+            queries.diagnostics(&file, Version::V4, &config, &registry);
+        }
+        assert_eq!(queries.diagnostics.borrow().len(), MAX_MEMOIZED_DIAGNOSTICS);
+
+        // File index is built from the ink! entity index and the selector table.
+        let file_index = queries.file_index(&file);
+        assert_eq!(file_index.hash, content_hash(code));
+        assert_eq!(file_index.symbols, symbols::symbols(&file));
+        assert_eq!(file_index.selectors.len(), 2);
+        assert_eq!(
+            file_index.selectors[1],
+            IndexedSelector {
+                range: message_range,
+                selector: 0xA,
+                kind: SymbolKind::Message,
+            }
+        );
+    }
+
+    #[test]
+    fn overlapping_selectors_works() {
+        let file_index = |code: &str| Queries::default().file_index(&InkFile::parse(code));
+        let root = file_index(
+            r#"
+            #[ink::contract]
+            mod my_contract {
+                mod messages;
+
+                impl MyContract {
+                    #[ink(constructor)]
+                    pub fn new() -> Self {}
+
+                    #[ink(message)]
+                    pub fn get(&self) {}
+                }
+            }
+        "#,
+        );
+        let child = file_index(
+            r#"
+            impl MyContract {
+                #[ink(constructor, selector = 0x9bae9d5e)]
+                pub fn default() -> Self {}
+
+                #[ink(message, selector = 0x9bae9d5e)]
+                pub fn set(&mut self) {}
+
+                #[ink(message)]
+                pub fn get_value(&self) {}
+            }
+
+            #[ink::trait_definition]
+            pub trait MyTrait {
+                #[ink(message, selector = 0x2f865bd9)]
+                fn get(&self);
+            }
+        "#,
+        );
+
+        // Trait definition messages aren't indexed.
+        assert_eq!(child.selectors.len(), 3);
+
+        // Only overlaps between callables of the same kind are reported.
+        let results = child.overlapping_selectors([("lib.rs", &root)]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].range, child.selectors[0].range);
+        assert!(results[0]
+            .message
+            .contains("`0x9bae9d5e` is also used in `lib.rs`"));
+        assert!(root.overlapping_selectors([]).is_empty());
+    }
+
+    #[test]
+    fn indexed_covering_ink_attribute_works() {
+        let code = r#"
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                impl MyContract {
+                    #[ink(constructor, payable)]
+                    pub fn new() -> Self {}
+
+                    #[doc(hidden)]
+                    #[ink(message, selector = 0xA)]
+                    pub fn my_message(&self) {}
+                }
+            }
+        "#;
+        let file = InkFile::parse(code);
+        let queries = Queries::default();
+
+        // Index lookups return the same ink! attributes as syntax tree traversals.
+        let len = TextSize::of(code);
+        for start in 0..=u32::from(len) {
+            for range in [
+                TextRange::empty(start.into()),
+                TextRange::new(start.into(), (start + 1).min(len.into()).into()),
+            ] {
+                assert_eq!(
+                    utils::indexed_covering_ink_attribute(&file, &queries, range),
+                    utils::covering_ink_attribute(&file, range),
+                    "range: {range:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn content_hash_works() {
+        // Reference values for the 64-bit FNV-1a hash (i.e. hashes are stable between sessions).
+        assert_eq!(content_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(content_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(content_hash("mod a {}"), content_hash("mod b {}"));
+    }
+}
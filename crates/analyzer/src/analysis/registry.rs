@@ -0,0 +1,136 @@
+//! Runtime registry for ink! attribute macros that are unknown to ink! analyzer
+//! (e.g. experimental `#[ink::precompile]` macros or macros from newer ink! versions).
+
+use ink_analyzer_ir::syntax::SyntaxKind;
+use ink_analyzer_ir::{FromAST, InkAttribute, InkAttributeKind, InkMacroKind};
+
+/// A registry of ink! attribute macros that are unknown to ink! analyzer.
+///
+/// Registered macros get completions and diagnostics (i.e. scope and argument validation)
+/// based on their definition, instead of being reported as unknown ink! attributes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MacroRegistry {
+    /// Registered ink! attribute macro definitions.
+    macros: Vec<MacroDefinition>,
+}
+
+/// Definition of an ink! attribute macro that is unknown to ink! analyzer.
+///
+/// # Note
+/// Only `ink` and `ink_e2e` crate paths are recognized as ink! attributes,
+/// so definitions for macros under other crate paths are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroDefinition {
+    /// Name of the crate that defines the macro (e.g. `ink`).
+    pub crate_name: String,
+    /// Name of the macro (e.g. `precompile`).
+    pub name: String,
+    /// Item kinds to which the macro can be applied.
+    pub scopes: Vec<MacroScope>,
+    /// Names of valid arguments for the macro.
+    pub args: Vec<String>,
+}
+
+/// An item kind to which a registered ink! attribute macro can be applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MacroScope {
+    /// A `mod` item.
+    Mod,
+    /// A `struct` item.
+    Struct,
+    /// An `enum` item.
+    Enum,
+    /// A `trait` item.
+    Trait,
+    /// An `impl` item.
+    Impl,
+    /// A `fn` item.
+    Fn,
+}
+
+impl MacroRegistry {
+    /// Creates an empty ink! attribute macro registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an ink! attribute macro definition
+    /// (replacing any existing definition with the same crate and macro name).
+    pub fn register(&mut self, definition: MacroDefinition) {
+        self.macros
+            .retain(|it| it.crate_name != definition.crate_name || it.name != definition.name);
+        self.macros.push(definition);
+    }
+
+    /// Returns the definition (if any) of the ink! attribute macro with the given crate and macro name.
+    pub fn get(&self, crate_name: &str, name: &str) -> Option<&MacroDefinition> {
+        self.macros
+            .iter()
+            .find(|it| it.crate_name == crate_name && it.name == name)
+    }
+
+    /// Returns an iterator over all registered ink! attribute macro definitions.
+    pub fn iter(&self) -> impl Iterator<Item = &MacroDefinition> {
+        self.macros.iter()
+    }
+
+    /// Returns the registered definition (if any) for an ink! attribute macro unknown to ink! analyzer.
+    pub(crate) fn resolve(&self, attr: &InkAttribute) -> Option<&MacroDefinition> {
+        if *attr.kind() != InkAttributeKind::Macro(InkMacroKind::Unknown) {
+            return None;
+        }
+        // Only 2 segment paths (i.e. `crate::name`) are valid macro paths.
+        if attr.ast().path()?.segments().count() != 2 {
+            return None;
+        }
+        self.get(&attr.ink().to_string(), &attr.ink_macro()?.to_string())
+    }
+}
+
+impl MacroDefinition {
+    /// Returns the path of the macro (e.g. `ink::precompile`).
+    pub fn path(&self) -> String {
+        format!("{}::{}", self.crate_name, self.name)
+    }
+
+    /// Returns true if the macro can be applied to an item of the given syntax kind
+    /// (either the kind of the item or of the item's keyword).
+    pub fn is_valid_scope(&self, syntax_kind: SyntaxKind) -> bool {
+        self.scopes.iter().any(|scope| scope.matches(syntax_kind))
+    }
+}
+
+impl MacroScope {
+    /// Returns true if the syntax kind (either of an item or of an item's keyword) matches the scope.
+    fn matches(self, syntax_kind: SyntaxKind) -> bool {
+        matches!(
+            (self, syntax_kind),
+            (MacroScope::Mod, SyntaxKind::MODULE | SyntaxKind::MOD_KW)
+                | (
+                    MacroScope::Struct,
+                    SyntaxKind::STRUCT | SyntaxKind::STRUCT_KW
+                )
+                | (MacroScope::Enum, SyntaxKind::ENUM | SyntaxKind::ENUM_KW)
+                | (MacroScope::Trait, SyntaxKind::TRAIT | SyntaxKind::TRAIT_KW)
+                | (MacroScope::Impl, SyntaxKind::IMPL | SyntaxKind::IMPL_KW)
+                | (MacroScope::Fn, SyntaxKind::FN | SyntaxKind::FN_KW)
+        )
+    }
+}
+
+impl std::fmt::Display for MacroScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MacroScope::Mod => "mod",
+                MacroScope::Struct => "struct",
+                MacroScope::Enum => "enum",
+                MacroScope::Trait => "trait",
+                MacroScope::Impl => "impl",
+                MacroScope::Fn => "fn",
+            }
+        )
+    }
+}
@@ -0,0 +1,190 @@
+//! ink! syntax aware selection ranges (i.e. for "expand selection").
+
+use ink_analyzer_ir::syntax::{
+    AstNode, SyntaxElement, SyntaxKind, SyntaxNode, TextRange, TextSize,
+};
+use ink_analyzer_ir::{ast, InkAttribute, InkFile, IsInkEntity};
+
+/// Computes selection ranges for the given offset,
+/// ordered from the narrowest to the widest range (i.e. each range contains the previous one).
+///
+/// Ranges are aware of ink! syntax, so for an offset in an ink! attribute argument,
+/// the chain is (roughly) ink! attribute argument -> ink! attribute -> ink! entity declaration
+/// -> ink! entity -> ink! contract module.
+pub fn selection_ranges(file: &InkFile, offset: TextSize) -> Vec<TextRange> {
+    let mut candidates = Vec::new();
+
+    let item_at_offset = file.item_at_offset(offset);
+    let Some(focused_token) = item_at_offset.focused_token() else {
+        return Vec::new();
+    };
+
+    // Adds the focused token (unless it's trivia).
+    if !focused_token.kind().is_trivia() {
+        candidates.push(focused_token.text_range());
+    }
+
+    for node in focused_token.parent_ancestors() {
+        // Adds the ink! attribute argument and its value (if any) for ink! attributes,
+        // because ink! attribute arguments aren't represented by syntax nodes
+        // (i.e. they're parsed from the token tree).
+        if let Some(ink_attr) = ast::Attr::cast(node.clone()).and_then(InkAttribute::cast) {
+            for arg in ink_attr
+                .args()
+                .iter()
+                .filter(|arg| arg.text_range().contains_inclusive(offset))
+            {
+                candidates.push(arg.text_range());
+                if let Some(value) = arg.value() {
+                    candidates.push(value.text_range());
+                }
+            }
+        }
+
+        // Adds the declaration (i.e. everything before the body) for items.
+        if ast::Item::can_cast(node.kind()) {
+            if let Some(declaration_range) = declaration_range(&node) {
+                candidates.push(declaration_range);
+            }
+        }
+
+        candidates.push(node.text_range());
+    }
+
+    // Sorts candidates from narrowest to widest and only keeps ranges that contain the previous range.
+    candidates.retain(|range| range.contains_inclusive(offset));
+    candidates.sort_by_key(|range| (range.len(), std::cmp::Reverse(range.start())));
+    let mut results: Vec<TextRange> = Vec::new();
+    for range in candidates {
+        let is_wider = results
+            .last()
+            .map_or(true, |prev| *prev != range && range.contains_range(*prev));
+        if is_wider {
+            results.push(range);
+        }
+    }
+    results
+}
+
+/// Returns the declaration range (if any) of an item
+/// (i.e. from the start of the item up to the end of the last non-trivia element before the item's body).
+fn declaration_range(item: &SyntaxNode) -> Option<TextRange> {
+    let body = item.children().find(|child| {
+        matches!(
+            child.kind(),
+            SyntaxKind::BLOCK_EXPR
+                | SyntaxKind::RECORD_FIELD_LIST
+                | SyntaxKind::ITEM_LIST
+                | SyntaxKind::ASSOC_ITEM_LIST
+                | SyntaxKind::VARIANT_LIST
+        )
+    })?;
+    let end = std::iter::successors(
+        body.prev_sibling_or_token(),
+        SyntaxElement::prev_sibling_or_token,
+    )
+    .find(|elem| !elem.kind().is_trivia())?
+    .text_range()
+    .end();
+    Some(TextRange::new(item.text_range().start(), end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::parse_offset_at;
+
+    #[test]
+    fn selection_ranges_works() {
+        let code = r#"
+#[ink::contract]
+mod my_contract {
+    impl MyContract {
+        #[ink(message, selector = 1)]
+        pub fn my_message(&self) {}
+    }
+}
+"#;
+        for (pat, expected_results) in [
+            // (pat, [(start_pat, end_pat)]) where:
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // start_pat = substring used to find the start of the range (see `test_utils::parse_offset_at` doc),
+            // end_pat = substring used to find the end of the range (see `test_utils::parse_offset_at` doc).
+
+            // ink! attribute argument value.
+            (
+                Some("selector = "),
+                vec![
+                    // value.
+                    (Some("selector = "), Some("selector = 1")),
+                    // argument.
+                    (Some("<-selector"), Some("selector = 1")),
+                    // token tree.
+                    (Some("<-(message"), Some("= 1)")),
+                    // meta.
+                    (Some("<-ink(message"), Some("= 1)")),
+                    // attribute.
+                    (Some("<-#[ink(message"), Some("= 1)]")),
+                    // declaration.
+                    (Some("<-#[ink(message"), Some("(&self)")),
+                    // entity.
+                    (Some("<-#[ink(message"), Some("(&self) {}")),
+                    // assoc item list.
+                    (Some("MyContract "), Some("{}\n    }")),
+                    // impl.
+                    (Some("<-impl"), Some("{}\n    }")),
+                    // item list.
+                    (Some("my_contract "), Some("}->")),
+                    // contract module.
+                    (Some("<-#[ink::contract]"), Some("}->")),
+                    // file.
+                    (Some("<-\n#[ink::contract]"), Some("}\n->")),
+                ],
+            ),
+            // ink! attribute argument without a value.
+            (
+                Some("(mess"),
+                vec![
+                    // argument.
+                    (Some("<-message,"), Some("(message")),
+                    // token tree.
+                    (Some("<-(message"), Some("= 1)")),
+                    // meta.
+                    (Some("<-ink(message"), Some("= 1)")),
+                    // attribute.
+                    (Some("<-#[ink(message"), Some("= 1)]")),
+                    // declaration.
+                    (Some("<-#[ink(message"), Some("(&self)")),
+                    // entity.
+                    (Some("<-#[ink(message"), Some("(&self) {}")),
+                    // assoc item list.
+                    (Some("MyContract "), Some("{}\n    }")),
+                    // impl.
+                    (Some("<-impl"), Some("{}\n    }")),
+                    // item list.
+                    (Some("my_contract "), Some("}->")),
+                    // contract module.
+                    (Some("<-#[ink::contract]"), Some("}->")),
+                    // file.
+                    (Some("<-\n#[ink::contract]"), Some("}\n->")),
+                ],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
+
+            let results = selection_ranges(&InkFile::parse(code), offset);
+
+            assert_eq!(
+                results,
+                expected_results
+                    .into_iter()
+                    .map(|(pat_start, pat_end)| TextRange::new(
+                        TextSize::from(parse_offset_at(code, pat_start).unwrap() as u32),
+                        TextSize::from(parse_offset_at(code, pat_end).unwrap() as u32)
+                    ))
+                    .collect::<Vec<TextRange>>(),
+                "pat: {pat:?}"
+            );
+        }
+    }
+}
@@ -1,39 +1,46 @@
-//! ink! attribute signature help.
+//! ink! attribute and ink! chain extension call signature help.
 
-use ink_analyzer_ir::syntax::{AstNode, TextRange, TextSize};
+use ink_analyzer_ir::ast::{HasArgList, HasName};
+use ink_analyzer_ir::syntax::{AstNode, SyntaxKind, TextRange, TextSize};
 use ink_analyzer_ir::{
-    FromAST, FromSyntax, InkArg, InkArgKind, InkArgValueKind, InkAttributeKind, InkFile,
-    IsInkEntity,
+    ast, ChainExtension, Contract, Extension, FromAST, FromSyntax, InkArg, InkArgKind,
+    InkArgValueKind, InkAttributeKind, InkFile, IsInkEntity, IsInkFn, IsInkTrait,
 };
 use itertools::Itertools;
 
+use crate::analysis::hover::content;
 use crate::analysis::utils;
 
 /// An ink! attribute signature help.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SignatureHelp {
     /// Text of the signature.
     pub label: String,
     /// Range where the signature applies.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
     pub range: TextRange,
     /// Parameters of the signature.
     pub parameters: Vec<SignatureParameter>,
     /// Index of the active parameter.
     pub active_parameter: Option<usize>,
-    /// Extra details about the signature.
+    /// Extra details about the signature (i.e. documentation for the ink! attribute macro or primary ink! attribute argument).
     pub detail: Option<String>,
 }
 
 /// An ink! attribute signature parameter.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SignatureParameter {
     /// Range of the parameter.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
     pub range: TextRange,
-    /// Extra details about the parameter.
+    /// Extra details about the parameter
+    /// (i.e. documentation, allowed value kind and default value for the ink! attribute argument).
     pub detail: Option<String>,
 }
 
-/// Computes ink! attribute signature help for the given offset.
+/// Computes ink! attribute and ink! chain extension call signature help for the given offset.
 pub fn signature_help(file: &InkFile, offset: TextSize) -> Vec<SignatureHelp> {
     let mut results = Vec::new();
     let item_at_offset = file.item_at_offset(offset);
@@ -194,12 +201,21 @@ pub fn signature_help(file: &InkFile, offset: TextSize) -> Vec<SignatureHelp> {
                     InkAttributeKind::Macro(_) => {
                         let optional_args = utils::valid_sibling_ink_args(*ink_attr.kind());
                         if !optional_args.is_empty() {
-                            add_signature(&mut results, &optional_args, focused_arg, range);
+                            add_signature(
+                                &mut results,
+                                &optional_args,
+                                focused_arg,
+                                range,
+                                content::doc(ink_attr.kind()),
+                            );
                         }
                     }
                 }
             }
         }
+    } else {
+        // Computes signature help for calls to ink! chain extension methods.
+        extension_call_signature(&mut results, file, offset);
     }
 
     results
@@ -209,12 +225,13 @@ pub fn signature_help(file: &InkFile, offset: TextSize) -> Vec<SignatureHelp> {
         .collect()
 }
 
-/// Computes signature and updates the accumulator given a list of arguments.
+/// Computes signature and updates the accumulator given a list of arguments and the signature documentation.
 fn add_signature(
     results: &mut Vec<SignatureHelp>,
     args: &[InkArgKind],
     focused_arg: Option<&InkArg>,
     range: TextRange,
+    doc: &str,
 ) {
     let mut signature = String::new();
     let mut params = Vec::new();
@@ -250,16 +267,13 @@ fn add_signature(
             }
         ));
 
-        let doc = [arg_value_kind.detail(), arg_kind.detail()]
-            .iter()
-            .filter(|it| !it.is_empty())
-            .join("\n\n");
+        let param_doc = param_doc(*arg_kind);
         params.push(SignatureParameter {
             range: TextRange::new(
                 TextSize::from(start_offset),
                 TextSize::from(start_offset + param.len() as u32),
             ),
-            detail: (!doc.is_empty()).then_some(doc),
+            detail: (!param_doc.is_empty()).then_some(param_doc),
         });
 
         if active_param.is_none() {
@@ -288,11 +302,37 @@ fn add_signature(
             range,
             parameters: params,
             active_parameter: active_param,
-            detail: None,
+            detail: (!doc.is_empty()).then(|| doc.to_string()),
         });
     }
 }
 
+/// Returns documentation for an ink! attribute argument signature parameter
+/// (i.e. a description of the argument, its allowed value kind and an example value).
+fn param_doc(arg_kind: InkArgKind) -> String {
+    let arg_value_kind = InkArgValueKind::from(arg_kind);
+    let value_doc = (arg_value_kind != InkArgValueKind::None).then(|| {
+        format!(
+            "**Value:** `{arg_value_kind}`{}",
+            if arg_value_kind.detail().is_empty() {
+                String::new()
+            } else {
+                format!(" - {}", arg_value_kind.detail())
+            }
+        )
+    });
+    // The insert text is a placeholder value (i.e. not the default value of the argument).
+    let (example_text, _) = utils::ink_arg_insert_text(arg_kind, None, None);
+    let example_doc =
+        (arg_value_kind != InkArgValueKind::None).then(|| format!("**Example:** `{example_text}`"));
+
+    [Some(arg_kind.detail().to_string()), value_doc, example_doc]
+        .into_iter()
+        .flatten()
+        .filter(|it| !it.is_empty())
+        .join("\n\n")
+}
+
 /// Computes signature based on a single "anchor" argument.
 fn anchor_signature(
     results: &mut Vec<SignatureHelp>,
@@ -307,7 +347,13 @@ fn anchor_signature(
             *anchor_arg,
         )))
         .collect();
-    add_signature(results, &args, focused_arg, range);
+    add_signature(
+        results,
+        &args,
+        focused_arg,
+        range,
+        content::doc(&InkAttributeKind::Arg(*anchor_arg)),
+    );
 }
 
 /// Computes signature based on a single complementary argument.
@@ -323,7 +369,13 @@ fn complementary_signature(
     // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/trait_def/config.rs#L60-L85>.
     // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/macro/src/lib.rs#L597-L643>.
     if *arg_kind == InkArgKind::Namespace {
-        add_signature(results, &[*arg_kind], focused_arg, range);
+        add_signature(
+            results,
+            &[*arg_kind],
+            focused_arg,
+            range,
+            content::doc(&InkAttributeKind::Arg(*arg_kind)),
+        );
     }
 
     // Determines the complementary argument's related primary arguments (if any).
@@ -344,9 +396,152 @@ fn complementary_signature(
     }
 }
 
+/// Computes signature help for calls to ink! chain extension methods
+/// (i.e. `self.env().extension().my_extension(..)` calls inside ink! contracts) at the given offset,
+/// based on the declared signature of the ink! extension in the ink! chain extension.
+fn extension_call_signature(results: &mut Vec<SignatureHelp>, file: &InkFile, offset: TextSize) {
+    // Resolves the call site (i.e. the closest method call whose argument list contains the offset).
+    let Some((call, arg_list)) = file
+        .syntax()
+        .token_at_offset(offset)
+        .left_biased()
+        .and_then(|token| {
+            token
+                .parent_ancestors()
+                .filter_map(ast::MethodCallExpr::cast)
+                .find_map(|call| {
+                    let arg_list = call.arg_list()?;
+                    // Opening parenthesis is required, closing parenthesis is not required.
+                    let is_after_left_paren = arg_list
+                        .l_paren_token()
+                        .map_or(false, |l_paren| l_paren.text_range().end() <= offset);
+                    let is_before_right_paren = arg_list
+                        .r_paren_token()
+                        .map_or(true, |r_paren| offset <= r_paren.text_range().start());
+                    (is_after_left_paren && is_before_right_paren).then_some((call, arg_list))
+                })
+        })
+    else {
+        return;
+    };
+
+    // Only calls on the chain extension instance (i.e. `<receiver>.extension().<method>(..)`)
+    // inside ink! contracts are resolved.
+    let is_extension_call = matches!(
+        call.receiver(),
+        Some(ast::Expr::MethodCallExpr(receiver))
+            if receiver.name_ref().map_or(false, |name| name.text() == "extension")
+    );
+    let is_in_contract = ink_analyzer_ir::ink_ancestors::<Contract>(call.syntax())
+        .next()
+        .is_some();
+    let Some(method_name) = call.name_ref().map(|name| name.to_string()) else {
+        return;
+    };
+    if !is_extension_call || !is_in_contract {
+        return;
+    }
+
+    // Determines the range where the signature help applies.
+    let range = TextRange::new(
+        arg_list
+            .l_paren_token()
+            .map_or(arg_list.syntax().text_range().start(), |l_paren| {
+                l_paren.text_range().end()
+            }),
+        arg_list
+            .r_paren_token()
+            .map_or(arg_list.syntax().text_range().end(), |r_paren| {
+                r_paren.text_range().start()
+            }),
+    );
+    // Determines the active argument (i.e. the number of commas before the offset).
+    let active_arg = arg_list
+        .syntax()
+        .children_with_tokens()
+        .filter(|it| it.kind() == SyntaxKind::COMMA && it.text_range().end() <= offset)
+        .count();
+
+    for chain_extension in file.chain_extensions() {
+        for extension in chain_extension.extensions() {
+            let is_match = extension
+                .fn_item()
+                .and_then(HasName::name)
+                .map_or(false, |name| name.to_string() == method_name);
+            if is_match {
+                add_extension_signature(results, chain_extension, extension, active_arg, range);
+            }
+        }
+    }
+}
+
+/// Computes signature from the declaration of an ink! extension and updates the accumulator.
+fn add_extension_signature(
+    results: &mut Vec<SignatureHelp>,
+    chain_extension: &ChainExtension,
+    extension: &Extension,
+    active_arg: usize,
+    range: TextRange,
+) {
+    let Some(fn_item) = extension.fn_item() else {
+        return;
+    };
+    let Some(name) = fn_item.name() else {
+        return;
+    };
+    // Normalizes whitespace (e.g. for multi-line declarations).
+    let normalize =
+        |node: &ink_analyzer_ir::syntax::SyntaxNode| node.to_string().split_whitespace().join(" ");
+
+    let mut signature = format!("fn {name}(");
+    let mut params = Vec::new();
+    for (idx, param) in fn_item
+        .param_list()
+        .iter()
+        .flat_map(ast::ParamList::params)
+        .enumerate()
+    {
+        if idx > 0 {
+            signature.push_str(", ");
+        }
+        let start_offset = signature.len() as u32;
+        signature.push_str(&normalize(param.syntax()));
+        params.push(SignatureParameter {
+            range: TextRange::new(
+                TextSize::from(start_offset),
+                TextSize::from(signature.len() as u32),
+            ),
+            detail: None,
+        });
+    }
+    signature.push(')');
+    if let Some(ret_type) = fn_item.ret_type() {
+        signature.push(' ');
+        signature.push_str(&normalize(ret_type.syntax()));
+    }
+
+    let id_doc = extension.id().map(|id| format!("**Extension ID:** `{id}`"));
+    let chain_extension_doc = chain_extension
+        .trait_item()
+        .and_then(HasName::name)
+        .map(|name| format!("Declared in the `{name}` ink! chain extension."));
+    let doc = [id_doc, chain_extension_doc]
+        .into_iter()
+        .flatten()
+        .join("\n\n");
+    results.push(SignatureHelp {
+        label: signature,
+        range,
+        active_parameter: (!params.is_empty()).then(|| active_arg.min(params.len() - 1)),
+        parameters: params,
+        detail: (!doc.is_empty()).then_some(doc),
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ink_analyzer_ir::InkMacroKind;
     use test_utils::parse_offset_at;
 
     #[test]
@@ -834,4 +1029,183 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn signature_help_docs_works() {
+        for (code, pat, expected_doc, expected_param_docs) in [
+            // (code, pat, doc, [(param_idx, [param_doc_substring])]) where:
+            // code = source code,
+            // pat = substring used to find the cursor offset for the signature help (see `test_utils::parse_offset_at` doc),
+            // doc = the expected documentation for the signature,
+            // param_idx = the index of the signature parameter,
+            // param_doc_substring = substring expected to be present in the documentation of the signature parameter.
+            (
+                "#[ink(message, selector=1)]",
+                Some("selector"),
+                content::doc(&InkAttributeKind::Arg(InkArgKind::Message)),
+                vec![
+                    (0, vec![InkArgKind::Message.detail()]),
+                    (
+                        3,
+                        vec![
+                            InkArgKind::Selector.detail(),
+                            "**Value:** `u32 | _`",
+                            "**Example:** `selector = 1`",
+                        ],
+                    ),
+                ],
+            ),
+            (
+                r#"#[ink_e2e::test(additional_contracts="adder/Cargo.toml")]"#,
+                Some("additional_contracts"),
+                content::doc(&InkAttributeKind::Macro(InkMacroKind::E2ETest)),
+                vec![
+                    (
+                        0,
+                        vec![
+                            InkArgKind::AdditionalContracts.detail(),
+                            "**Value:** `&str` - A space separated list.",
+                            r#"**Example:** `additional_contracts = """#,
+                        ],
+                    ),
+                    (
+                        1,
+                        vec![
+                            InkArgKind::Environment.detail(),
+                            "**Value:** `impl Environment`",
+                            "**Example:** `environment = crate::`",
+                        ],
+                    ),
+                ],
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
+
+            let results = signature_help(&InkFile::parse(code), offset);
+
+            assert_eq!(results.len(), 1, "code: {code}");
+            // Verifies signature documentation.
+            assert_eq!(
+                results[0].detail.as_deref(),
+                Some(expected_doc),
+                "code: {code}"
+            );
+            // Verifies parameter documentation.
+            for (param_idx, expected_substrings) in expected_param_docs {
+                let param_doc = results[0].parameters[param_idx]
+                    .detail
+                    .as_deref()
+                    .unwrap_or_default();
+                for expected_substring in expected_substrings {
+                    assert!(
+                        param_doc.contains(expected_substring),
+                        "code: {code}, param: {param_idx}, doc: {param_doc}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn extension_call_signature_help_works() {
+        let code = r#"
+            #[ink::chain_extension]
+            pub trait MyChainExtension {
+                type ErrorCode = MyErrorCode;
+
+                #[ink(extension = 0x3d26)]
+                fn read(key: &[u8], offset: u32) -> Vec<u8>;
+            }
+
+            #[ink::contract(env = crate::MyEnvironment)]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn get(&self) {
+                        self.env().extension().read(&[1], 2);
+                        self.read(&[1], 2);
+                    }
+                }
+            }
+
+            fn outside() {
+                env().extension().read(&[1], 2);
+            }
+        "#;
+        let label = "fn read(key: &[u8], offset: u32) -> Vec<u8>";
+        for (pat, expected_results) in [
+            // (pat, Option<(active_param, pat_start, pat_end)>) where:
+            // pat = substring used to find the cursor offset (see `test_utils::parse_offset_at` doc),
+            // active_param = the index of the active parameter,
+            // pat_start = substring used to find the start of the signature range (see `test_utils::parse_offset_at` doc),
+            // pat_end = substring used to find the end of the signature range (see `test_utils::parse_offset_at` doc).
+
+            // Calls to ink! chain extension methods inside ink! contracts.
+            (
+                Some("extension().read("),
+                Some((0, Some("extension().read("), Some("<-);\n"))),
+            ),
+            (
+                Some("extension().read(&[1"),
+                Some((0, Some("extension().read("), Some("<-);\n"))),
+            ),
+            (
+                Some("extension().read(&[1],"),
+                Some((1, Some("extension().read("), Some("<-);\n"))),
+            ),
+            (
+                Some("extension().read(&[1], 2"),
+                Some((1, Some("extension().read("), Some("<-);\n"))),
+            ),
+            // Outside the argument list.
+            (Some("extension().rea"), None),
+            // Not a call on the chain extension instance.
+            (Some("self.read("), None),
+            // Outside ink! contracts.
+            (
+                Some("outside() {\n                env().extension().read("),
+                None,
+            ),
+        ] {
+            let offset = TextSize::from(parse_offset_at(code, pat).unwrap() as u32);
+
+            let results = signature_help(&InkFile::parse(code), offset);
+
+            match expected_results {
+                Some((active_param, pat_start, pat_end)) => {
+                    assert_eq!(results.len(), 1, "pat: {pat:?}");
+                    assert_eq!(results[0].label, label, "pat: {pat:?}");
+                    assert_eq!(
+                        results[0].active_parameter,
+                        Some(active_param),
+                        "pat: {pat:?}"
+                    );
+                    assert_eq!(
+                        results[0].range,
+                        TextRange::new(
+                            TextSize::from(parse_offset_at(code, pat_start).unwrap() as u32),
+                            TextSize::from(parse_offset_at(code, pat_end).unwrap() as u32),
+                        ),
+                        "pat: {pat:?}"
+                    );
+                    assert_eq!(
+                        results[0]
+                            .parameters
+                            .iter()
+                            .map(|param| &label[param.range])
+                            .collect::<Vec<_>>(),
+                        vec!["key: &[u8]", "offset: u32"],
+                        "pat: {pat:?}"
+                    );
+                    let doc = results[0].detail.as_deref().unwrap();
+                    assert!(doc.contains("**Extension ID:** `15654`"), "doc: {doc}");
+                    assert!(doc.contains("`MyChainExtension`"), "doc: {doc}");
+                }
+                None => assert!(results.is_empty(), "pat: {pat:?}"),
+            }
+        }
+    }
 }
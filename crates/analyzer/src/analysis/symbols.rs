@@ -0,0 +1,450 @@
+//! ink! entity symbols and a symbol index for searching ink! entities across files.
+
+use ink_analyzer_ir::syntax::{AstNode, SyntaxNode, TextRange};
+use ink_analyzer_ir::{ast, FromSyntax, InkFile};
+use std::collections::HashMap;
+
+use super::queries::{FileIndex, IndexedSelector, Queries};
+
+/// An ink! entity symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Symbol {
+    /// Name of the ink! entity (e.g. the name of the ink! message `fn`).
+    pub name: String,
+    /// Kind of the ink! entity.
+    pub kind: SymbolKind,
+    /// Range of the ink! entity (i.e. the whole item including attributes).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
+    pub range: TextRange,
+    /// Range of the name of the ink! entity.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
+    pub name_range: TextRange,
+    /// Name of the enclosing ink! entity (if any) (e.g. the ink! contract for ink! messages).
+    pub container_name: Option<String>,
+}
+
+/// The kind of an ink! entity symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SymbolKind {
+    /// An ink! contract.
+    Contract,
+    /// An ink! storage definition.
+    Storage,
+    /// An ink! event.
+    Event,
+    /// An ink! constructor.
+    Constructor,
+    /// An ink! message.
+    Message,
+    /// An ink! trait definition.
+    TraitDefinition,
+    /// An ink! chain extension.
+    ChainExtension,
+    /// An ink! extension.
+    Extension,
+    /// An ink! storage item.
+    StorageItem,
+    /// An ink! test.
+    Test,
+    /// An ink! e2e test.
+    E2ETest,
+}
+
+impl SymbolKind {
+    /// All ink! entity symbol kinds.
+    const ALL: [SymbolKind; 11] = [
+        SymbolKind::Contract,
+        SymbolKind::Storage,
+        SymbolKind::Event,
+        SymbolKind::Constructor,
+        SymbolKind::Message,
+        SymbolKind::TraitDefinition,
+        SymbolKind::ChainExtension,
+        SymbolKind::Extension,
+        SymbolKind::StorageItem,
+        SymbolKind::Test,
+        SymbolKind::E2ETest,
+    ];
+
+    /// Returns the name of the symbol kind (i.e. the ink! attribute macro or argument name),
+    /// used as a kind filter in symbol search queries (e.g. `message: transfer`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            SymbolKind::Contract => "contract",
+            SymbolKind::Storage => "storage",
+            SymbolKind::Event => "event",
+            SymbolKind::Constructor => "constructor",
+            SymbolKind::Message => "message",
+            SymbolKind::TraitDefinition => "trait_definition",
+            SymbolKind::ChainExtension => "chain_extension",
+            SymbolKind::Extension => "extension",
+            SymbolKind::StorageItem => "storage_item",
+            SymbolKind::Test => "test",
+            SymbolKind::E2ETest => "e2e_test",
+        }
+    }
+
+    /// Returns the symbol kind for the given name (if any).
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+/// Computes symbols for ink! entities in the file.
+pub fn symbols(file: &InkFile) -> Vec<Symbol> {
+    let mut results = Vec::new();
+
+    for contract in file.contracts() {
+        let Some(contract_name) = add_symbol(&mut results, contract, SymbolKind::Contract, None)
+        else {
+            continue;
+        };
+        let container_name = Some(contract_name.as_str());
+        if let Some(storage) = contract.storage() {
+            add_symbol(&mut results, storage, SymbolKind::Storage, container_name);
+        }
+        for event in contract.events() {
+            add_symbol(&mut results, event, SymbolKind::Event, container_name);
+        }
+        for constructor in contract.constructors() {
+            add_symbol(
+                &mut results,
+                constructor,
+                SymbolKind::Constructor,
+                container_name,
+            );
+        }
+        for message in contract.messages() {
+            add_symbol(&mut results, message, SymbolKind::Message, container_name);
+        }
+        for test in contract.tests() {
+            add_symbol(&mut results, test, SymbolKind::Test, container_name);
+        }
+        for e2e_test in contract.e2e_tests() {
+            add_symbol(&mut results, e2e_test, SymbolKind::E2ETest, container_name);
+        }
+    }
+
+    for trait_definition in file.trait_definitions() {
+        let container_name = add_symbol(
+            &mut results,
+            trait_definition,
+            SymbolKind::TraitDefinition,
+            None,
+        );
+        for message in trait_definition.messages() {
+            add_symbol(
+                &mut results,
+                message,
+                SymbolKind::Message,
+                container_name.as_deref(),
+            );
+        }
+    }
+
+    for chain_extension in file.chain_extensions() {
+        let container_name = add_symbol(
+            &mut results,
+            chain_extension,
+            SymbolKind::ChainExtension,
+            None,
+        );
+        for extension in chain_extension.extensions() {
+            add_symbol(
+                &mut results,
+                extension,
+                SymbolKind::Extension,
+                container_name.as_deref(),
+            );
+        }
+    }
+
+    for storage_item in file.storage_items() {
+        add_symbol(&mut results, storage_item, SymbolKind::StorageItem, None);
+    }
+    for test in file.tests() {
+        add_symbol(&mut results, test, SymbolKind::Test, None);
+    }
+    for e2e_test in file.e2e_tests() {
+        add_symbol(&mut results, e2e_test, SymbolKind::E2ETest, None);
+    }
+
+    // Sorts symbols by position and removes duplicates
+    // (e.g. ink! tests that are collected both for the ink! contract and the file).
+    results.sort_by_key(|symbol| (symbol.range.start(), symbol.range.end()));
+    results.dedup_by(|a, b| a.range == b.range && a.kind == b.kind);
+    results
+}
+
+/// Adds a symbol for the ink! entity (if it has a name) and returns its name.
+fn add_symbol<T>(
+    results: &mut Vec<Symbol>,
+    entity: &T,
+    kind: SymbolKind,
+    container_name: Option<&str>,
+) -> Option<String>
+where
+    T: FromSyntax,
+{
+    let name = item_name(entity.syntax())?;
+    let name_text = name.text().to_string();
+    results.push(Symbol {
+        name: name_text.clone(),
+        kind,
+        range: entity.syntax().text_range(),
+        name_range: name.syntax().text_range(),
+        container_name: container_name.map(ToString::to_string),
+    });
+    Some(name_text)
+}
+
+/// Returns the name of the item (if any).
+fn item_name(node: &SyntaxNode) -> Option<ast::Name> {
+    node.children().find_map(ast::Name::cast)
+}
+
+/// An index of ink! entity symbols across multiple files (e.g. for workspace symbol search).
+///
+/// Files are identified by an arbitrary (but unique) id (e.g. a URI).
+///
+/// Each file is indexed together with a hash of its contents (see [`FileIndex`]),
+/// so a persisted index can be reused for files that are unchanged between sessions (see [`SymbolIndex::is_fresh`]).
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymbolIndex {
+    files: HashMap<String, FileIndex>,
+}
+
+impl SymbolIndex {
+    /// Creates an empty symbol index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds (or replaces) the symbols for the file with the given id.
+    pub fn insert(&mut self, id: String, file: &InkFile) {
+        self.insert_index(id, Queries::default().file_index(file));
+    }
+
+    /// Adds (or replaces) the file index (e.g. from [`Analysis::file_index`](crate::Analysis::file_index))
+    /// for the file with the given id.
+    pub fn insert_index(&mut self, id: String, index: FileIndex) {
+        // Files without ink! entity symbols are still indexed for their selectors
+        // (e.g. out-of-line child modules of ink! contracts).
+        if index.symbols.is_empty() && index.selectors.is_empty() {
+            self.files.remove(&id);
+        } else {
+            self.files.insert(id, index);
+        }
+    }
+
+    /// Removes (and returns) the file index for the file with the given id.
+    pub fn remove(&mut self, id: &str) -> Option<FileIndex> {
+        self.files.remove(id)
+    }
+
+    /// Adds (or replaces) all file indexes of the other symbol index.
+    pub fn extend(&mut self, other: SymbolIndex) {
+        self.files.extend(other.files);
+    }
+
+    /// Returns the symbols for the file with the given id (if any).
+    pub fn get(&self, id: &str) -> Option<&[Symbol]> {
+        self.files.get(id).map(|index| index.symbols.as_slice())
+    }
+
+    /// Returns the composed selectors of ink! constructors and ink! messages
+    /// for the file with the given id (if any).
+    pub fn selectors(&self, id: &str) -> Option<&[IndexedSelector]> {
+        self.files.get(id).map(|index| index.selectors.as_slice())
+    }
+
+    /// Returns the file index for the file with the given id (if any).
+    pub fn file_index(&self, id: &str) -> Option<&FileIndex> {
+        self.files.get(id)
+    }
+
+    /// Returns true if the file with the given id is indexed
+    /// for file contents with the given hash (see [`content_hash`](crate::content_hash)).
+    pub fn is_fresh(&self, id: &str, hash: u64) -> bool {
+        self.files.get(id).map_or(false, |index| index.hash == hash)
+    }
+
+    /// Searches the index for symbols whose names contain the query (case-insensitive),
+    /// and returns matching symbols paired with the id of their file.
+    ///
+    /// The query can be prefixed with a symbol kind name (see [`SymbolKind::name`])
+    /// followed by a colon to only search for ink! entities of that kind (e.g. `message: transfer`).
+    ///
+    /// Results are sorted by file id, then position.
+    pub fn search(&self, query: &str) -> Vec<(&str, &Symbol)> {
+        let (kind, name_query) = match query.split_once(':') {
+            Some((prefix, rest)) => match SymbolKind::from_name(prefix.trim()) {
+                Some(kind) => (Some(kind), rest),
+                None => (None, query),
+            },
+            None => (None, query),
+        };
+        let name_query = name_query.trim().to_lowercase();
+
+        let mut results: Vec<(&str, &Symbol)> = self
+            .files
+            .iter()
+            .flat_map(|(id, index)| {
+                index
+                    .symbols
+                    .iter()
+                    .map(move |symbol| (id.as_str(), symbol))
+            })
+            .filter(|(_, symbol)| {
+                kind.map_or(true, |kind| symbol.kind == kind)
+                    && symbol.name.to_lowercase().contains(&name_query)
+            })
+            .collect();
+        results.sort_by_key(|(id, symbol)| (*id, symbol.range.start(), symbol.range.end()));
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::queries::content_hash;
+    use test_utils::quote_as_str;
+
+    const CONTRACT: &str = r#"
+    #[ink::contract]
+    mod erc20 {
+        #[ink(storage)]
+        pub struct Erc20 {}
+
+        #[ink(event)]
+        pub struct Transfer {}
+
+        impl Erc20 {
+            #[ink(constructor)]
+            pub fn new() -> Self {}
+
+            #[ink(message)]
+            pub fn transfer(&mut self) {}
+
+            #[ink(message)]
+            pub fn transfer_from(&mut self) {}
+        }
+
+        #[cfg(test)]
+        mod tests {
+            #[ink::test]
+            fn transfer_works() {}
+        }
+    }
+    "#;
+
+    #[test]
+    fn symbols_works() {
+        let symbols = symbols(&InkFile::parse(CONTRACT));
+        let results: Vec<(&str, SymbolKind, Option<&str>)> = symbols
+            .iter()
+            .map(|symbol| {
+                (
+                    &CONTRACT[symbol.name_range],
+                    symbol.kind,
+                    symbol.container_name.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                ("erc20", SymbolKind::Contract, None),
+                ("Erc20", SymbolKind::Storage, Some("erc20")),
+                ("Transfer", SymbolKind::Event, Some("erc20")),
+                ("new", SymbolKind::Constructor, Some("erc20")),
+                ("transfer", SymbolKind::Message, Some("erc20")),
+                ("transfer_from", SymbolKind::Message, Some("erc20")),
+                ("transfer_works", SymbolKind::Test, Some("erc20")),
+            ]
+        );
+    }
+
+    #[test]
+    fn symbol_index_search_works() {
+        let mut index = SymbolIndex::new();
+        index.insert("erc20.rs".to_string(), &InkFile::parse(CONTRACT));
+        index.insert(
+            "trait.rs".to_string(),
+            &InkFile::parse(quote_as_str! {
+                #[ink::trait_definition]
+                pub trait Psp22 {
+                    #[ink(message)]
+                    fn transfer(&mut self);
+                }
+            }),
+        );
+        // Plain Rust files are not indexed.
+        index.insert("main.rs".to_string(), &InkFile::parse("fn main() {}"));
+        assert!(index.get("main.rs").is_none());
+
+        for (query, expected_results) in [
+            (
+                "transfer",
+                vec![
+                    ("erc20.rs", "Transfer"),
+                    ("erc20.rs", "transfer"),
+                    ("erc20.rs", "transfer_from"),
+                    ("erc20.rs", "transfer_works"),
+                    ("trait.rs", "transfer"),
+                ],
+            ),
+            ("TRANSFER_FROM", vec![("erc20.rs", "transfer_from")]),
+            (
+                "message: transfer",
+                vec![
+                    ("erc20.rs", "transfer"),
+                    ("erc20.rs", "transfer_from"),
+                    ("trait.rs", "transfer"),
+                ],
+            ),
+            ("event:", vec![("erc20.rs", "Transfer")]),
+            ("trait_definition:psp", vec![("trait.rs", "Psp22")]),
+            // Unknown kinds are part of the name query.
+            ("unknown: transfer", vec![]),
+            ("approve", vec![]),
+        ] {
+            let results: Vec<(&str, &str)> = index
+                .search(query)
+                .into_iter()
+                .map(|(id, symbol)| (id, symbol.name.as_str()))
+                .collect();
+            assert_eq!(results, expected_results, "query: {query}");
+        }
+
+        // Removes symbols for a file.
+        assert!(index.remove("trait.rs").is_some());
+        assert!(index.search("psp").is_empty());
+    }
+
+    #[test]
+    fn symbol_index_freshness_works() {
+        let mut index = SymbolIndex::new();
+        index.insert("erc20.rs".to_string(), &InkFile::parse(CONTRACT));
+
+        // Files are indexed with a hash of their contents.
+        assert!(index.is_fresh("erc20.rs", content_hash(CONTRACT)));
+        assert!(!index.is_fresh("erc20.rs", content_hash("mod erc20 {}")));
+        assert!(!index.is_fresh("trait.rs", content_hash(CONTRACT)));
+
+        // Selectors of ink! constructors and ink! messages are indexed.
+        assert_eq!(index.selectors("erc20.rs").map(<[_]>::len), Some(3));
+
+        // Extending the index replaces file indexes for the same file.
+        let mut other = SymbolIndex::new();
+        let code = "#[ink::contract]\nmod erc20 {}";
+        other.insert("erc20.rs".to_string(), &InkFile::parse(code));
+        index.extend(other);
+        assert!(index.is_fresh("erc20.rs", content_hash(code)));
+        assert_eq!(index.get("erc20.rs").map(<[_]>::len), Some(1));
+    }
+}
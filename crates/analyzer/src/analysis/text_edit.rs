@@ -4,15 +4,25 @@ use ink_analyzer_ir::syntax::{AstNode, SyntaxKind, TextRange, TextSize};
 use ink_analyzer_ir::{FromSyntax, InkFile};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::borrow::Cow;
 
+pub use builder::EditBuilder;
+pub use merge::{merge_edits, EditConflict};
+
+use super::indent::IndentStyle;
 use super::utils;
 
+mod builder;
+mod merge;
+
 /// A text edit (with an optional snippet - i.e tab stops and/or placeholders).
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextEdit {
     /// Replacement text for the text edit.
     pub text: String,
     /// Range to which the text edit will be applied.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::text_range"))]
     pub range: TextRange,
     /// Formatted snippet for the text edit (includes tab stops and/or placeholders).
     pub snippet: Option<String>,
@@ -60,6 +70,117 @@ impl TextEdit {
             snippet: None,
         }
     }
+
+    /// Returns the replacement text for a client with or without snippet support
+    /// (i.e. the snippet if snippets are supported and the text edit has one,
+    /// or the plain text otherwise, see [`Self::plain_text`]).
+    pub fn text_for(&self, snippet_support: bool) -> Cow<'_, str> {
+        match (snippet_support, self.snippet.as_deref()) {
+            (true, Some(snippet)) => Cow::Borrowed(snippet),
+            _ => self.plain_text(),
+        }
+    }
+
+    /// Returns the plain text (i.e. without tab stops and placeholders) for the text edit.
+    ///
+    /// Falls back to rendering the snippet as plain text if the text edit only has a snippet
+    /// (i.e. its text is empty).
+    pub fn plain_text(&self) -> Cow<'_, str> {
+        match self.snippet.as_deref() {
+            Some(snippet) if self.text.is_empty() && !snippet.is_empty() => {
+                Cow::Owned(render_snippet(snippet))
+            }
+            _ => Cow::Borrowed(&self.text),
+        }
+    }
+}
+
+/// Renders a snippet as plain text (i.e. removes tab stops, replaces placeholders with their text,
+/// replaces choices with their first option and unescapes escaped characters).
+///
+/// Ref: <https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#snippet_syntax>.
+fn render_snippet(snippet: &str) -> String {
+    let chars: Vec<char> = snippet.chars().collect();
+    let mut result = String::with_capacity(snippet.len());
+    render_snippet_part(&chars, &mut 0, &mut result, false);
+    result
+}
+
+/// Renders part of a snippet as plain text (i.e. either the whole snippet or the text of a placeholder).
+fn render_snippet_part(chars: &[char], idx: &mut usize, result: &mut String, is_placeholder: bool) {
+    let skip_digits = |idx: &mut usize| {
+        while chars.get(*idx).map_or(false, char::is_ascii_digit) {
+            *idx += 1;
+        }
+    };
+    while let Some(current) = chars.get(*idx).copied() {
+        *idx += 1;
+        match current {
+            '\\' => match chars.get(*idx) {
+                Some(next @ ('$' | '}' | '\\' | ',' | '|')) => {
+                    result.push(*next);
+                    *idx += 1;
+                }
+                _ => result.push(current),
+            },
+            // End of placeholder.
+            '}' if is_placeholder => return,
+            '$' => match chars.get(*idx) {
+                // Tab stop (e.g. `$1`).
+                Some(next) if next.is_ascii_digit() => skip_digits(idx),
+                // Tab stop (e.g. `${1}`), placeholder (e.g. `${1:text}`) or choice (e.g. `${1|one,two|}`).
+                Some('{') => {
+                    *idx += 1;
+                    skip_digits(idx);
+                    match chars.get(*idx) {
+                        Some(':') => {
+                            *idx += 1;
+                            render_snippet_part(chars, idx, result, true);
+                        }
+                        Some('|') => {
+                            *idx += 1;
+                            let mut is_first_option = true;
+                            while let Some(current) = chars.get(*idx).copied() {
+                                *idx += 1;
+                                match current {
+                                    '\\' => {
+                                        if let Some(next) = chars.get(*idx) {
+                                            if is_first_option {
+                                                result.push(*next);
+                                            }
+                                            *idx += 1;
+                                        }
+                                    }
+                                    ',' => is_first_option = false,
+                                    '|' if chars.get(*idx) == Some(&'}') => {
+                                        *idx += 1;
+                                        break;
+                                    }
+                                    _ if is_first_option => result.push(current),
+                                    _ => (),
+                                }
+                            }
+                        }
+                        Some('}') => *idx += 1,
+                        _ => (),
+                    }
+                }
+                _ => result.push(current),
+            },
+            _ => result.push(current),
+        }
+    }
+}
+
+/// Text edits for a document other than the analyzed file (e.g. the project's `Cargo.toml`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocumentEdit {
+    /// Path of the document relative to the directory of the analyzed file
+    /// (e.g. `Cargo.toml` for a `lib.rs` file at the root of an ink! project).
+    pub path: String,
+    /// Text edits for the document (i.e. text ranges are relative to the document's text).
+    pub edits: Vec<TextEdit>,
 }
 
 /// Format text edits (i.e. add indenting and new lines based on context).
@@ -118,7 +239,15 @@ pub fn format_edit(mut edit: TextEdit, file: &InkFile) -> TextEdit {
                             token_after.kind() != SyntaxKind::WHITESPACE
                         }) && token_before.text().contains('\n')
                             && !token_before.text().ends_with('\n'))
-                        .then_some(format!("\n{}", utils::end_indenting(token_before.text()),)),
+                        .then(|| {
+                            format!(
+                                "\n{}",
+                                // Converts to canonical indenting (see `IndentStyle` doc).
+                                IndentStyle::detect_for_node(file.syntax())
+                                    .unwrap_or_default()
+                                    .normalize(&utils::end_indenting(token_before.text()))
+                            )
+                        }),
                     )
                 }
                 // Handles edits at the beginning of blocks (i.e right after the opening curly bracket).
@@ -216,3 +345,67 @@ fn starts_with_two_or_more_newlines(text: &str) -> bool {
     static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([^\S\n]*\n[^\S\n]*){2,}").unwrap());
     RE.is_match(text)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_for_works() {
+        let range = TextRange::default();
+        for (edit, expected_snippet_text, expected_plain_text) in [
+            // No snippet.
+            (
+                TextEdit::new("text".to_string(), range, None),
+                "text",
+                "text",
+            ),
+            // Snippet with plain text.
+            (
+                TextEdit::new(
+                    "#[ink(message)]".to_string(),
+                    range,
+                    Some("#[ink(${1:message})]$0".to_string()),
+                ),
+                "#[ink(${1:message})]$0",
+                "#[ink(message)]",
+            ),
+            // Snippet only (i.e. plain text is rendered from the snippet).
+            (
+                TextEdit::new(
+                    String::new(),
+                    range,
+                    Some("pub fn ${1:my_message}(&self) {\n    $2\n}$0".to_string()),
+                ),
+                "pub fn ${1:my_message}(&self) {\n    $2\n}$0",
+                "pub fn my_message(&self) {\n    \n}",
+            ),
+        ] {
+            assert_eq!(edit.text_for(true), expected_snippet_text);
+            assert_eq!(edit.text_for(false), expected_plain_text);
+        }
+    }
+
+    #[test]
+    fn render_snippet_works() {
+        for (snippet, expected_text) in [
+            ("", ""),
+            ("text", "text"),
+            // Tab stops.
+            ("$1text$0", "text"),
+            ("${1}text${0}", "text"),
+            // Placeholders (including nested).
+            ("${1:text}", "text"),
+            ("${1:outer ${2:inner}}$0", "outer inner"),
+            // Choices.
+            ("${1|one,two,three|}", "one"),
+            (r"${1|o\,ne,two|}", "o,ne"),
+            // Escapes.
+            (r"\$1 \} \\", r"$1 } \"),
+            // Unrelated `$`.
+            ("$ $text", "$ $text"),
+        ] {
+            assert_eq!(render_snippet(snippet), expected_text, "snippet: {snippet}");
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Builder for insert text edits that are indented based on context.
+
+use ink_analyzer_ir::syntax::{SyntaxNode, TextSize};
+
+use super::TextEdit;
+use crate::analysis::utils;
+
+/// Builder for insert [`TextEdit`]s (with an optional snippet)
+/// whose multi-line text is indented based on the insert context.
+///
+/// NOTE: Like all code generators, the builder emits canonical indenting (see [`IndentStyle`](crate::analysis::indent::IndentStyle) doc),
+/// which is converted to the configured indentation style by the [`Analysis`](crate::Analysis) entry points.
+///
+/// Building is idempotent with respect to indenting, because the text (and snippet) is dedented
+/// before indenting is applied (i.e. already indented text isn't indented again).
+#[derive(Debug, Clone)]
+pub struct EditBuilder {
+    text: String,
+    snippet: Option<String>,
+    offset: TextSize,
+    indent: Option<String>,
+    prefix: Option<String>,
+    suffix: Option<String>,
+}
+
+impl EditBuilder {
+    /// Returns a builder for inserting the text at the given offset.
+    pub fn insert(text: impl Into<String>, offset: TextSize) -> Self {
+        Self {
+            text: text.into(),
+            snippet: None,
+            offset,
+            indent: None,
+            prefix: None,
+            suffix: None,
+        }
+    }
+
+    /// Sets the snippet (i.e. with tab stops and/or placeholders) for the inserted text.
+    pub fn snippet(mut self, snippet: impl Into<String>) -> Self {
+        self.snippet = Some(snippet.into());
+        self
+    }
+
+    /// Sets the indenting (i.e. canonical leading whitespace) for each line of the inserted text.
+    pub fn indent(mut self, indent: impl Into<String>) -> Self {
+        self.indent = Some(indent.into());
+        self
+    }
+
+    /// Infers the indenting for inserting a sibling of the syntax node (i.e. the node's indenting).
+    pub fn indent_as_sibling_of(self, node: &SyntaxNode) -> Self {
+        self.indent(utils::item_indenting(node).unwrap_or_default())
+    }
+
+    /// Infers the indenting for inserting a child of the syntax node (e.g. an item in a `mod` or `impl` block).
+    pub fn indent_as_child_of(self, node: &SyntaxNode) -> Self {
+        self.indent(utils::item_children_indenting(node))
+    }
+
+    /// Sets the prefix and suffix (e.g. delimiters and/or wrapping blocks)
+    /// which are added to the indented text (and snippet) as is.
+    pub fn affixes(mut self, prefix: Option<String>, suffix: Option<String>) -> Self {
+        self.prefix = prefix;
+        self.suffix = suffix;
+        self
+    }
+
+    /// Returns the insert text edit.
+    pub fn build(self) -> TextEdit {
+        let format = |input: &str| {
+            let text = match self.indent.as_deref() {
+                Some(indent) => utils::apply_indenting(&dedent(input), indent),
+                None => input.to_string(),
+            };
+            format!(
+                "{}{text}{}",
+                self.prefix.as_deref().unwrap_or_default(),
+                self.suffix.as_deref().unwrap_or_default()
+            )
+        };
+        TextEdit::insert_with_snippet(
+            format(&self.text),
+            self.offset,
+            self.snippet.as_deref().map(format),
+        )
+    }
+}
+
+/// Removes the common indenting (i.e. leading whitespace) of all non-empty lines.
+fn dedent(input: &str) -> String {
+    let common_indent = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.chars()
+                .take_while(|char| *char == ' ' || *char == '\t')
+                .count()
+        })
+        .min()
+        .unwrap_or_default();
+    if common_indent == 0 {
+        return input.to_string();
+    }
+    input
+        .lines()
+        .map(|line| line.get(common_indent..).unwrap_or_default())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_analyzer_ir::syntax::{AstNode, TextRange};
+    use ink_analyzer_ir::{ast, FromSyntax, InkFile};
+
+    #[test]
+    fn edit_builder_works() {
+        let offset = TextSize::from(0);
+        let plain = "fn my_fn() {\n    todo!()\n}";
+        let snippet = "fn ${1:my_fn}() {\n    ${2:todo!()}\n}";
+        let indented_plain = "    fn my_fn() {\n        todo!()\n    }";
+        let indented_snippet = "    fn ${1:my_fn}() {\n        ${2:todo!()}\n    }";
+
+        for (builder, expected_text, expected_snippet) in [
+            // No indenting.
+            (EditBuilder::insert(plain, offset), plain, None),
+            // Explicit indenting.
+            (
+                EditBuilder::insert(plain, offset)
+                    .snippet(snippet)
+                    .indent("    "),
+                indented_plain,
+                Some(indented_snippet),
+            ),
+            // Already indented text isn't indented again.
+            (
+                EditBuilder::insert(indented_plain, offset)
+                    .snippet(indented_snippet)
+                    .indent("    "),
+                indented_plain,
+                Some(indented_snippet),
+            ),
+            // Affixes aren't indented.
+            (
+                EditBuilder::insert(plain, offset)
+                    .indent("        ")
+                    .affixes(Some("\n".to_string()), Some("\n}".to_string())),
+                "\n        fn my_fn() {\n            todo!()\n        }\n}",
+                None,
+            ),
+        ] {
+            let edit = builder.build();
+            assert_eq!(edit.text, expected_text);
+            assert_eq!(edit.snippet.as_deref(), expected_snippet);
+            assert_eq!(edit.range, TextRange::new(offset, offset));
+        }
+
+        // Infers indenting from context.
+        let file = InkFile::parse("mod my_mod {\n    mod nested {\n    }\n}");
+        let nested = file
+            .syntax()
+            .descendants()
+            .filter_map(ast::Module::cast)
+            .nth(1)
+            .unwrap();
+        for (builder, expected_text) in [
+            (
+                EditBuilder::insert(plain, offset).indent_as_sibling_of(nested.syntax()),
+                indented_plain,
+            ),
+            (
+                EditBuilder::insert(plain, offset).indent_as_child_of(nested.syntax()),
+                "        fn my_fn() {\n            todo!()\n        }",
+            ),
+        ] {
+            assert_eq!(builder.build().text, expected_text);
+        }
+    }
+
+    #[test]
+    fn dedent_works() {
+        for (input, expected_output) in [
+            ("", ""),
+            ("text", "text"),
+            ("    text", "text"),
+            ("    a\n        b\n    c", "a\n    b\nc"),
+            ("    a\n\n    b", "a\n\nb"),
+            ("a\n    b", "a\n    b"),
+            ("\ta\n\t\tb", "a\n\tb"),
+        ] {
+            assert_eq!(dedent(input), expected_output);
+        }
+    }
+}
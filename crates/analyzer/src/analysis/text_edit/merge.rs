@@ -0,0 +1,187 @@
+//! Conflict detection and merging for sets of text edits (e.g. from several actions).
+
+use ink_analyzer_ir::syntax::TextRange;
+
+use super::TextEdit;
+
+/// An overlap between text edits from different sets of text edits, see [`merge_edits`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EditConflict {
+    /// Index of the earlier set of text edits.
+    pub first_set: usize,
+    /// Conflicting text edit from the earlier set of text edits.
+    pub first: TextEdit,
+    /// Index of the later set of text edits.
+    pub second_set: usize,
+    /// Conflicting text edit from the later set of text edits.
+    pub second: TextEdit,
+}
+
+/// Merges sets of text edits (e.g. from several actions) into a single set of text edits
+/// (sorted by position), or returns all overlaps between text edits from different sets.
+///
+/// Text edits conflict if their ranges overlap (i.e. share more than a boundary),
+/// so insertions (i.e. empty ranges) can share an offset with each other and with the boundaries
+/// of other text edits (in which case insertions from earlier sets are applied first),
+/// but insertions strictly inside the range of another text edit conflict with it.
+///
+/// Text edits that are identical to text edits from earlier sets are merged only once
+/// (e.g. when multiple quickfixes add the same `use` declaration).
+///
+/// NOTE: Overlaps between text edits from the same set aren't reported
+/// (e.g. text edits from a single action are assumed to be valid, see [`crate::ActionBuilder::build`]).
+///
+/// # Example
+/// ```
+/// use ink_analyzer::{merge_edits, TextEdit, TextRange, TextSize};
+///
+/// let range = |start: u32, end: u32| TextRange::new(TextSize::from(start), TextSize::from(end));
+/// let first = vec![TextEdit::delete(range(0, 5))];
+/// let second = vec![TextEdit::insert("a".to_string(), TextSize::from(5))];
+/// let third = vec![TextEdit::delete(range(3, 8))];
+///
+/// assert_eq!(merge_edits([&first, &second]).unwrap().len(), 2);
+/// // `third` overlaps the deletion in `first` and the insertion in `second`.
+/// assert_eq!(merge_edits([&first, &second, &third]).unwrap_err().len(), 2);
+/// ```
+pub fn merge_edits<I>(edit_sets: I) -> Result<Vec<TextEdit>, Vec<EditConflict>>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[TextEdit]>,
+{
+    let mut merged: Vec<(usize, TextEdit)> = Vec::new();
+    let mut conflicts = Vec::new();
+    for (set_idx, edits) in edit_sets.into_iter().enumerate() {
+        let n_prior_edits = merged.len();
+        for edit in edits.as_ref() {
+            let mut is_duplicate = false;
+            for (other_set_idx, other) in &merged[..n_prior_edits] {
+                if other == edit {
+                    is_duplicate = true;
+                } else if is_overlapping(other.range, edit.range) {
+                    conflicts.push(EditConflict {
+                        first_set: *other_set_idx,
+                        first: other.clone(),
+                        second_set: set_idx,
+                        second: edit.clone(),
+                    });
+                }
+            }
+            if !is_duplicate {
+                merged.push((set_idx, edit.clone()));
+            }
+        }
+    }
+
+    if conflicts.is_empty() {
+        // Sorts edits by position (the order of insertions at the same offset is preserved).
+        merged.sort_by_key(|(_, edit)| (edit.range.start(), edit.range.end()));
+        Ok(merged.into_iter().map(|(_, edit)| edit).collect())
+    } else {
+        Err(conflicts)
+    }
+}
+
+/// Returns true if the text ranges overlap (i.e. share more than a boundary),
+/// including insertions (i.e. empty ranges) strictly inside other ranges.
+fn is_overlapping(a: TextRange, b: TextRange) -> bool {
+    a.start() < b.end() && b.start() < a.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_analyzer_ir::syntax::TextSize;
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(TextSize::from(start), TextSize::from(end))
+    }
+
+    fn insert(text: &str, offset: u32) -> TextEdit {
+        TextEdit::insert(text.to_string(), TextSize::from(offset))
+    }
+
+    #[test]
+    fn merge_edits_works() {
+        for (edit_sets, expected_results) in [
+            // No edits.
+            (vec![], Ok(vec![])),
+            // Disjoint and adjacent edits (sorted by position).
+            (
+                vec![
+                    vec![TextEdit::delete(range(5, 10))],
+                    vec![
+                        TextEdit::delete(range(0, 5)),
+                        TextEdit::delete(range(12, 15)),
+                    ],
+                ],
+                Ok(vec![range(0, 5), range(5, 10), range(12, 15)]),
+            ),
+            // Insertions at boundaries and at the same offset.
+            (
+                vec![
+                    vec![TextEdit::delete(range(0, 5))],
+                    vec![insert("a", 5)],
+                    vec![insert("b", 5), insert("c", 0)],
+                ],
+                Ok(vec![range(0, 0), range(0, 5), range(5, 5), range(5, 5)]),
+            ),
+            // Identical edits are merged once.
+            (
+                vec![
+                    vec![insert("use a;", 0)],
+                    vec![insert("use a;", 0), TextEdit::delete(range(5, 10))],
+                ],
+                Ok(vec![range(0, 0), range(5, 10)]),
+            ),
+            // Overlapping edits.
+            (
+                vec![
+                    vec![TextEdit::delete(range(0, 5))],
+                    vec![TextEdit::delete(range(3, 8))],
+                    vec![insert("a", 2)],
+                ],
+                Err(vec![
+                    (0, range(0, 5), 1, range(3, 8)),
+                    (0, range(0, 5), 2, range(2, 2)),
+                ]),
+            ),
+            // Overlaps within the same set aren't reported.
+            (
+                vec![vec![
+                    TextEdit::delete(range(0, 5)),
+                    TextEdit::delete(range(3, 8)),
+                ]],
+                Ok(vec![range(0, 5), range(3, 8)]),
+            ),
+        ] {
+            let results = merge_edits(&edit_sets)
+                .map(|edits| edits.iter().map(|edit| edit.range).collect::<Vec<_>>())
+                .map_err(|conflicts| {
+                    conflicts
+                        .iter()
+                        .map(|conflict| {
+                            (
+                                conflict.first_set,
+                                conflict.first.range,
+                                conflict.second_set,
+                                conflict.second.range,
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                });
+            assert_eq!(results, expected_results);
+        }
+
+        // Insertions at the same offset are ordered by set.
+        let edits = merge_edits([vec![insert("a", 0)], vec![insert("b", 0)]]).unwrap();
+        assert_eq!(
+            edits
+                .iter()
+                .map(|edit| edit.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+    }
+}
@@ -1,17 +1,20 @@
 //! Utilities for ink! analysis.
 
-use ink_analyzer_ir::ast::{HasAttrs, HasDocComments, HasModuleItem, HasName};
+use ink_analyzer_ir::ast::{HasArgList, HasAttrs, HasDocComments, HasModuleItem, HasName};
 use ink_analyzer_ir::syntax::{
     AstNode, AstToken, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TextSize,
 };
 use ink_analyzer_ir::{
-    ast, Contract, FromAST, FromSyntax, InkArg, InkArgKind, InkArgValueKind, InkArgValueStringKind,
-    InkAttribute, InkAttributeKind, InkImpl, InkMacroKind, IsInkEntity, IsInkStruct, IsInkTrait,
-    Storage,
+    ast, Contract, FromAST, FromInkAttribute, FromSyntax, InkArg, InkArgKind, InkArgValueKind,
+    InkArgValueStringKind, InkAttribute, InkAttributeKind, InkFile, InkImpl, InkMacroKind,
+    IsInkEntity, IsInkStruct, IsInkTrait, Storage,
 };
 use itertools::Itertools;
 use std::collections::HashSet;
 
+use super::indent::IndentStyle;
+use super::queries::Queries;
+use super::text_edit::TextEdit;
 use crate::utils;
 
 /// Returns valid sibling ink! argument kinds for the given ink! attribute kind.
@@ -425,9 +428,7 @@ pub fn remove_invalid_ink_arg_suggestions_for_parent_item(
 ) {
     // Removes namespace suggestions for trait `impl` blocks.
     // Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/item_impl/mod.rs#L316-L320>.
-    if ast::Impl::can_cast(attr_parent.kind()) {
-        let impl_item =
-            ast::Impl::cast(attr_parent.clone()).expect("Should be able to cast to `impl` item.");
+    if let Some(impl_item) = ast::Impl::cast(attr_parent.clone()) {
         if impl_item.trait_().is_some() {
             suggestions.retain(|arg_kind| *arg_kind != InkArgKind::Namespace);
         }
@@ -546,6 +547,129 @@ pub fn is_cfg_e2e_tests_attr(attr: &ast::Attr) -> bool {
         })
 }
 
+/// Returns the entries (i.e. `Cargo.toml` paths) of the `additional_contracts` argument value
+/// of an ink! e2e test (i.e. a space-separated list) along with their text ranges.
+pub fn additional_contracts_entries(ink_arg: &InkArg) -> Vec<(String, TextRange)> {
+    let Some(value) = ink_arg.value() else {
+        return Vec::new();
+    };
+    let Some(text) = value.as_string() else {
+        return Vec::new();
+    };
+    // Entries start after the opening quote of the string literal.
+    let start = value.text_range().start() + TextSize::from(1);
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    for entry in text.split_whitespace() {
+        let Some(idx) = text[offset..].find(entry).map(|idx| idx + offset) else {
+            continue;
+        };
+        offset = idx + entry.len();
+        entries.push((
+            entry.to_string(),
+            TextRange::at(start + TextSize::of(&text[..idx]), TextSize::of(entry)),
+        ));
+    }
+    entries
+}
+
+/// Returns true if the node (or one of its ancestors) is gated behind the `e2e-tests` feature
+/// (i.e. it has a `#[cfg(all(test, feature = "e2e-tests"))]` attribute).
+pub fn is_cfg_e2e_tests_gated(node: &SyntaxNode) -> bool {
+    node.ancestors().any(|node| {
+        node.children()
+            .filter_map(ast::Attr::cast)
+            .any(|attr| is_cfg_e2e_tests_attr(&attr))
+    })
+}
+
+/// Returns true if the attribute is a conditional compilation flag for the `ink-debug` feature
+/// (e.g. `#[cfg(feature = "ink-debug")]` or `#[cfg(any(test, feature = "ink-debug"))]`).
+pub fn is_cfg_ink_debug_attr(attr: &ast::Attr) -> bool {
+    attr.path().map_or(false, |path| path.to_string() == "cfg")
+        && attr.token_tree().map_or(false, |token_tree| {
+            let mut meta = token_tree.syntax().to_string();
+            meta.retain(|it| !it.is_whitespace());
+            meta.contains(r#"feature="ink-debug""#)
+        })
+}
+
+/// Returns a text edit that declares a cargo feature in the given `Cargo.toml` text
+/// (i.e. at the end of the `[features]` table, which is added if it doesn't exist),
+/// or None if the feature is already declared.
+pub fn cargo_feature_edit(cargo_toml: &str, name: &str, value: &str) -> Option<TextEdit> {
+    // Determines the end offset of the `[features]` table (if any).
+    let mut features_end = None;
+    let mut is_features_table = false;
+    let mut offset = 0;
+    for line in cargo_toml.split_inclusive('\n') {
+        let text = line.split('#').next().unwrap_or_default().trim();
+        offset += line.len();
+        if text.starts_with('[') {
+            is_features_table = text == "[features]";
+            if is_features_table {
+                features_end = Some(offset);
+            }
+        } else if is_features_table && !text.is_empty() {
+            // Bails if the feature is already declared.
+            let key = text.split('=').next().unwrap_or_default().trim();
+            if key.trim_matches('"') == name {
+                return None;
+            }
+            features_end = Some(offset);
+        }
+    }
+
+    let feature = format!("{name} = {value}\n");
+    Some(match features_end {
+        // Appends the feature to the `[features]` table.
+        Some(end) => TextEdit::insert(
+            format!(
+                "{}{feature}",
+                if cargo_toml[..end].ends_with('\n') {
+                    ""
+                } else {
+                    "\n"
+                }
+            ),
+            TextSize::from(end as u32),
+        ),
+        // Adds a `[features]` table at the end of the file.
+        None => TextEdit::insert(
+            format!(
+                "{}[features]\n{feature}",
+                if cargo_toml.is_empty() {
+                    ""
+                } else if cargo_toml.ends_with('\n') {
+                    "\n"
+                } else {
+                    "\n\n"
+                }
+            ),
+            TextSize::from(cargo_toml.len() as u32),
+        ),
+    })
+}
+
+/// Returns the package name declared in the `[package]` table of the given `Cargo.toml` text (if any).
+pub fn cargo_package_name(cargo_toml: &str) -> Option<String> {
+    let mut is_package_table = false;
+    for line in cargo_toml.lines() {
+        let text = line.split('#').next().unwrap_or_default().trim();
+        if text.starts_with('[') {
+            is_package_table = text == "[package]";
+        } else if is_package_table {
+            if let Some((key, value)) = text.split_once('=') {
+                if key.trim() == "name" {
+                    let name = value.trim().trim_matches(|c| c == '"' || c == '\'');
+                    return (!name.is_empty()).then(|| name.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
 /// Returns the insert text and snippet (if appropriate) for ink! attribute argument including
 /// the `=` symbol after the ink! attribute argument name if necessary.
 ///
@@ -839,14 +963,25 @@ pub fn first_ink_arg_insert_offset_and_affixes(
         }))
 }
 
-/// Returns the indenting (preceding whitespace) of the syntax node.
+/// Returns the indenting (preceding whitespace) of the syntax node
+/// (converted to canonical indenting, see [`IndentStyle`] doc).
 pub fn item_indenting(node: &SyntaxNode) -> Option<String> {
     node.prev_sibling_or_token().and_then(|prev_elem| {
-        (prev_elem.kind() == SyntaxKind::WHITESPACE)
-            .then_some(end_indenting(prev_elem.to_string().as_str()))
+        (prev_elem.kind() == SyntaxKind::WHITESPACE).then(|| {
+            IndentStyle::detect_for_node(node)
+                .unwrap_or_default()
+                .normalize(&end_indenting(prev_elem.to_string().as_str()))
+        })
     })
 }
 
+/// Returns the text of the syntax node (converted to canonical indenting, see [`IndentStyle`] doc).
+pub fn item_text(node: &SyntaxNode) -> String {
+    IndentStyle::detect_for_node(node)
+        .unwrap_or_default()
+        .normalize_lines(&node.to_string())
+}
+
 /// Returns the indenting at the end of string of whitespace.
 ///
 /// NOTE: This function doesn't verify that the input is actually whitespace.
@@ -865,6 +1000,23 @@ pub fn item_children_indenting(node: &SyntaxNode) -> String {
         .unwrap_or("    ".to_string())
 }
 
+/// Returns the focus text range for the offset (i.e. for computing text range based results at a cursor offset).
+///
+/// The focus range is the text range of the covering attribute if the focused token (if any)
+/// is part of an attribute (including tokens at the edges of the attribute),
+/// otherwise it's the text range of the focused token (unless it's trivia i.e. whitespace or a comment),
+/// otherwise it's an empty text range at the offset.
+pub fn focus_range_at_offset<T: FromSyntax>(item: &T, offset: TextSize) -> TextRange {
+    let item_at_offset = item.item_at_offset(offset);
+    item_at_offset
+        .focused_token()
+        .filter(|token| !token.kind().is_trivia())
+        .map_or(TextRange::empty(offset), |token| {
+            ink_analyzer_ir::closest_ancestor_ast_type::<SyntaxToken, ast::Attr>(token)
+                .map_or(token.text_range(), |attr| attr.syntax().text_range())
+        })
+}
+
 /// Returns the deepest syntax element that fully covers text range (if any).
 pub fn focused_element<T: FromSyntax>(item: &T, range: TextRange) -> Option<SyntaxElement> {
     if range.is_empty() {
@@ -913,6 +1065,28 @@ pub fn covering_ink_attribute<T: FromSyntax>(item: &T, range: TextRange) -> Opti
     covering_attribute(item, range).and_then(InkAttribute::cast)
 }
 
+/// Returns the covering ink! attribute for the text range (if any)
+/// using the (memoized) ink! entity index (i.e. a binary search instead of a syntax tree traversal).
+///
+/// Falls back to [`covering_ink_attribute`] for empty text ranges that aren't in an indexed ink! attribute
+/// (e.g. offsets in unclosed attributes, see [`ink_analyzer_ir::ItemAtOffset`] doc).
+pub fn indexed_covering_ink_attribute(
+    file: &InkFile,
+    queries: &Queries,
+    range: TextRange,
+) -> Option<InkAttribute> {
+    let indexed_attr = queries
+        .index(file)
+        .attr_at_offset(range.start())
+        .filter(|attr| attr.syntax().text_range().contains_range(range))
+        .cloned();
+    if indexed_attr.is_none() && range.is_empty() {
+        covering_ink_attribute(file, range)
+    } else {
+        indexed_attr
+    }
+}
+
 /// Returns the parent AST item for the text range (if any).
 pub fn parent_ast_item<T: FromSyntax>(item: &T, range: TextRange) -> Option<ast::Item> {
     if range.is_empty() {
@@ -1614,6 +1788,91 @@ pub fn suggest_unique_id(preferred_id: Option<u32>, unavailable_ids: &mut HashSe
     suggested_id
 }
 
+/// Suggests a unique/unused name based on the preferred name
+/// (i.e. the preferred name if it's available, otherwise the preferred name with the lowest numeric suffix
+/// that's available starting from 2 - e.g. `message2` if `message` is unavailable).
+pub fn suggest_unique_name(preferred_name: &str, unavailable_names: &HashSet<String>) -> String {
+    std::iter::once(preferred_name.to_string())
+        .chain((2..).map(|idx| format!("{preferred_name}{idx}")))
+        .find(|name| !unavailable_names.contains(name))
+        .unwrap_or(preferred_name.to_string())
+}
+
+/// Returns the names of all named items (e.g. `struct`s, `enum`s, `fn`s e.t.c) in an item list.
+pub fn item_names(item_list: &ast::ItemList) -> HashSet<String> {
+    item_list
+        .items()
+        .filter_map(|item| item.syntax().children().find_map(ast::Name::cast))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Returns the names of all associated `fn`s in an associated items list.
+pub fn assoc_fn_names(assoc_item_list: &ast::AssocItemList) -> HashSet<String> {
+    assoc_item_list
+        .assoc_items()
+        .filter_map(|item| match item {
+            ast::AssocItem::Fn(fn_item) => fn_item.name(),
+            _ => None,
+        })
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Returns the names of all associated `fn`s in all `impl` blocks in an item list.
+pub fn impl_fn_names(item_list: &ast::ItemList) -> HashSet<String> {
+    item_list
+        .items()
+        .filter_map(|item| match item {
+            ast::Item::Impl(impl_item) => impl_item.assoc_item_list(),
+            _ => None,
+        })
+        .flat_map(|assoc_item_list| assoc_fn_names(&assoc_item_list))
+        .collect()
+}
+
+/// Returns true if the expression is an ink! environment accessor call (i.e. `self.env()` or `Self::env()`).
+pub fn is_env_call(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::MethodCallExpr(call) => {
+            call.name_ref()
+                .map_or(false, |name_ref| name_ref.text() == "env")
+                && call
+                    .arg_list()
+                    .map_or(true, |arg_list| arg_list.args().next().is_none())
+        }
+        ast::Expr::CallExpr(call) => match call.expr() {
+            Some(ast::Expr::PathExpr(path_expr)) => path_expr
+                .path()
+                .map_or(false, |path| path.syntax().to_string() == "Self::env"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns the parent ink! contract of the item (if any),
+/// either directly or via (non-ink!) inline child `mod` items of the ink! contract's `mod` item
+/// (e.g. for ink! impls split across child modules of an ink! contract).
+pub fn parent_contract(node: &SyntaxNode) -> Option<Contract> {
+    let mut parent = ink_analyzer_ir::parent_ast_item(node)?;
+    loop {
+        if let Some(contract) = ink_analyzer_ir::ink_attrs(parent.syntax()).find_map(Contract::cast)
+        {
+            return Some(contract);
+        }
+        match parent {
+            ast::Item::Module(module)
+                if module.item_list().is_some()
+                    && ink_analyzer_ir::ink_attrs(module.syntax()).next().is_none() =>
+            {
+                parent = ink_analyzer_ir::parent_ast_item(module.syntax())?;
+            }
+            _ => return None,
+        }
+    }
+}
+
 /// Returns text range of the contract `mod` "declaration"
 /// (i.e tokens between meta - attributes/rustdoc - and the start of the item list).
 pub fn contract_declaration_range(contract: &Contract) -> TextRange {
@@ -1653,3 +1912,193 @@ pub fn is_trivia_insensitive_eq(a: &SyntaxNode, b: &SyntaxNode) -> bool {
     };
     strip_trivia(a) == strip_trivia(b)
 }
+
+/// Compares two types for equivalence while ignoring trivia and resolving `use` aliases
+/// (e.g. `Result<(), Error>` and `Result<(), MyError>` are equivalent
+/// given `use crate::errors::Error;` and `use crate::errors::Error as MyError;` in their respective scopes).
+///
+/// NOTE: Leading `crate`, `self` and `super` path segments are ignored, and `std` and `alloc`
+/// are treated as aliases of `core`, because paths aren't resolved beyond `use` declarations.
+pub fn is_equivalent_type(a: &ast::Type, b: &ast::Type) -> bool {
+    canonical_type(a) == canonical_type(b)
+}
+
+/// Returns a canonical representation of a type (see [`is_equivalent_type`] doc).
+fn canonical_type(ty: &ast::Type) -> String {
+    let canonical_type_option =
+        |ty_option: Option<ast::Type>| ty_option.as_ref().map(canonical_type).unwrap_or_default();
+    match ty {
+        ast::Type::PathType(path_type) => path_type.path().map_or_else(
+            || trivia_free_text(ty.syntax()),
+            |path| canonical_path(&path),
+        ),
+        ast::Type::TupleType(tuple_type) => {
+            let fields: Vec<String> = tuple_type
+                .fields()
+                .map(|field| canonical_type(&field))
+                .collect();
+            format!(
+                "({}{})",
+                fields.join(","),
+                if fields.len() == 1 { "," } else { "" }
+            )
+        }
+        ast::Type::RefType(ref_type) => format!(
+            "&{}{}{}",
+            ref_type
+                .lifetime()
+                .map(|lifetime| format!("{lifetime} "))
+                .unwrap_or_default(),
+            if ref_type.mut_token().is_some() {
+                "mut "
+            } else {
+                ""
+            },
+            canonical_type_option(ref_type.ty())
+        ),
+        ast::Type::SliceType(slice_type) => format!("[{}]", canonical_type_option(slice_type.ty())),
+        ast::Type::ArrayType(array_type) => format!(
+            "[{};{}]",
+            canonical_type_option(array_type.ty()),
+            array_type
+                .const_arg()
+                .map(|arg| trivia_free_text(arg.syntax()))
+                .unwrap_or_default()
+        ),
+        ast::Type::ParenType(paren_type) => canonical_type_option(paren_type.ty()),
+        _ => trivia_free_text(ty.syntax()),
+    }
+}
+
+/// Returns a canonical representation of a path (see [`is_equivalent_type`] doc).
+fn canonical_path(path: &ast::Path) -> String {
+    let mut segments: Vec<String> = Vec::new();
+    for (idx, segment) in path.segments().enumerate() {
+        let Some(name) = segment.name_ref().map(|name_ref| name_ref.to_string()) else {
+            // Bails for paths with unsupported segments (e.g. qualified paths like `<T as Trait>::Output`).
+            return trivia_free_text(path.syntax());
+        };
+        // Resolves `use` aliases for the first segment.
+        match (idx == 0)
+            .then(|| resolve_use_alias(&name, path.syntax()))
+            .flatten()
+        {
+            Some(resolved_segments) => segments.extend(resolved_segments),
+            None => segments.push(name),
+        }
+        if let Some(generic_arg_list) = segment.generic_arg_list() {
+            let args = generic_arg_list
+                .generic_args()
+                .map(|arg| match &arg {
+                    ast::GenericArg::TypeArg(type_arg) => type_arg
+                        .ty()
+                        .as_ref()
+                        .map(canonical_type)
+                        .unwrap_or_default(),
+                    _ => trivia_free_text(arg.syntax()),
+                })
+                .join(",");
+            if let Some(last_segment) = segments.last_mut() {
+                last_segment.push_str(&format!("<{args}>"));
+            }
+        }
+    }
+
+    // Normalizes the root of the path.
+    let start = segments
+        .iter()
+        .take_while(|segment| matches!(segment.as_str(), "crate" | "self" | "super"))
+        .count();
+    let mut segments = segments.split_off(start);
+    if let Some(root) = segments.first_mut() {
+        if matches!(root.as_str(), "std" | "alloc") {
+            *root = "core".to_string();
+        }
+    }
+    segments.join("::")
+}
+
+/// Returns the path segments (if any) that the given name is imported as
+/// by a `use` declaration in scope for the syntax node.
+fn resolve_use_alias(name: &str, node: &SyntaxNode) -> Option<Vec<String>> {
+    node.ancestors().find_map(|ancestor| {
+        let items: Vec<ast::Item> = if let Some(item_list) = ast::ItemList::cast(ancestor.clone()) {
+            item_list.items().collect()
+        } else if let Some(file) = ast::SourceFile::cast(ancestor) {
+            file.items().collect()
+        } else {
+            return None;
+        };
+        items
+            .iter()
+            .filter_map(|item| match item {
+                ast::Item::Use(use_item) => use_item.use_tree(),
+                _ => None,
+            })
+            .flat_map(|use_tree| use_tree_aliases(&use_tree, &[]))
+            .find_map(|(alias, path)| (alias == name).then_some(path))
+    })
+}
+
+/// Returns all aliases (and their path segments) imported by a `use` tree
+/// (excluding glob imports and underscore imports).
+fn use_tree_aliases(use_tree: &ast::UseTree, prefix: &[String]) -> Vec<(String, Vec<String>)> {
+    let mut path = prefix.to_vec();
+    if let Some(use_path) = use_tree.path() {
+        path.extend(
+            use_path
+                .segments()
+                .filter_map(|segment| segment.name_ref())
+                .map(|name_ref| name_ref.to_string()),
+        );
+    }
+    if let Some(use_tree_list) = use_tree.use_tree_list() {
+        return use_tree_list
+            .use_trees()
+            .flat_map(|use_tree| use_tree_aliases(&use_tree, &path))
+            .collect();
+    }
+    if use_tree.star_token().is_some() {
+        return Vec::new();
+    }
+    // `self` imports the parent path (e.g. `use errors::{self}`).
+    if path.last().map_or(false, |segment| segment == "self") {
+        path.pop();
+    }
+    let alias = match use_tree.rename() {
+        // Underscore imports have no name.
+        Some(rename) => rename.name().map(|name| name.to_string()),
+        None => path.last().cloned(),
+    };
+    alias.map(|alias| vec![(alias, path)]).unwrap_or_default()
+}
+
+/// Returns the text of the syntax node without trivia (i.e. whitespace and comments).
+fn trivia_free_text(node: &SyntaxNode) -> String {
+    node.descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|token| !token.kind().is_trivia())
+        .map(|token| token.text().to_string())
+        .join("")
+}
+
+/// Names of methods that mutate their receiver
+/// (e.g. `Mapping::insert`, `Lazy::set` or `Vec::push`).
+pub const MUTATING_METHODS: [&str; 8] = [
+    "insert", "remove", "set", "take", "push", "pop", "clear", "truncate",
+];
+
+/// Returns the name of the `self` field at the root of a (possibly nested) field or index expression (if any)
+/// (e.g. `value` for `self.value`, `self.value.inner` or `self.values[0]`).
+pub fn self_field_name(expr: &ast::Expr) -> Option<String> {
+    match expr {
+        ast::Expr::FieldExpr(field_expr) => match field_expr.expr()? {
+            ast::Expr::PathExpr(path_expr) if path_expr.to_string() == "self" => {
+                Some(field_expr.name_ref()?.to_string())
+            }
+            receiver => self_field_name(&receiver),
+        },
+        ast::Expr::IndexExpr(index_expr) => self_field_name(&index_expr.base()?),
+        _ => None,
+    }
+}
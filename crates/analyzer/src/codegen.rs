@@ -1,9 +1,16 @@
 //! Utilities for generate ink! project files.
 
 use self::snippets::{CARGO_TOML_PLAIN, CARGO_TOML_SNIPPET, CONTRACT_PLAIN, CONTRACT_SNIPPET};
-use crate::utils;
+use self::templates::{
+    DNS_PLAIN, ERC1155_PLAIN, ERC20_PLAIN, ERC721_PLAIN, MULTISIG_PLAIN, PSP22_PLAIN,
+};
+use crate::{utils, IndentStyle};
 
+pub use docs::markdown_docs;
+
+mod docs;
 pub mod snippets;
+pub mod templates;
 
 /// Code stubs/snippets for creating an ink! project
 /// (i.e. code stubs/snippets for `lib.rs` and `Cargo.toml`).
@@ -15,9 +22,26 @@ pub struct Project {
     pub cargo: ProjectFile,
 }
 
+impl Project {
+    /// Returns the resource operations for creating the ink! project files
+    /// (i.e. `lib.rs` and `Cargo.toml` relative to the project root).
+    pub fn resource_ops(&self) -> Vec<ResourceOp> {
+        vec![
+            ResourceOp::CreateFile {
+                path: "lib.rs".to_string(),
+                file: self.lib.clone(),
+            },
+            ResourceOp::CreateFile {
+                path: "Cargo.toml".to_string(),
+                file: self.cargo.clone(),
+            },
+        ]
+    }
+}
+
 /// Code stubs/snippets for creating a file in an ink! project
 /// (e.g. `lib.rs` or `Cargo.toml` for an ink! contract).
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProjectFile {
     /// A plain text code stub.
     pub plain: String,
@@ -25,6 +49,55 @@ pub struct ProjectFile {
     pub snippet: Option<String>,
 }
 
+/// A resource (i.e. file or directory) operation in an ink! project.
+///
+/// Paths are either relative to the project root or absolute URIs.
+///
+/// Used to describe workspace edits that span multiple files (e.g. module extraction or migrations).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ResourceOp {
+    /// Creates a file with the given content.
+    CreateFile {
+        /// Path of the new file.
+        path: String,
+        /// Content of the new file.
+        file: ProjectFile,
+    },
+    /// Creates a directory.
+    CreateDir {
+        /// Path of the new directory.
+        path: String,
+    },
+    /// Renames (or moves) a file or directory.
+    Rename {
+        /// Current path of the file or directory.
+        from: String,
+        /// New path of the file or directory.
+        to: String,
+    },
+    /// Deletes a file or directory.
+    Delete {
+        /// Path of the file or directory.
+        path: String,
+        /// Whether the contents of a directory should be deleted recursively.
+        recursive: bool,
+    },
+}
+
+impl ResourceOp {
+    /// Returns the path of the file or directory targeted by the resource operation
+    /// (i.e. the new path for renames).
+    pub fn path(&self) -> &str {
+        match self {
+            ResourceOp::CreateFile { path, .. }
+            | ResourceOp::CreateDir { path }
+            | ResourceOp::Delete { path, .. } => path,
+            ResourceOp::Rename { to, .. } => to,
+        }
+    }
+}
+
 /// An ink! project error.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
@@ -39,8 +112,74 @@ pub enum Error {
     ContractName,
 }
 
+/// An ink! project template.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Template {
+    /// A minimal contract with a constructor, a message and test stubs.
+    #[default]
+    Minimal,
+    /// An ERC-20 fungible token contract.
+    ///
+    /// Ref: <https://eips.ethereum.org/EIPS/eip-20>.
+    Erc20,
+    /// An ERC-721 non-fungible token contract.
+    ///
+    /// Ref: <https://eips.ethereum.org/EIPS/eip-721>.
+    Erc721,
+    /// An ERC-1155 multi-token contract.
+    ///
+    /// Ref: <https://eips.ethereum.org/EIPS/eip-1155>.
+    Erc1155,
+    /// A PSP-22 fungible token contract.
+    ///
+    /// Ref: <https://github.com/w3f/PSPs/blob/master/PSPs/psp-22.md>.
+    Psp22,
+    /// A multisig wallet contract.
+    Multisig,
+    /// A domain name service contract.
+    Dns,
+}
+
+impl Template {
+    /// Returns the `lib.rs` code stub and snippet (if any) for the template
+    /// (with `my_contract` and `MyContract` placeholders for the contract `mod` and storage `struct` names).
+    fn lib(&self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Template::Minimal => (CONTRACT_PLAIN, Some(CONTRACT_SNIPPET)),
+            Template::Erc20 => (ERC20_PLAIN, None),
+            Template::Erc721 => (ERC721_PLAIN, None),
+            Template::Erc1155 => (ERC1155_PLAIN, None),
+            Template::Psp22 => (PSP22_PLAIN, None),
+            Template::Multisig => (MULTISIG_PLAIN, None),
+            Template::Dns => (DNS_PLAIN, None),
+        }
+    }
+}
+
 /// Returns code stubs/snippets for creating a new ink! project given a name.
 pub fn new_project(name: String) -> Result<Project, Error> {
+    new_project_with_indent_style(name, IndentStyle::default())
+}
+
+/// Returns code stubs/snippets for creating a new ink! project given a name,
+/// using the given indentation style for the generated code.
+pub fn new_project_with_indent_style(name: String, style: IndentStyle) -> Result<Project, Error> {
+    generate_project(name, Template::default(), style)
+}
+
+/// Returns code stubs/snippets for creating a new ink! project given a name and a template.
+pub fn new_project_with_template(name: String, template: Template) -> Result<Project, Error> {
+    generate_project(name, template, IndentStyle::default())
+}
+
+/// Returns code stubs/snippets for creating a new ink! project given a name, a template
+/// and the indentation style for the generated code.
+fn generate_project(
+    name: String,
+    template: Template,
+    style: IndentStyle,
+) -> Result<Project, Error> {
     // Validates that name is a valid Rust package name.
     // Ref: <https://doc.rust-lang.org/cargo/reference/manifest.html#the-name-field>.
     if name.is_empty()
@@ -62,17 +201,19 @@ pub fn new_project(name: String) -> Result<Project, Error> {
     let struct_name = utils::pascal_case(&module_name);
 
     // Returns project code stubs/snippets.
+    let (lib_plain, lib_snippet) = template.lib();
+    let lib = |code: &str| {
+        style.apply(
+            &code
+                .replace("my_contract", &module_name)
+                .replace("MyContract", &struct_name),
+        )
+    };
     Ok(Project {
         // Generates `lib.rs`.
         lib: ProjectFile {
-            plain: CONTRACT_PLAIN
-                .replace("my_contract", &module_name)
-                .replace("MyContract", &struct_name),
-            snippet: Some(
-                CONTRACT_SNIPPET
-                    .replace("my_contract", &module_name)
-                    .replace("MyContract", &struct_name),
-            ),
+            plain: lib(lib_plain),
+            snippet: lib_snippet.map(lib),
         },
         // Generates `Cargo.toml`.
         cargo: ProjectFile {
@@ -120,4 +261,70 @@ mod tests {
             assert_eq!(analysis.diagnostics().len(), 0);
         }
     }
+
+    #[test]
+    fn project_templates_works() {
+        for template in [
+            Template::Minimal,
+            Template::Erc20,
+            Template::Erc721,
+            Template::Erc1155,
+            Template::Psp22,
+            Template::Multisig,
+            Template::Dns,
+        ] {
+            // Generates an ink! contract project from the template.
+            let project = new_project_with_template("hello-world".to_string(), template).unwrap();
+
+            // Verifies that the placeholder names are replaced.
+            assert!(project.lib.plain.contains("pub mod hello_world {"));
+            assert!(project.lib.plain.contains("pub struct HelloWorld"));
+            assert!(!project.lib.plain.contains("MyContract"));
+            assert!(project.cargo.plain.contains(r#"name = "hello-world""#));
+
+            // Verifies that the generated code stub is a valid contract.
+            let analysis = Analysis::new(&project.lib.plain);
+            assert_eq!(analysis.diagnostics(), Vec::new(), "template: {template:?}");
+        }
+    }
+
+    #[test]
+    fn project_indent_style_works() {
+        let project =
+            new_project_with_indent_style("hello_world".to_string(), IndentStyle::Tabs).unwrap();
+
+        // Verifies that the generated code stub is indented with tabs.
+        assert!(project.lib.plain.contains("\n\t#[ink(storage)]"));
+        assert!(!project.lib.plain.contains("\n    "));
+
+        // Verifies that the generated code stub is still a valid contract.
+        let analysis = Analysis::new(&project.lib.plain);
+        assert_eq!(analysis.diagnostics().len(), 0);
+    }
+
+    #[test]
+    fn project_resource_ops_works() {
+        let project = new_project("hello_world".to_string()).unwrap();
+        let ops = project.resource_ops();
+
+        // Verifies that `lib.rs` and `Cargo.toml` creation operations are returned.
+        assert_eq!(
+            ops.iter().map(ResourceOp::path).collect::<Vec<&str>>(),
+            vec!["lib.rs", "Cargo.toml"]
+        );
+        assert_eq!(
+            ops[0],
+            ResourceOp::CreateFile {
+                path: "lib.rs".to_string(),
+                file: project.lib.clone(),
+            }
+        );
+        assert_eq!(
+            ops[1],
+            ResourceOp::CreateFile {
+                path: "Cargo.toml".to_string(),
+                file: project.cargo.clone(),
+            }
+        );
+    }
 }
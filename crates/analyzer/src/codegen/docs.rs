@@ -0,0 +1,338 @@
+//! Markdown documentation generation for ink! contracts.
+
+use ink_analyzer_ir::ast::HasName;
+use ink_analyzer_ir::syntax::AstNode;
+use ink_analyzer_ir::{ast, ChainExtension, FromSyntax, IsInkFn, IsInkStruct, IsInkTrait};
+
+use crate::{Analysis, CallableInfo, ContractInfo, EventInfo};
+
+/// Returns a Markdown summary of the ink! contracts and ink! chain extensions in the analyzed file
+/// (i.e. storage fields, constructors and messages with their selectors and signatures,
+/// events with their topics and chain extensions with their extension ids and signatures).
+pub fn markdown_docs(analysis: &Analysis) -> String {
+    let mut sections: Vec<String> = analysis
+        .contract_info()
+        .iter()
+        .map(|contract| contract_docs(analysis, contract))
+        .collect();
+    sections.extend(
+        analysis
+            .file()
+            .chain_extensions()
+            .iter()
+            .filter_map(chain_extension_docs),
+    );
+    let output = sections.concat();
+    if output.is_empty() {
+        output
+    } else {
+        // Removes trailing blank lines.
+        format!("{}\n", output.trim_end())
+    }
+}
+
+/// Returns the Markdown documentation for an ink! contract.
+fn contract_docs(analysis: &Analysis, contract: &ContractInfo) -> String {
+    let mut output = format!("# Contract `{}`\n\n", contract.name);
+    push_docs(&mut output, &contract.docs);
+
+    // Storage.
+    if let Some(storage) = &contract.storage {
+        output.push_str(&format!("## Storage `{storage}`\n\n"));
+        let fields = storage_fields(analysis, contract);
+        if fields.is_empty() {
+            output.push_str("No fields.\n\n");
+        } else {
+            output.push_str("| Field | Type |\n| --- | --- |\n");
+            for (name, ty) in fields {
+                output.push_str(&format!("| `{name}` | {} |\n", table_code(ty.as_deref())));
+            }
+            output.push('\n');
+        }
+    }
+
+    // Constructors and messages.
+    for (title, callables, is_message) in [
+        ("Constructors", &contract.constructors, false),
+        ("Messages", &contract.messages, true),
+    ] {
+        if callables.is_empty() {
+            continue;
+        }
+        output.push_str(&format!("## {title}\n\n"));
+        for callable in callables {
+            push_callable_docs(&mut output, callable, is_message);
+        }
+    }
+
+    // Events.
+    if !contract.events.is_empty() {
+        output.push_str("## Events\n\n");
+        for event in &contract.events {
+            push_event_docs(&mut output, event);
+        }
+    }
+
+    output
+}
+
+/// Adds the Markdown documentation for an ink! constructor or ink! message.
+fn push_callable_docs(output: &mut String, callable: &CallableInfo, is_message: bool) {
+    output.push_str(&format!("### `{}`\n\n", callable.label));
+    push_docs(output, &callable.docs);
+
+    // Composes the signature (i.e. the `self` receiver is only relevant for ink! messages).
+    let name = callable
+        .label
+        .rsplit("::")
+        .next()
+        .unwrap_or(&callable.label);
+    let receiver = is_message.then_some(if callable.is_mutable {
+        "&mut self"
+    } else {
+        "&self"
+    });
+    let params = receiver
+        .map(ToString::to_string)
+        .into_iter()
+        .chain(
+            callable
+                .args
+                .iter()
+                .map(|arg| format!("{}: {}", arg.name, arg.type_name.as_deref().unwrap_or("_"))),
+        )
+        .collect::<Vec<String>>()
+        .join(", ");
+    let ret = callable
+        .return_type
+        .as_ref()
+        .map(|ty| format!(" -> {ty}"))
+        .unwrap_or_default();
+    output.push_str(&format!("- Signature: `fn {name}({params}){ret}`\n"));
+
+    if let Some(selector) = callable.selector {
+        output.push_str(&format!("- Selector: `0x{selector:08x}`\n"));
+    }
+    let flags: Vec<&str> = [
+        (callable.is_payable, "payable"),
+        (callable.is_default, "default"),
+        (is_message && callable.is_mutable, "mutates storage"),
+    ]
+    .into_iter()
+    .filter_map(|(is_set, flag)| is_set.then_some(flag))
+    .collect();
+    if !flags.is_empty() {
+        output.push_str(&format!("- Flags: {}\n", flags.join(", ")));
+    }
+    output.push('\n');
+}
+
+/// Adds the Markdown documentation for an ink! event.
+fn push_event_docs(output: &mut String, event: &EventInfo) {
+    output.push_str(&format!("### `{}`\n\n", event.name));
+    push_docs(output, &event.docs);
+
+    match event.signature_topic {
+        Some(signature_topic) => {
+            let hex: String = signature_topic
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect();
+            output.push_str(&format!("- Signature topic: `0x{hex}`\n\n"));
+        }
+        None if event.is_anonymous => output.push_str("- Anonymous\n\n"),
+        None => (),
+    }
+
+    if event.fields.is_empty() {
+        output.push_str("No fields.\n\n");
+    } else {
+        output.push_str("| Field | Type | Topic |\n| --- | --- | --- |\n");
+        for field in &event.fields {
+            output.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                field.name,
+                table_code(field.type_name.as_deref()),
+                if field.is_topic { "yes" } else { "no" }
+            ));
+        }
+        output.push('\n');
+    }
+}
+
+/// Returns the Markdown documentation for an ink! chain extension (if it has a name).
+fn chain_extension_docs(chain_extension: &ChainExtension) -> Option<String> {
+    let trait_item = chain_extension.trait_item()?;
+    let mut output = format!("# Chain extension `{}`\n\n", trait_item.name()?);
+    push_docs(&mut output, &doc_text(trait_item));
+
+    if let Some(error_code) = chain_extension
+        .error_code()
+        .and_then(|type_alias| type_alias.ty())
+    {
+        output.push_str(&format!("- Error code: `{}`\n\n", error_code.syntax()));
+    }
+
+    for extension in chain_extension.extensions() {
+        let Some(fn_item) = extension.fn_item() else {
+            continue;
+        };
+        let Some(name) = fn_item.name() else {
+            continue;
+        };
+        output.push_str(&format!("## `{name}`\n\n"));
+        push_docs(&mut output, &doc_text(fn_item));
+
+        let params = fn_item
+            .param_list()
+            .map(|param_list| param_list.syntax().to_string())
+            .unwrap_or_else(|| "()".to_string());
+        let ret = fn_item
+            .ret_type()
+            .map(|ret_type| format!(" {}", ret_type.syntax()))
+            .unwrap_or_default();
+        output.push_str(&format!("- Signature: `fn {name}{params}{ret}`\n"));
+        if let Some(id) = extension.id() {
+            output.push_str(&format!("- Extension id: `{id}`\n"));
+        }
+        if extension
+            .handle_status_arg()
+            .map_or(true, |arg| arg.as_bool() != Ok(false))
+        {
+            output.push_str("- Handles status codes\n");
+        }
+        output.push('\n');
+    }
+
+    Some(output)
+}
+
+/// Returns the names and types of the storage fields of an ink! contract.
+fn storage_fields(analysis: &Analysis, contract: &ContractInfo) -> Vec<(String, Option<String>)> {
+    let struct_item = analysis
+        .file()
+        .contracts()
+        .iter()
+        .find(|it| it.syntax().text_range() == contract.range)
+        .and_then(|it| it.storage())
+        .and_then(IsInkStruct::struct_item);
+    match struct_item.and_then(ast::Struct::field_list) {
+        Some(ast::FieldList::RecordFieldList(field_list)) => field_list
+            .fields()
+            .filter_map(|field| {
+                Some((
+                    field.name()?.to_string(),
+                    field.ty().map(|ty| ty.syntax().to_string()),
+                ))
+            })
+            .collect(),
+        Some(ast::FieldList::TupleFieldList(field_list)) => field_list
+            .fields()
+            .enumerate()
+            .map(|(idx, field)| {
+                (
+                    idx.to_string(),
+                    field.ty().map(|ty| ty.syntax().to_string()),
+                )
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Adds doc comment text as a paragraph (if any).
+fn push_docs(output: &mut String, docs: &str) {
+    if !docs.trim().is_empty() {
+        output.push_str(docs.trim_end());
+        output.push_str("\n\n");
+    }
+}
+
+/// Returns the doc comment text of an item (i.e. without the comment markers).
+fn doc_text(item: &impl ast::HasDocComments) -> String {
+    item.doc_comments()
+        .filter_map(|comment| {
+            comment
+                .doc_comment()
+                .map(|text| text.strip_prefix(' ').unwrap_or(text).to_string())
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Returns inline code for a Markdown table cell (i.e. with pipes escaped).
+fn table_code(text: Option<&str>) -> String {
+    text.map(|text| format!("`{}`", text.replace('|', "\\|")))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_docs_works() {
+        let code = r#"
+            /// A flipper contract.
+            #[ink::contract]
+            mod flipper {
+                #[ink(storage)]
+                pub struct Flipper {
+                    value: bool,
+                }
+
+                /// Emitted when the value is flipped.
+                #[ink(event)]
+                pub struct Flipped {
+                    #[ink(topic)]
+                    from: Option<AccountId>,
+                    value: bool,
+                }
+
+                impl Flipper {
+                    /// Creates a new flipper.
+                    #[ink(constructor, default)]
+                    pub fn new(init_value: bool) -> Self {}
+
+                    #[ink(message, payable, selector = 0xA)]
+                    pub fn flip(&mut self) {}
+
+                    #[ink(message)]
+                    pub fn get(&self) -> bool {}
+                }
+            }
+
+            /// A random chain extension.
+            #[ink::chain_extension]
+            pub trait Random {
+                type ErrorCode = RandomReadErr;
+
+                #[ink(extension = 0x3d26, handle_status = false)]
+                fn fetch_random(subject: [u8; 32]) -> [u8; 32];
+            }
+        "#;
+        let output = markdown_docs(&Analysis::new(code));
+
+        for expected in [
+            "# Contract `flipper`\n\nA flipper contract.\n\n",
+            "## Storage `Flipper`\n\n| Field | Type |\n| --- | --- |\n| `value` | `bool` |\n\n",
+            "## Constructors\n\n### `new`\n\nCreates a new flipper.\n\n\
+            - Signature: `fn new(init_value: bool) -> Self`\n\
+            - Selector: `0x9bae9d5e`\n- Flags: default\n\n",
+            "### `flip`\n\n- Signature: `fn flip(&mut self)`\n\
+            - Selector: `0x0000000a`\n- Flags: payable, mutates storage\n\n",
+            "### `get`\n\n- Signature: `fn get(&self) -> bool`\n- Selector: `0x2f865bd9`\n\n",
+            "## Events\n\n### `Flipped`\n\nEmitted when the value is flipped.\n\n- Signature topic: `0x",
+            "| `from` | `Option<AccountId>` | yes |\n| `value` | `bool` | no |\n",
+            "# Chain extension `Random`\n\nA random chain extension.\n\n\
+            - Error code: `RandomReadErr`\n\n",
+            "## `fetch_random`\n\n- Signature: `fn fetch_random(subject: [u8; 32]) -> [u8; 32]`\n\
+            - Extension id: `15654`\n",
+        ] {
+            assert!(
+                output.contains(expected),
+                "expected:\n{expected}\noutput:\n{output}"
+            );
+        }
+    }
+}
@@ -0,0 +1,1209 @@
+//! ink! project templates (i.e. `lib.rs` code stubs for complete ink! contracts).
+//!
+//! NOTE: Templates use `my_contract` and `MyContract` as placeholders for the contract `mod` and storage `struct` names.
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.3.0/integration-tests/erc20/lib.rs>.
+pub const ERC20_PLAIN: &str = r#"#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+pub mod my_contract {
+    use ink::storage::Mapping;
+
+    /// A simple ERC-20 contract.
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MyContract {
+        /// Total token supply.
+        total_supply: Balance,
+        /// Mapping from owner to number of owned tokens.
+        balances: Mapping<AccountId, Balance>,
+        /// Mapping of the token amount which an account is allowed to withdraw from another account.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+    }
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs that `spender` is allowed to withdraw
+    /// up to the amount of `value` tokens from `owner`.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    /// The ERC-20 error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if not enough balance to fulfill a request is available.
+        InsufficientBalance,
+        /// Returned if not enough allowance to fulfill a request is available.
+        InsufficientAllowance,
+    }
+
+    /// The ERC-20 result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    impl MyContract {
+        /// Creates a new ERC-20 contract with the specified initial supply.
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            let mut balances = Mapping::default();
+            let caller = Self::env().caller();
+            balances.insert(caller, &total_supply);
+            Self::env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                value: total_supply,
+            });
+            Self {
+                total_supply,
+                balances,
+                allowances: Default::default(),
+            }
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(&from, &to, value)
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to the `value` amount.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.insert((&owner, &spender), &value);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens on the behalf of `from` to the account `to`.
+        #[ink(message)]
+        pub fn transfer_from(&mut self, from: AccountId, to: AccountId, value: Balance) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(Error::InsufficientAllowance);
+            }
+            self.transfer_from_to(&from, &to, value)?;
+            self.allowances.insert((&from, &caller), &(allowance - value));
+            Ok(())
+        }
+
+        /// Transfers `value` amount of tokens from account `from` to account `to`.
+        fn transfer_from_to(&mut self, from: &AccountId, to: &AccountId, value: Balance) -> Result<()> {
+            let from_balance = self.balance_of(*from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(*to);
+            self.balances.insert(to, &(to_balance + value));
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: Some(*to),
+                value,
+            });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn transfer_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = MyContract::new(100);
+            assert_eq!(contract.total_supply(), 100);
+            assert_eq!(contract.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(contract.balance_of(accounts.alice), 90);
+            assert_eq!(contract.balance_of(accounts.bob), 10);
+        }
+    }
+}"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.3.0/integration-tests/erc721/lib.rs>.
+pub const ERC721_PLAIN: &str = r#"#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+pub mod my_contract {
+    use ink::storage::Mapping;
+
+    /// A token ID.
+    pub type TokenId = u32;
+
+    /// A simple ERC-721 contract.
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MyContract {
+        /// Mapping from token to owner.
+        token_owner: Mapping<TokenId, AccountId>,
+        /// Mapping from token to approvals users.
+        token_approvals: Mapping<TokenId, AccountId>,
+        /// Mapping from owner to number of owned tokens.
+        owned_tokens_count: Mapping<AccountId, u32>,
+        /// Mapping from owner to operator approvals.
+        operator_approvals: Mapping<(AccountId, AccountId), ()>,
+    }
+
+    /// The ERC-721 error types.
+    #[derive(Debug, PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        NotOwner,
+        NotApproved,
+        TokenExists,
+        TokenNotFound,
+        NotAllowed,
+    }
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Event emitted when a token approve occurs.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Event emitted when an operator is enabled or disabled for an owner.
+    /// The operator can manage all NFTs of the owner.
+    #[ink(event)]
+    pub struct ApprovalForAll {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        approved: bool,
+    }
+
+    impl MyContract {
+        /// Creates a new ERC-721 token contract.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Default::default()
+        }
+
+        /// Returns the balance of the owner.
+        ///
+        /// This represents the amount of unique tokens the owner has.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> u32 {
+            self.owned_tokens_count.get(owner).unwrap_or_default()
+        }
+
+        /// Returns the owner of the token.
+        #[ink(message)]
+        pub fn owner_of(&self, id: TokenId) -> Option<AccountId> {
+            self.token_owner.get(id)
+        }
+
+        /// Returns the approved account ID for this token if any.
+        #[ink(message)]
+        pub fn get_approved(&self, id: TokenId) -> Option<AccountId> {
+            self.token_approvals.get(id)
+        }
+
+        /// Returns `true` if the operator is approved by the owner.
+        #[ink(message)]
+        pub fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+            self.operator_approvals.contains((&owner, &operator))
+        }
+
+        /// Approves or disapproves the operator for all tokens of the caller.
+        #[ink(message)]
+        pub fn set_approval_for_all(&mut self, to: AccountId, approved: bool) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if to == caller {
+                return Err(Error::NotAllowed);
+            }
+            if approved {
+                self.operator_approvals.insert((&caller, &to), &());
+            } else {
+                self.operator_approvals.remove((&caller, &to));
+            }
+            self.env().emit_event(ApprovalForAll {
+                owner: caller,
+                operator: to,
+                approved,
+            });
+            Ok(())
+        }
+
+        /// Approves the account to transfer the specified token on behalf of the caller.
+        #[ink(message)]
+        pub fn approve(&mut self, to: AccountId, id: TokenId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let owner = self.owner_of(id).ok_or(Error::TokenNotFound)?;
+            if !(owner == caller || self.is_approved_for_all(owner, caller)) {
+                return Err(Error::NotAllowed);
+            }
+            if self.token_approvals.contains(id) {
+                return Err(Error::NotAllowed);
+            }
+            self.token_approvals.insert(id, &to);
+            self.env().emit_event(Approval {
+                from: caller,
+                to,
+                id,
+            });
+            Ok(())
+        }
+
+        /// Transfers the token from the caller to the given destination.
+        #[ink(message)]
+        pub fn transfer(&mut self, destination: AccountId, id: TokenId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.transfer_token_from(&caller, &destination, id)
+        }
+
+        /// Transfer approved or owned token.
+        #[ink(message)]
+        pub fn transfer_from(&mut self, from: AccountId, to: AccountId, id: TokenId) -> Result<(), Error> {
+            self.transfer_token_from(&from, &to, id)
+        }
+
+        /// Creates a new token.
+        #[ink(message)]
+        pub fn mint(&mut self, id: TokenId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if self.token_owner.contains(id) {
+                return Err(Error::TokenExists);
+            }
+            self.token_owner.insert(id, &caller);
+            let count = self.balance_of(caller);
+            self.owned_tokens_count.insert(caller, &(count + 1));
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                id,
+            });
+            Ok(())
+        }
+
+        /// Deletes an existing token. Only the owner can burn the token.
+        #[ink(message)]
+        pub fn burn(&mut self, id: TokenId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let owner = self.owner_of(id).ok_or(Error::TokenNotFound)?;
+            if owner != caller {
+                return Err(Error::NotOwner);
+            }
+            let count = self.balance_of(caller);
+            self.owned_tokens_count.insert(caller, &(count - 1));
+            self.token_owner.remove(id);
+            self.token_approvals.remove(id);
+            self.env().emit_event(Transfer {
+                from: Some(caller),
+                to: None,
+                id,
+            });
+            Ok(())
+        }
+
+        /// Transfers token `id` `from` the sender to the `to` account.
+        fn transfer_token_from(&mut self, from: &AccountId, to: &AccountId, id: TokenId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let owner = self.owner_of(id).ok_or(Error::TokenNotFound)?;
+            let is_approved = self.get_approved(id) == Some(caller)
+                || self.is_approved_for_all(owner, caller);
+            if owner != caller && !is_approved {
+                return Err(Error::NotApproved);
+            }
+            if owner != *from {
+                return Err(Error::NotOwner);
+            }
+            if self.token_owner.contains(id) && *to == owner {
+                return Err(Error::NotAllowed);
+            }
+            let from_count = self.balance_of(*from);
+            self.owned_tokens_count.insert(from, &(from_count - 1));
+            let to_count = self.balance_of(*to);
+            self.owned_tokens_count.insert(to, &(to_count + 1));
+            self.token_approvals.remove(id);
+            self.token_owner.insert(id, to);
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: Some(*to),
+                id,
+            });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn mint_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = MyContract::new();
+            assert_eq!(contract.owner_of(1), None);
+            assert_eq!(contract.mint(1), Ok(()));
+            assert_eq!(contract.balance_of(accounts.alice), 1);
+            assert_eq!(contract.owner_of(1), Some(accounts.alice));
+        }
+    }
+}"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.3.0/integration-tests/erc1155/lib.rs>.
+pub const ERC1155_PLAIN: &str = r#"#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+pub mod my_contract {
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    /// A token ID.
+    pub type TokenId = u128;
+
+    /// A simple ERC-1155 contract
+    /// (NOTE: doesn't call the `on_erc1155_received` hooks of receiving contracts).
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MyContract {
+        /// Tracks the balances of accounts across the different tokens that they might be holding.
+        balances: Mapping<(AccountId, TokenId), Balance>,
+        /// Which accounts (called operators) have been approved to spend funds on behalf of an owner.
+        approvals: Mapping<(AccountId, AccountId), ()>,
+        /// A unique identifier for the tokens which have been minted (and are therefore supported) by this contract.
+        token_id_nonce: TokenId,
+    }
+
+    /// The ERC-1155 error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        UnexistentToken,
+        ZeroAddressTransfer,
+        NotApproved,
+        InsufficientBalance,
+        SelfApproval,
+        BatchTransferMismatch,
+    }
+
+    /// The ERC-1155 result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Indicate that a token transfer has occured.
+    #[ink(event)]
+    pub struct TransferSingle {
+        #[ink(topic)]
+        operator: Option<AccountId>,
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        token_id: TokenId,
+        value: Balance,
+    }
+
+    /// Indicate that an approval event has happened.
+    #[ink(event)]
+    pub struct ApprovalForAll {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        approved: bool,
+    }
+
+    impl MyContract {
+        /// Creates a new ERC-1155 token contract.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Default::default()
+        }
+
+        /// Creates a new token type with an initial supply owned by the caller, and returns its ID.
+        #[ink(message)]
+        pub fn create(&mut self, value: Balance) -> TokenId {
+            let caller = self.env().caller();
+            self.token_id_nonce += 1;
+            self.balances.insert((caller, self.token_id_nonce), &value);
+            self.env().emit_event(TransferSingle {
+                operator: Some(caller),
+                from: None,
+                to: Some(caller),
+                token_id: self.token_id_nonce,
+                value,
+            });
+            self.token_id_nonce
+        }
+
+        /// Mints more of an existing token type to the caller.
+        #[ink(message)]
+        pub fn mint(&mut self, token_id: TokenId, value: Balance) -> Result<()> {
+            if token_id == 0 || token_id > self.token_id_nonce {
+                return Err(Error::UnexistentToken);
+            }
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller, token_id);
+            self.balances.insert((caller, token_id), &(balance + value));
+            self.env().emit_event(TransferSingle {
+                operator: Some(caller),
+                from: None,
+                to: Some(caller),
+                token_id,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens of type `token_id` from `from` to `to`.
+        #[ink(message)]
+        pub fn safe_transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            token_id: TokenId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<()> {
+            self.ensure_transfer_allowed(from, to)?;
+            self.perform_transfer(from, to, token_id, value)
+        }
+
+        /// Transfers `values` tokens of types `token_ids` from `from` to `to`.
+        #[ink(message)]
+        pub fn safe_batch_transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            token_ids: Vec<TokenId>,
+            values: Vec<Balance>,
+            _data: Vec<u8>,
+        ) -> Result<()> {
+            self.ensure_transfer_allowed(from, to)?;
+            if token_ids.is_empty() || token_ids.len() != values.len() {
+                return Err(Error::BatchTransferMismatch);
+            }
+            for (token_id, value) in token_ids.into_iter().zip(values) {
+                self.perform_transfer(from, to, token_id, value)?;
+            }
+            Ok(())
+        }
+
+        /// Returns the balance of `owner` for the token of type `token_id`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId, token_id: TokenId) -> Balance {
+            self.balances.get((owner, token_id)).unwrap_or(0)
+        }
+
+        /// Returns the balances of all `owners` for all tokens of types `token_ids`.
+        #[ink(message)]
+        pub fn balance_of_batch(&self, owners: Vec<AccountId>, token_ids: Vec<TokenId>) -> Vec<Balance> {
+            let mut output = Vec::new();
+            for owner in &owners {
+                for token_id in &token_ids {
+                    output.push(self.balance_of(*owner, *token_id));
+                }
+            }
+            output
+        }
+
+        /// Enables or disables `operator` to manage all of the caller's tokens.
+        #[ink(message)]
+        pub fn set_approval_for_all(&mut self, operator: AccountId, approved: bool) -> Result<()> {
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(Error::SelfApproval);
+            }
+            if approved {
+                self.approvals.insert((&caller, &operator), &());
+            } else {
+                self.approvals.remove((&caller, &operator));
+            }
+            self.env().emit_event(ApprovalForAll {
+                owner: caller,
+                operator,
+                approved,
+            });
+            Ok(())
+        }
+
+        /// Returns `true` if `operator` is approved to manage all of the `owner`'s tokens.
+        #[ink(message)]
+        pub fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+            self.approvals.contains((&owner, &operator))
+        }
+
+        /// Ensures that the caller is allowed to transfer tokens from `from` to `to`.
+        fn ensure_transfer_allowed(&self, from: AccountId, to: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != from && !self.is_approved_for_all(from, caller) {
+                return Err(Error::NotApproved);
+            }
+            if to == AccountId::from([0x0; 32]) {
+                return Err(Error::ZeroAddressTransfer);
+            }
+            Ok(())
+        }
+
+        /// Performs a single token transfer.
+        fn perform_transfer(&mut self, from: AccountId, to: AccountId, token_id: TokenId, value: Balance) -> Result<()> {
+            let from_balance = self.balance_of(from, token_id);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+            self.balances.insert((from, token_id), &(from_balance - value));
+            let to_balance = self.balance_of(to, token_id);
+            self.balances.insert((to, token_id), &(to_balance + value));
+            self.env().emit_event(TransferSingle {
+                operator: Some(self.env().caller()),
+                from: Some(from),
+                to: Some(to),
+                token_id,
+                value,
+            });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn create_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = MyContract::new();
+            let token_id = contract.create(100);
+            assert_eq!(contract.balance_of(accounts.alice, token_id), 100);
+            assert_eq!(
+                contract.safe_transfer_from(accounts.alice, accounts.bob, token_id, 10, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(contract.balance_of(accounts.bob, token_id), 10);
+        }
+    }
+}"#;
+
+/// Ref: <https://github.com/w3f/PSPs/blob/master/PSPs/psp-22.md>.
+pub const PSP22_PLAIN: &str = r#"#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+pub mod my_contract {
+    use ink::prelude::{string::String, vec::Vec};
+    use ink::storage::Mapping;
+
+    /// A simple PSP-22 contract.
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MyContract {
+        /// Total token supply.
+        total_supply: Balance,
+        /// Mapping from owner to number of owned tokens.
+        balances: Mapping<AccountId, Balance>,
+        /// Mapping of the token amount which an account is allowed to withdraw from another account.
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+    }
+
+    /// Event emitted when a token transfer occurs.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Event emitted when an approval occurs that `spender` is allowed to withdraw
+    /// up to the amount of `value` tokens from `owner`.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        amount: Balance,
+    }
+
+    /// The PSP-22 error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PSP22Error {
+        /// Custom error type for implementation-based errors.
+        Custom(String),
+        /// Returned when an account does not have enough tokens to complete the operation.
+        InsufficientBalance,
+        /// Returned if there is not enough allowance to complete the operation.
+        InsufficientAllowance,
+    }
+
+    impl MyContract {
+        /// Creates a new PSP-22 contract with the specified initial supply.
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            let mut balances = Mapping::default();
+            let caller = Self::env().caller();
+            balances.insert(caller, &total_supply);
+            Self::env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                value: total_supply,
+            });
+            Self {
+                total_supply,
+                balances,
+                allowances: Default::default(),
+            }
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`
+        /// with additional `data` in unspecified format.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance, _data: Vec<u8>) -> Result<(), PSP22Error> {
+            let from = self.env().caller();
+            self.transfer_from_to(&from, &to, value)
+        }
+
+        /// Transfers `value` tokens on the behalf of `from` to the account `to`
+        /// with additional `data` in unspecified format.
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+            self.transfer_from_to(&from, &to, value)?;
+            self.approve_from_to(from, caller, allowance - value);
+            Ok(())
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to the `value` amount.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            self.approve_from_to(owner, spender, value);
+            Ok(())
+        }
+
+        /// Atomically increases the allowance granted to `spender` by the caller.
+        #[ink(message)]
+        pub fn increase_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            let allowance = self.allowance(owner, spender);
+            self.approve_from_to(owner, spender, allowance.saturating_add(delta_value));
+            Ok(())
+        }
+
+        /// Atomically decreases the allowance granted to `spender` by the caller.
+        #[ink(message)]
+        pub fn decrease_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            let allowance = self.allowance(owner, spender);
+            if allowance < delta_value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+            self.approve_from_to(owner, spender, allowance - delta_value);
+            Ok(())
+        }
+
+        /// Sets the allowance of `spender` for tokens of `owner`.
+        fn approve_from_to(&mut self, owner: AccountId, spender: AccountId, amount: Balance) {
+            self.allowances.insert((&owner, &spender), &amount);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                amount,
+            });
+        }
+
+        /// Transfers `value` amount of tokens from account `from` to account `to`.
+        fn transfer_from_to(&mut self, from: &AccountId, to: &AccountId, value: Balance) -> Result<(), PSP22Error> {
+            let from_balance = self.balance_of(*from);
+            if from_balance < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(*to);
+            self.balances.insert(to, &(to_balance + value));
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: Some(*to),
+                value,
+            });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn transfer_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = MyContract::new(100);
+            assert_eq!(contract.transfer(accounts.bob, 10, Vec::new()), Ok(()));
+            assert_eq!(contract.balance_of(accounts.alice), 90);
+            assert_eq!(contract.balance_of(accounts.bob), 10);
+        }
+    }
+}"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.3.0/integration-tests/multisig/lib.rs>.
+pub const MULTISIG_PLAIN: &str = r#"#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+pub mod my_contract {
+    use ink::env::call::{build_call, ExecutionInput};
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    /// A transaction ID.
+    pub type TransactionId = u32;
+
+    /// A transaction that will be executed once it's confirmed by the required number of owners.
+    #[derive(Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(Debug, PartialEq, Eq, scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Transaction {
+        /// The `AccountId` of the contract that is called in this transaction.
+        pub callee: AccountId,
+        /// The selector bytes that identifies the function of the callee that should be called.
+        pub selector: [u8; 4],
+        /// The SCALE encoded parameters that are passed to the called function.
+        pub input: Vec<u8>,
+        /// The amount of chain balance that is transferred to the callee.
+        pub transferred_value: Balance,
+        /// Gas limit for the execution of the call.
+        pub gas_limit: u64,
+    }
+
+    /// The multisig error types.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        NotOwner,
+        TransactionNotFound,
+        AlreadyConfirmed,
+        NotConfirmed,
+        TransactionFailed,
+    }
+
+    /// Raw (i.e. already SCALE encoded) call input.
+    struct CallInput<'a>(&'a [u8]);
+
+    impl<'a> scale::Encode for CallInput<'a> {
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(self.0);
+        }
+    }
+
+    /// Emitted when an owner confirms a transaction.
+    #[ink(event)]
+    pub struct Confirmation {
+        #[ink(topic)]
+        transaction: TransactionId,
+        #[ink(topic)]
+        from: AccountId,
+    }
+
+    /// Emitted when a transaction was submitted.
+    #[ink(event)]
+    pub struct Submission {
+        #[ink(topic)]
+        transaction: TransactionId,
+    }
+
+    /// Emitted when a transaction was executed.
+    #[ink(event)]
+    pub struct Execution {
+        #[ink(topic)]
+        transaction: TransactionId,
+    }
+
+    /// A simple multisig contract.
+    #[ink(storage)]
+    pub struct MyContract {
+        /// The owners of the wallet.
+        owners: Vec<AccountId>,
+        /// The number of confirmations required to execute a transaction.
+        requirement: u32,
+        /// The submitted transactions.
+        transactions: Mapping<TransactionId, Transaction>,
+        /// The number of confirmations of each transaction.
+        confirmation_count: Mapping<TransactionId, u32>,
+        /// The confirmations of each transaction by owner.
+        confirmations: Mapping<(TransactionId, AccountId), ()>,
+        /// The ID of the next submitted transaction.
+        next_id: TransactionId,
+    }
+
+    impl MyContract {
+        /// Creates a new multisig wallet with the given owners and number of required confirmations.
+        #[ink(constructor)]
+        pub fn new(requirement: u32, mut owners: Vec<AccountId>) -> Self {
+            owners.sort_unstable();
+            owners.dedup();
+            assert!(
+                0 < requirement && requirement as usize <= owners.len(),
+                "invalid requirement"
+            );
+            Self {
+                owners,
+                requirement,
+                transactions: Mapping::default(),
+                confirmation_count: Mapping::default(),
+                confirmations: Mapping::default(),
+                next_id: 0,
+            }
+        }
+
+        /// Submits a transaction and confirms it on behalf of the caller, returning its ID.
+        #[ink(message)]
+        pub fn submit_transaction(&mut self, transaction: Transaction) -> Result<TransactionId, Error> {
+            self.ensure_owner()?;
+            let id = self.next_id;
+            self.next_id += 1;
+            self.transactions.insert(id, &transaction);
+            self.env().emit_event(Submission { transaction: id });
+            self.confirm_transaction(id)?;
+            Ok(id)
+        }
+
+        /// Confirms a transaction on behalf of the caller.
+        #[ink(message)]
+        pub fn confirm_transaction(&mut self, id: TransactionId) -> Result<(), Error> {
+            let caller = self.ensure_owner()?;
+            if !self.transactions.contains(id) {
+                return Err(Error::TransactionNotFound);
+            }
+            if self.confirmations.contains((id, caller)) {
+                return Err(Error::AlreadyConfirmed);
+            }
+            self.confirmations.insert((id, caller), &());
+            let count = self.confirmation_count.get(id).unwrap_or_default() + 1;
+            self.confirmation_count.insert(id, &count);
+            self.env().emit_event(Confirmation {
+                transaction: id,
+                from: caller,
+            });
+            Ok(())
+        }
+
+        /// Revokes the caller's confirmation of a transaction.
+        #[ink(message)]
+        pub fn revoke_confirmation(&mut self, id: TransactionId) -> Result<(), Error> {
+            let caller = self.ensure_owner()?;
+            if !self.confirmations.contains((id, caller)) {
+                return Err(Error::NotConfirmed);
+            }
+            self.confirmations.remove((id, caller));
+            let count = self.confirmation_count.get(id).unwrap_or_default();
+            self.confirmation_count.insert(id, &count.saturating_sub(1));
+            Ok(())
+        }
+
+        /// Executes a transaction that has been confirmed by the required number of owners.
+        #[ink(message, payable)]
+        pub fn invoke_transaction(&mut self, id: TransactionId) -> Result<(), Error> {
+            let count = self.confirmation_count.get(id).unwrap_or_default();
+            if count < self.requirement {
+                return Err(Error::NotConfirmed);
+            }
+            let transaction = self.transactions.take(id).ok_or(Error::TransactionNotFound)?;
+            self.confirmation_count.remove(id);
+            for owner in &self.owners {
+                self.confirmations.remove((id, *owner));
+            }
+            let result = build_call::<<Self as ::ink::env::ContractEnv>::Env>()
+                .call(transaction.callee)
+                .gas_limit(transaction.gas_limit)
+                .transferred_value(transaction.transferred_value)
+                .exec_input(
+                    ExecutionInput::new(transaction.selector.into())
+                        .push_arg(CallInput(&transaction.input)),
+                )
+                .returns::<()>()
+                .try_invoke();
+            self.env().emit_event(Execution { transaction: id });
+            match result {
+                Ok(Ok(())) => Ok(()),
+                _ => Err(Error::TransactionFailed),
+            }
+        }
+
+        /// Returns the owners of the wallet.
+        #[ink(message)]
+        pub fn owners(&self) -> Vec<AccountId> {
+            self.owners.clone()
+        }
+
+        /// Returns the number of confirmations of a transaction.
+        #[ink(message)]
+        pub fn confirmation_count(&self, id: TransactionId) -> u32 {
+            self.confirmation_count.get(id).unwrap_or_default()
+        }
+
+        /// Returns the caller if it's an owner of the wallet, otherwise an error.
+        fn ensure_owner(&self) -> Result<AccountId, Error> {
+            let caller = self.env().caller();
+            if self.owners.contains(&caller) {
+                Ok(caller)
+            } else {
+                Err(Error::NotOwner)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn submit_transaction_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = MyContract::new(2, ink::prelude::vec![accounts.alice, accounts.bob]);
+            let id = contract
+                .submit_transaction(Transaction {
+                    callee: accounts.django,
+                    selector: [0x0; 4],
+                    input: Vec::new(),
+                    transferred_value: 0,
+                    gas_limit: 0,
+                })
+                .unwrap();
+            assert_eq!(contract.confirmation_count(id), 1);
+        }
+    }
+}"#;
+
+/// Ref: <https://github.com/paritytech/ink/blob/v4.3.0/integration-tests/dns/lib.rs>.
+pub const DNS_PLAIN: &str = r#"#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+pub mod my_contract {
+    use ink::storage::Mapping;
+
+    /// Emitted whenever a new name is registered.
+    #[ink(event)]
+    pub struct Register {
+        #[ink(topic)]
+        name: Hash,
+        #[ink(topic)]
+        from: AccountId,
+    }
+
+    /// Emitted whenever an address changes.
+    #[ink(event)]
+    pub struct SetAddress {
+        #[ink(topic)]
+        name: Hash,
+        from: AccountId,
+        #[ink(topic)]
+        old_address: Option<AccountId>,
+        #[ink(topic)]
+        new_address: AccountId,
+    }
+
+    /// Emitted whenever a name is being transferred.
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        name: Hash,
+        from: AccountId,
+        #[ink(topic)]
+        old_owner: Option<AccountId>,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    /// Domain name service contract inspired by
+    /// [this blog post](https://medium.com/@chainx_org/secure-and-decentralized-polkadot-domain-name-system-e06c35c2a48d).
+    #[ink(storage)]
+    pub struct MyContract {
+        /// A hashmap to store all name to addresses mapping.
+        name_to_address: Mapping<Hash, AccountId>,
+        /// A hashmap to store all name to owners mapping.
+        name_to_owner: Mapping<Hash, AccountId>,
+        /// The default address.
+        default_address: AccountId,
+    }
+
+    /// Errors that can occur upon calling this contract.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the name already exists upon registration.
+        NameAlreadyExists,
+        /// Returned if caller is not owner while required to.
+        CallerIsNotOwner,
+    }
+
+    /// Type alias for the contract's result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    impl MyContract {
+        /// Creates a new domain name service contract.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                name_to_address: Mapping::default(),
+                name_to_owner: Mapping::default(),
+                default_address: AccountId::from([0x0; 32]),
+            }
+        }
+
+        /// Register specific name with caller as owner.
+        #[ink(message)]
+        pub fn register(&mut self, name: Hash) -> Result<()> {
+            let caller = self.env().caller();
+            if self.name_to_owner.contains(name) {
+                return Err(Error::NameAlreadyExists);
+            }
+            self.name_to_owner.insert(name, &caller);
+            self.env().emit_event(Register { name, from: caller });
+            Ok(())
+        }
+
+        /// Set address for specific name.
+        #[ink(message)]
+        pub fn set_address(&mut self, name: Hash, new_address: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.get_owner_or_default(name) {
+                return Err(Error::CallerIsNotOwner);
+            }
+            let old_address = self.name_to_address.get(name);
+            self.name_to_address.insert(name, &new_address);
+            self.env().emit_event(SetAddress {
+                name,
+                from: caller,
+                old_address,
+                new_address,
+            });
+            Ok(())
+        }
+
+        /// Transfer owner to another address.
+        #[ink(message)]
+        pub fn transfer(&mut self, name: Hash, to: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.get_owner_or_default(name) {
+                return Err(Error::CallerIsNotOwner);
+            }
+            let old_owner = self.name_to_owner.get(name);
+            self.name_to_owner.insert(name, &to);
+            self.env().emit_event(Transfer {
+                name,
+                from: caller,
+                old_owner,
+                new_owner: to,
+            });
+            Ok(())
+        }
+
+        /// Get address for specific name.
+        #[ink(message)]
+        pub fn get_address(&self, name: Hash) -> AccountId {
+            self.name_to_address.get(name).unwrap_or(self.default_address)
+        }
+
+        /// Returns the owner given the hash or the default address.
+        #[ink(message)]
+        pub fn get_owner(&self, name: Hash) -> AccountId {
+            self.get_owner_or_default(name)
+        }
+
+        /// Returns the owner given the hash or the default address.
+        fn get_owner_or_default(&self, name: Hash) -> AccountId {
+            self.name_to_owner.get(name).unwrap_or(self.default_address)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn register_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let name = Hash::from([0x99; 32]);
+            let mut contract = MyContract::new();
+            assert_eq!(contract.register(name), Ok(()));
+            assert_eq!(contract.register(name), Err(Error::NameAlreadyExists));
+            assert_eq!(contract.get_owner(name), accounts.alice);
+        }
+    }
+}"#;
@@ -0,0 +1,344 @@
+//! A stable (i.e. versioned) JSON representation of ink! analyzer diagnostics
+//! (e.g. for external tools and CI integrations).
+//!
+//! Unlike the `Serialize` implementations of analysis result types (which mirror their Rust definitions),
+//! the types in this module follow a versioned schema (see [`SCHEMA_VERSION`] and [`SCHEMA`]),
+//! use 1-based lines and columns (i.e. in Unicode code points) for positions,
+//! and summarize quickfixes (i.e. without their text edits).
+//!
+//! # Example
+//! ```
+//! use ink_analyzer::json::Report;
+//!
+//! let code = "#[ink::contract]\nmod my_contract {}";
+//! let diagnostics = ink_analyzer::Analysis::new(code).diagnostics();
+//! let report = Report::new([("src/lib.rs", code, diagnostics.as_slice())]);
+//! let output = serde_json::to_string(&report).unwrap();
+//! assert!(output.starts_with(r#"{"schemaVersion":1,"#));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{LineIndex, PositionEncoding, ProjectReport, Severity, TextSize};
+
+/// Version of the JSON report schema.
+///
+/// The version is only incremented for breaking changes (e.g. removed or renamed fields),
+/// new optional fields may be added without a version change.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// JSON Schema (draft 2020-12) describing a JSON [`Report`].
+pub const SCHEMA: &str = include_str!("../schemas/report.schema.json");
+
+/// A JSON report of ink! analyzer diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    /// Version of the report schema (see [`SCHEMA_VERSION`]).
+    pub schema_version: u32,
+    /// The tool that produced the report.
+    pub tool: Tool,
+    /// Diagnostics for each analyzed file.
+    pub files: Vec<File>,
+    /// Diagnostic counts across all files.
+    pub summary: Summary,
+}
+
+/// The tool that produced a [`Report`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tool {
+    /// Name of the tool (i.e. `ink-analyzer`).
+    pub name: String,
+    /// Version of the tool.
+    pub version: String,
+}
+
+/// Diagnostics for a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct File {
+    /// Path of the file.
+    pub path: String,
+    /// Diagnostics for the file.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// A diagnostic error or warning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    /// Stable code of the check that produced the diagnostic (e.g. `E0017`) (if any).
+    pub code: Option<String>,
+    /// Name of the check that produced the diagnostic (e.g. `missing-storage`) (if any).
+    pub code_name: Option<String>,
+    /// Severity of the diagnostic (i.e. `error` or `warning`).
+    pub severity: String,
+    /// Error or warning message.
+    pub message: String,
+    /// Range of the diagnostic.
+    pub range: Range,
+    /// Quickfixes for the diagnostic (if any).
+    pub quickfixes: Vec<Quickfix>,
+}
+
+/// A text range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Range {
+    /// Start position.
+    pub start: Position,
+    /// End position (exclusive).
+    pub end: Position,
+}
+
+/// A position in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    /// 1-based line number.
+    pub line: u32,
+    /// 1-based column number (i.e. in Unicode code points).
+    pub column: u32,
+    /// 0-based byte offset.
+    pub offset: u32,
+}
+
+/// A quickfix summary (i.e. without its text edits).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Quickfix {
+    /// Label of the quickfix.
+    pub label: String,
+    /// Whether the quickfix is the preferred fix for the diagnostic
+    /// (i.e. safe to auto-apply).
+    pub is_preferred: bool,
+}
+
+/// Diagnostic counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    /// Number of analyzed files.
+    pub files: usize,
+    /// Number of errors.
+    pub errors: usize,
+    /// Number of warnings.
+    pub warnings: usize,
+}
+
+impl Report {
+    /// Creates a JSON report from the path, contents and diagnostics of each file
+    /// (the contents are used to convert diagnostic ranges to lines and columns).
+    pub fn new<'a>(
+        files: impl IntoIterator<Item = (&'a str, &'a str, &'a [crate::Diagnostic])>,
+    ) -> Self {
+        let files: Vec<File> = files
+            .into_iter()
+            .map(|(path, content, diagnostics)| {
+                let line_index = LineIndex::new(content);
+                File {
+                    path: path.to_string(),
+                    diagnostics: diagnostics
+                        .iter()
+                        .map(|diagnostic| self::diagnostic(&line_index, diagnostic))
+                        .collect(),
+                }
+            })
+            .collect();
+        let count = |severity: &str| {
+            files
+                .iter()
+                .flat_map(|file| &file.diagnostics)
+                .filter(|diagnostic| diagnostic.severity == severity)
+                .count()
+        };
+        let summary = Summary {
+            files: files.len(),
+            errors: count("error"),
+            warnings: count("warning"),
+        };
+
+        Self {
+            schema_version: SCHEMA_VERSION,
+            tool: Tool {
+                name: "ink-analyzer".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            files,
+            summary,
+        }
+    }
+
+    /// Creates a JSON report from a project report (see [`crate::analyze_project`]).
+    ///
+    /// File paths are relative to the project root (if possible).
+    pub fn from_project(report: &ProjectReport) -> Self {
+        let paths: Vec<String> = report
+            .files
+            .iter()
+            .map(|file| {
+                file.path
+                    .strip_prefix(&report.root)
+                    .unwrap_or(&file.path)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        Self::new(report.files.iter().zip(&paths).map(|(file, path)| {
+            (
+                path.as_str(),
+                file.content.as_str(),
+                file.diagnostics.as_slice(),
+            )
+        }))
+    }
+}
+
+/// Returns the JSON representation of a diagnostic.
+fn diagnostic(line_index: &LineIndex, diagnostic: &crate::Diagnostic) -> Diagnostic {
+    Diagnostic {
+        code: diagnostic.code.map(|code| format!("E{:04}", code.number())),
+        code_name: diagnostic.code.map(|code| code.name().to_string()),
+        severity: match diagnostic.severity {
+            Severity::Error => "error",
+            // Deprecations are reported as warnings (i.e. the schema's severities are stable).
+            Severity::Warning | Severity::Deprecation => "warning",
+        }
+        .to_string(),
+        message: diagnostic.message.clone(),
+        range: Range {
+            start: position(line_index, diagnostic.range.start()),
+            end: position(line_index, diagnostic.range.end()),
+        },
+        quickfixes: diagnostic
+            .quickfixes
+            .iter()
+            .flatten()
+            .map(|action| Quickfix {
+                label: action.label.clone(),
+                is_preferred: action.is_preferred,
+            })
+            .collect(),
+    }
+}
+
+/// Returns the position (i.e. 1-based line and column in Unicode code points) for the offset.
+fn position(line_index: &LineIndex, offset: TextSize) -> Position {
+    let (line, column) = line_index
+        .line_col(offset, PositionEncoding::Utf32)
+        .map_or((1, 1), |line_col| (line_col.line + 1, line_col.col + 1));
+    Position {
+        line,
+        column,
+        offset: offset.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Analysis;
+
+    #[test]
+    fn report_works() {
+        let code = "#[ink::contract]\nmod my_contract {\n}";
+        let diagnostics = Analysis::new(code).diagnostics();
+        let report = Report::new([("src/lib.rs", code, diagnostics.as_slice())]);
+
+        assert_eq!(report.schema_version, SCHEMA_VERSION);
+        assert_eq!(report.tool.name, "ink-analyzer");
+        assert_eq!(report.files.len(), 1);
+        assert_eq!(report.files[0].path, "src/lib.rs");
+        assert_eq!(report.files[0].diagnostics.len(), diagnostics.len());
+        assert_eq!(
+            report.summary,
+            Summary {
+                files: 1,
+                errors: diagnostics.len(),
+                warnings: 0,
+            }
+        );
+
+        let missing_storage = report.files[0]
+            .diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.code.as_deref() == Some("E0017"))
+            .unwrap();
+        assert_eq!(
+            missing_storage.code_name.as_deref(),
+            Some("missing-storage")
+        );
+        assert_eq!(missing_storage.severity, "error");
+        assert!(!missing_storage.quickfixes.is_empty());
+
+        // Serializes to camel case keys and roundtrips.
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(value["schemaVersion"], SCHEMA_VERSION);
+        assert!(value["files"][0]["diagnostics"][0]["range"]["start"]["line"].is_u64());
+        assert!(value["files"][0]["diagnostics"][0]["codeName"].is_string());
+        assert_eq!(serde_json::from_value::<Report>(value).unwrap(), report);
+    }
+
+    #[test]
+    fn position_works() {
+        let line_index = LineIndex::new("fn a() {}\nfn ä() {}");
+        for (offset, expected_position) in [
+            (0, (1, 1)),
+            (9, (1, 10)),
+            (10, (2, 1)),
+            // Columns are in Unicode code points (i.e. `ä` is 2 bytes).
+            (16, (2, 6)),
+        ] {
+            assert_eq!(
+                position(&line_index, TextSize::from(offset)),
+                Position {
+                    line: expected_position.0,
+                    column: expected_position.1,
+                    offset,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn schema_works() {
+        let schema: serde_json::Value = serde_json::from_str(SCHEMA).unwrap();
+        assert_eq!(
+            schema["properties"]["schemaVersion"]["const"],
+            SCHEMA_VERSION
+        );
+
+        // All report fields are described by the schema.
+        let code = "#[ink::contract]\nmod my_contract {\n}";
+        let diagnostics = Analysis::new(code).diagnostics();
+        let report =
+            serde_json::to_value(Report::new([("lib.rs", code, diagnostics.as_slice())])).unwrap();
+        fn assert_described(
+            value: &serde_json::Value,
+            schema: &serde_json::Value,
+            root: &serde_json::Value,
+        ) {
+            let schema = match schema["$ref"].as_str() {
+                Some(reference) => &root["$defs"][reference.trim_start_matches("#/$defs/")],
+                None => schema,
+            };
+            match value {
+                serde_json::Value::Object(object) => {
+                    for (key, value) in object {
+                        let property = &schema["properties"][key];
+                        assert!(!property.is_null(), "undescribed property: {key}");
+                        assert_described(value, property, root);
+                    }
+                }
+                serde_json::Value::Array(items) => {
+                    for item in items {
+                        assert_described(item, &schema["items"], root);
+                    }
+                }
+                _ => (),
+            }
+        }
+        assert_described(&report, &schema, &schema);
+    }
+}
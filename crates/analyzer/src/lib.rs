@@ -1,6 +1,14 @@
 //! # ink! Analyzer
 //! A library for semantic analysis of [ink!](https://use.ink/) smart contract code.
 //!
+//! # Robustness
+//! Analysis never panics on malformed input (i.e. incomplete or invalid Rust/ink! code,
+//! which is the norm while code is being edited).
+//! Analysis methods also return empty results (instead of panicking) for offsets and text ranges
+//! that aren't valid positions in the code (see [`Analysis::validate_range`] and [`AnalysisError`]).
+//! This guarantee is covered by integration tests for truncated (i.e. incomplete) code,
+//! and can be further exercised with the fuzz targets in the `fuzz` directory in the project root.
+//!
 //! # Example
 //! Analyzing ink! smart contract code.
 //!
@@ -65,15 +73,42 @@
 
 pub use self::{
     analysis::{
-        Action, ActionKind, Analysis, Completion, Diagnostic, Hover, InlayHint, Severity,
-        SignatureHelp, TextEdit,
+        child_module_candidates, content_hash, merge_edits, parent_module_candidates,
+        resolve_completion, Action, ActionBuilder, ActionKind, Analysis, AnalysisError, ArgInfo,
+        ArgMetadata, BuildError, CachedDiagnostics, CallableInfo, CallableMetadata, Completion,
+        CompletionKind, ContractInfo, ContractMetadata, Diagnostic, DiagnosticBuilder,
+        DiagnosticCode, DiagnosticsCache, DiagnosticsConfig, DocumentEdit, EditConflict,
+        EventFieldInfo, EventInfo, FileIndex, FoldingRange, FoldingRangeKind, Hover, HoverConfig,
+        IndentStyle, IndexedSelector, InlayHint, InlayHintsConfig, MacroDefinition, MacroRegistry,
+        MacroScope, RelatedInformation, Severity, SignatureHelp, Symbol, SymbolIndex, SymbolKind,
+        TextEdit, UnresolvedAction, Version,
+    },
+    codegen::{
+        markdown_docs, new_project, new_project_with_indent_style, new_project_with_template,
+        Error, Project, ProjectFile, ResourceOp, Template,
     },
-    codegen::{new_project, Error, Project, ProjectFile},
+    line_index::{LineCol, LineIndex, PositionEncoding},
+    project::{analyze_project, FileReport, ProjectReport},
+    sarif::{sarif, SarifFile},
+    self_check::{self_check, SelfCheckMismatch, SelfCheckReport},
 };
 pub use ink_analyzer_ir::syntax::{TextRange, TextSize};
 
+/// The version of ink! analyzer (e.g. for invalidating persisted indexes computed by other versions).
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 mod analysis;
 mod codegen;
+#[cfg(feature = "serde")]
+pub mod json;
+mod line_index;
+#[cfg(feature = "lsp")]
+pub mod lsp;
+mod project;
+mod sarif;
+mod self_check;
+#[cfg(feature = "serde")]
+mod serde_utils;
 mod utils;
 
 mod test_utils;
@@ -0,0 +1,212 @@
+//! Conversion between offsets and lines/columns
+//! (e.g. for non-LSP consumers like CLIs and web UIs that need to display positions).
+
+use std::collections::HashMap;
+
+use crate::TextSize;
+
+/// An index of line starts (and non-ASCII characters) for converting
+/// between offsets (i.e. [`TextSize`]) and zero-based lines and columns (i.e. [`LineCol`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Offsets of the start of each line.
+    line_starts: Vec<TextSize>,
+    /// Non-ASCII characters on each line (i.e. their line-relative UTF-8 offset and the character).
+    wide_chars: HashMap<u32, Vec<(TextSize, char)>>,
+    /// Length of the text.
+    len: TextSize,
+}
+
+/// A zero-based line and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineCol {
+    /// Zero-based line number.
+    pub line: u32,
+    /// Zero-based column (i.e. in code units of the encoding used for the conversion).
+    pub col: u32,
+}
+
+/// The encoding for columns (i.e. the unit in which columns are measured).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PositionEncoding {
+    /// Columns are UTF-8 code units (i.e. bytes).
+    Utf8,
+    /// Columns are UTF-16 code units (e.g. the default for LSP and JavaScript strings).
+    Utf16,
+    /// Columns are Unicode code points (i.e. characters).
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Returns the length of the character in code units of the encoding.
+    fn char_len(self, char: char) -> u32 {
+        match self {
+            PositionEncoding::Utf8 => char.len_utf8() as u32,
+            PositionEncoding::Utf16 => char.len_utf16() as u32,
+            PositionEncoding::Utf32 => 1,
+        }
+    }
+}
+
+impl LineIndex {
+    /// Creates a line index for the text.
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![TextSize::from(0)];
+        let mut wide_chars: HashMap<u32, Vec<(TextSize, char)>> = HashMap::new();
+        for (offset, char) in text.char_indices() {
+            let offset = TextSize::from(offset as u32);
+            if char == '\n' {
+                line_starts.push(offset + TextSize::of(char));
+            } else if !char.is_ascii() {
+                let line = line_starts.len() as u32 - 1;
+                let line_start = line_starts[line as usize];
+                wide_chars
+                    .entry(line)
+                    .or_default()
+                    .push((offset - line_start, char));
+            }
+        }
+        Self {
+            line_starts,
+            wide_chars,
+            len: TextSize::of(text),
+        }
+    }
+
+    /// Returns the zero-based line and column (in the given encoding) for the offset
+    /// (if the offset is within the text).
+    pub fn line_col(&self, offset: TextSize, encoding: PositionEncoding) -> Option<LineCol> {
+        if offset > self.len {
+            return None;
+        }
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let utf8_col = u32::from(offset - self.line_starts[line]);
+        let col = self
+            .wide_chars
+            .get(&(line as u32))
+            .map_or(utf8_col, |wide_chars| {
+                wide_chars
+                    .iter()
+                    .take_while(|(start, char)| {
+                        u32::from(*start) + PositionEncoding::Utf8.char_len(*char) <= utf8_col
+                    })
+                    .fold(utf8_col, |col, (_, char)| {
+                        col - PositionEncoding::Utf8.char_len(*char) + encoding.char_len(*char)
+                    })
+            });
+        Some(LineCol {
+            line: line as u32,
+            col,
+        })
+    }
+
+    /// Returns the offset for the zero-based line and column (in the given encoding)
+    /// (if the line and column are within the text).
+    pub fn offset(&self, line_col: LineCol, encoding: PositionEncoding) -> Option<TextSize> {
+        let line_start = *self.line_starts.get(line_col.line as usize)?;
+        let line_end = self
+            .line_starts
+            .get(line_col.line as usize + 1)
+            .map_or(self.len, |next_line_start| {
+                *next_line_start - TextSize::from(1)
+            });
+        // Difference between UTF-8 code units and encoding code units for wide characters before the column.
+        let mut delta = 0;
+        for (start, char) in self
+            .wide_chars
+            .get(&line_col.line)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+        {
+            let wide_start = u32::from(*start) - delta;
+            if wide_start >= line_col.col {
+                break;
+            }
+            let wide_len = encoding.char_len(*char);
+            // Columns within a wide character are invalid.
+            if wide_start + wide_len > line_col.col {
+                return None;
+            }
+            delta += PositionEncoding::Utf8.char_len(*char) - wide_len;
+        }
+        let offset = line_start + TextSize::from(line_col.col + delta);
+        (offset <= line_end).then_some(offset)
+    }
+
+    /// Returns the number of lines in the text.
+    pub fn len(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns true if the line index is empty (i.e. never true, because texts have at least one line).
+    pub fn is_empty(&self) -> bool {
+        self.line_starts.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_index_works() {
+        // `→` is 3 UTF-8 code units and 1 UTF-16 code unit,
+        // `𝕊` is 4 UTF-8 code units and 2 UTF-16 code units.
+        let text = "a\nb→c𝕊d\n\nend";
+        let index = LineIndex::new(text);
+        assert_eq!(index.len(), 4);
+
+        for (offset, utf8_col, utf16_col, utf32_col, line) in [
+            (0, 0, 0, 0, 0),
+            (1, 1, 1, 1, 0),
+            (2, 0, 0, 0, 1),
+            (3, 1, 1, 1, 1),
+            (6, 4, 2, 2, 1),
+            (7, 5, 3, 3, 1),
+            (11, 9, 5, 4, 1),
+            (12, 10, 6, 5, 1),
+            (13, 0, 0, 0, 2),
+            (14, 0, 0, 0, 3),
+            (17, 3, 3, 3, 3),
+        ] {
+            let offset = TextSize::from(offset);
+            for (encoding, col) in [
+                (PositionEncoding::Utf8, utf8_col),
+                (PositionEncoding::Utf16, utf16_col),
+                (PositionEncoding::Utf32, utf32_col),
+            ] {
+                let line_col = LineCol { line, col };
+                // Offset to line and column.
+                assert_eq!(
+                    index.line_col(offset, encoding),
+                    Some(line_col),
+                    "offset: {offset:?}, encoding: {encoding:?}"
+                );
+                // Line and column to offset.
+                assert_eq!(
+                    index.offset(line_col, encoding),
+                    Some(offset),
+                    "line_col: {line_col:?}, encoding: {encoding:?}"
+                );
+            }
+        }
+
+        // Offsets past the end of the text are invalid.
+        assert_eq!(
+            index.line_col(TextSize::from(18), PositionEncoding::Utf8),
+            None
+        );
+        for (line_col, encoding) in [
+            // Lines past the end of the text.
+            (LineCol { line: 4, col: 0 }, PositionEncoding::Utf8),
+            // Columns past the end of the line.
+            (LineCol { line: 0, col: 2 }, PositionEncoding::Utf8),
+            (LineCol { line: 3, col: 4 }, PositionEncoding::Utf16),
+            // Columns within a wide character.
+            (LineCol { line: 1, col: 2 }, PositionEncoding::Utf8),
+            (LineCol { line: 1, col: 4 }, PositionEncoding::Utf16),
+        ] {
+            assert_eq!(index.offset(line_col, encoding), None, "{line_col:?}");
+        }
+    }
+}
@@ -0,0 +1,484 @@
+//! Conversions between ink! analyzer and [LSP](https://microsoft.github.io/language-server-protocol/) types
+//! (i.e. [`lsp_types`]) for LSP servers.
+
+use std::collections::HashMap;
+
+use crate::{
+    Action, ActionKind, Completion, CompletionKind, Diagnostic, Hover, InlayHint, LineCol,
+    LineIndex, PositionEncoding, Severity, TextEdit, TextRange, TextSize,
+};
+
+/// LSP code action kind for "fix all" source actions (see [`ActionKind::SourceFixAll`])
+/// (i.e. a sub-kind of `source.fixAll`, so that clients can request ink! analyzer's "fix all" action specifically).
+pub const FIX_ALL_CODE_ACTION_KIND: lsp_types::CodeActionKind =
+    lsp_types::CodeActionKind::new("source.fixAll.inkAnalyzer");
+
+/// Context for converting between ink! analyzer and LSP types for a document
+/// (i.e. the document's URI, its line index and the negotiated position encoding).
+#[derive(Debug, Clone)]
+pub struct LspContext {
+    uri: lsp_types::Url,
+    line_index: LineIndex,
+    encoding: PositionEncoding,
+    snippet_support: bool,
+    /// Contexts for other documents (e.g. `Cargo.toml` files) that actions may edit.
+    documents: HashMap<lsp_types::Url, LspContext>,
+}
+
+impl LspContext {
+    /// Creates a conversion context for the document with the given URI and text.
+    pub fn new(uri: lsp_types::Url, text: &str, encoding: PositionEncoding) -> Self {
+        Self {
+            uri,
+            line_index: LineIndex::new(text),
+            encoding,
+            snippet_support: false,
+            documents: HashMap::new(),
+        }
+    }
+
+    /// Adds another document (e.g. a `Cargo.toml` file) whose text edits (if any)
+    /// should be included when converting actions, see [`Action`] conversions.
+    pub fn with_document(mut self, uri: lsp_types::Url, text: &str) -> Self {
+        let context = LspContext::new(uri.clone(), text, self.encoding);
+        self.documents.insert(uri, context);
+        self
+    }
+
+    /// Sets whether the client supports snippets (i.e. for completion text edits).
+    pub fn with_snippet_support(mut self, snippet_support: bool) -> Self {
+        self.snippet_support = snippet_support;
+        self
+    }
+
+    /// Returns the URI of the document.
+    pub fn uri(&self) -> &lsp_types::Url {
+        &self.uri
+    }
+}
+
+/// Converts ink! analyzer types to LSP types.
+pub trait ToLsp {
+    /// The LSP type.
+    type Output;
+
+    /// Converts to the LSP type (returns `None` if any text range is outside the document).
+    fn to_lsp(&self, context: &LspContext) -> Option<Self::Output>;
+}
+
+/// Converts LSP types to ink! analyzer types.
+pub trait FromLsp: Sized {
+    /// The LSP type.
+    type Input;
+
+    /// Converts from the LSP type (returns `None` if any position is outside the document).
+    fn from_lsp(value: &Self::Input, context: &LspContext) -> Option<Self>;
+}
+
+impl From<PositionEncoding> for lsp_types::PositionEncodingKind {
+    fn from(encoding: PositionEncoding) -> Self {
+        match encoding {
+            PositionEncoding::Utf8 => lsp_types::PositionEncodingKind::UTF8,
+            PositionEncoding::Utf16 => lsp_types::PositionEncodingKind::UTF16,
+            PositionEncoding::Utf32 => lsp_types::PositionEncodingKind::UTF32,
+        }
+    }
+}
+
+impl TryFrom<lsp_types::PositionEncodingKind> for PositionEncoding {
+    /// The unsupported LSP position encoding kind.
+    type Error = lsp_types::PositionEncodingKind;
+
+    fn try_from(kind: lsp_types::PositionEncodingKind) -> Result<Self, Self::Error> {
+        if kind == lsp_types::PositionEncodingKind::UTF8 {
+            Ok(PositionEncoding::Utf8)
+        } else if kind == lsp_types::PositionEncodingKind::UTF16 {
+            Ok(PositionEncoding::Utf16)
+        } else if kind == lsp_types::PositionEncodingKind::UTF32 {
+            Ok(PositionEncoding::Utf32)
+        } else {
+            Err(kind)
+        }
+    }
+}
+
+impl ToLsp for TextSize {
+    type Output = lsp_types::Position;
+
+    fn to_lsp(&self, context: &LspContext) -> Option<Self::Output> {
+        context
+            .line_index
+            .line_col(*self, context.encoding)
+            .map(|line_col| lsp_types::Position::new(line_col.line, line_col.col))
+    }
+}
+
+impl FromLsp for TextSize {
+    type Input = lsp_types::Position;
+
+    fn from_lsp(value: &Self::Input, context: &LspContext) -> Option<Self> {
+        context.line_index.offset(
+            LineCol {
+                line: value.line,
+                col: value.character,
+            },
+            context.encoding,
+        )
+    }
+}
+
+impl ToLsp for TextRange {
+    type Output = lsp_types::Range;
+
+    fn to_lsp(&self, context: &LspContext) -> Option<Self::Output> {
+        self.start()
+            .to_lsp(context)
+            .zip(self.end().to_lsp(context))
+            .map(|(start, end)| lsp_types::Range::new(start, end))
+    }
+}
+
+impl FromLsp for TextRange {
+    type Input = lsp_types::Range;
+
+    fn from_lsp(value: &Self::Input, context: &LspContext) -> Option<Self> {
+        TextSize::from_lsp(&value.start, context)
+            .zip(TextSize::from_lsp(&value.end, context))
+            .and_then(|(start, end)| (start <= end).then(|| TextRange::new(start, end)))
+    }
+}
+
+impl ToLsp for TextEdit {
+    type Output = lsp_types::TextEdit;
+
+    /// Converts to an LSP text edit (snippets are rendered as plain text).
+    fn to_lsp(&self, context: &LspContext) -> Option<Self::Output> {
+        self.range.to_lsp(context).map(|range| lsp_types::TextEdit {
+            range,
+            new_text: self.plain_text().into_owned(),
+        })
+    }
+}
+
+impl ToLsp for Diagnostic {
+    type Output = lsp_types::Diagnostic;
+
+    /// Converts to an LSP diagnostic (quickfixes are NOT included, see [`Action`] conversions).
+    fn to_lsp(&self, context: &LspContext) -> Option<Self::Output> {
+        self.range
+            .to_lsp(context)
+            .map(|range| lsp_types::Diagnostic {
+                range,
+                message: self.message.clone(),
+                severity: Some(match self.severity {
+                    Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+                    Severity::Warning | Severity::Deprecation => {
+                        lsp_types::DiagnosticSeverity::WARNING
+                    }
+                }),
+                code: self
+                    .code
+                    .map(|code| lsp_types::NumberOrString::String(code.to_string())),
+                code_description: self.code.and_then(|code| {
+                    lsp_types::Url::parse(&code.code_description())
+                        .ok()
+                        .map(|href| lsp_types::CodeDescription { href })
+                }),
+                // Related locations are always in the same document as the diagnostic.
+                related_information: self
+                    .related_information
+                    .as_ref()
+                    .map(|related_information| {
+                        related_information
+                            .iter()
+                            .filter_map(|info| {
+                                info.range.to_lsp(context).map(|range| {
+                                    lsp_types::DiagnosticRelatedInformation {
+                                        location: lsp_types::Location {
+                                            uri: context.uri.clone(),
+                                            range,
+                                        },
+                                        message: info.message.clone(),
+                                    }
+                                })
+                            })
+                            .collect()
+                    }),
+                tags: (self.severity == Severity::Deprecation)
+                    .then(|| vec![lsp_types::DiagnosticTag::DEPRECATED]),
+                ..Default::default()
+            })
+    }
+}
+
+impl ToLsp for Completion {
+    type Output = lsp_types::CompletionItem;
+
+    /// Converts to an LSP completion item
+    /// (snippets are downgraded to plain text unless the context has snippet support).
+    fn to_lsp(&self, context: &LspContext) -> Option<Self::Output> {
+        self.range.to_lsp(context).map(|range| {
+            let is_snippet = context.snippet_support && self.edit.snippet.is_some();
+            lsp_types::CompletionItem {
+                label: self.label.clone(),
+                kind: Some(self.kind.into()),
+                detail: self.detail.clone(),
+                documentation: self.documentation.as_ref().map(|documentation| {
+                    lsp_types::Documentation::MarkupContent(lsp_types::MarkupContent {
+                        kind: lsp_types::MarkupKind::Markdown,
+                        value: documentation.clone(),
+                    })
+                }),
+                sort_text: Some(self.sort_text.clone()),
+                filter_text: self.filter_text.clone(),
+                insert_text_format: Some(if is_snippet {
+                    lsp_types::InsertTextFormat::SNIPPET
+                } else {
+                    lsp_types::InsertTextFormat::PLAIN_TEXT
+                }),
+                text_edit: Some(
+                    lsp_types::TextEdit {
+                        range,
+                        new_text: self.edit.text_for(context.snippet_support).into_owned(),
+                    }
+                    .into(),
+                ),
+                ..Default::default()
+            }
+        })
+    }
+}
+
+impl From<CompletionKind> for lsp_types::CompletionItemKind {
+    fn from(kind: CompletionKind) -> Self {
+        match kind {
+            CompletionKind::Macro => lsp_types::CompletionItemKind::FUNCTION,
+            CompletionKind::Argument => lsp_types::CompletionItemKind::PROPERTY,
+            CompletionKind::Value => lsp_types::CompletionItemKind::VALUE,
+            CompletionKind::Snippet => lsp_types::CompletionItemKind::SNIPPET,
+        }
+    }
+}
+
+impl ToLsp for Hover {
+    type Output = lsp_types::Hover;
+
+    fn to_lsp(&self, context: &LspContext) -> Option<Self::Output> {
+        self.range.to_lsp(context).map(|range| lsp_types::Hover {
+            contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: self.content.clone(),
+            }),
+            range: Some(range),
+        })
+    }
+}
+
+impl ToLsp for InlayHint {
+    type Output = lsp_types::InlayHint;
+
+    fn to_lsp(&self, context: &LspContext) -> Option<Self::Output> {
+        self.position
+            .to_lsp(context)
+            .map(|position| lsp_types::InlayHint {
+                position,
+                label: lsp_types::InlayHintLabel::String(format!(": {}", self.label)),
+                kind: Some(lsp_types::InlayHintKind::TYPE),
+                text_edits: None,
+                tooltip: self.detail.clone().map(lsp_types::InlayHintTooltip::String),
+                padding_left: Some(true),
+                padding_right: None,
+                data: None,
+            })
+    }
+}
+
+impl From<ActionKind> for lsp_types::CodeActionKind {
+    fn from(kind: ActionKind) -> Self {
+        match kind {
+            ActionKind::QuickFix => lsp_types::CodeActionKind::QUICKFIX,
+            ActionKind::Refactor => lsp_types::CodeActionKind::REFACTOR,
+            ActionKind::RefactorExtract => lsp_types::CodeActionKind::REFACTOR_EXTRACT,
+            ActionKind::RefactorRewrite => lsp_types::CodeActionKind::REFACTOR_REWRITE,
+            ActionKind::Source => lsp_types::CodeActionKind::SOURCE,
+            ActionKind::SourceFixAll => FIX_ALL_CODE_ACTION_KIND,
+        }
+    }
+}
+
+impl ToLsp for Action {
+    type Output = lsp_types::CodeAction;
+
+    /// Converts to an LSP code action with a workspace edit (snippets are rendered as plain text).
+    ///
+    /// Text edits for other documents (if any) are converted using the contexts of documents
+    /// added with [`LspContext::with_document`] (their paths are resolved relative to the URI of the document).
+    /// Returns `None` if any of the text edits can't be converted
+    /// (i.e. partial multi-document edits are never returned).
+    fn to_lsp(&self, context: &LspContext) -> Option<Self::Output> {
+        let mut changes = HashMap::new();
+        for (uri, edits, context) in std::iter::once((context.uri.clone(), &self.edits, context))
+            .chain(
+                self.document_edits
+                    .iter()
+                    .map(|document_edit| {
+                        let uri = context.uri.join(&document_edit.path).ok()?;
+                        let context = context.documents.get(&uri)?;
+                        Some((uri, &document_edit.edits, context))
+                    })
+                    .collect::<Option<Vec<_>>>()?,
+            )
+        {
+            let edits = edits
+                .iter()
+                .map(|edit| edit.to_lsp(context))
+                .collect::<Option<Vec<_>>>()?;
+            if !edits.is_empty() {
+                changes.entry(uri).or_insert_with(Vec::new).extend(edits);
+            }
+        }
+
+        (!changes.is_empty()).then(|| lsp_types::CodeAction {
+            title: self.label.clone(),
+            kind: Some(self.kind.into()),
+            is_preferred: self.is_preferred.then_some(true),
+            edit: Some(lsp_types::WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Analysis;
+    use test_utils::parse_offset_at;
+
+    fn uri() -> lsp_types::Url {
+        lsp_types::Url::parse("file:///tmp/lib.rs").unwrap()
+    }
+
+    #[test]
+    fn position_conversion_works() {
+        let code = "// é\n#[ink::contract]";
+        for (encoding, expected_col) in [
+            (PositionEncoding::Utf8, 5),
+            (PositionEncoding::Utf16, 4),
+            (PositionEncoding::Utf32, 4),
+        ] {
+            let context = LspContext::new(uri(), code, encoding);
+            let offset = TextSize::from(5);
+            let position = offset.to_lsp(&context).unwrap();
+            assert_eq!(position, lsp_types::Position::new(0, expected_col));
+            assert_eq!(TextSize::from_lsp(&position, &context), Some(offset));
+
+            let range = TextRange::new(TextSize::from(0), TextSize::of(code));
+            let lsp_range = range.to_lsp(&context).unwrap();
+            assert_eq!(lsp_range.end, lsp_types::Position::new(1, 16));
+            assert_eq!(TextRange::from_lsp(&lsp_range, &context), Some(range));
+
+            let kind = lsp_types::PositionEncodingKind::from(encoding);
+            assert_eq!(PositionEncoding::try_from(kind), Ok(encoding));
+        }
+
+        // Out of bounds offsets and positions.
+        let context = LspContext::new(uri(), code, PositionEncoding::Utf16);
+        assert_eq!(TextSize::from(100).to_lsp(&context), None);
+        assert_eq!(
+            TextSize::from_lsp(&lsp_types::Position::new(5, 0), &context),
+            None
+        );
+    }
+
+    #[test]
+    fn code_action_kind_works() {
+        for (kind, expected_kind) in [
+            (ActionKind::QuickFix, lsp_types::CodeActionKind::QUICKFIX),
+            (ActionKind::Refactor, lsp_types::CodeActionKind::REFACTOR),
+            (ActionKind::Source, lsp_types::CodeActionKind::SOURCE),
+            (ActionKind::SourceFixAll, FIX_ALL_CODE_ACTION_KIND),
+        ] {
+            assert_eq!(lsp_types::CodeActionKind::from(kind), expected_kind);
+        }
+
+        // "Fix all" actions are a sub-kind of `source.fixAll`
+        // (i.e. clients that request `source.fixAll` actions get them too).
+        assert!(FIX_ALL_CODE_ACTION_KIND.as_str().starts_with(&format!(
+            "{}.",
+            lsp_types::CodeActionKind::SOURCE_FIX_ALL.as_str()
+        )));
+    }
+
+    #[test]
+    fn analysis_conversion_works() {
+        let code = r#"
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                impl MyContract {
+                    #[ink(constructor)]
+                    pub fn new() -> Self {}
+
+                    #[ink(message, payable, payable)]
+                    pub fn my_message(&self) {}
+                }
+            }
+        "#;
+        let analysis = Analysis::new(code);
+        let context = LspContext::new(uri(), code, PositionEncoding::Utf16);
+
+        // Diagnostics.
+        let diagnostics = analysis.diagnostics();
+        assert!(!diagnostics.is_empty());
+        for diagnostic in &diagnostics {
+            let lsp_diagnostic = diagnostic.to_lsp(&context).unwrap();
+            assert_eq!(lsp_diagnostic.message, diagnostic.message);
+            assert_eq!(
+                TextRange::from_lsp(&lsp_diagnostic.range, &context),
+                Some(diagnostic.range)
+            );
+        }
+
+        // Actions.
+        let offset = TextSize::from(parse_offset_at(code, Some("<-#[ink(message")).unwrap() as u32);
+        let actions = analysis.actions(TextRange::new(offset, offset));
+        assert!(!actions.is_empty());
+        for action in &actions {
+            let code_action = action.to_lsp(&context).unwrap();
+            assert_eq!(code_action.title, action.label);
+            let changes = code_action.edit.unwrap().changes.unwrap();
+            assert_eq!(changes[&uri()].len(), action.edits.len());
+        }
+
+        // Completions.
+        let completion_code = "#[ink(";
+        let completion_context = LspContext::new(uri(), completion_code, PositionEncoding::Utf16);
+        let completions = Analysis::new(completion_code).completions(TextSize::of(completion_code));
+        assert!(!completions.is_empty());
+        for completion in &completions {
+            let item = completion.to_lsp(&completion_context).unwrap();
+            assert_eq!(item.label, completion.label);
+            assert_eq!(
+                item.insert_text_format,
+                Some(lsp_types::InsertTextFormat::PLAIN_TEXT)
+            );
+        }
+
+        // Hover.
+        let offset = TextSize::from(parse_offset_at(code, Some("#[ink::con")).unwrap() as u32);
+        let hover = analysis
+            .hover(TextRange::new(offset, offset))
+            .unwrap()
+            .to_lsp(&context)
+            .unwrap();
+        assert!(matches!(
+            hover.contents,
+            lsp_types::HoverContents::Markup(content) if content.kind == lsp_types::MarkupKind::Markdown
+        ));
+    }
+}
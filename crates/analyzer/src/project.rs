@@ -0,0 +1,340 @@
+//! Batch analysis of ink! contract crates (e.g. for CLI and CI integrations).
+
+use ink_analyzer_ir::ast::HasName;
+use ink_analyzer_ir::syntax::AstNode;
+use ink_analyzer_ir::{ast, Contract, FromSyntax, InkFile};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::{Analysis, ContractInfo, Diagnostic, DiagnosticsConfig, FileIndex, Severity};
+
+/// Analysis results for an ink! contract crate, see [`analyze_project`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectReport {
+    /// Root directory of the crate.
+    pub root: PathBuf,
+    /// Analysis results for each file of the crate (sorted by path).
+    pub files: Vec<FileReport>,
+    /// Module declarations (i.e. `mod x;` items) whose files couldn't be found or read
+    /// (i.e. the expected path of the module file).
+    pub unresolved_modules: Vec<PathBuf>,
+}
+
+/// Analysis results for a file of an ink! contract crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileReport {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Contents of the file (e.g. for converting diagnostic ranges to lines and columns).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub content: String,
+    /// Diagnostics for the file (including cross-file selector diagnostics
+    /// for ink! contracts split across multiple files).
+    pub diagnostics: Vec<Diagnostic>,
+    /// ink! contracts in the file (i.e. constructors, messages and events with their selectors).
+    pub contracts: Vec<ContractInfo>,
+}
+
+impl ProjectReport {
+    /// Returns the number of diagnostic errors across all files.
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    /// Returns the number of diagnostic warnings (including deprecation warnings) across all files.
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning) + self.count(Severity::Deprecation)
+    }
+
+    /// Returns the number of diagnostics with the given severity across all files.
+    fn count(&self, severity: Severity) -> usize {
+        self.files
+            .iter()
+            .flat_map(|file| &file.diagnostics)
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+}
+
+/// Analyzes an ink! contract crate (i.e. the crate root and all its modules),
+/// and returns the diagnostics and ink! contract metadata for each file.
+///
+/// The crate root is either `lib.rs` (i.e. the layout generated by `cargo contract new`)
+/// or `src/lib.rs` in the root directory, and modules are resolved by following
+/// out-of-line module declarations (i.e. `mod x;` items).
+///
+/// The `Cargo.toml` in the root directory (if any) is used for crate level diagnostics
+/// (see [`DiagnosticsConfig::cargo_toml`]), and selectors are checked for overlaps across
+/// all the files of ink! contracts that are split across multiple files
+/// (see [`FileIndex::overlapping_selectors`]).
+pub fn analyze_project(root: &Path) -> ProjectReport {
+    let mut report = ProjectReport {
+        root: root.to_path_buf(),
+        files: Vec::new(),
+        unresolved_modules: Vec::new(),
+    };
+    let Some(crate_root) = ["lib.rs", "src/lib.rs"]
+        .into_iter()
+        .map(|path| root.join(path))
+        .find(|path| path.is_file())
+    else {
+        return report;
+    };
+    let cargo_toml = std::fs::read_to_string(root.join("Cargo.toml")).ok();
+
+    // Analyzes files (i.e. starting from the crate root),
+    // each file is queued with the path of the root file of the ink! contract it belongs to (if any).
+    let mut queue: Vec<(PathBuf, Option<PathBuf>)> = vec![(crate_root.clone(), None)];
+    let mut visited = HashSet::new();
+    // File indexes of the files of ink! contracts keyed by the path of the contract's root file.
+    let mut contract_files: HashMap<PathBuf, Vec<(usize, FileIndex)>> = HashMap::new();
+    while let Some((path, contract_root)) = queue.pop() {
+        if !visited.insert(path.clone()) {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            report.unresolved_modules.push(path);
+            continue;
+        };
+        let analysis = Analysis::new(&content);
+
+        // Queues module files.
+        // Modules declared in the root of an ink! contract (or in the root of
+        // an out-of-line child module of an ink! contract) are part of the ink! contract.
+        let module_dir = module_dir(&path);
+        let mut is_contract_root = false;
+        for (parents, name, is_contract_child) in module_declarations(analysis.file()) {
+            let dir = parents
+                .iter()
+                .fold(module_dir.clone(), |dir, parent| dir.join(parent));
+            let file_path = dir.join(format!("{name}.rs"));
+            let mod_path = dir.join(&name).join("mod.rs");
+            let module_path = if !file_path.is_file() && mod_path.is_file() {
+                mod_path
+            } else {
+                file_path
+            };
+            let child_contract_root = if is_contract_child {
+                is_contract_root = true;
+                Some(path.clone())
+            } else if parents.is_empty() {
+                contract_root.clone()
+            } else {
+                None
+            };
+            queue.push((module_path, child_contract_root));
+        }
+
+        // Computes diagnostics and ink! contract metadata.
+        let config = DiagnosticsConfig {
+            // Crate level diagnostics only apply to the crate root.
+            cargo_toml: (path == crate_root).then(|| cargo_toml.clone()).flatten(),
+            contract_child_module: contract_root.is_some(),
+            workspace_root: Some(root.to_path_buf()),
+            ..Default::default()
+        };
+        if let Some(contract_root) = contract_root
+            .clone()
+            .or_else(|| is_contract_root.then(|| path.clone()))
+        {
+            contract_files
+                .entry(contract_root)
+                .or_default()
+                .push((report.files.len(), analysis.file_index()));
+        }
+        report.files.push(FileReport {
+            diagnostics: analysis.diagnostics_with_config(&config),
+            contracts: analysis.contract_info(),
+            path,
+            content,
+        });
+    }
+
+    // Checks for overlapping selectors across the files of each ink! contract.
+    for files in contract_files.values() {
+        for (idx, index) in files {
+            let others: Vec<(String, &FileIndex)> = files
+                .iter()
+                .filter(|(other_idx, _)| other_idx != idx)
+                .map(|(other_idx, other)| {
+                    (report.files[*other_idx].path.display().to_string(), other)
+                })
+                .collect();
+            let diagnostics =
+                index.overlapping_selectors(others.iter().map(|(id, other)| (id.as_str(), *other)));
+            report.files[*idx].diagnostics.extend(diagnostics);
+        }
+    }
+
+    report.files.sort_by(|a, b| a.path.cmp(&b.path));
+    report.unresolved_modules.sort();
+    report
+}
+
+/// Returns the directory for the child module files of the file at the given path.
+///
+/// Ref: <https://doc.rust-lang.org/reference/items/modules.html#module-source-filenames>.
+fn module_dir(path: &Path) -> PathBuf {
+    let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    match path.file_stem().and_then(|stem| stem.to_str()) {
+        Some("lib" | "main" | "mod") | None => dir,
+        Some(stem) => dir.join(stem),
+    }
+}
+
+/// Returns the out-of-line module declarations (i.e. `mod x;` items) in the file,
+/// along with the names of their (inline) parent modules (if any),
+/// and whether they're declared in the root of an ink! contract.
+fn module_declarations(file: &InkFile) -> Vec<(Vec<String>, String, bool)> {
+    file.syntax()
+        .descendants()
+        .filter_map(ast::Module::cast)
+        .filter(|module| module.item_list().is_none())
+        .filter_map(|module| {
+            let name = module.name()?.to_string();
+            let mut parents = module
+                .syntax()
+                .ancestors()
+                .skip(1)
+                .filter_map(ast::Module::cast)
+                .filter_map(|parent| parent.name().map(|name| name.to_string()))
+                .collect::<Vec<_>>();
+            parents.reverse();
+            let is_contract_child =
+                ink_analyzer_ir::ink_parent::<Contract>(module.syntax()).is_some();
+            Some((parents, name, is_contract_child))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiagnosticCode;
+
+    #[test]
+    fn analyze_project_works() {
+        // Creates an ink! contract crate split across multiple files.
+        let root = std::env::temp_dir().join("ink_analyzer_analyze_project_works");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("flipper/getters")).unwrap();
+        std::fs::write(
+            root.join("lib.rs"),
+            r#"
+#[ink::contract]
+mod flipper {
+    mod getters;
+
+    #[ink(storage)]
+    pub struct Flipper {
+        value: bool,
+    }
+
+    impl Flipper {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { value: false }
+        }
+
+        #[ink(message)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+    }
+}
+
+mod utils;
+mod missing;
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("flipper/getters.rs"),
+            r#"
+use super::*;
+
+mod nested;
+
+impl Flipper {
+    #[ink(message)]
+    pub fn get(&self) -> bool {
+        self.value
+    }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("flipper/getters/nested.rs"),
+            r#"
+use super::*;
+
+impl Flipper {
+    #[ink(message, selector = 0x633aa551)]
+    pub fn flip_again(&mut self) {
+        self.value = !self.value;
+    }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("utils.rs"),
+            r#"
+impl Flipper {
+    #[ink(message)]
+    pub fn get(&self) -> bool {
+        self.value
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let report = analyze_project(&root);
+
+        // Analyzes all files.
+        let paths: Vec<PathBuf> = report.files.iter().map(|file| file.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                root.join("flipper/getters/nested.rs"),
+                root.join("flipper/getters.rs"),
+                root.join("lib.rs"),
+                root.join("utils.rs"),
+            ]
+        );
+        assert_eq!(report.unresolved_modules, vec![root.join("missing.rs")]);
+
+        // Extracts ink! contract metadata.
+        assert_eq!(report.files[2].contracts.len(), 1);
+        assert_eq!(report.files[2].contracts[0].messages.len(), 1);
+
+        // ink! messages are only valid in (nested) child modules of ink! contracts.
+        assert!(report.files[1].diagnostics.is_empty());
+        assert_eq!(report.files[3].diagnostics.len(), 1);
+
+        // Selectors are unique across all files of an ink! contract
+        // (i.e. the `flip_again` selector overlaps with the `flip` selector).
+        for (idx, other_path) in [(0, "lib.rs"), (2, "flipper/getters/nested.rs")] {
+            assert_eq!(report.files[idx].diagnostics.len(), 1);
+            assert_eq!(
+                report.files[idx].diagnostics[0].code,
+                Some(DiagnosticCode::OverlappingSelectors)
+            );
+            assert!(report.files[idx].diagnostics[0]
+                .message
+                .contains(&root.join(other_path).display().to_string()));
+        }
+        assert_eq!(report.error_count(), 3);
+        assert_eq!(report.warning_count(), 0);
+
+        // Missing crate roots yield empty reports.
+        let report = analyze_project(&root.join("missing"));
+        assert!(report.files.is_empty());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
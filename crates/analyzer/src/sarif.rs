@@ -0,0 +1,147 @@
+//! Conversion of ink! analyzer diagnostics to the Static Analysis Results Interchange Format (SARIF)
+//! (e.g. for CI integrations like GitHub code scanning).
+//!
+//! Ref: <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>.
+
+use crate::{Diagnostic, DiagnosticCode, LineIndex, PositionEncoding, Severity, TextSize};
+
+/// Diagnostics for a file, see [`sarif`].
+#[derive(Debug, Clone, Copy)]
+pub struct SarifFile<'a> {
+    /// URI of the file (e.g. a path relative to the repository root).
+    pub uri: &'a str,
+    /// Contents of the file (used to convert diagnostic ranges to lines and columns).
+    pub content: &'a str,
+    /// Diagnostics for the file.
+    pub diagnostics: &'a [Diagnostic],
+}
+
+/// Converts diagnostics for the given files to a SARIF 2.1.0 log (i.e. a JSON string).
+///
+/// The log describes a single run of ink! analyzer,
+/// with a rule for each diagnostic code (see [`DiagnosticCode`]),
+/// and regions use 1-based lines and columns (i.e. in Unicode code points).
+pub fn sarif(files: &[SarifFile]) -> String {
+    let rules: Vec<String> = DiagnosticCode::ALL
+        .iter()
+        .map(|code| {
+            format!(
+                r#"{{"id":{},"name":{},"helpUri":{}}}"#,
+                json_string(&rule_id(*code)),
+                json_string(code.name()),
+                json_string(&code.code_description())
+            )
+        })
+        .collect();
+    let results: Vec<String> = files
+        .iter()
+        .flat_map(|file| {
+            let line_index = LineIndex::new(file.content);
+            file.diagnostics
+                .iter()
+                .map(move |diagnostic| result(file, &line_index, diagnostic))
+        })
+        .collect();
+
+    format!(
+        r#"{{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{{"tool":{{"driver":{{"name":"ink-analyzer","version":{},"informationUri":"https://github.com/ink-analyzer/ink-analyzer","rules":[{}]}}}},"columnKind":"unicodeCodePoints","results":[{}]}}]}}"#,
+        json_string(env!("CARGO_PKG_VERSION")),
+        rules.join(","),
+        results.join(",")
+    )
+}
+
+/// Returns the SARIF result for a diagnostic.
+fn result(file: &SarifFile, line_index: &LineIndex, diagnostic: &Diagnostic) -> String {
+    let (start_line, start_column) = line_col(line_index, diagnostic.range.start());
+    let (end_line, end_column) = line_col(line_index, diagnostic.range.end());
+    let rule = diagnostic
+        .code
+        .and_then(|code| {
+            let rule_index = DiagnosticCode::ALL.iter().position(|it| *it == code)?;
+            Some(format!(
+                r#""ruleId":{},"ruleIndex":{rule_index},"#,
+                json_string(&rule_id(code))
+            ))
+        })
+        .unwrap_or_default();
+    format!(
+        r#"{{{rule}"level":{},"message":{{"text":{}}},"locations":[{{"physicalLocation":{{"artifactLocation":{{"uri":{}}},"region":{{"startLine":{start_line},"startColumn":{start_column},"endLine":{end_line},"endColumn":{end_column}}}}}}}]}}"#,
+        json_string(match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning | Severity::Deprecation => "warning",
+        }),
+        json_string(&diagnostic.message),
+        json_string(file.uri)
+    )
+}
+
+/// Returns the SARIF rule id for a diagnostic code (i.e. the number part of the code e.g. `E0017`).
+fn rule_id(code: DiagnosticCode) -> String {
+    format!("E{:04}", code.number())
+}
+
+/// Returns the 1-based line and column (i.e. in Unicode code points) for the offset.
+fn line_col(line_index: &LineIndex, offset: TextSize) -> (u32, u32) {
+    line_index
+        .line_col(offset, PositionEncoding::Utf32)
+        .map_or((1, 1), |line_col| (line_col.line + 1, line_col.col + 1))
+}
+
+/// Returns a JSON string literal for the text (i.e. quoted and escaped).
+fn json_string(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('"');
+    for char in text.chars() {
+        match char {
+            '"' => result.push_str(r#"\""#),
+            '\\' => result.push_str(r"\\"),
+            '\n' => result.push_str(r"\n"),
+            '\r' => result.push_str(r"\r"),
+            '\t' => result.push_str(r"\t"),
+            char if char.is_control() => result.push_str(&format!(r"\u{:04x}", char as u32)),
+            char => result.push(char),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Analysis;
+
+    #[test]
+    fn sarif_works() {
+        let code = "#[ink::contract]\nmod my_contract {\n}";
+        let diagnostics = Analysis::new(code).diagnostics();
+        let log = sarif(&[SarifFile {
+            uri: "src/lib.rs",
+            content: code,
+            diagnostics: &diagnostics,
+        }]);
+
+        // Log metadata.
+        assert!(log.starts_with(r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{"tool":{"driver":{"name":"ink-analyzer","#));
+        // Rule metadata.
+        assert!(log.contains(r#"{"id":"E0017","name":"missing-storage","helpUri":"https://github.com/ink-analyzer/ink-analyzer/blob/master/crates/analyzer/DIAGNOSTICS.md#e0017-missing-storage"}"#));
+        // Results.
+        assert_eq!(log.matches(r#""ruleId":"#).count(), diagnostics.len());
+        assert!(log.contains(r#""ruleId":"E0017","ruleIndex":16,"level":"error","#));
+        assert!(log.contains(r#""artifactLocation":{"uri":"src/lib.rs"}"#));
+    }
+
+    #[test]
+    fn json_string_works() {
+        for (text, expected_json) in [
+            ("", r#""""#),
+            ("text", r#""text""#),
+            (r#"a "quoted" \ path"#, r#""a \"quoted\" \\ path""#),
+            ("line\nbreak\ttab", r#""line\nbreak\ttab""#),
+            ("\u{1}", r#""\u0001""#),
+        ] {
+            assert_eq!(json_string(text), expected_json);
+        }
+    }
+}
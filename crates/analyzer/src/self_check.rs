@@ -0,0 +1,203 @@
+//! Runtime self-validation of ink! analyzer against bundled known-good and known-bad snippets.
+//!
+//! Helps users verify that their installed version/build (e.g. a wasm or FFI build)
+//! behaves as expected in their environment.
+
+use crate::codegen::snippets::CONTRACT_PLAIN;
+use crate::{Analysis, Severity, TextRange, TextSize};
+
+/// The result of running ink! analyzer against its bundled snippets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfCheckReport {
+    /// Number of checks that were run.
+    pub checks: usize,
+    /// Checks whose actual results didn't match the expected results.
+    pub mismatches: Vec<SelfCheckMismatch>,
+}
+
+impl SelfCheckReport {
+    /// Returns true if all checks passed.
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// A self-check whose actual result didn't match the expected result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfCheckMismatch {
+    /// Name of the check.
+    pub name: &'static str,
+    /// Description of the expected result.
+    pub expected: String,
+    /// Description of the actual result.
+    pub actual: String,
+}
+
+/// Expected result of a self-check.
+enum Expectation {
+    /// No diagnostics.
+    NoDiagnostics,
+    /// At least one error diagnostic.
+    Errors,
+    /// A completion with the given label at the end of the snippet.
+    Completion(&'static str),
+    /// Hover content for the given substring of the snippet.
+    Hover(&'static str),
+}
+
+/// Bundled known-good and known-bad snippets and their expected results.
+const CHECKS: [(&str, &str, Expectation); 5] = [
+    (
+        "new project contract has no diagnostics",
+        CONTRACT_PLAIN,
+        Expectation::NoDiagnostics,
+    ),
+    (
+        "ink! storage outside ink! contract is an error",
+        r#"
+#[ink(storage)]
+pub struct MyContract {}
+"#,
+        Expectation::Errors,
+    ),
+    (
+        "ink! contract with multiple ink! storage items is an error",
+        r#"
+#[ink::contract]
+mod my_contract {
+    #[ink(storage)]
+    pub struct MyContract {}
+
+    #[ink(storage)]
+    pub struct MyOtherContract {}
+}
+"#,
+        Expectation::Errors,
+    ),
+    (
+        "ink! attribute argument completions",
+        "#[ink(",
+        Expectation::Completion("message"),
+    ),
+    (
+        "ink! attribute argument hover",
+        r#"
+#[ink(message)]
+pub fn my_message(&self) {}
+"#,
+        Expectation::Hover("message"),
+    ),
+];
+
+/// Runs ink! analyzer against its bundled known-good and known-bad snippets,
+/// and reports any mismatches between the expected and actual results.
+pub fn self_check() -> SelfCheckReport {
+    let mismatches = CHECKS
+        .iter()
+        .filter_map(|(name, code, expectation)| {
+            check(code, expectation).map(|(expected, actual)| SelfCheckMismatch {
+                name,
+                expected,
+                actual,
+            })
+        })
+        .collect();
+
+    SelfCheckReport {
+        checks: CHECKS.len(),
+        mismatches,
+    }
+}
+
+/// Returns descriptions of the expected and actual results (in that order) if the snippet
+/// doesn't meet the expectation.
+fn check(code: &str, expectation: &Expectation) -> Option<(String, String)> {
+    let analysis = Analysis::new(code);
+    let (expected, actual) = match expectation {
+        Expectation::NoDiagnostics => {
+            let diagnostics = analysis.diagnostics();
+            if diagnostics.is_empty() {
+                return None;
+            }
+            (
+                "no diagnostics".to_string(),
+                format!("{} diagnostic(s)", diagnostics.len()),
+            )
+        }
+        Expectation::Errors => {
+            let diagnostics = analysis.diagnostics();
+            if diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.severity == Severity::Error)
+            {
+                return None;
+            }
+            (
+                "at least one error diagnostic".to_string(),
+                format!("{} non-error diagnostic(s)", diagnostics.len()),
+            )
+        }
+        Expectation::Completion(label) => {
+            let completions = analysis.completions(TextSize::from(code.len() as u32));
+            if completions
+                .iter()
+                .any(|completion| completion.label == *label)
+            {
+                return None;
+            }
+            (
+                format!("a `{label}` completion"),
+                format!("{} other completion(s)", completions.len()),
+            )
+        }
+        Expectation::Hover(pat) => {
+            let Some(start) = code.find(pat) else {
+                // A missing pattern is a broken check, not a pass.
+                return Some((
+                    format!("hover content for `{pat}`"),
+                    format!("`{pat}` not found in snippet"),
+                ));
+            };
+            let range = TextRange::new(
+                TextSize::from(start as u32),
+                TextSize::from((start + pat.len()) as u32),
+            );
+            if analysis.hover(range).is_some() {
+                return None;
+            }
+            (
+                format!("hover content for `{pat}`"),
+                "no hover content".to_string(),
+            )
+        }
+    };
+    Some((expected, actual))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_check_works() {
+        let report = self_check();
+        assert_eq!(report.checks, CHECKS.len());
+        assert!(report.is_ok(), "{:?}", report.mismatches);
+    }
+
+    #[test]
+    fn missing_hover_pattern_fails() {
+        let code = r#"
+#[ink(message)]
+pub fn my_message(&self) {}
+"#;
+        let mismatch = check(code, &Expectation::Hover("constructor"));
+        assert_eq!(
+            mismatch,
+            Some((
+                "hover content for `constructor`".to_string(),
+                "`constructor` not found in snippet".to_string(),
+            ))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! `Serialize` and `Deserialize` implementations for types from dependencies
+//! (i.e. text ranges, offsets and diagnostic codes) used by analysis result types.
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{DiagnosticCode, TextRange, TextSize};
+
+/// (De)serializes a [`TextSize`] as a `u32`.
+pub mod text_size {
+    use super::*;
+
+    pub fn serialize<S>(offset: &TextSize, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        u32::from(*offset).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<TextSize, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u32::deserialize(deserializer).map(TextSize::from)
+    }
+}
+
+/// (De)serializes a [`TextRange`] as a `{ "start": u32, "end": u32 }` object.
+pub mod text_range {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Range {
+        start: u32,
+        end: u32,
+    }
+
+    pub fn serialize<S>(range: &TextRange, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Range {
+            start: range.start().into(),
+            end: range.end().into(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<TextRange, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let range = Range::deserialize(deserializer)?;
+        if range.start > range.end {
+            return Err(D::Error::custom("invalid range: start is greater than end"));
+        }
+        Ok(TextRange::new(
+            TextSize::from(range.start),
+            TextSize::from(range.end),
+        ))
+    }
+}
+
+/// Serializes a [`DiagnosticCode`] as its string representation (e.g. `E0017-missing-storage`).
+impl Serialize for DiagnosticCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes a [`DiagnosticCode`] from either its full code, number or name (see [`DiagnosticCode::parse`]).
+impl<'de> Deserialize<'de> for DiagnosticCode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+        DiagnosticCode::parse(&text)
+            .ok_or_else(|| D::Error::custom(format!("unknown diagnostic code: `{text}`")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Analysis, Diagnostic, TextRange, TextSize};
+
+    #[test]
+    fn serde_round_trip_works() {
+        let code = "#[ink::contract]\nmod my_contract {}";
+        let analysis = Analysis::new(code);
+
+        // Diagnostics (including quickfixes and text edits).
+        let diagnostics = analysis.diagnostics();
+        let json = serde_json::to_string(&diagnostics).unwrap();
+        assert!(json.contains(r#""code":"E0017-missing-storage""#));
+        assert_eq!(
+            serde_json::from_str::<Vec<Diagnostic>>(&json).unwrap(),
+            diagnostics
+        );
+
+        // Other analysis results.
+        let range = TextRange::new(TextSize::from(0), TextSize::from(16));
+        let hover = analysis.hover(range).unwrap();
+        let json = serde_json::to_value(&hover).unwrap();
+        assert!(json["range"]["start"].is_u64() && json["range"]["end"].is_u64());
+        assert_eq!(serde_json::from_value::<crate::Hover>(json).unwrap(), hover);
+
+        let completions = analysis.completions(TextSize::from(9));
+        let json = serde_json::to_string(&completions).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<crate::Completion>>(&json).unwrap(),
+            completions
+        );
+
+        let inlay_hints = analysis.inlay_hints(None);
+        let json = serde_json::to_string(&inlay_hints).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<crate::InlayHint>>(&json).unwrap(),
+            inlay_hints
+        );
+
+        // Invalid values.
+        assert!(serde_json::from_str::<crate::DiagnosticCode>(r#""unknown""#).is_err());
+        assert!(serde_json::from_str::<crate::Hover>(
+            r#"{"range":{"start":2,"end":1},"content":""}"#
+        )
+        .is_err());
+    }
+}
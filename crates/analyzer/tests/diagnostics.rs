@@ -92,3 +92,16 @@ fn diagnostics_works() {
         }
     }
 }
+
+#[test]
+fn synthetic_contract_diagnostics_works() {
+    // Synthetic contracts (e.g. used for benchmarks) are valid ink! contracts.
+    for size in [1, 25] {
+        let code = test_utils::synthetic_contract(size);
+        let results = Analysis::new(&code).diagnostics();
+        assert!(
+            results.is_empty(),
+            "size: {size}, diagnostics: {results:#?}"
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! integration tests for ink! analyzer robustness against malformed code.
+
+use ink_analyzer::{Analysis, TextRange, TextSize};
+
+// The high-level methodology for malformed code test cases is:
+// - Read the source code of an ink! entity file in the `test-fixtures` directory
+//   (i.e. smaller files, because the number of truncations grows with the size of the file).
+// - Truncate the source code at regular offsets (i.e. mimicking incomplete code while typing).
+// - Run analyses on the truncated source code (and at the truncation offset).
+// - Verify that analysis doesn't panic.
+#[test]
+fn malformed_code_works() {
+    for source in [
+        "contracts/flipper",
+        "contracts/mother",
+        "chain_extensions/rand_extension",
+        "storage_items/non_packed_tuple_struct",
+        "trait_definitions/flipper_trait",
+    ] {
+        let code = test_utils::read_source_code(source);
+        for end in (0..code.len())
+            .step_by(37)
+            .filter(|end| code.is_char_boundary(*end))
+        {
+            let truncated_code = &code[..end];
+            let offset = TextSize::from(end as u32);
+            let analysis = Analysis::new(truncated_code);
+            analysis.diagnostics();
+            analysis.completions(offset);
+            analysis.actions(TextRange::empty(offset));
+            analysis.hover(TextRange::empty(offset));
+            analysis.signature_help(offset);
+        }
+    }
+}
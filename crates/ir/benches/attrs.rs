@@ -0,0 +1,116 @@
+//! Benchmarks for ink! attribute parsing (i.e. allocations and run times of `InkAttribute::cast`
+//! for files with many non-ink! attributes).
+//!
+//! Run with `cargo bench -p ink-analyzer-ir --bench attrs`.
+
+use ink_analyzer_ir::syntax::{AstNode, SourceFile};
+use ink_analyzer_ir::{ast, InkAttribute};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// A global allocator that counts allocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Number of times each benchmark is run.
+const ITERATIONS: u32 = 100;
+
+fn main() {
+    let code = code(500);
+    let attrs: Vec<ast::Attr> = SourceFile::parse(&code)
+        .tree()
+        .syntax()
+        .descendants()
+        .filter_map(ast::Attr::cast)
+        .collect();
+
+    bench("cast (all attributes)", || {
+        attrs.iter().cloned().filter_map(InkAttribute::cast).count()
+    });
+    bench("cast + kind (ink! attributes)", || {
+        attrs
+            .iter()
+            .cloned()
+            .filter_map(InkAttribute::cast)
+            .filter(|attr| !attr.kind().is_unknown())
+            .count()
+    });
+    bench("cast + args (ink! attributes)", || {
+        attrs
+            .iter()
+            .cloned()
+            .filter_map(InkAttribute::cast)
+            .map(|attr| attr.args().len())
+            .sum()
+    });
+}
+
+/// Runs the benchmark and prints the average allocations and run time per iteration.
+fn bench(name: &str, f: impl Fn() -> usize) {
+    let mut duration = Duration::ZERO;
+    let allocations_start = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        std::hint::black_box(f());
+        duration += start.elapsed();
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_start;
+    println!(
+        "{name}: {} allocations, {:?} per iteration",
+        allocations / ITERATIONS as usize,
+        duration / ITERATIONS
+    );
+}
+
+/// Returns code with the given number of items, each with multiple non-ink! attributes
+/// (and some ink! attributes).
+fn code(n_items: usize) -> String {
+    let mut code = String::from(
+        "#[ink::contract(env = crate::MyEnvironment, keep_attr = \"foo, bar\")]\nmod my_contract {\n",
+    );
+    for idx in 0..n_items {
+        code.push_str(&format!(
+            r#"
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[allow(dead_code)]
+    #[doc = "Item {idx}."]
+    pub struct Item{idx} {{
+        #[allow(unused)]
+        value: u32,
+    }}
+"#
+        ));
+        if idx % 10 == 0 {
+            code.push_str(&format!(
+                r#"
+    #[ink::storage_item(derive = true)]
+    pub struct StorageItem{idx} {{}}
+
+    impl Item{idx} {{
+        #[ink(message, payable, selector = {idx})]
+        pub fn message{idx}(&self) {{}}
+    }}
+"#
+            ));
+        }
+    }
+    code.push_str("}\n");
+    code
+}
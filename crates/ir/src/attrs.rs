@@ -1,7 +1,7 @@
 //! ink! attribute IR.
 
 use ink_analyzer_macro::FromAST;
-use itertools::Itertools;
+use once_cell::unsync::OnceCell;
 use ra_ap_syntax::{ast, AstNode, Direction, SyntaxNode};
 use std::cmp::Ordering;
 use std::fmt;
@@ -9,21 +9,25 @@ use std::fmt;
 use crate::traits::{FromAST, FromSyntax};
 
 use crate::meta::MetaName;
-pub use arg::{InkArg, InkArgKind, InkArgValueKind, InkArgValuePathKind, InkArgValueStringKind};
+pub use arg::{
+    InkArg, InkArgKind, InkArgValueError, InkArgValueKind, InkArgValuePathKind,
+    InkArgValueStringKind, KeepAttrEntry,
+};
 
 mod arg;
 pub mod meta;
 pub mod utils;
 
 /// An ink! specific attribute.
-#[derive(Debug, Clone, PartialEq, Eq, FromAST)]
+#[derive(Debug, Clone, FromAST)]
 pub struct InkAttribute {
     /// The kind of the ink! attribute e.g attribute macro like `#[ink::contract]`
     /// or attribute argument like `#[ink(storage)]`.
     kind: InkAttributeKind,
     /// ink! attribute arguments e.g message, payable, selector = 1
     /// for `#[ink(message, payable, selector = 1)]`
-    args: Vec<InkArg>,
+    /// (lazily parsed, because most callers only need the attribute kind).
+    args: OnceCell<Vec<InkArg>>,
     /// AST Node for ink! attribute.
     ast: ast::Attr,
     /// ink! path segment node.
@@ -40,55 +44,56 @@ impl InkAttribute {
         // Get attribute path segments.
         let mut path_segments = attr.path()?.segments();
 
+        // Compares the crate name without allocating (i.e. most attributes aren't ink! attributes).
         let ink_crate_segment = path_segments.next()?;
-        let ink_crate_name = ink_crate_segment.to_string();
-
-        (matches!(ink_crate_name.as_str(), "ink" | "ink_e2e")).then(|| {
-            let args = utils::parse_ink_args(&attr);
-            let possible_ink_macro_segment = path_segments.next();
-            let mut possible_ink_arg_name: Option<MetaName> = None;
-
-            let ink_attr_kind = match &possible_ink_macro_segment {
-                Some(ink_macro_segment) => {
-                    // More than one path segment means an ink! attribute macro e.g `#[ink::contract]` or `#[ink_e2e::test]`.
-                    match path_segments.next() {
-                        // Any more path segments means an unknown attribute macro e.g `#[ink::abc::xyz]` or `#[ink_e2e::abc::xyz]`.
-                        Some(_) => InkAttributeKind::Macro(InkMacroKind::Unknown),
-                        // Otherwise we parse the ink! macro kind from the macro path segment.
-                        None => InkAttributeKind::Macro(InkMacroKind::from((
-                            ink_crate_name.as_str(),
-                            ink_macro_segment.to_string().as_str(),
-                        ))),
-                    }
+        let ink_crate_name = ink_crate_segment.syntax().text();
+        if ink_crate_name != "ink" && ink_crate_name != "ink_e2e" {
+            return None;
+        }
+
+        let possible_ink_macro_segment = path_segments.next();
+        let mut possible_ink_arg_name: Option<MetaName> = None;
+
+        let ink_attr_kind = match &possible_ink_macro_segment {
+            Some(ink_macro_segment) => {
+                // More than one path segment means an ink! attribute macro e.g `#[ink::contract]` or `#[ink_e2e::test]`.
+                match path_segments.next() {
+                    // Any more path segments means an unknown attribute macro e.g `#[ink::abc::xyz]` or `#[ink_e2e::abc::xyz]`.
+                    Some(_) => InkAttributeKind::Macro(InkMacroKind::Unknown),
+                    // Otherwise we parse the ink! macro kind from the macro path segment.
+                    None => InkAttributeKind::Macro(InkMacroKind::from((
+                        ink_crate_name.to_string().as_str(),
+                        ink_macro_segment.to_string().as_str(),
+                    ))),
                 }
-                None => {
-                    // No additional path segments means either an ink! attribute argument (e.g `#[ink(storage)]`) or an unknown attribute.
-                    if args.is_empty() {
-                        match attr.token_tree() {
-                            // A token tree means an unknown ink! attribute argument.
-                            Some(_) => InkAttributeKind::Arg(InkArgKind::Unknown),
-                            // No token tree means an unknown ink! attribute macro.
-                            None => InkAttributeKind::Macro(InkMacroKind::Unknown),
-                        }
-                    } else {
-                        // Sort arguments so that we choose the "primary" `InkArgKind` for the attribute.
-                        // See [`utils::ink_arg_kind_sort_order`] doc.
-                        // Returns a new list so we don't change the original order for later analysis.
-                        let primary_arg = args.iter().sorted().next().unwrap();
-                        possible_ink_arg_name = primary_arg.name().cloned();
-                        InkAttributeKind::Arg(*primary_arg.kind())
+            }
+            None => {
+                // No additional path segments means either an ink! attribute argument (e.g `#[ink(storage)]`) or an unknown attribute.
+                // The "primary" argument determines the attribute kind,
+                // so only argument names are scanned (i.e. arguments are still parsed lazily).
+                // See [`utils::primary_ink_arg`] doc.
+                match utils::primary_ink_arg(&attr) {
+                    Some((primary_arg_kind, primary_arg_name)) => {
+                        possible_ink_arg_name = primary_arg_name;
+                        InkAttributeKind::Arg(primary_arg_kind)
                     }
+                    // No arguments means either an unknown ink! attribute argument
+                    // (i.e. a token tree) or an unknown ink! attribute macro (i.e. no token tree).
+                    None => match attr.token_tree() {
+                        Some(_) => InkAttributeKind::Arg(InkArgKind::Unknown),
+                        None => InkAttributeKind::Macro(InkMacroKind::Unknown),
+                    },
                 }
-            };
-
-            Self {
-                ast: attr,
-                kind: ink_attr_kind,
-                args,
-                ink: ink_crate_segment,
-                ink_macro: possible_ink_macro_segment,
-                ink_arg_name: possible_ink_arg_name,
             }
+        };
+
+        Some(Self {
+            ast: attr,
+            kind: ink_attr_kind,
+            args: OnceCell::new(),
+            ink: ink_crate_segment,
+            ink_macro: possible_ink_macro_segment,
+            ink_arg_name: possible_ink_arg_name,
         })
     }
 
@@ -101,8 +106,10 @@ impl InkAttribute {
     }
 
     /// Returns the ink! attribute arguments.
+    ///
+    /// Arguments are parsed on first access (and cached).
     pub fn args(&self) -> &[InkArg] {
-        &self.args
+        self.args.get_or_init(|| utils::parse_ink_args(&self.ast))
     }
 
     /// Returns the ink! path segment node.
@@ -131,6 +138,19 @@ impl InkAttribute {
     }
 }
 
+// Arguments are derived from the AST node (i.e. whether or not they've been parsed yet is irrelevant).
+impl PartialEq for InkAttribute {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.ast == other.ast
+            && self.ink == other.ink
+            && self.ink_macro == other.ink_macro
+            && self.ink_arg_name == other.ink_arg_name
+    }
+}
+
+impl Eq for InkAttribute {}
+
 impl Ord for InkAttribute {
     fn cmp(&self, other: &Self) -> Ordering {
         Ord::cmp(self.kind(), other.kind())
@@ -337,15 +357,19 @@ pub struct InkAttrData<T: AstNode> {
     syntax: SyntaxNode,
 }
 
-impl<T: AstNode> From<InkAttribute> for InkAttrData<T> {
-    fn from(attr: InkAttribute) -> Self {
-        Self {
-            ast: attr.syntax().parent().and_then(T::cast),
-            syntax: attr
-                .syntax()
-                .parent()
-                .expect("An attribute should always have a parent."),
-            attr,
+/// Returns the ink! attribute as the error if it has no parent
+/// (i.e. attributes are only detached from a syntax tree if they're created manually).
+impl<T: AstNode> TryFrom<InkAttribute> for InkAttrData<T> {
+    type Error = InkAttribute;
+
+    fn try_from(attr: InkAttribute) -> Result<Self, Self::Error> {
+        match attr.syntax().parent() {
+            Some(parent) => Ok(Self {
+                ast: T::cast(parent.clone()),
+                syntax: parent,
+                attr,
+            }),
+            None => Err(attr),
         }
     }
 }
@@ -767,4 +791,230 @@ mod tests {
             assert_eq!(actual_ink_attr, expected_ink_attr);
         }
     }
+
+    #[test]
+    fn lazy_args_works() {
+        for code in [
+            quote_as_str! {
+                #[ink::contract(env = crate::MyEnvironment, keep_attr = "foo")]
+            },
+            quote_as_str! {
+                #[ink(message, payable, selector = 1)]
+            },
+        ] {
+            let ink_attr = parse_first_ink_attribute(code);
+            let unparsed = ink_attr.clone();
+
+            // Arguments aren't parsed by casting (i.e. for both ink! attribute macros and arguments).
+            assert!(ink_attr.args.get().is_none());
+            assert!(!ink_attr.kind().is_unknown());
+            assert!(ink_attr.args.get().is_none());
+
+            // Arguments are parsed on first access, and parsing state doesn't affect equality.
+            assert!(!ink_attr.args().is_empty());
+            assert!(ink_attr.args.get().is_some());
+            assert!(unparsed.args.get().is_none());
+            assert_eq!(ink_attr, unparsed);
+            // NOTE: Argument values are parsed into new syntax trees, so they're compared by their text.
+            let args_text = |attr: &InkAttribute| -> Vec<String> {
+                attr.args().iter().map(ToString::to_string).collect()
+            };
+            assert_eq!(args_text(&ink_attr), args_text(&unparsed));
+        }
+    }
+
+    #[test]
+    fn keep_attr_list_works() {
+        for (code, expected_entries) in [
+            (r#"#[ink::contract(keep_attr = "")]"#, Some(vec![])),
+            (
+                r#"#[ink::contract(keep_attr = "foo,bar")]"#,
+                Some(vec![("foo", true), ("bar", true)]),
+            ),
+            (
+                r#"#[ink::contract(keep_attr = " cfg , rustfmt::skip, ")]"#,
+                Some(vec![("cfg", true), ("rustfmt::skip", true)]),
+            ),
+            (
+                r#"#[ink::contract(keep_attr = "foo bar,1foo,::foo,_")]"#,
+                Some(vec![
+                    ("foo bar", false),
+                    ("1foo", false),
+                    ("::foo", false),
+                    ("_", false),
+                ]),
+            ),
+            // Non-string values.
+            (r#"#[ink::contract(keep_attr = 1)]"#, None),
+            (r#"#[ink::contract(keep_attr)]"#, None),
+        ] {
+            let ink_attr = parse_first_ink_attribute(code);
+            let arg = ink_attr
+                .args()
+                .iter()
+                .find(|arg| *arg.kind() == InkArgKind::KeepAttr)
+                .unwrap();
+            let entries = arg.as_keep_attr_list();
+            assert_eq!(
+                entries.as_ref().map(|entries| entries
+                    .iter()
+                    .map(|entry| (entry.text(), entry.is_valid_path()))
+                    .collect::<Vec<_>>()),
+                expected_entries,
+                "code: {code}"
+            );
+            // Entry ranges match their text.
+            for entry in entries.unwrap_or_default() {
+                assert_eq!(&code[entry.text_range()], entry.text(), "code: {code}");
+            }
+        }
+
+        // Only `keep_attr` arguments have entries.
+        let ink_attr = parse_first_ink_attribute(r#"#[ink::contract(env = "foo")]"#);
+        assert!(ink_attr.args()[0].as_keep_attr_list().is_none());
+    }
+
+    #[test]
+    fn typed_value_accessors_works() {
+        let first_arg = |code: &str| parse_first_ink_attribute(code).args()[0].clone();
+
+        // `u32` values.
+        for (code, expected) in [
+            ("#[ink(selector = 1)]", Ok(1)),
+            ("#[ink(selector = 0xA)]", Ok(10)),
+            ("#[ink(extension = 0xFFFFFFFF)]", Ok(u32::MAX)),
+            (
+                "#[ink(selector = _)]",
+                Err(InkArgValueError::Invalid {
+                    expected: InkArgValueKind::U32OrWildcard,
+                    found: "_".to_string(),
+                }),
+            ),
+            (
+                "#[ink(extension = 0x100000000)]",
+                Err(InkArgValueError::Invalid {
+                    expected: InkArgValueKind::U32,
+                    found: "0x100000000".to_string(),
+                }),
+            ),
+            (
+                "#[ink(extension)]",
+                Err(InkArgValueError::Missing {
+                    expected: InkArgValueKind::U32,
+                }),
+            ),
+            (
+                "#[ink(handle_status = 1)]",
+                Err(InkArgValueError::Unsupported {
+                    arg_kind: InkArgKind::HandleStatus,
+                    value_kind: InkArgValueKind::Bool,
+                }),
+            ),
+        ] {
+            assert_eq!(first_arg(code).as_u32(), expected, "code: {code}");
+        }
+
+        // `bool` values.
+        for (code, expected) in [
+            ("#[ink(handle_status = true)]", Ok(true)),
+            ("#[ink::storage_item(derive = false)]", Ok(false)),
+            (
+                r#"#[ink(handle_status = "true")]"#,
+                Err(InkArgValueError::Invalid {
+                    expected: InkArgValueKind::Bool,
+                    found: r#""true""#.to_string(),
+                }),
+            ),
+            (
+                "#[ink(message = true)]",
+                Err(InkArgValueError::Unsupported {
+                    arg_kind: InkArgKind::Message,
+                    value_kind: InkArgValueKind::None,
+                }),
+            ),
+        ] {
+            assert_eq!(first_arg(code).as_bool(), expected, "code: {code}");
+        }
+
+        // `string` values.
+        for (code, expected) in [
+            (r#"#[ink(namespace = "my_namespace")]"#, Ok("my_namespace")),
+            (
+                r#"#[ink::contract(keep_attr = "foo, bar")]"#,
+                Ok("foo, bar"),
+            ),
+            (
+                r#"#[ink::e2e_test(additional_contracts = "a/Cargo.toml b/Cargo.toml")]"#,
+                Ok("a/Cargo.toml b/Cargo.toml"),
+            ),
+            // Namespaces must be valid identifiers.
+            (
+                r#"#[ink(namespace = "1namespace")]"#,
+                Err(InkArgValueError::Invalid {
+                    expected: InkArgValueKind::String(InkArgValueStringKind::Identifier),
+                    found: r#""1namespace""#.to_string(),
+                }),
+            ),
+            (
+                "#[ink(namespace = my_namespace)]",
+                Err(InkArgValueError::Invalid {
+                    expected: InkArgValueKind::String(InkArgValueStringKind::Identifier),
+                    found: "my_namespace".to_string(),
+                }),
+            ),
+            (
+                "#[ink(namespace =)]",
+                Err(InkArgValueError::Missing {
+                    expected: InkArgValueKind::String(InkArgValueStringKind::Identifier),
+                }),
+            ),
+        ] {
+            assert_eq!(
+                first_arg(code).as_string(),
+                expected.map(ToString::to_string),
+                "code: {code}"
+            );
+        }
+
+        // `path` values.
+        for (code, expected) in [
+            (
+                "#[ink::contract(env = crate::MyEnvironment)]",
+                Ok("crate::MyEnvironment"),
+            ),
+            (
+                "#[ink_e2e::test(environment = ink::env::DefaultEnvironment)]",
+                Ok("ink::env::DefaultEnvironment"),
+            ),
+            (
+                r#"#[ink::contract(env = "crate::MyEnvironment")]"#,
+                Err(InkArgValueError::Invalid {
+                    expected: InkArgValueKind::Path(InkArgValuePathKind::Environment),
+                    found: r#""crate::MyEnvironment""#.to_string(),
+                }),
+            ),
+        ] {
+            assert_eq!(
+                first_arg(code).as_path().map(|path| path.to_string()),
+                expected.map(ToString::to_string),
+                "code: {code}"
+            );
+        }
+
+        // Error messages.
+        assert_eq!(
+            first_arg(r#"#[ink(namespace = "1namespace")]"#)
+                .as_string()
+                .unwrap_err()
+                .to_string(),
+            r#"expected a `&str` value, found `"1namespace"` (A valid Rust identifier.)"#
+        );
+        assert_eq!(
+            first_arg("#[ink(message = true)]")
+                .as_bool()
+                .unwrap_err()
+                .to_string(),
+            "`message` argument doesn't take a value"
+        );
+    }
 }
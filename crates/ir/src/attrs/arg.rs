@@ -1,6 +1,7 @@
 //! ink! attribute argument IR.
 
-use ra_ap_syntax::{AstToken, TextRange};
+use itertools::Itertools;
+use ra_ap_syntax::{ast, AstNode, AstToken, SourceFile, SyntaxKind, TextRange, TextSize};
 use std::cmp::Ordering;
 use std::fmt;
 
@@ -57,6 +58,232 @@ impl InkArg {
     pub fn value(&self) -> Option<&MetaValue> {
         self.meta.value().result().ok()
     }
+
+    /// Returns the entries of a `keep_attr` argument's comma separated string value
+    /// (if the argument is a `keep_attr` argument with a string value).
+    ///
+    /// Empty entries (e.g. after a trailing comma) are skipped.
+    ///
+    /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/utils.rs#L92-L107>.
+    pub fn as_keep_attr_list(&self) -> Option<Vec<KeepAttrEntry>> {
+        if self.kind != InkArgKind::KeepAttr {
+            return None;
+        }
+        let value = self
+            .value()
+            .filter(|value| value.kind() == SyntaxKind::STRING)?;
+        let literal = value.to_string();
+        // Determines the text (and its offset) between the quotes.
+        let content_start = literal.find('"')? + 1;
+        let content_end = literal.rfind('"').filter(|end| *end >= content_start)?;
+        let content_offset = value.text_range().start() + TextSize::from(content_start as u32);
+        let mut entries = Vec::new();
+        let mut entry_start = 0;
+        for part in literal[content_start..content_end].split(',') {
+            let text = part.trim();
+            if !text.is_empty() {
+                let start = entry_start + (part.len() - part.trim_start().len());
+                entries.push(KeepAttrEntry {
+                    text: text.to_string(),
+                    range: TextRange::at(
+                        content_offset + TextSize::from(start as u32),
+                        TextSize::of(text),
+                    ),
+                });
+            }
+            // Skips the part and the comma.
+            entry_start += part.len() + 1;
+        }
+        Some(entries)
+    }
+
+    /// Returns the value as a `u32` (i.e. a decimal or hexadecimal integer literal)
+    /// if the argument takes a `u32` value (e.g. `extension` and `selector` arguments).
+    ///
+    /// NOTE: A wildcard/underscore (`_`) selector is not a `u32` value, use [`MetaValue::is_wildcard`] for those.
+    ///
+    /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L903-L910>.
+    pub fn as_u32(&self) -> Result<u32, InkArgValueError> {
+        let expected = self.expected_value_kind(|value_kind| {
+            matches!(
+                value_kind,
+                InkArgValueKind::U32 | InkArgValueKind::U32OrWildcard
+            )
+        })?;
+        let value = self.value_or_err(expected)?;
+        value
+            .as_u32()
+            .ok_or_else(|| InkArgValueError::invalid(expected, value))
+    }
+
+    /// Returns the value as a `bool` (i.e. a `true` or `false` literal)
+    /// if the argument takes a `bool` value (e.g. `derive` and `handle_status` arguments).
+    pub fn as_bool(&self) -> Result<bool, InkArgValueError> {
+        let expected =
+            self.expected_value_kind(|value_kind| value_kind == InkArgValueKind::Bool)?;
+        let value = self.value_or_err(expected)?;
+        value
+            .as_boolean()
+            .ok_or_else(|| InkArgValueError::invalid(expected, value))
+    }
+
+    /// Returns the value as a `String` (i.e. the contents of a string literal without the quotes)
+    /// if the argument takes a string value (e.g. `namespace` and `keep_attr` arguments).
+    ///
+    /// Values of arguments that take an identifier (e.g. `namespace`) must be valid Rust identifiers.
+    ///
+    /// Ref: <https://github.com/paritytech/ink/blob/v4.1.0/crates/ink/ir/src/ir/attrs.rs#L922-L926>.
+    pub fn as_string(&self) -> Result<String, InkArgValueError> {
+        let expected = self
+            .expected_value_kind(|value_kind| matches!(value_kind, InkArgValueKind::String(_)))?;
+        let value = self.value_or_err(expected)?;
+        value
+            .as_string()
+            .filter(|text| {
+                expected != InkArgValueKind::String(InkArgValueStringKind::Identifier)
+                    || is_ident(text)
+            })
+            .ok_or_else(|| InkArgValueError::invalid(expected, value))
+    }
+
+    /// Returns the value as a `Path` if the argument takes a path value (e.g. `env` and `environment` arguments).
+    ///
+    /// NOTE: The text range of the returned path is inaccurate (see [`MetaValue::as_path_with_inaccurate_text_range`]),
+    /// use [`MetaValue::text_range`] instead.
+    pub fn as_path(&self) -> Result<ast::Path, InkArgValueError> {
+        let expected =
+            self.expected_value_kind(|value_kind| matches!(value_kind, InkArgValueKind::Path(_)))?;
+        let value = self.value_or_err(expected)?;
+        value
+            .as_path_with_inaccurate_text_range()
+            .ok_or_else(|| InkArgValueError::invalid(expected, value))
+    }
+
+    /// Returns the value kind of the argument if it's accepted by the typed value accessor.
+    fn expected_value_kind<F>(&self, is_accepted: F) -> Result<InkArgValueKind, InkArgValueError>
+    where
+        F: Fn(InkArgValueKind) -> bool,
+    {
+        let value_kind = InkArgValueKind::from(self.kind);
+        if is_accepted(value_kind) {
+            Ok(value_kind)
+        } else {
+            Err(InkArgValueError::Unsupported {
+                arg_kind: self.kind,
+                value_kind,
+            })
+        }
+    }
+
+    /// Returns the meta value or an error if it's missing or can't be parsed.
+    fn value_or_err(&self, expected: InkArgValueKind) -> Result<&MetaValue, InkArgValueError> {
+        match self.meta.value() {
+            MetaOption::Ok(value) => Ok(value),
+            MetaOption::Err(elems) => Err(InkArgValueError::Invalid {
+                expected,
+                found: elems.iter().map(ToString::to_string).join(""),
+            }),
+            MetaOption::None => Err(InkArgValueError::Missing { expected }),
+        }
+    }
+}
+
+/// An entry of a `keep_attr` argument's comma separated string value
+/// (e.g. `foo` and `bar` for `keep_attr = "foo, bar"`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeepAttrEntry {
+    /// The entry text (without surrounding whitespace).
+    text: String,
+    /// The text range of the entry.
+    range: TextRange,
+}
+
+impl KeepAttrEntry {
+    /// Returns the entry text (without surrounding whitespace).
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the text range of the entry.
+    pub fn text_range(&self) -> TextRange {
+        self.range
+    }
+
+    /// Returns true if the entry is a syntactically valid attribute path (e.g. `cfg` or `rustfmt::skip`).
+    pub fn is_valid_path(&self) -> bool {
+        self.text.split("::").all(|segment| {
+            let mut chars = segment.chars();
+            chars
+                .next()
+                .map_or(false, |char| char == '_' || char.is_alphabetic())
+                && chars.all(|char| char == '_' || char.is_alphanumeric())
+                && segment != "_"
+        })
+    }
+}
+
+/// An error returned by the typed value accessors of an ink! attribute argument (e.g. [`InkArg::as_u32`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InkArgValueError {
+    /// The argument doesn't take the requested type of value (e.g. `as_bool` for a `selector` argument).
+    Unsupported {
+        arg_kind: InkArgKind,
+        value_kind: InkArgValueKind,
+    },
+    /// The argument is missing a value (e.g. `selector` or `selector =`).
+    Missing { expected: InkArgValueKind },
+    /// The argument's value is not valid for the argument (e.g. `selector = "foo"` or `namespace = "1foo"`).
+    Invalid {
+        expected: InkArgValueKind,
+        found: String,
+    },
+}
+
+impl InkArgValueError {
+    fn invalid(expected: InkArgValueKind, value: &MetaValue) -> Self {
+        Self::Invalid {
+            expected,
+            found: value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for InkArgValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InkArgValueError::Unsupported {
+                arg_kind,
+                value_kind,
+            } => match value_kind {
+                InkArgValueKind::None => write!(f, "`{arg_kind}` argument doesn't take a value"),
+                _ => write!(f, "`{arg_kind}` argument takes a `{value_kind}` value"),
+            },
+            InkArgValueError::Missing { expected } => {
+                write!(f, "expected a `{expected}` value, found nothing")
+            }
+            InkArgValueError::Invalid { expected, found } => {
+                write!(f, "expected a `{expected}` value, found `{found}`")?;
+                match expected.detail() {
+                    "" => Ok(()),
+                    detail => write!(f, " ({detail})"),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for InkArgValueError {}
+
+/// Returns true if the text is a valid Rust identifier.
+fn is_ident(text: &str) -> bool {
+    // Parses the text and finds the first identifier.
+    let file = SourceFile::parse(text).tree();
+    file.syntax()
+        .descendants_with_tokens()
+        .find_map(|elem| ast::Ident::cast(elem.into_token()?))
+        // Parsed identifier must be equal to the text.
+        .map_or(false, |ident| ident.text() == text)
 }
 
 impl fmt::Display for InkArg {
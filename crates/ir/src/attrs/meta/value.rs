@@ -92,21 +92,15 @@ impl MetaValue {
             }
         }
 
+        // Falls back to the range of the expression if there are no elements
+        // (i.e. the parse method ensures that elements are never empty, but the range should never panic).
+        let fallback = self.expr.syntax().text_range();
         TextRange::new(
-            start.unwrap_or(
-                self.elements
-                    .first()
-                    .expect("parse method ensures that elements is never be empty")
-                    .text_range()
-                    .start(),
-            ),
-            end.unwrap_or(
-                self.elements
-                    .last()
-                    .expect("parse method ensures that elements is never be empty")
-                    .text_range()
-                    .start(),
-            ),
+            start
+                .or_else(|| self.elements.first().map(|it| it.text_range().start()))
+                .unwrap_or(fallback.start()),
+            end.or_else(|| self.elements.last().map(|it| it.text_range().start()))
+                .unwrap_or(fallback.end()),
         )
     }
 
@@ -119,12 +113,14 @@ impl MetaValue {
     }
 
     /// Converts the value if it's an integer literal (decimal or hexadecimal) into a `u32`.
+    ///
+    /// Digit separators (i.e. underscores e.g. `0xFFFF_FFFF`) are ignored.
     pub fn as_u32(&self) -> Option<u32> {
         (self.kind() == SyntaxKind::INT_NUMBER).then(|| {
-            let value = self.to_string();
-            if value.starts_with("0x") {
+            let value = self.to_string().replace('_', "");
+            if let Some(hex_value) = value.strip_prefix("0x") {
                 // Check as hex.
-                u32::from_str_radix(value.strip_prefix("0x").unwrap(), 16).ok()
+                u32::from_str_radix(hex_value, 16).ok()
             } else {
                 // Check as decimal.
                 value.parse::<u32>().ok()
@@ -144,21 +140,11 @@ impl MetaValue {
     /// Converts the value if it's a string literal into a `String`.
     pub fn as_string(&self) -> Option<String> {
         (self.kind() == SyntaxKind::STRING).then(|| {
-            let mut value = self.to_string();
+            let value = self.to_string();
             // Strip leading and trailing escaped quotes.
-            if value.starts_with('\"') {
-                value = value
-                    .strip_prefix('\"')
-                    .expect("Should be able to strip prefix")
-                    .to_string();
-            }
-            if value.ends_with('\"') {
-                value = value
-                    .strip_suffix('\"')
-                    .expect("Should be able to strip suffix")
-                    .to_string();
-            }
-            value
+            let value = value.strip_prefix('\"').unwrap_or(&value);
+            let value = value.strip_suffix('\"').unwrap_or(value);
+            value.to_string()
         })
     }
 
@@ -222,4 +208,26 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn as_u32_works() {
+        for (code, expected_value) in [
+            // Decimal and hex integers.
+            (quote_as_str! { 10 }, Some(10)),
+            (quote_as_str! { 0xA }, Some(10)),
+            (quote_as_str! { 0xFFFF_FFFF }, Some(u32::MAX)),
+            (quote_as_str! { 1_000 }, Some(1000)),
+            // Overflowing, malformed and non-integer values.
+            (quote_as_str! { 0x1_0000_0000 }, None),
+            ("0XA", None),
+            (quote_as_str! { "10" }, None),
+            (quote_as_str! { _ }, None),
+        ] {
+            assert_eq!(
+                MetaValue::parse(&parse_syntax_elements(code)).and_then(|value| value.as_u32()),
+                expected_value,
+                "meta value: {code}"
+            );
+        }
+    }
 }
@@ -1,10 +1,10 @@
 //! ink! attribute IR utilities.
 
 use itertools::Itertools;
-use ra_ap_syntax::{ast, AstNode, AstToken, SyntaxElement, SyntaxToken, T};
+use ra_ap_syntax::{ast, AstNode, AstToken, SyntaxElement, SyntaxToken, TextSize, T};
 
 use crate::meta::{MetaName, MetaNameValue, MetaOption, MetaSeparator, MetaValue};
-use crate::InkArg;
+use crate::{InkArg, InkArgKind};
 
 /// Parse ink! attribute arguments.
 pub fn parse_ink_args(attr: &ast::Attr) -> Vec<InkArg> {
@@ -18,8 +18,54 @@ pub fn parse_ink_args(attr: &ast::Attr) -> Vec<InkArg> {
     }
 }
 
+/// Returns the kind and name (if valid) of the "primary" ink! attribute argument (if any)
+/// (i.e. the first argument with the highest priority kind, see [`InkArgKind`] `Ord` implementation),
+/// without parsing argument values.
+///
+/// The result is the same as the first argument of the sorted result of [`parse_ink_args`]
+/// (i.e. arguments are split by the same tokenizer, see [`meta_items`]).
+pub fn primary_ink_arg(attr: &ast::Attr) -> Option<(InkArgKind, Option<MetaName>)> {
+    meta_items(&attr.token_tree()?)
+        .into_iter()
+        .map(|(elems, _)| {
+            let name = match arg_name_meta_option(&meta_item_parts(elems)[0]) {
+                MetaOption::Ok(name) => Some(name),
+                _ => None,
+            };
+            let kind = name
+                .as_ref()
+                .map_or(InkArgKind::Unknown, |name| InkArgKind::from(name.text()));
+            (kind, name)
+        })
+        // Returns the first argument with the highest priority kind (i.e. like a stable sort).
+        .min_by(|(a, _), (b, _)| a.cmp(b))
+}
+
 /// Parse meta items.
 fn parse_meta_items(token_tree: &ast::TokenTree) -> Vec<MetaNameValue> {
+    meta_items(token_tree)
+        .into_iter()
+        .map(|(elems, offset)| {
+            if elems.is_empty() {
+                // Empty argument.
+                // Use last operator offset as the offset for the empty argument.
+                MetaNameValue::empty(offset)
+            } else {
+                let [name, eq, value] = meta_item_parts(elems);
+                MetaNameValue::new(
+                    arg_name_meta_option(&name),
+                    arg_eq_option(&eq),
+                    arg_value_meta_option(&value),
+                    offset,
+                )
+            }
+        })
+        .collect()
+}
+
+/// Returns the elements of the comma separated meta items in a token tree
+/// and the offset of the separator (or opening parenthesis) preceding each item.
+fn meta_items(token_tree: &ast::TokenTree) -> Vec<(Vec<SyntaxElement>, TextSize)> {
     let l_paren = token_tree.l_paren_token();
     let r_paren = token_tree.r_paren_token();
 
@@ -38,40 +84,37 @@ fn parse_meta_items(token_tree: &ast::TokenTree) -> Vec<MetaNameValue> {
         // Comma separated groups.
         .group_by(|token| token.kind() == T![,])
         .into_iter()
-        //.filter_map(|(is_sep, group)| (!is_sep || true).then_some(group))
         .filter_map(|(is_sep, mut group)| {
             if is_sep {
                 // This is the comma token, so we update last separator offset.
-                last_separator_offset = group.next().unwrap().text_range().end();
+                if let Some(separator) = group.next() {
+                    last_separator_offset = separator.text_range().end();
+                }
                 None
             } else {
-                let arg_elems: Vec<SyntaxElement> = group.collect();
-                if arg_elems.is_empty() {
-                    // Empty argument.
-                    // Use last operator offset as the offset for the empty argument.
-                    Some(MetaNameValue::empty(last_separator_offset))
-                } else {
-                    let arg_item_groups: Vec<Vec<SyntaxElement>> = arg_elems
-                        .into_iter()
-                        // Equal sign (=) separated groups.
-                        .group_by(|token| token.kind() == T![=])
-                        .into_iter()
-                        .map(|(_, group)| group.into_iter().collect())
-                        .collect();
-
-                    let empty = Vec::new();
-                    Some(MetaNameValue::new(
-                        arg_name_meta_option(arg_item_groups.get(0).unwrap_or(&empty)),
-                        arg_eq_option(arg_item_groups.get(1).unwrap_or(&empty)),
-                        arg_value_meta_option(arg_item_groups.get(2).unwrap_or(&empty)),
-                        last_separator_offset,
-                    ))
-                }
+                Some((group.collect(), last_separator_offset))
             }
         })
         .collect()
 }
 
+/// Splits the elements of a meta item into the name, equal sign (=) and value element groups.
+fn meta_item_parts(elems: Vec<SyntaxElement>) -> [Vec<SyntaxElement>; 3] {
+    let mut groups = elems
+        .into_iter()
+        // Equal sign (=) separated groups.
+        .group_by(|token| token.kind() == T![=])
+        .into_iter()
+        .map(|(_, group)| group.collect())
+        .collect::<Vec<Vec<SyntaxElement>>>()
+        .into_iter();
+    [
+        groups.next().unwrap_or_default(),
+        groups.next().unwrap_or_default(),
+        groups.next().unwrap_or_default(),
+    ]
+}
+
 fn only_non_trivia_elements(elems: &[SyntaxElement]) -> Vec<&SyntaxElement> {
     elems
         .iter()
@@ -125,6 +168,54 @@ mod tests {
     use ra_ap_syntax::SyntaxKind;
     use test_utils::quote_as_str;
 
+    #[test]
+    fn primary_ink_arg_works() {
+        for code in [
+            quote_as_str! {
+                #[ink::contract]
+            },
+            quote_as_str! {
+                #[ink()]
+            },
+            quote_as_str! {
+                #[ink(storage)]
+            },
+            quote_as_str! {
+                #[ink(payable, message, selector = 1)]
+            },
+            quote_as_str! {
+                #[ink(selector = _, constructor)]
+            },
+            quote_as_str! {
+                #[ink(anonymous, event)]
+            },
+            quote_as_str! {
+                #[ink(unknown, = 1, , payable)]
+            },
+            quote_as_str! {
+                #[ink(env = crate::Env, impl)]
+            },
+            quote_as_str! {
+                #[ink(a b, namespace = "my_namespace")]
+            },
+        ] {
+            let attr = parse_first_attribute(code);
+
+            // Returns the same "primary" argument as sorting all parsed arguments.
+            let args = parse_ink_args(&attr);
+            let expected = args
+                .iter()
+                .sorted()
+                .next()
+                .map(|arg| (*arg.kind(), arg.name().map(ToString::to_string)));
+            assert_eq!(
+                primary_ink_arg(&attr).map(|(kind, name)| (kind, name.map(|it| it.to_string()))),
+                expected,
+                "code: {code}"
+            );
+        }
+    }
+
     #[test]
     fn parse_ink_args_works() {
         for (code, expected_args) in [
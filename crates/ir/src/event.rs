@@ -1,11 +1,15 @@
 //! ink! event IR.
 
+use blake2::digest::consts::U32;
+use blake2::digest::Digest;
+use blake2::Blake2b;
 use ink_analyzer_macro::{FromInkAttribute, FromSyntax};
 use ra_ap_syntax::ast;
+use ra_ap_syntax::ast::HasName;
 
 use crate::traits::{FromInkAttribute, FromSyntax, IsInkStruct};
 use crate::tree::utils;
-use crate::{InkArg, InkArgKind, InkAttrData, InkAttribute, Topic};
+use crate::{Contract, InkArg, InkArgKind, InkAttrData, InkAttribute, Topic};
 
 /// An ink! event.
 #[derive(Debug, Clone, PartialEq, Eq, FromInkAttribute, FromSyntax)]
@@ -34,6 +38,46 @@ impl Event {
     pub fn topics(&self) -> &[Topic] {
         &self.topics
     }
+
+    /// Returns the signature topic of the ink! event (if any).
+    ///
+    /// Returns `None` for anonymous ink! events,
+    /// and for ink! events whose parent ink! contract storage name can't be determined.
+    ///
+    /// The signature topic is the SCALE encoded `PrefixedValue { prefix: b"", value: b"<Storage>::<Event>" }`
+    /// (i.e. a zero byte for the empty prefix followed by the path bytes),
+    /// zero-padded if it fits into 32 bytes, or its BLAKE-2b 256-bit hash otherwise.
+    ///
+    /// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/ink/codegen/src/generator/events.rs>.
+    ///
+    /// Ref: <https://github.com/paritytech/ink/blob/v4.2.0/crates/env/src/topics.rs>.
+    pub fn signature_topic(&self) -> Option<[u8; 32]> {
+        if self.anonymous_arg().is_some() {
+            return None;
+        }
+
+        let event_name = self.struct_item()?.name()?;
+        let storage_name = utils::ink_ancestors::<Contract>(self.syntax())
+            .next()?
+            .storage()?
+            .struct_item()?
+            .name()?;
+
+        // SCALE encoding of the empty prefix (i.e. compact encoded zero length) followed by the value bytes.
+        let mut encoded = vec![0u8];
+        encoded.extend(format!("{storage_name}::{event_name}").into_bytes());
+
+        let mut topic = [0u8; 32];
+        if encoded.len() <= topic.len() {
+            topic[..encoded.len()].copy_from_slice(&encoded);
+        } else {
+            // Computes the BLAKE-2b 256-bit hash for the encoded bytes.
+            let mut hasher = <Blake2b<U32>>::new();
+            hasher.update(encoded);
+            topic.copy_from_slice(&hasher.finalize());
+        }
+        Some(topic)
+    }
 }
 
 #[cfg(test)]
@@ -109,4 +153,75 @@ mod tests {
             assert!(event.struct_item().is_some());
         }
     }
+
+    #[test]
+    fn signature_topic_works() {
+        for (code, expected_topic) in [
+            // Signature topic fits into 32 bytes.
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod erc20 {
+                        #[ink(storage)]
+                        pub struct Erc20 {}
+
+                        #[ink(event)]
+                        pub struct Transfer {}
+                    }
+                },
+                Some("0045726332303a3a5472616e7366657200000000000000000000000000000000"),
+            ),
+            // Signature topic is hashed.
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod my_contract {
+                        #[ink(storage)]
+                        pub struct MyContractWithALongName {}
+
+                        #[ink(event)]
+                        pub struct MyEventWithALongName {}
+                    }
+                },
+                Some("e8390993b57a4163021164327284c447a91e6296a9435a21dc61168edaaefd10"),
+            ),
+            // Anonymous event.
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod erc20 {
+                        #[ink(storage)]
+                        pub struct Erc20 {}
+
+                        #[ink(event, anonymous)]
+                        pub struct Transfer {}
+                    }
+                },
+                None,
+            ),
+            // Missing storage.
+            (
+                quote_as_str! {
+                    #[ink::contract]
+                    mod erc20 {
+                        #[ink(event)]
+                        pub struct Transfer {}
+                    }
+                },
+                None,
+            ),
+        ] {
+            let file = crate::InkFile::parse(code);
+            let event = &file.contracts()[0].events()[0];
+
+            assert_eq!(
+                event.signature_topic().map(|topic| topic
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>()),
+                expected_topic.map(ToString::to_string),
+                "code: {code}"
+            );
+        }
+    }
 }
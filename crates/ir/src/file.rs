@@ -51,6 +51,28 @@ impl InkFile {
         Self::from(SourceFile::parse(code).tree())
     }
 
+    /// Returns true if the source code contains ink! entities
+    /// (i.e. ink! attributes like `#[ink::contract]`, `#[ink(storage)]` or `#[ink_e2e::test]`).
+    ///
+    /// This is a fast pre-scan of the source code (i.e. it doesn't parse the code or construct the ink! IR),
+    /// intended for cheaply skipping plain Rust files.
+    /// So it may return false positives (e.g. for ink! attributes in comments or string literals),
+    /// but never false negatives.
+    pub fn contains_ink_entities(code: &str) -> bool {
+        code.match_indices('#').any(|(idx, _)| {
+            code[idx + 1..]
+                .trim_start()
+                .strip_prefix('[')
+                .and_then(|rest| rest.trim_start().strip_prefix("ink"))
+                .map_or(false, |rest| {
+                    rest.strip_prefix("_e2e")
+                        .unwrap_or(rest)
+                        .trim_start()
+                        .starts_with(['(', ':', ']'])
+                })
+        })
+    }
+
     /// Returns ink! contracts in source file.
     pub fn contracts(&self) -> &[Contract] {
         &self.contracts
@@ -137,4 +159,34 @@ mod tests {
         // 2 tests.
         assert_eq!(file.tests().len(), 2);
     }
+
+    #[test]
+    fn contains_ink_entities_works() {
+        for (code, expected) in [
+            // Plain Rust.
+            ("", false),
+            ("fn main() {}", false),
+            ("#[cfg(test)]\nmod tests {}", false),
+            ("#![cfg_attr(not(feature = \"std\"), no_std)]", false),
+            ("#[inkwell::attr]\nfn my_fn() {}", false),
+            ("#[ink_env::attr]\nfn my_fn() {}", false),
+            ("use ink::prelude::vec::Vec;", false),
+            // ink! attribute macros.
+            ("#[ink::contract]\nmod my_contract {}", true),
+            ("#[ink::trait_definition]", true),
+            ("#[ink_e2e::test]", true),
+            ("# [ ink :: contract ]", true),
+            // ink! attribute arguments.
+            ("#[ink(storage)]\npub struct MyContract {}", true),
+            ("#[ink (message)]", true),
+            // Unknown ink! attributes.
+            ("#[ink]", true),
+        ] {
+            assert_eq!(
+                InkFile::contains_ink_entities(code),
+                expected,
+                "code: {code}"
+            );
+        }
+    }
 }
@@ -0,0 +1,412 @@
+//! ink! entity lookup index.
+
+use ra_ap_syntax::ast::HasName;
+use ra_ap_syntax::TextSize;
+use std::collections::HashMap;
+
+use crate::traits::{FromInkAttribute, FromSyntax, IsInkFn, IsInkStruct, IsInkTrait};
+use crate::tree::utils;
+use crate::{
+    ChainExtension, Constructor, Contract, Event, Extension, InkArgKind, InkAttribute,
+    InkAttributeKind, InkE2ETest, InkFile, InkMacroKind, InkTest, Message, Storage, StorageItem,
+    Topic, TraitDefinition,
+};
+
+/// An index of the ink! attributes and ink! entities in an ink! file,
+/// keyed by kind and name (where applicable).
+///
+/// The index is built with a single traversal of the file,
+/// so it's cheaper than repeated descendant walks when multiple queries are made for the same file.
+///
+/// Entities are indexed regardless of their position in the ink! entity tree
+/// (e.g. ink! messages outside an ink! contract are indexed too), in source order.
+#[derive(Debug, Clone, Default)]
+pub struct InkIndex {
+    /// ink! attributes (in source order).
+    attrs: Vec<InkAttribute>,
+    /// Indices of ink! attributes keyed by kind.
+    attrs_by_kind: HashMap<InkAttributeKind, Vec<usize>>,
+    /// ink! contracts.
+    contracts: Entities<Contract>,
+    /// ink! storage definitions.
+    storage: Vec<Storage>,
+    /// ink! events.
+    events: Entities<Event>,
+    /// ink! topics.
+    topics: Vec<Topic>,
+    /// ink! constructors.
+    constructors: Entities<Constructor>,
+    /// ink! messages.
+    messages: Entities<Message>,
+    /// ink! trait definitions.
+    trait_definitions: Entities<TraitDefinition>,
+    /// ink! chain extensions.
+    chain_extensions: Entities<ChainExtension>,
+    /// ink! extensions.
+    extensions: Entities<Extension>,
+    /// ink! storage items.
+    storage_items: Entities<StorageItem>,
+    /// ink! tests.
+    tests: Vec<InkTest>,
+    /// ink! e2e tests.
+    e2e_tests: Vec<InkE2ETest>,
+}
+
+/// ink! entities of a single type with a lookup table for their names.
+#[derive(Debug, Clone)]
+struct Entities<T> {
+    /// ink! entities (in source order).
+    items: Vec<T>,
+    /// Index of the first ink! entity with a given name.
+    names: HashMap<String, usize>,
+}
+
+impl<T> Default for Entities<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            names: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Entities<T> {
+    /// Adds an ink! entity with the given name (if any).
+    fn push(&mut self, item: T, name: Option<impl HasName>) {
+        if let Some(name) = name.and_then(|item| item.name()) {
+            self.names
+                .entry(name.to_string())
+                .or_insert(self.items.len());
+        }
+        self.items.push(item);
+    }
+
+    /// Returns the first ink! entity with the given name (if any).
+    fn by_name(&self, name: &str) -> Option<&T> {
+        self.names.get(name).map(|idx| &self.items[*idx])
+    }
+}
+
+impl InkIndex {
+    /// Builds an index of the ink! attributes and ink! entities in the ink! file.
+    pub fn new(file: &InkFile) -> Self {
+        let mut index = Self::default();
+        for attr in utils::ink_attrs_in_scope(file.syntax()) {
+            index
+                .attrs_by_kind
+                .entry(*attr.kind())
+                .or_default()
+                .push(index.attrs.len());
+            index.attrs.push(attr.clone());
+
+            match *attr.kind() {
+                InkAttributeKind::Macro(InkMacroKind::Contract) => {
+                    if let Some(contract) = Contract::cast(attr) {
+                        let name = contract.module().cloned();
+                        index.contracts.push(contract, name);
+                    }
+                }
+                InkAttributeKind::Macro(InkMacroKind::TraitDefinition) => {
+                    if let Some(trait_definition) = TraitDefinition::cast(attr) {
+                        let name = trait_definition.trait_item().cloned();
+                        index.trait_definitions.push(trait_definition, name);
+                    }
+                }
+                InkAttributeKind::Macro(InkMacroKind::ChainExtension) => {
+                    if let Some(chain_extension) = ChainExtension::cast(attr) {
+                        let name = chain_extension.trait_item().cloned();
+                        index.chain_extensions.push(chain_extension, name);
+                    }
+                }
+                InkAttributeKind::Macro(InkMacroKind::StorageItem) => {
+                    if let Some(storage_item) = StorageItem::cast(attr) {
+                        let name = storage_item.adt().cloned();
+                        index.storage_items.push(storage_item, name);
+                    }
+                }
+                InkAttributeKind::Macro(InkMacroKind::Test) => {
+                    index.tests.extend(InkTest::cast(attr));
+                }
+                InkAttributeKind::Macro(InkMacroKind::E2ETest) => {
+                    index.e2e_tests.extend(InkE2ETest::cast(attr));
+                }
+                InkAttributeKind::Arg(InkArgKind::Storage) => {
+                    index.storage.extend(Storage::cast(attr));
+                }
+                InkAttributeKind::Arg(InkArgKind::Event) => {
+                    if let Some(event) = Event::cast(attr) {
+                        let name = event.struct_item().cloned();
+                        index.events.push(event, name);
+                    }
+                }
+                InkAttributeKind::Arg(InkArgKind::Topic) => {
+                    index.topics.extend(Topic::cast(attr));
+                }
+                InkAttributeKind::Arg(InkArgKind::Constructor) => {
+                    if let Some(constructor) = Constructor::cast(attr) {
+                        let name = constructor.fn_item().cloned();
+                        index.constructors.push(constructor, name);
+                    }
+                }
+                InkAttributeKind::Arg(InkArgKind::Message) => {
+                    if let Some(message) = Message::cast(attr) {
+                        let name = message.fn_item().cloned();
+                        index.messages.push(message, name);
+                    }
+                }
+                InkAttributeKind::Arg(InkArgKind::Extension) => {
+                    if let Some(extension) = Extension::cast(attr) {
+                        let name = extension.fn_item().cloned();
+                        index.extensions.push(extension, name);
+                    }
+                }
+                _ => (),
+            }
+        }
+        index
+    }
+
+    /// Returns all ink! attributes (in source order).
+    pub fn attrs(&self) -> &[InkAttribute] {
+        &self.attrs
+    }
+
+    /// Returns ink! attributes of the given kind (in source order).
+    pub fn attrs_by_kind(&self, kind: InkAttributeKind) -> impl Iterator<Item = &InkAttribute> {
+        self.attrs_by_kind
+            .get(&kind)
+            .into_iter()
+            .flatten()
+            .map(|idx| &self.attrs[*idx])
+    }
+
+    /// Returns the ink! attribute whose text range contains the offset (if any).
+    pub fn attr_at_offset(&self, offset: TextSize) -> Option<&InkAttribute> {
+        // ink! attributes are in source order and don't overlap,
+        // so the only candidate is the last attribute that starts at or before the offset.
+        let idx = self
+            .attrs
+            .partition_point(|attr| attr.syntax().text_range().start() <= offset);
+        let attr = self.attrs.get(idx.checked_sub(1)?)?;
+        attr.syntax()
+            .text_range()
+            .contains_inclusive(offset)
+            .then_some(attr)
+    }
+
+    /// Returns ink! contracts.
+    pub fn contracts(&self) -> &[Contract] {
+        &self.contracts.items
+    }
+
+    /// Returns the first ink! contract with the given `mod` name (if any).
+    pub fn contract_by_name(&self, name: &str) -> Option<&Contract> {
+        self.contracts.by_name(name)
+    }
+
+    /// Returns ink! storage definitions.
+    pub fn storage(&self) -> &[Storage] {
+        &self.storage
+    }
+
+    /// Returns ink! events.
+    pub fn events(&self) -> &[Event] {
+        &self.events.items
+    }
+
+    /// Returns the first ink! event with the given `struct` name (if any).
+    pub fn event_by_name(&self, name: &str) -> Option<&Event> {
+        self.events.by_name(name)
+    }
+
+    /// Returns ink! topics.
+    pub fn topics(&self) -> &[Topic] {
+        &self.topics
+    }
+
+    /// Returns ink! constructors.
+    pub fn constructors(&self) -> &[Constructor] {
+        &self.constructors.items
+    }
+
+    /// Returns the first ink! constructor with the given `fn` name (if any).
+    pub fn constructor_by_name(&self, name: &str) -> Option<&Constructor> {
+        self.constructors.by_name(name)
+    }
+
+    /// Returns ink! messages.
+    pub fn messages(&self) -> &[Message] {
+        &self.messages.items
+    }
+
+    /// Returns the first ink! message with the given `fn` name (if any).
+    pub fn message_by_name(&self, name: &str) -> Option<&Message> {
+        self.messages.by_name(name)
+    }
+
+    /// Returns ink! trait definitions.
+    pub fn trait_definitions(&self) -> &[TraitDefinition] {
+        &self.trait_definitions.items
+    }
+
+    /// Returns the first ink! trait definition with the given `trait` name (if any).
+    pub fn trait_definition_by_name(&self, name: &str) -> Option<&TraitDefinition> {
+        self.trait_definitions.by_name(name)
+    }
+
+    /// Returns ink! chain extensions.
+    pub fn chain_extensions(&self) -> &[ChainExtension] {
+        &self.chain_extensions.items
+    }
+
+    /// Returns the first ink! chain extension with the given `trait` name (if any).
+    pub fn chain_extension_by_name(&self, name: &str) -> Option<&ChainExtension> {
+        self.chain_extensions.by_name(name)
+    }
+
+    /// Returns ink! extensions.
+    pub fn extensions(&self) -> &[Extension] {
+        &self.extensions.items
+    }
+
+    /// Returns the first ink! extension with the given `fn` name (if any).
+    pub fn extension_by_name(&self, name: &str) -> Option<&Extension> {
+        self.extensions.by_name(name)
+    }
+
+    /// Returns ink! storage items.
+    pub fn storage_items(&self) -> &[StorageItem] {
+        &self.storage_items.items
+    }
+
+    /// Returns the first ink! storage item with the given `struct`, `enum` or `union` name (if any).
+    pub fn storage_item_by_name(&self, name: &str) -> Option<&StorageItem> {
+        self.storage_items.by_name(name)
+    }
+
+    /// Returns ink! tests.
+    pub fn tests(&self) -> &[InkTest] {
+        &self.tests
+    }
+
+    /// Returns ink! e2e tests.
+    pub fn e2e_tests(&self) -> &[InkE2ETest] {
+        &self.e2e_tests
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ink_index_works() {
+        let code = r#"
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                #[ink(event)]
+                pub struct MyEvent {
+                    #[ink(topic)]
+                    value: bool,
+                }
+
+                impl MyContract {
+                    #[ink(constructor)]
+                    pub fn new() -> Self {}
+
+                    #[ink(message)]
+                    pub fn get(&self) {}
+
+                    #[ink(message, payable)]
+                    pub fn set(&mut self) {}
+                }
+            }
+
+            #[ink::trait_definition]
+            pub trait MyTrait {
+                #[ink(message)]
+                fn get(&self);
+            }
+
+            #[ink::chain_extension]
+            pub trait MyChainExtension {
+                type ErrorCode = ();
+
+                #[ink(extension = 1)]
+                fn my_extension();
+            }
+
+            #[ink::storage_item]
+            enum MyStorageItem {}
+        "#;
+        let index = InkIndex::new(&InkFile::parse(code));
+
+        // ink! attributes.
+        assert_eq!(index.attrs().len(), 12);
+        assert_eq!(
+            index
+                .attrs_by_kind(InkAttributeKind::Arg(InkArgKind::Message))
+                .count(),
+            3
+        );
+        assert_eq!(
+            index
+                .attrs_by_kind(InkAttributeKind::Macro(InkMacroKind::Test))
+                .count(),
+            0
+        );
+
+        // ink! entities.
+        assert_eq!(index.contracts().len(), 1);
+        assert_eq!(index.storage().len(), 1);
+        assert_eq!(index.events().len(), 1);
+        assert_eq!(index.topics().len(), 1);
+        assert_eq!(index.constructors().len(), 1);
+        assert_eq!(index.messages().len(), 3);
+        assert_eq!(index.trait_definitions().len(), 1);
+        assert_eq!(index.chain_extensions().len(), 1);
+        assert_eq!(index.extensions().len(), 1);
+        assert_eq!(index.storage_items().len(), 1);
+        assert!(index.tests().is_empty());
+        assert!(index.e2e_tests().is_empty());
+
+        // Lookups by name.
+        assert!(index.contract_by_name("my_contract").is_some());
+        assert!(index.event_by_name("MyEvent").is_some());
+        assert!(index.event_by_name("MyContract").is_none());
+        assert!(index.constructor_by_name("new").is_some());
+        assert!(index.message_by_name("set").is_some());
+        // The first message with a name is returned.
+        assert_eq!(index.message_by_name("get"), index.messages().first());
+        assert!(index.trait_definition_by_name("MyTrait").is_some());
+        assert!(index.chain_extension_by_name("MyChainExtension").is_some());
+        assert!(index.extension_by_name("my_extension").is_some());
+        assert!(index.storage_item_by_name("MyStorageItem").is_some());
+        assert!(index.message_by_name("unknown").is_none());
+
+        // ink! attributes at offsets.
+        for (pat, expected_attr) in [
+            (Some("<-#[ink::contract]"), Some("#[ink::contract]")),
+            (Some("#[ink::contract"), Some("#[ink::contract]")),
+            (Some("#[ink(message, pay"), Some("#[ink(message, payable)]")),
+            (Some("#[ink(extension = 1)]"), Some("#[ink(extension = 1)]")),
+            (Some("mod my_"), None),
+            (None, None),
+        ] {
+            let offset = TextSize::from(
+                pat.and_then(|pat| test_utils::parse_offset_at(code, Some(pat)))
+                    .unwrap_or(code.len()) as u32,
+            );
+            assert_eq!(
+                index
+                    .attr_at_offset(offset)
+                    .map(|attr| attr.syntax().to_string()),
+                expected_attr.map(ToString::to_string),
+                "pat: {pat:?}"
+            );
+        }
+    }
+}
@@ -60,31 +60,71 @@ impl InkImpl {
     }
 
     /// Returns the ink! trait definition (if any) for the ink! impl.
+    ///
+    /// The ink! trait definition is resolved by name from the current file,
+    /// using the path qualifier of the trait type (if any) to match the parent modules of the trait definition
+    /// (e.g. `impl traits::MyTrait for MyContract` matches `mod traits { #[ink::trait_definition] pub trait MyTrait {} }`).
     pub fn trait_definition(&self) -> Option<TraitDefinition> {
-        self.trait_type()
-            .and_then(|trait_type| match trait_type {
-                ast::Type::PathType(path_type) => path_type.path(),
-                _ => None,
+        let path = match self.trait_type()? {
+            ast::Type::PathType(path_type) => path_type.path()?,
+            _ => return None,
+        };
+        let name_ref = path.segment()?.name_ref()?;
+        // Module names in the path qualifier (if any), excluding `crate`, `self` and `super` keywords.
+        // NOTE: `ast::Path::segments` always yields all segments of the full path (i.e. including the trait name),
+        // so the qualifier segments are collected by walking the qualifiers instead.
+        let mut qualifier: Vec<String> = path
+            .qualifiers()
+            .filter_map(|qualifier| qualifier.segment()?.name_ref())
+            .map(|name_ref| name_ref.to_string())
+            .filter(|name| !matches!(name.as_str(), "crate" | "self" | "super"))
+            .collect();
+        qualifier.reverse();
+        let source = self.syntax().ancestors().last()?;
+
+        // Collects ink! trait definitions with a matching name and their parent module names.
+        let candidates: Vec<(Vec<String>, TraitDefinition)> = source
+            .descendants()
+            .filter_map(ast::Trait::cast)
+            .filter(|trait_item| {
+                trait_item
+                    .name()
+                    .map_or(false, |trait_name| trait_name.text() == name_ref.text())
             })
-            .and_then(|path| {
-                path.segment()
-                    .and_then(|path_segment| path_segment.name_ref())
+            .filter_map(|trait_item| {
+                let trait_definition = utils::ink_attrs(trait_item.syntax())
+                    .find_map(|attr| TraitDefinition::cast(attr.clone()))?;
+                let mut module_path: Vec<String> = trait_item
+                    .syntax()
+                    .ancestors()
+                    .filter_map(ast::Module::cast)
+                    .filter_map(|module| module.name().as_ref().map(ToString::to_string))
+                    .collect();
+                module_path.reverse();
+                Some((module_path, trait_definition))
             })
-            .zip(self.syntax().ancestors().last())
-            .and_then(|(name_ref, source)| {
-                source.children().find_map(|child| {
-                    ast::Trait::cast(child.clone())
-                        .filter(|trait_item| {
-                            trait_item
-                                .name()
-                                .map_or(false, |trait_name| trait_name.text() == name_ref.text())
-                        })
-                        .and_then(|trait_item| {
-                            utils::ink_attrs(trait_item.syntax())
-                                .find_map(|attr| TraitDefinition::cast(attr.clone()))
-                        })
+            .collect();
+
+        if qualifier.is_empty() {
+            // Prefers root level ink! trait definitions,
+            // otherwise falls back to the only ink! trait definition with a matching name (if any).
+            let mut candidates = candidates;
+            match candidates
+                .iter()
+                .position(|(module_path, _)| module_path.is_empty())
+            {
+                Some(idx) => Some(candidates.swap_remove(idx).1),
+                None => (candidates.len() == 1).then(|| candidates.remove(0).1),
+            }
+        } else {
+            candidates
+                .into_iter()
+                .find_map(|(module_path, trait_definition)| {
+                    module_path
+                        .ends_with(&qualifier)
+                        .then_some(trait_definition)
                 })
-            })
+        }
     }
 
     /// Returns the ink! impl attribute (if any).
@@ -170,6 +210,48 @@ mod tests {
                 1,
                 true,
             ),
+            (
+                quote_as_str! {
+                    mod traits {
+                        #[ink::trait_definition]
+                        pub trait MyTrait {
+                            #[ink(message)]
+                            fn my_message(&self);
+                        }
+                    }
+
+                    impl traits::MyTrait for MyContract {
+                        #[ink(message)]
+                        fn my_message(&self) {}
+                    }
+                },
+                false,
+                false,
+                0,
+                1,
+                true,
+            ),
+            (
+                quote_as_str! {
+                    mod traits {
+                        #[ink::trait_definition]
+                        pub trait MyTrait {
+                            #[ink(message)]
+                            fn my_message(&self);
+                        }
+                    }
+
+                    impl other::MyTrait for MyContract {
+                        #[ink(message)]
+                        fn my_message(&self) {}
+                    }
+                },
+                false,
+                false,
+                0,
+                1,
+                false,
+            ),
             (
                 quote_as_str! {
                     impl ::my_full::long_path::MyTrait for MyContract {
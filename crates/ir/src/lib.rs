@@ -40,8 +40,9 @@
 
 pub use self::{
     attrs::{
-        meta, InkArg, InkArgKind, InkArgValueKind, InkArgValuePathKind, InkArgValueStringKind,
-        InkAttrData, InkAttribute, InkAttributeKind, InkMacroKind,
+        meta, InkArg, InkArgKind, InkArgValueError, InkArgValueKind, InkArgValuePathKind,
+        InkArgValueStringKind, InkAttrData, InkAttribute, InkAttributeKind, InkMacroKind,
+        KeepAttrEntry,
     },
     chain_extension::ChainExtension,
     constructor::Constructor,
@@ -49,6 +50,7 @@ pub use self::{
     event::Event,
     extension::Extension,
     file::InkFile,
+    index::InkIndex,
     ink_e2e_test::InkE2ETest,
     ink_impl::InkImpl,
     ink_test::InkTest,
@@ -73,6 +75,10 @@ pub use self::{
         ink_impl_closest_descendants, ink_parent,
     },
     tree::{InkTree, ItemAtOffset},
+    visitor::{
+        walk_chain_extension, walk_contract, walk_event, walk_impl, walk_ink_file,
+        walk_trait_definition, InkVisitor,
+    },
 };
 
 // Re-export ra_ap_syntax as syntax.
@@ -88,6 +94,7 @@ mod contract;
 mod event;
 mod extension;
 mod file;
+mod index;
 mod ink_e2e_test;
 mod ink_impl;
 mod ink_test;
@@ -96,6 +103,7 @@ mod storage;
 mod storage_item;
 mod topic;
 mod trait_definition;
+mod visitor;
 
 mod iter;
 mod selector;
@@ -6,9 +6,9 @@ use blake2::Blake2b;
 use ra_ap_syntax::ast::HasName;
 use ra_ap_syntax::{ast, AstNode, SyntaxKind, TextRange};
 
-use crate::traits::{IsInkCallable, IsInkImplItem};
-use crate::tree::utils;
-use crate::{InkArg, InkArgKind};
+use crate::traits::{FromSyntax, IsInkCallable, IsInkImplItem};
+use crate::tree::{ast_ext, utils};
+use crate::{InkArg, InkArgKind, InkAttributeKind, InkImpl, InkMacroKind};
 
 /// The selector of an ink! callable entity.
 ///
@@ -28,38 +28,51 @@ impl Selector {
     where
         T: IsInkCallable,
     {
-        let selector_bytes: Option<[u8; 4]> = match Self::provided_int_selector(callable) {
+        match Self::provided_int_selector(callable) {
             // Manually provided integer selector is converted into bytes.
-            Some(manual_int_selector) => Some(manual_int_selector.to_be_bytes()),
-            // Otherwise the selector has to be computed, but only if the callable is a valid `fn` item.
-            None => {
-                Self::ident(callable).map(|callable_ident| {
-                    let trait_ident = Self::trait_ident(callable);
-                    let namespace = Self::namespace(callable);
-
-                    let pre_hash_bytes = [namespace, trait_ident, Some(callable_ident)]
-                        .into_iter()
-                        .flatten()
-                        .collect::<Vec<String>>()
-                        .join("::")
-                        .into_bytes();
-
-                    // Computes the BLAKE-2b 256-bit hash for the given input and stores it in output.
-                    let mut hasher = <Blake2b<U32>>::new();
-                    hasher.update(pre_hash_bytes);
-                    let hashed_bytes = hasher.finalize();
-
-                    [
-                        hashed_bytes[0],
-                        hashed_bytes[1],
-                        hashed_bytes[2],
-                        hashed_bytes[3],
-                    ]
-                })
-            }
-        };
+            Some(manual_int_selector) => Some(Self(manual_int_selector.to_be_bytes())),
+            // Otherwise the selector has to be computed.
+            None => Self::derive(callable),
+        }
+    }
 
-        selector_bytes.map(Self)
+    /// Returns the derived selector of the ink! callable entity
+    /// (i.e. the selector computed from the callable's identifier, parent trait and namespace,
+    /// ignoring any manually provided selector).
+    ///
+    /// For ink! messages declared in an ink! trait definition, this is the selector
+    /// the message will have in implementations of the ink! trait definition
+    /// (i.e. computed from the trait identifier and the trait definition's namespace).
+    ///
+    /// Ref: <https://github.com/paritytech/ink/blob/master/crates/ink/ir/src/ir/selector.rs#L74-L126>.
+    pub fn derive<T>(callable: &T) -> Option<Self>
+    where
+        T: IsInkCallable,
+    {
+        // The selector can only be computed if the callable is a valid `fn` item.
+        Self::ident(callable).map(|callable_ident| {
+            let trait_ident = Self::trait_ident(callable);
+            let namespace = Self::namespace(callable);
+
+            let pre_hash_bytes = [namespace, trait_ident, Some(callable_ident)]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<String>>()
+                .join("::")
+                .into_bytes();
+
+            // Computes the BLAKE-2b 256-bit hash for the given input and stores it in output.
+            let mut hasher = <Blake2b<U32>>::new();
+            hasher.update(pre_hash_bytes);
+            let hashed_bytes = hasher.finalize();
+
+            Self([
+                hashed_bytes[0],
+                hashed_bytes[1],
+                hashed_bytes[2],
+                hashed_bytes[3],
+            ])
+        })
     }
 
     /// Returns the underlying four bytes.
@@ -95,7 +108,13 @@ impl Selector {
     where
         T: IsInkCallable,
     {
-        match callable.impl_item()?.trait_()? {
+        let Some(impl_item) = callable.impl_item() else {
+            // ink! trait definition messages use the identifier of the trait definition.
+            return Self::trait_item(callable)?
+                .name()
+                .map(|name| name.to_string());
+        };
+        match impl_item.trait_()? {
             ast::Type::PathType(trait_path_type) => {
                 let trait_path = trait_path_type.path()?;
                 let is_full_path = trait_path.to_string().starts_with("::");
@@ -115,15 +134,47 @@ impl Selector {
         }
     }
 
-    /// Returns the identifier for callable's parent ink! impl namespace argument (if any).
+    /// Returns the identifier for callable's parent ink! impl namespace argument
+    /// (or parent ink! trait definition namespace argument for ink! trait definition messages
+    /// and implementations of ink! trait definitions in the same file) (if any).
     fn namespace<T>(callable: &T) -> Option<String>
     where
         T: IsInkCallable,
     {
-        utils::ink_arg_by_kind(callable.impl_item()?.syntax(), InkArgKind::Namespace)?
+        let parent = match callable.impl_item() {
+            // Implementations of ink! trait definitions use the namespace of the ink! trait definition.
+            Some(impl_item) => match impl_item
+                .trait_()
+                .and(InkImpl::cast(impl_item.syntax().clone()))
+                .and_then(|ink_impl| ink_impl.trait_definition())
+            {
+                Some(trait_definition) => trait_definition.syntax().clone(),
+                None => impl_item.syntax().clone(),
+            },
+            None => Self::trait_item(callable)?.syntax().clone(),
+        };
+        utils::ink_arg_by_kind(&parent, InkArgKind::Namespace)?
             .value()?
             .as_string()
     }
+
+    /// Returns the parent `trait` item of the callable (if any)
+    /// (i.e. for ink! messages declared in an ink! trait definition).
+    fn trait_item<T>(callable: &T) -> Option<ast::Trait>
+    where
+        T: IsInkCallable,
+    {
+        match ast_ext::parent_ast_item(callable.fn_item()?.syntax())? {
+            ast::Item::Trait(item)
+                if utils::ink_attrs(item.syntax()).any(|attr| {
+                    *attr.kind() == InkAttributeKind::Macro(InkMacroKind::TraitDefinition)
+                }) =>
+            {
+                Some(item)
+            }
+            _ => None,
+        }
+    }
 }
 
 /// An ink! selector argument.
@@ -353,6 +404,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn compose_trait_definition_message_works() {
+        for (code, expected_message_selector) in [
+            (
+                quote_as_str! {
+                    #[ink::trait_definition]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self);
+                    }
+                },
+                0x04C49446, // First 4-bytes of Blake2b-256 hash of "MyTrait::my_message"
+            ),
+            (
+                quote_as_str! {
+                    #[ink::trait_definition(namespace="my_namespace")]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self);
+                    }
+                },
+                0x84452B5E, // First 4-bytes of Blake2b-256 hash of "my_namespace::MyTrait::my_message"
+            ),
+            (
+                quote_as_str! {
+                    #[ink::trait_definition(namespace="my_namespace")]
+                    pub trait MyTrait {
+                        #[ink(message, selector=0xA)]
+                        fn my_message(&self);
+                    }
+                },
+                0x0000000A,
+            ),
+            // Implementations use the namespace of the ink! trait definition.
+            (
+                quote_as_str! {
+                    impl MyTrait for MyContract {
+                        #[ink(message)]
+                        fn my_message(&self) {}
+                    }
+
+                    #[ink::trait_definition(namespace="my_namespace")]
+                    pub trait MyTrait {
+                        #[ink(message)]
+                        fn my_message(&self);
+                    }
+                },
+                0x84452B5E, // First 4-bytes of Blake2b-256 hash of "my_namespace::MyTrait::my_message"
+            ),
+        ] {
+            // Parse ink! message.
+            let message: Message = first_ink_entity_of_type(code);
+
+            // Check selector.
+            assert_eq!(
+                Selector::compose(&message).unwrap().into_be_u32(),
+                expected_message_selector,
+                "code: {code}"
+            );
+        }
+    }
+
     #[test]
     fn cast_arg_works() {
         for (code, expected_kind, expected_is_wildcard, expected_u32_value) in [
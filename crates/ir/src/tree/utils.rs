@@ -188,14 +188,12 @@ pub fn ink_impl_closest_descendants(node: &SyntaxNode) -> impl Iterator<Item = I
                 ast_ext::parent_ast_item(attr.syntax()).map(|item| item.syntax().clone())
             } else if Constructor::can_cast(&attr) {
                 // impl parent of ink! constructor closest descendant.
-                Constructor::cast(attr)
-                    .expect("Should be able to cast")
+                Constructor::cast(attr)?
                     .impl_item()
                     .map(|item| item.syntax().clone())
             } else if Message::can_cast(&attr) {
                 // impl parent of ink! message closest descendant.
-                Message::cast(attr)
-                    .expect("Should be able to cast")
+                Message::cast(attr)?
                     .impl_item()
                     .map(|item| item.syntax().clone())
             } else {
@@ -245,13 +243,15 @@ where
     ink_attrs_closest_descendants(node)
         .flat_map(move |attr| {
             if T::can_cast(&attr) {
-                vec![T::cast(attr).expect("Should be able to cast")]
+                T::cast(attr).into_iter().collect::<Vec<_>>()
             } else if is_peekable_ancestor(&attr) {
-                ink_attrs_closest_descendants(
-                    <InkAttrData<ast::Impl> as From<_>>::from(attr).parent_syntax(),
-                )
-                .filter_map(T::cast)
-                .collect()
+                InkAttrData::<ast::Impl>::try_from(attr)
+                    .map(|data| {
+                        ink_attrs_closest_descendants(data.parent_syntax())
+                            .filter_map(T::cast)
+                            .collect()
+                    })
+                    .unwrap_or_default()
             } else {
                 Vec::new()
             }
@@ -0,0 +1,387 @@
+//! A visitor/walker API for traversing the ink! IR.
+//!
+//! # Example
+//! Count the ink! messages in a file.
+//!
+//! ```
+//! use ink_analyzer_ir::{walk_ink_file, InkFile, InkVisitor, Message};
+//!
+//! #[derive(Default)]
+//! struct MessageCounter(usize);
+//!
+//! impl InkVisitor for MessageCounter {
+//!     fn visit_message(&mut self, _: &Message) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! let file = InkFile::parse(r#"
+//!     #[ink::contract]
+//!     mod my_contract {
+//!         impl MyContract {
+//!             #[ink(message)]
+//!             pub fn my_message(&self) {}
+//!         }
+//!     }
+//! "#);
+//! let mut counter = MessageCounter::default();
+//! walk_ink_file(&mut counter, &file);
+//! assert_eq!(counter.0, 1);
+//! ```
+
+use crate::{
+    ChainExtension, Constructor, Contract, Event, Extension, InkE2ETest, InkFile, InkImpl, InkTest,
+    Message, Storage, StorageItem, Topic, TraitDefinition,
+};
+
+/// A visitor for ink! entities.
+///
+/// Each method is called when the walker reaches an ink! entity of the corresponding type.
+/// The default implementations of methods for entities with child ink! entities
+/// (e.g. [`InkVisitor::visit_contract`]) call the corresponding `walk_*` function
+/// (e.g. [`walk_contract`]) to visit the children,
+/// so overriding implementations should call the `walk_*` function to keep traversing the children (if needed).
+///
+/// Entities are visited in source order within each entity type,
+/// and each ink! entity is visited at most once.
+pub trait InkVisitor {
+    /// Visits an ink! contract (and its children by default).
+    fn visit_contract(&mut self, contract: &Contract) {
+        walk_contract(self, contract);
+    }
+
+    /// Visits an ink! storage definition.
+    fn visit_storage(&mut self, _storage: &Storage) {}
+
+    /// Visits an ink! event (and its topics by default).
+    fn visit_event(&mut self, event: &Event) {
+        walk_event(self, event);
+    }
+
+    /// Visits an ink! topic.
+    fn visit_topic(&mut self, _topic: &Topic) {}
+
+    /// Visits an ink! impl block (and its constructors and messages by default).
+    fn visit_impl(&mut self, ink_impl: &InkImpl) {
+        walk_impl(self, ink_impl);
+    }
+
+    /// Visits an ink! constructor.
+    fn visit_constructor(&mut self, _constructor: &Constructor) {}
+
+    /// Visits an ink! message.
+    fn visit_message(&mut self, _message: &Message) {}
+
+    /// Visits an ink! trait definition (and its messages by default).
+    fn visit_trait_definition(&mut self, trait_definition: &TraitDefinition) {
+        walk_trait_definition(self, trait_definition);
+    }
+
+    /// Visits an ink! chain extension (and its extensions by default).
+    fn visit_chain_extension(&mut self, chain_extension: &ChainExtension) {
+        walk_chain_extension(self, chain_extension);
+    }
+
+    /// Visits an ink! extension.
+    fn visit_extension(&mut self, _extension: &Extension) {}
+
+    /// Visits an ink! storage item.
+    fn visit_storage_item(&mut self, _storage_item: &StorageItem) {}
+
+    /// Visits an ink! test.
+    fn visit_test(&mut self, _test: &InkTest) {}
+
+    /// Visits an ink! e2e test.
+    fn visit_e2e_test(&mut self, _e2e_test: &InkE2ETest) {}
+}
+
+/// Walks the ink! entities in an ink! file (i.e. contracts, trait definitions, chain extensions,
+/// storage items, tests and e2e tests) and their children.
+pub fn walk_ink_file<V: InkVisitor + ?Sized>(visitor: &mut V, file: &InkFile) {
+    for contract in file.contracts() {
+        visitor.visit_contract(contract);
+    }
+    for trait_definition in file.trait_definitions() {
+        visitor.visit_trait_definition(trait_definition);
+    }
+    for chain_extension in file.chain_extensions() {
+        visitor.visit_chain_extension(chain_extension);
+    }
+    for storage_item in file.storage_items() {
+        visitor.visit_storage_item(storage_item);
+    }
+    for test in file.tests() {
+        visitor.visit_test(test);
+    }
+    for e2e_test in file.e2e_tests() {
+        visitor.visit_e2e_test(e2e_test);
+    }
+}
+
+/// Walks the children of an ink! contract (i.e. storage, events, impls, tests and e2e tests).
+///
+/// ink! constructors and messages are visited via their parent ink! impl block,
+/// except for those that aren't in any ink! impl block (e.g. free functions in the contract `mod` item),
+/// which are visited directly.
+pub fn walk_contract<V: InkVisitor + ?Sized>(visitor: &mut V, contract: &Contract) {
+    if let Some(storage) = contract.storage() {
+        visitor.visit_storage(storage);
+    }
+    for event in contract.events() {
+        visitor.visit_event(event);
+    }
+    for ink_impl in contract.impls() {
+        visitor.visit_impl(ink_impl);
+    }
+    for constructor in contract.constructors().iter().filter(|constructor| {
+        !contract
+            .impls()
+            .iter()
+            .any(|ink_impl| ink_impl.constructors().contains(constructor))
+    }) {
+        visitor.visit_constructor(constructor);
+    }
+    for message in contract.messages().iter().filter(|message| {
+        !contract
+            .impls()
+            .iter()
+            .any(|ink_impl| ink_impl.messages().contains(message))
+    }) {
+        visitor.visit_message(message);
+    }
+    for test in contract.tests() {
+        visitor.visit_test(test);
+    }
+    for e2e_test in contract.e2e_tests() {
+        visitor.visit_e2e_test(e2e_test);
+    }
+}
+
+/// Walks the children of an ink! event (i.e. topics).
+pub fn walk_event<V: InkVisitor + ?Sized>(visitor: &mut V, event: &Event) {
+    for topic in event.topics() {
+        visitor.visit_topic(topic);
+    }
+}
+
+/// Walks the children of an ink! impl block (i.e. constructors and messages).
+pub fn walk_impl<V: InkVisitor + ?Sized>(visitor: &mut V, ink_impl: &InkImpl) {
+    for constructor in ink_impl.constructors() {
+        visitor.visit_constructor(constructor);
+    }
+    for message in ink_impl.messages() {
+        visitor.visit_message(message);
+    }
+}
+
+/// Walks the children of an ink! trait definition (i.e. messages).
+pub fn walk_trait_definition<V: InkVisitor + ?Sized>(
+    visitor: &mut V,
+    trait_definition: &TraitDefinition,
+) {
+    for message in trait_definition.messages() {
+        visitor.visit_message(message);
+    }
+}
+
+/// Walks the children of an ink! chain extension (i.e. extensions).
+pub fn walk_chain_extension<V: InkVisitor + ?Sized>(
+    visitor: &mut V,
+    chain_extension: &ChainExtension,
+) {
+    for extension in chain_extension.extensions() {
+        visitor.visit_extension(extension);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IsInkFn, IsInkStruct, IsInkTrait};
+    use ra_ap_syntax::ast::HasName;
+    use test_utils::quote_as_str;
+
+    /// Records the names of visited entities.
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl Recorder {
+        fn record(&mut self, kind: &str, name: Option<impl ToString>) {
+            self.0.push(format!(
+                "{kind}:{}",
+                name.map(|name| name.to_string()).unwrap_or_default()
+            ));
+        }
+    }
+
+    impl InkVisitor for Recorder {
+        fn visit_contract(&mut self, contract: &Contract) {
+            self.record("contract", contract.module().and_then(HasName::name));
+            walk_contract(self, contract);
+        }
+
+        fn visit_storage(&mut self, storage: &Storage) {
+            self.record("storage", storage.struct_item().and_then(HasName::name));
+        }
+
+        fn visit_event(&mut self, event: &Event) {
+            self.record("event", event.struct_item().and_then(HasName::name));
+            walk_event(self, event);
+        }
+
+        fn visit_topic(&mut self, topic: &Topic) {
+            self.record("topic", topic.field().and_then(HasName::name));
+        }
+
+        fn visit_impl(&mut self, ink_impl: &InkImpl) {
+            self.record("impl", None::<String>);
+            walk_impl(self, ink_impl);
+        }
+
+        fn visit_constructor(&mut self, constructor: &Constructor) {
+            self.record("constructor", constructor.fn_item().and_then(HasName::name));
+        }
+
+        fn visit_message(&mut self, message: &Message) {
+            self.record("message", message.fn_item().and_then(HasName::name));
+        }
+
+        fn visit_trait_definition(&mut self, trait_definition: &TraitDefinition) {
+            self.record(
+                "trait_definition",
+                trait_definition.trait_item().and_then(HasName::name),
+            );
+            walk_trait_definition(self, trait_definition);
+        }
+
+        fn visit_chain_extension(&mut self, chain_extension: &ChainExtension) {
+            self.record(
+                "chain_extension",
+                chain_extension.trait_item().and_then(HasName::name),
+            );
+            walk_chain_extension(self, chain_extension);
+        }
+
+        fn visit_extension(&mut self, extension: &Extension) {
+            self.record("extension", extension.fn_item().and_then(HasName::name));
+        }
+
+        fn visit_storage_item(&mut self, storage_item: &StorageItem) {
+            self.record("storage_item", storage_item.adt().and_then(HasName::name));
+        }
+
+        fn visit_test(&mut self, test: &InkTest) {
+            self.record("test", test.fn_item().and_then(HasName::name));
+        }
+
+        fn visit_e2e_test(&mut self, e2e_test: &InkE2ETest) {
+            self.record("e2e_test", e2e_test.fn_item().and_then(HasName::name));
+        }
+    }
+
+    #[test]
+    fn walk_ink_file_works() {
+        let file = InkFile::parse(quote_as_str! {
+            #[ink::contract]
+            mod my_contract {
+                #[ink(storage)]
+                pub struct MyContract {}
+
+                #[ink(event)]
+                pub struct MyEvent {
+                    #[ink(topic)]
+                    value: bool,
+                }
+
+                impl MyContract {
+                    #[ink(constructor)]
+                    pub fn my_constructor() -> Self {}
+
+                    #[ink(message)]
+                    pub fn my_message(&self) {}
+                }
+
+                #[ink(message)]
+                pub fn orphan_message(&self) {}
+
+                #[cfg(test)]
+                mod tests {
+                    #[ink::test]
+                    fn it_works() {}
+                }
+            }
+
+            #[ink::trait_definition]
+            pub trait MyTrait {
+                #[ink(message)]
+                fn my_trait_message(&self);
+            }
+
+            #[ink::chain_extension]
+            pub trait MyChainExtension {
+                type ErrorCode = ();
+
+                #[ink(extension = 1)]
+                fn my_extension();
+            }
+
+            #[ink::storage_item]
+            struct MyStorageItem {}
+
+            #[ink_e2e::test]
+            async fn it_works(mut client: ::ink_e2e::Client<C, E>) -> E2EResult<()> {}
+        });
+
+        let mut recorder = Recorder::default();
+        walk_ink_file(&mut recorder, &file);
+        assert_eq!(
+            recorder.0,
+            vec![
+                "contract:my_contract",
+                "storage:MyContract",
+                "event:MyEvent",
+                "topic:value",
+                "impl:",
+                "constructor:my_constructor",
+                "message:my_message",
+                "message:orphan_message",
+                "test:it_works",
+                "trait_definition:MyTrait",
+                "message:my_trait_message",
+                "chain_extension:MyChainExtension",
+                "extension:my_extension",
+                "storage_item:MyStorageItem",
+                "e2e_test:it_works",
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_can_be_pruned() {
+        // Doesn't walk the children of contracts.
+        struct ContractsOnly(usize);
+
+        impl InkVisitor for ContractsOnly {
+            fn visit_contract(&mut self, _: &Contract) {
+                self.0 += 1;
+            }
+
+            fn visit_message(&mut self, _: &Message) {
+                unreachable!("contract children should not be visited");
+            }
+        }
+
+        let file = InkFile::parse(quote_as_str! {
+            #[ink::contract]
+            mod my_contract {
+                impl MyContract {
+                    #[ink(message)]
+                    pub fn my_message(&self) {}
+                }
+            }
+        });
+        let mut visitor = ContractsOnly(0);
+        walk_ink_file(&mut visitor, &file);
+        assert_eq!(visitor.0, 1);
+    }
+}
@@ -0,0 +1,512 @@
+//! A command-line interface (CLI) for running ink! analyzer diagnostics over files and directories
+//! (e.g. in CI pipelines).
+
+use clap::{Parser, Subcommand, ValueEnum};
+use ink_analyzer::{json, Analysis, FileReport, LineIndex, PositionEncoding, SarifFile, Severity};
+use std::collections::{hash_map::Entry, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// ink! analyzer CLI.
+#[derive(Debug, Parser)]
+#[command(name = "ink-analyzer")]
+#[command(version, about = "Runs ink! analyzer diagnostics over ink! smart contract files and directories.", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Files and/or directories to analyze (directories are searched recursively for ink! contract crates).
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = Format::Human)]
+    format: Format,
+    /// Exit with a failure status if there are any warnings (not just errors).
+    #[arg(long)]
+    deny_warnings: bool,
+}
+
+/// ink! analyzer CLI commands (i.e. instead of running diagnostics).
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Verifies that the installed ink! analyzer behaves as expected
+    /// (i.e. by running it against bundled known-good and known-bad snippets).
+    Doctor,
+    /// Prints a Markdown summary of the ink! contracts in a file
+    /// (i.e. storage fields, messages with selectors and signatures, events with topics and chain extensions).
+    Docs {
+        /// Path of the Rust file to document.
+        path: PathBuf,
+    },
+}
+
+/// Output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Human-readable output (i.e. one line per diagnostic).
+    Human,
+    /// JSON output (i.e. a versioned JSON report of diagnostics).
+    Json,
+    /// SARIF 2.1.0 output (e.g. for code scanning integrations).
+    Sarif,
+}
+
+/// Exit status for runs with errors (or warnings if denied) and failed self-checks.
+const EXIT_DIAGNOSTICS: u8 = 1;
+/// Exit status for invalid paths and I/O errors (same as CLI argument errors).
+const EXIT_ERROR: u8 = 2;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    // Runs the requested command (if any).
+    match cli.command {
+        Some(Command::Doctor) => return doctor(),
+        Some(Command::Docs { path }) => return docs(&path),
+        None => (),
+    }
+
+    // Analyzes files.
+    let results = match analyze_paths(&cli.paths) {
+        Ok(results) => results,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+
+    // Prints diagnostics.
+    match cli.format {
+        Format::Human => print!("{}", human_output(&results)),
+        Format::Json => println!("{}", json_output(&results)),
+        Format::Sarif => println!("{}", sarif_output(&results)),
+    }
+
+    // Returns exit status.
+    if is_failure(&results, cli.deny_warnings) {
+        ExitCode::from(EXIT_DIAGNOSTICS)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Returns true if there are any errors (or warnings if denied).
+///
+/// Deprecation warnings never fail a run.
+fn is_failure(results: &[FileReport], deny_warnings: bool) -> bool {
+    results
+        .iter()
+        .flat_map(|result| &result.diagnostics)
+        .any(|diagnostic| match diagnostic.severity {
+            Severity::Error => true,
+            Severity::Warning => deny_warnings,
+            Severity::Deprecation => false,
+        })
+}
+
+/// Runs ink! analyzer against bundled snippets and reports any mismatches.
+fn doctor() -> ExitCode {
+    let report = ink_analyzer::self_check();
+    for mismatch in &report.mismatches {
+        eprintln!(
+            "FAILED: {}\n  expected: {}\n  actual: {}",
+            mismatch.name, mismatch.expected, mismatch.actual
+        );
+    }
+
+    if report.is_ok() {
+        println!("ink! analyzer passed all {} self-checks.", report.checks);
+        ExitCode::SUCCESS
+    } else {
+        eprintln!(
+            "error: ink! analyzer failed {} of {} self-checks.",
+            report.mismatches.len(),
+            report.checks
+        );
+        ExitCode::from(EXIT_DIAGNOSTICS)
+    }
+}
+
+/// Prints a Markdown summary of the ink! contracts in the file.
+fn docs(path: &Path) -> ExitCode {
+    match std::fs::read_to_string(path) {
+        Ok(code) => {
+            print!("{}", ink_analyzer::markdown_docs(&Analysis::new(&code)));
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: failed to read `{}`: {error}", path.display());
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+/// Analyzes the given files and directories, and returns the analysis results for each file (sorted by path).
+///
+/// Directories are searched recursively for ink! contract crates (i.e. directories with a `Cargo.toml`,
+/// hidden directories, `target` directories and symbolic links are skipped), and each crate is analyzed (once)
+/// with [`ink_analyzer::analyze_project`] (directories without any crates are errors).
+/// Files are analyzed as part of the crate of their nearest `Cargo.toml`
+/// (or on their own if they're not part of the crate's module tree).
+fn analyze_paths(paths: &[PathBuf]) -> std::io::Result<Vec<FileReport>> {
+    let mut crate_roots = Vec::new();
+    let mut file_paths = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let n_crate_roots = crate_roots.len();
+            find_crate_roots(path, &mut crate_roots)?;
+            if crate_roots.len() == n_crate_roots {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("no ink! crates found in `{}`", path.display()),
+                ));
+            }
+        } else if path.is_file() {
+            file_paths.push(path.clone());
+        } else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("`{}` is not a file or directory", path.display()),
+            ));
+        }
+    }
+
+    // Resolves the crate root (i.e. the directory of the nearest `Cargo.toml`) of each file (if any).
+    let file_paths: Vec<(PathBuf, Option<PathBuf>)> = file_paths
+        .into_iter()
+        .map(|path| {
+            let root = path
+                .ancestors()
+                .skip(1)
+                .find(|dir| dir.join("Cargo.toml").is_file())
+                .map(Path::to_path_buf);
+            (path, root)
+        })
+        .collect();
+
+    // Analyzes each crate only once (i.e. keyed by its canonical path),
+    // even if it's referenced by multiple paths (e.g. multiple files in the same crate).
+    let mut crate_reports: HashMap<PathBuf, Vec<FileReport>> = HashMap::new();
+    let file_crate_roots = file_paths.iter().filter_map(|(_, root)| root.as_ref());
+    for root in crate_roots.iter().chain(file_crate_roots) {
+        if let Entry::Vacant(entry) = crate_reports.entry(std::fs::canonicalize(root)?) {
+            let report = ink_analyzer::analyze_project(root);
+            for module_path in &report.unresolved_modules {
+                eprintln!(
+                    "warning: failed to read module file `{}`",
+                    module_path.display()
+                );
+            }
+            entry.insert(report.files);
+        }
+    }
+
+    let mut results = Vec::new();
+    for root in &crate_roots {
+        results.extend(crate_reports[&std::fs::canonicalize(root)?].iter().cloned());
+    }
+    for (path, root) in file_paths {
+        // Compares canonical paths (e.g. to match relative paths and paths with `..` components).
+        let canonical_path = std::fs::canonicalize(&path)?;
+        let crate_file = match root {
+            Some(root) => crate_reports
+                .get(&std::fs::canonicalize(root)?)
+                .and_then(|files| {
+                    files.iter().find(|file| {
+                        std::fs::canonicalize(&file.path)
+                            .map_or(false, |file_path| file_path == canonical_path)
+                    })
+                })
+                .cloned(),
+            None => None,
+        };
+        match crate_file {
+            Some(file) => results.push(file),
+            None => {
+                let content = std::fs::read_to_string(&path)?;
+                let analysis = Analysis::new(&content);
+                results.push(FileReport {
+                    diagnostics: analysis.diagnostics(),
+                    contracts: analysis.contract_info(),
+                    path,
+                    content,
+                });
+            }
+        }
+    }
+
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    results.dedup_by(|a, b| a.path == b.path);
+    Ok(results)
+}
+
+/// Collects the root directories of crates (i.e. directories with a `Cargo.toml`) in the directory (recursively).
+///
+/// Symbolic links aren't followed (i.e. to avoid infinite recursion for cyclic links).
+fn find_crate_roots(dir: &Path, results: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if dir.join("Cargo.toml").is_file() {
+        results.push(dir.to_path_buf());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let is_skipped = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(true, |name| name.starts_with('.') || name == "target");
+        // NOTE: `DirEntry::file_type` doesn't follow symbolic links (unlike `Path::is_dir`).
+        if entry.file_type()?.is_dir() && !is_skipped {
+            find_crate_roots(&path, results)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the 1-based line and column (i.e. in characters) for the offset.
+fn line_col(line_index: &LineIndex, offset: ink_analyzer::TextSize) -> (u32, u32) {
+    line_index
+        .line_col(offset, PositionEncoding::Utf32)
+        .map_or((1, 1), |line_col| (line_col.line + 1, line_col.col + 1))
+}
+
+/// Returns human-readable output (i.e. one line per diagnostic, followed by a summary).
+fn human_output(results: &[FileReport]) -> String {
+    let mut output = String::new();
+    let (mut n_errors, mut n_warnings) = (0, 0);
+    for result in results {
+        let line_index = LineIndex::new(&result.content);
+        for diagnostic in &result.diagnostics {
+            let (line, col) = line_col(&line_index, diagnostic.range.start());
+            let severity = match diagnostic.severity {
+                Severity::Error => {
+                    n_errors += 1;
+                    "error"
+                }
+                Severity::Warning | Severity::Deprecation => {
+                    n_warnings += 1;
+                    "warning"
+                }
+            };
+            let code = diagnostic
+                .code
+                .map(|code| format!("[{code}]"))
+                .unwrap_or_default();
+            output.push_str(&format!(
+                "{}:{line}:{col}: {severity}{code}: {}\n",
+                result.path.display(),
+                diagnostic.message
+            ));
+        }
+    }
+    output.push_str(&format!(
+        "Analyzed {} file(s): {n_errors} error(s), {n_warnings} warning(s).\n",
+        results.len()
+    ));
+    output
+}
+
+/// Returns JSON output (i.e. a versioned JSON report, see [`ink_analyzer::json`]).
+fn json_output(results: &[FileReport]) -> serde_json::Value {
+    let paths: Vec<String> = results
+        .iter()
+        .map(|result| result.path.display().to_string())
+        .collect();
+    let report = json::Report::new(results.iter().zip(&paths).map(|(result, path)| {
+        (
+            path.as_str(),
+            result.content.as_str(),
+            result.diagnostics.as_slice(),
+        )
+    }));
+    serde_json::to_value(report).expect("JSON reports should be serializable")
+}
+
+/// Returns SARIF output (i.e. a SARIF 2.1.0 log).
+fn sarif_output(results: &[FileReport]) -> String {
+    let paths: Vec<String> = results
+        .iter()
+        .map(|result| result.path.display().to_string())
+        .collect();
+    let files: Vec<SarifFile> = results
+        .iter()
+        .zip(&paths)
+        .map(|(result, path)| SarifFile {
+            uri: path,
+            content: &result.content,
+            diagnostics: &result.diagnostics,
+        })
+        .collect();
+    ink_analyzer::sarif(&files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(content: &str) -> Vec<FileReport> {
+        vec![FileReport {
+            path: PathBuf::from("lib.rs"),
+            content: content.to_string(),
+            diagnostics: Analysis::new(content).diagnostics(),
+            contracts: Vec::new(),
+        }]
+    }
+
+    #[test]
+    fn analyze_paths_works() {
+        // Creates a workspace with an ink! contract crate split across multiple files.
+        let root = std::env::temp_dir().join("ink_analyzer_cli_analyze_paths_works");
+        let _ = std::fs::remove_dir_all(&root);
+        let crate_root = root.join("contracts/my_contract");
+        std::fs::create_dir_all(crate_root.join("src/my_contract/messages")).unwrap();
+        std::fs::create_dir_all(root.join("target/my_contract")).unwrap();
+        std::fs::write(root.join("target/my_contract/Cargo.toml"), "[package]").unwrap();
+        std::fs::write(
+            root.join("target/my_contract/lib.rs"),
+            "#[ink::contract]\nmod my_contract {}",
+        )
+        .unwrap();
+        std::fs::write(crate_root.join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(
+            crate_root.join("src/lib.rs"),
+            "#[ink::contract]\nmod my_contract {\n    mod messages;\n}\n\nmod utils;",
+        )
+        .unwrap();
+        std::fs::write(
+            crate_root.join("src/my_contract/messages/mod.rs"),
+            "impl MyContract {\n    #[ink(message)]\n    pub fn my_message(&self) {}\n}",
+        )
+        .unwrap();
+        std::fs::write(crate_root.join("src/utils.rs"), "").unwrap();
+        std::fs::write(
+            root.join("standalone.rs"),
+            "#[ink::contract]\nmod my_contract {}",
+        )
+        .unwrap();
+
+        // Analyzes crates in directories (except for `target` directories).
+        let results = analyze_paths(std::slice::from_ref(&root)).unwrap();
+        let paths: Vec<PathBuf> = results.iter().map(|file| file.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                crate_root.join("src/lib.rs"),
+                crate_root.join("src/my_contract/messages/mod.rs"),
+                crate_root.join("src/utils.rs"),
+            ]
+        );
+        // ink! messages are valid in out-of-line child modules of ink! contracts.
+        assert!(results[1].diagnostics.is_empty());
+
+        // Analyzes files as part of their crate (or on their own), without duplicates.
+        let results = analyze_paths(&[
+            crate_root.join("src/my_contract/messages/mod.rs"),
+            root.join("standalone.rs"),
+            crate_root.clone(),
+        ])
+        .unwrap();
+        let paths: Vec<PathBuf> = results.iter().map(|file| file.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                crate_root.join("src/lib.rs"),
+                crate_root.join("src/my_contract/messages/mod.rs"),
+                crate_root.join("src/utils.rs"),
+                root.join("standalone.rs"),
+            ]
+        );
+        assert!(results[1].diagnostics.is_empty());
+        assert!(!results[3].diagnostics.is_empty());
+
+        // Matches files to their crate files by canonical path.
+        let results =
+            analyze_paths(&[crate_root.join("src/../src/my_contract/messages/mod.rs")]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            std::fs::canonicalize(&results[0].path).unwrap(),
+            std::fs::canonicalize(crate_root.join("src/my_contract/messages/mod.rs")).unwrap()
+        );
+        // ink! messages are valid in out-of-line child modules of ink! contracts.
+        assert!(results[0].diagnostics.is_empty());
+
+        // Missing paths and directories without crates are errors.
+        assert!(analyze_paths(&[root.join("missing.rs")]).is_err());
+        let error = analyze_paths(&[crate_root.join("src")]).unwrap_err();
+        assert!(error.to_string().contains("no ink! crates found"));
+
+        // Symbolic links aren't followed (i.e. cyclic links don't cause infinite recursion).
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&root, crate_root.join("src/cycle")).unwrap();
+            let results = analyze_paths(std::slice::from_ref(&root)).unwrap();
+            assert_eq!(results.len(), 3);
+        }
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn is_failure_works() {
+        let with_severity = |severity| {
+            let mut results = results("#[ink::contract]\nmod my_contract {}");
+            for diagnostic in &mut results[0].diagnostics {
+                diagnostic.severity = severity;
+            }
+            results
+        };
+
+        for (severity, deny_warnings, expected) in [
+            (Severity::Error, false, true),
+            (Severity::Error, true, true),
+            (Severity::Warning, false, false),
+            (Severity::Warning, true, true),
+            (Severity::Deprecation, false, false),
+            (Severity::Deprecation, true, false),
+        ] {
+            assert_eq!(
+                is_failure(&with_severity(severity), deny_warnings),
+                expected,
+                "severity: {severity:?}, deny warnings: {deny_warnings}"
+            );
+        }
+        assert!(!is_failure(&results(""), true));
+    }
+
+    #[test]
+    fn human_output_works() {
+        let output = human_output(&results("#[ink::contract]\nmod my_contract {}"));
+        assert!(output.starts_with("lib.rs:"));
+        assert!(output.contains(": error[E0017-missing-storage]: "));
+        assert!(output.ends_with("Analyzed 1 file(s): 3 error(s), 0 warning(s).\n"));
+    }
+
+    #[test]
+    fn json_output_works() {
+        let output = json_output(&results("#[ink::contract]\nmod my_contract {}"));
+        assert_eq!(output["schemaVersion"], json::SCHEMA_VERSION);
+        assert_eq!(output["summary"]["errors"], 3);
+        let diagnostics = output["files"][0]["diagnostics"].as_array().unwrap();
+        assert_eq!(output["files"][0]["path"], "lib.rs");
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0]["severity"], "error");
+        // Lines are 1-based.
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], 2);
+        assert!(diagnostics[0]["code"].as_str().unwrap().starts_with('E'));
+    }
+
+    #[test]
+    fn sarif_output_works() {
+        let output: serde_json::Value = serde_json::from_str(&sarif_output(&results(
+            "#[ink::contract]\nmod my_contract {}",
+        )))
+        .unwrap();
+        assert_eq!(output["version"], "2.1.0");
+        let sarif_results = output["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(sarif_results.len(), 3);
+        assert_eq!(sarif_results[0]["level"], "error");
+        assert_eq!(
+            sarif_results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "lib.rs"
+        );
+    }
+}
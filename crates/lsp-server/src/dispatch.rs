@@ -1,12 +1,14 @@
 //! LSP server main loop for dispatching requests, notifications and handling responses.
 
 use crossbeam_channel::Sender;
+use ink_analyzer::Analysis;
 use lsp_types::request::Request;
 use std::collections::HashSet;
 
 use crate::dispatch::routers::{NotificationRouter, RequestRouter};
 use crate::memory::Memory;
-use crate::utils;
+use crate::settings::Settings;
+use crate::{translator, utils};
 use handlers::request::CreateProjectResponse;
 
 mod actions;
@@ -17,9 +19,22 @@ mod routers;
 pub fn main_loop(
     connection: lsp_server::Connection,
     client_capabilities: lsp_types::ClientCapabilities,
+) -> anyhow::Result<()> {
+    main_loop_with_settings(connection, client_capabilities, Settings::default())
+}
+
+/// Implements the main loop for dispatching LSP requests, notifications and handling responses
+/// using the given initial settings (e.g. parsed from the LSP client initialization options).
+pub fn main_loop_with_settings(
+    connection: lsp_server::Connection,
+    client_capabilities: lsp_types::ClientCapabilities,
+    settings: Settings,
 ) -> anyhow::Result<()> {
     // Creates a dispatcher.
-    let mut dispatcher = Dispatcher::new(&connection.sender, client_capabilities);
+    let mut dispatcher = Dispatcher::new(&connection.sender, client_capabilities, settings);
+
+    // Registers file watchers for files that diagnostics depend on (if supported by the client).
+    dispatcher.register_file_watchers()?;
 
     // Iterates over a crossbeam channel receiver for LSP messages (blocks until next message is received).
     // Ref: <https://docs.rs/crossbeam-channel/0.5.8/crossbeam_channel/#iteration>.
@@ -28,6 +43,7 @@ pub fn main_loop(
             lsp_server::Message::Request(req) => {
                 // Handles shutdown request.
                 if connection.handle_shutdown(&req)? {
+                    dispatcher.persist();
                     return Ok(());
                 }
 
@@ -38,6 +54,7 @@ pub fn main_loop(
                 // Handles exit notification in case it comes out of band of a shutdown request.
                 use lsp_types::notification::Notification;
                 if not.method == lsp_types::notification::Exit::METHOD {
+                    dispatcher.persist();
                     return Ok(());
                 }
 
@@ -49,6 +66,7 @@ pub fn main_loop(
         }
     }
 
+    dispatcher.persist();
     Ok(())
 }
 
@@ -57,22 +75,69 @@ struct Dispatcher<'a> {
     sender: &'a Sender<lsp_server::Message>,
     client_capabilities: lsp_types::ClientCapabilities,
     memory: Memory,
+    /// Documents for which ink! diagnostics have been published
+    /// (i.e. documents that contain or contained ink! entities).
+    ink_docs: HashSet<lsp_types::Url>,
 }
 
 const INITIALIZE_PROJECT_ID_PREFIX: &str = "initialize-project::";
 const SHOW_DOCUMENT_ID_PREFIX: &str = "show-document::";
+const WATCHED_FILES_REGISTRATION_ID: &str = "watched-files";
 
 impl<'a> Dispatcher<'a> {
     /// Creates a dispatcher for an LSP server connection.
     fn new(
         sender: &'a Sender<lsp_server::Message>,
         client_capabilities: lsp_types::ClientCapabilities,
+        settings: Settings,
     ) -> Self {
         Self {
             sender,
             client_capabilities,
-            memory: Memory::new(),
+            memory: Memory::with_settings(settings),
+            ink_docs: HashSet::new(),
+        }
+    }
+
+    /// Persists state that's reused between sessions (i.e. the symbol index, if enabled).
+    ///
+    /// Persisting is best-effort (i.e. failures don't prevent the server from shutting down).
+    fn persist(&mut self) {
+        let _ = self.memory.persist_symbol_index();
+    }
+
+    /// Registers file watchers for `Cargo.toml` and Rust files (if supported by the client),
+    /// so that diagnostics that depend on files on the file system are updated when they change
+    /// (see `handlers::notification::handle_did_change_watched_files`).
+    fn register_file_watchers(&self) -> anyhow::Result<()> {
+        if !utils::did_change_watched_files_dynamic_registration(&self.client_capabilities) {
+            return Ok(());
         }
+
+        let watchers = ["**/Cargo.toml", "**/*.rs"]
+            .into_iter()
+            .map(|pattern| lsp_types::FileSystemWatcher {
+                glob_pattern: lsp_types::GlobPattern::String(pattern.to_string()),
+                kind: None,
+            })
+            .collect();
+        use lsp_types::notification::Notification;
+        let params = lsp_types::RegistrationParams {
+            registrations: vec![lsp_types::Registration {
+                id: WATCHED_FILES_REGISTRATION_ID.to_string(),
+                method: lsp_types::notification::DidChangeWatchedFiles::METHOD.to_string(),
+                register_options: serde_json::to_value(
+                    lsp_types::DidChangeWatchedFilesRegistrationOptions { watchers },
+                )
+                .ok(),
+            }],
+        };
+        let req = lsp_server::Request::new(
+            lsp_server::RequestId::from(WATCHED_FILES_REGISTRATION_ID.to_string()),
+            lsp_types::request::RegisterCapability::METHOD.to_string(),
+            params,
+        );
+        self.send(req.into())
     }
 
     /// Handles LSP requests and sends responses (if any) as appropriate.
@@ -82,12 +147,30 @@ impl<'a> Dispatcher<'a> {
         let mut router = RequestRouter::new(req, &mut self.memory, &self.client_capabilities);
         let result = router
             .process::<lsp_types::request::Completion>(handlers::request::handle_completion)
+            .process::<lsp_types::request::ResolveCompletionItem>(
+                handlers::request::handle_completion_resolve,
+            )
             .process::<lsp_types::request::HoverRequest>(handlers::request::handle_hover)
             .process::<lsp_types::request::CodeActionRequest>(handlers::request::handle_code_action)
+            .process::<lsp_types::request::CodeActionResolveRequest>(
+                handlers::request::handle_code_action_resolve,
+            )
             .process::<lsp_types::request::InlayHintRequest>(handlers::request::handle_inlay_hint)
+            .process::<lsp_types::request::FoldingRangeRequest>(
+                handlers::request::handle_folding_range,
+            )
+            .process::<lsp_types::request::SelectionRangeRequest>(
+                handlers::request::handle_selection_range,
+            )
             .process::<lsp_types::request::SignatureHelpRequest>(
                 handlers::request::handle_signature_help,
             )
+            .process::<lsp_types::request::DocumentDiagnosticRequest>(
+                handlers::request::handle_document_diagnostic,
+            )
+            .process::<lsp_types::request::WorkspaceSymbolRequest>(
+                handlers::request::handle_workspace_symbol,
+            )
             .process::<lsp_types::request::ExecuteCommand>(
                 handlers::request::handle_execute_command,
             )
@@ -111,27 +194,20 @@ impl<'a> Dispatcher<'a> {
                 let doc_changes = project
                     .files
                     .iter()
-                    .flat_map(|(uri, content)| {
-                        vec![
-                            lsp_types::DocumentChangeOperation::Op(lsp_types::ResourceOp::Create(
-                                lsp_types::CreateFile {
-                                    uri: uri.clone(),
-                                    options: None,
-                                    annotation_id: None,
+                    .filter_map(|(uri, content)| {
+                        translator::to_lsp::resource_op(
+                            ink_analyzer::ResourceOp::CreateFile {
+                                // File URIs are absolute, so they're used as is.
+                                path: uri.to_string(),
+                                file: ink_analyzer::ProjectFile {
+                                    plain: content.to_owned(),
+                                    snippet: None,
                                 },
-                            )),
-                            lsp_types::DocumentChangeOperation::Edit(lsp_types::TextDocumentEdit {
-                                text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
-                                    uri: uri.clone(),
-                                    version: None,
-                                },
-                                edits: vec![lsp_types::OneOf::Left(lsp_types::TextEdit {
-                                    range: lsp_types::Range::default(),
-                                    new_text: content.to_owned(),
-                                })],
-                            }),
-                        ]
+                            },
+                            &project.uri,
+                        )
                     })
+                    .flatten()
                     .collect();
                 let params = lsp_types::ApplyWorkspaceEditParams {
                     label: Some("new ink! project".to_string()),
@@ -175,6 +251,12 @@ impl<'a> Dispatcher<'a> {
             .process::<lsp_types::notification::DidCloseTextDocument>(
                 handlers::notification::handle_did_close_text_document,
             )?
+            .process::<lsp_types::notification::DidChangeConfiguration>(
+                handlers::notification::handle_did_change_configuration,
+            )?
+            .process::<lsp_types::notification::DidChangeWatchedFiles>(
+                handlers::notification::handle_did_change_watched_files,
+            )?
             .finish();
 
         // Process memory changes (if any) made by notification handlers.
@@ -235,9 +317,55 @@ impl<'a> Dispatcher<'a> {
 
     /// Sends diagnostics notifications to the client for changed (including new) documents.
     fn publish_diagnostics(&mut self, changes: &HashSet<lsp_types::Url>) -> anyhow::Result<()> {
+        // Skips publishing diagnostics if the client pulls diagnostics instead
+        // (see `handlers::request::handle_document_diagnostic`).
+        if utils::pull_diagnostics_support(&self.client_capabilities) {
+            return Ok(());
+        }
+
+        // Skips plain Rust documents (i.e. documents without ink! entities),
+        // unless ink! diagnostics were previously published for them (i.e. so that stale diagnostics are cleared).
+        let mut ink_changes = HashSet::new();
+        for uri in changes {
+            let is_ink_doc = self
+                .memory
+                .get(uri.as_str())
+                .map_or(false, |doc| Analysis::contains_ink_entities(&doc.content));
+            if is_ink_doc {
+                self.ink_docs.insert(uri.clone());
+                ink_changes.insert(uri.clone());
+            } else if self.ink_docs.remove(uri) {
+                ink_changes.insert(uri.clone());
+            }
+        }
+
+        // Publishes partial diagnostics while diagnostics are computed (if enabled).
+        if self.memory.settings().diagnostics.streaming {
+            use lsp_types::notification::Notification;
+            let sender = self.sender;
+            for uri in &ink_changes {
+                let mut result = Ok(());
+                actions::stream_diagnostics(
+                    uri,
+                    &mut self.memory,
+                    &self.client_capabilities,
+                    |params| {
+                        if result.is_ok() {
+                            let notification = lsp_server::Notification::new(
+                                lsp_types::notification::PublishDiagnostics::METHOD.to_string(),
+                                params,
+                            );
+                            result = sender.send(notification.into());
+                        }
+                    },
+                );
+                result.map_err(|error| anyhow::format_err!("Failed to send message: {error}"))?;
+            }
+        }
+
         // Composes `PublishDiagnostics` notification parameters for documents with changes.
         if let Some(params_list) =
-            actions::publish_diagnostics(changes, &self.memory, &self.client_capabilities)?
+            actions::publish_diagnostics(&ink_changes, &mut self.memory, &self.client_capabilities)?
         {
             // Composes and sends `PublishDiagnostics` notifications for all documents with changes.
             for params in params_list {
@@ -292,7 +420,7 @@ mod tests {
                     uri: uri.clone(),
                     language_id: "rust".to_string(),
                     version: 0,
-                    text: "".to_string(),
+                    text: "#[ink::contract]\nmod my_contract {}".to_string(),
                 },
             })
             .unwrap(),
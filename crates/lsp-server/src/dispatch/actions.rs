@@ -1,6 +1,5 @@
 //! Utilities for composing LSP requests and notifications.
 
-use ink_analyzer::Analysis;
 use line_index::LineIndex;
 use std::collections::HashSet;
 
@@ -11,22 +10,22 @@ use crate::{translator, utils};
 /// Composes `PublishDiagnostics` notification parameters for a set of documents with changes.
 pub fn publish_diagnostics(
     changes: &HashSet<lsp_types::Url>,
-    memory: &Memory,
+    memory: &mut Memory,
     client_capabilities: &lsp_types::ClientCapabilities,
 ) -> anyhow::Result<Option<Vec<lsp_types::PublishDiagnosticsParams>>> {
     // Iterates over all documents with changes and compose diagnostics parameters.
     let params: Vec<lsp_types::PublishDiagnosticsParams> = changes
         .iter()
         .map(|uri| {
-            let (diagnostics, version, line_index) = match memory.get(uri.as_str()) {
-                // Computes diagnostics for document.
-                Some(doc) => (
-                    Analysis::new(&doc.content).diagnostics(),
-                    Some(doc.version),
-                    Some(LineIndex::new(&doc.content)),
-                ),
+            // Computes (or retrieves cached) diagnostics for document.
+            let diagnostics = memory
+                .diagnostics(uri.as_str())
+                .map(|result| result.diagnostics.clone())
+                .unwrap_or_default();
+            let (version, line_index) = match memory.get(uri.as_str()) {
+                Some(doc) => (Some(doc.version), Some(LineIndex::new(&doc.content))),
                 // Clears diagnostics for missing documents.
-                None => (Vec::new(), None, None),
+                None => (None, None),
             };
 
             // Composes translation context.
@@ -41,9 +40,9 @@ pub fn publish_diagnostics(
                 diagnostics: diagnostics
                     .into_iter()
                     .filter_map(|diagnostic| {
-                        translation_context
-                            .as_ref()
-                            .and_then(|context| translator::to_lsp::diagnostic(diagnostic, context))
+                        translation_context.as_ref().and_then(|context| {
+                            translator::to_lsp::diagnostic(diagnostic, uri, context)
+                        })
                     })
                     .collect(),
                 version,
@@ -54,6 +53,48 @@ pub fn publish_diagnostics(
     Ok((!params.is_empty()).then_some(params))
 }
 
+/// Number of diagnostics per partial `PublishDiagnostics` notification (see [`stream_diagnostics`]).
+const STREAMING_BATCH_SIZE: usize = 10;
+
+/// Computes diagnostics for a document (if they're not yet cached), and passes
+/// partial `PublishDiagnostics` notification parameters (i.e. for the diagnostics computed so far)
+/// to the callback after every [`STREAMING_BATCH_SIZE`] diagnostics.
+///
+/// The complete results are cached, so they can then be published using [`publish_diagnostics`].
+pub fn stream_diagnostics<F>(
+    uri: &lsp_types::Url,
+    memory: &mut Memory,
+    client_capabilities: &lsp_types::ClientCapabilities,
+    mut callback: F,
+) where
+    F: FnMut(lsp_types::PublishDiagnosticsParams),
+{
+    let Some(doc) = memory.get(uri.as_str()) else {
+        return;
+    };
+    let version = doc.version;
+    let translation_context = PositionTranslationContext {
+        encoding: utils::position_encoding(client_capabilities),
+        line_index: LineIndex::new(&doc.content),
+    };
+
+    let mut diagnostics = Vec::new();
+    memory.diagnostics_with(uri.as_str(), |diagnostic| {
+        if let Some(diagnostic) =
+            translator::to_lsp::diagnostic(diagnostic, uri, &translation_context)
+        {
+            diagnostics.push(diagnostic);
+            if diagnostics.len() % STREAMING_BATCH_SIZE == 0 {
+                callback(lsp_types::PublishDiagnosticsParams {
+                    uri: uri.clone(),
+                    diagnostics: diagnostics.clone(),
+                    version: Some(version),
+                });
+            }
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,4 +137,39 @@ mod tests {
         // 3 Expected diagnostics for missing storage, constructor and message.
         assert_eq!(params.diagnostics.len(), 3);
     }
+
+    #[test]
+    fn stream_diagnostics_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+
+        // Creates client capabilities.
+        let client_capabilities = simple_client_config();
+
+        // Creates test document with more diagnostics than the streaming batch size.
+        let messages: String = (0..STREAMING_BATCH_SIZE)
+            .map(|idx| format!("#[ink(message)]\nfn message_{idx}(&self) {{}}\n"))
+            .collect();
+        let uri = document(messages, &mut memory);
+
+        // Streams partial diagnostics and verifies the expected results.
+        let mut partial_results = Vec::new();
+        stream_diagnostics(&uri, &mut memory, &client_capabilities, |params| {
+            partial_results.push(params)
+        });
+        assert!(!partial_results.is_empty());
+        assert!(partial_results.iter().all(
+            |params| params.uri == uri && params.diagnostics.len() % STREAMING_BATCH_SIZE == 0
+        ));
+        let streamed_count = partial_results.len();
+
+        // Verifies that complete results are cached (i.e. they're not streamed again).
+        let result = memory.diagnostics(uri.as_str()).cloned().unwrap();
+        assert!(result.diagnostics.len() >= STREAMING_BATCH_SIZE);
+        stream_diagnostics(&uri, &mut memory, &client_capabilities, |params| {
+            partial_results.push(params)
+        });
+        assert_eq!(partial_results.len(), streamed_count);
+        assert_eq!(memory.diagnostics(uri.as_str()), Some(&result));
+    }
 }
@@ -1,10 +1,12 @@
 //! LSP notification handlers.
 
 use lsp_types::{
-    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams,
 };
 
 use crate::memory::Memory;
+use crate::settings::Settings;
 
 /// Handles `DidOpenTextDocument` notification.
 pub fn handle_did_open_text_document(
@@ -53,6 +55,32 @@ pub fn handle_did_close_text_document(
     Ok(())
 }
 
+/// Handles `DidChangeConfiguration` notification.
+pub fn handle_did_change_configuration(
+    params: DidChangeConfigurationParams,
+    memory: &mut Memory,
+) -> anyhow::Result<()> {
+    // Updates settings in memory (invalid settings are ignored).
+    if let Some(settings) = Settings::from_json(&params.settings) {
+        memory.set_settings(settings);
+    }
+
+    Ok(())
+}
+
+/// Handles `DidChangeWatchedFiles` notification.
+pub fn handle_did_change_watched_files(
+    params: DidChangeWatchedFilesParams,
+    memory: &mut Memory,
+) -> anyhow::Result<()> {
+    // Invalidates state that depends on files on the file system (e.g. `Cargo.toml`).
+    if !params.changes.is_empty() {
+        memory.files_changed();
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,7 +117,7 @@ mod tests {
         assert_eq!(
             memory.get(uri.as_ref()),
             Some(Document {
-                content: content.clone(),
+                content: content.as_str().into(),
                 version
             })
             .as_ref()
@@ -146,7 +174,7 @@ mod tests {
         assert_eq!(
             memory.get(uri.as_ref()),
             Some(Document {
-                content: updated_content,
+                content: updated_content.into(),
                 version: updated_version
             })
             .as_ref()
@@ -174,4 +202,56 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(memory.get(uri.as_ref()), None);
     }
+
+    #[test]
+    fn handle_did_change_configuration_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+        assert!(!memory.settings().inlay_hints.default_values);
+
+        // Calls handler with valid settings and verifies that the settings are updated.
+        let result = handle_did_change_configuration(
+            DidChangeConfigurationParams {
+                settings: serde_json::json!({ "inlayHints": { "defaultValues": true } }),
+            },
+            &mut memory,
+        );
+        assert!(result.is_ok());
+        assert!(memory.settings().inlay_hints.default_values);
+
+        // Calls handler with invalid settings and verifies that the settings are unchanged.
+        let result = handle_did_change_configuration(
+            DidChangeConfigurationParams {
+                settings: serde_json::Value::Null,
+            },
+            &mut memory,
+        );
+        assert!(result.is_ok());
+        assert!(memory.settings().inlay_hints.default_values);
+    }
+
+    #[test]
+    fn handle_did_change_watched_files_works() {
+        // Initializes memory with an open document.
+        let mut memory = Memory::new();
+        let uri = document_uri();
+        memory.insert(uri.to_string(), "".to_string(), 0);
+        memory.take_changes();
+
+        // Calls handler with file changes and verifies that open documents are marked as changed.
+        let result = handle_did_change_watched_files(
+            DidChangeWatchedFilesParams {
+                changes: vec![lsp_types::FileEvent {
+                    uri: lsp_types::Url::parse("file:///project/Cargo.toml").unwrap(),
+                    typ: lsp_types::FileChangeType::CHANGED,
+                }],
+            },
+            &mut memory,
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            memory.take_changes(),
+            Some(std::collections::HashSet::from([uri.to_string()]))
+        );
+    }
 }
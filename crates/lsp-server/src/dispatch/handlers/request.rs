@@ -1,12 +1,13 @@
 //! LSP request handlers.
 
-use ink_analyzer::Analysis;
+use ink_analyzer::{Analysis, InlayHintsConfig};
 use line_index::LineIndex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::memory::Memory;
 use crate::translator::PositionTranslationContext;
+use crate::vfs::VfsSnapshot;
 use crate::{translator, utils};
 
 /// Handles completion request.
@@ -17,8 +18,8 @@ pub fn handle_completion(
 ) -> anyhow::Result<Option<lsp_types::CompletionResponse>> {
     // Gets document uri and retrieves document from memory.
     let id = params.text_document_position.text_document.uri.to_string();
-    match memory.get(&id) {
-        Some(doc) => {
+    match memory.analysis(&id).zip(memory.get(&id)) {
+        Some((analysis, doc)) => {
             // Composes translation context.
             let translation_context = PositionTranslationContext {
                 encoding: utils::position_encoding(client_capabilities),
@@ -33,7 +34,7 @@ pub fn handle_completion(
             .ok_or(anyhow::format_err!("Invalid offset."))?;
 
             // Computes ink! analyzer completions and translates them into an LSP completion list.
-            let completion_items: Vec<lsp_types::CompletionItem> = Analysis::new(&doc.content)
+            let completion_items: Vec<lsp_types::CompletionItem> = analysis
                 .completions(offset)
                 .into_iter()
                 .filter_map(|completion| {
@@ -59,6 +60,34 @@ pub fn handle_completion(
     }
 }
 
+/// Handles completion resolve request.
+pub fn handle_completion_resolve(
+    params: lsp_types::CompletionItem,
+    _memory: &mut Memory,
+    _client_capabilities: &lsp_types::ClientCapabilities,
+) -> anyhow::Result<lsp_types::CompletionItem> {
+    // Resolves documentation from the completion identifier in the completion resolve data (if any)
+    // (i.e. the identifier fully determines the documentation, so the document isn't re-analyzed).
+    let documentation = params
+        .data
+        .as_ref()
+        .and_then(|data| data.get("id"))
+        .and_then(serde_json::Value::as_str)
+        .and_then(ink_analyzer::resolve_completion)
+        .map(|doc| {
+            lsp_types::Documentation::MarkupContent(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: doc,
+            })
+        });
+
+    // Returns completion item with documentation (if any).
+    Ok(lsp_types::CompletionItem {
+        documentation: documentation.or(params.documentation),
+        ..params
+    })
+}
+
 /// Handles hover request.
 pub fn handle_hover(
     params: lsp_types::HoverParams,
@@ -71,8 +100,8 @@ pub fn handle_hover(
         .text_document
         .uri
         .to_string();
-    match memory.get(&id) {
-        Some(doc) => {
+    match memory.analysis(&id).zip(memory.get(&id)) {
+        Some((analysis, doc)) => {
             // Composes translation context.
             let translation_context = PositionTranslationContext {
                 encoding: utils::position_encoding(client_capabilities),
@@ -87,7 +116,7 @@ pub fn handle_hover(
             .ok_or(anyhow::format_err!("Invalid offset."))?;
 
             // Computes ink! analyzer hover content and translates it to an LSP hover.
-            Ok(Analysis::new(&doc.content)
+            Ok(analysis
                 .hover(ink_analyzer::TextRange::empty(offset))
                 .and_then(|hover| translator::to_lsp::hover(hover, &translation_context)))
         }
@@ -105,8 +134,10 @@ pub fn handle_code_action(
     // Gets document uri and retrieves document from memory.
     let uri = params.text_document.uri;
     let id = uri.to_string();
-    match memory.get(&id) {
-        Some(doc) => {
+    // Uses the same diagnostics configuration as published diagnostics for all actions.
+    let config = memory.diagnostics_config(&id);
+    match memory.analysis(&id).zip(memory.get(&id)) {
+        Some((analysis, doc)) => {
             // Composes translation context.
             let translation_context = PositionTranslationContext {
                 encoding: utils::position_encoding(client_capabilities),
@@ -117,15 +148,61 @@ pub fn handle_code_action(
             let text_range = translator::from_lsp::text_range(params.range, &translation_context)
                 .ok_or(anyhow::format_err!("Invalid range."))?;
 
+            // Computes the "fix all" source action (if explicitly requested by kind).
+            let snapshot = memory.snapshot();
+            let document_context = document_context(&snapshot, client_capabilities);
+            let document_changes = utils::document_changes_support(client_capabilities);
+            let is_fix_all_requested = params.context.only.as_ref().is_some_and(|kinds| {
+                kinds.iter().any(|kind| {
+                    utils::is_code_action_sub_kind(&utils::FIX_ALL_CODE_ACTION_KIND, kind)
+                })
+            });
+            let fix_all = is_fix_all_requested
+                .then(|| analysis.fix_all_with_config(&config))
+                .flatten()
+                .and_then(|action| {
+                    translator::to_lsp::code_action(
+                        action,
+                        uri.clone(),
+                        Some(doc.version),
+                        &translation_context,
+                        document_changes,
+                        &document_context,
+                    )
+                });
+
+            // Returns unresolved code actions (i.e. without edits) if the client supports resolving them.
+            if utils::code_action_resolve_support(client_capabilities) {
+                return Ok(Some(
+                    analysis
+                        .unresolved_actions_with_config(text_range, &config)
+                        .into_iter()
+                        .map(|action| {
+                            translator::to_lsp::unresolved_code_action(action, &uri, params.range)
+                                .into()
+                        })
+                        .chain(fix_all.map(Into::into))
+                        .collect(),
+                ));
+            }
+
             // Computes ink! analyzer actions and translates them to LSP code actions.
             Ok(Some(
-                Analysis::new(&doc.content)
-                    .actions(text_range)
+                analysis
+                    .actions_with_config(text_range, &config)
                     .into_iter()
                     .filter_map(|action| {
-                        translator::to_lsp::code_action(action, uri.clone(), &translation_context)
-                            .map(Into::into)
+                        translator::to_lsp::code_action(
+                            action,
+                            uri.clone(),
+                            Some(doc.version),
+                            &translation_context,
+                            document_changes,
+                            &document_context,
+                        )
+                        .map(Into::into)
                     })
+                    .chain(fix_all.map(Into::into))
                     .collect(),
             ))
         }
@@ -134,6 +211,91 @@ pub fn handle_code_action(
     }
 }
 
+/// Handles code action resolve request.
+pub fn handle_code_action_resolve(
+    params: lsp_types::CodeAction,
+    memory: &mut Memory,
+    client_capabilities: &lsp_types::ClientCapabilities,
+) -> anyhow::Result<lsp_types::CodeAction> {
+    // Gets document uri, LSP range and action identifier from the code action resolve data.
+    let (uri, range, id) = params
+        .data
+        .as_ref()
+        .and_then(serde_json::Value::as_object)
+        .and_then(|data| {
+            let uri = data
+                .get("uri")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|uri| lsp_types::Url::parse(uri).ok())?;
+            let range = data
+                .get("range")
+                .and_then(|range| serde_json::from_value::<lsp_types::Range>(range.clone()).ok())?;
+            let id = data.get("id").and_then(serde_json::Value::as_str)?;
+            Some((uri, range, id.to_string()))
+        })
+        .ok_or(anyhow::format_err!("Invalid code action data."))?;
+    let analysis = memory
+        .analysis(uri.as_str())
+        .ok_or(anyhow::format_err!("Unknown document: {uri}."))?;
+    // Uses the same diagnostics configuration as for the unresolved action.
+    let config = memory.diagnostics_config(uri.as_str());
+    let doc = memory
+        .get(uri.as_str())
+        .ok_or(anyhow::format_err!("Unknown document: {uri}."))?;
+
+    // Composes translation context.
+    let translation_context = PositionTranslationContext {
+        encoding: utils::position_encoding(client_capabilities),
+        line_index: LineIndex::new(&doc.content),
+    };
+
+    // Converts LSP range to ink! analyzer text range.
+    let text_range = translator::from_lsp::text_range(range, &translation_context)
+        .ok_or(anyhow::format_err!("Invalid range."))?;
+
+    // Computes the ink! analyzer action and translates it to an LSP code action.
+    let snapshot = memory.snapshot();
+    let code_action = analysis
+        .resolve_action_with_config(text_range, &id, &config)
+        .and_then(|action| {
+            translator::to_lsp::code_action(
+                action,
+                uri,
+                Some(doc.version),
+                &translation_context,
+                utils::document_changes_support(client_capabilities),
+                document_context(&snapshot, client_capabilities),
+            )
+        })
+        .ok_or(anyhow::format_err!("Failed to resolve code action."))?;
+
+    // Returns code action with edit (and snippets data if any).
+    Ok(lsp_types::CodeAction {
+        edit: code_action.edit,
+        data: code_action.data,
+        ..params
+    })
+}
+
+/// Returns a function that resolves the version and translation context for other documents edited by actions
+/// (i.e. from memory for open documents, or from the file system otherwise).
+fn document_context<'a>(
+    snapshot: &'a VfsSnapshot,
+    client_capabilities: &'a lsp_types::ClientCapabilities,
+) -> impl Fn(&lsp_types::Url) -> Option<(Option<i32>, PositionTranslationContext)> + 'a {
+    move |doc_uri: &lsp_types::Url| {
+        snapshot.read(doc_uri).map(|file| {
+            (
+                file.version,
+                PositionTranslationContext {
+                    encoding: utils::position_encoding(client_capabilities),
+                    line_index: LineIndex::new(&file.content),
+                },
+            )
+        })
+    }
+}
+
 /// Handles inlay hint request.
 pub fn handle_inlay_hint(
     params: lsp_types::InlayHintParams,
@@ -143,8 +305,8 @@ pub fn handle_inlay_hint(
     // Gets document uri and retrieves document from memory.
     let uri = params.text_document.uri;
     let id = uri.to_string();
-    match memory.get(&id) {
-        Some(doc) => {
+    match memory.analysis(&id).zip(memory.get(&id)) {
+        Some((analysis, doc)) => {
             // Composes translation context.
             let translation_context = PositionTranslationContext {
                 encoding: utils::position_encoding(client_capabilities),
@@ -155,10 +317,12 @@ pub fn handle_inlay_hint(
             let text_range = translator::from_lsp::text_range(params.range, &translation_context)
                 .ok_or(anyhow::format_err!("Invalid range."))?;
 
-            // Computes ink! analyzer inlay hints and translates them to LSP inlay hints.
+            // Computes ink! analyzer inlay hints (using the inlay hints settings)
+            // and translates them to LSP inlay hints.
+            let config = InlayHintsConfig::from(&memory.settings().inlay_hints);
             Ok(Some(
-                Analysis::new(&doc.content)
-                    .inlay_hints(Some(text_range))
+                analysis
+                    .inlay_hints_with_config(Some(text_range), &config)
                     .into_iter()
                     .filter_map(|hint| {
                         translator::to_lsp::inlay_hint(hint, &translation_context).map(Into::into)
@@ -171,6 +335,84 @@ pub fn handle_inlay_hint(
     }
 }
 
+/// Handles folding range request.
+pub fn handle_folding_range(
+    params: lsp_types::FoldingRangeParams,
+    memory: &mut Memory,
+    client_capabilities: &lsp_types::ClientCapabilities,
+) -> anyhow::Result<Option<Vec<lsp_types::FoldingRange>>> {
+    // Gets document uri and retrieves document from memory.
+    let uri = params.text_document.uri;
+    let id = uri.to_string();
+    match memory.analysis(&id).zip(memory.get(&id)) {
+        Some((analysis, doc)) => {
+            // Composes translation context.
+            let translation_context = PositionTranslationContext {
+                encoding: utils::position_encoding(client_capabilities),
+                line_index: LineIndex::new(&doc.content),
+            };
+
+            // Computes ink! analyzer folding ranges and translates them to LSP folding ranges.
+            Ok(Some(
+                analysis
+                    .folding_ranges()
+                    .into_iter()
+                    .filter_map(|folding_range| {
+                        translator::to_lsp::folding_range(folding_range, &translation_context)
+                    })
+                    .collect(),
+            ))
+        }
+        // Empty response for missing documents.
+        None => Ok(None),
+    }
+}
+
+/// Handles selection range request.
+pub fn handle_selection_range(
+    params: lsp_types::SelectionRangeParams,
+    memory: &mut Memory,
+    client_capabilities: &lsp_types::ClientCapabilities,
+) -> anyhow::Result<Option<Vec<lsp_types::SelectionRange>>> {
+    // Gets document uri and retrieves document from memory.
+    let uri = params.text_document.uri;
+    let id = uri.to_string();
+    match memory.analysis(&id).zip(memory.get(&id)) {
+        Some((analysis, doc)) => {
+            // Composes translation context.
+            let translation_context = PositionTranslationContext {
+                encoding: utils::position_encoding(client_capabilities),
+                line_index: LineIndex::new(&doc.content),
+            };
+
+            // Computes ink! analyzer selection ranges for each position and translates them to LSP selection ranges.
+            Ok(Some(
+                params
+                    .positions
+                    .into_iter()
+                    .map(|position| {
+                        translator::from_lsp::offset(position, &translation_context)
+                            .and_then(|offset| {
+                                translator::to_lsp::selection_range(
+                                    analysis.selection_ranges(offset),
+                                    &translation_context,
+                                )
+                            })
+                            // Falls back to an empty range at the position,
+                            // because the response must have a selection range for each position.
+                            .unwrap_or(lsp_types::SelectionRange {
+                                range: lsp_types::Range::new(position, position),
+                                parent: None,
+                            })
+                    })
+                    .collect(),
+            ))
+        }
+        // Empty response for missing documents.
+        None => Ok(None),
+    }
+}
+
 /// Handles signature help request.
 pub fn handle_signature_help(
     params: lsp_types::SignatureHelpParams,
@@ -180,8 +422,8 @@ pub fn handle_signature_help(
     // Gets document uri and retrieves document from memory.
     let uri = params.text_document_position_params.text_document.uri;
     let id = uri.to_string();
-    match memory.get(&id) {
-        Some(doc) => {
+    match memory.analysis(&id).zip(memory.get(&id)) {
+        Some((analysis, doc)) => {
             // Composes translation context.
             let translation_context = PositionTranslationContext {
                 encoding: utils::position_encoding(client_capabilities),
@@ -197,7 +439,7 @@ pub fn handle_signature_help(
 
             // Computes ink! analyzer signature help and translates it to LSP signature help.
             Ok(translator::to_lsp::signature_help(
-                &Analysis::new(&doc.content).signature_help(offset),
+                &analysis.signature_help(offset),
                 &utils::signature_support(client_capabilities),
                 params
                     .context
@@ -211,6 +453,117 @@ pub fn handle_signature_help(
     }
 }
 
+/// Handles document diagnostic (i.e. pull diagnostics) request.
+pub fn handle_document_diagnostic(
+    params: lsp_types::DocumentDiagnosticParams,
+    memory: &mut Memory,
+    client_capabilities: &lsp_types::ClientCapabilities,
+) -> anyhow::Result<lsp_types::DocumentDiagnosticReportResult> {
+    let uri = params.text_document.uri;
+    let id = uri.to_string();
+    let full_report = |result_id: Option<String>, items: Vec<lsp_types::Diagnostic>| {
+        lsp_types::DocumentDiagnosticReportResult::Report(
+            lsp_types::DocumentDiagnosticReport::Full(
+                lsp_types::RelatedFullDocumentDiagnosticReport {
+                    related_documents: None,
+                    full_document_diagnostic_report: lsp_types::FullDocumentDiagnosticReport {
+                        result_id,
+                        items,
+                    },
+                },
+            ),
+        )
+    };
+
+    // Returns an empty report for missing documents and plain Rust documents (i.e. documents without ink! entities).
+    let is_ink_doc = memory
+        .get(&id)
+        .map_or(false, |doc| Analysis::contains_ink_entities(&doc.content));
+    if !is_ink_doc {
+        return Ok(full_report(None, Vec::new()));
+    }
+
+    // Computes (or retrieves cached) diagnostics for the document.
+    let Some(result) = memory.diagnostics(&id).cloned() else {
+        return Ok(full_report(None, Vec::new()));
+    };
+
+    // Returns an unchanged report if the client already has the latest diagnostics.
+    if params.previous_result_id.as_deref() == Some(result.result_id.as_str()) {
+        return Ok(lsp_types::DocumentDiagnosticReportResult::Report(
+            lsp_types::DocumentDiagnosticReport::Unchanged(
+                lsp_types::RelatedUnchangedDocumentDiagnosticReport {
+                    related_documents: None,
+                    unchanged_document_diagnostic_report:
+                        lsp_types::UnchangedDocumentDiagnosticReport {
+                            result_id: result.result_id,
+                        },
+                },
+            ),
+        ));
+    }
+
+    // Translates ink! analyzer diagnostics to LSP diagnostics.
+    let items = match memory.get(&id) {
+        Some(doc) => {
+            // Composes translation context.
+            let translation_context = PositionTranslationContext {
+                encoding: utils::position_encoding(client_capabilities),
+                line_index: LineIndex::new(&doc.content),
+            };
+            result
+                .diagnostics
+                .into_iter()
+                .filter_map(|diagnostic| {
+                    translator::to_lsp::diagnostic(diagnostic, &uri, &translation_context)
+                })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    Ok(full_report(Some(result.result_id), items))
+}
+
+/// Handles workspace symbol request.
+pub fn handle_workspace_symbol(
+    params: lsp_types::WorkspaceSymbolParams,
+    memory: &mut Memory,
+    client_capabilities: &lsp_types::ClientCapabilities,
+) -> anyhow::Result<Option<lsp_types::WorkspaceSymbolResponse>> {
+    // Searches the ink! entity symbol index for all open documents
+    // (and the persisted symbol index for unchanged closed documents).
+    let results = memory.search_symbols(&params.query);
+
+    // Translates ink! analyzer symbols to LSP symbol information
+    // (translation contexts are reused for symbols in the same document).
+    let encoding = utils::position_encoding(client_capabilities);
+    let snapshot = memory.snapshot();
+    let mut translation_contexts: HashMap<String, Option<PositionTranslationContext>> =
+        HashMap::new();
+    let mut symbols = Vec::new();
+    for (id, symbol) in results {
+        let Ok(uri) = lsp_types::Url::parse(&id) else {
+            continue;
+        };
+        let translation_context = translation_contexts.entry(id).or_insert_with(|| {
+            snapshot.read(&uri).map(|file| PositionTranslationContext {
+                encoding: encoding.clone(),
+                line_index: LineIndex::new(&file.content),
+            })
+        });
+        if let Some(symbol) = translation_context
+            .as_ref()
+            .and_then(|translation_context| {
+                translator::to_lsp::symbol(&symbol, uri, translation_context)
+            })
+        {
+            symbols.push(symbol);
+        }
+    }
+
+    Ok(Some(lsp_types::WorkspaceSymbolResponse::Flat(symbols)))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateProjectResponse {
     pub name: String,
@@ -218,11 +571,17 @@ pub struct CreateProjectResponse {
     pub files: HashMap<lsp_types::Url, String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyMetadataResponse {
+    pub uri: lsp_types::Url,
+    pub diagnostics: Vec<lsp_types::Diagnostic>,
+}
+
 /// Handles execute command request.
 pub fn handle_execute_command(
     params: lsp_types::ExecuteCommandParams,
-    _memory: &mut Memory,
-    _client_capabilities: &lsp_types::ClientCapabilities,
+    memory: &mut Memory,
+    client_capabilities: &lsp_types::ClientCapabilities,
 ) -> anyhow::Result<Option<serde_json::Value>> {
     // Handles create project command.
     if params.command == "createProject" {
@@ -278,6 +637,57 @@ pub fn handle_execute_command(
                 "The name and root arguments are required!"
             )),
         }
+    } else if params.command == "verifyMetadata" {
+        // Handles verify metadata command.
+        let args =
+            params
+                .arguments
+                .first()
+                .and_then(serde_json::Value::as_object)
+                .and_then(|arg| {
+                    arg.get("uri")
+                        .and_then(|it| it.as_str())
+                        .and_then(|it| lsp_types::Url::parse(it).ok())
+                        .zip(arg.get("metadata").and_then(|it| {
+                            ink_analyzer::ContractMetadata::from_json(&it.to_string())
+                        }))
+                });
+
+        match args {
+            Some((uri, metadata)) => {
+                // Uses the same diagnostics configuration as published diagnostics
+                // (i.e. the metadata name is matched against the package name from the crate's `Cargo.toml`).
+                let config = memory.diagnostics_config(uri.as_str());
+                match memory.analysis(uri.as_str()).zip(memory.get(uri.as_str())) {
+                    Some((analysis, doc)) => {
+                        // Composes translation context.
+                        let translation_context = PositionTranslationContext {
+                            encoding: utils::position_encoding(client_capabilities),
+                            line_index: LineIndex::new(&doc.content),
+                        };
+
+                        // Verifies the document against the metadata and translates the results.
+                        let diagnostics = analysis
+                            .verify_metadata_with_config(&metadata, &config)
+                            .into_iter()
+                            .filter_map(|diagnostic| {
+                                translator::to_lsp::diagnostic(
+                                    diagnostic,
+                                    &uri,
+                                    &translation_context,
+                                )
+                            })
+                            .collect();
+                        Ok(serde_json::to_value(VerifyMetadataResponse { uri, diagnostics }).ok())
+                    }
+                    None => Err(anyhow::format_err!("Unknown document: {uri}")),
+                }
+            }
+            // Error for missing or invalid args.
+            None => Err(anyhow::format_err!(
+                "The uri and (valid) metadata arguments are required!"
+            )),
+        }
     } else {
         Err(anyhow::format_err!("Unknown command: {}!", params.command))
     }
@@ -324,6 +734,63 @@ mod tests {
         assert!(completion_items[0].label.contains("contract"));
     }
 
+    #[test]
+    fn handle_completion_resolve_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+
+        // Creates test document.
+        let uri = document("#[ink::co]".to_string(), &mut memory);
+
+        // Computes completions.
+        let completion_item = match handle_completion(
+            lsp_types::CompletionParams {
+                text_document_position: lsp_types::TextDocumentPositionParams {
+                    text_document: lsp_types::TextDocumentIdentifier { uri },
+                    position: lsp_types::Position {
+                        line: 0,
+                        character: 9,
+                    },
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+                context: None,
+            },
+            &mut memory,
+            &simple_client_config(),
+        )
+        .unwrap()
+        .unwrap()
+        {
+            lsp_types::CompletionResponse::List(it) => Some(it),
+            lsp_types::CompletionResponse::Array(_) => None,
+        }
+        .unwrap()
+        .items
+        .remove(0);
+        // Completion resolve data only includes the completion identifier.
+        assert_eq!(
+            completion_item.data,
+            Some(serde_json::json!({ "id": "ink::contract" }))
+        );
+        // Clears documentation (i.e. to simulate clients that drop it until resolving).
+        let completion_item = lsp_types::CompletionItem {
+            documentation: None,
+            ..completion_item
+        };
+
+        // Calls handler and verifies that the expected documentation is returned.
+        let result =
+            handle_completion_resolve(completion_item, &mut memory, &simple_client_config());
+        assert!(result.is_ok());
+        let documentation = match result.unwrap().documentation.unwrap() {
+            lsp_types::Documentation::MarkupContent(it) => Some(it.value),
+            lsp_types::Documentation::String(_) => None,
+        }
+        .unwrap();
+        assert!(documentation.contains("ink::contract"));
+    }
+
     #[test]
     fn handle_hover_works() {
         // Initializes memory.
@@ -396,6 +863,129 @@ mod tests {
         .contains("Add ink! contract"));
     }
 
+    #[test]
+    fn handle_code_action_fix_all_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+
+        // Creates test document.
+        let uri = document(
+            "#[ink::contract]\nmod my_contract {}".to_string(),
+            &mut memory,
+        );
+
+        // Calls handler and verifies that only the requested "fix all" source action is added.
+        let fix_all_actions = |only: Option<Vec<lsp_types::CodeActionKind>>,
+                               memory: &mut Memory| {
+            handle_code_action(
+                lsp_types::CodeActionParams {
+                    text_document: lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+                    range: lsp_types::Range::default(),
+                    context: lsp_types::CodeActionContext {
+                        only,
+                        ..Default::default()
+                    },
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                },
+                memory,
+                &simple_client_config(),
+            )
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .filter_map(|it| match it {
+                lsp_types::CodeActionOrCommand::CodeAction(it) => Some(it),
+                lsp_types::CodeActionOrCommand::Command(_) => None,
+            })
+            .filter(|it| it.kind == Some(utils::FIX_ALL_CODE_ACTION_KIND))
+            .collect::<Vec<_>>()
+        };
+        for (only, expected_n_actions) in [
+            (None, 0),
+            (Some(vec![lsp_types::CodeActionKind::SOURCE_FIX_ALL]), 1),
+            (Some(vec![lsp_types::CodeActionKind::SOURCE]), 1),
+            (Some(vec![lsp_types::CodeActionKind::QUICKFIX]), 0),
+        ] {
+            let actions = fix_all_actions(only, &mut memory);
+            assert_eq!(actions.len(), expected_n_actions);
+            assert!(actions.iter().all(|action| action.edit.is_some()));
+        }
+    }
+
+    #[test]
+    fn handle_code_action_resolve_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+
+        // Creates test document.
+        let uri = document("mod my_contract {}".to_string(), &mut memory);
+
+        // Client capabilities with code action resolve support.
+        let client_capabilities = lsp_types::ClientCapabilities {
+            text_document: Some(lsp_types::TextDocumentClientCapabilities {
+                code_action: Some(lsp_types::CodeActionClientCapabilities {
+                    resolve_support: Some(lsp_types::CodeActionCapabilityResolveSupport {
+                        properties: vec!["edit".to_string()],
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..simple_client_config()
+        };
+
+        // Computes unresolved code actions.
+        let code_action = match handle_code_action(
+            lsp_types::CodeActionParams {
+                text_document: lsp_types::TextDocumentIdentifier { uri },
+                range: lsp_types::Range {
+                    start: lsp_types::Position {
+                        line: 0,
+                        character: 0,
+                    },
+                    end: lsp_types::Position {
+                        line: 0,
+                        character: 15,
+                    },
+                },
+                context: Default::default(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            },
+            &mut memory,
+            &client_capabilities,
+        )
+        .unwrap()
+        .unwrap()
+        .remove(0)
+        {
+            lsp_types::CodeActionOrCommand::CodeAction(it) => Some(it),
+            lsp_types::CodeActionOrCommand::Command(_) => None,
+        }
+        .unwrap();
+        // Initial code action payloads don't include edits.
+        assert!(code_action.title.contains("Add ink! contract"));
+        assert!(code_action.edit.is_none());
+        assert!(code_action.data.is_some());
+
+        // Calls handler and verifies that the expected edits are returned.
+        let result = handle_code_action_resolve(code_action, &mut memory, &client_capabilities);
+        assert!(result.is_ok());
+        let resolved_code_action = result.unwrap();
+        assert!(resolved_code_action.title.contains("Add ink! contract"));
+        let edit = serde_json::to_string(&resolved_code_action.edit.unwrap()).unwrap();
+        assert!(edit.contains("#[ink::contract]"));
+
+        // Invalid code action data is an error.
+        assert!(handle_code_action_resolve(
+            lsp_types::CodeAction::default(),
+            &mut memory,
+            &client_capabilities
+        )
+        .is_err());
+    }
+
     #[test]
     fn handle_inlay_hint_works() {
         // Initializes memory.
@@ -410,7 +1000,7 @@ mod tests {
         // Calls handler and verifies that the expected inlay hints are returned.
         let result = handle_inlay_hint(
             lsp_types::InlayHintParams {
-                text_document: lsp_types::TextDocumentIdentifier { uri },
+                text_document: lsp_types::TextDocumentIdentifier { uri: uri.clone() },
                 range: lsp_types::Range {
                     start: lsp_types::Position {
                         line: 0,
@@ -450,6 +1040,262 @@ mod tests {
         }
         .unwrap()
         .contains("comma separated"));
+
+        // Disables argument kind inlay hints and verifies that no inlay hints are returned.
+        memory.set_settings(crate::settings::Settings {
+            inlay_hints: crate::settings::InlayHintsSettings {
+                argument_kinds: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let result = handle_inlay_hint(
+            lsp_types::InlayHintParams {
+                text_document: lsp_types::TextDocumentIdentifier { uri },
+                range: lsp_types::Range {
+                    start: lsp_types::Position {
+                        line: 0,
+                        character: 0,
+                    },
+                    end: lsp_types::Position {
+                        line: 0,
+                        character: 57,
+                    },
+                },
+                work_done_progress_params: Default::default(),
+            },
+            &mut memory,
+            &simple_client_config(),
+        );
+        assert!(result.unwrap().unwrap().is_empty());
+    }
+
+    #[test]
+    fn handle_folding_range_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+
+        // Creates test document.
+        let uri = document(
+            "#[ink::contract]\nmod my_contract {\n}".to_string(),
+            &mut memory,
+        );
+
+        // Calls handler and verifies that the expected folding ranges are returned.
+        let result = handle_folding_range(
+            lsp_types::FoldingRangeParams {
+                text_document: lsp_types::TextDocumentIdentifier { uri },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            },
+            &mut memory,
+            &simple_client_config(),
+        );
+        assert!(result.is_ok());
+        let folding_ranges = result.unwrap().unwrap();
+        assert_eq!(folding_ranges.len(), 1);
+        assert_eq!(folding_ranges[0].start_line, 1);
+        assert_eq!(folding_ranges[0].end_line, 2);
+        assert_eq!(
+            folding_ranges[0].kind,
+            Some(lsp_types::FoldingRangeKind::Region)
+        );
+    }
+
+    #[test]
+    fn handle_document_diagnostic_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+
+        // Creates test document.
+        let uri = document(
+            "#[ink::contract]\nmod my_contract {\n}".to_string(),
+            &mut memory,
+        );
+
+        let pull_diagnostics = |memory: &mut Memory, previous_result_id: Option<String>| {
+            handle_document_diagnostic(
+                lsp_types::DocumentDiagnosticParams {
+                    text_document: lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+                    identifier: None,
+                    previous_result_id,
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                },
+                memory,
+                &simple_client_config(),
+            )
+            .unwrap()
+        };
+
+        // Verifies that a full report is returned for the first request.
+        let result_id = match pull_diagnostics(&mut memory, None) {
+            lsp_types::DocumentDiagnosticReportResult::Report(
+                lsp_types::DocumentDiagnosticReport::Full(report),
+            ) => {
+                let report = report.full_document_diagnostic_report;
+                // 3 Expected diagnostics for missing storage, constructor and message.
+                assert_eq!(report.items.len(), 3);
+                report.result_id.unwrap()
+            }
+            report => panic!("Expected full report, got: {report:?}"),
+        };
+
+        // Verifies that an unchanged report is returned if the document hasn't changed.
+        assert!(matches!(
+            pull_diagnostics(&mut memory, Some(result_id.clone())),
+            lsp_types::DocumentDiagnosticReportResult::Report(
+                lsp_types::DocumentDiagnosticReport::Unchanged(
+                    lsp_types::RelatedUnchangedDocumentDiagnosticReport {
+                        unchanged_document_diagnostic_report:
+                            lsp_types::UnchangedDocumentDiagnosticReport { result_id: id },
+                        ..
+                    }
+                )
+            ) if id == result_id
+        ));
+
+        // Verifies that a full report is returned if the document has changed.
+        memory.update(uri.as_str(), "fn main() {}".to_string(), 1);
+        assert!(matches!(
+            pull_diagnostics(&mut memory, Some(result_id)),
+            lsp_types::DocumentDiagnosticReportResult::Report(
+                lsp_types::DocumentDiagnosticReport::Full(
+                    lsp_types::RelatedFullDocumentDiagnosticReport {
+                        full_document_diagnostic_report: lsp_types::FullDocumentDiagnosticReport {
+                            items,
+                            ..
+                        },
+                        ..
+                    }
+                )
+            ) if items.is_empty()
+        ));
+    }
+
+    #[test]
+    fn handle_workspace_symbol_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+
+        // Creates test documents.
+        let uri = document(
+            "#[ink::contract]\nmod my_contract {\n    #[ink(event)]\n    pub struct MyEvent {}\n}"
+                .to_string(),
+            &mut memory,
+        );
+        let other_uri = lsp_types::Url::parse("file:///tmp/other.rs").unwrap();
+        memory.insert(
+            other_uri.to_string(),
+            "#[ink::trait_definition]\npub trait MyTrait {\n    #[ink(message)]\n    fn transfer(&self);\n}"
+                .to_string(),
+            0,
+        );
+
+        for (query, expected_results) in [
+            (
+                "MyEvent",
+                vec![(
+                    "MyEvent",
+                    lsp_types::SymbolKind::EVENT,
+                    uri.clone(),
+                    (3, 15, 3, 22),
+                )],
+            ),
+            (
+                "message: transfer",
+                vec![(
+                    "transfer",
+                    lsp_types::SymbolKind::METHOD,
+                    other_uri.clone(),
+                    (3, 7, 3, 15),
+                )],
+            ),
+            ("unknown", vec![]),
+        ] {
+            // Calls handler and verifies that the expected symbols are returned.
+            let result = handle_workspace_symbol(
+                lsp_types::WorkspaceSymbolParams {
+                    query: query.to_string(),
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                },
+                &mut memory,
+                &simple_client_config(),
+            );
+            let symbols = match result.unwrap().unwrap() {
+                lsp_types::WorkspaceSymbolResponse::Flat(it) => Some(it),
+                lsp_types::WorkspaceSymbolResponse::Nested(_) => None,
+            }
+            .unwrap();
+            let results: Vec<_> = symbols
+                .into_iter()
+                .map(|symbol| {
+                    let range = symbol.location.range;
+                    (
+                        symbol.name,
+                        symbol.kind,
+                        symbol.location.uri,
+                        (
+                            range.start.line,
+                            range.start.character,
+                            range.end.line,
+                            range.end.character,
+                        ),
+                    )
+                })
+                .collect();
+            let expected_results: Vec<_> = expected_results
+                .into_iter()
+                .map(|(name, kind, uri, range)| (name.to_string(), kind, uri, range))
+                .collect();
+            assert_eq!(results, expected_results, "query: {query}");
+        }
+    }
+
+    #[test]
+    fn handle_selection_range_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+
+        // Creates test document.
+        let uri = document(
+            "#[ink::contract]\nmod my_contract {\n}".to_string(),
+            &mut memory,
+        );
+
+        // Calls handler and verifies that the expected selection ranges are returned.
+        let result = handle_selection_range(
+            lsp_types::SelectionRangeParams {
+                text_document: lsp_types::TextDocumentIdentifier { uri },
+                positions: vec![lsp_types::Position {
+                    line: 0,
+                    character: 4,
+                }],
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            },
+            &mut memory,
+            &simple_client_config(),
+        );
+        assert!(result.is_ok());
+        let selection_ranges = result.unwrap().unwrap();
+        assert_eq!(selection_ranges.len(), 1);
+        // The narrowest range is the `ink` path segment.
+        assert_eq!(
+            selection_ranges[0].range,
+            lsp_types::Range::new(
+                lsp_types::Position {
+                    line: 0,
+                    character: 2,
+                },
+                lsp_types::Position {
+                    line: 0,
+                    character: 5,
+                }
+            )
+        );
+        assert!(selection_ranges[0].parent.is_some());
     }
 
     #[test]
@@ -539,4 +1385,97 @@ mod tests {
         assert!(lib_content.contains("#[ink::contract]\npub mod hello_ink {"));
         assert!(cargo_content.contains(r#"name = "hello_ink""#));
     }
+
+    #[test]
+    fn handle_execute_command_verify_metadata_works() {
+        // Initializes memory.
+        let mut memory = Memory::new();
+
+        // Creates test document.
+        let uri = document(
+            r#"
+#[ink::contract]
+mod flipper {
+    impl Flipper {
+        #[ink(constructor)]
+        pub fn new(init_value: bool) -> Self {}
+
+        #[ink(message)]
+        pub fn toggle(&mut self) {}
+    }
+}"#
+            .to_string(),
+            &mut memory,
+        );
+
+        // Calls handler and verifies that the expected response is returned.
+        let result = handle_execute_command(
+            lsp_types::ExecuteCommandParams {
+                command: "verifyMetadata".to_string(),
+                arguments: vec![serde_json::json!({
+                    "uri": uri,
+                    "metadata": {
+                        "spec": {
+                            "constructors": [{
+                                "label": "new",
+                                "selector": "0x9bae9d5e",
+                                "args": [{
+                                    "label": "init_value",
+                                    "type": { "displayName": ["bool"], "type": 0 }
+                                }]
+                            }],
+                            "messages": [{
+                                "label": "flip",
+                                "selector": "0x633aa551",
+                                "args": []
+                            }]
+                        }
+                    }
+                })],
+                work_done_progress_params: Default::default(),
+            },
+            &mut memory,
+            &simple_client_config(),
+        );
+        assert!(result.is_ok());
+        let resp: VerifyMetadataResponse =
+            serde_json::from_value(result.unwrap().unwrap()).unwrap();
+        assert_eq!(resp.uri, uri);
+        let messages: Vec<&str> = resp
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect();
+        assert_eq!(messages.len(), 2);
+        // Diagnostics are sorted by range (i.e. the contract declaration comes first).
+        assert!(messages[0].contains("`flip` from the contract metadata is not defined in code"));
+        assert!(messages[1].contains("`toggle` is not defined in the contract metadata"));
+
+        // Calls handler with metadata for another contract,
+        // and verifies that the unmatched metadata is reported once.
+        let result = handle_execute_command(
+            lsp_types::ExecuteCommandParams {
+                command: "verifyMetadata".to_string(),
+                arguments: vec![serde_json::json!({
+                    "uri": uri,
+                    "metadata": {
+                        "contract": { "name": "erc20", "version": "0.1.0" },
+                        "spec": {
+                            "constructors": [],
+                            "messages": []
+                        }
+                    }
+                })],
+                work_done_progress_params: Default::default(),
+            },
+            &mut memory,
+            &simple_client_config(),
+        );
+        let resp: VerifyMetadataResponse =
+            serde_json::from_value(result.unwrap().unwrap()).unwrap();
+        assert_eq!(resp.diagnostics.len(), 1);
+        assert!(resp.diagnostics[0]
+            .message
+            .contains("The contract metadata is for `erc20`"));
+    }
 }
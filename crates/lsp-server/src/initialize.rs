@@ -54,7 +54,7 @@ pub fn server_capabilities(
         )),
         hover_provider: Some(lsp_types::HoverProviderCapability::Simple(true)),
         completion_provider: Some(lsp_types::CompletionOptions {
-            resolve_provider: None,
+            resolve_provider: Some(true),
             // ink! completions are all attribute based.
             trigger_characters: Some(vec![
                 "[".to_string(),
@@ -66,29 +66,48 @@ pub fn server_capabilities(
             work_done_progress_options: Default::default(),
             completion_item: Default::default(),
         }),
-        code_action_provider: Some(utils::code_actions_kinds(client_capabilities).map_or(
-            lsp_types::CodeActionProviderCapability::Simple(true),
-            |code_action_kinds| {
-                lsp_types::CodeActionProviderCapability::Options(lsp_types::CodeActionOptions {
-                    code_action_kinds: Some(code_action_kinds),
-                    work_done_progress_options: Default::default(),
-                    resolve_provider: None,
-                })
+        code_action_provider: Some(
+            match (
+                utils::code_actions_kinds(client_capabilities),
+                utils::code_action_resolve_support(client_capabilities),
+            ) {
+                (None, false) => lsp_types::CodeActionProviderCapability::Simple(true),
+                (code_action_kinds, resolve_support) => {
+                    lsp_types::CodeActionProviderCapability::Options(lsp_types::CodeActionOptions {
+                        code_action_kinds,
+                        work_done_progress_options: Default::default(),
+                        // Edits are computed lazily for clients that support resolving code actions.
+                        resolve_provider: resolve_support.then_some(true),
+                    })
+                }
             },
-        )),
+        ),
         inlay_hint_provider: Some(lsp_types::OneOf::Right(
             lsp_types::InlayHintServerCapabilities::Options(lsp_types::InlayHintOptions {
                 work_done_progress_options: Default::default(),
                 resolve_provider: None,
             }),
         )),
+        folding_range_provider: Some(lsp_types::FoldingRangeProviderCapability::Simple(true)),
+        selection_range_provider: Some(lsp_types::SelectionRangeProviderCapability::Simple(true)),
         signature_help_provider: Some(lsp_types::SignatureHelpOptions {
             trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
             retrigger_characters: None,
             work_done_progress_options: Default::default(),
         }),
+        // Diagnostics are only pulled by clients that support it (otherwise, they're published).
+        diagnostic_provider: utils::pull_diagnostics_support(client_capabilities).then(|| {
+            lsp_types::DiagnosticServerCapabilities::Options(lsp_types::DiagnosticOptions {
+                identifier: Some("ink-analyzer".to_string()),
+                // ink! diagnostics are computed per file.
+                inter_file_dependencies: false,
+                workspace_diagnostics: false,
+                work_done_progress_options: Default::default(),
+            })
+        }),
+        workspace_symbol_provider: Some(lsp_types::OneOf::Left(true)),
         execute_command_provider: Some(lsp_types::ExecuteCommandOptions {
-            commands: vec!["createProject".to_string()],
+            commands: vec!["createProject".to_string(), "verifyMetadata".to_string()],
             work_done_progress_options: Default::default(),
         }),
         ..Default::default()
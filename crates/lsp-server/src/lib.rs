@@ -2,11 +2,17 @@
 //!
 //! Installation and usage instructions for the ink! Language Server (`ink-lsp-server`) binary can be found in the [crate README on GitHub](https://github.com/ink-analyzer/ink-analyzer/tree/master/crates/lsp-server) (or [crates.io](https://crates.io/crates/ink-lsp-server)).
 
-pub use {dispatch::main_loop, initialize::initialize};
+pub use {
+    dispatch::{main_loop, main_loop_with_settings},
+    initialize::initialize,
+    settings::{InlayHintsSettings, Settings},
+};
 
 mod dispatch;
 mod initialize;
 mod memory;
+mod settings;
 mod test_utils;
 pub mod translator;
 mod utils;
+mod vfs;
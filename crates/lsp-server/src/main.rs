@@ -44,7 +44,13 @@ fn run_server() -> anyhow::Result<()> {
     let (connection, initialize_params) = ink_lsp_server::initialize(connection)?;
 
     // Runs the message dispatch loop.
-    ink_lsp_server::main_loop(connection, initialize_params.capabilities)?;
+    // Parses settings from initialization options (if any).
+    let settings = initialize_params
+        .initialization_options
+        .as_ref()
+        .and_then(ink_lsp_server::Settings::from_json)
+        .unwrap_or_default();
+    ink_lsp_server::main_loop_with_settings(connection, initialize_params.capabilities, settings)?;
 
     // Waits for thread to finish.
     io_threads.join()?;
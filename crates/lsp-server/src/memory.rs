@@ -1,64 +1,542 @@
+use ink_analyzer::{
+    Analysis, CachedDiagnostics, Diagnostic, DiagnosticsCache, DiagnosticsConfig, FileIndex,
+    Symbol, SymbolIndex,
+};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::mem;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::{fs, mem};
+
+use crate::settings::Settings;
+use crate::vfs::{Vfs, VfsSnapshot};
 
 /// Store a copy of the open documents in memory and tracks documents with unprocessed changes.
+///
+/// Open documents are overlaid on the file system contents (see [`Vfs`]).
 pub struct Memory {
-    docs: HashMap<String, Document>,
+    vfs: Vfs,
     changes: HashSet<String>,
+    /// Index of ink! entity symbols for open documents (see [`Memory::symbol_index`]).
+    symbols: SymbolIndex,
+    /// Documents whose symbols are missing from (or outdated in) the symbol index.
+    stale_symbols: HashSet<String>,
+    /// Persisted index of ink! entity symbols for documents that are not open
+    /// (see [`Settings::symbol_index_path`]), including closed files indexed for cross-file features
+    /// (see [`Memory::file_index`]).
+    persisted_symbols: SymbolIndex,
+    /// Analysis of the current version of each document (see [`Memory::analysis`]).
+    analyses: HashMap<String, (i32, Rc<Analysis>)>,
+    /// Last diagnostics computed for each document version (see [`Memory::diagnostics`]).
+    diagnostics: DiagnosticsCache,
+    /// Diagnostics configuration and cross-file selector diagnostics (see [`Memory::overlapping_selectors`])
+    /// used for the cached diagnostics of each document.
+    diagnostics_configs: HashMap<String, (DiagnosticsConfig, Vec<Diagnostic>)>,
+    /// Documents whose diagnostics configuration and cross-file selector diagnostics may be outdated
+    /// (i.e. because other documents or files on the file system changed).
+    stale_diagnostics_configs: HashSet<String>,
+    /// Server settings (e.g. from LSP client initialization options).
+    settings: Settings,
 }
 
 impl Memory {
     /// Creates a new instance.
     pub fn new() -> Self {
         Self {
-            docs: HashMap::new(),
+            vfs: Vfs::new(),
             changes: HashSet::new(),
+            symbols: SymbolIndex::new(),
+            stale_symbols: HashSet::new(),
+            persisted_symbols: SymbolIndex::new(),
+            analyses: HashMap::new(),
+            diagnostics: DiagnosticsCache::new(),
+            diagnostics_configs: HashMap::new(),
+            stale_diagnostics_configs: HashSet::new(),
+            settings: Settings::default(),
+        }
+    }
+
+    /// Creates a new instance with the given settings.
+    ///
+    /// The persisted symbol index (if any) is loaded from the symbol index path.
+    pub fn with_settings(settings: Settings) -> Self {
+        Self {
+            persisted_symbols: load_symbol_index(settings.symbol_index_path.as_deref()),
+            settings,
+            ..Self::new()
         }
     }
 
+    /// Returns the server settings.
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Updates the server settings.
+    ///
+    /// Cached analyses and diagnostics are cleared and all open documents are marked as changed
+    /// (i.e. so that diagnostics are recomputed and republished using the new settings).
+    pub fn set_settings(&mut self, settings: Settings) {
+        if settings.symbol_index_path != self.settings.symbol_index_path {
+            self.persisted_symbols = load_symbol_index(settings.symbol_index_path.as_deref());
+        }
+        self.settings = settings;
+        self.analyses.clear();
+        self.diagnostics.clear();
+        self.diagnostics_configs.clear();
+        self.changes.extend(self.vfs.overlay_ids().cloned());
+    }
+
+    /// Handles changes to files on the file system (e.g. `Cargo.toml` and out-of-line child module files).
+    ///
+    /// Diagnostics configurations of all open documents are marked as stale
+    /// and all open documents are marked as changed (i.e. so that diagnostics are republished if necessary).
+    pub fn files_changed(&mut self) {
+        self.mark_diagnostics_configs_stale();
+        self.changes.extend(self.vfs.overlay_ids().cloned());
+    }
+
+    /// Marks the diagnostics configurations of all open documents as stale
+    /// (i.e. so that they're recomputed the next time diagnostics are requested, see [`Memory::diagnostics_with`]).
+    fn mark_diagnostics_configs_stale(&mut self) {
+        self.stale_diagnostics_configs
+            .extend(self.vfs.overlay_ids().cloned());
+    }
+
     /// Adds document.
     pub fn insert(&mut self, id: String, content: String, version: i32) {
-        self.docs.insert(id.clone(), Document { content, version });
+        self.vfs.add_overlay(id.clone(), content, version);
+        self.stale_symbols.insert(id.clone());
+        self.mark_diagnostics_configs_stale();
+        self.mark_child_modules_changed(&id);
         self.changes.insert(id);
     }
 
     /// Retrieves document.
     pub fn get(&self, id: &str) -> Option<&Document> {
-        self.docs.get(id)
+        self.vfs.overlay(id)
     }
 
     /// Updates document.
     pub fn update(&mut self, id: &str, content: String, version: i32) -> bool {
-        match self.docs.get_mut(id) {
-            Some(doc) => {
-                doc.content = content;
-                doc.version = version;
-                self.changes.insert(id.to_string());
-                true
-            }
-            None => false,
+        let is_updated = self.vfs.update_overlay(id, content, version);
+        if is_updated {
+            self.stale_symbols.insert(id.to_string());
+            self.mark_diagnostics_configs_stale();
+            self.mark_child_modules_changed(id);
+            self.changes.insert(id.to_string());
         }
+        is_updated
     }
 
     /// Removes document.
     pub fn remove(&mut self, id: &str) -> Option<Document> {
-        self.docs.remove(id).map(|doc| {
+        self.vfs.remove_overlay(id).inspect(|_| {
+            // Keeps the symbols of closed documents for persisting (if enabled),
+            // they're only used while the file contents are unchanged (see `Memory::search_symbols`).
+            if let Some(index) = self.symbols.remove(id) {
+                if self.settings.symbol_index_path.is_some() {
+                    self.persisted_symbols.insert_index(id.to_string(), index);
+                }
+            }
+            self.stale_symbols.remove(id);
+            self.analyses.remove(id);
+            self.diagnostics.remove(id);
+            self.diagnostics_configs.remove(id);
+            self.stale_diagnostics_configs.remove(id);
+            self.mark_diagnostics_configs_stale();
+            self.mark_child_modules_changed(id);
             self.changes.insert(id.to_string());
-            doc
         })
     }
 
+    /// Marks open documents that are out-of-line child modules of the document as changed
+    /// (i.e. because their diagnostics depend on the contents of their parent module file,
+    /// see [`Memory::is_contract_child_module`]).
+    fn mark_child_modules_changed(&mut self, id: &str) {
+        let Some(parent_path) = file_path(id) else {
+            return;
+        };
+        let child_ids: Vec<String> = self
+            .vfs
+            .overlay_ids()
+            .filter(|child_id| {
+                *child_id != id
+                    && file_path(child_id).map_or(false, |path| {
+                        ink_analyzer::parent_module_candidates(&path)
+                            .iter()
+                            .any(|(candidate_path, _)| *candidate_path == parent_path)
+                    })
+            })
+            .cloned()
+            .collect();
+        self.changes.extend(child_ids);
+    }
+
+    /// Returns a snapshot of the open documents overlaid on the file system contents
+    /// (i.e. for an analysis pass that reads other files).
+    pub fn snapshot(&self) -> VfsSnapshot {
+        self.vfs.snapshot()
+    }
+
+    /// Returns the index of ink! entity symbols for all open documents.
+    ///
+    /// The index is updated lazily (i.e. only documents that changed since the last call are re-indexed),
+    /// and persisted symbols are reused for documents whose contents are unchanged since they were indexed.
+    pub fn symbol_index(&mut self) -> &SymbolIndex {
+        for id in mem::take(&mut self.stale_symbols) {
+            match self.vfs.overlay(&id) {
+                // Skips parsing plain Rust documents (i.e. documents without ink! entities).
+                Some(doc) if Analysis::contains_ink_entities(&doc.content) => {
+                    let hash = ink_analyzer::content_hash(&doc.content);
+                    if self.symbols.is_fresh(&id, hash) {
+                        continue;
+                    }
+                    match self.persisted_symbols.remove(&id) {
+                        Some(index) if index.hash == hash => self.symbols.insert_index(id, index),
+                        _ => {
+                            if let Some(analysis) = self.analysis(&id) {
+                                self.symbols.insert_index(id, analysis.file_index());
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    self.symbols.remove(&id);
+                }
+            }
+        }
+        &self.symbols
+    }
+
+    /// Searches the symbol index for open documents (see [`Memory::symbol_index`])
+    /// and the persisted symbol index for closed documents,
+    /// and returns matching symbols paired with the id of their document.
+    ///
+    /// Persisted symbols are only included for documents whose file contents
+    /// are unchanged since they were indexed.
+    ///
+    /// Results are sorted by document id, then position.
+    pub fn search_symbols(&mut self, query: &str) -> Vec<(String, Symbol)> {
+        let mut results: Vec<(String, Symbol)> = self
+            .symbol_index()
+            .search(query)
+            .into_iter()
+            .map(|(id, symbol)| (id.to_string(), symbol.clone()))
+            .collect();
+
+        let snapshot = self.vfs.snapshot();
+        let mut freshness: HashMap<&str, bool> = HashMap::new();
+        for (id, symbol) in self.persisted_symbols.search(query) {
+            if self.vfs.overlay(id).is_some() {
+                continue;
+            }
+            let is_fresh = *freshness.entry(id).or_insert_with(|| {
+                lsp_types::Url::parse(id)
+                    .ok()
+                    .and_then(|uri| snapshot.read(&uri))
+                    .map_or(false, |file| {
+                        self.persisted_symbols
+                            .is_fresh(id, ink_analyzer::content_hash(&file.content))
+                    })
+            });
+            if is_fresh {
+                results.push((id.to_string(), symbol.clone()));
+            }
+        }
+
+        results.sort_by(|(a_id, a), (b_id, b)| {
+            (a_id, a.range.start(), a.range.end()).cmp(&(b_id, b.range.start(), b.range.end()))
+        });
+        results
+    }
+
+    /// Persists the symbol index for open documents and the persisted symbol index for closed documents
+    /// to the symbol index path (if any, see [`Settings::symbol_index_path`]).
+    pub fn persist_symbol_index(&mut self) -> anyhow::Result<()> {
+        let Some(path) = self.settings.symbol_index_path.clone() else {
+            return Ok(());
+        };
+        let mut index = self.persisted_symbols.clone();
+        index.extend(self.symbol_index().clone());
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let persisted = PersistedSymbolIndex {
+            version: ink_analyzer::VERSION.to_string(),
+            index,
+        };
+        fs::write(path, serde_json::to_string(&persisted)?)?;
+        Ok(())
+    }
+
+    /// Returns the file index of the document or file (if it exists and contains ink! entities).
+    ///
+    /// File indexes from the symbol index are used for open documents,
+    /// and persisted file indexes are used for closed files whose contents are unchanged
+    /// since they were indexed (i.e. closed files are only analyzed if they're not indexed,
+    /// and their file index is then added to the persisted symbol index for reuse).
+    fn file_index(&mut self, id: &str) -> Option<FileIndex> {
+        if self.vfs.overlay(id).is_some() {
+            // Open documents without an index have no ink! entities.
+            return self.symbol_index().file_index(id).cloned();
+        }
+        let file = lsp_types::Url::parse(id)
+            .ok()
+            .and_then(|uri| self.vfs.snapshot().read(&uri))
+            .filter(|file| Analysis::contains_ink_entities(&file.content))?;
+        let hash = ink_analyzer::content_hash(&file.content);
+        match self.persisted_symbols.file_index(id) {
+            Some(index) if index.hash == hash => Some(index.clone()),
+            _ => {
+                let index = Analysis::new(&file.content).file_index();
+                self.persisted_symbols
+                    .insert_index(id.to_string(), index.clone());
+                Some(index)
+            }
+        }
+    }
+
+    /// Returns the names of the out-of-line child modules in the root of the ink! contracts
+    /// in the file (if any), see [`Memory::file_index`].
+    fn contract_child_modules(&mut self, path: &Path) -> Vec<String> {
+        lsp_types::Url::from_file_path(path)
+            .ok()
+            .and_then(|uri| self.file_index(uri.as_str()))
+            .map(|index| index.contract_child_modules)
+            .unwrap_or_default()
+    }
+
+    /// Returns diagnostics for selectors of the document that overlap with selectors
+    /// in the other files of the ink! contract the document belongs to
+    /// (i.e. for ink! contracts split across multiple files via out-of-line child modules),
+    /// see [`FileIndex::overlapping_selectors`].
+    ///
+    /// Other files are only analyzed if they're not indexed (see [`Memory::file_index`]).
+    pub fn overlapping_selectors(&mut self, id: &str) -> Vec<Diagnostic> {
+        let other_ids = self.contract_files(id);
+        if other_ids.is_empty() {
+            return Vec::new();
+        }
+        let Some(index) = self.file_index(id) else {
+            return Vec::new();
+        };
+
+        let others: Vec<(String, FileIndex)> = other_ids
+            .into_iter()
+            .filter_map(|other_id| self.file_index(&other_id).map(|other| (other_id, other)))
+            .collect();
+        index.overlapping_selectors(others.iter().map(|(id, index)| (id.as_str(), index)))
+    }
+
+    /// Returns the ids of the other files of the ink! contract the document belongs to (if any)
+    /// (i.e. the file of the ink! contract's root module and its out-of-line child module files).
+    fn contract_files(&mut self, id: &str) -> Vec<String> {
+        let Some(path) = file_path(id) else {
+            return Vec::new();
+        };
+
+        // Finds the file of the ink! contract's root module (i.e. the document or its parent module file).
+        let child_modules = self.contract_child_modules(&path);
+        let root = if child_modules.is_empty() {
+            ink_analyzer::parent_module_candidates(&path)
+                .into_iter()
+                .find_map(|(parent_path, name)| {
+                    let child_modules = self.contract_child_modules(&parent_path);
+                    child_modules
+                        .contains(&name)
+                        .then_some((parent_path, child_modules))
+                })
+        } else {
+            Some((path.clone(), child_modules))
+        };
+        let Some((root_path, child_modules)) = root else {
+            return Vec::new();
+        };
+
+        let snapshot = self.vfs.snapshot();
+        std::iter::once(root_path.clone())
+            .chain(child_modules.iter().flat_map(|name| {
+                ink_analyzer::child_module_candidates(&root_path, name)
+                    .into_iter()
+                    .find(|child_path| {
+                        lsp_types::Url::from_file_path(child_path)
+                            .ok()
+                            .and_then(|uri| snapshot.read(&uri))
+                            .is_some()
+                    })
+            }))
+            .filter(|other_path| *other_path != path)
+            .filter_map(|other_path| lsp_types::Url::from_file_path(other_path).ok())
+            .map(|uri| uri.to_string())
+            .collect()
+    }
+
+    /// Returns true if the document is an out-of-line child module of an ink! contract
+    /// (i.e. a parent module file declares it via a `mod x;` item in the root of an ink! contract).
+    fn is_contract_child_module(&mut self, id: &str) -> bool {
+        file_path(id).map_or(false, |path| {
+            ink_analyzer::parent_module_candidates(&path)
+                .into_iter()
+                .any(|(parent_path, name)| {
+                    self.contract_child_modules(&parent_path).contains(&name)
+                })
+        })
+    }
+
+    /// Returns an analysis of the current version of the document (if it exists)
+    /// using the ink! version and indentation style settings.
+    ///
+    /// Analyses are cached per document version, so all requests for the same document version
+    /// share the same parsed file and memoized queries (e.g. the ink! entity index and diagnostics).
+    pub fn analysis(&mut self, id: &str) -> Option<Rc<Analysis>> {
+        let doc = self.vfs.overlay(id)?;
+        if let Some((version, analysis)) = self.analyses.get(id) {
+            if *version == doc.version {
+                return Some(Rc::clone(analysis));
+            }
+        }
+
+        let version = self
+            .settings
+            .ink_version
+            .map(Into::into)
+            .unwrap_or_default();
+        let analysis = Analysis::new_with_version(&doc.content, version);
+        let analysis = Rc::new(match self.settings.indent_style {
+            Some(style) => analysis.with_indent_style(style.into()),
+            None => analysis,
+        });
+        self.analyses
+            .insert(id.to_string(), (doc.version, Rc::clone(&analysis)));
+        Some(analysis)
+    }
+
+    /// Returns the diagnostics for the current version of the document (if it exists).
+    ///
+    /// Diagnostics are cached per document version (i.e. unchanged documents are not re-analyzed).
+    pub fn diagnostics(&mut self, id: &str) -> Option<&CachedDiagnostics> {
+        self.diagnostics_with(id, |_| ())
+    }
+
+    /// Returns the diagnostics for the current version of the document (if it exists),
+    /// and passes each diagnostic to the callback as soon as it's available
+    /// if the diagnostics are not yet cached (e.g. for publishing partial results for large files).
+    ///
+    /// The diagnostics configuration and cross-file selector diagnostics of the document are only
+    /// recomputed after changes to other documents or files on the file system (see [`Memory::files_changed`]).
+    pub fn diagnostics_with<F>(&mut self, id: &str, mut callback: F) -> Option<&CachedDiagnostics>
+    where
+        F: FnMut(Diagnostic),
+    {
+        let analysis = self.analysis(id)?;
+        let version = self.vfs.overlay(id)?.version;
+        let key = match self.diagnostics_configs.get(id) {
+            Some(key) if !self.stale_diagnostics_configs.contains(id) => key.clone(),
+            _ => {
+                self.stale_diagnostics_configs.remove(id);
+                let key = (self.diagnostics_config(id), self.overlapping_selectors(id));
+                // Cached diagnostics are only valid for the same configuration and cross-file selector diagnostics
+                // (e.g. the contract child module status, `Cargo.toml` and other files of the ink! contract
+                // can change without changes to the document).
+                if self.diagnostics_configs.get(id) != Some(&key) {
+                    self.diagnostics.remove(id);
+                    self.diagnostics_configs.insert(id.to_string(), key.clone());
+                }
+                key
+            }
+        };
+        let (config, selector_diagnostics) = key;
+        Some(self.diagnostics.get_or_compute(id, version, || {
+            analysis.diagnostics_with(&config, &mut callback);
+            for diagnostic in &selector_diagnostics {
+                callback(diagnostic.clone());
+            }
+            // Returns the memoized (and sorted) results of the streamed diagnostics,
+            // followed by the cross-file selector diagnostics.
+            let mut results = analysis.diagnostics_with_config(&config);
+            results.extend(selector_diagnostics);
+            results
+        }))
+    }
+
+    /// Returns the diagnostics configuration for the document
+    /// (i.e. the configuration used for both diagnostics and quickfixes, e.g. for "fix all" actions).
+    pub fn diagnostics_config(&mut self, id: &str) -> DiagnosticsConfig {
+        let snapshot = self.vfs.snapshot();
+        let cargo_toml_path = self
+            .settings
+            .cargo_toml
+            .clone()
+            .or_else(|| file_path(id).and_then(|path| nearest_cargo_toml(&snapshot, &path)));
+        DiagnosticsConfig {
+            cargo_toml: cargo_toml_path.as_deref().and_then(|path| {
+                lsp_types::Url::from_file_path(path)
+                    .ok()
+                    .and_then(|uri| snapshot.read(&uri))
+                    .map(|file| file.content.to_string())
+            }),
+            contract_child_module: self.is_contract_child_module(id),
+            // NOTE: `additional_contracts` entries are relative to the contract crate root
+            // (i.e. the directory of its `Cargo.toml`), so the workspace root setting is only a fallback.
+            workspace_root: cargo_toml_path
+                .as_deref()
+                .and_then(Path::parent)
+                .map(Path::to_path_buf)
+                .or_else(|| self.settings.workspace_root.clone()),
+            ..self.settings.diagnostics_config()
+        }
+    }
+
     /// Retrieves the document identifiers for documents with unprocessed changes and clears the change tracker.
     pub fn take_changes(&mut self) -> Option<HashSet<String>> {
         (!self.changes.is_empty()).then_some(mem::take(&mut self.changes))
     }
 }
 
+/// A persisted symbol index tagged with the version of ink! analyzer that computed it.
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedSymbolIndex {
+    version: String,
+    index: SymbolIndex,
+}
+
+/// Loads the persisted symbol index from the path (if any),
+/// or returns an empty symbol index if it doesn't exist, is invalid
+/// or was computed by a different version of ink! analyzer
+/// (i.e. because symbols and selectors may be computed differently by other versions).
+fn load_symbol_index(path: Option<&Path>) -> SymbolIndex {
+    path.and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<PersistedSymbolIndex>(&content).ok())
+        .filter(|persisted| persisted.version == ink_analyzer::VERSION)
+        .map(|persisted| persisted.index)
+        .unwrap_or_default()
+}
+
+/// Returns the file system path of the document (if any).
+fn file_path(id: &str) -> Option<PathBuf> {
+    lsp_types::Url::parse(id)
+        .ok()
+        .and_then(|uri| uri.to_file_path().ok())
+}
+
+/// Returns the path of the nearest `Cargo.toml` (if any) for the file
+/// (i.e. in the directory of the file or any of its ancestor directories).
+fn nearest_cargo_toml(snapshot: &VfsSnapshot, path: &Path) -> Option<PathBuf> {
+    path.ancestors().skip(1).find_map(|dir| {
+        let cargo_toml = dir.join("Cargo.toml");
+        lsp_types::Url::from_file_path(&cargo_toml)
+            .ok()
+            .and_then(|uri| snapshot.read(&uri))
+            .map(|_| cargo_toml)
+    })
+}
+
 /// Represents a text document.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Document {
     /// The contents of the text documents.
-    pub content: String,
+    pub content: Rc<str>,
     /// The version of the text document as defined by the LSP client.
     pub version: i32,
 }
@@ -92,7 +570,7 @@ mod tests {
         assert_eq!(
             memory.get("1"),
             Some(Document {
-                content: "A".to_string(),
+                content: Rc::from("A"),
                 version: 0
             })
             .as_ref()
@@ -105,7 +583,7 @@ mod tests {
         assert_eq!(
             memory.get("1"),
             Some(Document {
-                content: "A1".to_string(),
+                content: Rc::from("A1"),
                 version: 1
             })
             .as_ref()
@@ -130,4 +608,391 @@ mod tests {
         // Tries to remove non-existent document.
         assert!(memory.remove("0").is_none());
     }
+
+    #[test]
+    fn diagnostics_works() {
+        let mut memory = Memory::new();
+        memory.insert(
+            "1".to_string(),
+            "#[ink::contract]\nmod my_contract {}".to_string(),
+            0,
+        );
+
+        // Computes diagnostics for the current document version.
+        let result = memory.diagnostics("1").cloned().unwrap();
+        // 3 Expected diagnostics for missing storage, constructor and message.
+        assert_eq!(result.diagnostics.len(), 3);
+        // Returns cached diagnostics for unchanged documents.
+        assert_eq!(memory.diagnostics("1"), Some(&result));
+
+        // Recomputes diagnostics for updated documents.
+        memory.update("1", "".to_string(), 1);
+        let updated_result = memory.diagnostics("1").cloned().unwrap();
+        assert!(updated_result.diagnostics.is_empty());
+        assert_ne!(updated_result.result_id, result.result_id);
+
+        // No diagnostics for missing documents.
+        memory.remove("1");
+        assert!(memory.diagnostics("1").is_none());
+    }
+
+    #[test]
+    fn contract_child_module_diagnostics_works() {
+        let mut memory = Memory::new();
+        let child_id = "file:///project/src/messages.rs";
+        memory.insert(
+            child_id.to_string(),
+            "impl MyContract {\n    #[ink(message)]\n    pub fn my_message(&self) {}\n}"
+                .to_string(),
+            0,
+        );
+
+        // ink! messages are invalid at the root of plain Rust files.
+        assert!(!memory.diagnostics(child_id).unwrap().diagnostics.is_empty());
+
+        // ink! messages are valid at the root of out-of-line child modules of ink! contracts,
+        // and open child modules are marked as changed when their parent module file changes
+        // (i.e. their cached diagnostics are recomputed even though the child module is unchanged).
+        memory.take_changes();
+        let parent_id = "file:///project/src/lib.rs";
+        memory.insert(
+            parent_id.to_string(),
+            "#[ink::contract]\nmod my_contract {\n    mod messages;\n}".to_string(),
+            0,
+        );
+        assert_eq!(
+            memory.take_changes(),
+            Some(HashSet::from([child_id.to_string(), parent_id.to_string()]))
+        );
+        assert!(memory.diagnostics(child_id).unwrap().diagnostics.is_empty());
+
+        // Recomputes diagnostics when the parent module file no longer declares the child module.
+        memory.update(
+            parent_id,
+            "#[ink::contract]\nmod my_contract {}".to_string(),
+            1,
+        );
+        assert_eq!(
+            memory.take_changes(),
+            Some(HashSet::from([child_id.to_string(), parent_id.to_string()]))
+        );
+        assert!(!memory.diagnostics(child_id).unwrap().diagnostics.is_empty());
+    }
+
+    #[test]
+    fn diagnostics_config_invalidation_works() {
+        // Creates an ink! contract crate on disk.
+        let root = std::env::temp_dir().join("ink_analyzer_memory_config_invalidation_works");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"flipper\"").unwrap();
+        let id = lsp_types::Url::from_file_path(root.join("src/lib.rs"))
+            .unwrap()
+            .to_string();
+        let cached_cargo_toml = |memory: &Memory| {
+            memory
+                .diagnostics_configs
+                .get(&id)
+                .and_then(|(config, _)| config.cargo_toml.clone())
+        };
+
+        let mut memory = Memory::new();
+        memory.insert(
+            id.clone(),
+            "#[ink::contract]\nmod my_contract {}".to_string(),
+            0,
+        );
+        let result = memory.diagnostics(&id).cloned().unwrap();
+        assert_eq!(
+            cached_cargo_toml(&memory).as_deref(),
+            Some("[package]\nname = \"flipper\"")
+        );
+
+        // Reuses the cached diagnostics configuration (and diagnostics) until file changes are reported.
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"erc20\"").unwrap();
+        assert_eq!(memory.diagnostics(&id), Some(&result));
+        assert_eq!(
+            cached_cargo_toml(&memory).as_deref(),
+            Some("[package]\nname = \"flipper\"")
+        );
+
+        // Recomputes the diagnostics configuration after file changes are reported.
+        memory.take_changes();
+        memory.files_changed();
+        assert_eq!(memory.take_changes(), Some(HashSet::from([id.clone()])));
+        memory.diagnostics(&id);
+        assert_eq!(
+            cached_cargo_toml(&memory).as_deref(),
+            Some("[package]\nname = \"erc20\"")
+        );
+    }
+
+    #[test]
+    fn analysis_works() {
+        let mut memory = Memory::new();
+        memory.insert("1".to_string(), "mod my_module {}".to_string(), 0);
+
+        // Reuses the analysis for unchanged documents.
+        let analysis = memory.analysis("1").unwrap();
+        assert!(Rc::ptr_eq(&analysis, &memory.analysis("1").unwrap()));
+
+        // Creates a new analysis for updated documents.
+        memory.update("1", "fn main() {}".to_string(), 1);
+        let updated_analysis = memory.analysis("1").unwrap();
+        assert!(!Rc::ptr_eq(&analysis, &updated_analysis));
+
+        // Creates a new analysis when settings change.
+        memory.set_settings(Settings::default());
+        assert!(!Rc::ptr_eq(
+            &updated_analysis,
+            &memory.analysis("1").unwrap()
+        ));
+
+        // No analysis for missing documents.
+        memory.remove("1");
+        assert!(memory.analysis("1").is_none());
+    }
+
+    #[test]
+    fn settings_works() {
+        // Creates an ink! contract crate on disk.
+        let root = std::env::temp_dir().join("ink_analyzer_memory_settings_works");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"flipper\"").unwrap();
+        let id = lsp_types::Url::from_file_path(root.join("src/lib.rs"))
+            .unwrap()
+            .to_string();
+
+        let mut memory = Memory::new();
+        memory.insert(
+            id.clone(),
+            "#![cfg_attr(not(feature = \"std\"), no_std, no_main)]\n#[ink::contract]\nmod my_contract {}"
+                .to_string(),
+            0,
+        );
+        // 3 Expected diagnostics for missing storage, constructor and message.
+        assert_eq!(memory.diagnostics(&id).unwrap().diagnostics.len(), 3);
+        memory.take_changes();
+
+        // Resolves `Cargo.toml` and the workspace root from the document path.
+        let config = memory.diagnostics_config(&id);
+        assert_eq!(
+            config.cargo_toml.as_deref(),
+            Some("[package]\nname = \"flipper\"")
+        );
+        assert_eq!(config.workspace_root, Some(root.clone()));
+
+        // Applies diagnostics, ink! version and indentation style settings.
+        memory.set_settings(
+            Settings::from_json(&serde_json::json!({
+                "diagnostics": { "disabled": ["missing-storage"] },
+                "inkVersion": "v3",
+                "indentStyle": "tabs",
+                "workspaceRoot": "/project"
+            }))
+            .unwrap(),
+        );
+        let config = memory.diagnostics_config(&id);
+        assert_eq!(config.version, Some(ink_analyzer::Version::V3));
+        // The directory of the nearest `Cargo.toml` takes precedence over the workspace root setting.
+        assert_eq!(config.workspace_root, Some(root.clone()));
+        let analysis = memory.analysis(&id).unwrap();
+        assert_eq!(analysis.version(), ink_analyzer::Version::V3);
+        assert_eq!(analysis.indent_style(), ink_analyzer::IndentStyle::Tabs);
+        // Marks open documents as changed.
+        assert_eq!(memory.take_changes(), Some(HashSet::from([id.clone()])));
+        // 2 Expected diagnostics for missing constructor and message (i.e. missing storage is disabled).
+        assert_eq!(memory.diagnostics(&id).unwrap().diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn symbol_index_works() {
+        let mut memory = Memory::new();
+        let contract = |name: &str| {
+            format!("#[ink::contract]\nmod {name} {{\n    #[ink(event)]\n    pub struct MyEvent {{}}\n}}")
+        };
+        let search = |memory: &mut Memory, query: &str| -> Vec<(String, String)> {
+            memory
+                .symbol_index()
+                .search(query)
+                .into_iter()
+                .map(|(id, symbol)| (id.to_string(), symbol.name.clone()))
+                .collect()
+        };
+
+        // Indexes inserted documents.
+        memory.insert("1".to_string(), contract("contract_a"), 0);
+        memory.insert("2".to_string(), "fn main() {}".to_string(), 0);
+        assert_eq!(
+            search(&mut memory, "MyEvent"),
+            vec![("1".to_string(), "MyEvent".to_string())]
+        );
+
+        // Re-indexes updated documents.
+        memory.update("1", contract("contract_b"), 1);
+        memory.update("2", contract("contract_c"), 1);
+        assert_eq!(
+            search(&mut memory, "contract:"),
+            vec![
+                ("1".to_string(), "contract_b".to_string()),
+                ("2".to_string(), "contract_c".to_string())
+            ]
+        );
+
+        // Removes symbols for removed documents.
+        memory.remove("1");
+        assert_eq!(
+            search(&mut memory, "MyEvent"),
+            vec![("2".to_string(), "MyEvent".to_string())]
+        );
+    }
+
+    #[test]
+    fn symbol_index_persistence_works() {
+        // Creates a temporary project directory.
+        let dir = std::env::temp_dir().join("ink_analyzer_memory_symbol_index_persistence_works");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("lib.rs");
+        let code =
+            "#[ink::contract]\nmod my_contract {\n    #[ink(event)]\n    pub struct MyEvent {}\n}";
+        fs::write(&path, code).unwrap();
+        let id = lsp_types::Url::from_file_path(&path).unwrap().to_string();
+        let settings = Settings {
+            symbol_index_path: Some(dir.join("cache").join("symbols.json")),
+            ..Default::default()
+        };
+        let search = |memory: &mut Memory, query: &str| -> Vec<(String, String)> {
+            memory
+                .search_symbols(query)
+                .into_iter()
+                .map(|(id, symbol)| (id, symbol.name))
+                .collect()
+        };
+        let expected_results = vec![(id.clone(), "MyEvent".to_string())];
+
+        // Persists the symbol index for open documents.
+        let mut memory = Memory::with_settings(settings.clone());
+        memory.insert(id.clone(), code.to_string(), 0);
+        assert_eq!(search(&mut memory, "MyEvent"), expected_results);
+        memory.persist_symbol_index().unwrap();
+
+        // Loads the persisted symbol index (i.e. symbols for unchanged closed documents are searchable).
+        let mut memory = Memory::with_settings(settings.clone());
+        assert_eq!(search(&mut memory, "MyEvent"), expected_results);
+
+        // Reuses persisted symbols for unchanged documents (i.e. without analyzing them).
+        memory.insert(id.clone(), code.to_string(), 0);
+        assert_eq!(search(&mut memory, "MyEvent"), expected_results);
+        assert!(memory.analyses.is_empty());
+
+        // Keeps symbols of closed documents.
+        memory.remove(&id);
+        assert_eq!(search(&mut memory, "MyEvent"), expected_results);
+        memory.persist_symbol_index().unwrap();
+
+        // Ignores persisted symbols computed by other versions of ink! analyzer.
+        let index_path = settings.symbol_index_path.clone().unwrap();
+        let content = fs::read_to_string(&index_path).unwrap();
+        let other_version_content = content.replace(
+            &format!(r#""version":"{}""#, ink_analyzer::VERSION),
+            r#""version":"0.0.0""#,
+        );
+        assert_ne!(content, other_version_content);
+        fs::write(&index_path, other_version_content).unwrap();
+        let mut other_version_memory = Memory::with_settings(settings.clone());
+        assert!(search(&mut other_version_memory, "MyEvent").is_empty());
+        fs::write(&index_path, content).unwrap();
+
+        // Ignores persisted symbols for changed files.
+        fs::write(&path, code.replace("MyEvent", "OtherEvent")).unwrap();
+        let mut memory = Memory::with_settings(settings);
+        assert!(search(&mut memory, "MyEvent").is_empty());
+        assert!(search(&mut memory, "OtherEvent").is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn overlapping_selectors_works() {
+        // Creates a temporary project directory with an ink! contract split across multiple files.
+        let dir = std::env::temp_dir().join("ink_analyzer_memory_overlapping_selectors_works");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let root_path = dir.join("lib.rs");
+        let root_code = r#"
+#[ink::contract]
+mod my_contract {
+    mod messages;
+
+    #[ink(storage)]
+    pub struct MyContract {}
+
+    impl MyContract {
+        #[ink(constructor)]
+        pub fn new() -> Self {}
+
+        #[ink(message)]
+        pub fn get(&self) {}
+    }
+}"#;
+        fs::write(&root_path, root_code).unwrap();
+        let child_path = dir.join("messages.rs");
+        let child_code = r#"
+impl MyContract {
+    #[ink(message, selector = 0x2f865bd9)]
+    pub fn get_value(&self) {}
+}"#;
+        fs::write(&child_path, child_code).unwrap();
+        let root_id = lsp_types::Url::from_file_path(&root_path)
+            .unwrap()
+            .to_string();
+        let child_id = lsp_types::Url::from_file_path(&child_path)
+            .unwrap()
+            .to_string();
+
+        // Uses the file index of the closed contract root file.
+        let mut memory = Memory::new();
+        memory.insert(child_id.clone(), child_code.to_string(), 0);
+        let results = memory.overlapping_selectors(&child_id);
+        assert_eq!(results.len(), 1);
+        assert!(results[0].message.contains(&root_id));
+        // Reuses the file index of the closed contract root file (i.e. it's only analyzed once).
+        let root_index = memory
+            .persisted_symbols
+            .file_index(&root_id)
+            .cloned()
+            .unwrap();
+        assert_eq!(
+            root_index.contract_child_modules,
+            vec!["messages".to_string()]
+        );
+        assert_eq!(memory.contract_files(&child_id), vec![root_id.clone()]);
+        assert_eq!(
+            memory.persisted_symbols.file_index(&root_id),
+            Some(&root_index)
+        );
+        assert!(memory
+            .diagnostics(&child_id)
+            .unwrap()
+            .diagnostics
+            .contains(&results[0]));
+
+        // Uses the file index of the open child module file.
+        memory.insert(root_id.clone(), root_code.to_string(), 0);
+        let results = memory.overlapping_selectors(&root_id);
+        assert_eq!(results.len(), 1);
+        assert!(results[0].message.contains(&child_id));
+
+        // Cached diagnostics are updated when other files of the ink! contract change.
+        memory.update(&child_id, child_code.replace("0x2f865bd9", "0x1"), 1);
+        assert!(memory.overlapping_selectors(&root_id).is_empty());
+        assert!(!memory
+            .diagnostics(&root_id)
+            .unwrap()
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.message.contains(&child_id)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,303 @@
+//! ink! Language Server settings.
+
+use ink_analyzer::{DiagnosticCode, DiagnosticsConfig, IndentStyle, Version};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// ink! Language Server settings
+/// (i.e. from LSP client initialization options and `workspace/didChangeConfiguration` notifications).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Settings {
+    /// Inlay hints settings.
+    pub inlay_hints: InlayHintsSettings,
+    /// Diagnostics settings.
+    pub diagnostics: DiagnosticsSettings,
+    /// The target ink! version (if not set, ink! 4.x is assumed).
+    pub ink_version: Option<InkVersion>,
+    /// Indentation style for generated code
+    /// (if not set, the indentation style is detected from each document).
+    pub indent_style: Option<IndentStyleSettings>,
+    /// Path of the `Cargo.toml` of the ink! contract crate
+    /// (if not set, the nearest `Cargo.toml` of each document is used).
+    pub cargo_toml: Option<PathBuf>,
+    /// Root directory of the ink! contract crate, used only for documents without a `Cargo.toml`
+    /// (i.e. the directory of the `Cargo.toml` of each document takes precedence).
+    pub workspace_root: Option<PathBuf>,
+    /// Path of the file for persisting the ink! entity symbol index between sessions
+    /// (if not set, the symbol index is not persisted).
+    pub symbol_index_path: Option<PathBuf>,
+}
+
+/// Diagnostics settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DiagnosticsSettings {
+    /// Codes of disabled checks (e.g. `E0017`, `missing-storage` or `E0017-missing-storage`).
+    pub disabled: Vec<String>,
+    /// Severity overrides for checks keyed by code (e.g. `{ "E0017": "warning" }`).
+    pub severity_overrides: HashMap<String, SeverityLevel>,
+    /// Whether to run audit diagnostics (e.g. for debug-only code that's compiled into the contract).
+    pub audit: bool,
+    /// Whether to publish partial diagnostics while diagnostics for a document are computed
+    /// (e.g. for large files).
+    pub streaming: bool,
+}
+
+/// The severity level of a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SeverityLevel {
+    /// A diagnostic error.
+    Error,
+    /// A diagnostic warning.
+    Warning,
+    /// A deprecation warning.
+    Deprecation,
+}
+
+impl From<SeverityLevel> for ink_analyzer::Severity {
+    fn from(level: SeverityLevel) -> Self {
+        match level {
+            SeverityLevel::Error => Self::Error,
+            SeverityLevel::Warning => Self::Warning,
+            SeverityLevel::Deprecation => Self::Deprecation,
+        }
+    }
+}
+
+/// The ink! language version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InkVersion {
+    /// ink! 3.x
+    V3,
+    /// ink! 4.x
+    V4,
+}
+
+impl From<InkVersion> for Version {
+    fn from(version: InkVersion) -> Self {
+        match version {
+            InkVersion::V3 => Self::V3,
+            InkVersion::V4 => Self::V4,
+        }
+    }
+}
+
+/// Indentation style settings (i.e. `"tabs"` or `{ "spaces": 2 }`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndentStyleSettings {
+    /// Indents with the given number of spaces per indent level.
+    Spaces(usize),
+    /// Indents with a tab per indent level.
+    Tabs,
+}
+
+impl From<IndentStyleSettings> for IndentStyle {
+    fn from(settings: IndentStyleSettings) -> Self {
+        match settings {
+            IndentStyleSettings::Spaces(width) => Self::Spaces(width),
+            IndentStyleSettings::Tabs => Self::Tabs,
+        }
+    }
+}
+
+/// Inlay hints settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InlayHintsSettings {
+    /// Whether to show the value kinds of ink! attribute arguments.
+    pub argument_kinds: bool,
+    /// Whether to show the computed selectors of ink! constructors and ink! messages.
+    pub selectors: bool,
+    /// Whether to show the signature topics of ink! events.
+    pub signature_topics: bool,
+    /// Whether to show the default values of omitted complementary ink! attribute arguments.
+    pub default_values: bool,
+    /// Maximum length (in characters) of inlay hint labels (if any).
+    pub max_length: Option<usize>,
+}
+
+impl Default for InlayHintsSettings {
+    fn default() -> Self {
+        let config = ink_analyzer::InlayHintsConfig::default();
+        Self {
+            argument_kinds: config.argument_kinds,
+            selectors: config.selectors,
+            signature_topics: config.signature_topics,
+            default_values: config.default_values,
+            max_length: config.max_length,
+        }
+    }
+}
+
+impl From<&InlayHintsSettings> for ink_analyzer::InlayHintsConfig {
+    fn from(settings: &InlayHintsSettings) -> Self {
+        Self {
+            argument_kinds: settings.argument_kinds,
+            selectors: settings.selectors,
+            signature_topics: settings.signature_topics,
+            default_values: settings.default_values,
+            max_length: settings.max_length,
+        }
+    }
+}
+
+/// Key under which settings may be nested in a settings object (e.g. in VS Code `settings.json`).
+const SETTINGS_SECTION: &str = "inkAnalyzer";
+
+impl Settings {
+    /// Parses settings from a JSON value (e.g. LSP client initialization options or
+    /// the settings of a `workspace/didChangeConfiguration` notification).
+    ///
+    /// Settings can either be top-level keys or nested under an `inkAnalyzer` key.
+    /// Returns `None` if the value isn't a valid settings object.
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let value = value.get(SETTINGS_SECTION).unwrap_or(value);
+        value
+            .is_object()
+            .then(|| serde_json::from_value(value.clone()).ok())
+            .flatten()
+    }
+
+    /// Returns the diagnostics configuration for the settings
+    /// (i.e. without document specific options, see [`crate::memory::Memory::diagnostics_config`]).
+    ///
+    /// Invalid diagnostic codes are ignored.
+    pub fn diagnostics_config(&self) -> DiagnosticsConfig {
+        DiagnosticsConfig {
+            version: self.ink_version.map(Version::from),
+            audit: self.diagnostics.audit,
+            disabled: self
+                .diagnostics
+                .disabled
+                .iter()
+                .filter_map(|code| DiagnosticCode::parse(code))
+                .collect(),
+            severity_overrides: self
+                .diagnostics
+                .severity_overrides
+                .iter()
+                .filter_map(|(code, level)| {
+                    DiagnosticCode::parse(code).map(|code| (code, (*level).into()))
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_json_works() {
+        for (value, expected_results) in [
+            (json!({}), Some(Settings::default())),
+            (json!(null), None),
+            (json!("inlayHints"), None),
+            // Partial settings use defaults for missing keys.
+            (
+                json!({ "inlayHints": { "selectors": false, "maxLength": 10 } }),
+                Some(Settings {
+                    inlay_hints: InlayHintsSettings {
+                        selectors: false,
+                        max_length: Some(10),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            ),
+            // Nested settings.
+            (
+                json!({ "inkAnalyzer": { "inlayHints": { "defaultValues": true } } }),
+                Some(Settings {
+                    inlay_hints: InlayHintsSettings {
+                        default_values: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            ),
+            // Diagnostics and code generation settings.
+            (
+                json!({
+                    "diagnostics": {
+                        "disabled": ["E0017"],
+                        "severityOverrides": { "missing-constructor": "warning" },
+                        "audit": true,
+                        "streaming": true
+                    },
+                    "inkVersion": "v3",
+                    "indentStyle": { "spaces": 2 },
+                    "cargoToml": "/project/Cargo.toml",
+                    "workspaceRoot": "/project",
+                    "symbolIndexPath": "/project/target/ink-analyzer/symbols.json"
+                }),
+                Some(Settings {
+                    diagnostics: DiagnosticsSettings {
+                        disabled: vec!["E0017".to_string()],
+                        severity_overrides: HashMap::from([(
+                            "missing-constructor".to_string(),
+                            SeverityLevel::Warning,
+                        )]),
+                        audit: true,
+                        streaming: true,
+                    },
+                    ink_version: Some(InkVersion::V3),
+                    indent_style: Some(IndentStyleSettings::Spaces(2)),
+                    cargo_toml: Some(PathBuf::from("/project/Cargo.toml")),
+                    workspace_root: Some(PathBuf::from("/project")),
+                    symbol_index_path: Some(PathBuf::from(
+                        "/project/target/ink-analyzer/symbols.json",
+                    )),
+                    ..Default::default()
+                }),
+            ),
+            (
+                json!({ "indentStyle": "tabs" }),
+                Some(Settings {
+                    indent_style: Some(IndentStyleSettings::Tabs),
+                    ..Default::default()
+                }),
+            ),
+            // Invalid values.
+            (json!({ "inlayHints": { "selectors": "no" } }), None),
+            (json!({ "inkVersion": "v5" }), None),
+        ] {
+            assert_eq!(Settings::from_json(&value), expected_results);
+        }
+    }
+
+    #[test]
+    fn diagnostics_config_works() {
+        let settings = Settings::from_json(&json!({
+            "diagnostics": {
+                // Invalid codes are ignored.
+                "disabled": ["missing-storage", "E9999"],
+                "severityOverrides": { "E0019": "warning", "unknown": "error" },
+                "audit": true
+            },
+            "inkVersion": "v3"
+        }))
+        .unwrap();
+        assert_eq!(
+            settings.diagnostics_config(),
+            DiagnosticsConfig {
+                version: Some(Version::V3),
+                audit: true,
+                disabled: [DiagnosticCode::MissingStorage].into_iter().collect(),
+                severity_overrides: HashMap::from([(
+                    DiagnosticCode::MissingConstructor,
+                    ink_analyzer::Severity::Warning
+                )]),
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! Utilities for translating from ink! analyzer to LSP types.
 
 use line_index::WideEncoding;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use crate::translator::PositionTranslationContext;
@@ -50,6 +51,7 @@ pub fn range(
 /// Translates ink! analyzer diagnostic to LSP diagnostic.
 pub fn diagnostic(
     diagnostic: ink_analyzer::Diagnostic,
+    uri: &lsp_types::Url,
     context: &PositionTranslationContext,
 ) -> Option<lsp_types::Diagnostic> {
     range(diagnostic.range, context).map(|range| lsp_types::Diagnostic {
@@ -57,8 +59,38 @@ pub fn diagnostic(
         message: diagnostic.message,
         severity: Some(match diagnostic.severity {
             ink_analyzer::Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
-            ink_analyzer::Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+            ink_analyzer::Severity::Warning | ink_analyzer::Severity::Deprecation => {
+                lsp_types::DiagnosticSeverity::WARNING
+            }
+        }),
+        code: diagnostic
+            .code
+            .map(|code| lsp_types::NumberOrString::String(code.to_string())),
+        code_description: diagnostic.code.and_then(|code| {
+            lsp_types::Url::parse(&code.code_description())
+                .ok()
+                .map(|href| lsp_types::CodeDescription { href })
+        }),
+        // Related locations are always in the same document as the diagnostic.
+        related_information: diagnostic.related_information.map(|related_information| {
+            related_information
+                .into_iter()
+                .filter_map(|info| {
+                    self::range(info.range, context).map(|range| {
+                        lsp_types::DiagnosticRelatedInformation {
+                            location: lsp_types::Location {
+                                uri: uri.clone(),
+                                range,
+                            },
+                            message: info.message,
+                        }
+                    })
+                })
+                .collect()
         }),
+        // Deprecated ink! constructs are tagged (e.g. so that editors can render them with a strikethrough).
+        tags: (diagnostic.severity == ink_analyzer::Severity::Deprecation)
+            .then(|| vec![lsp_types::DiagnosticTag::DEPRECATED]),
         ..Default::default()
     })
 }
@@ -71,22 +103,30 @@ pub fn completion(
 ) -> Option<lsp_types::CompletionItem> {
     range(completion.range, context).map(|range| lsp_types::CompletionItem {
         label: completion.label,
-        kind: Some(lsp_types::CompletionItemKind::FUNCTION),
+        kind: Some(completion.kind.into()),
         detail: completion.detail,
+        documentation: completion.documentation.map(|documentation| {
+            lsp_types::Documentation::MarkupContent(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: documentation,
+            })
+        }),
+        sort_text: Some(completion.sort_text),
+        filter_text: completion.filter_text,
         insert_text_format: snippet_support.then_some(match completion.edit.snippet.as_ref() {
             Some(_) => lsp_types::InsertTextFormat::SNIPPET,
             None => lsp_types::InsertTextFormat::PLAIN_TEXT,
         }),
+        // Downgrades snippets to plain text for clients without snippet support.
         text_edit: Some(
             lsp_types::TextEdit {
                 range,
-                new_text: match (snippet_support, completion.edit.snippet) {
-                    (true, Some(snippet)) => snippet,
-                    _ => completion.edit.text,
-                },
+                new_text: completion.edit.text_for(snippet_support).into_owned(),
             }
             .into(),
         ),
+        // Identifier for lazily resolving the full documentation (if any).
+        data: completion.id.map(|id| serde_json::json!({ "id": id })),
         ..Default::default()
     })
 }
@@ -105,47 +145,99 @@ pub fn hover(
 }
 
 /// Translates ink! analyzer action to LSP code action.
-pub fn code_action(
+///
+/// Text edits for other documents (if any) are translated using the version and translation context
+/// returned by `document_context` for the document's URI (i.e. the document path resolved relative to `uri`),
+/// into a multi-document workspace edit (with versioned document changes if `document_changes` is true).
+/// Returns None if any of the other documents can't be resolved (i.e. partial multi-document edits are never applied).
+pub fn code_action<F>(
     action: ink_analyzer::Action,
     uri: lsp_types::Url,
+    version: Option<i32>,
     context: &PositionTranslationContext,
-) -> Option<lsp_types::CodeAction> {
-    let edits: Vec<(&str, lsp_types::Range, Option<&str>)> = action
+    document_changes: bool,
+    document_context: F,
+) -> Option<lsp_types::CodeAction>
+where
+    F: Fn(&lsp_types::Url) -> Option<(Option<i32>, PositionTranslationContext)>,
+{
+    let edits: Vec<(Cow<str>, lsp_types::Range, Option<&str>)> = action
         .edits
         .iter()
         .filter_map(|edit| {
             range(edit.range, context)
-                .map(|range| (edit.text.as_str(), range, edit.snippet.as_deref()))
+                .map(|range| (edit.plain_text(), range, edit.snippet.as_deref()))
         })
         .collect();
 
-    (!edits.is_empty()).then(|| {
+    // Translates text edits for other documents (if any).
+    let mut other_docs: Vec<(lsp_types::Url, Option<i32>, Vec<lsp_types::TextEdit>)> = Vec::new();
+    for document_edit in &action.document_edits {
+        let doc_uri = uri.join(&document_edit.path).ok()?;
+        let (doc_version, doc_context) = document_context(&doc_uri)?;
+        let doc_edits = document_edit
+            .edits
+            .iter()
+            .map(|edit| {
+                range(edit.range, &doc_context).map(|range| lsp_types::TextEdit {
+                    range,
+                    new_text: edit.plain_text().into_owned(),
+                })
+            })
+            .collect::<Option<Vec<lsp_types::TextEdit>>>()?;
+        other_docs.push((doc_uri, doc_version, doc_edits));
+    }
+
+    (!edits.is_empty() || !other_docs.is_empty()).then(|| {
         let snippets: Vec<(String, String)> = edits
             .iter()
             .filter_map(|(text, _, snippet)| {
-                snippet.map(|snippet| ((*text).to_string(), snippet.to_string()))
+                snippet.map(|snippet| (text.to_string(), snippet.to_string()))
+            })
+            .collect();
+        let lsp_edits: Vec<lsp_types::TextEdit> = edits
+            .into_iter()
+            .map(|(text, range, _)| lsp_types::TextEdit {
+                range,
+                new_text: text.into_owned(),
             })
             .collect();
 
         lsp_types::CodeAction {
             title: action.label,
-            kind: Some(match action.kind {
-                ink_analyzer::ActionKind::QuickFix => lsp_types::CodeActionKind::QUICKFIX,
-                ink_analyzer::ActionKind::Refactor => lsp_types::CodeActionKind::REFACTOR_REWRITE,
-                _ => lsp_types::CodeActionKind::EMPTY,
-            }),
-            edit: Some(lsp_types::WorkspaceEdit {
-                changes: Some(HashMap::from([(
-                    uri,
-                    edits
-                        .into_iter()
-                        .map(|(text, range, _)| lsp_types::TextEdit {
-                            range,
-                            new_text: text.to_string(),
-                        })
-                        .collect(),
-                )])),
-                ..Default::default()
+            kind: Some(action.kind.into()),
+            is_preferred: action.is_preferred.then_some(true),
+            edit: Some(if other_docs.is_empty() {
+                lsp_types::WorkspaceEdit {
+                    changes: Some(HashMap::from([(uri, lsp_edits)])),
+                    ..Default::default()
+                }
+            } else {
+                let docs = [(uri, version, lsp_edits)]
+                    .into_iter()
+                    .filter(|(_, _, edits)| !edits.is_empty())
+                    .chain(other_docs);
+                if document_changes {
+                    // Uses versioned document changes (if supported by the client).
+                    lsp_types::WorkspaceEdit {
+                        document_changes: Some(lsp_types::DocumentChanges::Edits(
+                            docs.map(|(uri, version, edits)| lsp_types::TextDocumentEdit {
+                                text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
+                                    uri,
+                                    version,
+                                },
+                                edits: edits.into_iter().map(lsp_types::OneOf::Left).collect(),
+                            })
+                            .collect(),
+                        )),
+                        ..Default::default()
+                    }
+                } else {
+                    lsp_types::WorkspaceEdit {
+                        changes: Some(docs.map(|(uri, _, edits)| (uri, edits)).collect()),
+                        ..Default::default()
+                    }
+                }
             }),
             // Add snippet for clients that have the middleware to apply code actions edits as snippets.
             data: (!snippets.is_empty()).then(|| {
@@ -165,6 +257,28 @@ pub fn code_action(
     })
 }
 
+/// Translates ink! analyzer unresolved action to LSP code action (i.e. without an edit).
+///
+/// The URI and LSP range of the code action request, and the action identifier are added as the code action's data,
+/// so that the edit can be computed by a `codeAction/resolve` request.
+pub fn unresolved_code_action(
+    action: ink_analyzer::UnresolvedAction,
+    uri: &lsp_types::Url,
+    range: lsp_types::Range,
+) -> lsp_types::CodeAction {
+    lsp_types::CodeAction {
+        title: action.label,
+        kind: Some(action.kind.into()),
+        is_preferred: action.is_preferred.then_some(true),
+        data: Some(serde_json::json!({
+            "uri": uri.to_string(),
+            "range": range,
+            "id": action.id,
+        })),
+        ..Default::default()
+    }
+}
+
 /// Translates ink! analyzer inlay hint to LSP inlay hint.
 pub fn inlay_hint(
     hint: ink_analyzer::InlayHint,
@@ -182,6 +296,79 @@ pub fn inlay_hint(
     })
 }
 
+/// Translates ink! analyzer folding range to LSP folding range.
+pub fn folding_range(
+    folding_range: ink_analyzer::FoldingRange,
+    context: &PositionTranslationContext,
+) -> Option<lsp_types::FoldingRange> {
+    range(folding_range.range, context).map(|range| lsp_types::FoldingRange {
+        start_line: range.start.line,
+        start_character: None,
+        end_line: range.end.line,
+        end_character: None,
+        kind: Some(match folding_range.kind {
+            ink_analyzer::FoldingRangeKind::Comment => lsp_types::FoldingRangeKind::Comment,
+            ink_analyzer::FoldingRangeKind::Region => lsp_types::FoldingRangeKind::Region,
+        }),
+        collapsed_text: None,
+    })
+}
+
+/// Translates ink! analyzer symbol to LSP symbol information.
+pub fn symbol(
+    symbol: &ink_analyzer::Symbol,
+    uri: lsp_types::Url,
+    context: &PositionTranslationContext,
+) -> Option<lsp_types::SymbolInformation> {
+    range(symbol.name_range, context).map(|range| {
+        // `deprecated` field is itself deprecated in favor of `tags`, but it's still required.
+        #[allow(deprecated)]
+        lsp_types::SymbolInformation {
+            name: symbol.name.clone(),
+            kind: match symbol.kind {
+                ink_analyzer::SymbolKind::Contract => lsp_types::SymbolKind::MODULE,
+                ink_analyzer::SymbolKind::Storage | ink_analyzer::SymbolKind::StorageItem => {
+                    lsp_types::SymbolKind::STRUCT
+                }
+                ink_analyzer::SymbolKind::Event => lsp_types::SymbolKind::EVENT,
+                ink_analyzer::SymbolKind::Constructor => lsp_types::SymbolKind::CONSTRUCTOR,
+                ink_analyzer::SymbolKind::Message | ink_analyzer::SymbolKind::Extension => {
+                    lsp_types::SymbolKind::METHOD
+                }
+                ink_analyzer::SymbolKind::TraitDefinition
+                | ink_analyzer::SymbolKind::ChainExtension => lsp_types::SymbolKind::INTERFACE,
+                ink_analyzer::SymbolKind::Test | ink_analyzer::SymbolKind::E2ETest => {
+                    lsp_types::SymbolKind::FUNCTION
+                }
+            },
+            tags: None,
+            deprecated: None,
+            location: lsp_types::Location { uri, range },
+            container_name: symbol.container_name.clone(),
+        }
+    })
+}
+
+/// Translates ink! analyzer selection ranges (ordered from the narrowest to the widest range)
+/// to an LSP selection range.
+pub fn selection_range(
+    ranges: Vec<ink_analyzer::TextRange>,
+    context: &PositionTranslationContext,
+) -> Option<lsp_types::SelectionRange> {
+    ranges
+        .into_iter()
+        .rev()
+        .try_fold(None, |parent, text_range| {
+            range(text_range, context).map(|range| {
+                Some(lsp_types::SelectionRange {
+                    range,
+                    parent: parent.map(Box::new),
+                })
+            })
+        })
+        .flatten()
+}
+
 /// Translates ink! analyzer offset to LSP offset.
 pub fn offset(
     offset: ink_analyzer::TextSize,
@@ -284,6 +471,67 @@ pub fn signature_help(
     })
 }
 
+/// Translates ink! analyzer resource operation to LSP document change operations.
+///
+/// File creations are followed by an edit that inserts the file content,
+/// while directory creations translate to no operations because LSP has no directory creation operation
+/// (i.e. clients create missing parent directories when creating files).
+pub fn resource_op(
+    op: ink_analyzer::ResourceOp,
+    root: &lsp_types::Url,
+) -> Option<Vec<lsp_types::DocumentChangeOperation>> {
+    let uri = |path: &str| root.join(path).ok();
+    Some(match op {
+        ink_analyzer::ResourceOp::CreateFile { path, file } => {
+            let uri = uri(&path)?;
+            vec![
+                lsp_types::DocumentChangeOperation::Op(lsp_types::ResourceOp::Create(
+                    lsp_types::CreateFile {
+                        uri: uri.clone(),
+                        options: None,
+                        annotation_id: None,
+                    },
+                )),
+                lsp_types::DocumentChangeOperation::Edit(lsp_types::TextDocumentEdit {
+                    text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
+                        uri,
+                        version: None,
+                    },
+                    edits: vec![lsp_types::OneOf::Left(lsp_types::TextEdit {
+                        range: lsp_types::Range::default(),
+                        new_text: file.plain,
+                    })],
+                }),
+            ]
+        }
+        ink_analyzer::ResourceOp::CreateDir { .. } => Vec::new(),
+        ink_analyzer::ResourceOp::Rename { from, to } => {
+            vec![lsp_types::DocumentChangeOperation::Op(
+                lsp_types::ResourceOp::Rename(lsp_types::RenameFile {
+                    old_uri: uri(&from)?,
+                    new_uri: uri(&to)?,
+                    options: None,
+                    annotation_id: None,
+                }),
+            )]
+        }
+        ink_analyzer::ResourceOp::Delete { path, recursive } => {
+            vec![lsp_types::DocumentChangeOperation::Op(
+                lsp_types::ResourceOp::Delete(lsp_types::DeleteFile {
+                    uri: uri(&path)?,
+                    options: Some(lsp_types::DeleteFileOptions {
+                        recursive: Some(recursive),
+                        ignore_if_not_exists: None,
+                        annotation_id: None,
+                    }),
+                }),
+            )]
+        }
+        // Ignores unknown resource operations.
+        _ => Vec::new(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,4 +573,325 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn completion_snippet_support_works() {
+        let context = PositionTranslationContext {
+            encoding: lsp_types::PositionEncodingKind::UTF8,
+            line_index: LineIndex::new("#[ink(me)]"),
+        };
+        let range = ink_analyzer::TextRange::new(
+            ink_analyzer::TextSize::from(6),
+            ink_analyzer::TextSize::from(8),
+        );
+        for (snippet_support, expected_text, expected_format) in [
+            (
+                true,
+                "message${1:, payable}",
+                Some(lsp_types::InsertTextFormat::SNIPPET),
+            ),
+            // Snippets are downgraded to plain text for clients without snippet support.
+            (false, "message", None),
+        ] {
+            let item = completion(
+                ink_analyzer::Completion {
+                    label: "message".to_string(),
+                    range,
+                    edit: ink_analyzer::TextEdit::new(
+                        "message".to_string(),
+                        range,
+                        Some("message${1:, payable}".to_string()),
+                    ),
+                    detail: None,
+                    id: None,
+                    kind: ink_analyzer::CompletionKind::Argument,
+                    sort_text: "00001_message".to_string(),
+                    filter_text: None,
+                    documentation: None,
+                },
+                snippet_support,
+                &context,
+            )
+            .unwrap();
+            assert_eq!(item.insert_text_format, expected_format);
+            let new_text = match item.text_edit.unwrap() {
+                lsp_types::CompletionTextEdit::Edit(edit) => edit.new_text,
+                lsp_types::CompletionTextEdit::InsertAndReplace(edit) => edit.new_text,
+            };
+            assert_eq!(new_text, expected_text);
+        }
+    }
+
+    #[test]
+    fn diagnostic_code_works() {
+        let uri = lsp_types::Url::parse("file:///tmp/hello_ink/lib.rs").unwrap();
+        let context = PositionTranslationContext {
+            encoding: lsp_types::PositionEncodingKind::UTF8,
+            line_index: LineIndex::new("mod my_contract {}\n"),
+        };
+        let result = diagnostic(
+            ink_analyzer::Diagnostic::builder("Missing ink! storage definition.")
+                .range(ink_analyzer::TextRange::new(
+                    ink_analyzer::TextSize::from(0),
+                    ink_analyzer::TextSize::from(15),
+                ))
+                .code(ink_analyzer::DiagnosticCode::MissingStorage)
+                .build()
+                .unwrap(),
+            &uri,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(
+            result.code,
+            Some(lsp_types::NumberOrString::String(
+                "E0017-missing-storage".to_string()
+            ))
+        );
+        assert_eq!(
+            result.code_description.unwrap().href.as_str(),
+            "https://github.com/ink-analyzer/ink-analyzer/blob/master/crates/analyzer/DIAGNOSTICS.md#e0017-missing-storage"
+        );
+    }
+
+    #[test]
+    fn diagnostic_deprecation_tag_works() {
+        let uri = lsp_types::Url::parse("file:///tmp/hello_ink/lib.rs").unwrap();
+        let context = PositionTranslationContext {
+            encoding: lsp_types::PositionEncodingKind::UTF8,
+            line_index: LineIndex::new("mod my_contract {}\n"),
+        };
+        for (severity, expected_severity, expected_tags) in [
+            (
+                ink_analyzer::Severity::Error,
+                lsp_types::DiagnosticSeverity::ERROR,
+                None,
+            ),
+            (
+                ink_analyzer::Severity::Warning,
+                lsp_types::DiagnosticSeverity::WARNING,
+                None,
+            ),
+            (
+                ink_analyzer::Severity::Deprecation,
+                lsp_types::DiagnosticSeverity::WARNING,
+                Some(vec![lsp_types::DiagnosticTag::DEPRECATED]),
+            ),
+        ] {
+            let result = diagnostic(
+                ink_analyzer::Diagnostic::builder("Deprecated ink! construct.")
+                    .range(ink_analyzer::TextRange::new(
+                        ink_analyzer::TextSize::from(0),
+                        ink_analyzer::TextSize::from(15),
+                    ))
+                    .severity(severity)
+                    .build()
+                    .unwrap(),
+                &uri,
+                &context,
+            )
+            .unwrap();
+            assert_eq!(result.severity, Some(expected_severity), "{severity:?}");
+            assert_eq!(result.tags, expected_tags, "{severity:?}");
+        }
+    }
+
+    #[test]
+    fn code_action_document_edits_works() {
+        let uri = lsp_types::Url::parse("file:///tmp/hello_ink/lib.rs").unwrap();
+        let cargo_uri = lsp_types::Url::parse("file:///tmp/hello_ink/Cargo.toml").unwrap();
+        let context = || PositionTranslationContext {
+            encoding: lsp_types::PositionEncodingKind::UTF8,
+            line_index: LineIndex::new("mod my_contract {}\n"),
+        };
+        let action =
+            ink_analyzer::Action::builder("Add e2e tests.", ink_analyzer::ActionKind::QuickFix)
+                .range(ink_analyzer::TextRange::new(
+                    ink_analyzer::TextSize::from(0),
+                    ink_analyzer::TextSize::from(3),
+                ))
+                .edit(ink_analyzer::TextEdit::insert(
+                    "#[cfg(all(test, feature = \"e2e-tests\"))]\nmod e2e_tests {}\n".to_string(),
+                    ink_analyzer::TextSize::from(19),
+                ))
+                .document_edit(
+                    "Cargo.toml",
+                    ink_analyzer::TextEdit::insert(
+                        "e2e-tests = []\n".to_string(),
+                        ink_analyzer::TextSize::from(19),
+                    ),
+                )
+                .build()
+                .unwrap();
+        let expected_edit = |line: u32, text: &str| lsp_types::TextEdit {
+            range: lsp_types::Range::new(
+                lsp_types::Position::new(line, 0),
+                lsp_types::Position::new(line, 0),
+            ),
+            new_text: text.to_string(),
+        };
+
+        // Versioned document changes.
+        let result = code_action(
+            action.clone(),
+            uri.clone(),
+            Some(2),
+            &context(),
+            true,
+            |uri| (*uri == cargo_uri).then(|| (Some(5), context())),
+        )
+        .unwrap();
+        assert_eq!(
+            result.edit.unwrap().document_changes,
+            Some(lsp_types::DocumentChanges::Edits(vec![
+                lsp_types::TextDocumentEdit {
+                    text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
+                        uri: uri.clone(),
+                        version: Some(2),
+                    },
+                    edits: vec![lsp_types::OneOf::Left(expected_edit(
+                        1,
+                        "#[cfg(all(test, feature = \"e2e-tests\"))]\nmod e2e_tests {}\n"
+                    ))],
+                },
+                lsp_types::TextDocumentEdit {
+                    text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
+                        uri: cargo_uri.clone(),
+                        version: Some(5),
+                    },
+                    edits: vec![lsp_types::OneOf::Left(expected_edit(1, "e2e-tests = []\n"))],
+                },
+            ]))
+        );
+
+        // Multi-document changes (i.e. without versioned document changes support).
+        let result = code_action(
+            action.clone(),
+            uri.clone(),
+            Some(2),
+            &context(),
+            false,
+            |uri| (*uri == cargo_uri).then(|| (Some(5), context())),
+        )
+        .unwrap();
+        let changes = result.edit.unwrap().changes.unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[&cargo_uri],
+            vec![expected_edit(1, "e2e-tests = []\n")]
+        );
+
+        // Preferred actions.
+        let result = code_action(
+            ink_analyzer::Action {
+                is_preferred: true,
+                ..action.clone()
+            },
+            uri.clone(),
+            Some(2),
+            &context(),
+            true,
+            |uri| (*uri == cargo_uri).then(|| (Some(5), context())),
+        )
+        .unwrap();
+        assert_eq!(result.is_preferred, Some(true));
+
+        // "Fix all" source actions use the same code action kind as `ink_analyzer::lsp` conversions
+        // (i.e. the kind advertised in the server capabilities and requested by clients).
+        let result = code_action(
+            ink_analyzer::Action {
+                kind: ink_analyzer::ActionKind::SourceFixAll,
+                ..action.clone()
+            },
+            uri.clone(),
+            Some(2),
+            &context(),
+            true,
+            |uri| (*uri == cargo_uri).then(|| (Some(5), context())),
+        )
+        .unwrap();
+        assert_eq!(
+            result.kind,
+            Some(lsp_types::CodeActionKind::from(
+                ink_analyzer::ActionKind::SourceFixAll
+            ))
+        );
+        assert_eq!(result.kind, Some(crate::utils::FIX_ALL_CODE_ACTION_KIND));
+
+        // Unresolved document.
+        assert!(code_action(action, uri, Some(2), &context(), true, |_| None).is_none());
+    }
+
+    #[test]
+    fn resource_op_works() {
+        let root = lsp_types::Url::parse("file:///tmp/hello_ink/").unwrap();
+        for (op, expected_ops) in [
+            (
+                ink_analyzer::ResourceOp::CreateFile {
+                    path: "lib.rs".to_string(),
+                    file: ink_analyzer::ProjectFile {
+                        plain: "// lib".to_string(),
+                        snippet: None,
+                    },
+                },
+                vec![
+                    lsp_types::DocumentChangeOperation::Op(lsp_types::ResourceOp::Create(
+                        lsp_types::CreateFile {
+                            uri: root.join("lib.rs").unwrap(),
+                            options: None,
+                            annotation_id: None,
+                        },
+                    )),
+                    lsp_types::DocumentChangeOperation::Edit(lsp_types::TextDocumentEdit {
+                        text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
+                            uri: root.join("lib.rs").unwrap(),
+                            version: None,
+                        },
+                        edits: vec![lsp_types::OneOf::Left(lsp_types::TextEdit {
+                            range: lsp_types::Range::default(),
+                            new_text: "// lib".to_string(),
+                        })],
+                    }),
+                ],
+            ),
+            (
+                ink_analyzer::ResourceOp::CreateDir {
+                    path: "src".to_string(),
+                },
+                vec![],
+            ),
+            (
+                ink_analyzer::ResourceOp::Rename {
+                    from: "lib.rs".to_string(),
+                    to: "src/lib.rs".to_string(),
+                },
+                vec![lsp_types::DocumentChangeOperation::Op(
+                    lsp_types::ResourceOp::Rename(lsp_types::RenameFile {
+                        old_uri: root.join("lib.rs").unwrap(),
+                        new_uri: root.join("src/lib.rs").unwrap(),
+                        options: None,
+                        annotation_id: None,
+                    }),
+                )],
+            ),
+            (
+                ink_analyzer::ResourceOp::Delete {
+                    path: "src/".to_string(),
+                    recursive: true,
+                },
+                vec![lsp_types::DocumentChangeOperation::Op(
+                    lsp_types::ResourceOp::Delete(lsp_types::DeleteFile {
+                        uri: root.join("src/").unwrap(),
+                        options: Some(lsp_types::DeleteFileOptions {
+                            recursive: Some(true),
+                            ignore_if_not_exists: None,
+                            annotation_id: None,
+                        }),
+                    }),
+                )],
+            ),
+        ] {
+            assert_eq!(resource_op(op, &root), Some(expected_ops));
+        }
+    }
 }
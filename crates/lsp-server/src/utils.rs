@@ -2,13 +2,18 @@
 
 use lsp_server::RequestId;
 use lsp_types::{ClientCapabilities, CodeActionKind, PositionEncodingKind};
-use std::collections::HashSet;
 
-const SERVER_CODE_ACTION_KINDS: [CodeActionKind; 4] = [
+/// LSP code action kind for the "fix all auto-fixable problems" source action.
+pub use ink_analyzer::lsp::FIX_ALL_CODE_ACTION_KIND;
+
+const SERVER_CODE_ACTION_KINDS: [CodeActionKind; 7] = [
     CodeActionKind::EMPTY,
     CodeActionKind::QUICKFIX,
     CodeActionKind::REFACTOR,
+    CodeActionKind::REFACTOR_EXTRACT,
     CodeActionKind::REFACTOR_REWRITE,
+    CodeActionKind::SOURCE,
+    FIX_ALL_CODE_ACTION_KIND,
 ];
 
 /// Returns the preferred LSP `PositionEncodingKind` based on the LSP client's capabilities.
@@ -40,20 +45,38 @@ pub fn code_actions_kinds(client_capabilities: &ClientCapabilities) -> Option<Ve
         .and_then(|it| {
             // If client defines supported code action kinds,
             // return only code actions supported by both the client and server (if any), otherwise return none.
-            let code_actions_kinds: Vec<CodeActionKind> = HashSet::from(SERVER_CODE_ACTION_KINDS)
-                .intersection(
-                    &it.code_action_kind
-                        .value_set
+            // NOTE: Client code action kinds are base kinds (e.g. `source` includes `source.fixAll.inkAnalyzer`).
+            let client_kinds: Vec<CodeActionKind> = it
+                .code_action_kind
+                .value_set
+                .iter()
+                .map(|it| CodeActionKind::from(it.to_string()))
+                .collect();
+            let code_actions_kinds: Vec<CodeActionKind> = SERVER_CODE_ACTION_KINDS
+                .into_iter()
+                .filter(|kind| {
+                    client_kinds
                         .iter()
-                        .map(|it| CodeActionKind::from(it.to_string()))
-                        .collect::<HashSet<CodeActionKind>>(),
-                )
-                .cloned()
+                        .any(|base| is_code_action_sub_kind(kind, base))
+                })
                 .collect();
             (!code_actions_kinds.is_empty()).then_some(code_actions_kinds)
         })
 }
 
+/// Returns true if the code action kind is the same as, or a sub-kind of, the base code action kind
+/// (e.g. `source.fixAll.inkAnalyzer` is a sub-kind of both `source.fixAll` and `source`).
+///
+/// NOTE: The empty code action kind is only a base kind for itself.
+pub fn is_code_action_sub_kind(kind: &CodeActionKind, base: &CodeActionKind) -> bool {
+    kind == base
+        || (!base.as_str().is_empty()
+            && kind
+                .as_str()
+                .strip_prefix(base.as_str())
+                .map_or(false, |suffix| suffix.starts_with('.')))
+}
+
 /// Returns true if the LSP client advertises completion snippet support, or false otherwise.
 pub fn snippet_support(client_capabilities: &ClientCapabilities) -> bool {
     client_capabilities
@@ -65,6 +88,40 @@ pub fn snippet_support(client_capabilities: &ClientCapabilities) -> bool {
         .unwrap_or(false)
 }
 
+/// Returns true if the LSP client supports pulling diagnostics (i.e. `textDocument/diagnostic` requests), or false otherwise.
+pub fn pull_diagnostics_support(client_capabilities: &ClientCapabilities) -> bool {
+    client_capabilities
+        .text_document
+        .as_ref()
+        .map_or(false, |it| it.diagnostic.is_some())
+}
+
+/// Returns true if the LSP client supports dynamic registration of file watchers
+/// (i.e. `workspace/didChangeWatchedFiles` notifications), or false otherwise.
+pub fn did_change_watched_files_dynamic_registration(
+    client_capabilities: &ClientCapabilities,
+) -> bool {
+    client_capabilities
+        .workspace
+        .as_ref()
+        .and_then(|it| it.did_change_watched_files.as_ref())
+        .and_then(|it| it.dynamic_registration)
+        .unwrap_or(false)
+}
+
+/// Returns true if the LSP client supports lazily resolving the edits of code actions
+/// (i.e. `codeAction/resolve` requests), or false otherwise.
+pub fn code_action_resolve_support(client_capabilities: &ClientCapabilities) -> bool {
+    client_capabilities
+        .text_document
+        .as_ref()
+        .and_then(|it| it.code_action.as_ref())
+        .and_then(|it| it.resolve_support.as_ref())
+        .map_or(false, |it| {
+            it.properties.iter().any(|property| property == "edit")
+        })
+}
+
 /// Information about supported signature information features.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SignatureSupport {
@@ -104,6 +161,16 @@ pub fn request_id_as_str(id: RequestId) -> Option<String> {
         .map(ToString::to_string)
 }
 
+/// Returns true if the LSP client supports versioned document changes in workspace edits, or false otherwise.
+pub fn document_changes_support(client_capabilities: &ClientCapabilities) -> bool {
+    client_capabilities
+        .workspace
+        .as_ref()
+        .and_then(|it| it.workspace_edit.as_ref())
+        .and_then(|it| it.document_changes)
+        .unwrap_or(false)
+}
+
 /// Returns true if the LSP client advertises capabilities needed to create new projects via workspace edit, or false otherwise.
 pub fn can_create_project_via_workspace_edit(client_capabilities: &ClientCapabilities) -> bool {
     client_capabilities.workspace.as_ref().map_or(false, |it| {
@@ -125,12 +192,14 @@ mod tests {
     use super::*;
     use crate::utils::{code_actions_kinds, position_encoding, SERVER_CODE_ACTION_KINDS};
     use lsp_types::{
-        CodeActionClientCapabilities, CodeActionKindLiteralSupport, CodeActionLiteralSupport,
-        CompletionClientCapabilities, CompletionItemCapability, GeneralClientCapabilities,
-        ParameterInformationSettings, ResourceOperationKind, SignatureHelpClientCapabilities,
-        SignatureInformationSettings, TextDocumentClientCapabilities, WorkspaceClientCapabilities,
+        CodeActionCapabilityResolveSupport, CodeActionClientCapabilities,
+        CodeActionKindLiteralSupport, CodeActionLiteralSupport, CompletionClientCapabilities,
+        CompletionItemCapability, GeneralClientCapabilities, ParameterInformationSettings,
+        ResourceOperationKind, SignatureHelpClientCapabilities, SignatureInformationSettings,
+        TextDocumentClientCapabilities, WorkspaceClientCapabilities,
         WorkspaceEditClientCapabilities,
     };
+    use std::collections::HashSet;
 
     fn config_with_encodings(encodings: Option<Vec<PositionEncodingKind>>) -> ClientCapabilities {
         ClientCapabilities {
@@ -309,18 +378,34 @@ mod tests {
             ),
             (
                 config_with_code_action_kinds(
-                    [CodeActionKind::EMPTY, CodeActionKind::SOURCE].to_vec(),
+                    [CodeActionKind::EMPTY, CodeActionKind::QUICKFIX].to_vec(),
                 ),
-                Some(HashSet::from([CodeActionKind::EMPTY])),
+                Some(HashSet::from([
+                    CodeActionKind::EMPTY,
+                    CodeActionKind::QUICKFIX,
+                ])),
             ),
+            // Client code action kinds are base kinds.
             (
                 config_with_code_action_kinds(
                     [CodeActionKind::EMPTY, CodeActionKind::SOURCE].to_vec(),
                 ),
-                Some(HashSet::from([CodeActionKind::EMPTY])),
+                Some(HashSet::from([
+                    CodeActionKind::EMPTY,
+                    CodeActionKind::SOURCE,
+                    FIX_ALL_CODE_ACTION_KIND,
+                ])),
+            ),
+            (
+                config_with_code_action_kinds([CodeActionKind::REFACTOR].to_vec()),
+                Some(HashSet::from([
+                    CodeActionKind::REFACTOR,
+                    CodeActionKind::REFACTOR_EXTRACT,
+                    CodeActionKind::REFACTOR_REWRITE,
+                ])),
             ),
             (
-                config_with_code_action_kinds([CodeActionKind::SOURCE].to_vec()),
+                config_with_code_action_kinds([CodeActionKind::SOURCE_ORGANIZE_IMPORTS].to_vec()),
                 None,
             ),
         ] {
@@ -349,6 +434,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pull_diagnostics_support_works() {
+        for (client_capabilities, expected_result) in [
+            // Default is `false`.
+            (ClientCapabilities::default(), false),
+            // Set capability is properly parsed.
+            (
+                ClientCapabilities {
+                    text_document: Some(TextDocumentClientCapabilities {
+                        diagnostic: Some(Default::default()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                true,
+            ),
+        ] {
+            // Verifies the pull diagnostics support is parsed properly based on client capabilities.
+            assert_eq!(
+                pull_diagnostics_support(&client_capabilities),
+                expected_result
+            );
+        }
+    }
+
+    #[test]
+    fn code_action_resolve_support_works() {
+        let config_with_resolve_properties = |properties: Vec<&str>| ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                code_action: Some(CodeActionClientCapabilities {
+                    resolve_support: Some(CodeActionCapabilityResolveSupport {
+                        properties: properties.into_iter().map(ToString::to_string).collect(),
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        for (client_capabilities, expected_result) in [
+            // Default is `false`.
+            (ClientCapabilities::default(), false),
+            // Edit property is required.
+            (config_with_resolve_properties(vec![]), false),
+            (config_with_resolve_properties(vec!["documentation"]), false),
+            (config_with_resolve_properties(vec!["edit"]), true),
+            (
+                config_with_resolve_properties(vec!["documentation", "edit"]),
+                true,
+            ),
+        ] {
+            // Verifies the code action resolve support is parsed properly based on client capabilities.
+            assert_eq!(
+                code_action_resolve_support(&client_capabilities),
+                expected_result
+            );
+        }
+    }
+
     #[test]
     fn signature_support_works() {
         for (client_capabilities, expected_result) in [
@@ -0,0 +1,170 @@
+//! A virtual file system (VFS) where file system contents are overlaid with in-memory contents
+//! (i.e. unsaved editor buffers).
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::memory::Document;
+
+/// A virtual file system where file system contents are overlaid with in-memory contents.
+///
+/// Cross-file features should read files from a [`VfsSnapshot`] (see [`Vfs::snapshot`])
+/// so that they see unsaved changes (and the same contents) for the entire analysis pass.
+#[derive(Debug, Default)]
+pub struct Vfs {
+    overlays: HashMap<String, Document>,
+}
+
+impl Vfs {
+    /// Creates a new instance (without any overlays).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds (or replaces) the overlay for a file.
+    pub fn add_overlay(&mut self, id: String, content: String, version: i32) {
+        self.overlays.insert(
+            id,
+            Document {
+                content: content.into(),
+                version,
+            },
+        );
+    }
+
+    /// Updates the overlay for a file (if any), returns false if the file has no overlay.
+    pub fn update_overlay(&mut self, id: &str, content: String, version: i32) -> bool {
+        match self.overlays.get_mut(id) {
+            Some(doc) => {
+                doc.content = content.into();
+                doc.version = version;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the overlay for a file (if any).
+    pub fn remove_overlay(&mut self, id: &str) -> Option<Document> {
+        self.overlays.remove(id)
+    }
+
+    /// Retrieves the overlay for a file (if any).
+    pub fn overlay(&self, id: &str) -> Option<&Document> {
+        self.overlays.get(id)
+    }
+
+    /// Returns the identifiers of files with overlays.
+    pub fn overlay_ids(&self) -> impl Iterator<Item = &String> {
+        self.overlays.keys()
+    }
+
+    /// Returns a snapshot of the VFS (i.e. for an analysis pass).
+    ///
+    /// The snapshot captures the current overlays (without copying their contents),
+    /// while files on the file system are read lazily (i.e. on first read) and then cached,
+    /// so subsequent reads of the same file return the same contents
+    /// (but changes to a file on the file system before its first read are visible to the snapshot).
+    pub fn snapshot(&self) -> VfsSnapshot {
+        VfsSnapshot {
+            overlays: self.overlays.clone(),
+            disk: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+/// A snapshot of a [`Vfs`].
+#[derive(Debug)]
+pub struct VfsSnapshot {
+    overlays: HashMap<String, Document>,
+    disk: RefCell<HashMap<String, Option<Rc<str>>>>,
+}
+
+/// The contents of a file in a [`VfsSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    /// The contents of the file.
+    pub content: Rc<str>,
+    /// The version of the file as defined by the LSP client
+    /// (only set for files with an overlay).
+    pub version: Option<i32>,
+}
+
+impl VfsSnapshot {
+    /// Reads a file (i.e. from its overlay if any, or from the file system otherwise).
+    pub fn read(&self, uri: &lsp_types::Url) -> Option<File> {
+        if let Some(doc) = self.overlays.get(uri.as_str()) {
+            return Some(File {
+                content: Rc::clone(&doc.content),
+                version: Some(doc.version),
+            });
+        }
+        self.disk
+            .borrow_mut()
+            .entry(uri.to_string())
+            .or_insert_with(|| {
+                uri.to_file_path()
+                    .ok()
+                    .and_then(|path| std::fs::read_to_string(path).ok())
+                    .map(Rc::from)
+            })
+            .clone()
+            .map(|content| File {
+                content,
+                version: None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vfs_works() {
+        // Creates a file on disk.
+        let path = std::env::temp_dir().join("ink_analyzer_vfs_works.rs");
+        std::fs::write(&path, "disk").unwrap();
+        let uri = lsp_types::Url::from_file_path(&path).unwrap();
+        let missing_uri = lsp_types::Url::parse("file:///missing/lib.rs").unwrap();
+
+        // Reads from disk for files without overlays.
+        let mut vfs = Vfs::new();
+        let snapshot = vfs.snapshot();
+        assert_eq!(
+            snapshot.read(&uri),
+            Some(File {
+                content: Rc::from("disk"),
+                version: None
+            })
+        );
+        assert_eq!(snapshot.read(&missing_uri), None);
+
+        // Reads from overlays (if any).
+        vfs.add_overlay(uri.to_string(), "overlay".to_string(), 1);
+        let overlay_snapshot = vfs.snapshot();
+        assert_eq!(
+            overlay_snapshot.read(&uri),
+            Some(File {
+                content: Rc::from("overlay"),
+                version: Some(1)
+            })
+        );
+        assert!(vfs.update_overlay(uri.as_str(), "overlay2".to_string(), 2));
+        assert!(!vfs.update_overlay(missing_uri.as_str(), "missing".to_string(), 1));
+
+        // Snapshots are consistent (i.e. unaffected by later changes to overlays,
+        // and to files on the file system after they're read).
+        std::fs::write(&path, "disk2").unwrap();
+        assert_eq!(&*snapshot.read(&uri).unwrap().content, "disk");
+        assert_eq!(&*overlay_snapshot.read(&uri).unwrap().content, "overlay");
+        assert_eq!(&*vfs.snapshot().read(&uri).unwrap().content, "overlay2");
+
+        // Reads from disk after removing the overlay.
+        assert!(vfs.remove_overlay(uri.as_str()).is_some());
+        assert_eq!(&*vfs.snapshot().read(&uri).unwrap().content, "disk2");
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -94,12 +94,13 @@ pub fn impl_from_ink_attribute(ast: &syn::DeriveInput) -> syn::Result<TokenStrea
                     }
 
                     fn cast(attr: #ir_crate_path::InkAttribute) -> Option<Self> {
-                        Self::can_cast(&attr).then(|| {
-                            let ink_attr_data = #ir_crate_path::InkAttrData::from(attr);
-                            Self {
-                                #( #field_values, )*
-                                ink_attr: ink_attr_data,
-                            }
+                        if !Self::can_cast(&attr) {
+                            return None;
+                        }
+                        let ink_attr_data = #ir_crate_path::InkAttrData::try_from(attr).ok()?;
+                        Some(Self {
+                            #( #field_values, )*
+                            ink_attr: ink_attr_data,
                         })
                     }
                 }
@@ -178,12 +179,13 @@ mod tests {
                 }
 
                 fn cast(attr: #ir_crate_path::InkAttribute) -> Option<Self> {
-                    Self::can_cast(&attr).then(|| {
-                        let ink_attr_data = #ir_crate_path::InkAttrData::from(attr);
-                        Self {
-                            #( #optional_fields, )*
-                            ink_attr: ink_attr_data,
-                        }
+                    if !Self::can_cast(&attr) {
+                        return None;
+                    }
+                    let ink_attr_data = #ir_crate_path::InkAttrData::try_from(attr).ok()?;
+                    Some(Self {
+                        #( #optional_fields, )*
+                        ink_attr: ink_attr_data,
                     })
                 }
             }
@@ -94,6 +94,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                             ],
                             // constructor actions.
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod erc20"),
+                                        end_pat: Some("<-mod erc20"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -111,6 +118,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                             ],
                             // message actions.
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod erc20"),
+                                        end_pat: Some("<-mod erc20"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -127,6 +141,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod erc20"),
+                                        end_pat: Some("<-mod erc20"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -143,6 +164,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod erc20"),
+                                        end_pat: Some("<-mod erc20"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -159,6 +187,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod erc20"),
+                                        end_pat: Some("<-mod erc20"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -175,6 +210,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod erc20"),
+                                        end_pat: Some("<-mod erc20"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -191,6 +233,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod erc20"),
+                                        end_pat: Some("<-mod erc20"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -323,6 +372,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-pub mod flipper"),
+                                        end_pat: Some("<-pub mod flipper"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -339,6 +395,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-pub mod flipper"),
+                                        end_pat: Some("<-pub mod flipper"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -355,6 +418,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-pub mod flipper"),
+                                        end_pat: Some("<-pub mod flipper"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -371,6 +441,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-pub mod flipper"),
+                                        end_pat: Some("<-pub mod flipper"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -529,6 +606,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod mother"),
+                                        end_pat: Some("<-mod mother"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -545,6 +629,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod mother"),
+                                        end_pat: Some("<-mod mother"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -561,6 +652,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod mother"),
+                                        end_pat: Some("<-mod mother"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -577,6 +675,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod mother"),
+                                        end_pat: Some("<-mod mother"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -593,6 +698,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod mother"),
+                                        end_pat: Some("<-mod mother"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -609,6 +721,13 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                 ]
                             ],
                             vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink::contract]",
+                                        start_pat: Some("<-mod mother"),
+                                        end_pat: Some("<-mod mother"),
+                                    }
+                                ],
                                 vec![
                                     TestResultTextRange {
                                         text: "",
@@ -1207,6 +1326,16 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                     results: TestCaseResults::Diagnostic {
                         n: 7,
                         quickfixes: vec![
+                            // Add a new method.
+                            vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink(message)]",
+                                        start_pat: Some("\n        ) -> Result<()>;"),
+                                        end_pat: Some("\n        ) -> Result<()>;"),
+                                    }
+                                ],
+                            ],
                             // Add ink! message attribute to existing methods.
                             vec![
                                 vec![
@@ -1262,16 +1391,6 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                     }
                                 ],
                             ],
-                            // Add a new method.
-                            vec![
-                                vec![
-                                    TestResultTextRange {
-                                        text: "#[ink(message)]",
-                                        start_pat: Some("\n        ) -> Result<()>;"),
-                                        end_pat: Some("\n        ) -> Result<()>;"),
-                                    }
-                                ],
-                            ],
                         ]
                     },
                 },
@@ -1352,6 +1471,16 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                     results: TestCaseResults::Diagnostic {
                         n: 3,
                         quickfixes: vec![
+                            // Add a new method.
+                            vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "#[ink(message)]",
+                                        start_pat: Some("fn get(&self) -> bool;"),
+                                        end_pat: Some("fn get(&self) -> bool;"),
+                                    }
+                                ],
+                            ],
                             // Add ink! message attribute to existing methods.
                             vec![
                                 vec![
@@ -1371,13 +1500,53 @@ pub fn diagnostics_fixtures() -> Vec<TestGroup> {
                                     }
                                 ],
                             ],
-                            // Add a new method.
+                        ]
+                    },
+                },
+                TestCase {
+                    // Makes an ink! trait definition message `async`.
+                    modifications: Some(vec![TestCaseModification {
+                        start_pat: Some("<-fn flip(&mut self);"),
+                        end_pat: Some("<-fn flip(&mut self);"),
+                        replacement: "async ",
+                    }]),
+                    params: None,
+                    // 1 "ink! message must not be `async`" error.
+                    results: TestCaseResults::Diagnostic {
+                        n: 1,
+                        quickfixes: vec![
+                            // Remove `async` keyword.
                             vec![
                                 vec![
                                     TestResultTextRange {
-                                        text: "#[ink(message)]",
-                                        start_pat: Some("fn get(&self) -> bool;"),
-                                        end_pat: Some("fn get(&self) -> bool;"),
+                                        text: "",
+                                        start_pat: Some("<-async fn flip(&mut self);"),
+                                        end_pat: Some("async "),
+                                    }
+                                ],
+                            ],
+                        ]
+                    },
+                },
+                TestCase {
+                    // Makes an ink! message in a trait implementation `async`.
+                    modifications: Some(vec![TestCaseModification {
+                        start_pat: Some("<-fn flip(&mut self) {"),
+                        end_pat: Some("<-fn flip(&mut self) {"),
+                        replacement: "async ",
+                    }]),
+                    params: None,
+                    // 1 "ink! message must not be `async`" error.
+                    results: TestCaseResults::Diagnostic {
+                        n: 1,
+                        quickfixes: vec![
+                            // Remove `async` keyword.
+                            vec![
+                                vec![
+                                    TestResultTextRange {
+                                        text: "",
+                                        start_pat: Some("<-async fn flip(&mut self) {"),
+                                        end_pat: Some("async "),
                                     }
                                 ],
                             ],
@@ -1907,14 +2076,6 @@ pub fn actions_fixtures() -> Vec<TestGroup> {
                         pat: Some("<-mod erc20"),
                     })),
                     results: TestCaseResults::Action(vec![
-                        TestResultAction {
-                            label: "Add",
-                            edits: vec![TestResultTextRange {
-                                text: r#"(keep_attr = "")"#,
-                                start_pat: Some("#[ink::contract"),
-                                end_pat: Some("#[ink::contract"),
-                            }],
-                        },
                         TestResultAction {
                             label: "Flatten",
                             edits: vec![
@@ -1930,6 +2091,14 @@ pub fn actions_fixtures() -> Vec<TestGroup> {
                                 },
                             ],
                         },
+                        TestResultAction {
+                            label: "Add",
+                            edits: vec![TestResultTextRange {
+                                text: r#"(keep_attr = "")"#,
+                                start_pat: Some("#[ink::contract"),
+                                end_pat: Some("#[ink::contract"),
+                            }],
+                        },
                         TestResultAction {
                             label: "Add",
                             edits: vec![TestResultTextRange {
@@ -2122,6 +2291,46 @@ pub fn actions_fixtures() -> Vec<TestGroup> {
                                 end_pat: Some("<-impl Erc20 {"),
                             }],
                         },
+                        TestResultAction {
+                            label: "Extract",
+                            edits: vec![
+                                TestResultTextRange {
+                                    text: "",
+                                    start_pat: Some("<-/// Returns the total token supply."),
+                                    end_pat: Some("<-/// Returns the account balance for the specified `owner`."),
+                                },
+                                TestResultTextRange {
+                                    text: "",
+                                    start_pat: Some("<-/// Returns the account balance for the specified `owner`."),
+                                    end_pat: Some("self.balance_of_impl(&owner)\n        }\n\n        "),
+                                },
+                                TestResultTextRange {
+                                    text: "",
+                                    start_pat: Some("<-/// Returns the amount which `spender` is still allowed"),
+                                    end_pat: Some("self.allowance_impl(&owner, &spender)\n        }\n\n        "),
+                                },
+                                TestResultTextRange {
+                                    text: "",
+                                    start_pat: Some("<-/// Transfers `value` amount of tokens from the caller's account"),
+                                    end_pat: Some("<-/// Allows `spender` to withdraw"),
+                                },
+                                TestResultTextRange {
+                                    text: "",
+                                    start_pat: Some("<-/// Allows `spender` to withdraw"),
+                                    end_pat: Some("<-/// Transfers `value` tokens on the behalf"),
+                                },
+                                TestResultTextRange {
+                                    text: "",
+                                    start_pat: Some("<-/// Transfers `value` tokens on the behalf"),
+                                    end_pat: Some("&(allowance - value));\n            Ok(())\n        }\n\n        "),
+                                },
+                                TestResultTextRange {
+                                    text: "#[ink::trait_definition]",
+                                    start_pat: Some("<-\n\n    #[cfg(test)]"),
+                                    end_pat: Some("<-\n\n    #[cfg(test)]"),
+                                },
+                            ],
+                        },
                         TestResultAction {
                             label: "Add",
                             edits: vec![TestResultTextRange {
@@ -2516,14 +2725,51 @@ pub fn actions_fixtures() -> Vec<TestGroup> {
                     params: Some(TestCaseParams::Action(TestParamsOffsetOnly {
                         pat: Some("<-impl BaseErc20 for Erc20 {"),
                     })),
-                    results: TestCaseResults::Action(vec![TestResultAction {
-                        label: "Add",
-                        edits: vec![TestResultTextRange {
-                            text: "#[ink(impl)]",
-                            start_pat: Some("<-impl BaseErc20 for Erc20 {"),
-                            end_pat: Some("<-impl BaseErc20 for Erc20 {"),
-                        }],
-                    }]),
+                    results: TestCaseResults::Action(vec![
+                        TestResultAction {
+                            label: "Pin current selectors",
+                            edits: vec![
+                                TestResultTextRange {
+                                    text: ", selector = 0x8244a1ad",
+                                    start_pat: Some("<-)]\n        fn total_supply(&self)"),
+                                    end_pat: Some("<-)]\n        fn total_supply(&self)"),
+                                },
+                                TestResultTextRange {
+                                    text: ", selector = 0x933ae3c8",
+                                    start_pat: Some("<-)]\n        fn balance_of(&self"),
+                                    end_pat: Some("<-)]\n        fn balance_of(&self"),
+                                },
+                                TestResultTextRange {
+                                    text: ", selector = 0x74a27ac8",
+                                    start_pat: Some("<-)]\n        fn allowance(&self"),
+                                    end_pat: Some("<-)]\n        fn allowance(&self"),
+                                },
+                                TestResultTextRange {
+                                    text: ", selector = 0xfa9833a3",
+                                    start_pat: Some("<-)]\n        fn transfer(&mut self"),
+                                    end_pat: Some("<-)]\n        fn transfer(&mut self"),
+                                },
+                                TestResultTextRange {
+                                    text: ", selector = 0x922e291f",
+                                    start_pat: Some("<-)]\n        fn approve(&mut self"),
+                                    end_pat: Some("<-)]\n        fn approve(&mut self"),
+                                },
+                                TestResultTextRange {
+                                    text: ", selector = 0x839f0263",
+                                    start_pat: Some("<-)]\n        fn transfer_from("),
+                                    end_pat: Some("<-)]\n        fn transfer_from("),
+                                },
+                            ],
+                        },
+                        TestResultAction {
+                            label: "Add",
+                            edits: vec![TestResultTextRange {
+                                text: "#[ink(impl)]",
+                                start_pat: Some("<-impl BaseErc20 for Erc20 {"),
+                                end_pat: Some("<-impl BaseErc20 for Erc20 {"),
+                            }],
+                        },
+                    ]),
                 },
             ],
         },
@@ -2571,14 +2817,76 @@ pub fn actions_fixtures() -> Vec<TestGroup> {
                     params: Some(TestCaseParams::Action(TestParamsOffsetOnly {
                         pat: Some("<-pub trait Psp22Extension {"),
                     })),
-                    results: TestCaseResults::Action(vec![TestResultAction {
-                        label: "Add",
-                        edits: vec![TestResultTextRange {
-                            text: "#[ink(extension = 1)]",
-                            start_pat: Some("<-\n}\n\n#[derive(scale::Encode, scale::Decode)]"),
-                            end_pat: Some("<-\n}\n\n#[derive(scale::Encode, scale::Decode)]"),
-                        }],
-                    }]),
+                    results: TestCaseResults::Action(vec![
+                        TestResultAction {
+                            label: "Renumber",
+                            edits: vec![
+                                TestResultTextRange {
+                                    text: "3",
+                                    start_pat: Some("<-0x3d26)]"),
+                                    end_pat: Some("0x3d26"),
+                                },
+                                TestResultTextRange {
+                                    text: "2",
+                                    start_pat: Some("<-0x3420)]"),
+                                    end_pat: Some("0x3420"),
+                                },
+                                TestResultTextRange {
+                                    text: "7",
+                                    start_pat: Some("<-0x7271)]"),
+                                    end_pat: Some("0x7271"),
+                                },
+                                TestResultTextRange {
+                                    text: "1",
+                                    start_pat: Some("<-0x162d)]"),
+                                    end_pat: Some("0x162d"),
+                                },
+                                TestResultTextRange {
+                                    text: "6",
+                                    start_pat: Some("<-0x6568)]"),
+                                    end_pat: Some("0x6568"),
+                                },
+                                TestResultTextRange {
+                                    text: "4",
+                                    start_pat: Some("<-0x4d47)]"),
+                                    end_pat: Some("0x4d47"),
+                                },
+                                TestResultTextRange {
+                                    text: "10",
+                                    start_pat: Some("<-0xdb20)]"),
+                                    end_pat: Some("0xdb20"),
+                                },
+                                TestResultTextRange {
+                                    text: "5",
+                                    start_pat: Some("<-0x54b3)]"),
+                                    end_pat: Some("0x54b3"),
+                                },
+                                TestResultTextRange {
+                                    text: "9",
+                                    start_pat: Some("<-0xb20f)]"),
+                                    end_pat: Some("0xb20f"),
+                                },
+                                TestResultTextRange {
+                                    text: "8",
+                                    start_pat: Some("<-0x96d6)]"),
+                                    end_pat: Some("0x96d6"),
+                                },
+                                TestResultTextRange {
+                                    text: "11",
+                                    start_pat: Some("<-0xfecb)]"),
+                                    end_pat: Some("0xfecb"),
+                                },
+                            ],
+                        },
+                        TestResultAction {
+                            label: "Add",
+                            edits: vec![TestResultTextRange {
+                                text: "#[ink(extension = 1)]",
+                                start_pat: Some("<-\n}\n\n#[derive(scale::Encode, scale::Decode)]"),
+                                end_pat: Some("<-\n}\n\n#[derive(scale::Encode, scale::Decode)]"),
+                            }],
+                        },
+                    ]),
                 },
                 TestCase {
                     modifications: Some(vec![TestCaseModification {
@@ -3090,8 +3398,75 @@ pub fn inlay_hints_fixtures() -> Vec<TestGroup> {
                         start_pat: Some("<-"),
                         end_pat: Some("->"),
                     }))),
-                    // Describes the expected results.
-                    results: TestCaseResults::InlayHints(vec![]),
+                    // Describes the expected results
+                    // (i.e. computed selectors for ink! constructors and ink! messages positioned at the end of each `fn` name).
+                    results: TestCaseResults::InlayHints(vec![
+                        // Transfer.
+                        TestResultTextOffsetRange {
+                            text:
+                                "0x0045726332303a3a5472616e7366657200000000000000000000000000000000",
+                            pos_pat: Some("pub struct Transfer"),
+                            range_start_pat: Some("<-Transfer {"),
+                            range_end_pat: Some("pub struct Transfer"),
+                        },
+                        // Approval.
+                        TestResultTextOffsetRange {
+                            text:
+                                "0x0045726332303a3a417070726f76616c00000000000000000000000000000000",
+                            pos_pat: Some("pub struct Approval"),
+                            range_start_pat: Some("<-Approval {"),
+                            range_end_pat: Some("pub struct Approval"),
+                        },
+                        // new.
+                        TestResultTextOffsetRange {
+                            text: "0x9bae9d5e",
+                            pos_pat: Some("pub fn new"),
+                            range_start_pat: Some("<-new(total_supply"),
+                            range_end_pat: Some("pub fn new"),
+                        },
+                        // total_supply.
+                        TestResultTextOffsetRange {
+                            text: "0xdb6375a8",
+                            pos_pat: Some("pub fn total_supply"),
+                            range_start_pat: Some("<-total_supply(&self)"),
+                            range_end_pat: Some("pub fn total_supply"),
+                        },
+                        // balance_of.
+                        TestResultTextOffsetRange {
+                            text: "0x0f755a56",
+                            pos_pat: Some("pub fn balance_of"),
+                            range_start_pat: Some("<-balance_of(&self, owner: AccountId)"),
+                            range_end_pat: Some("pub fn balance_of"),
+                        },
+                        // allowance.
+                        TestResultTextOffsetRange {
+                            text: "0x6a00165e",
+                            pos_pat: Some("pub fn allowance"),
+                            range_start_pat: Some("<-allowance(&self, owner: AccountId"),
+                            range_end_pat: Some("pub fn allowance"),
+                        },
+                        // transfer.
+                        TestResultTextOffsetRange {
+                            text: "0x84a15da1",
+                            pos_pat: Some("pub fn transfer"),
+                            range_start_pat: Some("<-transfer(&mut self, to"),
+                            range_end_pat: Some("pub fn transfer"),
+                        },
+                        // approve.
+                        TestResultTextOffsetRange {
+                            text: "0x681266a0",
+                            pos_pat: Some("pub fn approve"),
+                            range_start_pat: Some("<-approve(&mut self"),
+                            range_end_pat: Some("pub fn approve"),
+                        },
+                        // transfer_from.
+                        TestResultTextOffsetRange {
+                            text: "0x0b396f18",
+                            pos_pat: Some("pub fn transfer_from"),
+                            range_start_pat: Some("<-transfer_from("),
+                            range_end_pat: Some("pub fn transfer_from"),
+                        },
+                    ]),
                 },
                 TestCase {
                     // Replaces `#[ink::contract]` with `#[ink::contract(env=MyEnvironment, keep_attr="foo,bar")]` in the source code.
@@ -3119,6 +3494,71 @@ pub fn inlay_hints_fixtures() -> Vec<TestGroup> {
                             range_start_pat: Some(r#"#[ink::contract(env=MyEnvironment, "#),
                             range_end_pat: Some(r#"#[ink::contract(env=MyEnvironment, keep_attr"#),
                         },
+                        // Transfer.
+                        TestResultTextOffsetRange {
+                            text:
+                                "0x0045726332303a3a5472616e7366657200000000000000000000000000000000",
+                            pos_pat: Some("pub struct Transfer"),
+                            range_start_pat: Some("<-Transfer {"),
+                            range_end_pat: Some("pub struct Transfer"),
+                        },
+                        // Approval.
+                        TestResultTextOffsetRange {
+                            text:
+                                "0x0045726332303a3a417070726f76616c00000000000000000000000000000000",
+                            pos_pat: Some("pub struct Approval"),
+                            range_start_pat: Some("<-Approval {"),
+                            range_end_pat: Some("pub struct Approval"),
+                        },
+                        // new.
+                        TestResultTextOffsetRange {
+                            text: "0x9bae9d5e",
+                            pos_pat: Some("pub fn new"),
+                            range_start_pat: Some("<-new(total_supply"),
+                            range_end_pat: Some("pub fn new"),
+                        },
+                        // total_supply.
+                        TestResultTextOffsetRange {
+                            text: "0xdb6375a8",
+                            pos_pat: Some("pub fn total_supply"),
+                            range_start_pat: Some("<-total_supply(&self)"),
+                            range_end_pat: Some("pub fn total_supply"),
+                        },
+                        // balance_of.
+                        TestResultTextOffsetRange {
+                            text: "0x0f755a56",
+                            pos_pat: Some("pub fn balance_of"),
+                            range_start_pat: Some("<-balance_of(&self, owner: AccountId)"),
+                            range_end_pat: Some("pub fn balance_of"),
+                        },
+                        // allowance.
+                        TestResultTextOffsetRange {
+                            text: "0x6a00165e",
+                            pos_pat: Some("pub fn allowance"),
+                            range_start_pat: Some("<-allowance(&self, owner: AccountId"),
+                            range_end_pat: Some("pub fn allowance"),
+                        },
+                        // transfer.
+                        TestResultTextOffsetRange {
+                            text: "0x84a15da1",
+                            pos_pat: Some("pub fn transfer"),
+                            range_start_pat: Some("<-transfer(&mut self, to"),
+                            range_end_pat: Some("pub fn transfer"),
+                        },
+                        // approve.
+                        TestResultTextOffsetRange {
+                            text: "0x681266a0",
+                            pos_pat: Some("pub fn approve"),
+                            range_start_pat: Some("<-approve(&mut self"),
+                            range_end_pat: Some("pub fn approve"),
+                        },
+                        // transfer_from.
+                        TestResultTextOffsetRange {
+                            text: "0x0b396f18",
+                            pos_pat: Some("pub fn transfer_from"),
+                            range_start_pat: Some("<-transfer_from("),
+                            range_end_pat: Some("pub fn transfer_from"),
+                        },
                     ]),
                 },
                 TestCase {
@@ -3129,6 +3569,29 @@ pub fn inlay_hints_fixtures() -> Vec<TestGroup> {
                     }]),
                     params: Some(TestCaseParams::InlayHints(None)),
                     results: TestCaseResults::InlayHints(vec![
+                        // Transfer.
+                        TestResultTextOffsetRange {
+                            text:
+                                "0x0045726332303a3a5472616e7366657200000000000000000000000000000000",
+                            pos_pat: Some("pub struct Transfer"),
+                            range_start_pat: Some("<-Transfer {"),
+                            range_end_pat: Some("pub struct Transfer"),
+                        },
+                        // Approval.
+                        TestResultTextOffsetRange {
+                            text:
+                                "0x0045726332303a3a417070726f76616c00000000000000000000000000000000",
+                            pos_pat: Some("pub struct Approval"),
+                            range_start_pat: Some("<-Approval {"),
+                            range_end_pat: Some("pub struct Approval"),
+                        },
+                        // new.
+                        TestResultTextOffsetRange {
+                            text: "0x9bae9d5e",
+                            pos_pat: Some("pub fn new"),
+                            range_start_pat: Some("<-new(total_supply"),
+                            range_end_pat: Some("pub fn new"),
+                        },
                         // selector.
                         TestResultTextOffsetRange {
                             text: "u32 | _",
@@ -3136,6 +3599,41 @@ pub fn inlay_hints_fixtures() -> Vec<TestGroup> {
                             range_start_pat: Some("<-selector=_"),
                             range_end_pat: Some("<-=_"),
                         },
+                        // balance_of.
+                        TestResultTextOffsetRange {
+                            text: "0x0f755a56",
+                            pos_pat: Some("pub fn balance_of"),
+                            range_start_pat: Some("<-balance_of(&self, owner: AccountId)"),
+                            range_end_pat: Some("pub fn balance_of"),
+                        },
+                        // allowance.
+                        TestResultTextOffsetRange {
+                            text: "0x6a00165e",
+                            pos_pat: Some("pub fn allowance"),
+                            range_start_pat: Some("<-allowance(&self, owner: AccountId"),
+                            range_end_pat: Some("pub fn allowance"),
+                        },
+                        // transfer.
+                        TestResultTextOffsetRange {
+                            text: "0x84a15da1",
+                            pos_pat: Some("pub fn transfer"),
+                            range_start_pat: Some("<-transfer(&mut self, to"),
+                            range_end_pat: Some("pub fn transfer"),
+                        },
+                        // approve.
+                        TestResultTextOffsetRange {
+                            text: "0x681266a0",
+                            pos_pat: Some("pub fn approve"),
+                            range_start_pat: Some("<-approve(&mut self"),
+                            range_end_pat: Some("pub fn approve"),
+                        },
+                        // transfer_from.
+                        TestResultTextOffsetRange {
+                            text: "0x0b396f18",
+                            pos_pat: Some("pub fn transfer_from"),
+                            range_start_pat: Some("<-transfer_from("),
+                            range_end_pat: Some("pub fn transfer_from"),
+                        },
                     ]),
                 },
                 TestCase {
@@ -3146,6 +3644,71 @@ pub fn inlay_hints_fixtures() -> Vec<TestGroup> {
                     }]),
                     params: Some(TestCaseParams::InlayHints(None)),
                     results: TestCaseResults::InlayHints(vec![
+                        // Transfer.
+                        TestResultTextOffsetRange {
+                            text:
+                                "0x0045726332303a3a5472616e7366657200000000000000000000000000000000",
+                            pos_pat: Some("pub struct Transfer"),
+                            range_start_pat: Some("<-Transfer {"),
+                            range_end_pat: Some("pub struct Transfer"),
+                        },
+                        // Approval.
+                        TestResultTextOffsetRange {
+                            text:
+                                "0x0045726332303a3a417070726f76616c00000000000000000000000000000000",
+                            pos_pat: Some("pub struct Approval"),
+                            range_start_pat: Some("<-Approval {"),
+                            range_end_pat: Some("pub struct Approval"),
+                        },
+                        // new.
+                        TestResultTextOffsetRange {
+                            text: "0x9bae9d5e",
+                            pos_pat: Some("pub fn new"),
+                            range_start_pat: Some("<-new(total_supply"),
+                            range_end_pat: Some("pub fn new"),
+                        },
+                        // total_supply.
+                        TestResultTextOffsetRange {
+                            text: "0xdb6375a8",
+                            pos_pat: Some("pub fn total_supply"),
+                            range_start_pat: Some("<-total_supply(&self)"),
+                            range_end_pat: Some("pub fn total_supply"),
+                        },
+                        // balance_of.
+                        TestResultTextOffsetRange {
+                            text: "0x0f755a56",
+                            pos_pat: Some("pub fn balance_of"),
+                            range_start_pat: Some("<-balance_of(&self, owner: AccountId)"),
+                            range_end_pat: Some("pub fn balance_of"),
+                        },
+                        // allowance.
+                        TestResultTextOffsetRange {
+                            text: "0x6a00165e",
+                            pos_pat: Some("pub fn allowance"),
+                            range_start_pat: Some("<-allowance(&self, owner: AccountId"),
+                            range_end_pat: Some("pub fn allowance"),
+                        },
+                        // transfer.
+                        TestResultTextOffsetRange {
+                            text: "0x84a15da1",
+                            pos_pat: Some("pub fn transfer"),
+                            range_start_pat: Some("<-transfer(&mut self, to"),
+                            range_end_pat: Some("pub fn transfer"),
+                        },
+                        // approve.
+                        TestResultTextOffsetRange {
+                            text: "0x681266a0",
+                            pos_pat: Some("pub fn approve"),
+                            range_start_pat: Some("<-approve(&mut self"),
+                            range_end_pat: Some("pub fn approve"),
+                        },
+                        // transfer_from.
+                        TestResultTextOffsetRange {
+                            text: "0x0b396f18",
+                            pos_pat: Some("pub fn transfer_from"),
+                            range_start_pat: Some("<-transfer_from("),
+                            range_end_pat: Some("pub fn transfer_from"),
+                        },
                         // additional_contracts.
                         TestResultTextOffsetRange {
                             text: "&str",
@@ -3190,7 +3753,153 @@ pub fn inlay_hints_fixtures() -> Vec<TestGroup> {
                 TestCase {
                     modifications: None,
                     params: Some(TestCaseParams::InlayHints(None)),
-                    results: TestCaseResults::InlayHints(vec![]),
+                    results: TestCaseResults::InlayHints(vec![
+                        // total_supply.
+                        TestResultTextOffsetRange {
+                            text: "0x8244a1ad",
+                            pos_pat: Some("<-(&self) -> Balance;"),
+                            range_start_pat: Some("<-total_supply(&self) -> Balance;"),
+                            range_end_pat: Some("<-(&self) -> Balance;"),
+                        },
+                        // balance_of.
+                        TestResultTextOffsetRange {
+                            text: "0x933ae3c8",
+                            pos_pat: Some("<-(&self, owner: AccountId) -> Balance;"),
+                            range_start_pat: Some(
+                                "<-balance_of(&self, owner: AccountId) -> Balance;",
+                            ),
+                            range_end_pat: Some("<-(&self, owner: AccountId) -> Balance;"),
+                        },
+                        // allowance.
+                        TestResultTextOffsetRange {
+                            text: "0x74a27ac8",
+                            pos_pat: Some(
+                                "<-(&self, owner: AccountId, spender: AccountId) -> Balance;",
+                            ),
+                            range_start_pat: Some(
+                                "<-allowance(&self, owner: AccountId, spender: AccountId) -> Balance;",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&self, owner: AccountId, spender: AccountId) -> Balance;",
+                            ),
+                        },
+                        // transfer.
+                        TestResultTextOffsetRange {
+                            text: "0xfa9833a3",
+                            pos_pat: Some(
+                                "<-(&mut self, to: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                            range_start_pat: Some(
+                                "<-transfer(&mut self, to: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&mut self, to: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                        },
+                        // approve.
+                        TestResultTextOffsetRange {
+                            text: "0x922e291f",
+                            pos_pat: Some(
+                                "<-(&mut self, spender: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                            range_start_pat: Some(
+                                "<-approve(&mut self, spender: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&mut self, spender: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                        },
+                        // transfer_from.
+                        TestResultTextOffsetRange {
+                            text: "0x839f0263",
+                            pos_pat: Some("fn transfer_from"),
+                            range_start_pat: Some("<-transfer_from("),
+                            range_end_pat: Some("fn transfer_from"),
+                        },
+                        // Transfer.
+                        TestResultTextOffsetRange {
+                            text: "0x0045726332303a3a5472616e7366657200000000000000000000000000000000",
+                            pos_pat: Some("pub struct Transfer"),
+                            range_start_pat: Some("<-Transfer {"),
+                            range_end_pat: Some("pub struct Transfer"),
+                        },
+                        // Approval.
+                        TestResultTextOffsetRange {
+                            text: "0x0045726332303a3a417070726f76616c00000000000000000000000000000000",
+                            pos_pat: Some("pub struct Approval"),
+                            range_start_pat: Some("<-Approval {"),
+                            range_end_pat: Some("pub struct Approval"),
+                        },
+                        // new.
+                        TestResultTextOffsetRange {
+                            text: "0x9bae9d5e",
+                            pos_pat: Some("pub fn new"),
+                            range_start_pat: Some("<-new(total_supply: Balance)"),
+                            range_end_pat: Some("pub fn new"),
+                        },
+                        // total_supply.
+                        TestResultTextOffsetRange {
+                            text: "0x8244a1ad",
+                            pos_pat: Some("<-(&self) -> Balance {"),
+                            range_start_pat: Some("<-total_supply(&self) -> Balance {"),
+                            range_end_pat: Some("<-(&self) -> Balance {"),
+                        },
+                        // balance_of.
+                        TestResultTextOffsetRange {
+                            text: "0x933ae3c8",
+                            pos_pat: Some("<-(&self, owner: AccountId) -> Balance {"),
+                            range_start_pat: Some(
+                                "<-balance_of(&self, owner: AccountId) -> Balance {",
+                            ),
+                            range_end_pat: Some("<-(&self, owner: AccountId) -> Balance {"),
+                        },
+                        // allowance.
+                        TestResultTextOffsetRange {
+                            text: "0x74a27ac8",
+                            pos_pat: Some(
+                                "<-(&self, owner: AccountId, spender: AccountId) -> Balance {",
+                            ),
+                            range_start_pat: Some(
+                                "<-allowance(&self, owner: AccountId, spender: AccountId) -> Balance {",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&self, owner: AccountId, spender: AccountId) -> Balance {",
+                            ),
+                        },
+                        // transfer.
+                        TestResultTextOffsetRange {
+                            text: "0xfa9833a3",
+                            pos_pat: Some(
+                                "<-(&mut self, to: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                            range_start_pat: Some(
+                                "<-transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&mut self, to: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                        },
+                        // approve.
+                        TestResultTextOffsetRange {
+                            text: "0x922e291f",
+                            pos_pat: Some(
+                                "<-(&mut self, spender: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                            range_start_pat: Some(
+                                "<-approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&mut self, spender: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                        },
+                        // transfer_from.
+                        TestResultTextOffsetRange {
+                            text: "0x839f0263",
+                            pos_pat: Some("#[ink(message)]\n        fn transfer_from->"),
+                            range_start_pat: Some("<-transfer_from(\n            &mut self,->"),
+                            range_end_pat: Some("#[ink(message)]\n        fn transfer_from->"),
+                        },
+                    ]),
                 },
                 TestCase {
                     modifications: Some(vec![TestCaseModification {
@@ -3220,6 +3929,151 @@ pub fn inlay_hints_fixtures() -> Vec<TestGroup> {
                                 r#"#[ink::trait_definition(namespace="my_namespace", keep_attr"#,
                             ),
                         },
+                        // total_supply.
+                        TestResultTextOffsetRange {
+                            text: "0x04f946b1",
+                            pos_pat: Some("<-(&self) -> Balance;"),
+                            range_start_pat: Some("<-total_supply(&self) -> Balance;"),
+                            range_end_pat: Some("<-(&self) -> Balance;"),
+                        },
+                        // balance_of.
+                        TestResultTextOffsetRange {
+                            text: "0x6d08d153",
+                            pos_pat: Some("<-(&self, owner: AccountId) -> Balance;"),
+                            range_start_pat: Some(
+                                "<-balance_of(&self, owner: AccountId) -> Balance;",
+                            ),
+                            range_end_pat: Some("<-(&self, owner: AccountId) -> Balance;"),
+                        },
+                        // allowance.
+                        TestResultTextOffsetRange {
+                            text: "0x1899fe3c",
+                            pos_pat: Some(
+                                "<-(&self, owner: AccountId, spender: AccountId) -> Balance;",
+                            ),
+                            range_start_pat: Some(
+                                "<-allowance(&self, owner: AccountId, spender: AccountId) -> Balance;",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&self, owner: AccountId, spender: AccountId) -> Balance;",
+                            ),
+                        },
+                        // transfer.
+                        TestResultTextOffsetRange {
+                            text: "0x5cb28809",
+                            pos_pat: Some(
+                                "<-(&mut self, to: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                            range_start_pat: Some(
+                                "<-transfer(&mut self, to: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&mut self, to: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                        },
+                        // approve.
+                        TestResultTextOffsetRange {
+                            text: "0xe29bba91",
+                            pos_pat: Some(
+                                "<-(&mut self, spender: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                            range_start_pat: Some(
+                                "<-approve(&mut self, spender: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&mut self, spender: AccountId, value: Balance) -> Result<()>;",
+                            ),
+                        },
+                        // transfer_from.
+                        TestResultTextOffsetRange {
+                            text: "0x20f0853f",
+                            pos_pat: Some("fn transfer_from"),
+                            range_start_pat: Some("<-transfer_from("),
+                            range_end_pat: Some("fn transfer_from"),
+                        },
+                        // Transfer.
+                        TestResultTextOffsetRange {
+                            text: "0x0045726332303a3a5472616e7366657200000000000000000000000000000000",
+                            pos_pat: Some("pub struct Transfer"),
+                            range_start_pat: Some("<-Transfer {"),
+                            range_end_pat: Some("pub struct Transfer"),
+                        },
+                        // Approval.
+                        TestResultTextOffsetRange {
+                            text: "0x0045726332303a3a417070726f76616c00000000000000000000000000000000",
+                            pos_pat: Some("pub struct Approval"),
+                            range_start_pat: Some("<-Approval {"),
+                            range_end_pat: Some("pub struct Approval"),
+                        },
+                        // new.
+                        TestResultTextOffsetRange {
+                            text: "0x9bae9d5e",
+                            pos_pat: Some("pub fn new"),
+                            range_start_pat: Some("<-new(total_supply: Balance)"),
+                            range_end_pat: Some("pub fn new"),
+                        },
+                        // total_supply.
+                        TestResultTextOffsetRange {
+                            text: "0x04f946b1",
+                            pos_pat: Some("<-(&self) -> Balance {"),
+                            range_start_pat: Some("<-total_supply(&self) -> Balance {"),
+                            range_end_pat: Some("<-(&self) -> Balance {"),
+                        },
+                        // balance_of.
+                        TestResultTextOffsetRange {
+                            text: "0x6d08d153",
+                            pos_pat: Some("<-(&self, owner: AccountId) -> Balance {"),
+                            range_start_pat: Some(
+                                "<-balance_of(&self, owner: AccountId) -> Balance {",
+                            ),
+                            range_end_pat: Some("<-(&self, owner: AccountId) -> Balance {"),
+                        },
+                        // allowance.
+                        TestResultTextOffsetRange {
+                            text: "0x1899fe3c",
+                            pos_pat: Some(
+                                "<-(&self, owner: AccountId, spender: AccountId) -> Balance {",
+                            ),
+                            range_start_pat: Some(
+                                "<-allowance(&self, owner: AccountId, spender: AccountId) -> Balance {",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&self, owner: AccountId, spender: AccountId) -> Balance {",
+                            ),
+                        },
+                        // transfer.
+                        TestResultTextOffsetRange {
+                            text: "0x5cb28809",
+                            pos_pat: Some(
+                                "<-(&mut self, to: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                            range_start_pat: Some(
+                                "<-transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&mut self, to: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                        },
+                        // approve.
+                        TestResultTextOffsetRange {
+                            text: "0xe29bba91",
+                            pos_pat: Some(
+                                "<-(&mut self, spender: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                            range_start_pat: Some(
+                                "<-approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                            range_end_pat: Some(
+                                "<-(&mut self, spender: AccountId, value: Balance) -> Result<()> {",
+                            ),
+                        },
+                        // transfer_from.
+                        TestResultTextOffsetRange {
+                            text: "0x20f0853f",
+                            pos_pat: Some("#[ink(message)]\n        fn transfer_from->"),
+                            range_start_pat: Some("<-transfer_from(\n            &mut self,->"),
+                            range_end_pat: Some("#[ink(message)]\n        fn transfer_from->"),
+                        },
                     ]),
                 },
             ],
@@ -3305,6 +4159,13 @@ pub fn inlay_hints_fixtures() -> Vec<TestGroup> {
                         range_start_pat: Some("#[ink::contract("),
                         range_end_pat: Some("#[ink::contract(env"),
                     },
+                    // constructor.
+                    TestResultTextOffsetRange {
+                        text: "0x9bae9d5e",
+                        pos_pat: Some("pub fn new"),
+                        range_start_pat: Some("<-new() -> Self"),
+                        range_end_pat: Some("pub fn new"),
+                    },
                     // selectors.
                     TestResultTextOffsetRange {
                         text: "u32 | _",
@@ -3395,6 +4256,41 @@ pub fn inlay_hints_fixtures() -> Vec<TestGroup> {
                         range_start_pat: Some("#[ink::contract("),
                         range_end_pat: Some("#[ink::contract(env"),
                     },
+                    // RandomUpdated.
+                    TestResultTextOffsetRange {
+                        text: "0x0052616e64457874656e73696f6e3a3a52616e646f6d55706461746564000000",
+                        pos_pat: Some("pub struct RandomUpdated"),
+                        range_start_pat: Some("<-RandomUpdated {"),
+                        range_end_pat: Some("pub struct RandomUpdated"),
+                    },
+                    // new.
+                    TestResultTextOffsetRange {
+                        text: "0x9bae9d5e",
+                        pos_pat: Some("pub fn new"),
+                        range_start_pat: Some("<-new(init_value"),
+                        range_end_pat: Some("pub fn new"),
+                    },
+                    // new_default.
+                    TestResultTextOffsetRange {
+                        text: "0x61ef7e3e",
+                        pos_pat: Some("pub fn new_default"),
+                        range_start_pat: Some("<-new_default()"),
+                        range_end_pat: Some("pub fn new_default"),
+                    },
+                    // update.
+                    TestResultTextOffsetRange {
+                        text: "0x5f234f5d",
+                        pos_pat: Some("pub fn update"),
+                        range_start_pat: Some("<-update(&mut self"),
+                        range_end_pat: Some("pub fn update"),
+                    },
+                    // get.
+                    TestResultTextOffsetRange {
+                        text: "0x2f865bd9",
+                        pos_pat: Some("pub fn get"),
+                        range_start_pat: Some("<-get(&self)"),
+                        range_end_pat: Some("pub fn get"),
+                    },
                 ]),
             }],
         },
@@ -348,6 +348,72 @@ pub fn remove_whitespace(mut text: String) -> String {
     text
 }
 
+/// Returns the source code of a valid synthetic ink! contract of the given size
+/// (e.g. for benchmarks and performance regression tests).
+///
+/// The contract has `size` storage fields, ink! events and pairs of ink! messages
+/// (i.e. a setter that emits an event and a getter for each storage field),
+/// and an ink! constructor for every 10 storage fields
+/// (i.e. `size` should be at least 1, because ink! contracts require at least one ink! message).
+pub fn synthetic_contract(size: usize) -> String {
+    let mut code = String::from(
+        "#![cfg_attr(not(feature = \"std\"), no_std, no_main)]\n\n#[ink::contract]\nmod synthetic {\n",
+    );
+
+    // ink! events.
+    for idx in 0..size {
+        code.push_str(&format!(
+            r#"    #[ink(event)]
+    pub struct Updated{idx} {{
+        #[ink(topic)]
+        value: u32,
+    }}
+
+"#
+        ));
+    }
+
+    // ink! storage.
+    code.push_str("    #[ink(storage)]\n    #[derive(Default)]\n    pub struct Synthetic {\n");
+    for idx in 0..size {
+        code.push_str(&format!("        field{idx}: u32,\n"));
+    }
+    code.push_str("    }\n\n    impl Synthetic {\n");
+
+    // ink! constructors.
+    for idx in 0..=(size / 10) {
+        code.push_str(&format!(
+            r#"        #[ink(constructor)]
+        pub fn new{idx}() -> Self {{
+            Self::default()
+        }}
+
+"#
+        ));
+    }
+
+    // ink! messages.
+    for idx in 0..size {
+        code.push_str(&format!(
+            r#"        #[ink(message)]
+        pub fn set{idx}(&mut self, value: u32) {{
+            self.field{idx} = value;
+            self.env().emit_event(Updated{idx} {{ value }});
+        }}
+
+        #[ink(message)]
+        pub fn get{idx}(&self) -> u32 {{
+            self.field{idx}
+        }}
+
+"#
+        ));
+    }
+    code.push_str("    }\n}\n");
+
+    code
+}
+
 /// A custom string type used in test comparisons where we only want a partial match
 /// (i.e. either both strings are empty or the RHS is a substring of the LHS in comparisons)
 #[derive(Debug, Clone, Eq)]
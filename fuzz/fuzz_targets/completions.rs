@@ -0,0 +1,18 @@
+//! Fuzzes ink! analyzer completions (i.e. `Analysis::completions`) with arbitrary UTF-8 input
+//! at arbitrary offsets.
+
+#![no_main]
+
+use ink_analyzer::{Analysis, TextSize};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, u32)| {
+    let (code, offset) = input;
+    // Offsets are clamped to valid positions (i.e. char boundaries in the code),
+    // because clients only request completions at valid positions.
+    let mut offset = offset as usize % (code.len() + 1);
+    while !code.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let _ = Analysis::new(code).completions(TextSize::from(offset as u32));
+});
@@ -0,0 +1,9 @@
+//! Fuzzes ink! analyzer diagnostics (i.e. `Analysis::diagnostics`) with arbitrary UTF-8 input.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|code: &str| {
+    let _ = ink_analyzer::Analysis::new(code).diagnostics();
+});
@@ -0,0 +1,16 @@
+//! Fuzzes ink! IR generation (i.e. `InkFile::parse`) with arbitrary UTF-8 input.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|code: &str| {
+    let file = ink_analyzer_ir::InkFile::parse(code);
+    // Accesses ink! entities in the IR tree.
+    for contract in file.contracts() {
+        let _ = contract.storage();
+        let _ = contract.events();
+        let _ = contract.constructors();
+        let _ = contract.messages();
+    }
+});